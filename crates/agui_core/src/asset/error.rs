@@ -0,0 +1,28 @@
+use std::sync::Arc;
+
+use url::Url;
+
+/// An error that occurred while loading an asset.
+///
+/// Errors are cheap to clone, so every load of an asset that was in progress at the same
+/// time receives the same error.
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum AssetError {
+    #[error("no asset source is registered for the `{0}` scheme")]
+    UnsupportedScheme(String),
+
+    #[error("asset not found: {0}")]
+    NotFound(Url),
+
+    #[error("invalid data url: {0}")]
+    InvalidDataUrl(&'static str),
+
+    #[error("failed to read asset: {0}")]
+    Io(#[source] Arc<std::io::Error>),
+}
+
+impl From<std::io::Error> for AssetError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(Arc::new(err))
+    }
+}
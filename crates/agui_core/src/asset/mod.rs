@@ -0,0 +1,470 @@
+use std::sync::{Arc, OnceLock};
+
+use futures_util::{
+    future::{BoxFuture, Shared},
+    FutureExt,
+};
+use lru::LruCache;
+use parking_lot::{Mutex, RwLock};
+use rustc_hash::FxHashMap;
+use url::Url;
+
+use crate::unit::{Font, FontData, Texture, TextureData};
+
+mod error;
+mod source;
+
+pub use error::*;
+pub use source::*;
+
+type PendingLoad = Shared<BoxFuture<'static, Result<Arc<[u8]>, AssetError>>>;
+
+/// Resolves URLs into the bytes of the asset they point to.
+///
+/// Each URL scheme is handled by a registered [`AssetSource`]. By default, `file:` and
+/// `data:` URLs are supported; further schemes, such as an `asset:` scheme backed by an
+/// [`AssetBundle`] or [`AssetDirectory`], may be registered as needed.
+///
+/// Loaded assets are cached until they're evicted. The cache is bounded by the total
+/// number of bytes it holds, and the least recently used assets are evicted when it's
+/// exceeded. Cloning a resolver is cheap, and all clones share the same sources and cache.
+#[derive(Clone)]
+pub struct AssetResolver {
+    inner: Arc<AssetResolverInner>,
+}
+
+struct AssetResolverInner {
+    sources: RwLock<FxHashMap<String, Arc<dyn AssetSource>>>,
+    cache: Mutex<AssetCache>,
+}
+
+struct AssetCache {
+    entries: LruCache<Url, Arc<[u8]>>,
+
+    /// Loads that are in progress, so that concurrent loads of the same URL share them.
+    pending: FxHashMap<Url, PendingLoad>,
+
+    byte_budget: usize,
+    current_bytes: usize,
+}
+
+impl AssetResolver {
+    pub const DEFAULT_BYTE_BUDGET: usize = 50 << 20;
+
+    /// Creates a resolver with no registered sources.
+    pub fn empty() -> Self {
+        Self {
+            inner: Arc::new(AssetResolverInner {
+                sources: RwLock::default(),
+                cache: Mutex::new(AssetCache {
+                    entries: LruCache::unbounded(),
+
+                    pending: FxHashMap::default(),
+
+                    byte_budget: Self::DEFAULT_BYTE_BUDGET,
+                    current_bytes: 0,
+                }),
+            }),
+        }
+    }
+
+    /// Creates a resolver that supports `file:` and `data:` URLs.
+    pub fn new() -> Self {
+        let resolver = Self::empty();
+
+        resolver.register("file", FileSource);
+        resolver.register("data", DataSource);
+
+        resolver
+    }
+
+    /// Returns the resolver shared by the entire application.
+    pub fn global() -> &'static Self {
+        static GLOBAL: OnceLock<AssetResolver> = OnceLock::new();
+
+        GLOBAL.get_or_init(Self::new)
+    }
+
+    /// Registers the source used to load URLs of the given scheme, replacing any source
+    /// that was previously registered for it.
+    pub fn register(&self, scheme: impl Into<String>, source: impl AssetSource + 'static) {
+        self.inner
+            .sources
+            .write()
+            .insert(scheme.into().to_ascii_lowercase(), Arc::new(source));
+    }
+
+    /// Returns `true` if a source is registered for the given scheme.
+    pub fn supports(&self, scheme: &str) -> bool {
+        self.inner
+            .sources
+            .read()
+            .contains_key(&scheme.to_ascii_lowercase())
+    }
+
+    pub fn byte_budget(&self) -> usize {
+        self.inner.cache.lock().byte_budget
+    }
+
+    /// Changes the maximum number of bytes the cache may hold, evicting assets as
+    /// necessary to fit within it.
+    pub fn set_byte_budget(&self, byte_budget: usize) {
+        let mut cache = self.inner.cache.lock();
+
+        cache.byte_budget = byte_budget;
+        cache.evict();
+    }
+
+    /// The number of bytes of assets currently held by the cache.
+    pub fn current_bytes(&self) -> usize {
+        self.inner.cache.lock().current_bytes
+    }
+
+    /// Loads the bytes of the asset at the given URL.
+    ///
+    /// The asset is cached once loaded, so subsequent loads of the same URL resolve
+    /// immediately, and loads of a URL that's already being loaded wait for that load to
+    /// finish rather than starting another. Failed loads are not cached, and neither are
+    /// `data:` URLs, since their bytes are already held by the URL itself.
+    pub async fn load(&self, url: &Url) -> Result<Arc<[u8]>, AssetError> {
+        if url.scheme() == "data" {
+            return self.source(url)?.load(url).await;
+        }
+
+        let load = {
+            let mut cache = self.inner.cache.lock();
+
+            if let Some(bytes) = cache.entries.get(url) {
+                return Ok(Arc::clone(bytes));
+            }
+
+            match cache.pending.get(url) {
+                Some(load) => load.clone(),
+
+                None => {
+                    let load = self.source(url)?.load(url).shared();
+
+                    cache.pending.insert(url.clone(), load.clone());
+
+                    load
+                }
+            }
+        };
+
+        let result = load.clone().await;
+
+        let mut cache = self.inner.cache.lock();
+
+        // Only the first of the loads waiting on the result needs to store it, and the
+        // asset may have been evicted while it was loading
+        if cache
+            .pending
+            .get(url)
+            .is_some_and(|pending| pending.ptr_eq(&load))
+        {
+            cache.pending.remove(url);
+
+            if let Ok(bytes) = &result {
+                cache.insert(url.clone(), Arc::clone(bytes));
+            }
+        }
+
+        result
+    }
+
+    fn source(&self, url: &Url) -> Result<Arc<dyn AssetSource>, AssetError> {
+        self.inner
+            .sources
+            .read()
+            .get(url.scheme())
+            .map(Arc::clone)
+            .ok_or_else(|| AssetError::UnsupportedScheme(url.scheme().to_owned()))
+    }
+
+    /// Removes the asset at the given URL from the cache, so it will be reloaded the next
+    /// time it's requested.
+    pub fn evict(&self, url: &Url) {
+        let mut cache = self.inner.cache.lock();
+
+        cache.pending.remove(url);
+
+        if let Some(bytes) = cache.entries.pop(url) {
+            cache.current_bytes -= bytes.len();
+        }
+    }
+
+    pub fn clear_cache(&self) {
+        let mut cache = self.inner.cache.lock();
+
+        cache.entries.clear();
+        cache.pending.clear();
+        cache.current_bytes = 0;
+    }
+
+    /// Loads the encoded bytes of a texture that refers to a URL. Other textures are
+    /// returned as-is.
+    pub async fn resolve_texture(&self, texture: &Texture) -> Result<Texture, AssetError> {
+        match texture.data() {
+            TextureData::Url(url) => Ok(Texture::from_bytes(self.load(url).await?.to_vec())),
+
+            _ => Ok(texture.clone()),
+        }
+    }
+
+    /// Loads the bytes of a font that refers to a URL, keeping its index within a font
+    /// collection. Other fonts are returned as-is.
+    pub async fn resolve_font(&self, font: &Font) -> Result<Font, AssetError> {
+        match font.as_ref() {
+            FontData::Url(url) => {
+                Ok(Font::from_bytes(self.load(url).await?.to_vec()).with_index(font.index()))
+            }
+
+            _ => Ok(font.clone()),
+        }
+    }
+}
+
+impl AssetCache {
+    /// Caches the bytes of an asset. Assets larger than the entire byte budget are not
+    /// cached.
+    fn insert(&mut self, url: Url, bytes: Arc<[u8]>) {
+        let size = bytes.len();
+
+        if size > self.byte_budget {
+            return;
+        }
+
+        if let Some(previous) = self.entries.put(url, bytes) {
+            self.current_bytes -= previous.len();
+        }
+
+        self.current_bytes += size;
+        self.evict();
+    }
+
+    fn evict(&mut self) {
+        while self.current_bytes > self.byte_budget {
+            let Some((_, bytes)) = self.entries.pop_lru() else {
+                break;
+            };
+
+            self.current_bytes -= bytes.len();
+        }
+    }
+}
+
+impl Default for AssetResolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PartialEq for AssetResolver {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl std::fmt::Debug for AssetResolver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut schemes = self
+            .inner
+            .sources
+            .read()
+            .keys()
+            .cloned()
+            .collect::<Vec<_>>();
+
+        schemes.sort();
+
+        f.debug_struct("AssetResolver")
+            .field("schemes", &schemes)
+            .field("cached", &self.inner.cache.lock().entries.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{
+            atomic::{AtomicBool, AtomicUsize, Ordering},
+            Arc,
+        },
+        task::Poll,
+    };
+
+    use futures_util::{future::BoxFuture, FutureExt};
+    use url::Url;
+
+    use crate::asset::{AssetBundle, AssetError, AssetResolver, AssetSource};
+
+    /// Counts its loads, which don't finish until `ready` is set.
+    struct SlowSource {
+        loads: Arc<AtomicUsize>,
+        ready: Arc<AtomicBool>,
+    }
+
+    impl AssetSource for SlowSource {
+        fn load(&self, _: &Url) -> BoxFuture<'static, Result<Arc<[u8]>, AssetError>> {
+            self.loads.fetch_add(1, Ordering::Relaxed);
+
+            let ready = Arc::clone(&self.ready);
+
+            futures_util::future::poll_fn(move |cx| {
+                if ready.load(Ordering::Relaxed) {
+                    Poll::Ready(Ok(Arc::from(b"slow".as_slice())))
+                } else {
+                    cx.waker().wake_by_ref();
+
+                    Poll::Pending
+                }
+            })
+            .boxed()
+        }
+    }
+
+    #[test]
+    fn loads_from_registered_scheme() {
+        let resolver = AssetResolver::empty();
+
+        resolver.register(
+            "asset",
+            AssetBundle::new().with("images/logo.png", b"logo".as_slice()),
+        );
+
+        let url = Url::parse("asset://images/logo.png").unwrap();
+
+        let bytes = futures_executor::block_on(resolver.load(&url)).unwrap();
+
+        assert_eq!(&*bytes, b"logo");
+    }
+
+    #[test]
+    fn rejects_unknown_scheme() {
+        let resolver = AssetResolver::empty();
+
+        let url = Url::parse("asset://images/logo.png").unwrap();
+
+        assert!(matches!(
+            futures_executor::block_on(resolver.load(&url)),
+            Err(AssetError::UnsupportedScheme(scheme)) if scheme == "asset"
+        ));
+    }
+
+    #[test]
+    fn caches_loaded_assets() {
+        let resolver = AssetResolver::empty();
+
+        resolver.register(
+            "asset",
+            AssetBundle::new().with("text.txt", b"first".as_slice()),
+        );
+
+        let url = Url::parse("asset://text.txt").unwrap();
+
+        futures_executor::block_on(resolver.load(&url)).unwrap();
+
+        // Replacing the source doesn't affect assets that are already cached
+        resolver.register(
+            "asset",
+            AssetBundle::new().with("text.txt", b"second".as_slice()),
+        );
+
+        assert_eq!(
+            &*futures_executor::block_on(resolver.load(&url)).unwrap(),
+            b"first"
+        );
+
+        resolver.evict(&url);
+
+        assert_eq!(
+            &*futures_executor::block_on(resolver.load(&url)).unwrap(),
+            b"second"
+        );
+    }
+
+    #[test]
+    fn shares_concurrent_loads() {
+        let resolver = AssetResolver::empty();
+
+        let loads = Arc::new(AtomicUsize::new(0));
+        let ready = Arc::new(AtomicBool::new(false));
+
+        resolver.register(
+            "slow",
+            SlowSource {
+                loads: Arc::clone(&loads),
+                ready: Arc::clone(&ready),
+            },
+        );
+
+        let url = Url::parse("slow://asset").unwrap();
+
+        let ((first, second), _) = futures_executor::block_on(futures_util::future::join(
+            futures_util::future::join(resolver.load(&url), resolver.load(&url)),
+            async move { ready.store(true, Ordering::Relaxed) },
+        ));
+
+        assert_eq!(&*first.unwrap(), b"slow");
+        assert_eq!(&*second.unwrap(), b"slow");
+
+        assert_eq!(loads.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn evicts_least_recently_used_assets_over_budget() {
+        let resolver = AssetResolver::empty();
+
+        resolver.register(
+            "asset",
+            AssetBundle::new()
+                .with("a", [0; 4].as_slice())
+                .with("b", [0; 4].as_slice())
+                .with("c", [0; 4].as_slice()),
+        );
+
+        resolver.set_byte_budget(8);
+
+        for path in ["a", "b", "a", "c"] {
+            let url = Url::parse(&format!("asset://{}", path)).unwrap();
+
+            futures_executor::block_on(resolver.load(&url)).unwrap();
+        }
+
+        assert_eq!(resolver.current_bytes(), 8);
+
+        // Replacing the source reveals which assets are still cached
+        resolver.register(
+            "asset",
+            AssetBundle::new()
+                .with("a", [1; 4].as_slice())
+                .with("b", [1; 4].as_slice())
+                .with("c", [1; 4].as_slice()),
+        );
+
+        let load = |path: &str| {
+            let url = Url::parse(&format!("asset://{}", path)).unwrap();
+
+            futures_executor::block_on(resolver.load(&url)).unwrap()[0]
+        };
+
+        assert_eq!(load("a"), 0);
+        assert_eq!(load("c"), 0);
+        assert_eq!(load("b"), 1);
+    }
+
+    #[test]
+    fn does_not_cache_data_urls() {
+        let resolver = AssetResolver::new();
+
+        let url = Url::parse("data:text/plain,hello").unwrap();
+
+        assert_eq!(
+            &*futures_executor::block_on(resolver.load(&url)).unwrap(),
+            b"hello"
+        );
+
+        assert_eq!(resolver.current_bytes(), 0);
+    }
+}
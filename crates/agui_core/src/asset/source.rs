@@ -0,0 +1,208 @@
+use std::{
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+};
+
+use base64::Engine;
+use futures_util::{future::BoxFuture, FutureExt};
+use rustc_hash::FxHashMap;
+use url::Url;
+
+use crate::{asset::AssetError, task::spawn_blocking};
+
+/// Loads the bytes of assets for one or more URL schemes.
+pub trait AssetSource: Send + Sync {
+    fn load(&self, url: &Url) -> BoxFuture<'static, Result<Arc<[u8]>, AssetError>>;
+}
+
+/// Loads assets from the local filesystem using `file://` URLs.
+#[derive(Debug, Default)]
+pub struct FileSource;
+
+impl AssetSource for FileSource {
+    fn load(&self, url: &Url) -> BoxFuture<'static, Result<Arc<[u8]>, AssetError>> {
+        let url = url.clone();
+
+        async move {
+            let path = url
+                .to_file_path()
+                .map_err(|_| AssetError::NotFound(url.clone()))?;
+
+            read_file(url, path).await
+        }
+        .boxed()
+    }
+}
+
+/// Decodes assets embedded directly in `data:` URLs, either base64 or percent encoded.
+#[derive(Debug, Default)]
+pub struct DataSource;
+
+impl AssetSource for DataSource {
+    fn load(&self, url: &Url) -> BoxFuture<'static, Result<Arc<[u8]>, AssetError>> {
+        futures_util::future::ready(decode_data_url(url)).boxed()
+    }
+}
+
+/// A set of assets embedded in the binary, keyed on their path.
+///
+/// For a source registered as the `asset` scheme, `asset://images/logo.png` would load
+/// the entry at `images/logo.png`.
+#[derive(Debug, Default)]
+pub struct AssetBundle {
+    entries: FxHashMap<String, Arc<[u8]>>,
+}
+
+impl AssetBundle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, path: impl Into<String>, bytes: impl Into<Arc<[u8]>>) -> Self {
+        self.insert(path, bytes);
+        self
+    }
+
+    pub fn insert(&mut self, path: impl Into<String>, bytes: impl Into<Arc<[u8]>>) {
+        self.entries
+            .insert(path.into().trim_start_matches('/').to_owned(), bytes.into());
+    }
+}
+
+impl FromIterator<(&'static str, &'static [u8])> for AssetBundle {
+    fn from_iter<T: IntoIterator<Item = (&'static str, &'static [u8])>>(iter: T) -> Self {
+        iter.into_iter().fold(Self::new(), |bundle, (path, bytes)| {
+            bundle.with(path, bytes)
+        })
+    }
+}
+
+impl AssetSource for AssetBundle {
+    fn load(&self, url: &Url) -> BoxFuture<'static, Result<Arc<[u8]>, AssetError>> {
+        let result = self
+            .entries
+            .get(&asset_path(url))
+            .map(Arc::clone)
+            .ok_or_else(|| AssetError::NotFound(url.clone()));
+
+        futures_util::future::ready(result).boxed()
+    }
+}
+
+/// Loads assets relative to a directory on the local filesystem.
+///
+/// For a source registered as the `asset` scheme, `asset://images/logo.png` would load
+/// `images/logo.png` within the directory. Paths may not escape the directory.
+#[derive(Debug)]
+pub struct AssetDirectory {
+    root: PathBuf,
+}
+
+impl AssetDirectory {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+impl AssetSource for AssetDirectory {
+    fn load(&self, url: &Url) -> BoxFuture<'static, Result<Arc<[u8]>, AssetError>> {
+        let url = url.clone();
+        let path = asset_path(&url);
+
+        let is_contained = Path::new(&path)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)));
+
+        if !is_contained {
+            return futures_util::future::ready(Err(AssetError::NotFound(url))).boxed();
+        }
+
+        read_file(url, self.root.join(path)).boxed()
+    }
+}
+
+async fn read_file(url: Url, path: PathBuf) -> Result<Arc<[u8]>, AssetError> {
+    match spawn_blocking(move || std::fs::read(path)).await {
+        Ok(bytes) => Ok(Arc::from(bytes)),
+
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Err(AssetError::NotFound(url)),
+
+        Err(err) => Err(AssetError::from(err)),
+    }
+}
+
+/// Returns the host and path of the URL, joined, without a leading slash.
+fn asset_path(url: &Url) -> String {
+    let path = percent_encoding::percent_decode_str(url.path()).decode_utf8_lossy();
+    let path = path.trim_start_matches('/');
+
+    match url.host_str() {
+        Some(host) if path.is_empty() => host.to_owned(),
+        Some(host) => format!("{}/{}", host, path),
+        None => path.to_owned(),
+    }
+}
+
+fn decode_data_url(url: &Url) -> Result<Arc<[u8]>, AssetError> {
+    // The url crate doesn't parse the contents of data URLs, so they're still encoded
+    let (header, data) = url
+        .path()
+        .split_once(',')
+        .ok_or(AssetError::InvalidDataUrl("missing data separator"))?;
+
+    let data = match url.query() {
+        Some(query) => format!("{}?{}", data, query),
+        None => data.to_owned(),
+    };
+
+    let bytes = percent_encoding::percent_decode_str(&data).collect::<Vec<_>>();
+
+    if header.ends_with(";base64") {
+        let bytes = bytes
+            .into_iter()
+            .filter(|byte| !byte.is_ascii_whitespace())
+            .collect::<Vec<_>>();
+
+        base64::engine::general_purpose::STANDARD
+            .decode(bytes)
+            .map(Arc::from)
+            .map_err(|_| AssetError::InvalidDataUrl("invalid base64 data"))
+    } else {
+        Ok(Arc::from(bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use url::Url;
+
+    use super::{asset_path, decode_data_url};
+
+    #[test]
+    fn decodes_base64_data_url() {
+        let url = Url::parse("data:text/plain;base64,aGVsbG8gd29ybGQ=").unwrap();
+
+        assert_eq!(&*decode_data_url(&url).unwrap(), b"hello world");
+    }
+
+    #[test]
+    fn decodes_percent_encoded_data_url() {
+        let url = Url::parse("data:,hello%20world").unwrap();
+
+        assert_eq!(&*decode_data_url(&url).unwrap(), b"hello world");
+    }
+
+    #[test]
+    fn rejects_data_url_without_separator() {
+        let url = Url::parse("data:text/plain").unwrap();
+
+        assert!(decode_data_url(&url).is_err());
+    }
+
+    #[test]
+    fn joins_host_and_path() {
+        let url = Url::parse("asset://images/icons/logo%20dark.png").unwrap();
+
+        assert_eq!(asset_path(&url), "images/icons/logo dark.png");
+    }
+}
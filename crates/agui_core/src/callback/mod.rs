@@ -1,9 +1,12 @@
 use std::{
     any::{Any, TypeId},
+    hash::{Hash, Hasher},
     marker::PhantomData,
     sync::Arc,
 };
 
+use rustc_hash::FxHasher;
+
 use crate::{callback::strategies::CallbackStrategy, element::ElementId};
 
 pub mod strategies;
@@ -12,6 +15,9 @@ pub mod strategies;
 pub struct CallbackId {
     element_id: ElementId,
     type_id: TypeId,
+
+    /// Distinguishes callbacks that were bound to different arguments.
+    binding: Option<u64>,
 }
 
 impl CallbackId {
@@ -41,6 +47,7 @@ where
             callback_id: CallbackId {
                 element_id,
                 type_id: TypeId::of::<F>(),
+                binding: None,
             },
         }
     }
@@ -68,6 +75,34 @@ where
     }
 }
 
+impl<A> Callback<A>
+where
+    A: Any + Send + Clone + Hash,
+{
+    /// Returns a callback that takes no argument, and calls this one with `arg`.
+    ///
+    /// Bound callbacks are equal if they bind the same callback to equal arguments, so they
+    /// may be created anew on each build without being seen as a change.
+    pub fn bind(&self, arg: A) -> Callback<()> {
+        let mut hasher = FxHasher::default();
+        self.callback_id.binding.hash(&mut hasher);
+        arg.hash(&mut hasher);
+
+        Callback {
+            strategy: Arc::new(BoundCallback {
+                callback: self.clone(),
+                arg,
+            }),
+
+            phantom: PhantomData,
+            callback_id: CallbackId {
+                binding: Some(hasher.finish()),
+                ..self.callback_id
+            },
+        }
+    }
+}
+
 impl<A> PartialEq for Callback<A>
 where
     A: ?Sized,
@@ -104,3 +139,57 @@ where
 
 unsafe impl<A: ?Sized> Send for Callback<A> {}
 unsafe impl<A: ?Sized> Sync for Callback<A> {}
+
+struct BoundCallback<A> {
+    callback: Callback<A>,
+    arg: A,
+}
+
+impl<A> CallbackStrategy for BoundCallback<A>
+where
+    A: Any + Send + Clone,
+{
+    fn call_unchecked(&self, _: CallbackId, _: Box<dyn Any + Send>) {
+        self.callback.call(self.arg.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{
+        callback::{strategies::mocks::MockCallbackStratgy, Callback},
+        element::ElementId,
+    };
+
+    #[test]
+    fn bound_callback_calls_with_its_argument() {
+        let strategy = MockCallbackStratgy::default();
+
+        let callback =
+            Callback::<String>::new::<()>(Arc::new(strategy.clone()), ElementId::default());
+
+        callback.bind(String::from("foo")).call(());
+
+        let calls = strategy.calls.lock().unwrap();
+
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].0, callback.id());
+        assert_eq!(calls[0].1.downcast_ref::<String>().unwrap(), "foo");
+    }
+
+    #[test]
+    fn bound_callbacks_are_equal_by_argument() {
+        let callback = Callback::<String>::new::<()>(
+            Arc::new(MockCallbackStratgy::default()),
+            ElementId::default(),
+        );
+
+        let foo = callback.bind(String::from("foo"));
+
+        assert_eq!(foo, callback.bind(String::from("foo")));
+        assert_ne!(foo, callback.bind(String::from("bar")));
+        assert_ne!(foo.id(), callback.id());
+    }
+}
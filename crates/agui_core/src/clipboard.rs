@@ -0,0 +1,98 @@
+use std::sync::{Arc, OnceLock};
+
+use parking_lot::{Mutex, RwLock};
+
+/// Reads and writes the text held by a clipboard, such as the one provided by the
+/// operating system.
+pub trait ClipboardProvider: Send + Sync {
+    fn read_text(&self) -> Option<String>;
+
+    fn write_text(&self, text: &str);
+}
+
+/// A clipboard that only exists within the application.
+#[derive(Debug, Default)]
+pub struct LocalClipboard {
+    text: Mutex<Option<String>>,
+}
+
+impl ClipboardProvider for LocalClipboard {
+    fn read_text(&self) -> Option<String> {
+        self.text.lock().clone()
+    }
+
+    fn write_text(&self, text: &str) {
+        self.text.lock().replace(text.to_owned());
+    }
+}
+
+/// A handle to a clipboard that text can be copied to and pasted from.
+///
+/// Cloning a clipboard is cheap, and all clones share the same provider.
+#[derive(Clone)]
+pub struct Clipboard {
+    provider: Arc<dyn ClipboardProvider>,
+}
+
+impl Clipboard {
+    pub fn new(provider: impl ClipboardProvider + 'static) -> Self {
+        Self {
+            provider: Arc::new(provider),
+        }
+    }
+
+    /// Returns the clipboard shared by the entire application.
+    ///
+    /// Until a platform integration replaces it with [`set_global`](Self::set_global),
+    /// this is a [`LocalClipboard`].
+    pub fn global() -> Self {
+        Self::global_slot().read().clone()
+    }
+
+    /// Replaces the clipboard shared by the entire application.
+    pub fn set_global(clipboard: Self) {
+        *Self::global_slot().write() = clipboard;
+    }
+
+    fn global_slot() -> &'static RwLock<Self> {
+        static GLOBAL: OnceLock<RwLock<Clipboard>> = OnceLock::new();
+
+        GLOBAL.get_or_init(|| RwLock::new(Self::new(LocalClipboard::default())))
+    }
+
+    pub fn read_text(&self) -> Option<String> {
+        self.provider.read_text()
+    }
+
+    pub fn write_text(&self, text: &str) {
+        self.provider.write_text(text);
+    }
+}
+
+impl PartialEq for Clipboard {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.provider, &other.provider)
+    }
+}
+
+impl std::fmt::Debug for Clipboard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Clipboard").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Clipboard, LocalClipboard};
+
+    #[test]
+    fn clones_share_their_contents() {
+        let clipboard = Clipboard::new(LocalClipboard::default());
+
+        assert_eq!(clipboard.read_text(), None);
+
+        clipboard.clone().write_text("copied");
+
+        assert_eq!(clipboard.read_text().as_deref(), Some("copied"));
+    }
+}
@@ -1,6 +1,7 @@
 use crate::{
     element::{ContextDirtyRenderObject, ContextRenderObject},
     render::RenderObjectId,
+    task::clock::Clock,
 };
 
 pub trait RenderingTaskNotifyStrategy: Send {
@@ -10,6 +11,7 @@ pub trait RenderingTaskNotifyStrategy: Send {
 
 pub struct RenderingTaskContext {
     notify_strategy: Option<Box<dyn RenderingTaskNotifyStrategy>>,
+    clock: Clock,
 
     render_object_id: RenderObjectId,
 }
@@ -18,6 +20,7 @@ impl RenderingTaskContext {
     pub(crate) fn new(render_object_id: RenderObjectId) -> Self {
         RenderingTaskContext {
             notify_strategy: None,
+            clock: Clock::default(),
             render_object_id,
         }
     }
@@ -28,10 +31,20 @@ impl RenderingTaskContext {
     {
         Self {
             notify_strategy: Some(Box::new(strategy)),
+            clock: self.clock,
 
             render_object_id: self.render_object_id,
         }
     }
+
+    pub(crate) fn with_clock(self, clock: Clock) -> Self {
+        Self { clock, ..self }
+    }
+
+    /// The clock of the executor running this task.
+    pub fn clock(&self) -> &Clock {
+        &self.clock
+    }
 }
 
 impl ContextRenderObject for RenderingTaskContext {
@@ -1,4 +1,7 @@
-use crate::element::{ContextElement, ElementId};
+use crate::{
+    element::{ContextElement, ElementId},
+    task::clock::Clock,
+};
 
 pub trait ElementTaskNotifyStrategy {
     fn mark_needs_build(&mut self, element_id: ElementId);
@@ -6,6 +9,7 @@ pub trait ElementTaskNotifyStrategy {
 
 pub struct ElementTaskContext {
     notify_strategy: Option<Box<dyn ElementTaskNotifyStrategy>>,
+    clock: Clock,
 
     element_id: ElementId,
 }
@@ -14,6 +18,7 @@ impl ElementTaskContext {
     pub(crate) fn new(element_id: ElementId) -> Self {
         ElementTaskContext {
             notify_strategy: None,
+            clock: Clock::default(),
             element_id,
         }
     }
@@ -24,10 +29,15 @@ impl ElementTaskContext {
     {
         Self {
             notify_strategy: Some(Box::new(strategy)),
+            clock: self.clock,
 
             element_id: self.element_id,
         }
     }
+
+    pub(crate) fn with_clock(self, clock: Clock) -> Self {
+        Self { clock, ..self }
+    }
 }
 
 impl ContextElement for ElementTaskContext {
@@ -37,6 +47,11 @@ impl ContextElement for ElementTaskContext {
 }
 
 impl ElementTaskContext {
+    /// The clock of the executor running this task.
+    pub fn clock(&self) -> &Clock {
+        &self.clock
+    }
+
     pub fn mark_needs_build(&mut self) {
         let Some(notify_strategy) = self.notify_strategy.as_mut() else {
             tracing::warn!(
@@ -1,3 +1,4 @@
 pub mod build;
 pub mod inherited;
 pub mod render;
+pub mod view;
@@ -0,0 +1,131 @@
+use std::rc::Rc;
+
+use crate::{
+    element::{
+        lifecycle::ElementLifecycle, render::ElementRender, view::ElementView,
+        widget::ElementWidget, Element, ElementBuilder, ElementComparison,
+        RenderObjectCreateContext, RenderObjectUpdateContext,
+    },
+    engine::rendering::{mock::RecordingView, view::View},
+    render::object::{
+        RenderObject, RenderObjectImpl, RenderObjectIntrinsicSizeContext, RenderObjectLayoutContext,
+    },
+    unit::{Constraints, IntrinsicDimension, Size},
+    widget::{IntoWidget, Widget},
+};
+
+/// Creates a new view for its child, which is drawn to the given [`RecordingView`].
+#[derive(Clone)]
+pub struct MockViewWidget {
+    pub view: RecordingView,
+
+    pub child: Widget,
+}
+
+impl MockViewWidget {
+    pub fn new(view: RecordingView, child: impl IntoWidget) -> Self {
+        Self {
+            view,
+
+            child: child.into_widget(),
+        }
+    }
+}
+
+impl IntoWidget for MockViewWidget {
+    fn into_widget(self) -> Widget {
+        Widget::new(self)
+    }
+}
+
+impl ElementBuilder for MockViewWidget {
+    type Element = MockedElementView;
+
+    fn create_element(self: Rc<Self>) -> Element {
+        Element::new_view(MockedElementView::new(self))
+    }
+}
+
+pub struct MockedElementView {
+    widget: Rc<MockViewWidget>,
+}
+
+impl MockedElementView {
+    pub fn new(widget: Rc<MockViewWidget>) -> Self {
+        Self { widget }
+    }
+}
+
+impl ElementLifecycle for MockedElementView {
+    fn update(&mut self, new_widget: &Widget) -> ElementComparison {
+        if let Some(new_widget) = new_widget.downcast::<MockViewWidget>() {
+            // The view is only created once, so a different recording needs a new element
+            if !new_widget.view.is_same_recording(&self.widget.view) {
+                return ElementComparison::Invalid;
+            }
+
+            if Rc::ptr_eq(&new_widget, &self.widget) {
+                ElementComparison::Identical
+            } else {
+                self.widget = new_widget;
+
+                ElementComparison::Changed
+            }
+        } else {
+            ElementComparison::Invalid
+        }
+    }
+}
+
+impl ElementWidget for MockedElementView {
+    type Widget = MockViewWidget;
+
+    fn widget(&self) -> &Rc<Self::Widget> {
+        &self.widget
+    }
+}
+
+impl ElementRender for MockedElementView {
+    fn children(&self) -> Vec<Widget> {
+        vec![self.widget.child.clone()]
+    }
+
+    fn create_render_object(&self, _: &mut RenderObjectCreateContext) -> RenderObject {
+        RenderObject::new(RenderMockView)
+    }
+
+    fn is_valid_render_object(&self, render_object: &RenderObject) -> bool {
+        render_object.is::<RenderMockView>()
+    }
+
+    fn update_render_object(&self, _: &mut RenderObjectUpdateContext, _: &mut RenderObject) {}
+}
+
+impl ElementView for MockedElementView {
+    fn create_view(&mut self) -> Box<dyn View + Send> {
+        Box::new(self.widget.view.clone())
+    }
+}
+
+struct RenderMockView;
+
+impl RenderObjectImpl for RenderMockView {
+    fn intrinsic_size(
+        &self,
+        ctx: &mut RenderObjectIntrinsicSizeContext,
+        dimension: IntrinsicDimension,
+        cross_extent: f32,
+    ) -> f32 {
+        ctx.iter_children()
+            .next()
+            .map(|child| child.compute_intrinsic_size(dimension, cross_extent))
+            .unwrap_or(0.0)
+    }
+
+    fn layout(&self, ctx: &mut RenderObjectLayoutContext, constraints: Constraints) -> Size {
+        ctx.iter_children_mut()
+            .next()
+            .map(|mut child| child.compute_layout(constraints))
+            .unwrap_or_else(|| constraints.smallest())
+    }
+}
@@ -5,7 +5,7 @@ use std::{
 
 use crate::{
     element::{ContextElement, ElementId, ElementTaskContext, ElementTaskNotifyStrategy},
-    task::{error::TaskError, TaskHandle},
+    task::{clock::Clock, error::TaskError, TaskHandle},
 };
 
 pub struct CreateElementTask {
@@ -26,6 +26,13 @@ impl CreateElementTask {
         Self {
             ctx: self.ctx.with_notify_strategy(strategy),
 
+            func: self.func,
+        }
+    }
+    pub fn with_clock(self, clock: Clock) -> Self {
+        Self {
+            ctx: self.ctx.with_clock(clock),
+
             func: self.func,
         }
     }
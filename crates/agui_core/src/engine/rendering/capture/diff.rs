@@ -0,0 +1,333 @@
+use std::fmt;
+
+use rustc_hash::FxHashMap;
+
+use crate::{
+    engine::rendering::capture::SceneCapture,
+    render::canvas::{Canvas, CanvasLayer},
+};
+
+/// A single way in which two captures differ.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Difference {
+    /// Where the difference is, such as `/0/1 canvas.children[0].canvas.head[2]` for the
+    /// third command in the first layer of the second child of the root.
+    pub path: String,
+
+    /// The value in the first capture, or `None` if it only exists in the second.
+    pub before: Option<String>,
+
+    /// The value in the second capture, or `None` if it only exists in the first.
+    pub after: Option<String>,
+}
+
+impl Difference {
+    fn new(path: String, before: Option<String>, after: Option<String>) -> Self {
+        Self {
+            path,
+            before,
+            after,
+        }
+    }
+
+    fn changed(path: String, before: &impl fmt::Debug, after: &impl fmt::Debug) -> Self {
+        Self::new(
+            path,
+            Some(format!("{:?}", before)),
+            Some(format!("{:?}", after)),
+        )
+    }
+}
+
+impl fmt::Display for Difference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.before, &self.after) {
+            (Some(before), Some(after)) => write!(f, "{}: {} -> {}", self.path, before, after),
+            (Some(before), None) => write!(f, "{}: removed {}", self.path, before),
+            (None, Some(after)) => write!(f, "{}: added {}", self.path, after),
+            (None, None) => write!(f, "{}", self.path),
+        }
+    }
+}
+
+/// Compares two canvases, command by command.
+pub fn diff_canvases(before: &Canvas, after: &Canvas) -> Vec<Difference> {
+    let mut differences = Vec::new();
+
+    diff_canvas(&mut differences, "canvas", before, after);
+
+    differences
+}
+
+pub(super) fn diff_scenes(before: &SceneCapture, after: &SceneCapture) -> Vec<Difference> {
+    let mut differences = Vec::new();
+
+    let before_paths = before.paths();
+    let after_paths = after.paths();
+
+    let after_indices = after_paths
+        .iter()
+        .enumerate()
+        .map(|(idx, path)| (path.as_str(), idx))
+        .collect::<FxHashMap<_, _>>();
+
+    for (path, object) in before_paths.iter().zip(&before.objects) {
+        let Some(other) = after_indices
+            .get(path.as_str())
+            .map(|idx| &after.objects[*idx])
+        else {
+            differences.push(Difference::new(
+                path.clone(),
+                Some(String::from("render object")),
+                None,
+            ));
+
+            continue;
+        };
+
+        if object.offset != other.offset {
+            differences.push(Difference::changed(
+                format!("{} offset", path),
+                &object.offset,
+                &other.offset,
+            ));
+        }
+
+        if object.size != other.size {
+            differences.push(Difference::changed(
+                format!("{} size", path),
+                &object.size,
+                &other.size,
+            ));
+        }
+
+        match (&object.canvas, &other.canvas) {
+            (Some(canvas), Some(other_canvas)) => {
+                diff_canvas(
+                    &mut differences,
+                    &format!("{} canvas", path),
+                    canvas,
+                    other_canvas,
+                );
+            }
+
+            (Some(canvas), None) => differences.push(Difference::new(
+                format!("{} canvas", path),
+                Some(format!("{:?}", canvas)),
+                None,
+            )),
+
+            (None, Some(canvas)) => differences.push(Difference::new(
+                format!("{} canvas", path),
+                None,
+                Some(format!("{:?}", canvas)),
+            )),
+
+            (None, None) => {}
+        }
+    }
+
+    let before_paths = before_paths
+        .iter()
+        .map(String::as_str)
+        .collect::<rustc_hash::FxHashSet<_>>();
+
+    for path in &after_paths {
+        if !before_paths.contains(path.as_str()) {
+            differences.push(Difference::new(
+                path.clone(),
+                None,
+                Some(String::from("render object")),
+            ));
+        }
+    }
+
+    differences
+}
+
+fn diff_canvas(differences: &mut Vec<Difference>, path: &str, before: &Canvas, after: &Canvas) {
+    if before.size != after.size {
+        differences.push(Difference::changed(
+            format!("{}.size", path),
+            &before.size,
+            &after.size,
+        ));
+    }
+
+    diff_list(
+        differences,
+        &format!("{}.paints", path),
+        &before.paints,
+        &after.paints,
+    );
+
+    diff_list(
+        differences,
+        &format!("{}.head", path),
+        &before.head,
+        &after.head,
+    );
+
+    for idx in 0..before.children.len().max(after.children.len()) {
+        diff_layer(
+            differences,
+            &format!("{}.children[{}]", path, idx),
+            before.children.get(idx),
+            after.children.get(idx),
+        );
+    }
+
+    diff_layer(
+        differences,
+        &format!("{}.tail", path),
+        before.tail.as_deref(),
+        after.tail.as_deref(),
+    );
+}
+
+fn diff_layer(
+    differences: &mut Vec<Difference>,
+    path: &str,
+    before: Option<&CanvasLayer>,
+    after: Option<&CanvasLayer>,
+) {
+    match (before, after) {
+        (Some(before), Some(after)) => {
+            if before.offset != after.offset {
+                differences.push(Difference::changed(
+                    format!("{}.offset", path),
+                    &before.offset,
+                    &after.offset,
+                ));
+            }
+
+            if before.style != after.style {
+                differences.push(Difference::changed(
+                    format!("{}.style", path),
+                    &before.style,
+                    &after.style,
+                ));
+            }
+
+            diff_canvas(
+                differences,
+                &format!("{}.canvas", path),
+                &before.canvas,
+                &after.canvas,
+            );
+        }
+
+        (Some(before), None) => differences.push(Difference::new(
+            path.to_owned(),
+            Some(format!("{:?}", before)),
+            None,
+        )),
+
+        (None, Some(after)) => differences.push(Difference::new(
+            path.to_owned(),
+            None,
+            Some(format!("{:?}", after)),
+        )),
+
+        (None, None) => {}
+    }
+}
+
+fn diff_list<T>(differences: &mut Vec<Difference>, path: &str, before: &[T], after: &[T])
+where
+    T: fmt::Debug + PartialEq,
+{
+    for idx in 0..before.len().max(after.len()) {
+        let before = before.get(idx);
+        let after = after.get(idx);
+
+        if before == after {
+            continue;
+        }
+
+        differences.push(Difference::new(
+            format!("{}[{}]", path, idx),
+            before.map(|before| format!("{:?}", before)),
+            after.map(|after| format!("{:?}", after)),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        engine::rendering::capture::tests::scene,
+        render::canvas::{
+            paint::Paint,
+            painter::{CanvasPainter, Head},
+        },
+        unit::{Color, Offset, Size},
+    };
+
+    use super::{diff_canvases, Difference};
+
+    #[test]
+    fn identical_scenes_have_no_differences() {
+        assert!(scene().diff(&scene()).is_empty());
+    }
+
+    #[test]
+    fn finds_changed_objects() {
+        let before = scene();
+
+        let mut after = scene();
+
+        after.objects[2].offset = Offset::new(4.0, 4.0);
+        after.objects.pop();
+
+        assert_eq!(
+            before.diff(&after),
+            vec![
+                Difference {
+                    path: String::from("/0/0/0 offset"),
+                    before: Some(format!("{:?}", Offset::new(2.0, 2.0))),
+                    after: Some(format!("{:?}", Offset::new(4.0, 4.0))),
+                },
+                Difference {
+                    path: String::from("/0/1"),
+                    before: Some(String::from("render object")),
+                    after: None,
+                },
+            ]
+        );
+
+        let differences = after.diff(&before);
+
+        assert_eq!(differences.len(), 2);
+        assert_eq!(differences[1].to_string(), "/0/1: added render object");
+    }
+
+    #[test]
+    fn finds_changed_commands() {
+        let mut before = scene().objects[1].canvas.clone().unwrap();
+        let mut after = before.clone();
+
+        for (canvas, color) in [(&mut before, 0.0), (&mut after, 1.0)] {
+            let mut painter = CanvasPainter::<Head<()>>::begin(canvas);
+
+            let brush = painter.add_paint(Paint {
+                color: Color::from_rgb((color, 0.0, 0.0)),
+                ..Paint::default()
+            });
+
+            painter.draw_rect(&brush);
+        }
+
+        after.size = Size::new(6.0, 6.0);
+
+        let differences = diff_canvases(&before, &after);
+
+        assert_eq!(
+            differences
+                .iter()
+                .map(|difference| difference.path.as_str())
+                .collect::<Vec<_>>(),
+            vec!["canvas.size", "canvas.paints[0]"]
+        );
+    }
+}
@@ -0,0 +1,356 @@
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::engine::rendering::capture::fonts::{self, FontTable};
+
+/// The version of the format written by this version of agui. Captures written by any
+/// other version are rejected, rather than risk being misread.
+pub const FORMAT_VERSION: u16 = 1;
+
+/// Marks the start of the binary encoding.
+const MAGIC: &[u8; 4] = b"AGUI";
+
+#[derive(Debug, thiserror::Error)]
+pub enum CaptureError {
+    #[error("the data is not a capture")]
+    InvalidHeader,
+
+    #[error("unsupported format version {0}, expected {FORMAT_VERSION}")]
+    UnsupportedVersion(u16),
+
+    #[error("invalid json: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("invalid binary data: {0}")]
+    Binary(#[from] bincode::Error),
+
+    #[error("render object {object} refers to parent {parent}, which is not listed before it")]
+    InvalidParent { object: usize, parent: usize },
+
+    #[error("render object {0} has no parent, but the scene already has a root")]
+    MultipleRoots(usize),
+}
+
+#[derive(Serialize)]
+struct Envelope<'a, T> {
+    version: u16,
+    fonts: &'a FontTable,
+    data: &'a T,
+}
+
+#[derive(Deserialize)]
+struct Header {
+    version: u16,
+}
+
+#[derive(Deserialize)]
+struct OwnedEnvelope {
+    fonts: FontTable,
+    data: serde_json::Value,
+}
+
+/// Encodes the value as versioned JSON.
+pub fn to_json<T: Serialize>(value: &T) -> Result<String, CaptureError> {
+    // Fonts are only known once the value has been serialized, but are written first
+    let (data, fonts) = fonts::intern(|| serde_json::to_value(value));
+
+    Ok(serde_json::to_string(&Envelope {
+        version: FORMAT_VERSION,
+        fonts: &fonts,
+        data: &data?,
+    })?)
+}
+
+/// Decodes a value from JSON written by [`to_json`].
+pub fn from_json<T: DeserializeOwned>(json: &str) -> Result<T, CaptureError> {
+    // The version is checked first, so that a capture from another version is reported as
+    // such rather than as whatever part of it fails to parse
+    let header = serde_json::from_str::<Header>(json)?;
+
+    if header.version != FORMAT_VERSION {
+        return Err(CaptureError::UnsupportedVersion(header.version));
+    }
+
+    let envelope = serde_json::from_str::<OwnedEnvelope>(json)?;
+
+    Ok(fonts::resolve(envelope.fonts, || {
+        serde_json::from_value(envelope.data)
+    })?)
+}
+
+/// Encodes the value in a compact, versioned binary format.
+pub fn to_bytes<T: Serialize>(value: &T) -> Result<Vec<u8>, CaptureError> {
+    let mut bytes = MAGIC.to_vec();
+
+    bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+
+    let (data, fonts) = fonts::intern(|| bincode::serialize(value));
+
+    bincode::serialize_into(&mut bytes, &fonts)?;
+
+    bytes.extend(data?);
+
+    Ok(bytes)
+}
+
+/// Decodes a value from bytes written by [`to_bytes`].
+pub fn from_bytes<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, CaptureError> {
+    let Some(data) = bytes.strip_prefix(MAGIC.as_slice()) else {
+        return Err(CaptureError::InvalidHeader);
+    };
+
+    let Some((version, data)) = data.split_first_chunk::<2>() else {
+        return Err(CaptureError::InvalidHeader);
+    };
+
+    let version = u16::from_le_bytes(*version);
+
+    if version != FORMAT_VERSION {
+        return Err(CaptureError::UnsupportedVersion(version));
+    }
+
+    let mut data = data;
+
+    let fonts = bincode::deserialize_from::<_, FontTable>(&mut data)?;
+
+    Ok(fonts::resolve(fonts, || bincode::deserialize(data))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use crate::{
+        render::canvas::{
+            command::CanvasCommand,
+            paint::Paint,
+            painter::{CanvasPainter, Head},
+            Canvas, CanvasLayer, LayerStyle,
+        },
+        unit::{
+            Color, Font, GlyphRun, Gradient, GradientStop, LayerFilter, Offset, PaintingStyle,
+            PositionedGlyph, Rect, Shape, Size, StrokeStyle, TextStyle, Texture,
+        },
+    };
+
+    use super::{from_bytes, from_json, to_bytes, to_json, CaptureError, FORMAT_VERSION};
+
+    fn canvas() -> Canvas {
+        let mut canvas = Canvas::new(Size::new(100.0, 50.0));
+
+        {
+            let mut painter = CanvasPainter::<Head<()>>::begin(&mut canvas);
+
+            let fill = painter.add_paint(Paint {
+                color: Color::from_rgb((1.0, 0.0, 0.0)),
+                gradient: Some(Gradient::linear(
+                    Offset::ZERO,
+                    Offset::new(1.0, 0.0),
+                    [
+                        GradientStop::new(0.0, Color::from_rgb((0.0, 0.0, 1.0))),
+                        GradientStop::new(1.0, Color::from_rgb((0.0, 1.0, 0.0))),
+                    ],
+                )),
+                ..Paint::default()
+            });
+
+            let stroke = painter.add_paint(Paint {
+                style: PaintingStyle::Stroke(StrokeStyle::default().dashes(1.0, [2.0, 3.0])),
+                ..Paint::default()
+            });
+
+            painter.draw_rect(&fill);
+            painter.draw_rounded_rect(&stroke, 1.0, 2.0, 3.0, 4.0);
+
+            painter.draw_texture_at(
+                Rect::new(0.0, 0.0, 10.0, 10.0),
+                Shape::Circle,
+                Texture::from_rgba8(1, 1, vec![0, 0, 0, 255]),
+                Default::default(),
+            );
+
+            painter.draw_text(
+                &fill,
+                TextStyle {
+                    font: Font::from_family("serif"),
+                    size: 16.0,
+                    color: Color::from_rgb((0.0, 0.0, 0.0)),
+                    h_align: Default::default(),
+                    v_align: Default::default(),
+                    ..TextStyle::default()
+                },
+                "hello",
+            );
+
+            let mut painter = painter.layer(&fill, Shape::Rect, |mut layer| {
+                let brush = layer.add_paint(Paint::default());
+
+                layer.draw_rect(&brush);
+            });
+
+            let brush = painter.add_paint(Paint::default());
+
+            let mut tail =
+                painter.start_filtered_layer(&brush, Shape::Circle, LayerFilter::blur(2.0));
+
+            let brush = tail.add_paint(Paint::default());
+
+            tail.draw_rect(&brush);
+        }
+
+        canvas
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let canvas = canvas();
+
+        let json = to_json(&canvas).unwrap();
+
+        assert!(json.starts_with(&format!("{{\"version\":{}", FORMAT_VERSION)));
+
+        assert_eq!(from_json::<Canvas>(&json).unwrap(), canvas);
+    }
+
+    #[test]
+    fn round_trips_through_bytes() {
+        let canvas = canvas();
+
+        let bytes = to_bytes(&canvas).unwrap();
+
+        assert_eq!(from_bytes::<Canvas>(&bytes).unwrap(), canvas);
+    }
+
+    #[test]
+    fn round_trips_paths() {
+        let command = CanvasCommand::Shape {
+            paint_idx: 0,
+            rect: Rect::new(0.0, 0.0, 10.0, 10.0),
+            shape: Shape::Path({
+                let mut builder = lyon::path::Path::builder();
+
+                builder.begin(lyon::math::point(0.0, 0.0));
+                builder.line_to(lyon::math::point(10.0, 0.0));
+                builder.line_to(lyon::math::point(5.0, 10.0));
+                builder.close();
+
+                builder.build()
+            }),
+        };
+
+        let bytes = to_bytes(&command).unwrap();
+
+        assert_eq!(from_bytes::<CanvasCommand>(&bytes).unwrap(), command);
+
+        let layer = CanvasLayer::new(
+            Rect::new(1.0, 2.0, 3.0, 4.0),
+            LayerStyle {
+                paint_idx: 0,
+
+                shape: Shape::Rect,
+
+                filter: None,
+                backdrop_filter: Some(LayerFilter::blur(1.0)),
+            },
+        );
+
+        let json = to_json(&layer).unwrap();
+
+        assert_eq!(from_json::<CanvasLayer>(&json).unwrap(), layer);
+
+        let text = CanvasCommand::Text {
+            paint_idx: 0,
+            rect: Rect::default(),
+            text_style: TextStyle {
+                font: Font::from_bytes(vec![1, 2, 3]),
+                size: 12.0,
+                color: Color::from_rgb((0.0, 0.0, 0.0)),
+                h_align: Default::default(),
+                v_align: Default::default(),
+                ..TextStyle::default()
+            },
+            text: Cow::Borrowed("hi"),
+        };
+
+        assert_eq!(
+            from_bytes::<CanvasCommand>(&to_bytes(&text).unwrap()).unwrap(),
+            text
+        );
+    }
+
+    #[test]
+    fn writes_each_font_once() {
+        let font = Font::from_bytes(vec![7; 4096]);
+
+        let run = |id| GlyphRun {
+            font: font.clone(),
+            font_size: 16.0,
+
+            range: 0..1,
+            rtl: false,
+
+            glyphs: vec![PositionedGlyph {
+                id,
+                cluster: 0,
+
+                x: 0.0,
+                y: 12.0,
+
+                advance: 8.0,
+            }],
+        };
+
+        let commands = (0..16)
+            .map(|id| CanvasCommand::Glyphs {
+                paint_idx: 0,
+                rect: Rect::default(),
+                runs: vec![run(id), run(id + 1)],
+            })
+            .collect::<Vec<_>>();
+
+        let bytes = to_bytes(&commands).unwrap();
+
+        assert!(bytes.len() < 4096 * 2);
+
+        let decoded = from_bytes::<Vec<CanvasCommand>>(&bytes).unwrap();
+
+        assert_eq!(decoded, commands);
+
+        // Decoded fonts share their data, like the fonts they were captured from
+        let fonts = decoded
+            .iter()
+            .flat_map(|command| match command {
+                CanvasCommand::Glyphs { runs, .. } => runs.iter().map(|run| run.font.id()),
+                _ => unreachable!(),
+            })
+            .collect::<Vec<_>>();
+
+        assert!(fonts.iter().all(|id| *id == fonts[0]));
+
+        let json = to_json(&commands).unwrap();
+
+        assert!(json.len() < 4096 * 4);
+        assert_eq!(from_json::<Vec<CanvasCommand>>(&json).unwrap(), commands);
+    }
+
+    #[test]
+    fn rejects_other_versions() {
+        let mut bytes = to_bytes(&Size::new(1.0, 2.0)).unwrap();
+
+        bytes[4] = FORMAT_VERSION as u8 + 1;
+
+        assert!(matches!(
+            from_bytes::<Size>(&bytes),
+            Err(CaptureError::UnsupportedVersion(_))
+        ));
+
+        assert!(matches!(
+            from_bytes::<Size>(b"PNG"),
+            Err(CaptureError::InvalidHeader)
+        ));
+
+        assert!(matches!(
+            from_json::<Size>(r#"{"version":999,"data":{"something":"else"}}"#),
+            Err(CaptureError::UnsupportedVersion(999))
+        ));
+    }
+}
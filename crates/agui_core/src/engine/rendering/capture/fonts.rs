@@ -0,0 +1,101 @@
+use std::{cell::RefCell, sync::Arc};
+
+use rustc_hash::FxHashMap;
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+
+thread_local! {
+    /// The table that the data of fonts is written to or read from while a capture is
+    /// being encoded or decoded.
+    static FONT_TABLE: RefCell<Option<FontTable>> = const { RefCell::new(None) };
+}
+
+/// The data of each font used in a capture, which is written once ahead of the capture
+/// rather than with every text style and glyph run that uses it.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct FontTable {
+    fonts: Vec<Arc<Vec<u8>>>,
+
+    /// The index of each font's data in the table, by its address.
+    #[serde(skip)]
+    indices: FxHashMap<*const Vec<u8>, u32>,
+}
+
+impl FontTable {
+    fn intern(&mut self, data: &Arc<Vec<u8>>) -> u32 {
+        *self.indices.entry(Arc::as_ptr(data)).or_insert_with(|| {
+            self.fonts.push(Arc::clone(data));
+
+            (self.fonts.len() - 1) as u32
+        })
+    }
+}
+
+/// Calls the function with the given table in use, returning the table afterwards.
+fn with_table<R>(table: FontTable, func: impl FnOnce() -> R) -> (R, FontTable) {
+    let previous = FONT_TABLE.with(|cell| cell.replace(Some(table)));
+
+    let result = func();
+
+    let table = FONT_TABLE
+        .with(|cell| cell.replace(previous))
+        .expect("font table was removed while in use");
+
+    (result, table)
+}
+
+/// Calls the function, interning the data of any font it serializes into a table.
+pub(crate) fn intern<R>(func: impl FnOnce() -> R) -> (R, FontTable) {
+    with_table(FontTable::default(), func)
+}
+
+/// Calls the function, reading the data of any font it deserializes from the table.
+pub(crate) fn resolve<R>(table: FontTable, func: impl FnOnce() -> R) -> R {
+    with_table(table, func).0
+}
+
+#[derive(Serialize)]
+#[serde(rename = "FontBytes")]
+enum FontBytesRef<'a> {
+    Inline(&'a Arc<Vec<u8>>),
+    Interned(u32),
+}
+
+#[derive(Deserialize)]
+enum FontBytes {
+    Inline(Arc<Vec<u8>>),
+    Interned(u32),
+}
+
+/// Serializes the data of a font as an index into the font table, if one is in use.
+pub(crate) fn serialize<S>(data: &Arc<Vec<u8>>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let interned = FONT_TABLE.with(|cell| {
+        cell.borrow_mut()
+            .as_mut()
+            .map(|table| FontBytesRef::Interned(table.intern(data)))
+    });
+
+    interned
+        .unwrap_or(FontBytesRef::Inline(data))
+        .serialize(serializer)
+}
+
+pub(crate) fn deserialize<'de, D>(deserializer: D) -> Result<Arc<Vec<u8>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match FontBytes::deserialize(deserializer)? {
+        FontBytes::Inline(data) => Ok(data),
+
+        FontBytes::Interned(idx) => FONT_TABLE
+            .with(|cell| {
+                cell.borrow()
+                    .as_ref()
+                    .and_then(|table| table.fonts.get(idx as usize))
+                    .cloned()
+            })
+            .ok_or_else(|| D::Error::custom(format!("font {} is not in the font table", idx))),
+    }
+}
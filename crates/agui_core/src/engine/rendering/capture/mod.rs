@@ -0,0 +1,326 @@
+//! Captures canvases and synced scenes so that they can be saved, replayed into another
+//! [`View`], or compared against each other.
+
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use slotmap::SlotMap;
+
+use crate::{
+    engine::rendering::{scene::Scene, view::View},
+    render::{canvas::Canvas, RenderObjectId},
+    unit::{Offset, Size},
+};
+
+mod diff;
+mod encoding;
+pub(crate) mod fonts;
+mod view;
+
+pub use diff::*;
+pub use encoding::*;
+pub use view::*;
+
+/// The render objects of a view as they were when it was last synced.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SceneCapture {
+    /// The render objects in the scene, depth first, starting from the root. Parents are
+    /// always listed before their children.
+    pub objects: Vec<CapturedRenderObject>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CapturedRenderObject {
+    /// The index of the object's parent in the scene, or `None` for the root.
+    pub parent: Option<usize>,
+
+    /// The offset of the render object relative to its parent.
+    pub offset: Offset,
+
+    pub size: Size,
+
+    /// The canvas from the most recent time the render object was painted.
+    pub canvas: Option<Canvas>,
+}
+
+impl SceneCapture {
+    pub(crate) fn from_scene(scene: &Scene) -> Self {
+        let mut indices = FxHashMap::<RenderObjectId, usize>::default();
+
+        let objects = scene
+            .iter()
+            .enumerate()
+            .map(|(idx, (render_object_id, object))| {
+                indices.insert(render_object_id, idx);
+
+                CapturedRenderObject {
+                    parent: scene
+                        .get_parent(render_object_id)
+                        .and_then(|parent_id| indices.get(&parent_id))
+                        .copied(),
+
+                    offset: object.offset,
+                    size: object.size,
+                    canvas: object.canvas.clone(),
+                }
+            })
+            .collect();
+
+        Self { objects }
+    }
+
+    pub fn len(&self) -> usize {
+        self.objects.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+
+    /// Checks that the scene forms a single tree, with each object listed after its parent.
+    ///
+    /// Captures written by [`CapturingView`] always do, but one that was truncated or
+    /// edited by hand may not.
+    pub fn validate(&self) -> Result<(), CaptureError> {
+        for (idx, object) in self.objects.iter().enumerate() {
+            match object.parent {
+                Some(parent) if parent >= idx => {
+                    return Err(CaptureError::InvalidParent {
+                        object: idx,
+                        parent,
+                    });
+                }
+
+                None if idx > 0 => return Err(CaptureError::MultipleRoots(idx)),
+
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Feeds the scene into the view as though it were being built from scratch, then
+    /// syncs the view.
+    ///
+    /// New render object IDs are created for the scene, since the ones it was captured
+    /// with mean nothing outside of the tree they came from. They're returned in the
+    /// same order as the scene's objects.
+    ///
+    /// The scene is [validated](Self::validate) first, and nothing is fed into the view if
+    /// it isn't a well-formed tree.
+    pub fn replay(&self, view: &mut dyn View) -> Result<Vec<RenderObjectId>, CaptureError> {
+        self.validate()?;
+
+        let mut ids = SlotMap::<RenderObjectId, ()>::default();
+
+        let render_object_ids = self
+            .objects
+            .iter()
+            .map(|_| ids.insert(()))
+            .collect::<Vec<_>>();
+
+        for (object, render_object_id) in self.objects.iter().zip(&render_object_ids) {
+            let parent_render_object_id = object.parent.map(|parent| render_object_ids[parent]);
+
+            view.on_attach(parent_render_object_id, *render_object_id);
+
+            view.on_size_changed(*render_object_id, object.size);
+            view.on_offset_changed(*render_object_id, object.offset);
+
+            if let Some(canvas) = &object.canvas {
+                view.on_paint(*render_object_id, canvas.clone());
+            }
+        }
+
+        view.on_sync();
+
+        Ok(render_object_ids)
+    }
+
+    /// Encodes the scene as versioned JSON.
+    pub fn to_json(&self) -> Result<String, CaptureError> {
+        encoding::to_json(self)
+    }
+
+    /// Decodes a scene from JSON written by [`to_json`](Self::to_json).
+    pub fn from_json(json: &str) -> Result<Self, CaptureError> {
+        encoding::from_json(json)
+    }
+
+    /// Encodes the scene in a compact, versioned binary format.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, CaptureError> {
+        encoding::to_bytes(self)
+    }
+
+    /// Decodes a scene from bytes written by [`to_bytes`](Self::to_bytes).
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CaptureError> {
+        encoding::from_bytes(bytes)
+    }
+
+    /// Compares the scene against another, object by object. Objects are matched by
+    /// their position in the tree rather than their identity.
+    pub fn diff(&self, other: &SceneCapture) -> Vec<Difference> {
+        diff::diff_scenes(self, other)
+    }
+
+    /// Returns the path of each object in the scene, made up of its index amongst its
+    /// siblings and those of each of its ancestors.
+    pub(crate) fn paths(&self) -> Vec<String> {
+        let mut paths = Vec::<String>::with_capacity(self.objects.len());
+        let mut child_counts = vec![0_usize; self.objects.len()];
+        let mut root_count = 0;
+
+        for object in &self.objects {
+            let (parent_path, sibling_idx) =
+                match object.parent.filter(|parent| *parent < paths.len()) {
+                    Some(parent) => {
+                        child_counts[parent] += 1;
+
+                        (paths[parent].as_str(), child_counts[parent] - 1)
+                    }
+
+                    None => {
+                        root_count += 1;
+
+                        ("", root_count - 1)
+                    }
+                };
+
+            paths.push(format!("{}/{}", parent_path, sibling_idx));
+        }
+
+        paths
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        engine::rendering::mock::RecordingView,
+        render::canvas::Canvas,
+        unit::{Offset, Size},
+    };
+
+    use super::{CaptureError, CapturedRenderObject, SceneCapture};
+
+    pub(super) fn scene() -> SceneCapture {
+        SceneCapture {
+            objects: vec![
+                CapturedRenderObject {
+                    parent: None,
+                    size: Size::new(10.0, 10.0),
+                    ..CapturedRenderObject::default()
+                },
+                CapturedRenderObject {
+                    parent: Some(0),
+                    offset: Offset::new(1.0, 1.0),
+                    size: Size::new(5.0, 5.0),
+                    canvas: Some(Canvas::new(Size::new(5.0, 5.0))),
+                },
+                CapturedRenderObject {
+                    parent: Some(1),
+                    offset: Offset::new(2.0, 2.0),
+                    size: Size::new(1.0, 1.0),
+                    canvas: None,
+                },
+                CapturedRenderObject {
+                    parent: Some(0),
+                    size: Size::new(3.0, 3.0),
+                    ..CapturedRenderObject::default()
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn names_objects_by_their_position() {
+        assert_eq!(scene().paths(), vec!["/0", "/0/0", "/0/0/0", "/0/1"]);
+    }
+
+    #[test]
+    fn replays_into_views() {
+        let scene = scene();
+
+        let recording = RecordingView::new();
+
+        let ids = scene.replay(&mut recording.clone()).unwrap();
+
+        let recorded = recording.scene();
+
+        assert_eq!(recorded.sync_count(), 1);
+        assert_eq!(recorded.root(), Some(ids[0]));
+        assert_eq!(recorded.get_children(ids[0]), &[ids[1], ids[3]]);
+
+        assert_eq!(recorded.global_offset(ids[2]), Some(Offset::new(3.0, 3.0)));
+        assert_eq!(
+            recorded.get(ids[1]).unwrap().canvas,
+            Some(Canvas::new(Size::new(5.0, 5.0)))
+        );
+    }
+
+    #[test]
+    fn round_trips_scenes() {
+        let scene = scene();
+
+        assert_eq!(
+            SceneCapture::from_json(&scene.to_json().unwrap()).unwrap(),
+            scene
+        );
+
+        assert_eq!(
+            SceneCapture::from_bytes(&scene.to_bytes().unwrap()).unwrap(),
+            scene
+        );
+    }
+
+    #[test]
+    fn rejects_truncated_scenes() {
+        let mut scene = scene();
+
+        // The object's parent was cut off, leaving it to refer past the end of the scene
+        scene.objects.remove(1);
+
+        let recording = RecordingView::new();
+
+        assert!(matches!(
+            scene.replay(&mut recording.clone()),
+            Err(CaptureError::InvalidParent {
+                object: 1,
+                parent: 1
+            })
+        ));
+
+        // Nothing reaches the view
+        assert!(recording.scene().is_empty());
+        assert_eq!(recording.scene().sync_count(), 0);
+    }
+
+    #[test]
+    fn rejects_misordered_scenes() {
+        let mut scene = scene();
+
+        scene.objects.swap(1, 2);
+        scene.objects[1].parent = Some(2);
+        scene.objects[2].parent = Some(0);
+
+        assert!(matches!(
+            scene.validate(),
+            Err(CaptureError::InvalidParent {
+                object: 1,
+                parent: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn rejects_scenes_with_multiple_roots() {
+        let mut scene = scene();
+
+        scene.objects[3].parent = None;
+
+        assert!(matches!(
+            scene.validate(),
+            Err(CaptureError::MultipleRoots(3))
+        ));
+    }
+}
@@ -0,0 +1,167 @@
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+
+use crate::{
+    engine::rendering::{
+        capture::SceneCapture,
+        scene::{Scene, SceneChange},
+        strategies::RenderingTreeTextLayoutStrategy,
+        view::View,
+    },
+    render::{canvas::Canvas, RenderObjectId},
+    unit::{Offset, Size},
+};
+
+/// Wraps a [`View`], keeping a copy of everything sent to it so that the scene can be
+/// captured as it was when the view was last synced.
+///
+/// Every call is forwarded to the wrapped view unchanged.
+pub struct CapturingView<V> {
+    inner: V,
+
+    changes: Vec<SceneChange>,
+
+    scene: Arc<Mutex<Scene>>,
+}
+
+/// Captures the scene of a [`CapturingView`], from anywhere.
+#[derive(Clone)]
+pub struct CaptureHandle {
+    scene: Arc<Mutex<Scene>>,
+}
+
+impl<V> CapturingView<V>
+where
+    V: View,
+{
+    pub fn new(inner: V) -> Self {
+        Self {
+            inner,
+
+            changes: Vec::default(),
+
+            scene: Arc::default(),
+        }
+    }
+
+    pub fn handle(&self) -> CaptureHandle {
+        CaptureHandle {
+            scene: Arc::clone(&self.scene),
+        }
+    }
+
+    pub fn inner(&self) -> &V {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> V {
+        self.inner
+    }
+}
+
+impl CaptureHandle {
+    /// Returns the scene as it was when the view was last synced.
+    pub fn capture(&self) -> SceneCapture {
+        SceneCapture::from_scene(&self.scene.lock())
+    }
+}
+
+impl<V> View for CapturingView<V>
+where
+    V: View,
+{
+    fn text_layout(&self) -> &dyn RenderingTreeTextLayoutStrategy {
+        self.inner.text_layout()
+    }
+
+    fn text_layout_mut(&mut self) -> &mut dyn RenderingTreeTextLayoutStrategy {
+        self.inner.text_layout_mut()
+    }
+
+    fn on_attach(
+        &mut self,
+        parent_render_object_id: Option<RenderObjectId>,
+        render_object_id: RenderObjectId,
+    ) {
+        self.changes.push(SceneChange::Attach {
+            parent_render_object_id,
+            render_object_id,
+        });
+
+        self.inner
+            .on_attach(parent_render_object_id, render_object_id);
+    }
+
+    fn on_detach(&mut self, render_object_id: RenderObjectId) {
+        self.changes.push(SceneChange::Detach { render_object_id });
+
+        self.inner.on_detach(render_object_id);
+    }
+
+    fn on_size_changed(&mut self, render_object_id: RenderObjectId, size: Size) {
+        self.changes.push(SceneChange::SizeChanged {
+            render_object_id,
+            size,
+        });
+
+        self.inner.on_size_changed(render_object_id, size);
+    }
+
+    fn on_offset_changed(&mut self, render_object_id: RenderObjectId, offset: Offset) {
+        self.changes.push(SceneChange::OffsetChanged {
+            render_object_id,
+            offset,
+        });
+
+        self.inner.on_offset_changed(render_object_id, offset);
+    }
+
+    fn on_paint(&mut self, render_object_id: RenderObjectId, canvas: Canvas) {
+        self.changes.push(SceneChange::Paint {
+            render_object_id,
+            canvas: canvas.clone(),
+        });
+
+        self.inner.on_paint(render_object_id, canvas);
+    }
+
+    fn on_sync(&mut self) {
+        // Changes are only applied once the view syncs, so that a capture never sees a
+        // partially updated scene
+        {
+            let mut scene = self.scene.lock();
+
+            for change in self.changes.drain(..) {
+                scene.apply(change);
+            }
+        }
+
+        self.inner.on_sync();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::rendering::{capture::tests::scene, mock::RecordingView};
+
+    use super::CapturingView;
+
+    #[test]
+    fn captures_synced_scenes() {
+        let scene = scene();
+
+        let recording = RecordingView::new();
+
+        let mut view = CapturingView::new(recording.clone());
+        let handle = view.handle();
+
+        scene.replay(&mut view).unwrap();
+
+        assert_eq!(handle.capture(), scene);
+
+        // Calls are forwarded to the wrapped view
+        assert_eq!(recording.scene().len(), scene.len());
+        assert_eq!(recording.scene().sync_count(), 1);
+    }
+}
@@ -0,0 +1,24 @@
+use crate::unit::{Color, Font, TextStyle};
+
+/// The data of the DejaVu Sans font shipped with the examples, for tests that need to lay
+/// out or draw real glyphs.
+pub const DEJA_VU_SANS: &[u8] = include_bytes!("../../../../../../examples/fonts/DejaVuSans.ttf");
+
+/// Returns a new [`Font`] holding [`DEJA_VU_SANS`].
+///
+/// Each call loads the data anew, so fonts returned by separate calls have different
+/// [identities](Font::id).
+pub fn deja_vu_sans() -> Font {
+    Font::from_bytes(DEJA_VU_SANS.to_vec())
+}
+
+/// A style drawing text in [DejaVu Sans](deja_vu_sans) at the given size and color,
+/// aligned to the top left.
+pub fn text_style(size: f32, color: Color) -> TextStyle {
+    TextStyle {
+        font: deja_vu_sans(),
+        size,
+        color,
+        ..TextStyle::default()
+    }
+}
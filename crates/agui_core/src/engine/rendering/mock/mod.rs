@@ -0,0 +1,7 @@
+mod fonts;
+mod recording;
+mod text_layout;
+
+pub use fonts::*;
+pub use recording::*;
+pub use text_layout::*;
@@ -0,0 +1,360 @@
+use std::{ops::Deref, sync::Arc};
+
+use parking_lot::{Mutex, MutexGuard};
+use rustc_hash::FxHashMap;
+
+use crate::{
+    engine::rendering::{
+        mock::FixedTextLayout,
+        scene::{Scene, SceneChange},
+        strategies::RenderingTreeTextLayoutStrategy,
+        view::View,
+    },
+    render::{canvas::Canvas, RenderObjectId},
+    unit::{Offset, Size},
+};
+
+/// A call that was made to a [`RecordingView`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ViewCall {
+    Attach {
+        parent_render_object_id: Option<RenderObjectId>,
+        render_object_id: RenderObjectId,
+    },
+
+    Detach {
+        render_object_id: RenderObjectId,
+    },
+
+    SizeChanged {
+        render_object_id: RenderObjectId,
+        size: Size,
+    },
+
+    OffsetChanged {
+        render_object_id: RenderObjectId,
+        offset: Offset,
+    },
+
+    /// The render object was painted. The canvas it produced is kept in the view's
+    /// [`RecordedScene`].
+    Paint {
+        render_object_id: RenderObjectId,
+    },
+
+    Sync,
+}
+
+impl ViewCall {
+    /// The render object the call was made for, if any.
+    pub fn render_object_id(&self) -> Option<RenderObjectId> {
+        match self {
+            ViewCall::Attach {
+                render_object_id, ..
+            }
+            | ViewCall::Detach { render_object_id }
+            | ViewCall::SizeChanged {
+                render_object_id, ..
+            }
+            | ViewCall::OffsetChanged {
+                render_object_id, ..
+            }
+            | ViewCall::Paint { render_object_id } => Some(*render_object_id),
+
+            ViewCall::Sync => None,
+        }
+    }
+}
+
+/// A [`View`] that records every call made to it, and reconstructs the scene it would
+/// have drawn.
+///
+/// Clones of the view share the same recording, so a clone may be kept by the test while
+/// the original is handed to the rendering tree. Text is measured using [`FixedTextLayout`].
+#[derive(Clone, Default)]
+pub struct RecordingView {
+    text_layout: FixedTextLayout,
+
+    calls: Arc<Mutex<Vec<ViewCall>>>,
+
+    scene: Arc<Mutex<RecordedScene>>,
+}
+
+impl RecordingView {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the calls made to the view, in the order they were made.
+    pub fn calls(&self) -> MutexGuard<'_, Vec<ViewCall>> {
+        self.calls.lock()
+    }
+
+    /// Returns the calls made to the view since this was last called.
+    pub fn take_calls(&self) -> Vec<ViewCall> {
+        std::mem::take(&mut *self.calls.lock())
+    }
+
+    /// Returns the scene as it currently stands, with every recorded call applied.
+    pub fn scene(&self) -> MutexGuard<'_, RecordedScene> {
+        self.scene.lock()
+    }
+
+    /// Whether both views share the same recording.
+    pub fn is_same_recording(&self, other: &RecordingView) -> bool {
+        Arc::ptr_eq(&self.calls, &other.calls)
+    }
+
+    fn record(&self, call: ViewCall) {
+        self.calls.lock().push(call);
+    }
+}
+
+impl View for RecordingView {
+    fn text_layout(&self) -> &dyn RenderingTreeTextLayoutStrategy {
+        &self.text_layout
+    }
+
+    fn text_layout_mut(&mut self) -> &mut dyn RenderingTreeTextLayoutStrategy {
+        &mut self.text_layout
+    }
+
+    fn on_attach(
+        &mut self,
+        parent_render_object_id: Option<RenderObjectId>,
+        render_object_id: RenderObjectId,
+    ) {
+        self.record(ViewCall::Attach {
+            parent_render_object_id,
+            render_object_id,
+        });
+
+        self.scene.lock().apply(SceneChange::Attach {
+            parent_render_object_id,
+            render_object_id,
+        });
+    }
+
+    fn on_detach(&mut self, render_object_id: RenderObjectId) {
+        self.record(ViewCall::Detach { render_object_id });
+
+        self.scene
+            .lock()
+            .apply(SceneChange::Detach { render_object_id });
+    }
+
+    fn on_size_changed(&mut self, render_object_id: RenderObjectId, size: Size) {
+        self.record(ViewCall::SizeChanged {
+            render_object_id,
+            size,
+        });
+
+        self.scene.lock().apply(SceneChange::SizeChanged {
+            render_object_id,
+            size,
+        });
+    }
+
+    fn on_offset_changed(&mut self, render_object_id: RenderObjectId, offset: Offset) {
+        self.record(ViewCall::OffsetChanged {
+            render_object_id,
+            offset,
+        });
+
+        self.scene.lock().apply(SceneChange::OffsetChanged {
+            render_object_id,
+            offset,
+        });
+    }
+
+    fn on_paint(&mut self, render_object_id: RenderObjectId, canvas: Canvas) {
+        self.record(ViewCall::Paint { render_object_id });
+
+        self.scene.lock().apply(SceneChange::Paint {
+            render_object_id,
+            canvas,
+        });
+    }
+
+    fn on_sync(&mut self) {
+        self.record(ViewCall::Sync);
+
+        self.scene.lock().sync_count += 1;
+    }
+}
+
+/// The render objects attached to a [`RecordingView`].
+///
+/// Calls are applied to the [`Scene`] as they're made, rather than once the view syncs,
+/// so it always reflects every call that was recorded.
+#[derive(Default)]
+pub struct RecordedScene {
+    scene: Scene,
+
+    paint_counts: FxHashMap<RenderObjectId, usize>,
+
+    sync_count: usize,
+}
+
+impl Deref for RecordedScene {
+    type Target = Scene;
+
+    fn deref(&self) -> &Self::Target {
+        &self.scene
+    }
+}
+
+impl RecordedScene {
+    /// How many times the view has been synced.
+    pub fn sync_count(&self) -> usize {
+        self.sync_count
+    }
+
+    /// How many times the render object has been painted since it was attached.
+    pub fn paint_count(&self, render_object_id: RenderObjectId) -> usize {
+        self.paint_counts
+            .get(&render_object_id)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn apply(&mut self, change: SceneChange) {
+        let is_detach = matches!(change, SceneChange::Detach { .. });
+
+        if let SceneChange::Paint {
+            render_object_id, ..
+        } = &change
+        {
+            *self.paint_counts.entry(*render_object_id).or_default() += 1;
+        }
+
+        self.scene.apply(change);
+
+        if is_detach {
+            let scene = &self.scene;
+
+            self.paint_counts
+                .retain(|render_object_id, _| scene.contains(*render_object_id));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use slotmap::SlotMap;
+
+    use crate::{
+        engine::rendering::view::View,
+        render::{canvas::Canvas, RenderObjectId},
+        unit::{Offset, Size},
+    };
+
+    use super::{RecordingView, ViewCall};
+
+    #[test]
+    fn records_calls() {
+        let mut ids = SlotMap::<RenderObjectId, ()>::default();
+
+        let root_id = ids.insert(());
+
+        let recording = RecordingView::new();
+
+        let mut view = recording.clone();
+
+        view.on_attach(None, root_id);
+        view.on_size_changed(root_id, Size::new(10.0, 10.0));
+        view.on_paint(root_id, Canvas::new(Size::new(10.0, 10.0)));
+        view.on_sync();
+
+        assert_eq!(
+            recording.take_calls(),
+            vec![
+                ViewCall::Attach {
+                    parent_render_object_id: None,
+                    render_object_id: root_id
+                },
+                ViewCall::SizeChanged {
+                    render_object_id: root_id,
+                    size: Size::new(10.0, 10.0)
+                },
+                ViewCall::Paint {
+                    render_object_id: root_id
+                },
+                ViewCall::Sync,
+            ]
+        );
+
+        assert!(recording.calls().is_empty());
+
+        assert_eq!(recording.scene().sync_count(), 1);
+    }
+
+    #[test]
+    fn reconstructs_the_scene() {
+        let mut ids = SlotMap::<RenderObjectId, ()>::default();
+
+        let root_id = ids.insert(());
+        let child_id = ids.insert(());
+        let grandchild_id = ids.insert(());
+
+        let recording = RecordingView::new();
+
+        let mut view = recording.clone();
+
+        view.on_attach(None, root_id);
+        view.on_attach(Some(root_id), child_id);
+        view.on_attach(Some(child_id), grandchild_id);
+
+        view.on_offset_changed(child_id, Offset::new(5.0, 5.0));
+        view.on_offset_changed(grandchild_id, Offset::new(1.0, 2.0));
+        view.on_size_changed(grandchild_id, Size::new(3.0, 4.0));
+
+        view.on_paint(grandchild_id, Canvas::new(Size::new(3.0, 4.0)));
+        view.on_paint(grandchild_id, Canvas::new(Size::new(3.0, 4.0)));
+
+        {
+            let scene = recording.scene();
+
+            assert_eq!(scene.root(), Some(root_id));
+            assert_eq!(scene.get_children(root_id), &[child_id]);
+            assert_eq!(scene.get_parent(grandchild_id), Some(child_id));
+
+            assert_eq!(
+                scene.iter().map(|(id, _)| id).collect::<Vec<_>>(),
+                vec![root_id, child_id, grandchild_id]
+            );
+
+            assert_eq!(
+                scene.global_offset(grandchild_id),
+                Some(Offset::new(6.0, 7.0))
+            );
+
+            let grandchild = scene.get(grandchild_id).unwrap();
+
+            assert_eq!(grandchild.size, Size::new(3.0, 4.0));
+            assert_eq!(grandchild.canvas, Some(Canvas::new(Size::new(3.0, 4.0))));
+
+            assert_eq!(scene.paint_count(grandchild_id), 2);
+            assert_eq!(scene.paint_count(child_id), 0);
+        }
+
+        // Moving the grandchild to the root keeps its state
+        view.on_attach(Some(root_id), grandchild_id);
+
+        {
+            let scene = recording.scene();
+
+            assert_eq!(scene.get_children(root_id), &[child_id, grandchild_id]);
+            assert_eq!(scene.paint_count(grandchild_id), 2);
+        }
+
+        view.on_detach(child_id);
+
+        {
+            let scene = recording.scene();
+
+            assert_eq!(scene.len(), 2);
+            assert!(scene.get(child_id).is_none());
+        }
+    }
+}
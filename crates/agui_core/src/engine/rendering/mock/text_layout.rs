@@ -0,0 +1,66 @@
+use crate::{
+    engine::rendering::strategies::RenderingTreeTextLayoutStrategy,
+    unit::{Constraints, IntrinsicDimension, Size, TextStyle},
+};
+
+/// A text layout that doesn't load any fonts.
+///
+/// Every character is laid out as a square as wide as the font size, and lines are only
+/// broken at newlines. This keeps text sizes predictable in tests. Letter spacing, line
+/// height and the maximum number of lines are honoured.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct FixedTextLayout;
+
+impl FixedTextLayout {
+    fn measure(text_style: &TextStyle, text: &str) -> Size {
+        let (columns, lines) = text.lines().fold((0, 0), |(columns, lines), line| {
+            (columns.max(line.chars().count()), lines + 1)
+        });
+
+        let lines = text_style
+            .max_lines
+            .map_or(lines, |max_lines| lines.min(max_lines));
+
+        Size::new(
+            columns as f32 * (text_style.size + text_style.letter_spacing),
+            lines as f32 * text_style.size * text_style.line_height.unwrap_or(1.0),
+        )
+    }
+}
+
+impl RenderingTreeTextLayoutStrategy for FixedTextLayout {
+    fn compute_intrinsic_size(
+        &self,
+        text_style: &TextStyle,
+        text: &str,
+        dimension: IntrinsicDimension,
+        _: f32,
+    ) -> f32 {
+        match dimension {
+            IntrinsicDimension::MinWidth => {
+                if text.is_empty() {
+                    0.0
+                } else if !text_style.soft_wrap {
+                    Self::measure(text_style, text).width
+                } else {
+                    text_style.size + text_style.letter_spacing
+                }
+            }
+
+            IntrinsicDimension::MaxWidth => Self::measure(text_style, text).width,
+
+            IntrinsicDimension::MinHeight | IntrinsicDimension::MaxHeight => {
+                Self::measure(text_style, text).height
+            }
+        }
+    }
+
+    fn compute_size(
+        &mut self,
+        text_style: &TextStyle,
+        text: &str,
+        constraints: Constraints,
+    ) -> Size {
+        constraints.constrain(Self::measure(text_style, text))
+    }
+}
@@ -1,6 +1,11 @@
+#[cfg(feature = "serde")]
+pub mod capture;
 pub mod context;
 pub mod errors;
+#[cfg(any(test, feature = "mocks"))]
+pub mod mock;
 mod render_views;
+pub mod scene;
 pub mod scheduler;
 pub mod strategies;
 mod tree;
@@ -0,0 +1,563 @@
+use crate::{
+    render::{canvas::Canvas, RenderObjectId},
+    unit::{Offset, Rect, Size},
+    util::tree::{storage::SparseSecondaryMapStorage, Tree},
+};
+
+#[derive(Debug, Default)]
+pub struct SceneObject {
+    pub size: Size,
+
+    pub offset: Offset,
+
+    /// The most recent canvas painted by the object, if any.
+    pub canvas: Option<Canvas>,
+}
+
+/// A change to the render objects of a view, which is applied to its [`Scene`] when the
+/// view is synced.
+#[derive(Debug)]
+pub enum SceneChange {
+    Attach {
+        parent_render_object_id: Option<RenderObjectId>,
+        render_object_id: RenderObjectId,
+    },
+
+    Detach {
+        render_object_id: RenderObjectId,
+    },
+
+    SizeChanged {
+        render_object_id: RenderObjectId,
+        size: Size,
+    },
+
+    OffsetChanged {
+        render_object_id: RenderObjectId,
+        offset: Offset,
+    },
+
+    Paint {
+        render_object_id: RenderObjectId,
+        canvas: Canvas,
+    },
+}
+
+/// A copy of the render objects in a view, along with what they last painted.
+///
+/// Backends draw from this, and it's what mock and capturing views keep to inspect what a
+/// view was sent.
+#[derive(Default)]
+pub struct Scene {
+    tree: Tree<RenderObjectId, SceneObject, SparseSecondaryMapStorage>,
+
+    size: Option<Size>,
+
+    /// The area of the scene that may look different since damage was last taken.
+    damage: Option<Rect>,
+}
+
+impl Scene {
+    /// Returns the size of the root object, or `None` if it hasn't been laid out.
+    pub fn size(&self) -> Option<Size> {
+        self.size
+    }
+
+    pub fn root(&self) -> Option<RenderObjectId> {
+        self.tree.root()
+    }
+
+    pub fn len(&self) -> usize {
+        self.tree.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+
+    pub fn contains(&self, render_object_id: RenderObjectId) -> bool {
+        self.tree.contains(render_object_id)
+    }
+
+    pub fn get(&self, render_object_id: RenderObjectId) -> Option<&SceneObject> {
+        self.tree.get(render_object_id)
+    }
+
+    pub fn get_parent(&self, render_object_id: RenderObjectId) -> Option<RenderObjectId> {
+        self.tree.get_parent(render_object_id).copied()
+    }
+
+    pub fn get_children(&self, render_object_id: RenderObjectId) -> &[RenderObjectId] {
+        self.tree
+            .get_children(render_object_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Iterates over the objects in the scene, depth first, starting from the root.
+    pub fn iter(&self) -> impl Iterator<Item = (RenderObjectId, &SceneObject)> + '_ {
+        self.tree.iter_down().map(|render_object_id| {
+            (
+                render_object_id,
+                self.tree
+                    .get(render_object_id)
+                    .expect("render object missing from the scene"),
+            )
+        })
+    }
+
+    /// Returns the offset of the object relative to the root of the scene.
+    pub fn global_offset(&self, render_object_id: RenderObjectId) -> Option<Offset> {
+        let object = self.tree.get(render_object_id)?;
+
+        Some(self.parent_origin(render_object_id) + object.offset)
+    }
+
+    /// Returns the area of the scene that may look different since this was last called,
+    /// or `None` if nothing visible has changed.
+    ///
+    /// This accounts for the paint bounds of each canvas, so changes to anything that
+    /// paints outside of its object, such as a shadow, damage everywhere it painted.
+    pub fn take_damage(&mut self) -> Option<Rect> {
+        self.damage.take()
+    }
+
+    fn add_damage(&mut self, rect: Option<Rect>) {
+        self.damage = match (self.damage, rect) {
+            (Some(damage), Some(rect)) => Some(damage.union(rect)),
+            (damage, rect) => damage.or(rect),
+        };
+    }
+
+    /// Returns the origin of the object's parent, relative to the root.
+    fn parent_origin(&self, render_object_id: RenderObjectId) -> Offset {
+        self.tree
+            .iter_parents(render_object_id)
+            .filter_map(|parent_id| self.tree.get(parent_id))
+            .fold(Offset::ZERO, |origin, parent| origin + parent.offset)
+    }
+
+    /// Returns the area painted by the object and its descendants, relative to the root.
+    fn subtree_bounds(&self, render_object_id: RenderObjectId) -> Option<Rect> {
+        self.subtree_bounds_from(render_object_id, self.parent_origin(render_object_id))
+    }
+
+    fn subtree_bounds_from(
+        &self,
+        render_object_id: RenderObjectId,
+        parent_origin: Offset,
+    ) -> Option<Rect> {
+        let object = self.tree.get(render_object_id)?;
+
+        let origin = parent_origin + object.offset;
+
+        let bounds = object
+            .canvas
+            .as_ref()
+            .map(|canvas| canvas.paint_bounds.translate(origin));
+
+        self.tree
+            .get_children(render_object_id)
+            .into_iter()
+            .flatten()
+            .filter_map(|child_id| self.subtree_bounds_from(*child_id, origin))
+            .fold(bounds, |bounds, child_bounds| match bounds {
+                Some(bounds) => Some(bounds.union(child_bounds)),
+                None => Some(child_bounds),
+            })
+    }
+
+    pub fn apply(&mut self, change: SceneChange) {
+        match change {
+            SceneChange::Attach {
+                parent_render_object_id,
+                render_object_id,
+            } => self.attach(parent_render_object_id, render_object_id),
+
+            SceneChange::Detach { render_object_id } => self.detach(render_object_id),
+
+            SceneChange::SizeChanged {
+                render_object_id,
+                size,
+            } => self.set_size(render_object_id, size),
+
+            SceneChange::OffsetChanged {
+                render_object_id,
+                offset,
+            } => self.set_offset(render_object_id, offset),
+
+            SceneChange::Paint {
+                render_object_id,
+                canvas,
+            } => self.paint(render_object_id, canvas),
+        }
+    }
+
+    fn attach(
+        &mut self,
+        parent_render_object_id: Option<RenderObjectId>,
+        render_object_id: RenderObjectId,
+    ) {
+        if self.tree.contains(render_object_id) {
+            self.add_damage(self.subtree_bounds(render_object_id));
+
+            self.tree
+                .reparent(parent_render_object_id, render_object_id)
+                .expect("failed to reparent render object");
+
+            self.add_damage(self.subtree_bounds(render_object_id));
+        } else {
+            self.tree.insert(
+                parent_render_object_id,
+                render_object_id,
+                SceneObject::default(),
+            );
+        }
+    }
+
+    fn detach(&mut self, render_object_id: RenderObjectId) {
+        self.add_damage(self.subtree_bounds(render_object_id));
+
+        self.tree.remove_subtree(render_object_id);
+    }
+
+    fn set_size(&mut self, render_object_id: RenderObjectId, size: Size) {
+        self.tree
+            .get_mut(render_object_id)
+            .expect("received size for a non-existent object")
+            .size = size;
+
+        if self.tree.root() == Some(render_object_id) {
+            // A resized scene is drawn anew
+            self.add_damage(Some(Rect::from(size)));
+
+            self.size = Some(size);
+        }
+    }
+
+    fn set_offset(&mut self, render_object_id: RenderObjectId, offset: Offset) {
+        let object = self
+            .tree
+            .get(render_object_id)
+            .expect("received offset for a non-existent object");
+
+        if object.offset == offset {
+            return;
+        }
+
+        self.add_damage(self.subtree_bounds(render_object_id));
+
+        self.tree.get_mut(render_object_id).unwrap().offset = offset;
+
+        self.add_damage(self.subtree_bounds(render_object_id));
+    }
+
+    fn paint(&mut self, render_object_id: RenderObjectId, canvas: Canvas) {
+        let origin = self.parent_origin(render_object_id);
+
+        let object = self
+            .tree
+            .get_mut(render_object_id)
+            .expect("received canvas for a removed object");
+
+        let origin = origin + object.offset;
+
+        let damage = match &object.canvas {
+            Some(old) => old.paint_bounds.union(canvas.paint_bounds),
+            None => canvas.paint_bounds,
+        };
+
+        object.canvas = Some(canvas);
+
+        self.add_damage(Some(damage.translate(origin)));
+    }
+
+    /// Visits every object in the scene from the root down, ending each object only once
+    /// all of its descendants have been visited.
+    pub fn visit<V>(&self, visitor: &mut V)
+    where
+        V: SceneVisitor + ?Sized,
+    {
+        let mut object_stack = Vec::<(usize, RenderObjectId, Offset)>::new();
+
+        // Subtrees of objects that the visitor skipped aren't visited at all
+        let mut skip_below = None::<usize>;
+
+        for object_id in self.tree.iter_down() {
+            let object = self.tree.get(object_id).unwrap();
+            let object_depth = self.tree.get_depth(object_id).unwrap();
+
+            if let Some(depth) = skip_below {
+                if object_depth > depth {
+                    continue;
+                }
+
+                skip_below = None;
+            }
+
+            // End any objects in the stack that are at the same level or deeper than this one
+            while let Some(object_id) = object_stack
+                .last()
+                .filter(|(depth, ..)| *depth >= object_depth)
+                .map(|(_, object_id, _)| *object_id)
+            {
+                visitor.end_object(object_id, self.tree.get(object_id).unwrap());
+
+                object_stack.pop();
+            }
+
+            let parent_origin = object_stack
+                .last()
+                .map(|(.., origin)| *origin)
+                .unwrap_or(Offset::ZERO);
+
+            match visitor.begin_object(object_id, object, parent_origin) {
+                Some(origin) => object_stack.push((object_depth, object_id, origin)),
+
+                None => skip_below = Some(object_depth),
+            }
+        }
+
+        // End any remaining objects in the stack
+        while let Some((_, object_id, _)) = object_stack.pop() {
+            visitor.end_object(object_id, self.tree.get(object_id).unwrap());
+        }
+    }
+}
+
+/// Draws the objects of a [`Scene`] as it's [visited](Scene::visit).
+pub trait SceneVisitor {
+    /// Called before any of the object's descendants are visited, with the origin of its
+    /// parent. Returns the origin of the object, or `None` to skip it and its descendants.
+    #[allow(unused_variables)]
+    fn begin_object(
+        &mut self,
+        render_object_id: RenderObjectId,
+        object: &SceneObject,
+        parent_origin: Offset,
+    ) -> Option<Offset> {
+        let origin = parent_origin + object.offset;
+
+        if let Some(canvas) = &object.canvas {
+            self.begin_canvas(origin, canvas);
+        }
+
+        Some(origin)
+    }
+
+    /// Called once all of the object's descendants have been visited.
+    #[allow(unused_variables)]
+    fn end_object(&mut self, render_object_id: RenderObjectId, object: &SceneObject) {
+        if let Some(canvas) = &object.canvas {
+            self.end_canvas(canvas);
+        }
+    }
+
+    /// Draws the canvas at the given origin. If the canvas has a tail, its layer is left
+    /// open so that the object's descendants are drawn within it.
+    fn begin_canvas(&mut self, origin: Offset, canvas: &Canvas);
+
+    /// Ends the layers that were left open by [`begin_canvas`](Self::begin_canvas).
+    fn end_canvas(&mut self, canvas: &Canvas);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        render::{canvas::Canvas, RenderObjectId},
+        unit::{Offset, Rect, Size},
+    };
+    use slotmap::KeyData;
+
+    use super::{Scene, SceneChange, SceneObject, SceneVisitor};
+
+    fn id(idx: u64) -> RenderObjectId {
+        RenderObjectId::from(KeyData::from_ffi(idx))
+    }
+
+    #[derive(Default)]
+    struct Recorder {
+        events: Vec<String>,
+
+        skip: Option<RenderObjectId>,
+    }
+
+    impl SceneVisitor for Recorder {
+        fn begin_object(
+            &mut self,
+            render_object_id: RenderObjectId,
+            object: &SceneObject,
+            parent_origin: Offset,
+        ) -> Option<Offset> {
+            if self.skip == Some(render_object_id) {
+                return None;
+            }
+
+            let origin = parent_origin + object.offset;
+
+            self.events
+                .push(format!("begin {:?} at {:?}", render_object_id, origin));
+
+            Some(origin)
+        }
+
+        fn end_object(&mut self, render_object_id: RenderObjectId, _: &SceneObject) {
+            self.events.push(format!("end {:?}", render_object_id));
+        }
+
+        fn begin_canvas(&mut self, _: Offset, _: &Canvas) {}
+
+        fn end_canvas(&mut self, _: &Canvas) {}
+    }
+
+    fn scene() -> Scene {
+        let mut scene = Scene::default();
+
+        for (parent, child, offset) in [
+            (None, 1, Offset::new(0.0, 0.0)),
+            (Some(1), 2, Offset::new(10.0, 0.0)),
+            (Some(2), 3, Offset::new(0.0, 5.0)),
+            (Some(1), 4, Offset::new(0.0, 20.0)),
+        ] {
+            scene.apply(SceneChange::Attach {
+                parent_render_object_id: parent.map(id),
+                render_object_id: id(child),
+            });
+
+            scene.apply(SceneChange::OffsetChanged {
+                render_object_id: id(child),
+                offset,
+            });
+        }
+
+        scene
+    }
+
+    #[test]
+    fn tracks_the_size_of_the_root() {
+        let mut scene = scene();
+
+        scene.apply(SceneChange::SizeChanged {
+            render_object_id: id(2),
+            size: Size::new(5.0, 5.0),
+        });
+
+        assert_eq!(scene.size(), None);
+
+        scene.apply(SceneChange::SizeChanged {
+            render_object_id: id(1),
+            size: Size::new(50.0, 50.0),
+        });
+
+        assert_eq!(scene.size(), Some(Size::new(50.0, 50.0)));
+    }
+
+    #[test]
+    fn visits_children_within_their_parents() {
+        let mut recorder = Recorder::default();
+
+        scene().visit(&mut recorder);
+
+        assert_eq!(
+            recorder.events,
+            [
+                format!("begin {:?} at {:?}", id(1), Offset::new(0.0, 0.0)),
+                format!("begin {:?} at {:?}", id(2), Offset::new(10.0, 0.0)),
+                format!("begin {:?} at {:?}", id(3), Offset::new(10.0, 5.0)),
+                format!("end {:?}", id(3)),
+                format!("end {:?}", id(2)),
+                format!("begin {:?} at {:?}", id(4), Offset::new(0.0, 20.0)),
+                format!("end {:?}", id(4)),
+                format!("end {:?}", id(1)),
+            ]
+        );
+    }
+
+    #[test]
+    fn skips_the_descendants_of_skipped_objects() {
+        let mut recorder = Recorder {
+            skip: Some(id(2)),
+            ..Recorder::default()
+        };
+
+        scene().visit(&mut recorder);
+
+        assert_eq!(
+            recorder.events,
+            [
+                format!("begin {:?} at {:?}", id(1), Offset::new(0.0, 0.0)),
+                format!("begin {:?} at {:?}", id(4), Offset::new(0.0, 20.0)),
+                format!("end {:?}", id(4)),
+                format!("end {:?}", id(1)),
+            ]
+        );
+    }
+
+    fn canvas(paint_bounds: Rect) -> Canvas {
+        Canvas {
+            paint_bounds,
+
+            ..Canvas::new(Size::new(10.0, 10.0))
+        }
+    }
+
+    #[test]
+    fn painting_damages_the_paint_bounds() {
+        let mut scene = scene();
+
+        assert_eq!(scene.take_damage(), None);
+
+        scene.apply(SceneChange::Paint {
+            render_object_id: id(3),
+            canvas: canvas(Rect::new(-2.0, -2.0, 14.0, 14.0)),
+        });
+
+        assert_eq!(scene.take_damage(), Some(Rect::new(8.0, 3.0, 14.0, 14.0)));
+        assert_eq!(scene.take_damage(), None);
+
+        // Both what was painted before and what's painted now are damaged
+        scene.apply(SceneChange::Paint {
+            render_object_id: id(3),
+            canvas: canvas(Rect::new(0.0, 0.0, 20.0, 10.0)),
+        });
+
+        assert_eq!(scene.take_damage(), Some(Rect::new(8.0, 3.0, 22.0, 14.0)));
+    }
+
+    #[test]
+    fn moving_an_object_damages_its_descendants() {
+        let mut scene = scene();
+
+        scene.apply(SceneChange::Paint {
+            render_object_id: id(3),
+            canvas: canvas(Rect::new(0.0, 0.0, 10.0, 10.0)),
+        });
+
+        scene.take_damage();
+
+        scene.apply(SceneChange::OffsetChanged {
+            render_object_id: id(2),
+            offset: Offset::new(20.0, 0.0),
+        });
+
+        assert_eq!(scene.take_damage(), Some(Rect::new(10.0, 5.0, 20.0, 10.0)));
+    }
+
+    #[test]
+    fn detaching_an_object_damages_its_descendants() {
+        let mut scene = scene();
+
+        scene.apply(SceneChange::Paint {
+            render_object_id: id(3),
+            canvas: canvas(Rect::new(0.0, 0.0, 10.0, 10.0)),
+        });
+
+        scene.take_damage();
+
+        scene.apply(SceneChange::Detach {
+            render_object_id: id(2),
+        });
+
+        assert_eq!(scene.take_damage(), Some(Rect::new(10.0, 5.0, 10.0, 10.0)));
+    }
+}
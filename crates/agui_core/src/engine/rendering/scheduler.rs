@@ -6,7 +6,7 @@ use std::{
 use crate::{
     element::{ContextRenderObject, RenderingTaskContext, RenderingTaskNotifyStrategy},
     render::RenderObjectId,
-    task::{error::TaskError, TaskHandle},
+    task::{clock::Clock, error::TaskError, TaskHandle},
 };
 
 pub struct CreateRenderingTask {
@@ -27,6 +27,13 @@ impl CreateRenderingTask {
         Self {
             ctx: self.ctx.with_notify_strategy(strategy),
 
+            func: self.func,
+        }
+    }
+    pub fn with_clock(self, clock: Clock) -> Self {
+        Self {
+            ctx: self.ctx.with_clock(clock),
+
             func: self.func,
         }
     }
@@ -5,7 +5,7 @@ use crate::{
         view::View,
     },
     render::{object::RenderObject, RenderObjectId},
-    unit::{Constraints, IntrinsicDimension, Size, TextStyle},
+    unit::{Constraints, IntrinsicDimension, Paragraph, ParagraphSpan, Size, TextStyle},
 };
 
 pub trait RenderingTreeCreateStrategy {
@@ -64,4 +64,40 @@ pub trait RenderingTreeTextLayoutStrategy {
         text: &str,
         constraints: Constraints,
     ) -> Size;
+
+    /// Computes an intrinsic dimension of `text`, where parts of it are laid out
+    /// differently according to `spans`.
+    ///
+    /// Returns `None` if the strategy can't lay out spans, in which case they're ignored
+    /// and the text is measured with [`compute_intrinsic_size`](Self::compute_intrinsic_size).
+    #[allow(unused_variables)]
+    fn compute_paragraph_intrinsic_size(
+        &self,
+        font_style: &TextStyle,
+        text: &str,
+        spans: &[ParagraphSpan],
+        dimension: IntrinsicDimension,
+        cross_axis: f32,
+    ) -> Option<f32> {
+        None
+    }
+
+    /// Lays out `text` into a paragraph that can be painted without being shaped again.
+    ///
+    /// The `spans` are sorted and don't overlap, and any text they don't cover is laid
+    /// out with `font_style`, which also determines how the lines are aligned.
+    ///
+    /// Returns `None` if the strategy can't produce paragraphs, in which case the spans
+    /// are ignored, and the text is measured with [`compute_size`](Self::compute_size)
+    /// and painted as a string.
+    #[allow(unused_variables)]
+    fn layout_paragraph(
+        &mut self,
+        font_style: &TextStyle,
+        text: &str,
+        spans: &[ParagraphSpan],
+        constraints: Constraints,
+    ) -> Option<Paragraph> {
+        None
+    }
 }
@@ -1,7 +1,8 @@
 use core::panic;
 use std::hash::BuildHasherDefault;
 
-use rustc_hash::{FxHashSet, FxHasher};
+use glam::{Mat4, Vec3};
+use rustc_hash::{FxHashMap, FxHashSet, FxHasher};
 use slotmap::{SecondaryMap, SparseSecondaryMap};
 
 use crate::{
@@ -17,10 +18,14 @@ use crate::{
         view::View,
         RenderViews,
     },
+    input::{EventResult, InputEvent, PointerEvent, PointerEventKind, PointerId},
     render::{
-        object::{RenderObject, RenderObjectLayoutContext},
+        object::{
+            RenderObject, RenderObjectContext, RenderObjectEventContext, RenderObjectLayoutContext,
+        },
         RenderObjectId,
     },
+    unit::{HitTestResult, Offset},
     util::tree::Tree,
 };
 
@@ -33,6 +38,12 @@ pub struct RenderingTree {
     forgotten_elements: SparseSecondaryMap<RenderObjectId, (), BuildHasherDefault<FxHasher>>,
 
     render_views: RenderViews,
+
+    focused: Option<RenderObjectId>,
+
+    /// The render objects that were hit when each pointer went down, along with their
+    /// global transforms. Events for that pointer are sent to them until it's released.
+    pointer_targets: FxHashMap<PointerId, Vec<(RenderObjectId, Mat4)>>,
 }
 
 impl RenderingTree {
@@ -40,6 +51,11 @@ impl RenderingTree {
         self.tree.get(id)
     }
 
+    /// Returns the render object created for the given element.
+    pub fn get_render_object_id(&self, element_id: ElementId) -> Option<RenderObjectId> {
+        self.element_mapping.get(element_id).copied()
+    }
+
     pub fn with<F, R>(&mut self, id: RenderObjectId, func: F) -> Option<R>
     where
         F: FnOnce(&mut Self, &mut RenderObject) -> R,
@@ -216,6 +232,13 @@ impl RenderingTree {
             self.render_views.remove_view(render_object_id);
         }
 
+        if self
+            .focused
+            .is_some_and(|render_object_id| !self.tree.contains(render_object_id))
+        {
+            self.focused = None;
+        }
+
         if failed_to_unmount.is_empty() {
             Ok(())
         } else {
@@ -380,6 +403,189 @@ impl RenderingTree {
         self.render_views.sync();
     }
 
+    /// Returns the render object that keyboard events are sent to.
+    pub fn focused(&self) -> Option<RenderObjectId> {
+        self.focused
+    }
+
+    /// Moves focus to the given render object, notifying the render objects that gained
+    /// and lost focus.
+    pub fn set_focus(&mut self, render_object_id: Option<RenderObjectId>) {
+        let previous = self.focused;
+
+        self.focused = render_object_id.filter(|id| self.tree.contains(*id));
+
+        self.notify_focus_changed(previous);
+    }
+
+    /// Finds the render objects at the given position, relative to the root of the tree.
+    pub fn hit_test(&self, position: Offset) -> HitTestResult {
+        let mut result = HitTestResult::new();
+
+        let Some(root_id) = self.tree.root() else {
+            return result;
+        };
+
+        let root = self
+            .tree
+            .get(root_id)
+            .expect("root render object missing during hit test");
+
+        result.push_offset(root.offset());
+
+        root.hit_test(
+            RenderObjectContext {
+                tree: &self.tree,
+
+                render_object_id: &root_id,
+            },
+            &mut result,
+            position - root.offset(),
+        );
+
+        result
+    }
+
+    /// Sends an input event to the render objects that should receive it.
+    ///
+    /// Pointer events are sent to every render object hit at the pointer's position,
+    /// starting with the deepest one. Once a pointer goes down, the rest of its events are
+    /// sent to the same render objects until it's released. Keyboard, text and IME events are
+    /// sent to the focused render object, then to each of its ancestors until one of them
+    /// handles it.
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn dispatch_event(&mut self, event: InputEvent) {
+        let previous_focus = self.focused;
+
+        match event {
+            InputEvent::Pointer(event) => self.dispatch_pointer_event(event),
+
+            InputEvent::Key(_) | InputEvent::Text(_) | InputEvent::Ime(_) => {
+                self.dispatch_focused_event(&event)
+            }
+
+            InputEvent::FocusChanged(_) => {
+                tracing::warn!("focus changes cannot be dispatched, use set_focus instead");
+            }
+        }
+
+        self.notify_focus_changed(previous_focus);
+    }
+
+    fn dispatch_pointer_event(&mut self, event: PointerEvent) {
+        let targets = match event.kind {
+            PointerEventKind::Down => {
+                let targets = self.pointer_targets_at(event.position);
+
+                self.pointer_targets.insert(event.pointer, targets.clone());
+
+                targets
+            }
+
+            PointerEventKind::Move => match self.pointer_targets.get(&event.pointer) {
+                Some(targets) => targets.clone(),
+                None => self.pointer_targets_at(event.position),
+            },
+
+            PointerEventKind::Up | PointerEventKind::Cancel => {
+                match self.pointer_targets.remove(&event.pointer) {
+                    Some(targets) => targets,
+                    None => self.pointer_targets_at(event.position),
+                }
+            }
+
+            PointerEventKind::Scroll { .. } => self.pointer_targets_at(event.position),
+        };
+
+        for (render_object_id, transform) in targets {
+            let Some(render_object) = self.tree.get(render_object_id) else {
+                continue;
+            };
+
+            let local_position = transform.inverse().transform_point3(Vec3::new(
+                event.position.x,
+                event.position.y,
+                0.0,
+            ));
+
+            render_object.handle_event(
+                &mut RenderObjectEventContext {
+                    render_object_id: &render_object_id,
+                    size: &render_object.size(),
+
+                    focused: &mut self.focused,
+                },
+                &InputEvent::Pointer(PointerEvent {
+                    local_position: Offset::new(local_position.x, local_position.y),
+
+                    ..event
+                }),
+            );
+        }
+    }
+
+    fn pointer_targets_at(&self, position: Offset) -> Vec<(RenderObjectId, Mat4)> {
+        self.hit_test(position)
+            .path()
+            .iter()
+            .map(|entry| (entry.render_object_id, entry.global_transform()))
+            .collect()
+    }
+
+    fn dispatch_focused_event(&mut self, event: &InputEvent) {
+        let mut target_id = self.focused;
+
+        while let Some(render_object_id) = target_id {
+            let Some(render_object) = self.tree.get(render_object_id) else {
+                break;
+            };
+
+            let result = render_object.handle_event(
+                &mut RenderObjectEventContext {
+                    render_object_id: &render_object_id,
+                    size: &render_object.size(),
+
+                    focused: &mut self.focused,
+                },
+                event,
+            );
+
+            if result == EventResult::Handled {
+                break;
+            }
+
+            target_id = self.tree.get_parent(render_object_id).copied();
+        }
+    }
+
+    fn notify_focus_changed(&mut self, previous: Option<RenderObjectId>) {
+        if self.focused == previous {
+            return;
+        }
+
+        for (render_object_id, has_focus) in [(previous, false), (self.focused, true)] {
+            let Some(render_object_id) = render_object_id else {
+                continue;
+            };
+
+            let Some(render_object) = self.tree.get(render_object_id) else {
+                continue;
+            };
+
+            let mut focused = self.focused;
+
+            render_object.handle_event(
+                &mut RenderObjectEventContext {
+                    render_object_id: &render_object_id,
+                    size: &render_object.size(),
+
+                    focused: &mut focused,
+                },
+                &InputEvent::FocusChanged(has_focus),
+            );
+        }
+    }
+
     pub(crate) fn get_view(&self, render_object_id: RenderObjectId) -> Option<&dyn View> {
         self.render_views.get(render_object_id)
     }
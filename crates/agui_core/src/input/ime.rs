@@ -0,0 +1,25 @@
+use std::ops::Range;
+
+/// An event from an input method editor (IME), which composes text over several key
+/// presses before committing it, such as when typing Chinese or Japanese.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ImeEvent {
+    /// The input method was enabled, and may start sending preedit text.
+    Enabled,
+
+    /// The text being composed changed. This replaces any previous preedit text, and an
+    /// empty string means composition was cancelled.
+    Preedit {
+        text: String,
+
+        /// The byte range of the preedit text that's selected, or where its caret is if
+        /// the range is empty. If `None`, no caret should be shown.
+        cursor: Option<Range<usize>>,
+    },
+
+    /// The composed text was finished, and should replace the preedit text.
+    Commit(String),
+
+    /// The input method was disabled, and any preedit text should be removed.
+    Disabled,
+}
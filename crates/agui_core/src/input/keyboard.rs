@@ -0,0 +1,121 @@
+bitflags::bitflags! {
+    /// The modifier keys held down when an event occurred.
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct Modifiers: u8 {
+        const SHIFT = 1 << 0;
+        const CONTROL = 1 << 1;
+        const ALT = 1 << 2;
+
+        /// The "windows" key on PC, or the "command" key on Mac.
+        const SUPER = 1 << 3;
+    }
+}
+
+/// A key on the keyboard, after the keyboard layout has been applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Key {
+    /// A key that doesn't produce a character.
+    Named(NamedKey),
+
+    /// A key that produces a character, such as a letter or digit.
+    Character(char),
+}
+
+impl From<NamedKey> for Key {
+    fn from(key: NamedKey) -> Self {
+        Self::Named(key)
+    }
+}
+
+impl From<char> for Key {
+    fn from(ch: char) -> Self {
+        Self::Character(ch)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NamedKey {
+    Enter,
+    Tab,
+    Space,
+    Backspace,
+    Delete,
+    Escape,
+    Insert,
+
+    ArrowLeft,
+    ArrowRight,
+    ArrowUp,
+    ArrowDown,
+
+    Home,
+    End,
+    PageUp,
+    PageDown,
+
+    Shift,
+    Control,
+    Alt,
+    Super,
+    CapsLock,
+
+    F1,
+    F2,
+    F3,
+    F4,
+    F5,
+    F6,
+    F7,
+    F8,
+    F9,
+    F10,
+    F11,
+    F12,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyState {
+    Pressed,
+    Released,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyEvent {
+    pub key: Key,
+    pub state: KeyState,
+
+    pub modifiers: Modifiers,
+
+    /// Whether this event was generated by the key being held down.
+    pub repeat: bool,
+}
+
+impl KeyEvent {
+    pub fn pressed(key: impl Into<Key>) -> Self {
+        Self {
+            key: key.into(),
+            state: KeyState::Pressed,
+
+            modifiers: Modifiers::empty(),
+
+            repeat: false,
+        }
+    }
+
+    pub fn released(key: impl Into<Key>) -> Self {
+        Self {
+            state: KeyState::Released,
+
+            ..Self::pressed(key)
+        }
+    }
+
+    pub fn with_modifiers(mut self, modifiers: Modifiers) -> Self {
+        self.modifiers = modifiers;
+        self
+    }
+
+    pub fn is_pressed(&self) -> bool {
+        self.state == KeyState::Pressed
+    }
+}
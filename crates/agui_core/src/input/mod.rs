@@ -0,0 +1,79 @@
+mod ime;
+mod keyboard;
+mod pointer;
+
+pub use ime::*;
+pub use keyboard::*;
+pub use pointer::*;
+
+/// An input event sent to the render objects in the tree.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputEvent {
+    /// A pointer was pressed, moved, released or scrolled.
+    ///
+    /// Sent to every render object that was hit at the pointer's position.
+    Pointer(PointerEvent),
+
+    /// A key was pressed or released.
+    ///
+    /// Sent to the focused render object, then to each of its ancestors until one of them
+    /// handles it.
+    Key(KeyEvent),
+
+    /// Text was entered, either by a key press or by an input method.
+    ///
+    /// Sent the same way as [`InputEvent::Key`].
+    Text(String),
+
+    /// An input method is composing text.
+    ///
+    /// Sent the same way as [`InputEvent::Key`].
+    Ime(ImeEvent),
+
+    /// The render object receiving the event gained or lost focus.
+    ///
+    /// This is sent by the rendering tree whenever the focus changes, and cannot be
+    /// dispatched.
+    FocusChanged(bool),
+}
+
+impl From<PointerEvent> for InputEvent {
+    fn from(event: PointerEvent) -> Self {
+        Self::Pointer(event)
+    }
+}
+
+impl From<KeyEvent> for InputEvent {
+    fn from(event: KeyEvent) -> Self {
+        Self::Key(event)
+    }
+}
+
+impl From<ImeEvent> for InputEvent {
+    fn from(event: ImeEvent) -> Self {
+        Self::Ime(event)
+    }
+}
+
+impl From<String> for InputEvent {
+    fn from(text: String) -> Self {
+        Self::Text(text)
+    }
+}
+
+impl From<&str> for InputEvent {
+    fn from(text: &str) -> Self {
+        Self::Text(text.to_owned())
+    }
+}
+
+/// Whether a render object handled an event sent to it.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventResult {
+    /// The event was handled, and should not be sent to any ancestors.
+    Handled,
+
+    /// The event was not handled.
+    #[default]
+    Ignored,
+}
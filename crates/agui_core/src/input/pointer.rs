@@ -0,0 +1,123 @@
+use std::time::Duration;
+
+use crate::{input::Modifiers, unit::Offset};
+
+/// Identifies a single pointer, such as a mouse or a finger on a touch screen.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PointerId(pub u64);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PointerEventKind {
+    /// The pointer made contact with the screen, or a mouse button was pressed.
+    Down,
+
+    /// The pointer moved. If it's not currently down, this is a hover.
+    Move,
+
+    /// The pointer stopped making contact with the screen, or a mouse button was released.
+    Up,
+
+    /// The pointer is no longer being tracked, and any gesture it was part of should be
+    /// abandoned.
+    Cancel,
+
+    /// The pointer scrolled by the given amount.
+    Scroll { delta: Offset },
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PointerButton {
+    /// The left mouse button, or contact with a touch screen.
+    #[default]
+    Primary,
+
+    /// The right mouse button.
+    Secondary,
+
+    /// The middle mouse button.
+    Middle,
+
+    Other(u16),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PointerEvent {
+    pub pointer: PointerId,
+
+    pub kind: PointerEventKind,
+    pub button: PointerButton,
+
+    /// The position of the pointer relative to the root of the tree.
+    pub position: Offset,
+
+    /// The position of the pointer in the coordinate space of the render object receiving
+    /// the event.
+    ///
+    /// This is filled in when the event is dispatched to each render object.
+    pub local_position: Offset,
+
+    /// The time at which the event occurred, relative to an arbitrary point in time.
+    ///
+    /// This is only meaningful when compared against the timestamps of other events.
+    pub timestamp: Duration,
+
+    pub modifiers: Modifiers,
+}
+
+impl PointerEvent {
+    pub fn new(kind: PointerEventKind, position: Offset) -> Self {
+        Self {
+            pointer: PointerId::default(),
+
+            kind,
+            button: PointerButton::default(),
+
+            position,
+            local_position: position,
+
+            timestamp: Duration::ZERO,
+
+            modifiers: Modifiers::empty(),
+        }
+    }
+
+    pub fn down(position: Offset) -> Self {
+        Self::new(PointerEventKind::Down, position)
+    }
+
+    pub fn moved(position: Offset) -> Self {
+        Self::new(PointerEventKind::Move, position)
+    }
+
+    pub fn up(position: Offset) -> Self {
+        Self::new(PointerEventKind::Up, position)
+    }
+
+    pub fn cancel(position: Offset) -> Self {
+        Self::new(PointerEventKind::Cancel, position)
+    }
+
+    pub fn scroll(position: Offset, delta: Offset) -> Self {
+        Self::new(PointerEventKind::Scroll { delta }, position)
+    }
+
+    pub fn with_pointer(mut self, pointer: PointerId) -> Self {
+        self.pointer = pointer;
+        self
+    }
+
+    pub fn with_button(mut self, button: PointerButton) -> Self {
+        self.button = button;
+        self
+    }
+
+    pub fn with_timestamp(mut self, timestamp: Duration) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    pub fn with_modifiers(mut self, modifiers: Modifiers) -> Self {
+        self.modifiers = modifiers;
+        self
+    }
+}
@@ -7,10 +7,13 @@
 
 // #![warn(missing_docs)]
 
+pub mod asset;
 pub mod callback;
+pub mod clipboard;
 pub mod element;
 pub mod engine;
 pub mod inheritance;
+pub mod input;
 pub mod query;
 pub mod reactivity;
 pub mod render;
@@ -1,9 +1,10 @@
 use std::borrow::Cow;
 
-use crate::unit::{Bounds, Rect, Shape, TextStyle, Texture};
+use crate::unit::{Bounds, BoxShadow, GlyphRun, Rect, Shape, TextStyle, Texture};
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 #[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CanvasCommand {
     Shape {
         paint_idx: usize,
@@ -12,6 +13,15 @@ pub enum CanvasCommand {
         shape: Shape,
     },
 
+    /// A shadow cast by a `shape` in the given `rect`. The shadow's offset, spread, and
+    /// blur are applied by the renderer, so it may paint outside of the `rect`.
+    Shadow {
+        rect: Rect,
+        shape: Shape,
+
+        shadow: BoxShadow,
+    },
+
     Texture {
         rect: Rect,
         shape: Shape,
@@ -28,4 +38,14 @@ pub enum CanvasCommand {
         text_style: TextStyle,
         text: Cow<'static, str>,
     },
+
+    /// Glyphs that were shaped during layout, positioned relative to the top left of the
+    /// `rect`. Renderers draw them as-is, without shaping the text again.
+    Glyphs {
+        paint_idx: usize,
+
+        rect: Rect,
+
+        runs: Vec<GlyphRun>,
+    },
 }
@@ -1,16 +1,21 @@
 use crate::{
     render::canvas::{command::CanvasCommand, paint::Paint},
-    unit::{Offset, Shape, Size},
+    unit::{LayerFilter, Offset, Rect, Shape, Size},
 };
 
 pub mod command;
 pub mod paint;
 pub mod painter;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Canvas {
     pub size: Size,
 
+    /// The area covered by the canvas' paint, relative to its origin. This may extend
+    /// beyond its size, such as when a shadow is cast.
+    pub paint_bounds: Rect,
+
     pub paints: Vec<Paint>,
 
     pub head: Vec<CanvasCommand>,
@@ -18,8 +23,25 @@ pub struct Canvas {
     pub tail: Option<Box<CanvasLayer>>,
 }
 
-#[derive(Debug, PartialEq)]
+impl Canvas {
+    /// Creates an empty canvas of the given size, whose paint bounds match its size.
+    pub fn new(size: Size) -> Self {
+        Self {
+            size,
+            paint_bounds: Rect::from(size),
+
+            paints: Vec::default(),
+
+            head: Vec::default(),
+            children: Vec::default(),
+            tail: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 #[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CanvasLayer {
     pub offset: Offset,
 
@@ -28,10 +50,59 @@ pub struct CanvasLayer {
     pub canvas: Canvas,
 }
 
-#[derive(Debug, PartialEq)]
+impl CanvasLayer {
+    pub(crate) fn new(rect: Rect, style: LayerStyle) -> Self {
+        Self {
+            offset: rect.into(),
+
+            style,
+
+            canvas: Canvas::new(rect.into()),
+        }
+    }
+
+    /// The area of the parent canvas that the layer may draw into. This is the layer's own
+    /// rect, grown by however far its filter spreads its contents.
+    ///
+    /// The layer's shape clips its contents before they're filtered, so a filtered layer
+    /// may draw outside of its shape.
+    pub fn bounds(&self) -> Rect {
+        let rect = self.offset & self.canvas.size;
+
+        let Some(filter) = self.style.filter else {
+            return rect;
+        };
+
+        let (extent_x, extent_y) = filter.extent();
+
+        Rect::new(
+            rect.left - extent_x,
+            rect.top - extent_y,
+            rect.width + extent_x * 2.0,
+            rect.height + extent_y * 2.0,
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 #[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LayerStyle {
+    /// The paint used to composite the layer. The alpha of its color is applied as the
+    /// opacity of the layer's contents, and its blend mode determines how they're blended
+    /// with what was drawn behind the layer.
     pub paint_idx: usize,
 
     pub shape: Shape,
+
+    /// A filter applied to the contents of the layer once they've been drawn and clipped
+    /// to its shape. The filtered contents may spread beyond the shape, up to the layer's
+    /// [bounds](CanvasLayer::bounds).
+    pub filter: Option<LayerFilter>,
+
+    /// A filter applied to everything drawn behind the layer, within its shape, before
+    /// the contents of the layer are drawn.
+    ///
+    /// Only the software backend filters what's behind a layer. Other backends ignore this.
+    pub backdrop_filter: Option<LayerFilter>,
 }
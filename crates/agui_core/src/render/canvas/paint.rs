@@ -1,12 +1,19 @@
 use std::marker::PhantomData;
 
-use crate::unit::{BlendMode, Color};
+use crate::unit::{BlendMode, Color, FillRule, Gradient, PaintingStyle};
 
 #[derive(Default, Debug, Clone, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Paint {
     pub anti_alias: bool,
     pub color: Color,
+
+    /// If set, the gradient is painted instead of the solid `color`.
+    pub gradient: Option<Gradient>,
+
     pub blend_mode: BlendMode,
+    pub style: PaintingStyle,
+    pub fill_rule: FillRule,
 }
 
 #[derive(Debug, PartialEq, Eq, Hash)]
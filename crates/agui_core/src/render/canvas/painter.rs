@@ -1,6 +1,6 @@
 use std::{borrow::Cow, marker::PhantomData};
 
-use lyon::path::Path;
+use lyon::{geom::point, path::Path};
 
 use crate::{
     render::canvas::{
@@ -8,7 +8,9 @@ use crate::{
         paint::{Brush, Paint},
         Canvas, CanvasLayer, LayerStyle,
     },
-    unit::{Rect, Shape, Size, TextStyle},
+    unit::{
+        Bounds, BoxShadow, GlyphRun, LayerFilter, Offset, Rect, Shape, Size, TextStyle, Texture,
+    },
 };
 
 pub trait CanvasPainterState {}
@@ -25,10 +27,17 @@ pub struct Tail<T> {
     phantom: PhantomData<T>,
 }
 
+/// The state of a layer created with [`CanvasPainter::nested_layer_at`]. Each of these
+/// layers has a state of its own, so their brushes can't be used in any other layer.
+pub struct Nested<'layer> {
+    phantom: PhantomData<fn(&'layer ()) -> &'layer ()>,
+}
+
 impl CanvasPainterState for () {}
 impl<T> CanvasPainterState for Head<T> where T: CanvasPainterState {}
 impl<T> CanvasPainterState for Layer<T> where T: CanvasPainterState {}
 impl<T> CanvasPainterState for Tail<T> where T: CanvasPainterState {}
+impl CanvasPainterState for Nested<'_> {}
 
 pub struct CanvasPainter<'paint, State = Head<()>>
 where
@@ -103,33 +112,69 @@ where
     {
         tracing::trace!("creating new layer");
 
-        self.canvas.children.push(CanvasLayer {
-            offset: rect.into(),
-
-            style: LayerStyle {
+        self.canvas.children.push(CanvasLayer::new(
+            rect,
+            LayerStyle {
                 paint_idx: brush.idx(),
 
                 shape,
+
+                filter: None,
+                backdrop_filter: None,
             },
+        ));
+
+        func(CanvasPainter {
+            phantom: PhantomData,
+
+            canvas: &mut self.canvas.children.last_mut().unwrap().canvas,
+        });
 
-            canvas: Canvas {
-                size: rect.into(),
+        CanvasPainter::<Tail<State>>::begin(self.canvas)
+    }
 
-                paints: Vec::default(),
+    /// Creates a layer with `shape` without consuming the painter, so that layers can be
+    /// nested to a depth that isn't known ahead of time, such as those of a document. It
+    /// will be the `rect` of the canvas.
+    pub fn nested_layer<F>(&mut self, brush: &Brush<State>, shape: Shape, func: F)
+    where
+        F: for<'layer> FnOnce(CanvasPainter<'layer, Head<Nested<'layer>>>),
+    {
+        let rect = self.canvas.size.into();
+
+        self.nested_layer_at(rect, brush, shape, func);
+    }
+
+    /// Creates a layer in the defined `rect` with `shape` without consuming the painter, so
+    /// that layers can be nested to a depth that isn't known ahead of time, such as those of
+    /// a document.
+    ///
+    /// The layer is painted with a painter in a state that's unique to it, however deeply
+    /// it's nested. Like any other layer, nothing else may be drawn to this painter once it
+    /// has been created, but further layers may be.
+    pub fn nested_layer_at<F>(&mut self, rect: Rect, brush: &Brush<State>, shape: Shape, func: F)
+    where
+        F: for<'layer> FnOnce(CanvasPainter<'layer, Head<Nested<'layer>>>),
+    {
+        tracing::trace!("creating new nested layer");
 
-                head: Vec::default(),
-                children: Vec::default(),
-                tail: None,
+        self.canvas.children.push(CanvasLayer::new(
+            rect,
+            LayerStyle {
+                paint_idx: brush.idx(),
+
+                shape,
+
+                filter: None,
+                backdrop_filter: None,
             },
-        });
+        ));
 
         func(CanvasPainter {
             phantom: PhantomData,
 
             canvas: &mut self.canvas.children.last_mut().unwrap().canvas,
         });
-
-        CanvasPainter::<Tail<State>>::begin(self.canvas)
     }
 
     /// Starts a layer with `shape` which child widgets will drawn to. It will be the `rect` of the canvas.
@@ -152,25 +197,99 @@ where
     ) -> CanvasPainter<'paint, Head<State>> {
         tracing::trace!("starting new layer");
 
-        self.canvas.tail = Some(Box::new(CanvasLayer {
-            offset: rect.into(),
+        self.start_layer_with_style(
+            rect,
+            LayerStyle {
+                paint_idx: brush.idx(),
+
+                shape,
+
+                filter: None,
+                backdrop_filter: None,
+            },
+        )
+    }
+
+    /// Starts a layer with `shape` which child widgets will drawn to, applying `filter` to
+    /// everything drawn within it. It will be the `rect` of the canvas.
+    pub fn start_filtered_layer(
+        self,
+        brush: &Brush<State>,
+        shape: Shape,
+        filter: LayerFilter,
+    ) -> CanvasPainter<'paint, Head<State>> {
+        let rect = self.canvas.size.into();
 
-            style: LayerStyle {
+        self.start_filtered_layer_at(rect, brush, shape, filter)
+    }
+
+    /// Starts a layer in the defined `rect` with `shape` which child widgets will drawn to,
+    /// applying `filter` to everything drawn within it.
+    pub fn start_filtered_layer_at(
+        self,
+        rect: Rect,
+        brush: &Brush<State>,
+        shape: Shape,
+        filter: LayerFilter,
+    ) -> CanvasPainter<'paint, Head<State>> {
+        tracing::trace!("starting new filtered layer");
+
+        self.start_layer_with_style(
+            rect,
+            LayerStyle {
                 paint_idx: brush.idx(),
 
                 shape,
+
+                filter: Some(filter),
+                backdrop_filter: None,
             },
+        )
+    }
+
+    /// Starts a layer with `shape` which child widgets will drawn to, applying `filter` to
+    /// everything that was drawn behind it. It will be the `rect` of the canvas.
+    pub fn start_backdrop_layer(
+        self,
+        brush: &Brush<State>,
+        shape: Shape,
+        filter: LayerFilter,
+    ) -> CanvasPainter<'paint, Head<State>> {
+        let rect = self.canvas.size.into();
+
+        self.start_backdrop_layer_at(rect, brush, shape, filter)
+    }
+
+    /// Starts a layer in the defined `rect` with `shape` which child widgets will drawn to,
+    /// applying `filter` to everything that was drawn behind it.
+    pub fn start_backdrop_layer_at(
+        self,
+        rect: Rect,
+        brush: &Brush<State>,
+        shape: Shape,
+        filter: LayerFilter,
+    ) -> CanvasPainter<'paint, Head<State>> {
+        tracing::trace!("starting new backdrop layer");
 
-            canvas: Canvas {
-                size: rect.into(),
+        self.start_layer_with_style(
+            rect,
+            LayerStyle {
+                paint_idx: brush.idx(),
 
-                paints: Vec::default(),
+                shape,
 
-                head: Vec::default(),
-                children: Vec::default(),
-                tail: None,
+                filter: None,
+                backdrop_filter: Some(filter),
             },
-        }));
+        )
+    }
+
+    fn start_layer_with_style(
+        self,
+        rect: Rect,
+        style: LayerStyle,
+    ) -> CanvasPainter<'paint, Head<State>> {
+        self.canvas.tail = Some(Box::new(CanvasLayer::new(rect, style)));
 
         CanvasPainter::<Head<State>>::begin(&mut self.canvas.tail.as_mut().unwrap().canvas)
     }
@@ -258,6 +377,86 @@ where
         });
     }
 
+    /// Draws the shadow cast by `shape`. It will be the `rect` of the canvas.
+    pub fn draw_shadow(&mut self, shape: Shape, shadow: BoxShadow) {
+        self.draw_shadow_at(self.canvas.size.into(), shape, shadow);
+    }
+
+    /// Draws the shadow cast by `shape` in the defined `rect`. The shadow may extend
+    /// beyond the `rect` depending on its offset, spread, and blur.
+    pub fn draw_shadow_at(&mut self, rect: Rect, shape: Shape, shadow: BoxShadow) {
+        tracing::trace!("drawing shadow");
+
+        self.push_command(CanvasCommand::Shadow {
+            rect,
+            shape,
+
+            shadow,
+        });
+    }
+
+    /// Draws a straight line from `from` to `to`. The brush should use a stroke style.
+    pub fn draw_line(&mut self, brush: &Brush<Head<State>>, from: Offset, to: Offset) {
+        self.draw_polyline(brush, &[from, to]);
+    }
+
+    /// Draws a series of connected line segments through `points`. The brush should
+    /// use a stroke style.
+    pub fn draw_polyline(&mut self, brush: &Brush<Head<State>>, points: &[Offset]) {
+        tracing::trace!("drawing polyline");
+
+        if points.len() < 2 {
+            return;
+        }
+
+        let mut builder = Path::builder();
+
+        builder.begin(point(points[0].x, points[0].y));
+
+        for offset in &points[1..] {
+            builder.line_to(point(offset.x, offset.y));
+        }
+
+        builder.end(false);
+
+        self.push_command(CanvasCommand::Shape {
+            paint_idx: brush.idx(),
+
+            rect: self.canvas.size.into(),
+            shape: Shape::Path(builder.build()),
+        });
+    }
+
+    /// Draws a texture. It will be the `rect` of the canvas.
+    ///
+    /// The `tex_bounds` select the region of the texture to draw, as fractions of its
+    /// width and height.
+    pub fn draw_texture(&mut self, shape: Shape, texture: Texture, tex_bounds: Bounds) {
+        self.draw_texture_at(self.canvas.size.into(), shape, texture, tex_bounds);
+    }
+
+    /// Draws a texture in the defined `rect`, clipped to the `shape`.
+    ///
+    /// The `tex_bounds` select the region of the texture to draw, as fractions of its
+    /// width and height.
+    pub fn draw_texture_at(
+        &mut self,
+        rect: Rect,
+        shape: Shape,
+        texture: Texture,
+        tex_bounds: Bounds,
+    ) {
+        tracing::trace!("drawing texture");
+
+        self.push_command(CanvasCommand::Texture {
+            rect,
+            shape,
+
+            texture,
+            tex_bounds,
+        });
+    }
+
     /// Draws text on the canvas. It will be wrapped to the `rect` of the canvas.
     pub fn draw_text<T>(&mut self, brush: &Brush<Head<State>>, text_style: TextStyle, text: T)
     where
@@ -287,10 +486,166 @@ where
             text: text.into(),
         });
     }
+
+    /// Draws glyph runs that were laid out ahead of time, such as those of a
+    /// [`Paragraph`](crate::unit::Paragraph), positioned relative to the top left of the
+    /// canvas.
+    pub fn draw_glyphs(&mut self, brush: &Brush<Head<State>>, runs: Vec<GlyphRun>) {
+        self.draw_glyphs_at(self.canvas.size.into(), brush, runs);
+    }
+
+    /// Draws glyph runs that were laid out ahead of time, positioned relative to the top
+    /// left of the `rect`.
+    pub fn draw_glyphs_at(&mut self, rect: Rect, brush: &Brush<Head<State>>, runs: Vec<GlyphRun>) {
+        tracing::trace!("drawing glyphs");
+
+        self.push_command(CanvasCommand::Glyphs {
+            paint_idx: brush.idx(),
+
+            rect,
+
+            runs,
+        });
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::{
+        render::canvas::{command::CanvasCommand, paint::Paint, Canvas},
+        unit::{
+            Font, GlyphRun, Offset, PaintingStyle, PositionedGlyph, Rect, Shape, Size, StrokeStyle,
+        },
+    };
+
+    use super::{CanvasPainter, CanvasPainterState, Head};
+
+    fn canvas() -> Canvas {
+        Canvas::new(Size::new(100.0, 100.0))
+    }
+
     #[test]
     pub fn canvas_style() {}
+
+    #[test]
+    pub fn draw_line() {
+        let mut canvas = canvas();
+
+        {
+            let mut painter = CanvasPainter::<Head<()>>::begin(&mut canvas);
+
+            let brush = painter.add_paint(Paint {
+                style: PaintingStyle::Stroke(StrokeStyle::new(2.0)),
+                ..Paint::default()
+            });
+
+            painter.draw_line(&brush, Offset::new(0.0, 0.0), Offset::new(50.0, 25.0));
+        }
+
+        assert_eq!(canvas.head.len(), 1, "should have drawn one command");
+
+        let CanvasCommand::Shape {
+            shape: Shape::Path(path),
+            ..
+        } = &canvas.head[0]
+        else {
+            panic!("expected a path shape");
+        };
+
+        assert_eq!(path.iter().count(), 3, "should be a begin, line, and end");
+    }
+
+    #[test]
+    pub fn draw_polyline_ignores_single_point() {
+        let mut canvas = canvas();
+
+        {
+            let mut painter = CanvasPainter::<Head<()>>::begin(&mut canvas);
+
+            let brush = painter.add_paint(Paint::default());
+
+            painter.draw_polyline(&brush, &[Offset::new(10.0, 10.0)]);
+        }
+
+        assert!(canvas.head.is_empty(), "should not have drawn anything");
+    }
+
+    #[test]
+    pub fn draw_glyphs() {
+        let mut canvas = canvas();
+
+        let runs = vec![GlyphRun {
+            font: Font::from_family("Test"),
+            font_size: 16.0,
+
+            range: 0..1,
+            rtl: false,
+
+            glyphs: vec![PositionedGlyph {
+                id: 1,
+                cluster: 0,
+
+                x: 0.0,
+                y: 12.0,
+
+                advance: 8.0,
+            }],
+        }];
+
+        {
+            let mut painter = CanvasPainter::<Head<()>>::begin(&mut canvas);
+
+            let brush = painter.add_paint(Paint::default());
+
+            painter.draw_glyphs(&brush, runs.clone());
+        }
+
+        assert_eq!(
+            canvas.head,
+            vec![CanvasCommand::Glyphs {
+                paint_idx: 0,
+                rect: Rect::new(0.0, 0.0, 100.0, 100.0),
+                runs,
+            }],
+            "should have drawn the glyph runs over the whole canvas"
+        );
+    }
+
+    #[test]
+    pub fn nested_layers() {
+        fn paint_nested<S>(canvas: &mut CanvasPainter<Head<S>>, depth: usize)
+        where
+            S: CanvasPainterState,
+        {
+            let brush = canvas.add_paint(Paint::default());
+
+            canvas.draw_rect(&brush);
+
+            if depth == 0 {
+                return;
+            }
+
+            // Siblings may be added after a nested layer
+            for _ in 0..2 {
+                canvas.nested_layer(&brush, Shape::Rect, |mut canvas| {
+                    paint_nested(&mut canvas, depth - 1);
+                });
+            }
+        }
+
+        let mut canvas = canvas();
+
+        paint_nested(&mut CanvasPainter::<Head<()>>::begin(&mut canvas), 3);
+
+        let mut layer = &canvas;
+
+        for _ in 0..3 {
+            assert_eq!(layer.head.len(), 1, "should have drawn before the layers");
+            assert_eq!(layer.children.len(), 2, "should have added both layers");
+
+            layer = &layer.children[1].canvas;
+        }
+
+        assert!(layer.children.is_empty(), "should have stopped nesting");
+    }
 }
@@ -0,0 +1,39 @@
+use crate::{
+    element::ContextRenderObject,
+    engine::rendering::RenderingTree,
+    render::{object::context::IterChildrenLayout, RenderObjectId},
+};
+
+pub struct RenderObjectBaselineContext<'ctx> {
+    pub(crate) tree: &'ctx RenderingTree,
+
+    pub render_object_id: &'ctx RenderObjectId,
+
+    pub children: &'ctx [RenderObjectId],
+}
+
+impl ContextRenderObject for RenderObjectBaselineContext<'_> {
+    fn render_object_id(&self) -> RenderObjectId {
+        *self.render_object_id
+    }
+}
+
+impl<'ctx> RenderObjectBaselineContext<'ctx> {
+    pub fn has_children(&self) -> bool {
+        !self.children.is_empty()
+    }
+
+    pub fn child_count(&self) -> usize {
+        self.children.len()
+    }
+
+    pub fn iter_children(&self) -> IterChildrenLayout {
+        IterChildrenLayout {
+            tree: self.tree,
+
+            index: 0,
+
+            children: self.children,
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use crate::{element::ContextRenderObject, render::RenderObjectId, unit::Size};
+
+pub struct RenderObjectEventContext<'ctx> {
+    pub render_object_id: &'ctx RenderObjectId,
+
+    pub size: &'ctx Size,
+
+    pub(crate) focused: &'ctx mut Option<RenderObjectId>,
+}
+
+impl ContextRenderObject for RenderObjectEventContext<'_> {
+    fn render_object_id(&self) -> RenderObjectId {
+        *self.render_object_id
+    }
+}
+
+impl RenderObjectEventContext<'_> {
+    pub fn size(&self) -> Size {
+        *self.size
+    }
+
+    /// Whether this render object currently receives keyboard events.
+    pub fn has_focus(&self) -> bool {
+        *self.focused == Some(*self.render_object_id)
+    }
+
+    /// Makes this render object the target of keyboard events.
+    pub fn request_focus(&mut self) {
+        *self.focused = Some(*self.render_object_id);
+    }
+
+    /// Removes focus from this render object, if it has it.
+    pub fn unfocus(&mut self) {
+        if self.has_focus() {
+            *self.focused = None;
+        }
+    }
+}
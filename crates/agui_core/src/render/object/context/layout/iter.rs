@@ -3,10 +3,13 @@ use crate::{
         context::RenderingLayoutContext, strategies::RenderingTreeLayoutStrategy, RenderingTree,
     },
     render::{
-        object::{RenderObjectIntrinsicSizeContext, RenderObjectLayoutContext},
+        object::{
+            RenderObjectBaselineContext, RenderObjectIntrinsicSizeContext,
+            RenderObjectLayoutContext,
+        },
         RenderObjectId,
     },
-    unit::{Constraints, IntrinsicDimension, Offset, Size},
+    unit::{Constraints, IntrinsicDimension, Offset, Size, TextBaseline},
 };
 
 pub struct IterChildrenLayout<'ctx> {
@@ -81,6 +84,38 @@ impl ChildLayout<'_> {
             cross_extent,
         )
     }
+
+    pub fn offset(&self) -> Offset {
+        let render_object_id = self.render_object_id();
+
+        self.tree
+            .get(render_object_id)
+            .expect("child render object missing during layout")
+            .offset()
+    }
+
+    /// Returns the distance from the top of the child to the given baseline, or `None` if
+    /// it doesn't have one. The child must have been laid out.
+    pub fn compute_distance_to_baseline(&self, baseline: TextBaseline) -> Option<f32> {
+        let render_object_id = self.render_object_id();
+
+        let render_object_node = self
+            .tree
+            .as_ref()
+            .get_node(render_object_id)
+            .expect("child render object missing during layout");
+
+        render_object_node.borrow().compute_distance_to_baseline(
+            &mut RenderObjectBaselineContext {
+                tree: self.tree,
+
+                render_object_id: &render_object_id,
+
+                children: render_object_node.children(),
+            },
+            baseline,
+        )
+    }
 }
 
 pub struct IterChildrenLayoutMut<'ctx> {
@@ -140,26 +175,31 @@ impl ChildLayoutMut<'_> {
         self.children[self.index]
     }
 
-    pub fn compute_intrinsic_size(&self, dimension: IntrinsicDimension, cross_extent: f32) -> f32 {
-        let render_object_id = self.render_object_id();
+    /// Returns a view of the child that can't lay it out, for the queries the two share.
+    fn as_child_layout(&self) -> ChildLayout<'_> {
+        ChildLayout {
+            tree: self.tree,
 
-        let render_object_node = self
-            .tree
-            .as_ref()
-            .get_node(render_object_id)
-            .expect("child render object missing during layout");
+            index: self.index,
 
-        render_object_node.borrow().intrinsic_size(
-            &mut RenderObjectIntrinsicSizeContext {
-                tree: self.tree,
+            children: self.children,
+        }
+    }
 
-                render_object_id: &render_object_id,
+    pub fn compute_intrinsic_size(&self, dimension: IntrinsicDimension, cross_extent: f32) -> f32 {
+        self.as_child_layout()
+            .compute_intrinsic_size(dimension, cross_extent)
+    }
 
-                children: render_object_node.children(),
-            },
-            dimension,
-            cross_extent,
-        )
+    pub fn offset(&self) -> Offset {
+        self.as_child_layout().offset()
+    }
+
+    /// Returns the distance from the top of the child to the given baseline, or `None` if
+    /// it doesn't have one. The child must have been laid out.
+    pub fn compute_distance_to_baseline(&self, baseline: TextBaseline) -> Option<f32> {
+        self.as_child_layout()
+            .compute_distance_to_baseline(baseline)
     }
 
     /// Computes the layout of the child render object without returning its size.
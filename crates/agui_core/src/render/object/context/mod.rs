@@ -4,12 +4,16 @@ use crate::{
     util::tree::Tree,
 };
 
+mod baseline;
+mod event;
 mod hit_test;
 mod intrinsic_size;
 mod layout;
 mod mount;
 mod unmount;
 
+pub use baseline::*;
+pub use event::*;
 pub use hit_test::*;
 pub use intrinsic_size::*;
 pub use layout::*;
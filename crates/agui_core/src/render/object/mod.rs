@@ -1,7 +1,11 @@
 use crate::{
     engine::rendering::context::RenderingLayoutContext,
+    input::{EventResult, InputEvent},
     render::RenderObjectId,
-    unit::{AsAny, Constraints, HitTest, HitTestResult, IntrinsicDimension, Offset, Rect, Size},
+    unit::{
+        AsAny, Constraints, HitTest, HitTestResult, IntrinsicDimension, Offset, Rect, Size,
+        TextBaseline,
+    },
 };
 
 use super::canvas::{
@@ -109,6 +113,16 @@ impl RenderObject {
             .intrinsic_size(ctx, dimension, cross_extent)
     }
 
+    #[tracing::instrument(level = "trace", skip(self, ctx))]
+    pub fn compute_distance_to_baseline<'ctx>(
+        &self,
+        ctx: &mut RenderObjectBaselineContext<'ctx>,
+        baseline: TextBaseline,
+    ) -> Option<f32> {
+        self.render_object
+            .compute_distance_to_baseline(ctx, baseline)
+    }
+
     #[tracing::instrument(level = "trace", skip(self, ctx))]
     pub fn layout<'ctx>(
         &mut self,
@@ -253,6 +267,15 @@ impl RenderObject {
         hit
     }
 
+    #[tracing::instrument(level = "trace", skip(self, ctx))]
+    pub fn handle_event(
+        &self,
+        ctx: &mut RenderObjectEventContext,
+        event: &InputEvent,
+    ) -> EventResult {
+        self.render_object.handle_event(ctx, event)
+    }
+
     pub fn does_paint(&self) -> bool {
         self.render_object.does_paint()
     }
@@ -264,17 +287,22 @@ impl RenderObject {
     #[tracing::instrument(level = "trace", skip(self))]
     pub fn paint(&self) -> Canvas {
         let mut canvas = Canvas {
-            size: self.size(),
+            paint_bounds: self.paint_bounds(),
 
-            paints: Vec::default(),
-
-            head: Vec::default(),
-            children: Vec::default(),
-            tail: None,
+            ..Canvas::new(self.size())
         };
 
         self.render_object.paint(CanvasPainter::begin(&mut canvas));
 
+        // Filtered layers may spread beyond the bounds the render object reported
+        canvas.paint_bounds = canvas
+            .children
+            .iter()
+            .chain(canvas.tail.as_deref())
+            .fold(canvas.paint_bounds, |bounds, layer| {
+                bounds.union(layer.bounds())
+            });
+
         canvas
     }
 }
@@ -363,14 +391,38 @@ pub trait RenderObjectImpl: AsAny + Send {
         }
     }
 
+    /// Returns the distance from the top of this render object to the given baseline of
+    /// its first line of text, or `None` if it doesn't contain any text. This is only
+    /// called after the render object has been laid out.
+    ///
+    /// By default, a render object with a single child takes the baseline of its child,
+    /// moved by the child's offset.
+    fn compute_distance_to_baseline<'ctx>(
+        &self,
+        ctx: &mut RenderObjectBaselineContext<'ctx>,
+        baseline: TextBaseline,
+    ) -> Option<f32> {
+        if ctx.child_count() != 1 {
+            return None;
+        }
+
+        let child = ctx.iter_children().next()?;
+
+        child
+            .compute_distance_to_baseline(baseline)
+            .map(|distance| distance + child.offset().y)
+    }
+
     fn hit_test<'ctx>(
         &self,
         ctx: &'ctx mut RenderObjectHitTestContext<'ctx>,
         position: Offset,
     ) -> HitTest {
         if ctx.size.contains(position) {
-            while let Some(mut child) = ctx.iter_children().next_back() {
-                let offset = position - child.offset();
+            let mut children = ctx.iter_children();
+
+            while let Some(mut child) = children.next_back() {
+                let offset = child.offset();
 
                 if child.hit_test_with_offset(offset, position) == HitTest::Absorb {
                     return HitTest::Absorb;
@@ -381,6 +433,20 @@ pub trait RenderObjectImpl: AsAny + Send {
         HitTest::Pass
     }
 
+    /// Handles an input event sent to this render object.
+    ///
+    /// Pointer events are sent to every render object that absorbed the hit test at the
+    /// pointer's position, and keyboard events are sent to the focused render object. See
+    /// [`InputEvent`] for details.
+    #[allow(unused_variables)]
+    fn handle_event<'ctx>(
+        &self,
+        ctx: &mut RenderObjectEventContext<'ctx>,
+        event: &InputEvent,
+    ) -> EventResult {
+        EventResult::Ignored
+    }
+
     /// Whether this render object is capable of painting.
     ///
     /// Returning `false` causes this render object to be skipped during painting,
@@ -0,0 +1,201 @@
+use std::{
+    collections::VecDeque,
+    future::Future,
+    num::NonZeroUsize,
+    pin::pin,
+    sync::OnceLock,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures_util::{task::noop_waker_ref, FutureExt};
+use parking_lot::{Condvar, Mutex};
+
+/// How long a thread waits for more work before it exits.
+const KEEP_ALIVE: Duration = Duration::from_secs(10);
+
+/// Runs `func` on a shared pool of threads, returning a future that resolves to its result.
+///
+/// This should be used for work that would otherwise block the executor, such as file
+/// access or decoding. The pool runs at most one thread per core, queueing any work beyond
+/// that until a thread frees up. If `func` panics, the panic is resumed when the future is
+/// awaited.
+pub fn spawn_blocking<T, F>(func: F) -> impl Future<Output = T> + Send + 'static
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    BlockingPool::global().spawn(func)
+}
+
+type Job = Box<dyn FnOnce() + Send>;
+
+struct BlockingPool {
+    max_threads: usize,
+
+    state: Mutex<PoolState>,
+    work_available: Condvar,
+}
+
+#[derive(Default)]
+struct PoolState {
+    queue: VecDeque<Job>,
+
+    threads: usize,
+    idle_threads: usize,
+}
+
+impl BlockingPool {
+    fn new(max_threads: usize) -> Self {
+        Self {
+            max_threads: max_threads.max(1),
+
+            state: Mutex::default(),
+            work_available: Condvar::new(),
+        }
+    }
+
+    fn global() -> &'static BlockingPool {
+        static GLOBAL: OnceLock<BlockingPool> = OnceLock::new();
+
+        GLOBAL.get_or_init(|| {
+            BlockingPool::new(
+                std::thread::available_parallelism()
+                    .map(NonZeroUsize::get)
+                    .unwrap_or(4),
+            )
+        })
+    }
+
+    fn spawn<T, F>(&'static self, func: F) -> impl Future<Output = T> + Send + 'static
+    where
+        T: Send + 'static,
+        F: FnOnce() -> T + Send + 'static,
+    {
+        let (remote, handle) = async move { func() }.remote_handle();
+
+        self.execute(Box::new(move || {
+            // The future completes on its first poll, so there's no need for a real executor
+            let mut remote = pin!(remote);
+
+            let poll = remote
+                .as_mut()
+                .poll(&mut Context::from_waker(noop_waker_ref()));
+
+            debug_assert!(
+                matches!(poll, Poll::Ready(())),
+                "blocking task did not complete"
+            );
+        }));
+
+        handle
+    }
+
+    fn execute(&'static self, job: Job) {
+        let mut state = self.state.lock();
+
+        state.queue.push_back(job);
+
+        // Only start another thread if the idle ones can't take on everything queued
+        if state.queue.len() > state.idle_threads && state.threads < self.max_threads {
+            state.threads += 1;
+
+            std::thread::Builder::new()
+                .name("agui-blocking".into())
+                .spawn(move || self.run_worker())
+                .expect("failed to spawn blocking thread");
+        } else {
+            self.work_available.notify_one();
+        }
+    }
+
+    fn run_worker(&self) {
+        let mut state = self.state.lock();
+
+        loop {
+            if let Some(job) = state.queue.pop_front() {
+                // Panics are caught by the job's remote handle, so the thread survives them
+                drop(state);
+
+                job();
+
+                state = self.state.lock();
+
+                continue;
+            }
+
+            state.idle_threads += 1;
+
+            let timed_out = self
+                .work_available
+                .wait_for(&mut state, KEEP_ALIVE)
+                .timed_out();
+
+            state.idle_threads -= 1;
+
+            if timed_out && state.queue.is_empty() {
+                state.threads -= 1;
+
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    use futures_util::future::join_all;
+
+    use super::{spawn_blocking, BlockingPool};
+
+    #[test]
+    fn resolves_to_result() {
+        let result = futures_executor::block_on(spawn_blocking(|| 40 + 2));
+
+        assert_eq!(result, 42);
+    }
+
+    #[test]
+    fn runs_at_most_max_threads_at_once() {
+        let pool = Box::leak(Box::new(BlockingPool::new(2)));
+
+        let running = Arc::new(AtomicUsize::new(0));
+        let most_running = Arc::new(AtomicUsize::new(0));
+
+        let tasks = (0..8)
+            .map(|_| {
+                let running = Arc::clone(&running);
+                let most_running = Arc::clone(&most_running);
+
+                pool.spawn(move || {
+                    let now_running = running.fetch_add(1, Ordering::SeqCst) + 1;
+
+                    most_running.fetch_max(now_running, Ordering::SeqCst);
+
+                    std::thread::sleep(Duration::from_millis(10));
+
+                    running.fetch_sub(1, Ordering::SeqCst);
+                })
+            })
+            .collect::<Vec<_>>();
+
+        futures_executor::block_on(join_all(tasks));
+
+        assert_eq!(most_running.load(Ordering::SeqCst), 2);
+        assert!(pool.state.lock().threads <= 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "oops")]
+    fn resumes_panics_when_awaited() {
+        futures_executor::block_on(spawn_blocking(|| panic!("oops")));
+    }
+}
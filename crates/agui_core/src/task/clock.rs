@@ -0,0 +1,300 @@
+use std::{
+    collections::BTreeMap,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll, Waker},
+    time::{Duration, Instant},
+};
+
+use futures_timer::Delay;
+use parking_lot::Mutex;
+
+/// The source of time for the async tasks spawned by an executor.
+///
+/// By default this follows the system clock. An executor may instead be given a clock
+/// created from a [`ManualClock`], which only moves forward when it is advanced. This lets
+/// tests step through timers without waiting on them.
+#[derive(Clone)]
+pub struct Clock {
+    inner: ClockInner,
+}
+
+#[derive(Clone)]
+enum ClockInner {
+    System(Instant),
+    Manual(Arc<Mutex<Timers>>),
+}
+
+impl Default for Clock {
+    fn default() -> Self {
+        Self::system()
+    }
+}
+
+impl Clock {
+    /// Creates a clock that follows real time, starting from now.
+    pub fn system() -> Self {
+        Self {
+            inner: ClockInner::System(Instant::now()),
+        }
+    }
+
+    /// How much time has passed since the clock was created.
+    pub fn now(&self) -> Duration {
+        match &self.inner {
+            ClockInner::System(start) => start.elapsed(),
+            ClockInner::Manual(timers) => timers.lock().now,
+        }
+    }
+
+    /// Returns a future that resolves once `duration` has passed on this clock.
+    pub fn sleep(&self, duration: Duration) -> Sleep {
+        match &self.inner {
+            ClockInner::System(_) => Sleep {
+                inner: SleepInner::System(Delay::new(duration)),
+            },
+
+            ClockInner::Manual(timers) => Sleep {
+                inner: SleepInner::Manual {
+                    deadline: timers.lock().now + duration,
+                    timers: Arc::clone(timers),
+                    key: None,
+                },
+            },
+        }
+    }
+}
+
+impl std::fmt::Debug for Clock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Clock")
+            .field(
+                "kind",
+                match self.inner {
+                    ClockInner::System(_) => &"system",
+                    ClockInner::Manual(_) => &"manual",
+                },
+            )
+            .field("now", &self.now())
+            .finish()
+    }
+}
+
+/// A clock that only moves forward when it is told to.
+///
+/// Sleeps created from [`ManualClock::clock`] resolve when the clock is advanced past their
+/// deadline, waking the tasks that are waiting on them.
+#[derive(Clone, Default)]
+pub struct ManualClock {
+    timers: Arc<Mutex<Timers>>,
+}
+
+impl ManualClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a [`Clock`] that follows this one.
+    pub fn clock(&self) -> Clock {
+        Clock {
+            inner: ClockInner::Manual(Arc::clone(&self.timers)),
+        }
+    }
+
+    /// How far the clock has been advanced.
+    pub fn now(&self) -> Duration {
+        self.timers.lock().now
+    }
+
+    /// The earliest deadline of any pending sleep, if there is one.
+    ///
+    /// Sleeps are only registered once they've been polled, so a task that hasn't run yet
+    /// may be waiting to create an earlier one.
+    pub fn next_deadline(&self) -> Option<Duration> {
+        self.timers
+            .lock()
+            .pending
+            .first_key_value()
+            .map(|(&(deadline, _), _)| deadline)
+    }
+
+    /// Moves the clock forward by `duration`, waking every sleep whose deadline has passed.
+    pub fn advance(&self, duration: Duration) {
+        let now = self.now() + duration;
+
+        self.advance_to(now);
+    }
+
+    /// Moves the clock forward to `now`, waking every sleep whose deadline has passed.
+    ///
+    /// The clock never moves backwards, so this does nothing if `now` has already passed.
+    pub fn advance_to(&self, now: Duration) {
+        let expired = {
+            let mut timers = self.timers.lock();
+
+            if now <= timers.now {
+                return;
+            }
+
+            timers.now = now;
+
+            // Everything after the split point is still pending
+            let pending = timers.pending.split_off(&(now, u64::MAX));
+
+            std::mem::replace(&mut timers.pending, pending)
+        };
+
+        // Wake outside of the lock, since a waker may poll the sleep immediately
+        for waker in expired.into_values() {
+            waker.wake();
+        }
+    }
+}
+
+impl std::fmt::Debug for ManualClock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let timers = self.timers.lock();
+
+        f.debug_struct("ManualClock")
+            .field("now", &timers.now)
+            .field("pending", &timers.pending.len())
+            .finish()
+    }
+}
+
+#[derive(Default)]
+struct Timers {
+    now: Duration,
+
+    next_key: u64,
+    pending: BTreeMap<(Duration, u64), Waker>,
+}
+
+/// A future returned by [`Clock::sleep`].
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Sleep {
+    inner: SleepInner,
+}
+
+enum SleepInner {
+    System(Delay),
+
+    Manual {
+        timers: Arc<Mutex<Timers>>,
+        deadline: Duration,
+
+        key: Option<u64>,
+    },
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match &mut self.inner {
+            SleepInner::System(delay) => Pin::new(delay).poll(cx),
+
+            SleepInner::Manual {
+                timers,
+                deadline,
+                key,
+            } => {
+                let mut timers = timers.lock();
+
+                if timers.now >= *deadline {
+                    if let Some(key) = key.take() {
+                        timers.pending.remove(&(*deadline, key));
+                    }
+
+                    return Poll::Ready(());
+                }
+
+                let key = *key.get_or_insert_with(|| {
+                    timers.next_key += 1;
+                    timers.next_key
+                });
+
+                timers.pending.insert((*deadline, key), cx.waker().clone());
+
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl Drop for Sleep {
+    fn drop(&mut self) {
+        if let SleepInner::Manual {
+            timers,
+            deadline,
+            key: Some(key),
+        } = &self.inner
+        {
+            timers.lock().pending.remove(&(*deadline, *key));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        future::Future,
+        pin::pin,
+        task::{Context, Poll},
+        time::Duration,
+    };
+
+    use futures_util::task::noop_waker_ref;
+
+    use super::ManualClock;
+
+    #[test]
+    fn sleeps_resolve_once_the_clock_passes_their_deadline() {
+        let manual = ManualClock::new();
+
+        let mut sleep = pin!(manual.clock().sleep(Duration::from_millis(100)));
+
+        let mut cx = Context::from_waker(noop_waker_ref());
+
+        assert_eq!(sleep.as_mut().poll(&mut cx), Poll::Pending);
+        assert_eq!(manual.next_deadline(), Some(Duration::from_millis(100)));
+
+        manual.advance(Duration::from_millis(99));
+
+        assert_eq!(sleep.as_mut().poll(&mut cx), Poll::Pending);
+
+        manual.advance(Duration::from_millis(1));
+
+        assert_eq!(manual.next_deadline(), None);
+        assert_eq!(sleep.as_mut().poll(&mut cx), Poll::Ready(()));
+    }
+
+    #[test]
+    fn dropped_sleeps_are_unregistered() {
+        let manual = ManualClock::new();
+
+        {
+            let mut sleep = pin!(manual.clock().sleep(Duration::from_secs(1)));
+
+            let _ = sleep
+                .as_mut()
+                .poll(&mut Context::from_waker(noop_waker_ref()));
+
+            assert_eq!(manual.next_deadline(), Some(Duration::from_secs(1)));
+        }
+
+        assert_eq!(manual.next_deadline(), None);
+    }
+
+    #[test]
+    fn never_moves_backwards() {
+        let manual = ManualClock::new();
+
+        manual.advance_to(Duration::from_secs(2));
+        manual.advance_to(Duration::from_secs(1));
+
+        assert_eq!(manual.now(), Duration::from_secs(2));
+        assert_eq!(manual.clock().now(), Duration::from_secs(2));
+    }
+}
@@ -1,5 +1,8 @@
+mod blocking;
+pub mod clock;
 pub mod context;
 pub mod error;
 mod handle;
 
+pub use blocking::spawn_blocking;
 pub use handle::TaskHandle;
@@ -2,7 +2,7 @@ use std::ops::{
     Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
 };
 
-use super::{Offset, Rect, Size};
+use super::{Offset, Rect, Size, TextDirection};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Alignment {
@@ -175,3 +175,119 @@ impl RemAssign for Alignment {
         self.y %= rhs.y;
     }
 }
+
+/// An alignment whose horizontal component is measured from the start of the text
+/// direction, so that it's mirrored for right-to-left text.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AlignmentDirectional {
+    /// The horizontal component, where `-1.0` is the start and `1.0` is the end.
+    pub start: f32,
+    pub y: f32,
+}
+
+impl Default for AlignmentDirectional {
+    fn default() -> Self {
+        Self::CENTER
+    }
+}
+
+impl AlignmentDirectional {
+    /// The top corner on the start side.
+    pub const TOP_START: AlignmentDirectional = AlignmentDirectional::new(-1.0, -1.0);
+
+    /// The center point along the top edge.
+    pub const TOP_CENTER: AlignmentDirectional = AlignmentDirectional::new(0.0, -1.0);
+
+    /// The top corner on the end side.
+    pub const TOP_END: AlignmentDirectional = AlignmentDirectional::new(1.0, -1.0);
+
+    /// The center point along the start edge.
+    pub const CENTER_START: AlignmentDirectional = AlignmentDirectional::new(-1.0, 0.0);
+
+    /// The center point, both horizontally and vertically.
+    pub const CENTER: AlignmentDirectional = AlignmentDirectional::new(0.0, 0.0);
+
+    /// The center point along the end edge.
+    pub const CENTER_END: AlignmentDirectional = AlignmentDirectional::new(1.0, 0.0);
+
+    /// The bottom corner on the start side.
+    pub const BOTTOM_START: AlignmentDirectional = AlignmentDirectional::new(-1.0, 1.0);
+
+    /// The center point along the bottom edge.
+    pub const BOTTOM_CENTER: AlignmentDirectional = AlignmentDirectional::new(0.0, 1.0);
+
+    /// The bottom corner on the end side.
+    pub const BOTTOM_END: AlignmentDirectional = AlignmentDirectional::new(1.0, 1.0);
+
+    pub const fn new(start: f32, y: f32) -> Self {
+        Self { start, y }
+    }
+
+    pub fn resolve(&self, text_direction: TextDirection) -> Alignment {
+        match text_direction {
+            TextDirection::LeftToRight => Alignment::new(self.start, self.y),
+            TextDirection::RightToLeft => Alignment::new(-self.start, self.y),
+        }
+    }
+}
+
+/// Either an [`Alignment`], or an [`AlignmentDirectional`] that depends on the text
+/// direction.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AlignmentGeometry {
+    Absolute(Alignment),
+    Directional(AlignmentDirectional),
+}
+
+impl Default for AlignmentGeometry {
+    fn default() -> Self {
+        Self::Absolute(Alignment::default())
+    }
+}
+
+impl AlignmentGeometry {
+    pub fn resolve(&self, text_direction: TextDirection) -> Alignment {
+        match self {
+            Self::Absolute(alignment) => *alignment,
+            Self::Directional(alignment) => alignment.resolve(text_direction),
+        }
+    }
+}
+
+impl From<Alignment> for AlignmentGeometry {
+    fn from(alignment: Alignment) -> Self {
+        Self::Absolute(alignment)
+    }
+}
+
+impl From<AlignmentDirectional> for AlignmentGeometry {
+    fn from(alignment: AlignmentDirectional) -> Self {
+        Self::Directional(alignment)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::unit::{Alignment, AlignmentDirectional, AlignmentGeometry, TextDirection};
+
+    #[test]
+    fn directional_alignments_are_mirrored_for_rtl() {
+        let alignment = AlignmentGeometry::from(AlignmentDirectional::TOP_START);
+
+        assert_eq!(
+            alignment.resolve(TextDirection::LeftToRight),
+            Alignment::TOP_LEFT
+        );
+
+        assert_eq!(
+            alignment.resolve(TextDirection::RightToLeft),
+            Alignment::TOP_RIGHT
+        );
+
+        assert_eq!(
+            AlignmentGeometry::from(Alignment::TOP_LEFT).resolve(TextDirection::RightToLeft),
+            Alignment::TOP_LEFT,
+            "absolute alignments should not be mirrored"
+        );
+    }
+}
@@ -2,6 +2,7 @@ use super::EdgeInsets;
 
 /// Holds information about each side.
 #[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Bounds {
     pub top: f32,
     pub right: f32,
@@ -0,0 +1,77 @@
+use super::{Color, Offset, Rect};
+
+/// A shadow cast by a box.
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BoxShadow {
+    pub color: Color,
+
+    /// The displacement of the shadow from the box casting it.
+    pub offset: Offset,
+
+    /// How far the shadow is blurred. Larger values produce softer edges.
+    pub blur_radius: f32,
+
+    /// How far the shadow is expanded before it is blurred. Negative values shrink it.
+    pub spread_radius: f32,
+}
+
+impl BoxShadow {
+    pub const fn new(color: Color, offset: Offset, blur_radius: f32, spread_radius: f32) -> Self {
+        Self {
+            color,
+            offset,
+            blur_radius,
+            spread_radius,
+        }
+    }
+
+    /// The standard deviation of the gaussian blur applied to the shadow.
+    pub fn blur_sigma(&self) -> f32 {
+        if self.blur_radius > 0.0 {
+            self.blur_radius * 0.57735 + 0.5
+        } else {
+            0.0
+        }
+    }
+
+    /// The area of the shape before blurring, given the `rect` of the box casting it.
+    pub fn shadow_rect(&self, rect: Rect) -> Rect {
+        rect.translate(self.offset).inflate(self.spread_radius)
+    }
+
+    /// The total area covered by the shadow, given the `rect` of the box casting it.
+    ///
+    /// A gaussian blur is visually indistinguishable from zero beyond three standard
+    /// deviations, so the blurred edges are accounted for up to that distance.
+    pub fn bounds(&self, rect: Rect) -> Rect {
+        self.shadow_rect(rect).inflate(self.blur_sigma() * 3.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::unit::{BoxShadow, Color, Offset, Rect};
+
+    #[test]
+    fn unblurred_bounds() {
+        let shadow = BoxShadow::new(Color::default(), Offset::new(4.0, 2.0), 0.0, 1.0);
+
+        assert_eq!(
+            shadow.bounds(Rect::new(0.0, 0.0, 10.0, 10.0)),
+            Rect::new(3.0, 1.0, 12.0, 12.0)
+        );
+    }
+
+    #[test]
+    fn blurred_bounds_contain_shadow() {
+        let shadow = BoxShadow::new(Color::default(), Offset::new(0.0, 4.0), 8.0, 0.0);
+
+        let rect = Rect::new(0.0, 0.0, 10.0, 10.0);
+
+        let bounds = shadow.bounds(rect);
+
+        assert!(bounds.left < 0.0 && bounds.top < 4.0);
+        assert!(bounds.right() > 10.0 && bounds.bottom() > 14.0);
+    }
+}
@@ -78,3 +78,68 @@ impl EdgeInsets {
         }
     }
 }
+
+/// Insets whose horizontal sides are named by the text direction, so that they're
+/// mirrored for right-to-left text.
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct EdgeInsetsDirectional {
+    pub top: f32,
+    pub end: f32,
+    pub bottom: f32,
+    pub start: f32,
+}
+
+impl EdgeInsetsDirectional {
+    pub fn new(top: f32, end: f32, bottom: f32, start: f32) -> Self {
+        Self {
+            top,
+            end,
+            bottom,
+            start,
+        }
+    }
+
+    pub fn resolve(&self, text_direction: TextDirection) -> EdgeInsets {
+        EdgeInsets {
+            top: self.top,
+            right: self.end,
+            bottom: self.bottom,
+            left: self.start,
+        }
+        .resolve(text_direction)
+    }
+}
+
+/// Either [`EdgeInsets`], or [`EdgeInsetsDirectional`] that depend on the text direction.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub enum EdgeInsetsGeometry {
+    Absolute(EdgeInsets),
+    Directional(EdgeInsetsDirectional),
+}
+
+impl Default for EdgeInsetsGeometry {
+    fn default() -> Self {
+        Self::Absolute(EdgeInsets::default())
+    }
+}
+
+impl EdgeInsetsGeometry {
+    pub fn resolve(&self, text_direction: TextDirection) -> EdgeInsets {
+        match self {
+            Self::Absolute(insets) => *insets,
+            Self::Directional(insets) => insets.resolve(text_direction),
+        }
+    }
+}
+
+impl From<EdgeInsets> for EdgeInsetsGeometry {
+    fn from(insets: EdgeInsets) -> Self {
+        Self::Absolute(insets)
+    }
+}
+
+impl From<EdgeInsetsDirectional> for EdgeInsetsGeometry {
+    fn from(insets: EdgeInsetsDirectional) -> Self {
+        Self::Directional(insets)
+    }
+}
@@ -1,13 +1,20 @@
 use std::{borrow::Cow, sync::Arc};
 
+use url::Url;
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Font {
     data: FontData,
+
+    /// The index of the face within the font data, for collections holding several faces.
+    #[cfg_attr(feature = "serde", serde(default))]
+    index: u32,
 }
 
 impl Font {
     pub fn new(data: FontData) -> Self {
-        Self { data }
+        Self { data, index: 0 }
     }
 
     pub fn from_bytes(bytes: Vec<u8>) -> Self {
@@ -17,6 +24,22 @@ impl Font {
     pub fn from_family(family: impl Into<Cow<'static, str>>) -> Self {
         Self::new(FontData::Family(family.into()))
     }
+
+    /// Creates a font that is loaded from the given URL using an
+    /// [`AssetResolver`](crate::asset::AssetResolver).
+    pub fn from_url(url: Url) -> Self {
+        Self::new(FontData::Url(url))
+    }
+
+    /// Uses the face at `index` within a font collection, such as a `.ttc` file.
+    pub fn with_index(mut self, index: u32) -> Self {
+        self.index = index;
+        self
+    }
+
+    pub fn index(&self) -> u32 {
+        self.index
+    }
 }
 
 impl AsRef<FontData> for Font {
@@ -25,10 +48,17 @@ impl AsRef<FontData> for Font {
     }
 }
 
-#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FontData {
+    /// Captures write the data of each font once, in a table that this refers to.
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::engine::rendering::capture::fonts")
+    )]
     Bytes(Arc<Vec<u8>>),
     Family(Cow<'static, str>),
+    Url(Url),
 }
 
 impl std::fmt::Debug for FontData {
@@ -44,6 +74,97 @@ impl std::fmt::Debug for FontData {
         match self {
             Self::Bytes(_) => f.debug_tuple("Bytes").field(&DebugNonExhaustive).finish(),
             Self::Family(family) => f.debug_tuple("Family").field(family).finish(),
+            Self::Url(url) => f.debug_tuple("Url").field(url).finish(),
+        }
+    }
+}
+
+/// The number of leading bytes of font data that are hashed.
+const HASHED_PREFIX_LEN: usize = 256;
+
+impl std::hash::Hash for FontData {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+
+        match self {
+            // Font files can be many megabytes, so only their length and table directory
+            // are hashed. Fonts that share these are still told apart by comparing them.
+            Self::Bytes(bytes) => {
+                bytes.len().hash(state);
+                bytes[..bytes.len().min(HASHED_PREFIX_LEN)].hash(state);
+            }
+
+            Self::Family(family) => family.hash(state),
+            Self::Url(url) => url.hash(state),
+        }
+    }
+}
+
+/// Identifies a font by the identity of its data rather than its contents, so that fonts
+/// holding their data are hashed and compared without reading it.
+///
+/// Fonts holding equal data that was loaded separately have different identities.
+#[derive(Debug, Clone)]
+pub struct FontId(Font);
+
+impl Font {
+    pub fn id(&self) -> FontId {
+        FontId(self.clone())
+    }
+}
+
+impl FontId {
+    pub fn font(&self) -> &Font {
+        &self.0
+    }
+}
+
+impl PartialEq for FontId {
+    fn eq(&self, other: &Self) -> bool {
+        match (&self.0.data, &other.0.data) {
+            (FontData::Bytes(a), FontData::Bytes(b)) => {
+                Arc::ptr_eq(a, b) && self.0.index == other.0.index
+            }
+
+            _ => self.0 == other.0,
         }
     }
 }
+
+impl Eq for FontId {}
+
+impl std::hash::Hash for FontId {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        match &self.0.data {
+            // The id holds onto the data, so its address can't be reused by another font
+            FontData::Bytes(bytes) => {
+                Arc::as_ptr(bytes).hash(state);
+                self.0.index.hash(state);
+            }
+
+            _ => self.0.hash(state),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Font;
+
+    #[test]
+    fn ids_compare_the_identity_of_font_data() {
+        let font = Font::from_bytes(vec![0; 512]);
+
+        assert_eq!(font.id(), font.clone().id());
+        assert_ne!(font.id(), font.clone().with_index(1).id());
+
+        // Equal, but loaded separately
+        assert_eq!(font, Font::from_bytes(vec![0; 512]));
+        assert_ne!(font.id(), Font::from_bytes(vec![0; 512]).id());
+
+        let family = Font::from_family("Arial");
+
+        assert_eq!(family.id(), Font::from_family("Arial").id());
+        assert_ne!(family.id(), Font::from_family("Verdana").id());
+    }
+}
@@ -0,0 +1,50 @@
+use std::ops::Range;
+
+use super::Font;
+
+/// A sequence of shaped glyphs that are drawn with the same font and size.
+///
+/// Glyph runs are produced by text layout, and contain everything a renderer needs to
+/// draw the text exactly where layout placed it, without shaping it again.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GlyphRun {
+    pub font: Font,
+    pub font_size: f32,
+
+    /// The byte range of the source text that the run was shaped from.
+    pub range: Range<usize>,
+
+    /// Whether the run's text is right-to-left, in which case its glyphs are in the
+    /// reverse order of their clusters.
+    pub rtl: bool,
+
+    pub glyphs: Vec<PositionedGlyph>,
+}
+
+impl GlyphRun {
+    /// Returns the total advance of the glyphs in the run.
+    pub fn advance(&self) -> f32 {
+        self.glyphs.iter().map(|glyph| glyph.advance).sum()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PositionedGlyph {
+    /// The index of the glyph within its font.
+    pub id: u16,
+
+    /// The byte offset in the source text of the cluster that the glyph was shaped from.
+    /// Several glyphs may share a cluster, and a single glyph may cover several characters
+    /// (such as a ligature).
+    pub cluster: usize,
+
+    /// The position of the glyph's origin, on the baseline, relative to the top left of
+    /// the laid out text.
+    pub x: f32,
+    pub y: f32,
+
+    /// How far the pen moves horizontally after drawing the glyph.
+    pub advance: f32,
+}
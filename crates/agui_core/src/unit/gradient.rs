@@ -0,0 +1,104 @@
+use crate::unit::{Color, Offset};
+
+/// A gradient that may be used in place of a solid color when painting.
+///
+/// Gradient coordinates are relative to the origin of the rect the shape is drawn in.
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Gradient {
+    pub kind: GradientKind,
+
+    /// The colors of the gradient, ordered by their offset.
+    pub stops: Vec<GradientStop>,
+
+    /// How the gradient is painted outside of its bounds.
+    pub extend: GradientExtend,
+}
+
+impl Gradient {
+    /// A gradient that transitions between its stops along the line from `start` to `end`.
+    pub fn linear(start: Offset, end: Offset, stops: impl Into<Vec<GradientStop>>) -> Self {
+        Self {
+            kind: GradientKind::Linear { start, end },
+            stops: stops.into(),
+            extend: GradientExtend::default(),
+        }
+    }
+
+    /// A gradient that transitions between its stops outwards from `center` until it
+    /// reaches `radius`.
+    pub fn radial(center: Offset, radius: f32, stops: impl Into<Vec<GradientStop>>) -> Self {
+        Self {
+            kind: GradientKind::Radial {
+                center,
+                radius,
+                focal: center,
+            },
+            stops: stops.into(),
+            extend: GradientExtend::default(),
+        }
+    }
+
+    /// Sets the point a radial gradient starts from. Ignored for linear gradients.
+    #[must_use]
+    pub fn with_focal(mut self, point: Offset) -> Self {
+        if let GradientKind::Radial { focal, .. } = &mut self.kind {
+            *focal = point;
+        }
+
+        self
+    }
+
+    #[must_use]
+    pub fn with_extend(mut self, extend: GradientExtend) -> Self {
+        self.extend = extend;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GradientKind {
+    Linear {
+        start: Offset,
+        end: Offset,
+    },
+
+    Radial {
+        center: Offset,
+        radius: f32,
+
+        /// The point at which the gradient begins. This is usually the `center`.
+        focal: Offset,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GradientStop {
+    /// The position of the stop along the gradient, from `0.0` to `1.0`.
+    pub offset: f32,
+
+    pub color: Color,
+}
+
+impl GradientStop {
+    pub const fn new(offset: f32, color: Color) -> Self {
+        Self { offset, color }
+    }
+}
+
+/// How a gradient is painted beyond its first and last stops.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GradientExtend {
+    /// Continue painting the color of the nearest stop.
+    #[default]
+    Pad,
+
+    /// Repeat the gradient from its first stop.
+    Repeat,
+
+    /// Repeat the gradient, alternating its direction each time.
+    Reflect,
+}
@@ -30,13 +30,26 @@ impl HitTestEntry {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct HitTestResult {
     path: Vec<HitTestEntry>,
     transforms: Vec<Mat4>,
 }
 
 impl HitTestResult {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the render objects that were hit, starting with the deepest one.
+    pub fn path(&self) -> &[HitTestEntry] {
+        &self.path
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.path.is_empty()
+    }
+
     fn current_transform(&self) -> Mat4 {
         self.transforms.last().copied().unwrap_or_default()
     }
@@ -0,0 +1,29 @@
+/// A filter applied to the pixels of a layer.
+///
+/// Not every backend can filter layers. The software backend supports every filter, as
+/// does the svg backend for filters on a layer's contents. The pdf and vello backends draw
+/// filtered layers unfiltered and log a warning, while the terminal ignores filters.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LayerFilter {
+    /// A gaussian blur with the given standard deviation along each axis.
+    Blur { sigma_x: f32, sigma_y: f32 },
+}
+
+impl LayerFilter {
+    /// A gaussian blur with the same standard deviation along both axes.
+    pub const fn blur(sigma: f32) -> Self {
+        Self::Blur {
+            sigma_x: sigma,
+            sigma_y: sigma,
+        }
+    }
+
+    /// How far the filter may spread pixels beyond their original position.
+    pub fn extent(&self) -> (f32, f32) {
+        match self {
+            Self::Blur { sigma_x, sigma_y } => (sigma_x * 3.0, sigma_y * 3.0),
+        }
+    }
+}
@@ -2,20 +2,29 @@ mod alignment;
 mod axis;
 mod blend_mode;
 mod bounds;
+mod box_shadow;
 mod clip_behavior;
 mod color;
 mod constraints;
 mod data;
 mod edge_insets;
 mod font;
+mod glyph_run;
+mod gradient;
 mod hit_test;
 mod intrinsic_dimension;
 mod key;
+mod layer_filter;
 mod offset;
+mod painting_style;
+mod paragraph;
+mod path;
 mod rect;
 mod shape;
 mod size;
+mod text_baseline;
 mod text_direction;
+mod text_position;
 mod text_style;
 mod texture;
 mod view_config;
@@ -24,20 +33,29 @@ pub use alignment::*;
 pub use axis::*;
 pub use blend_mode::*;
 pub use bounds::*;
+pub use box_shadow::*;
 pub use clip_behavior::*;
 pub use color::*;
 pub use constraints::*;
 pub use data::*;
 pub use edge_insets::*;
 pub use font::*;
+pub use glyph_run::*;
+pub use gradient::*;
 pub use hit_test::*;
 pub use intrinsic_dimension::*;
 pub use key::*;
+pub use layer_filter::*;
 pub use offset::*;
+pub use painting_style::*;
+pub use paragraph::*;
+pub use path::*;
 pub use rect::*;
 pub use shape::*;
 pub use size::*;
+pub use text_baseline::*;
 pub use text_direction::*;
+pub use text_position::*;
 pub use text_style::*;
 pub use texture::*;
 pub use view_config::*;
@@ -7,6 +7,7 @@ use glam::{Vec2, Vec3};
 use super::{Rect, Size};
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Offset {
     pub x: f32,
     pub y: f32,
@@ -0,0 +1,125 @@
+/// Whether a shape is painted by filling its interior or by drawing its outline.
+#[derive(Debug, Default, Clone, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PaintingStyle {
+    /// Fill the interior of the shape.
+    #[default]
+    Fill,
+
+    /// Draw the outline of the shape using the given stroke.
+    Stroke(StrokeStyle),
+}
+
+/// How the interior of a shape is determined when it is filled.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FillRule {
+    /// A point is inside the shape if the outline winds around it a non-zero number of
+    /// times, counting clockwise crossings against counter-clockwise ones.
+    #[default]
+    NonZero,
+
+    /// A point is inside the shape if the outline crosses any ray from it an odd number
+    /// of times.
+    EvenOdd,
+}
+
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StrokeStyle {
+    /// The width of the stroke, centered on the outline of the shape.
+    pub width: f32,
+
+    pub join: StrokeJoin,
+    pub cap: StrokeCap,
+
+    /// The limit at which a [`StrokeJoin::Miter`] join is replaced with a bevel, as a
+    /// ratio of the miter length to the stroke width.
+    pub miter_limit: f32,
+
+    /// Alternating lengths of dashes and gaps. If this is empty, the stroke is solid.
+    pub dash_pattern: Vec<f32>,
+
+    /// The distance into the dash pattern at which the stroke begins.
+    pub dash_offset: f32,
+}
+
+impl Default for StrokeStyle {
+    fn default() -> Self {
+        Self {
+            width: 1.0,
+
+            join: StrokeJoin::default(),
+            cap: StrokeCap::default(),
+
+            miter_limit: 4.0,
+
+            dash_pattern: Vec::default(),
+            dash_offset: 0.0,
+        }
+    }
+}
+
+impl StrokeStyle {
+    pub fn new(width: f32) -> Self {
+        Self {
+            width,
+            ..Self::default()
+        }
+    }
+
+    pub fn join(mut self, join: StrokeJoin) -> Self {
+        self.join = join;
+        self
+    }
+
+    pub fn cap(mut self, cap: StrokeCap) -> Self {
+        self.cap = cap;
+        self
+    }
+
+    pub fn miter_limit(mut self, miter_limit: f32) -> Self {
+        self.miter_limit = miter_limit;
+        self
+    }
+
+    pub fn dashes(mut self, offset: f32, pattern: impl IntoIterator<Item = f32>) -> Self {
+        self.dash_offset = offset;
+        self.dash_pattern = pattern.into_iter().collect();
+        self
+    }
+
+    pub fn is_dashed(&self) -> bool {
+        self.dash_pattern.iter().any(|length| *length > 0.0)
+    }
+}
+
+/// How the corners between two connected segments of a stroke are drawn.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StrokeJoin {
+    /// Extend the outer edges of both segments until they meet at a sharp point.
+    #[default]
+    Miter,
+
+    /// Connect the outer edges of both segments with a circular arc.
+    Round,
+
+    /// Connect the outer edges of both segments with a straight line.
+    Bevel,
+}
+
+/// How the ends of an open stroke are drawn.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StrokeCap {
+    /// End the stroke exactly at the end point.
+    #[default]
+    Butt,
+
+    /// End the stroke with a semicircle centered on the end point.
+    Round,
+
+    /// Extend the stroke past the end point by half of its width.
+    Square,
+}
@@ -0,0 +1,546 @@
+use std::ops::Range;
+
+use super::{GlyphRun, Offset, Rect, Size, TextAffinity, TextPosition, TextStyle};
+
+/// Selection rects that are closer than this are merged together.
+const MERGE_TOLERANCE: f32 = 1e-3;
+
+/// Text that has been shaped and broken into lines, ready to be painted.
+///
+/// Paragraphs are produced by a view's text layout strategy, and are painted by emitting
+/// their glyph runs, so that the text is never shaped more than once.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Paragraph {
+    /// The size of the paragraph, within the constraints it was laid out with.
+    pub size: Size,
+
+    pub lines: Vec<TextLine>,
+
+    /// Where each placeholder was placed, in the order the placeholders were given in.
+    pub placeholders: Vec<Rect>,
+
+    /// The width of the widest piece of text that can't be broken across lines.
+    pub min_intrinsic_width: f32,
+
+    /// The width of the text if it were never wrapped.
+    pub max_intrinsic_width: f32,
+
+    /// Whether lines were dropped because the text needed more lines than its style's
+    /// `max_lines` allows.
+    pub did_exceed_max_lines: bool,
+}
+
+impl Paragraph {
+    /// Returns the height of the laid out lines, which may differ from the height of the
+    /// paragraph if it was constrained.
+    pub fn content_height(&self) -> f32 {
+        self.lines.iter().map(|line| line.height).sum()
+    }
+
+    pub fn glyph_runs(&self) -> impl Iterator<Item = &GlyphRun> {
+        self.lines.iter().flat_map(|line| &line.runs)
+    }
+
+    /// Returns the position in the text closest to `offset`, which is relative to the top
+    /// left of the paragraph.
+    ///
+    /// Points above or below the paragraph resolve to its first or last line, and points
+    /// past either end of a line resolve to that end.
+    pub fn position_for_offset(&self, offset: Offset) -> TextPosition {
+        let Some(line) = self
+            .lines
+            .iter()
+            .find(|line| offset.y < line.top + line.height)
+            .or(self.lines.last())
+        else {
+            return TextPosition::default();
+        };
+
+        let clusters = ClusterBounds::of_line(line);
+
+        let Some(cluster) = clusters
+            .iter()
+            .find(|cluster| offset.x < cluster.right)
+            .or(clusters.last())
+        else {
+            return TextPosition::new(line.range.start);
+        };
+
+        // The left half of a cluster is its start, unless the text is right-to-left
+        if (offset.x < (cluster.left + cluster.right) / 2.0) != cluster.rtl {
+            TextPosition::new(cluster.range.start)
+        } else {
+            TextPosition::upstream(cluster.range.end)
+        }
+    }
+
+    /// Returns the index of the line that a caret at `position` is drawn on, if the
+    /// paragraph has any lines.
+    pub fn line_for_position(&self, position: TextPosition) -> Option<usize> {
+        let idx = self
+            .lines
+            .iter()
+            .position(|line| position.offset < line.range.end)
+            .unwrap_or(self.lines.len().checked_sub(1)?);
+
+        // A position where a line was wrapped may belong to the end of the previous line,
+        // but not if that line ended with a line break
+        if position.affinity == TextAffinity::Upstream
+            && idx > 0
+            && self.lines[idx].range.start == position.offset
+            && self.lines[idx - 1].content_end() == position.offset
+        {
+            return Some(idx - 1);
+        }
+
+        Some(idx)
+    }
+
+    /// Returns where a caret at `position` is drawn, as a rect with no width that spans
+    /// the height of its line.
+    pub fn caret_rect(&self, position: TextPosition) -> Rect {
+        let Some(line) = self.line_for_position(position).map(|idx| &self.lines[idx]) else {
+            return Rect::default();
+        };
+
+        let clusters = ClusterBounds::of_line(line);
+
+        let leading = clusters
+            .iter()
+            .find(|cluster| cluster.range.start == position.offset)
+            .map(ClusterBounds::leading);
+
+        let trailing = clusters
+            .iter()
+            .find(|cluster| cluster.range.end == position.offset)
+            .map(ClusterBounds::trailing);
+
+        let x = match position.affinity {
+            TextAffinity::Upstream => trailing.or(leading),
+            TextAffinity::Downstream => leading.or(trailing),
+        }
+        .or_else(|| {
+            // The position is within a cluster, such as a ligature
+            clusters
+                .iter()
+                .find(|cluster| cluster.range.contains(&position.offset))
+                .map(ClusterBounds::leading)
+        })
+        .unwrap_or(line.left);
+
+        Rect::new(x, line.top, 0.0, line.height)
+    }
+
+    /// Returns the rects that cover the text in `range`, in order from the first line to
+    /// the last. Adjacent pieces of the selection on the same line are merged together.
+    pub fn selection_rects(&self, range: Range<usize>) -> Vec<Rect> {
+        let mut rects = Vec::<Rect>::new();
+
+        if range.is_empty() {
+            return rects;
+        }
+
+        for line in &self.lines {
+            if line.range.end <= range.start || line.range.start >= range.end {
+                continue;
+            }
+
+            let line_start = rects.len();
+
+            for cluster in ClusterBounds::of_line(line) {
+                if cluster.range.end <= range.start || cluster.range.start >= range.end {
+                    continue;
+                }
+
+                match rects[line_start..].last_mut() {
+                    Some(rect) if (rect.right() - cluster.left).abs() < MERGE_TOLERANCE => {
+                        rect.width = cluster.right - rect.left;
+                    }
+
+                    _ => rects.push(Rect::new(
+                        cluster.left,
+                        line.top,
+                        cluster.right - cluster.left,
+                        line.height,
+                    )),
+                }
+            }
+        }
+
+        rects
+    }
+
+    /// Returns the metrics of each line of the paragraph.
+    pub fn line_metrics(&self) -> Vec<LineMetrics> {
+        self.lines
+            .iter()
+            .enumerate()
+            .map(|(line_number, line)| LineMetrics {
+                line_number,
+
+                range: line.range.clone(),
+
+                ascent: line.baseline - line.top,
+                descent: line.top + line.height - line.baseline,
+
+                top: line.top,
+                baseline: line.baseline,
+                height: line.height,
+
+                left: line.left,
+                width: line.width,
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TextLine {
+    /// The byte range of the source text in the line, including any trailing whitespace
+    /// and line breaks.
+    pub range: Range<usize>,
+
+    pub top: f32,
+    pub baseline: f32,
+    pub height: f32,
+
+    /// The offset of the start of the line from the left of the paragraph, after alignment.
+    pub left: f32,
+
+    /// The width of the line, excluding trailing whitespace.
+    pub width: f32,
+
+    pub runs: Vec<GlyphRun>,
+}
+
+impl TextLine {
+    /// Returns the end of the text laid out on the line, which excludes any line break
+    /// that ends it.
+    fn content_end(&self) -> usize {
+        self.runs
+            .iter()
+            .map(|run| run.range.end)
+            .max()
+            .unwrap_or(self.range.start)
+    }
+}
+
+/// The measurements of a single line of a laid out [`Paragraph`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineMetrics {
+    /// The index of the line within the paragraph.
+    pub line_number: usize,
+
+    /// The byte range of the source text in the line, including any trailing whitespace
+    /// and line breaks.
+    pub range: Range<usize>,
+
+    /// The distance from the top of the line to its baseline.
+    pub ascent: f32,
+
+    /// The distance from the baseline of the line to its bottom.
+    pub descent: f32,
+
+    pub top: f32,
+    pub baseline: f32,
+    pub height: f32,
+
+    pub left: f32,
+    pub width: f32,
+}
+
+/// The horizontal extent of a cluster of glyphs on a line, and the text it was shaped
+/// from.
+struct ClusterBounds {
+    range: Range<usize>,
+
+    left: f32,
+    right: f32,
+
+    rtl: bool,
+}
+
+impl ClusterBounds {
+    /// Returns the clusters of a line, from left to right.
+    fn of_line(line: &TextLine) -> Vec<Self> {
+        let mut clusters = Vec::<Self>::new();
+
+        // Ellipses don't cover any text, so there's nowhere within them to place a caret
+        for run in line.runs.iter().filter(|run| !run.range.is_empty()) {
+            let run_start = clusters.len();
+
+            let mut pen = run.glyphs.first().map_or(line.left, |glyph| glyph.x);
+
+            for glyph in &run.glyphs {
+                match clusters[run_start..].last_mut() {
+                    Some(cluster) if cluster.range.start == glyph.cluster => {
+                        cluster.right += glyph.advance;
+                    }
+
+                    _ => clusters.push(Self {
+                        range: glyph.cluster..glyph.cluster,
+
+                        left: pen,
+                        right: pen + glyph.advance,
+
+                        rtl: run.rtl,
+                    }),
+                }
+
+                pen += glyph.advance;
+            }
+
+            // Each cluster ends where the one after it in the text starts
+            let mut end = run.range.end;
+
+            let run_clusters = &mut clusters[run_start..];
+
+            if run.rtl {
+                for cluster in run_clusters.iter_mut() {
+                    cluster.range.end = end;
+                    end = cluster.range.start;
+                }
+            } else {
+                for cluster in run_clusters.iter_mut().rev() {
+                    cluster.range.end = end;
+                    end = cluster.range.start;
+                }
+            }
+        }
+
+        clusters
+    }
+
+    /// Returns where a caret before the cluster is drawn.
+    fn leading(&self) -> f32 {
+        if self.rtl {
+            self.right
+        } else {
+            self.left
+        }
+    }
+
+    /// Returns where a caret after the cluster is drawn.
+    fn trailing(&self) -> f32 {
+        if self.rtl {
+            self.left
+        } else {
+            self.right
+        }
+    }
+}
+
+/// A part of a paragraph's text that's laid out differently to the rest of it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParagraphSpan {
+    /// Text that's laid out with its own style, instead of the paragraph's.
+    Text {
+        range: Range<usize>,
+        style: TextStyle,
+    },
+
+    /// Space reserved for an inline box, such as a widget, in place of the character at
+    /// `offset`. The bottom of the box sits on the baseline of its line.
+    ///
+    /// The character should be the object replacement character (`U+FFFC`).
+    Placeholder { offset: usize, size: Size },
+}
+
+impl ParagraphSpan {
+    /// Returns the byte range of the text covered by the span.
+    pub fn range(&self) -> Range<usize> {
+        match self {
+            Self::Text { range, .. } => range.clone(),
+            Self::Placeholder { offset, .. } => {
+                *offset..*offset + OBJECT_REPLACEMENT_CHAR.len_utf8()
+            }
+        }
+    }
+}
+
+/// The character that stands in for an inline box within a paragraph's text.
+pub const OBJECT_REPLACEMENT_CHAR: char = '\u{FFFC}';
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Range;
+
+    use crate::unit::{
+        Font, GlyphRun, Offset, Paragraph, PositionedGlyph, Rect, Size, TextLine, TextPosition,
+    };
+
+    const ADVANCE: f32 = 10.0;
+    const LINE_HEIGHT: f32 = 20.0;
+
+    /// The runs of a line, as their range and whether they're right-to-left, and where
+    /// the line ends.
+    type Line<'a> = (&'a [(Range<usize>, bool)], usize);
+
+    /// Lays out each line's runs one after another, with every character one glyph wide.
+    fn lay_out(text: &str, lines: &[Line]) -> Paragraph {
+        let mut line_start = 0;
+
+        let lines = lines
+            .iter()
+            .enumerate()
+            .map(|(idx, (runs, line_end))| {
+                let top = idx as f32 * LINE_HEIGHT;
+
+                let mut x = 0.0;
+
+                let runs = runs
+                    .iter()
+                    .map(|(range, rtl)| {
+                        let mut clusters = text[range.clone()]
+                            .char_indices()
+                            .map(|(offset, _)| range.start + offset)
+                            .collect::<Vec<_>>();
+
+                        if *rtl {
+                            clusters.reverse();
+                        }
+
+                        let glyphs = clusters
+                            .into_iter()
+                            .map(|cluster| {
+                                let glyph = PositionedGlyph {
+                                    id: 0,
+                                    cluster,
+
+                                    x,
+                                    y: top + 16.0,
+
+                                    advance: ADVANCE,
+                                };
+
+                                x += ADVANCE;
+
+                                glyph
+                            })
+                            .collect();
+
+                        GlyphRun {
+                            font: Font::from_family("Test"),
+                            font_size: 16.0,
+
+                            range: range.clone(),
+                            rtl: *rtl,
+
+                            glyphs,
+                        }
+                    })
+                    .collect();
+
+                let line = TextLine {
+                    range: line_start..*line_end,
+
+                    top,
+                    baseline: top + 16.0,
+                    height: LINE_HEIGHT,
+
+                    left: 0.0,
+                    width: x,
+
+                    runs,
+                };
+
+                line_start = *line_end;
+
+                line
+            })
+            .collect::<Vec<_>>();
+
+        Paragraph {
+            size: Size::new(100.0, lines.len() as f32 * LINE_HEIGHT),
+
+            lines,
+
+            ..Paragraph::default()
+        }
+    }
+
+    #[test]
+    fn positions_are_found_at_the_nearest_edge() {
+        // "ab cd" wrapped after the space
+        let paragraph = lay_out("ab cd", &[(&[(0..3, false)], 3), (&[(3..5, false)], 5)]);
+
+        assert_eq!(
+            paragraph.position_for_offset(Offset::new(4.0, 5.0)),
+            TextPosition::new(0)
+        );
+
+        assert_eq!(
+            paragraph.position_for_offset(Offset::new(16.0, 5.0)),
+            TextPosition::upstream(2)
+        );
+
+        assert_eq!(
+            paragraph.position_for_offset(Offset::new(200.0, 5.0)),
+            TextPosition::upstream(3),
+            "should stop at the end of the line"
+        );
+
+        assert_eq!(
+            paragraph.position_for_offset(Offset::new(-5.0, 500.0)),
+            TextPosition::new(3),
+            "should resolve to the last line"
+        );
+    }
+
+    #[test]
+    fn affinity_decides_the_line_of_a_wrapped_caret() {
+        let paragraph = lay_out("ab cd", &[(&[(0..3, false)], 3), (&[(3..5, false)], 5)]);
+
+        assert_eq!(
+            paragraph.caret_rect(TextPosition::upstream(3)),
+            Rect::new(30.0, 0.0, 0.0, LINE_HEIGHT)
+        );
+
+        assert_eq!(
+            paragraph.caret_rect(TextPosition::new(3)),
+            Rect::new(0.0, LINE_HEIGHT, 0.0, LINE_HEIGHT)
+        );
+
+        // A line that ends in a line break can't hold a caret after the break
+        let paragraph = lay_out("ab\ncd", &[(&[(0..2, false)], 3), (&[(3..5, false)], 5)]);
+
+        assert_eq!(
+            paragraph.line_for_position(TextPosition::upstream(3)),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn right_to_left_carets_are_mirrored() {
+        let text = "ab \u{5D0}\u{5D1}";
+
+        let paragraph = lay_out(text, &[(&[(0..3, false), (3..7, true)], 7)]);
+
+        // The first right-to-left character is drawn on the right of its run
+        assert_eq!(paragraph.caret_rect(TextPosition::new(3)).left, 50.0);
+        assert_eq!(paragraph.caret_rect(TextPosition::new(5)).left, 40.0);
+        assert_eq!(paragraph.caret_rect(TextPosition::upstream(7)).left, 30.0);
+
+        assert_eq!(
+            paragraph.position_for_offset(Offset::new(48.0, 5.0)),
+            TextPosition::new(3)
+        );
+    }
+
+    #[test]
+    fn selection_rects_are_merged_on_each_line() {
+        let paragraph = lay_out("ab cd", &[(&[(0..3, false)], 3), (&[(3..5, false)], 5)]);
+
+        assert_eq!(
+            paragraph.selection_rects(1..4),
+            [
+                Rect::new(10.0, 0.0, 20.0, LINE_HEIGHT),
+                Rect::new(0.0, LINE_HEIGHT, 10.0, LINE_HEIGHT)
+            ]
+        );
+
+        assert!(paragraph.selection_rects(2..2).is_empty());
+    }
+}
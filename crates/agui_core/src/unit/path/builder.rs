@@ -0,0 +1,331 @@
+use lyon::{
+    geom::ArcFlags,
+    math::{point, vector, Angle, Point, Vector},
+    path::{builder::WithSvg, path::BuilderImpl, traits::SvgPathBuilder, Path},
+};
+use svgtypes::{PathParser, PathSegment};
+
+use crate::unit::{Offset, Size};
+
+use super::PathError;
+
+/// Builds a [`Path`] out of lines, curves and arcs.
+///
+/// Starting a new sub-path with [`move_to`](Self::move_to) implicitly ends the previous
+/// one without closing it.
+pub struct PathBuilder {
+    builder: WithSvg<BuilderImpl>,
+}
+
+impl Default for PathBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PathBuilder {
+    pub fn new() -> Self {
+        Self {
+            builder: Path::svg_builder(),
+        }
+    }
+
+    /// Creates a builder containing the path described by an SVG path data string, such
+    /// as the `d` attribute of a `<path>` element.
+    pub fn from_svg(data: &str) -> Result<Self, PathError> {
+        let mut builder = Self::new();
+
+        for segment in PathParser::from(data) {
+            builder.push_svg_segment(segment?);
+        }
+
+        Ok(builder)
+    }
+
+    /// The end point of the last segment that was added.
+    pub fn current_position(&self) -> Offset {
+        let position = self.builder.current_position();
+
+        Offset::new(position.x, position.y)
+    }
+
+    /// Begins a new sub-path at `to`.
+    pub fn move_to(mut self, to: impl Into<Offset>) -> Self {
+        self.builder.move_to(to_point(to.into()));
+        self
+    }
+
+    /// Adds a straight line from the current position to `to`.
+    pub fn line_to(mut self, to: impl Into<Offset>) -> Self {
+        self.builder.line_to(to_point(to.into()));
+        self
+    }
+
+    /// Adds a quadratic bézier curve from the current position to `to`.
+    pub fn quad_to(mut self, ctrl: impl Into<Offset>, to: impl Into<Offset>) -> Self {
+        self.builder
+            .quadratic_bezier_to(to_point(ctrl.into()), to_point(to.into()));
+        self
+    }
+
+    /// Adds a cubic bézier curve from the current position to `to`.
+    pub fn cubic_to(
+        mut self,
+        ctrl1: impl Into<Offset>,
+        ctrl2: impl Into<Offset>,
+        to: impl Into<Offset>,
+    ) -> Self {
+        self.builder.cubic_bezier_to(
+            to_point(ctrl1.into()),
+            to_point(ctrl2.into()),
+            to_point(to.into()),
+        );
+        self
+    }
+
+    /// Adds an elliptical arc from the current position to `to`, following the same
+    /// rules as the SVG `A` command.
+    ///
+    /// The `rotation` of the ellipse's x axis is given in radians. Of the four arcs that
+    /// could connect the two points, `large_arc` and `sweep` select the one that spans
+    /// more than 180 degrees and the one that is drawn clockwise, respectively.
+    pub fn arc_to(
+        mut self,
+        to: impl Into<Offset>,
+        radii: Size,
+        rotation: f32,
+        large_arc: bool,
+        sweep: bool,
+    ) -> Self {
+        self.builder.arc_to(
+            vector(radii.width, radii.height),
+            Angle::radians(rotation),
+            ArcFlags { large_arc, sweep },
+            to_point(to.into()),
+        );
+        self
+    }
+
+    /// Closes the current sub-path with a straight line back to its starting point.
+    pub fn close(mut self) -> Self {
+        self.builder.close();
+        self
+    }
+
+    pub fn build(self) -> Path {
+        self.builder.build()
+    }
+
+    fn push_svg_segment(&mut self, segment: PathSegment) {
+        let builder = &mut self.builder;
+
+        let point = |x: f64, y: f64| -> Point { point(x as f32, y as f32) };
+        let vector = |x: f64, y: f64| -> Vector { vector(x as f32, y as f32) };
+
+        match segment {
+            PathSegment::MoveTo { abs: true, x, y } => {
+                builder.move_to(point(x, y));
+            }
+            PathSegment::MoveTo { abs: false, x, y } => {
+                builder.relative_move_to(vector(x, y));
+            }
+
+            PathSegment::LineTo { abs: true, x, y } => {
+                builder.line_to(point(x, y));
+            }
+            PathSegment::LineTo { abs: false, x, y } => {
+                builder.relative_line_to(vector(x, y));
+            }
+
+            PathSegment::HorizontalLineTo { abs: true, x } => {
+                builder.horizontal_line_to(x as f32);
+            }
+            PathSegment::HorizontalLineTo { abs: false, x } => {
+                builder.relative_horizontal_line_to(x as f32);
+            }
+
+            PathSegment::VerticalLineTo { abs: true, y } => {
+                builder.vertical_line_to(y as f32);
+            }
+            PathSegment::VerticalLineTo { abs: false, y } => {
+                builder.relative_vertical_line_to(y as f32);
+            }
+
+            PathSegment::CurveTo {
+                abs,
+                x1,
+                y1,
+                x2,
+                y2,
+                x,
+                y,
+            } => {
+                if abs {
+                    builder.cubic_bezier_to(point(x1, y1), point(x2, y2), point(x, y));
+                } else {
+                    builder.relative_cubic_bezier_to(vector(x1, y1), vector(x2, y2), vector(x, y));
+                }
+            }
+
+            PathSegment::SmoothCurveTo { abs, x2, y2, x, y } => {
+                if abs {
+                    builder.smooth_cubic_bezier_to(point(x2, y2), point(x, y));
+                } else {
+                    builder.smooth_relative_cubic_bezier_to(vector(x2, y2), vector(x, y));
+                }
+            }
+
+            PathSegment::Quadratic { abs, x1, y1, x, y } => {
+                if abs {
+                    builder.quadratic_bezier_to(point(x1, y1), point(x, y));
+                } else {
+                    builder.relative_quadratic_bezier_to(vector(x1, y1), vector(x, y));
+                }
+            }
+
+            PathSegment::SmoothQuadratic { abs: true, x, y } => {
+                builder.smooth_quadratic_bezier_to(point(x, y));
+            }
+            PathSegment::SmoothQuadratic { abs: false, x, y } => {
+                builder.smooth_relative_quadratic_bezier_to(vector(x, y));
+            }
+
+            PathSegment::EllipticalArc {
+                abs,
+                rx,
+                ry,
+                x_axis_rotation,
+                large_arc,
+                sweep,
+                x,
+                y,
+            } => {
+                let radii = vector(rx, ry);
+                let rotation = Angle::degrees(x_axis_rotation as f32);
+                let flags = ArcFlags { large_arc, sweep };
+
+                if abs {
+                    builder.arc_to(radii, rotation, flags, point(x, y));
+                } else {
+                    builder.relative_arc_to(radii, rotation, flags, vector(x, y));
+                }
+            }
+
+            PathSegment::ClosePath { .. } => builder.close(),
+        }
+    }
+}
+
+fn to_point(offset: Offset) -> Point {
+    point(offset.x, offset.y)
+}
+
+#[cfg(test)]
+mod tests {
+    use lyon::path::{Event, Path};
+
+    use crate::unit::{Offset, Rect, Size};
+
+    use super::PathBuilder;
+    use crate::unit::PathExt;
+
+    fn endpoints(path: &Path) -> Vec<(f32, f32)> {
+        path.iter()
+            .filter_map(|event| match event {
+                Event::Begin { at } => Some((at.x, at.y)),
+                Event::Line { to, .. } | Event::Quadratic { to, .. } | Event::Cubic { to, .. } => {
+                    Some((to.x, to.y))
+                }
+                Event::End { .. } => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parses_absolute_and_relative_commands() {
+        let path = PathBuilder::from_svg("M10 10 L20 10 l0 10 H10 v-5 h5 V10 z")
+            .unwrap()
+            .build();
+
+        assert_eq!(
+            endpoints(&path),
+            vec![
+                (10.0, 10.0),
+                (20.0, 10.0),
+                (20.0, 20.0),
+                (10.0, 20.0),
+                (10.0, 15.0),
+                (15.0, 15.0),
+                (15.0, 10.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_curves() {
+        let path = PathBuilder::from_svg("M0 0 C0 10 10 10 10 0 S20 -10 20 0 Q25 5 30 0 T40 0")
+            .unwrap()
+            .build();
+
+        assert_eq!(
+            endpoints(&path),
+            vec![
+                (0.0, 0.0),
+                (10.0, 0.0),
+                (20.0, 0.0),
+                (30.0, 0.0),
+                (40.0, 0.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_arcs() {
+        let path = PathBuilder::from_svg("M0 10 A10 10 0 0 1 20 10")
+            .unwrap()
+            .build();
+
+        let bounds = path.bounds();
+
+        assert!((bounds.top - 0.0).abs() < 0.01, "{:?}", bounds);
+        assert!((bounds.width - 20.0).abs() < 0.01, "{:?}", bounds);
+    }
+
+    #[test]
+    fn rejects_invalid_data() {
+        assert!(PathBuilder::from_svg("M0 0 L10").is_err());
+        assert!(PathBuilder::from_svg("X10 10").is_err());
+    }
+
+    #[test]
+    fn builds_arcs() {
+        let path = PathBuilder::new()
+            .move_to((0.0, 0.0))
+            .arc_to((10.0, 10.0), Size::new(10.0, 10.0), 0.0, false, false)
+            .build();
+
+        let bounds = path.bounds();
+
+        assert!((bounds.width - 10.0).abs() < 0.01, "{:?}", bounds);
+        assert!((bounds.height - 10.0).abs() < 0.01, "{:?}", bounds);
+    }
+
+    #[test]
+    fn starts_new_sub_paths() {
+        let path = PathBuilder::new()
+            .move_to(Offset::new(0.0, 0.0))
+            .line_to(Offset::new(10.0, 0.0))
+            .move_to(Offset::new(0.0, 10.0))
+            .line_to(Offset::new(10.0, 10.0))
+            .build();
+
+        assert_eq!(
+            path.iter()
+                .filter(|event| matches!(event, Event::Begin { .. }))
+                .count(),
+            2
+        );
+
+        assert_eq!(path.bounds(), Rect::new(0.0, 0.0, 10.0, 10.0));
+    }
+}
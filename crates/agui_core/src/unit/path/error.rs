@@ -0,0 +1,5 @@
+#[derive(Debug, thiserror::Error)]
+pub enum PathError {
+    #[error("invalid svg path data: {0}")]
+    Parse(#[from] svgtypes::Error),
+}
@@ -0,0 +1,191 @@
+use lyon::path::Path;
+
+use crate::unit::{FillRule, Offset, Rect, StrokeStyle};
+
+mod builder;
+mod error;
+mod ops;
+
+pub use builder::*;
+pub use error::*;
+
+/// The maximum distance that flattened curves may deviate from the original path.
+pub(crate) const PATH_TOLERANCE: f32 = 0.1;
+
+/// Geometric queries and operations on a [`Path`].
+pub trait PathExt {
+    /// Returns the smallest rect that contains every point along the path.
+    fn bounds(&self) -> Rect;
+
+    /// Returns whether `point` lies inside the path when it is filled using `fill_rule`.
+    fn contains(&self, point: Offset, fill_rule: FillRule) -> bool;
+
+    /// Returns the area covered by either path.
+    fn union(&self, other: &Path) -> Path;
+
+    /// Returns the area covered by both paths.
+    fn intersect(&self, other: &Path) -> Path;
+
+    /// Returns the area covered by this path but not by `other`.
+    fn difference(&self, other: &Path) -> Path;
+
+    /// Returns the area covered by exactly one of the paths.
+    fn xor(&self, other: &Path) -> Path;
+
+    /// Returns a path that, when filled, covers the same area as stroking this path
+    /// with `stroke`.
+    fn stroke_to_fill(&self, stroke: &StrokeStyle) -> Path;
+}
+
+impl PathExt for Path {
+    fn bounds(&self) -> Rect {
+        if self.iter().next().is_none() {
+            return Rect::default();
+        }
+
+        let bounds = lyon::algorithms::aabb::bounding_box(self.iter());
+
+        Rect::new(bounds.min.x, bounds.min.y, bounds.width(), bounds.height())
+    }
+
+    fn contains(&self, point: Offset, fill_rule: FillRule) -> bool {
+        lyon::algorithms::hit_test::hit_test_path(
+            &lyon::math::point(point.x, point.y),
+            self.iter(),
+            match fill_rule {
+                FillRule::NonZero => lyon::path::FillRule::NonZero,
+                FillRule::EvenOdd => lyon::path::FillRule::EvenOdd,
+            },
+            PATH_TOLERANCE,
+        )
+    }
+
+    fn union(&self, other: &Path) -> Path {
+        ops::overlay(self, other, ops::BooleanOp::Union)
+    }
+
+    fn intersect(&self, other: &Path) -> Path {
+        ops::overlay(self, other, ops::BooleanOp::Intersect)
+    }
+
+    fn difference(&self, other: &Path) -> Path {
+        ops::overlay(self, other, ops::BooleanOp::Difference)
+    }
+
+    fn xor(&self, other: &Path) -> Path {
+        ops::overlay(self, other, ops::BooleanOp::Xor)
+    }
+
+    fn stroke_to_fill(&self, stroke: &StrokeStyle) -> Path {
+        ops::stroke_to_fill(self, stroke)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lyon::path::Path;
+
+    use crate::unit::{FillRule, Offset, Rect, StrokeStyle};
+
+    use super::{PathBuilder, PathExt};
+
+    fn rect(left: f32, top: f32, width: f32, height: f32) -> Path {
+        PathBuilder::new()
+            .move_to(Offset::new(left, top))
+            .line_to(Offset::new(left + width, top))
+            .line_to(Offset::new(left + width, top + height))
+            .line_to(Offset::new(left, top + height))
+            .close()
+            .build()
+    }
+
+    fn assert_rect_eq(actual: Rect, expected: Rect) {
+        let close = |a: f32, b: f32| (a - b).abs() < 0.01;
+
+        assert!(
+            close(actual.left, expected.left)
+                && close(actual.top, expected.top)
+                && close(actual.width, expected.width)
+                && close(actual.height, expected.height),
+            "expected {:?} to be {:?}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn bounds() {
+        assert_rect_eq(
+            rect(10.0, 20.0, 30.0, 40.0).bounds(),
+            Rect::new(10.0, 20.0, 30.0, 40.0),
+        );
+
+        assert_eq!(Path::new().bounds(), Rect::default());
+    }
+
+    #[test]
+    fn contains() {
+        let path = rect(0.0, 0.0, 10.0, 10.0);
+
+        assert!(path.contains(Offset::new(5.0, 5.0), FillRule::NonZero));
+        assert!(!path.contains(Offset::new(15.0, 5.0), FillRule::NonZero));
+    }
+
+    #[test]
+    fn contains_respects_fill_rule() {
+        // Two overlapping squares wound in the same direction
+        let path = PathBuilder::from_svg("M0 0 H10 V10 H0 Z M2 2 H8 V8 H2 Z")
+            .unwrap()
+            .build();
+
+        assert!(path.contains(Offset::new(5.0, 5.0), FillRule::NonZero));
+        assert!(!path.contains(Offset::new(5.0, 5.0), FillRule::EvenOdd));
+        assert!(path.contains(Offset::new(1.0, 1.0), FillRule::EvenOdd));
+    }
+
+    #[test]
+    fn boolean_ops() {
+        let a = rect(0.0, 0.0, 10.0, 10.0);
+        let b = rect(5.0, 0.0, 10.0, 10.0);
+
+        let union = a.union(&b);
+        assert_rect_eq(union.bounds(), Rect::new(0.0, 0.0, 15.0, 10.0));
+        assert!(union.contains(Offset::new(12.0, 5.0), FillRule::NonZero));
+
+        let intersect = a.intersect(&b);
+        assert_rect_eq(intersect.bounds(), Rect::new(5.0, 0.0, 5.0, 10.0));
+        assert!(!intersect.contains(Offset::new(2.0, 5.0), FillRule::NonZero));
+
+        let difference = a.difference(&b);
+        assert_rect_eq(difference.bounds(), Rect::new(0.0, 0.0, 5.0, 10.0));
+        assert!(!difference.contains(Offset::new(7.0, 5.0), FillRule::NonZero));
+
+        let xor = a.xor(&b);
+        assert_rect_eq(xor.bounds(), Rect::new(0.0, 0.0, 15.0, 10.0));
+        assert!(xor.contains(Offset::new(2.0, 5.0), FillRule::NonZero));
+        assert!(!xor.contains(Offset::new(7.0, 5.0), FillRule::NonZero));
+        assert!(xor.contains(Offset::new(12.0, 5.0), FillRule::NonZero));
+    }
+
+    #[test]
+    fn disjoint_intersection_is_empty() {
+        let a = rect(0.0, 0.0, 10.0, 10.0);
+        let b = rect(20.0, 0.0, 10.0, 10.0);
+
+        assert_eq!(a.intersect(&b).iter().count(), 0);
+    }
+
+    #[test]
+    fn stroke_to_fill() {
+        let line = PathBuilder::new()
+            .move_to(Offset::new(0.0, 0.0))
+            .line_to(Offset::new(10.0, 0.0))
+            .build();
+
+        let stroked = line.stroke_to_fill(&StrokeStyle::new(4.0));
+
+        assert_rect_eq(stroked.bounds(), Rect::new(0.0, -2.0, 10.0, 4.0));
+        assert!(stroked.contains(Offset::new(5.0, 1.0), FillRule::NonZero));
+        assert!(!stroked.contains(Offset::new(5.0, 3.0), FillRule::NonZero));
+    }
+}
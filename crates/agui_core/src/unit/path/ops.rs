@@ -0,0 +1,154 @@
+use i_overlay::{
+    core::{fill_rule::FillRule, overlay_rule::OverlayRule},
+    float::single::SingleFloatOverlay,
+};
+use lyon::path::{iterator::PathIterator, Event, Path};
+
+use crate::unit::{StrokeCap, StrokeJoin, StrokeStyle};
+
+use super::{PathBuilder, PATH_TOLERANCE};
+
+pub(super) enum BooleanOp {
+    Union,
+    Intersect,
+    Difference,
+    Xor,
+}
+
+/// Combines two paths, both filled using the non-zero rule. Curves are flattened into
+/// line segments, so the resulting path consists only of closed polygons.
+pub(super) fn overlay(subject: &Path, clip: &Path, op: BooleanOp) -> Path {
+    let subject = to_contours(subject);
+    let clip = to_contours(clip);
+
+    let shapes = subject.overlay(
+        &clip,
+        match op {
+            BooleanOp::Union => OverlayRule::Union,
+            BooleanOp::Intersect => OverlayRule::Intersect,
+            BooleanOp::Difference => OverlayRule::Difference,
+            BooleanOp::Xor => OverlayRule::Xor,
+        },
+        FillRule::NonZero,
+    );
+
+    let mut builder = PathBuilder::new();
+
+    // Each shape is an outer contour followed by its holes, which are wound in the
+    // opposite direction so the result may be filled with either fill rule.
+    for contour in shapes.into_iter().flatten() {
+        let mut points = contour.into_iter();
+
+        let Some([x, y]) = points.next() else {
+            continue;
+        };
+
+        builder = builder.move_to((x, y));
+
+        for [x, y] in points {
+            builder = builder.line_to((x, y));
+        }
+
+        builder = builder.close();
+    }
+
+    builder.build()
+}
+
+/// Flattens a path into closed polygons, dropping any that can't enclose an area.
+fn to_contours(path: &Path) -> Vec<Vec<[f32; 2]>> {
+    let mut contours = Vec::new();
+    let mut contour = Vec::new();
+
+    for event in path.iter().flattened(PATH_TOLERANCE) {
+        match event {
+            Event::Begin { at } => {
+                contour = vec![[at.x, at.y]];
+            }
+
+            Event::Line { to, .. } => contour.push([to.x, to.y]),
+
+            Event::End { .. } => {
+                if contour.len() >= 3 {
+                    contours.push(std::mem::take(&mut contour));
+                }
+            }
+
+            Event::Quadratic { .. } | Event::Cubic { .. } => {
+                unreachable!("flattened paths only contain lines")
+            }
+        }
+    }
+
+    contours
+}
+
+pub(super) fn stroke_to_fill(path: &Path, stroke: &StrokeStyle) -> Path {
+    let mut style = kurbo::Stroke::new(stroke.width as f64)
+        .with_join(match stroke.join {
+            StrokeJoin::Miter => kurbo::Join::Miter,
+            StrokeJoin::Round => kurbo::Join::Round,
+            StrokeJoin::Bevel => kurbo::Join::Bevel,
+        })
+        .with_caps(match stroke.cap {
+            StrokeCap::Butt => kurbo::Cap::Butt,
+            StrokeCap::Round => kurbo::Cap::Round,
+            StrokeCap::Square => kurbo::Cap::Square,
+        })
+        .with_miter_limit(stroke.miter_limit as f64);
+
+    if stroke.is_dashed() {
+        style = style.with_dashes(
+            stroke.dash_offset as f64,
+            stroke.dash_pattern.iter().map(|length| *length as f64),
+        );
+    }
+
+    let outline = kurbo::stroke(
+        to_kurbo(path),
+        &style,
+        &kurbo::StrokeOpts::default(),
+        PATH_TOLERANCE as f64,
+    );
+
+    let point = |point: kurbo::Point| (point.x as f32, point.y as f32);
+
+    let mut builder = PathBuilder::new();
+
+    for element in outline.elements() {
+        builder = match *element {
+            kurbo::PathEl::MoveTo(to) => builder.move_to(point(to)),
+            kurbo::PathEl::LineTo(to) => builder.line_to(point(to)),
+            kurbo::PathEl::QuadTo(ctrl, to) => builder.quad_to(point(ctrl), point(to)),
+            kurbo::PathEl::CurveTo(ctrl1, ctrl2, to) => {
+                builder.cubic_to(point(ctrl1), point(ctrl2), point(to))
+            }
+            kurbo::PathEl::ClosePath => builder.close(),
+        };
+    }
+
+    builder.build()
+}
+
+fn to_kurbo(path: &Path) -> Vec<kurbo::PathEl> {
+    let point = |point: lyon::math::Point| kurbo::Point::new(point.x as f64, point.y as f64);
+
+    path.iter()
+        .filter_map(|event| match event {
+            Event::Begin { at } => Some(kurbo::PathEl::MoveTo(point(at))),
+            Event::Line { to, .. } => Some(kurbo::PathEl::LineTo(point(to))),
+            Event::Quadratic { ctrl, to, .. } => {
+                Some(kurbo::PathEl::QuadTo(point(ctrl), point(to)))
+            }
+            Event::Cubic {
+                ctrl1, ctrl2, to, ..
+            } => Some(kurbo::PathEl::CurveTo(
+                point(ctrl1),
+                point(ctrl2),
+                point(to),
+            )),
+            Event::End { close: true, .. } => Some(kurbo::PathEl::ClosePath),
+            Event::End { close: false, .. } => None,
+        })
+        .collect()
+}
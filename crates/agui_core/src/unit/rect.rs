@@ -1,7 +1,8 @@
-use super::Size;
+use super::{Offset, Size};
 
 /// Holds exact position and size values.
 #[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Rect {
     pub left: f32,
     pub top: f32,
@@ -19,10 +20,59 @@ impl Rect {
         }
     }
 
+    pub fn right(&self) -> f32 {
+        self.left + self.width
+    }
+
+    pub fn bottom(&self) -> f32 {
+        self.top + self.height
+    }
+
     pub fn contains(&self, point: (f32, f32)) -> bool {
         (point.0 >= self.left && point.0 <= self.left + self.width)
             && (point.1 >= self.top && point.1 <= self.top + self.height)
     }
+
+    /// Returns `true` if the rects overlap. Rects that only share an edge don't overlap.
+    pub fn intersects(&self, other: Rect) -> bool {
+        self.left < other.right()
+            && other.left < self.right()
+            && self.top < other.bottom()
+            && other.top < self.bottom()
+    }
+
+    /// Returns a new rect moved by `offset`.
+    pub fn translate(&self, offset: Offset) -> Self {
+        Self {
+            left: self.left + offset.x,
+            top: self.top + offset.y,
+            ..*self
+        }
+    }
+
+    /// Returns a new rect with each side pushed outwards by `amount`. A negative
+    /// amount shrinks the rect, though never below a size of zero.
+    pub fn inflate(&self, amount: f32) -> Self {
+        Self {
+            left: self.left - amount,
+            top: self.top - amount,
+            width: (self.width + amount * 2.0).max(0.0),
+            height: (self.height + amount * 2.0).max(0.0),
+        }
+    }
+
+    /// Returns the smallest rect that contains both this rect and `other`.
+    pub fn union(&self, other: Rect) -> Self {
+        let left = self.left.min(other.left);
+        let top = self.top.min(other.top);
+
+        Self {
+            left,
+            top,
+            width: self.right().max(other.right()) - left,
+            height: self.bottom().max(other.bottom()) - top,
+        }
+    }
 }
 
 impl From<Size> for Rect {
@@ -35,3 +85,42 @@ impl From<Size> for Rect {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::unit::{Offset, Rect};
+
+    #[test]
+    fn inflate() {
+        let rect = Rect::new(10.0, 10.0, 20.0, 20.0);
+
+        assert_eq!(rect.inflate(5.0), Rect::new(5.0, 5.0, 30.0, 30.0));
+        assert_eq!(rect.inflate(-15.0), Rect::new(25.0, 25.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn intersects() {
+        let rect = Rect::new(0.0, 0.0, 10.0, 10.0);
+
+        assert!(rect.intersects(Rect::new(5.0, 5.0, 10.0, 10.0)));
+        assert!(rect.intersects(Rect::new(2.0, 2.0, 1.0, 1.0)));
+
+        assert!(!rect.intersects(Rect::new(10.0, 0.0, 5.0, 5.0)));
+        assert!(!rect.intersects(Rect::new(-5.0, 20.0, 30.0, 5.0)));
+    }
+
+    #[test]
+    fn union() {
+        let rect = Rect::new(0.0, 0.0, 10.0, 10.0);
+
+        assert_eq!(
+            rect.union(Rect::new(5.0, -5.0, 10.0, 10.0)),
+            Rect::new(0.0, -5.0, 15.0, 15.0)
+        );
+
+        assert_eq!(
+            rect.union(rect.translate(Offset::new(2.0, 3.0))),
+            Rect::new(0.0, 0.0, 12.0, 13.0)
+        );
+    }
+}
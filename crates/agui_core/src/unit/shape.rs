@@ -1,12 +1,13 @@
 use lyon::{
     geom::euclid::Point2D,
-    math::{Angle, Vector},
+    math::{Angle, Transform, Vector},
     path::{builder::BorderRadii, Path, Winding},
 };
 
-use crate::unit::Rect;
+use crate::unit::{FillRule, Offset, PathExt, Rect};
 
 #[derive(Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Shape {
     #[default]
     Rect,
@@ -46,10 +47,9 @@ impl PartialEq for Shape {
                     && l_bottom_left == r_bottom_left
             }
 
-            (Self::Path(l0), Self::Path(r0)) => l0
-                .iter_with_attributes()
-                .zip(r0.iter_with_attributes())
-                .all(|(e0, e1)| e0 == e1),
+            (Self::Path(l0), Self::Path(r0)) => {
+                l0.iter_with_attributes().eq(r0.iter_with_attributes())
+            }
 
             _ => core::mem::discriminant(self) == core::mem::discriminant(other),
         }
@@ -57,6 +57,9 @@ impl PartialEq for Shape {
 }
 
 impl Shape {
+    /// Builds a path that outlines this shape when it is drawn to fill `rect`.
+    ///
+    /// Custom paths are positioned relative to the origin of `rect`.
     pub fn build_path(&self, rect: Rect) -> Path {
         match self {
             Self::Rect => {
@@ -65,7 +68,7 @@ impl Shape {
                 builder.add_rectangle(
                     &lyon::math::Box2D {
                         min: Point2D::new(rect.left, rect.top),
-                        max: Point2D::new(rect.width, rect.height),
+                        max: Point2D::new(rect.right(), rect.bottom()),
                     },
                     Winding::Positive,
                 );
@@ -84,7 +87,7 @@ impl Shape {
                 builder.add_rounded_rectangle(
                     &lyon::math::Box2D {
                         min: Point2D::new(rect.left, rect.top),
-                        max: Point2D::new(rect.width, rect.height),
+                        max: Point2D::new(rect.right(), rect.bottom()),
                     },
                     &BorderRadii {
                         top_left: top_left.max(f32::EPSILON), // Lyon sucks ass
@@ -102,8 +105,8 @@ impl Shape {
                 let mut builder = Path::builder();
 
                 builder.add_ellipse(
-                    Point2D::new(rect.left + rect.width, rect.top + rect.height),
-                    Vector::new(rect.width, rect.height),
+                    Point2D::new(rect.left + rect.width / 2.0, rect.top + rect.height / 2.0),
+                    Vector::new(rect.width / 2.0, rect.height / 2.0),
                     Angle::radians(0.0),
                     Winding::Positive,
                 );
@@ -111,7 +114,26 @@ impl Shape {
                 builder.build()
             }
 
-            Self::Path(path) => path.clone(),
+            Self::Path(path) => path
+                .clone()
+                .transformed(&Transform::translation(rect.left, rect.top)),
+        }
+    }
+
+    /// Returns whether `point` lies inside this shape when it is drawn to fill `rect`.
+    pub fn contains(&self, rect: Rect, point: Offset) -> bool {
+        match self {
+            Self::Rect => rect.contains((point.x, point.y)),
+
+            Self::Path(path) => path.contains(
+                Offset::new(point.x - rect.left, point.y - rect.top),
+                FillRule::NonZero,
+            ),
+
+            _ => {
+                rect.contains((point.x, point.y))
+                    && self.build_path(rect).contains(point, FillRule::NonZero)
+            }
         }
     }
 }
@@ -148,3 +170,75 @@ impl std::fmt::Debug for Shape {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::unit::{Offset, PathBuilder, PathExt, Rect};
+
+    use super::Shape;
+
+    #[test]
+    fn builds_paths_within_rect() {
+        let rect = Rect::new(10.0, 20.0, 30.0, 40.0);
+
+        for shape in [
+            Shape::Rect,
+            Shape::RoundedRect {
+                top_left: 5.0,
+                top_right: 5.0,
+                bottom_right: 5.0,
+                bottom_left: 5.0,
+            },
+            Shape::Circle,
+        ] {
+            let bounds = shape.build_path(rect).bounds();
+
+            assert!(
+                (bounds.left - rect.left).abs() < 0.01
+                    && (bounds.top - rect.top).abs() < 0.01
+                    && (bounds.width - rect.width).abs() < 0.01
+                    && (bounds.height - rect.height).abs() < 0.01,
+                "{:?} built a path with bounds {:?}",
+                shape,
+                bounds
+            );
+        }
+    }
+
+    #[test]
+    fn contains() {
+        let rect = Rect::new(10.0, 10.0, 20.0, 20.0);
+
+        assert!(Shape::Circle.contains(rect, Offset::new(20.0, 20.0)));
+        assert!(!Shape::Circle.contains(rect, Offset::new(11.0, 11.0)));
+
+        let triangle = Shape::Path(
+            PathBuilder::new()
+                .move_to((0.0, 0.0))
+                .line_to((20.0, 0.0))
+                .line_to((0.0, 20.0))
+                .close()
+                .build(),
+        );
+
+        assert!(triangle.contains(rect, Offset::new(12.0, 12.0)));
+        assert!(!triangle.contains(rect, Offset::new(28.0, 28.0)));
+    }
+
+    #[test]
+    fn paths_of_different_lengths_are_not_equal() {
+        let short = PathBuilder::new()
+            .move_to((0.0, 0.0))
+            .line_to((10.0, 0.0))
+            .build();
+
+        let long = PathBuilder::new()
+            .move_to((0.0, 0.0))
+            .line_to((10.0, 0.0))
+            .line_to((10.0, 10.0))
+            .build();
+
+        assert_ne!(Shape::Path(short.clone()), Shape::Path(long));
+        assert_eq!(Shape::Path(short.clone()), Shape::Path(short));
+    }
+}
@@ -6,6 +6,7 @@ use super::{Axis, Offset, Rect};
 
 /// Holds width and height values.
 #[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Size {
     pub width: f32,
     pub height: f32,
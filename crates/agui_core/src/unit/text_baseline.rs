@@ -0,0 +1,10 @@
+/// A horizontal line that text is aligned to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TextBaseline {
+    /// The horizontal line used to align the bottom of glyphs for alphabetic characters.
+    Alphabetic,
+
+    /// The horizontal line used to align ideographic characters.
+    Ideographic,
+}
@@ -1,6 +1,14 @@
+/// The direction that text, and anything laid out alongside it, flows in.
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TextDirection {
     #[default]
     LeftToRight,
     RightToLeft,
 }
+
+impl TextDirection {
+    pub fn is_rtl(&self) -> bool {
+        *self == Self::RightToLeft
+    }
+}
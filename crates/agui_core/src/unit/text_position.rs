@@ -0,0 +1,79 @@
+use std::ops::Range;
+
+/// Which side of a position in text a caret belongs to, when that position could be
+/// drawn in two places, such as at the end of a line that was wrapped.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TextAffinity {
+    /// The position belongs to the character before it, so a caret at the end of a
+    /// wrapped line is drawn at the end of that line.
+    Upstream,
+
+    /// The position belongs to the character after it, so a caret at the end of a
+    /// wrapped line is drawn at the start of the next line.
+    #[default]
+    Downstream,
+}
+
+/// A position between two characters of some text.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TextPosition {
+    /// The byte offset in the text of the character after the position.
+    pub offset: usize,
+
+    pub affinity: TextAffinity,
+}
+
+impl TextPosition {
+    pub fn new(offset: usize) -> Self {
+        Self {
+            offset,
+            affinity: TextAffinity::Downstream,
+        }
+    }
+
+    pub fn upstream(offset: usize) -> Self {
+        Self {
+            offset,
+            affinity: TextAffinity::Upstream,
+        }
+    }
+}
+
+/// A range of selected text, given as the byte offsets it was started and finished at.
+///
+/// The `extent` may come before the `base` if the selection was made backwards. If they're
+/// equal, the selection is collapsed into a caret.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TextSelection {
+    pub base: usize,
+    pub extent: usize,
+}
+
+impl TextSelection {
+    pub fn new(base: usize, extent: usize) -> Self {
+        Self { base, extent }
+    }
+
+    pub fn collapsed(offset: usize) -> Self {
+        Self::new(offset, offset)
+    }
+
+    pub fn is_collapsed(&self) -> bool {
+        self.base == self.extent
+    }
+
+    pub fn start(&self) -> usize {
+        self.base.min(self.extent)
+    }
+
+    pub fn end(&self) -> usize {
+        self.base.max(self.extent)
+    }
+
+    pub fn range(&self) -> Range<usize> {
+        self.start()..self.end()
+    }
+}
@@ -1,6 +1,7 @@
-use super::{Color, Font};
+use super::{Color, Font, TextDirection};
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TextStyle {
     pub font: Font,
 
@@ -9,6 +10,75 @@ pub struct TextStyle {
 
     pub h_align: HorizontalAlign,
     pub v_align: VerticalAlign,
+
+    /// The base direction of the text, which decides which side lines start on. If unset,
+    /// each paragraph takes the direction of its first strongly directional character.
+    pub text_direction: Option<TextDirection>,
+
+    /// The most lines the text may be laid out over. Any further text is dropped, and the
+    /// last line is ended according to `overflow`.
+    pub max_lines: Option<usize>,
+
+    /// How text that doesn't fit is shown.
+    pub overflow: TextOverflow,
+
+    /// Whether lines are wrapped when they're too wide. If not, lines only break at
+    /// explicit line breaks.
+    pub soft_wrap: bool,
+
+    /// The height of each line, as a multiple of the font size. If unset, the font's own
+    /// line height is used.
+    pub line_height: Option<f32>,
+
+    /// Extra space added after each character.
+    pub letter_spacing: f32,
+
+    /// Extra space added to each whitespace character, on top of `letter_spacing`.
+    pub word_spacing: f32,
+
+    pub font_weight: FontWeight,
+    pub font_style: FontStyle,
+    pub font_stretch: FontStretch,
+
+    pub decoration: TextDecoration,
+    pub decoration_style: TextDecorationStyle,
+
+    /// The color of the decoration. If unset, the text's color is used.
+    pub decoration_color: Option<Color>,
+}
+
+impl Default for TextStyle {
+    /// Black, 14 pixel text in the system's sans-serif font family.
+    fn default() -> Self {
+        Self {
+            font: Font::from_family("sans-serif"),
+
+            size: 14.0,
+            color: Color::from_rgb((0.0, 0.0, 0.0)),
+
+            h_align: HorizontalAlign::default(),
+            v_align: VerticalAlign::default(),
+
+            text_direction: None,
+
+            max_lines: None,
+            overflow: TextOverflow::default(),
+            soft_wrap: true,
+
+            line_height: None,
+
+            letter_spacing: 0.0,
+            word_spacing: 0.0,
+
+            font_weight: FontWeight::default(),
+            font_style: FontStyle::default(),
+            font_stretch: FontStretch::default(),
+
+            decoration: TextDecoration::NONE,
+            decoration_style: TextDecorationStyle::default(),
+            decoration_color: None,
+        }
+    }
 }
 
 impl TextStyle {
@@ -36,10 +106,77 @@ impl TextStyle {
         self.v_align = v_align;
         self
     }
+
+    pub fn text_direction(mut self, text_direction: TextDirection) -> Self {
+        self.text_direction = Some(text_direction);
+        self
+    }
+
+    pub fn max_lines(mut self, max_lines: usize) -> Self {
+        self.max_lines = Some(max_lines);
+        self
+    }
+
+    pub fn overflow(mut self, overflow: TextOverflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    pub fn soft_wrap(mut self, soft_wrap: bool) -> Self {
+        self.soft_wrap = soft_wrap;
+        self
+    }
+
+    pub fn line_height(mut self, line_height: f32) -> Self {
+        self.line_height = Some(line_height);
+        self
+    }
+
+    pub fn letter_spacing(mut self, letter_spacing: f32) -> Self {
+        self.letter_spacing = letter_spacing;
+        self
+    }
+
+    pub fn word_spacing(mut self, word_spacing: f32) -> Self {
+        self.word_spacing = word_spacing;
+        self
+    }
+
+    pub fn font_weight(mut self, font_weight: FontWeight) -> Self {
+        self.font_weight = font_weight;
+        self
+    }
+
+    pub fn font_style(mut self, font_style: FontStyle) -> Self {
+        self.font_style = font_style;
+        self
+    }
+
+    pub fn font_stretch(mut self, font_stretch: FontStretch) -> Self {
+        self.font_stretch = font_stretch;
+        self
+    }
+
+    pub fn decoration(mut self, decoration: TextDecoration) -> Self {
+        self.decoration = decoration;
+        self
+    }
+
+    pub fn decoration_style(mut self, decoration_style: TextDecorationStyle) -> Self {
+        self.decoration_style = decoration_style;
+        self
+    }
+
+    pub fn decoration_color(mut self, decoration_color: Color) -> Self {
+        self.decoration_color = Some(decoration_color);
+        self
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum HorizontalAlign {
+    /// Aligned to the side that lines start on, which is the right for right-to-left text.
     Start,
     Center,
     End,
@@ -52,6 +189,7 @@ impl Default for HorizontalAlign {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum VerticalAlign {
     Top,
     Center,
@@ -63,3 +201,167 @@ impl Default for VerticalAlign {
         Self::Top
     }
 }
+
+/// The thickness of a font's strokes, from 100 (thin) to 900 (black).
+///
+/// Fonts loaded from bytes only have the weight they were designed with, so the weight
+/// only takes effect when a face is picked from a font family.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FontWeight(pub u16);
+
+impl FontWeight {
+    pub const THIN: Self = Self(100);
+    pub const EXTRA_LIGHT: Self = Self(200);
+    pub const LIGHT: Self = Self(300);
+    pub const NORMAL: Self = Self(400);
+    pub const MEDIUM: Self = Self(500);
+    pub const SEMI_BOLD: Self = Self(600);
+    pub const BOLD: Self = Self(700);
+    pub const EXTRA_BOLD: Self = Self(800);
+    pub const BLACK: Self = Self(900);
+}
+
+impl Default for FontWeight {
+    fn default() -> Self {
+        Self::NORMAL
+    }
+}
+
+/// The lines drawn over text, in the text's color.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TextDecoration {
+    pub underline: bool,
+    pub overline: bool,
+    pub line_through: bool,
+}
+
+impl TextDecoration {
+    pub const NONE: Self = Self {
+        underline: false,
+        overline: false,
+        line_through: false,
+    };
+
+    pub const UNDERLINE: Self = Self {
+        underline: true,
+        ..Self::NONE
+    };
+
+    pub const OVERLINE: Self = Self {
+        overline: true,
+        ..Self::NONE
+    };
+
+    pub const LINE_THROUGH: Self = Self {
+        line_through: true,
+        ..Self::NONE
+    };
+
+    pub fn is_none(&self) -> bool {
+        *self == Self::NONE
+    }
+
+    /// Returns a decoration that draws the lines of both `self` and `other`.
+    pub fn union(self, other: Self) -> Self {
+        Self {
+            underline: self.underline || other.underline,
+            overline: self.overline || other.overline,
+            line_through: self.line_through || other.line_through,
+        }
+    }
+}
+
+/// The slant of a font.
+///
+/// Like [`FontWeight`], this only takes effect when a face is picked from a font family.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FontStyle {
+    #[default]
+    Normal,
+
+    /// A face designed to be slanted, usually with differently shaped glyphs.
+    Italic,
+
+    /// An upright face that's been slanted.
+    Oblique,
+}
+
+/// How condensed or expanded a font's glyphs are.
+///
+/// Like [`FontWeight`], this only takes effect when a face is picked from a font family.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FontStretch {
+    UltraCondensed,
+    ExtraCondensed,
+    Condensed,
+    SemiCondensed,
+    #[default]
+    Normal,
+    SemiExpanded,
+    Expanded,
+    ExtraExpanded,
+    UltraExpanded,
+}
+
+impl FontStretch {
+    /// Returns the width of the glyphs as a percentage of the normal width.
+    pub fn to_percentage(self) -> f32 {
+        match self {
+            Self::UltraCondensed => 50.0,
+            Self::ExtraCondensed => 62.5,
+            Self::Condensed => 75.0,
+            Self::SemiCondensed => 87.5,
+            Self::Normal => 100.0,
+            Self::SemiExpanded => 112.5,
+            Self::Expanded => 125.0,
+            Self::ExtraExpanded => 150.0,
+            Self::UltraExpanded => 200.0,
+        }
+    }
+}
+
+/// How the lines of a [`TextDecoration`] are drawn.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TextDecorationStyle {
+    #[default]
+    Solid,
+    Double,
+    Dotted,
+    Dashed,
+    Wavy,
+}
+
+/// How text that doesn't fit within its bounds, or within its maximum number of lines, is
+/// shown.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TextOverflow {
+    /// Cut the text off at its bounds.
+    #[default]
+    Clip,
+
+    /// Fade the text out towards its bounds.
+    Fade,
+
+    /// Replace the text that doesn't fit with an ellipsis.
+    ///
+    /// Lines that are too wide are shortened at the given position, while the last line
+    /// before any lines dropped by `max_lines` always ends with an ellipsis.
+    Ellipsis(EllipsisPosition),
+}
+
+/// Where an ellipsis replaces text that doesn't fit on a line.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EllipsisPosition {
+    Start,
+    Middle,
+
+    #[default]
+    End,
+}
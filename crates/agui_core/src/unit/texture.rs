@@ -3,6 +3,7 @@ use std::sync::Arc;
 use url::Url;
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Texture {
     inner: Arc<TextureData>,
 }
@@ -18,6 +19,21 @@ impl Texture {
         Self::new(TextureData::Bytes(bytes))
     }
 
+    /// Creates a texture from decoded, non-premultiplied RGBA pixels.
+    pub fn from_rgba8(width: u32, height: u32, pixels: Vec<u8>) -> Self {
+        debug_assert_eq!(
+            pixels.len(),
+            width as usize * height as usize * 4,
+            "pixel data does not match the texture dimensions"
+        );
+
+        Self::new(TextureData::Rgba8 {
+            width,
+            height,
+            pixels,
+        })
+    }
+
     pub fn from_url(url: Url) -> Self {
         Self::new(TextureData::Url(url))
     }
@@ -25,10 +41,106 @@ impl Texture {
     pub fn parse_url(url: &str) -> Result<Self, url::ParseError> {
         Ok(Self::from_url(Url::parse(url)?))
     }
+
+    pub fn data(&self) -> &TextureData {
+        &self.inner
+    }
+
+    /// Returns the width and height of the texture in pixels, if it has been decoded.
+    pub fn dimensions(&self) -> Option<(u32, u32)> {
+        match self.data() {
+            TextureData::Rgba8 { width, height, .. } => Some((*width, *height)),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TextureData {
+    /// Encoded image data, such as the contents of a PNG file.
     Bytes(Vec<u8>),
+
     Url(Url),
+
+    /// Decoded, non-premultiplied RGBA pixels, row by row.
+    Rgba8 {
+        width: u32,
+        height: u32,
+        pixels: Vec<u8>,
+    },
+}
+
+/// Identifies a texture by the identity of its data rather than its contents, so that
+/// textures are hashed and compared without reading their pixels.
+///
+/// Textures created separately from equal data have different identities, unless they
+/// refer to the same URL.
+#[derive(Debug, Clone)]
+pub struct TextureId(Texture);
+
+impl Texture {
+    pub fn id(&self) -> TextureId {
+        TextureId(self.clone())
+    }
+}
+
+impl TextureId {
+    pub fn texture(&self) -> &Texture {
+        &self.0
+    }
+}
+
+impl PartialEq for TextureId {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.0.data(), other.0.data()) {
+            (TextureData::Url(a), TextureData::Url(b)) => a == b,
+
+            _ => Arc::ptr_eq(&self.0.inner, &other.0.inner),
+        }
+    }
+}
+
+impl Eq for TextureId {}
+
+impl std::hash::Hash for TextureId {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        match self.0.data() {
+            TextureData::Url(url) => url.hash(state),
+
+            // The id holds onto the data, so its address can't be reused by another texture
+            _ => Arc::as_ptr(&self.0.inner).hash(state),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Texture;
+
+    #[test]
+    fn ids_compare_the_identity_of_texture_data() {
+        let texture = Texture::from_bytes(vec![0; 512]);
+
+        assert_eq!(texture.id(), texture.clone().id());
+
+        // Equal, but created separately
+        assert_eq!(texture, Texture::from_bytes(vec![0; 512]));
+        assert_ne!(texture.id(), Texture::from_bytes(vec![0; 512]).id());
+
+        let url = Texture::parse_url("https://example.com/image.png").unwrap();
+
+        assert_eq!(
+            url.id(),
+            Texture::parse_url("https://example.com/image.png")
+                .unwrap()
+                .id()
+        );
+        assert_ne!(
+            url.id(),
+            Texture::parse_url("https://example.com/other.png")
+                .unwrap()
+                .id()
+        );
+    }
 }
@@ -13,8 +13,10 @@ use agui_core::{
     callback::strategies::CallbackStrategy,
     element::{deferred::resolver::DeferredResolver, ElementCallbackContext, ElementId},
     engine::{elements::ElementTree, rendering::RenderingTree},
+    input::InputEvent,
     reactivity::{BuildError, SpawnAndInflateError},
     render::RenderObjectId,
+    task::clock::Clock,
     widget::IntoWidget,
 };
 use rustc_hash::{FxHashSet, FxHasher};
@@ -66,6 +68,13 @@ pub struct LocalEngineExecutor {
 
 impl Default for LocalEngineExecutor {
     fn default() -> Self {
+        Self::with_clock(Clock::default())
+    }
+}
+
+impl LocalEngineExecutor {
+    /// Creates an executor whose async tasks follow the given clock.
+    pub fn with_clock(clock: Clock) -> Self {
         let (callback_tx, callback_rx) = mpsc::channel();
 
         let (needs_build_tx, needs_build_rx) = mpsc::channel();
@@ -92,6 +101,7 @@ impl Default for LocalEngineExecutor {
             render_update_tx,
 
             spawner: pool.spawner(),
+            clock,
         };
 
         Self {
@@ -121,11 +131,17 @@ impl Default for LocalEngineExecutor {
             render_update_rx,
         }
     }
-}
 
-impl LocalEngineExecutor {
     pub fn with_root(root: impl IntoWidget) -> Result<Self, SpawnAndInflateError<ElementId>> {
-        let mut executor = Self::default();
+        Self::with_root_and_clock(root, Clock::default())
+    }
+
+    /// Inflates `root` in an executor whose async tasks follow the given clock.
+    pub fn with_root_and_clock(
+        root: impl IntoWidget,
+        clock: Clock,
+    ) -> Result<Self, SpawnAndInflateError<ElementId>> {
+        let mut executor = Self::with_clock(clock);
 
         let mut spawned_elements = VecDeque::<ElementId>::default();
 
@@ -189,6 +205,20 @@ impl LocalEngineExecutor {
 }
 
 impl LocalEngineExecutor {
+    pub fn element_tree(&self) -> &ElementTree {
+        &self.element_tree
+    }
+
+    pub fn rendering_tree(&self) -> &RenderingTree {
+        &self.rendering_tree
+    }
+
+    /// Sends an input event to the render objects that should receive it. Any callbacks
+    /// it causes to be invoked are executed during the next update.
+    pub fn dispatch_event(&mut self, event: impl Into<InputEvent>) {
+        self.rendering_tree.dispatch_event(event.into());
+    }
+
     #[tracing::instrument(level = "debug", skip(self))]
     fn update_widgets(&mut self) {
         tracing::trace!("widget update started");
@@ -465,20 +495,24 @@ impl EngineExecutor for LocalEngineExecutor {
 
     fn run_until_stalled(&mut self) {
         'update_tree: loop {
-            let update_future = self.element_update_rx.wait().fuse();
-            let render_future = self.render_update_rx.wait().fuse();
+            let mut update_future = self.element_update_rx.wait().fuse();
+            let mut render_future = self.render_update_rx.wait().fuse();
 
             self.update();
 
             // Run futures until no more progress can be made and no more tree updates are
             // pending.
             loop {
-                if !self.pool.try_run_one() {
-                    return;
+                // The update itself may have queued more work, such as callbacks that
+                // invoke other callbacks.
+                if (&mut update_future).now_or_never().is_some()
+                    || (&mut render_future).now_or_never().is_some()
+                {
+                    continue 'update_tree;
                 }
 
-                if update_future.is_terminated() || render_future.is_terminated() {
-                    continue 'update_tree;
+                if !self.pool.try_run_one() {
+                    return;
                 }
             }
         }
@@ -10,7 +10,7 @@ use agui_core::{
         rendering::scheduler::{CreateRenderingTask, RenderingSchedulerStrategy},
     },
     render::RenderObjectId,
-    task::{error::TaskError, TaskHandle},
+    task::{clock::Clock, error::TaskError, TaskHandle},
 };
 
 #[derive(Clone)]
@@ -25,6 +25,7 @@ pub struct LocalScheduler {
     pub render_update_tx: notify::Flag,
 
     pub spawner: LocalSpawner,
+    pub clock: Clock,
 }
 
 impl ElementSchedulerStrategy for LocalScheduler {
@@ -49,6 +50,7 @@ impl ElementSchedulerStrategy for LocalScheduler {
                 needs_build_tx: self.needs_build_tx.clone(),
                 element_update_tx: self.element_update_tx.clone(),
             })
+            .with_clock(self.clock.clone())
             .into_future();
 
         match self.spawner.spawn_local_with_handle(fut) {
@@ -89,6 +91,7 @@ impl RenderingSchedulerStrategy for LocalScheduler {
 
                 render_update_tx: self.render_update_tx.clone(),
             })
+            .with_clock(self.clock.clone())
             .into_future();
 
         match self.spawner.spawn_local_with_handle(fut) {
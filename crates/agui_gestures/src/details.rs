@@ -0,0 +1,34 @@
+use agui_core::unit::Offset;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TapDetails {
+    /// The position of the pointer relative to the root of the tree.
+    pub position: Offset,
+
+    /// The position of the pointer relative to the gesture detector.
+    pub local_position: Offset,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DragStartDetails {
+    /// The position the pointer went down at, relative to the root of the tree.
+    pub position: Offset,
+
+    /// The position the pointer went down at, relative to the gesture detector.
+    pub local_position: Offset,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DragUpdateDetails {
+    /// How far the pointer has moved since the previous update.
+    pub delta: Offset,
+
+    pub position: Offset,
+    pub local_position: Offset,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DragEndDetails {
+    /// The velocity of the pointer when it was released, in logical pixels per second.
+    pub velocity: Offset,
+}
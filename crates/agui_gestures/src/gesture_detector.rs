@@ -0,0 +1,386 @@
+use std::time::Duration;
+
+use agui_core::{
+    callback::Callback,
+    input::{PointerButton, PointerEvent, PointerEventKind, PointerId},
+    unit::Offset,
+    widget::Widget,
+};
+use agui_elements::stateful::{StatefulBuildContext, StatefulWidget, WidgetState};
+use agui_macros::StatefulWidget;
+use agui_primitives::listener::{HitTestBehavior, Listener};
+
+use crate::details::{DragEndDetails, DragStartDetails, DragUpdateDetails, TapDetails};
+
+/// How far a pointer may move after going down before it's considered a drag rather
+/// than a tap.
+pub const TOUCH_SLOP: f32 = 18.0;
+
+/// The longest time between two taps for them to be considered a double tap.
+pub const DOUBLE_TAP_TIMEOUT: Duration = Duration::from_millis(300);
+
+/// The furthest apart two taps may be for them to be considered a double tap.
+pub const DOUBLE_TAP_SLOP: f32 = 100.0;
+
+/// Recognizes taps and drags made with the primary pointer button within its child.
+///
+/// Only a single pointer is tracked at a time. Once a pointer moves further than
+/// [`TOUCH_SLOP`] from where it went down, any pending tap is cancelled and the gesture
+/// becomes a drag.
+#[derive(StatefulWidget, Debug)]
+#[props(default)]
+pub struct GestureDetector {
+    #[prop(default = HitTestBehavior::Opaque)]
+    pub behavior: HitTestBehavior,
+
+    pub on_tap_down: Option<Callback<TapDetails>>,
+    pub on_tap_up: Option<Callback<TapDetails>>,
+    pub on_tap: Option<Callback<()>>,
+    pub on_tap_cancel: Option<Callback<()>>,
+
+    pub on_double_tap: Option<Callback<()>>,
+
+    pub on_pan_start: Option<Callback<DragStartDetails>>,
+    pub on_pan_update: Option<Callback<DragUpdateDetails>>,
+    pub on_pan_end: Option<Callback<DragEndDetails>>,
+
+    #[prop(into)]
+    pub child: Option<Widget>,
+}
+
+impl StatefulWidget for GestureDetector {
+    type State = GestureDetectorState;
+
+    fn create_state(&self) -> Self::State {
+        GestureDetectorState::default()
+    }
+}
+
+#[derive(Default)]
+pub struct GestureDetectorState {
+    tracking: Option<TrackedPointer>,
+
+    /// Where and when the last tap occurred, if it may still become a double tap.
+    last_tap: Option<(Offset, Duration)>,
+}
+
+struct TrackedPointer {
+    pointer: PointerId,
+
+    down_position: Offset,
+
+    last_position: Offset,
+    last_timestamp: Duration,
+
+    velocity: Offset,
+
+    is_dragging: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Gesture {
+    TapDown(TapDetails),
+    TapUp(TapDetails),
+    Tap,
+    TapCancel,
+    DoubleTap,
+    PanStart(DragStartDetails),
+    PanUpdate(DragUpdateDetails),
+    PanEnd(DragEndDetails),
+}
+
+impl GestureDetectorState {
+    fn handle_pointer_event(&mut self, event: PointerEvent) -> Vec<Gesture> {
+        let mut gestures = Vec::new();
+
+        if event.kind == PointerEventKind::Down {
+            if self.tracking.is_none() && event.button == PointerButton::Primary {
+                self.tracking = Some(TrackedPointer {
+                    pointer: event.pointer,
+
+                    down_position: event.position,
+
+                    last_position: event.position,
+                    last_timestamp: event.timestamp,
+
+                    velocity: Offset::ZERO,
+
+                    is_dragging: false,
+                });
+
+                gestures.push(Gesture::TapDown(TapDetails {
+                    position: event.position,
+                    local_position: event.local_position,
+                }));
+            }
+
+            return gestures;
+        }
+
+        let Some(tracking) = self
+            .tracking
+            .as_mut()
+            .filter(|tracking| tracking.pointer == event.pointer)
+        else {
+            return gestures;
+        };
+
+        match event.kind {
+            PointerEventKind::Move => {
+                if !tracking.is_dragging
+                    && (event.position - tracking.down_position).distance() > TOUCH_SLOP
+                {
+                    tracking.is_dragging = true;
+
+                    // The tap can no longer happen, so a pending double tap can't either.
+                    self.last_tap = None;
+
+                    gestures.push(Gesture::TapCancel);
+
+                    let offset_from_down = event.position - tracking.down_position;
+
+                    gestures.push(Gesture::PanStart(DragStartDetails {
+                        position: tracking.down_position,
+                        local_position: event.local_position - offset_from_down,
+                    }));
+                }
+
+                if tracking.is_dragging {
+                    let delta = event.position - tracking.last_position;
+
+                    let elapsed = event.timestamp.saturating_sub(tracking.last_timestamp);
+
+                    if !elapsed.is_zero() {
+                        tracking.velocity = delta / elapsed.as_secs_f32();
+                    }
+
+                    gestures.push(Gesture::PanUpdate(DragUpdateDetails {
+                        delta,
+
+                        position: event.position,
+                        local_position: event.local_position,
+                    }));
+                }
+
+                tracking.last_position = event.position;
+                tracking.last_timestamp = event.timestamp;
+            }
+
+            PointerEventKind::Up => {
+                let tracking = self.tracking.take().expect("pointer is being tracked");
+
+                if tracking.is_dragging {
+                    gestures.push(Gesture::PanEnd(DragEndDetails {
+                        velocity: tracking.velocity,
+                    }));
+                } else {
+                    gestures.push(Gesture::TapUp(TapDetails {
+                        position: event.position,
+                        local_position: event.local_position,
+                    }));
+
+                    gestures.push(Gesture::Tap);
+
+                    let is_double_tap =
+                        self.last_tap
+                            .is_some_and(|(last_position, last_timestamp)| {
+                                event.timestamp.saturating_sub(last_timestamp) <= DOUBLE_TAP_TIMEOUT
+                                    && (event.position - last_position).distance()
+                                        <= DOUBLE_TAP_SLOP
+                            });
+
+                    if is_double_tap {
+                        self.last_tap = None;
+
+                        gestures.push(Gesture::DoubleTap);
+                    } else {
+                        self.last_tap = Some((event.position, event.timestamp));
+                    }
+                }
+            }
+
+            PointerEventKind::Cancel => {
+                let tracking = self.tracking.take().expect("pointer is being tracked");
+
+                if tracking.is_dragging {
+                    gestures.push(Gesture::PanEnd(DragEndDetails {
+                        velocity: Offset::ZERO,
+                    }));
+                } else {
+                    gestures.push(Gesture::TapCancel);
+                }
+            }
+
+            PointerEventKind::Down | PointerEventKind::Scroll { .. } => {}
+        }
+
+        gestures
+    }
+}
+
+impl WidgetState for GestureDetectorState {
+    type Widget = GestureDetector;
+
+    fn build(&mut self, ctx: &mut StatefulBuildContext<Self>) -> Widget {
+        let on_pointer = ctx.callback(|ctx, event: PointerEvent| {
+            for gesture in ctx.state.handle_pointer_event(event) {
+                dispatch_gesture(ctx.widget, gesture);
+            }
+        });
+
+        Listener {
+            behavior: ctx.widget.behavior,
+
+            on_pointer_down: Some(on_pointer.clone()),
+            on_pointer_move: Some(on_pointer.clone()),
+            on_pointer_up: Some(on_pointer.clone()),
+            on_pointer_cancel: Some(on_pointer),
+            on_pointer_scroll: None,
+
+            child: ctx.widget.child.clone(),
+        }
+        .into()
+    }
+}
+
+fn dispatch_gesture(widget: &GestureDetector, gesture: Gesture) {
+    match gesture {
+        Gesture::TapDown(details) => call(&widget.on_tap_down, details),
+        Gesture::TapUp(details) => call(&widget.on_tap_up, details),
+        Gesture::Tap => call(&widget.on_tap, ()),
+        Gesture::TapCancel => call(&widget.on_tap_cancel, ()),
+        Gesture::DoubleTap => call(&widget.on_double_tap, ()),
+        Gesture::PanStart(details) => call(&widget.on_pan_start, details),
+        Gesture::PanUpdate(details) => call(&widget.on_pan_update, details),
+        Gesture::PanEnd(details) => call(&widget.on_pan_end, details),
+    }
+}
+
+fn call<A>(callback: &Option<Callback<A>>, arg: A)
+where
+    A: std::any::Any + Send,
+{
+    if let Some(callback) = callback {
+        callback.call(arg);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use agui_core::{
+        input::{PointerButton, PointerEvent, PointerId},
+        unit::Offset,
+    };
+
+    use super::{Gesture, GestureDetectorState, DOUBLE_TAP_TIMEOUT, TOUCH_SLOP};
+
+    fn gestures(state: &mut GestureDetectorState, events: &[PointerEvent]) -> Vec<Gesture> {
+        events
+            .iter()
+            .flat_map(|event| state.handle_pointer_event(*event))
+            .collect()
+    }
+
+    #[test]
+    fn recognizes_taps() {
+        let mut state = GestureDetectorState::default();
+
+        let gestures = gestures(
+            &mut state,
+            &[
+                PointerEvent::down(Offset::new(10.0, 10.0)),
+                PointerEvent::moved(Offset::new(12.0, 10.0)),
+                PointerEvent::up(Offset::new(12.0, 10.0)),
+            ],
+        );
+
+        assert!(matches!(gestures[0], Gesture::TapDown(_)));
+        assert!(matches!(gestures[1], Gesture::TapUp(_)));
+        assert_eq!(gestures[2], Gesture::Tap);
+        assert_eq!(gestures.len(), 3);
+    }
+
+    #[test]
+    fn moving_past_slop_becomes_a_drag() {
+        let mut state = GestureDetectorState::default();
+
+        let gestures = gestures(
+            &mut state,
+            &[
+                PointerEvent::down(Offset::new(0.0, 0.0)),
+                PointerEvent::moved(Offset::new(TOUCH_SLOP + 1.0, 0.0))
+                    .with_timestamp(Duration::from_millis(100)),
+                PointerEvent::moved(Offset::new(TOUCH_SLOP + 11.0, 0.0))
+                    .with_timestamp(Duration::from_millis(200)),
+                PointerEvent::up(Offset::new(TOUCH_SLOP + 11.0, 0.0)),
+            ],
+        );
+
+        assert!(!gestures.contains(&Gesture::Tap));
+        assert!(gestures.contains(&Gesture::TapCancel));
+
+        let total_delta = gestures
+            .iter()
+            .filter_map(|gesture| match gesture {
+                Gesture::PanUpdate(details) => Some(details.delta),
+                _ => None,
+            })
+            .fold(Offset::ZERO, |total, delta| total + delta);
+
+        assert_eq!(total_delta, Offset::new(TOUCH_SLOP + 11.0, 0.0));
+
+        let Some(Gesture::PanEnd(details)) = gestures.last() else {
+            panic!("the drag should have ended");
+        };
+
+        assert_eq!(details.velocity, Offset::new(100.0, 0.0));
+    }
+
+    #[test]
+    fn recognizes_double_taps() {
+        let mut state = GestureDetectorState::default();
+
+        let tap_at = |timestamp: Duration| {
+            [
+                PointerEvent::down(Offset::ZERO).with_timestamp(timestamp),
+                PointerEvent::up(Offset::ZERO).with_timestamp(timestamp),
+            ]
+        };
+
+        assert!(!gestures(&mut state, &tap_at(Duration::ZERO)).contains(&Gesture::DoubleTap));
+
+        assert!(
+            gestures(&mut state, &tap_at(Duration::from_millis(100))).contains(&Gesture::DoubleTap)
+        );
+
+        // A third tap starts over, rather than being a second double tap
+        assert!(!gestures(&mut state, &tap_at(Duration::from_millis(200)))
+            .contains(&Gesture::DoubleTap));
+
+        assert!(!gestures(
+            &mut state,
+            &tap_at(Duration::from_millis(200) + DOUBLE_TAP_TIMEOUT * 2)
+        )
+        .contains(&Gesture::DoubleTap));
+    }
+
+    #[test]
+    fn ignores_other_pointers_and_buttons() {
+        let mut state = GestureDetectorState::default();
+
+        let gestures = gestures(
+            &mut state,
+            &[
+                PointerEvent::down(Offset::ZERO).with_button(PointerButton::Secondary),
+                PointerEvent::up(Offset::ZERO).with_button(PointerButton::Secondary),
+                PointerEvent::down(Offset::ZERO),
+                PointerEvent::up(Offset::ZERO).with_pointer(PointerId(1)),
+            ],
+        );
+
+        assert_eq!(gestures.len(), 1);
+        assert!(matches!(gestures[0], Gesture::TapDown(_)));
+    }
+}
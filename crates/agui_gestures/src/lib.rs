@@ -1 +1,2 @@
-
+pub mod details;
+pub mod gesture_detector;
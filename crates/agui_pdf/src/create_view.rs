@@ -0,0 +1,72 @@
+use std::rc::Rc;
+
+use agui_core::{
+    element::{Element, ElementBuilder},
+    widget::{IntoWidget, Widget},
+};
+use agui_macros::WidgetProps;
+use agui_renderer::view::{ViewWidget, ViewWidgetElement};
+
+use crate::{
+    pages::PdfScope,
+    renderer::PdfRenderer,
+    view::{PdfView, PdfViewHandle},
+};
+
+#[derive(WidgetProps)]
+pub struct CreatePdfView<BuilderFn>
+where
+    BuilderFn: Fn(&PdfViewHandle) -> Widget + Clone + 'static,
+{
+    pub renderer: PdfRenderer,
+
+    pub builder: BuilderFn,
+}
+
+impl<BuilderFn> IntoWidget for CreatePdfView<BuilderFn>
+where
+    BuilderFn: Fn(&PdfViewHandle) -> Widget + Clone + 'static,
+{
+    fn into_widget(self) -> Widget {
+        Widget::new(self)
+    }
+}
+
+impl<BuilderFn> ElementBuilder for CreatePdfView<BuilderFn>
+where
+    BuilderFn: Fn(&PdfViewHandle) -> Widget + Clone + 'static,
+{
+    type Element = ViewWidgetElement<Self>;
+
+    fn create_element(self: Rc<Self>) -> Element
+    where
+        Self: Sized,
+    {
+        Element::new_view(ViewWidgetElement::new(self))
+    }
+}
+
+impl<BuilderFn> ViewWidget for CreatePdfView<BuilderFn>
+where
+    BuilderFn: Fn(&PdfViewHandle) -> Widget + Clone + 'static,
+{
+    type View = PdfView;
+    type ViewHandle = PdfViewHandle;
+
+    fn create_view(&self) -> (Self::View, Self::ViewHandle) {
+        self.renderer.new_view()
+    }
+
+    fn is_same_view(&self, other: &Self) -> bool {
+        self.renderer == other.renderer
+    }
+
+    fn build(&self, view_handle: &Self::ViewHandle) -> Widget {
+        PdfScope {
+            page_layout: view_handle.page_layout(),
+
+            child: (self.builder)(view_handle),
+        }
+        .into_widget()
+    }
+}
@@ -0,0 +1,189 @@
+use std::{cell::RefCell, rc::Rc};
+
+use agui_core::{
+    unit::Size,
+    widget::{IntoWidget, Widget},
+};
+use agui_executor::{EngineExecutor, LocalEngineExecutor};
+use agui_primitives::sized_box::SizedBox;
+
+use crate::{create_view::CreatePdfView, renderer::PdfRenderer, view::PdfViewHandle};
+
+pub mod create_view;
+mod pages;
+mod render;
+pub mod renderer;
+pub mod view;
+
+pub use pages::{MultiPage, PageBreak};
+
+/// The size of each page in a document, in points.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PageConfiguration {
+    pub size: Size,
+}
+
+impl PageConfiguration {
+    pub const A4: Self = Self {
+        size: Size {
+            width: 595.276,
+            height: 841.89,
+        },
+    };
+
+    pub const LETTER: Self = Self {
+        size: Size {
+            width: 612.0,
+            height: 792.0,
+        },
+    };
+}
+
+/// Builds the widget against the configured page size, and returns it as a PDF document.
+///
+/// A [`MultiPage`] within the widget splits its children across as many pages as they
+/// need; otherwise, the document has a single page. Text is drawn with the fonts it was
+/// laid out with, which are embedded in the document.
+pub fn render_to_pdf(widget: impl IntoWidget, config: PageConfiguration) -> Vec<u8> {
+    let child = widget.into_widget();
+
+    let view_handle = Rc::new(RefCell::new(None::<PdfViewHandle>));
+
+    let root = CreatePdfView {
+        renderer: PdfRenderer::new(),
+
+        builder: {
+            let view_handle = Rc::clone(&view_handle);
+
+            move |handle: &PdfViewHandle| -> Widget {
+                view_handle.borrow_mut().replace(handle.clone());
+
+                SizedBox {
+                    width: Some(config.size.width),
+                    height: Some(config.size.height),
+
+                    child: Some(child.clone()),
+                }
+                .into_widget()
+            }
+        },
+    };
+
+    let mut executor = LocalEngineExecutor::with_root(root).expect("failed to build tree");
+
+    executor.run_until_stalled();
+
+    let view_handle = view_handle
+        .borrow_mut()
+        .take()
+        .expect("the view was never created");
+
+    view_handle.document()
+}
+
+#[cfg(test)]
+mod tests {
+    use agui_core::{
+        unit::{Color, Size},
+        widget::IntoWidget,
+    };
+    use agui_primitives::{colored_box::ColoredBox, sized_box::SizedBox};
+
+    use crate::{render_to_pdf, MultiPage, PageBreak, PageConfiguration};
+
+    const PAGE: PageConfiguration = PageConfiguration {
+        size: Size {
+            width: 100.0,
+            height: 100.0,
+        },
+    };
+
+    fn page_count(document: &[u8]) -> usize {
+        String::from_utf8_lossy(document)
+            .matches("/Type /Page ")
+            .count()
+    }
+
+    fn block(height: f32) -> SizedBox {
+        SizedBox {
+            width: None,
+            height: Some(height),
+
+            child: Some(
+                ColoredBox {
+                    color: Color::from_rgb((1.0, 0.0, 0.0)),
+
+                    child: None,
+                }
+                .into_widget(),
+            ),
+        }
+    }
+
+    #[test]
+    fn renders_a_single_page() {
+        let document = render_to_pdf(
+            ColoredBox {
+                color: Color::from_rgb((1.0, 0.0, 0.0)),
+
+                child: None,
+            },
+            PageConfiguration::A4,
+        );
+
+        assert!(document.starts_with(b"%PDF-1.7"));
+        assert!(document.ends_with(b"%%EOF\n"));
+
+        assert_eq!(page_count(&document), 1);
+        assert!(String::from_utf8_lossy(&document).contains("/MediaBox [0 0 595.276 841.89]"));
+    }
+
+    #[test]
+    fn moves_children_onto_new_pages() {
+        let document = render_to_pdf(
+            MultiPage::builder()
+                .children(vec![
+                    block(60.0).into_widget(),
+                    block(60.0).into_widget(),
+                    block(30.0).into_widget(),
+                ])
+                .build(),
+            PAGE,
+        );
+
+        assert_eq!(page_count(&document), 2);
+    }
+
+    #[test]
+    fn breaks_pages() {
+        let document = render_to_pdf(
+            MultiPage::builder()
+                .children(vec![
+                    block(10.0).into_widget(),
+                    PageBreak.into_widget(),
+                    block(10.0).into_widget(),
+                    PageBreak.into_widget(),
+                    block(10.0).into_widget(),
+                ])
+                .build(),
+            PAGE,
+        );
+
+        assert_eq!(page_count(&document), 3);
+    }
+
+    #[test]
+    fn splits_tall_children() {
+        let document = render_to_pdf(
+            MultiPage::builder()
+                .header(block(10.0).into_widget())
+                .footer(block(10.0).into_widget())
+                .children(vec![block(200.0).into_widget()])
+                .build(),
+            PAGE,
+        );
+
+        // Each page has 80 points of content between the header and footer
+        assert_eq!(page_count(&document), 3);
+    }
+}
@@ -0,0 +1,340 @@
+use std::sync::Arc;
+
+use agui_core::{
+    element::{
+        ContextDirtyRenderObject, ContextRenderObject, RenderObjectCreateContext,
+        RenderObjectUpdateContext,
+    },
+    render::{
+        object::{RenderObjectImpl, RenderObjectIntrinsicSizeContext, RenderObjectLayoutContext},
+        RenderObjectId,
+    },
+    unit::{Constraints, IntrinsicDimension, Rect, Size},
+    widget::{IntoWidget, Widget},
+};
+use agui_elements::{
+    inherited::InheritedWidget,
+    render::RenderObjectWidget,
+    stateless::{StatelessBuildContext, StatelessWidget},
+};
+use agui_macros::{InheritedWidget, RenderObjectWidget, StatelessWidget};
+use agui_primitives::sized_box::SizedBox;
+use parking_lot::Mutex;
+use rustc_hash::FxHashMap;
+
+pub(crate) type SharedPageLayout = Arc<Mutex<Option<PageLayout>>>;
+
+/// How a [`MultiPage`] split its children across pages, as of its most recent layout.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct PageLayout {
+    pub render_object_id: RenderObjectId,
+
+    /// The area of each page that the children are drawn in, relative to the
+    /// [`MultiPage`].
+    pub content: Rect,
+
+    /// Where each page starts, measured down the children as if they were laid out in a
+    /// single unbroken column.
+    pub page_tops: Vec<f32>,
+
+    /// The top and bottom of each child, measured the same way as `page_tops`.
+    pub children: FxHashMap<RenderObjectId, (f32, f32)>,
+}
+
+/// Lays out its children one after another down the page, moving on to a new page after
+/// each [`PageBreak`] or whenever a child doesn't fit in the space left on the current
+/// one. The header and footer are repeated at the top and bottom of every page.
+///
+/// Children taller than a page are split across as many pages as they need. Pages are
+/// only produced when drawn by a PDF view; elsewhere, only the first page is visible.
+#[derive(StatelessWidget, Debug)]
+#[props(default)]
+pub struct MultiPage {
+    #[prop(into)]
+    pub header: Option<Widget>,
+
+    #[prop(into)]
+    pub footer: Option<Widget>,
+
+    pub children: Vec<Widget>,
+}
+
+impl StatelessWidget for MultiPage {
+    fn build(&self, ctx: &mut StatelessBuildContext<Self>) -> Widget {
+        let page_layout = ctx
+            .depend_on_inherited_widget::<PdfScope>()
+            .map(|scope| Arc::clone(&scope.page_layout));
+
+        Paginated {
+            page_layout,
+
+            header: self.header.clone(),
+            footer: self.footer.clone(),
+
+            children: self.children.clone(),
+        }
+        .into_widget()
+    }
+}
+
+/// Starts a new page when placed directly within a [`MultiPage`]. Anywhere else, it
+/// takes up no space.
+#[derive(StatelessWidget, Debug, Default)]
+pub struct PageBreak;
+
+impl StatelessWidget for PageBreak {
+    fn build(&self, _: &mut StatelessBuildContext<Self>) -> Widget {
+        SizedBox::shrink().into_widget()
+    }
+}
+
+/// Gives any [`MultiPage`] within a PDF view somewhere to report its pages to.
+#[derive(InheritedWidget)]
+pub(crate) struct PdfScope {
+    pub page_layout: SharedPageLayout,
+
+    pub child: Widget,
+}
+
+impl InheritedWidget for PdfScope {
+    fn child(&self) -> Widget {
+        self.child.clone()
+    }
+
+    fn should_notify(&self, old_widget: &Self) -> bool {
+        !Arc::ptr_eq(&self.page_layout, &old_widget.page_layout)
+    }
+}
+
+#[derive(RenderObjectWidget, Debug)]
+struct Paginated {
+    page_layout: Option<SharedPageLayout>,
+
+    header: Option<Widget>,
+    footer: Option<Widget>,
+
+    children: Vec<Widget>,
+}
+
+impl Paginated {
+    fn breaks(&self) -> Vec<bool> {
+        self.children
+            .iter()
+            .map(|child| child.downcast::<PageBreak>().is_some())
+            .collect()
+    }
+}
+
+impl RenderObjectWidget for Paginated {
+    type RenderObject = RenderPaginated;
+
+    fn children(&self) -> Vec<Widget> {
+        self.header
+            .iter()
+            .chain(self.footer.iter())
+            .chain(self.children.iter())
+            .cloned()
+            .collect()
+    }
+
+    fn create_render_object(&self, _: &mut RenderObjectCreateContext) -> Self::RenderObject {
+        RenderPaginated {
+            page_layout: self.page_layout.clone(),
+
+            has_header: self.header.is_some(),
+            has_footer: self.footer.is_some(),
+
+            breaks: self.breaks(),
+        }
+    }
+
+    fn update_render_object(
+        &self,
+        ctx: &mut RenderObjectUpdateContext,
+        render_object: &mut Self::RenderObject,
+    ) {
+        render_object.update_page_layout(ctx, self.page_layout.clone());
+
+        render_object.update_has_header(ctx, self.header.is_some());
+        render_object.update_has_footer(ctx, self.footer.is_some());
+
+        render_object.update_breaks(ctx, self.breaks());
+    }
+}
+
+struct RenderPaginated {
+    page_layout: Option<SharedPageLayout>,
+
+    has_header: bool,
+    has_footer: bool,
+
+    /// Whether each of the children, excluding the header and footer, is a page break.
+    breaks: Vec<bool>,
+}
+
+impl RenderPaginated {
+    fn update_page_layout(
+        &mut self,
+        ctx: &mut RenderObjectUpdateContext,
+        page_layout: Option<SharedPageLayout>,
+    ) {
+        let is_same = match (&self.page_layout, &page_layout) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        };
+
+        if is_same {
+            return;
+        }
+
+        self.page_layout = page_layout;
+        ctx.mark_needs_layout();
+    }
+
+    fn update_has_header(&mut self, ctx: &mut RenderObjectUpdateContext, has_header: bool) {
+        if self.has_header == has_header {
+            return;
+        }
+
+        self.has_header = has_header;
+        ctx.mark_needs_layout();
+    }
+
+    fn update_has_footer(&mut self, ctx: &mut RenderObjectUpdateContext, has_footer: bool) {
+        if self.has_footer == has_footer {
+            return;
+        }
+
+        self.has_footer = has_footer;
+        ctx.mark_needs_layout();
+    }
+
+    fn update_breaks(&mut self, ctx: &mut RenderObjectUpdateContext, breaks: Vec<bool>) {
+        if self.breaks == breaks {
+            return;
+        }
+
+        self.breaks = breaks;
+        ctx.mark_needs_layout();
+    }
+}
+
+impl RenderObjectImpl for RenderPaginated {
+    fn intrinsic_size(
+        &self,
+        ctx: &mut RenderObjectIntrinsicSizeContext,
+        dimension: IntrinsicDimension,
+        cross_extent: f32,
+    ) -> f32 {
+        let sizes = ctx
+            .iter_children()
+            .map(|child| child.compute_intrinsic_size(dimension, cross_extent));
+
+        if dimension.is_width() {
+            sizes.fold(0.0, f32::max)
+        } else {
+            sizes.sum()
+        }
+    }
+
+    fn layout(&self, ctx: &mut RenderObjectLayoutContext, constraints: Constraints) -> Size {
+        let render_object_id = ctx.render_object_id();
+
+        let width = constraints.max_width();
+        let page_height = constraints.max_height();
+
+        let page_constraints = Constraints::new(width, width, 0.0, page_height);
+
+        let mut children = ctx.iter_children_mut();
+
+        let mut header_height = 0.0;
+        let mut footer_height = 0.0;
+
+        if self.has_header {
+            let mut header = children.next().expect("header missing during layout");
+
+            header_height = header.compute_layout(page_constraints).height;
+
+            header.set_offset((0.0, 0.0));
+        }
+
+        if self.has_footer {
+            let mut footer = children.next().expect("footer missing during layout");
+
+            footer_height = footer.compute_layout(page_constraints).height;
+
+            if page_height.is_finite() {
+                footer.set_offset((0.0, page_height - footer_height));
+            }
+        }
+
+        let content_height = (page_height - header_height - footer_height).max(0.0);
+
+        let mut page_tops = vec![0.0];
+        let mut extents = FxHashMap::default();
+
+        let mut page_top = 0.0;
+        let mut y: f32 = 0.0;
+
+        let mut index = 0;
+
+        while let Some(mut child) = children.next() {
+            let is_break = self.breaks.get(index).copied().unwrap_or(false);
+
+            index += 1;
+
+            if is_break {
+                child.layout(Constraints::tight(Size::ZERO));
+                child.set_offset((0.0, header_height + y));
+
+                page_top = y;
+                page_tops.push(page_top);
+
+                continue;
+            }
+
+            let height = child
+                .compute_layout(Constraints::new(width, width, 0.0, f32::INFINITY))
+                .height;
+
+            // Move the child to a fresh page if that's enough for it to fit
+            if y > page_top && y + height > page_top + content_height && height <= content_height {
+                page_top = y;
+                page_tops.push(page_top);
+            }
+
+            child.set_offset((0.0, header_height + y));
+
+            extents.insert(child.render_object_id(), (y, y + height));
+
+            y += height;
+
+            // Continue any child that overflows the page onto the ones that follow
+            if content_height > 0.0 {
+                while y - page_top > content_height {
+                    page_top += content_height;
+                    page_tops.push(page_top);
+                }
+            }
+        }
+
+        if let Some(page_layout) = &self.page_layout {
+            page_layout.lock().replace(PageLayout {
+                render_object_id,
+
+                content: Rect::new(0.0, header_height, width, content_height),
+
+                page_tops,
+
+                children: extents,
+            });
+        }
+
+        if page_height.is_finite() {
+            constraints.constrain(Size::new(width, page_height))
+        } else {
+            constraints.constrain(Size::new(width, header_height + y + footer_height))
+        }
+    }
+}
@@ -0,0 +1,226 @@
+use std::fmt::Write;
+
+use agui_core::unit::{
+    BlendMode, Color, FillRule, Rect, Shape, StrokeCap, StrokeJoin, StrokeStyle,
+};
+use lyon::path::Event;
+
+/// Formats a number for use in a content stream, dropping any unnecessary precision.
+pub(crate) fn num(value: f32) -> String {
+    let value = (value * 1000.0).round() / 1000.0;
+
+    // Avoid writing `-0`
+    if value == 0.0 {
+        return String::from("0");
+    }
+
+    value.to_string()
+}
+
+/// Returns the red, green, and blue components of the color, separated by spaces.
+pub(crate) fn color(color: Color) -> String {
+    format!(
+        "{} {} {}",
+        num(color.red.clamp(0.0, 1.0)),
+        num(color.green.clamp(0.0, 1.0)),
+        num(color.blue.clamp(0.0, 1.0))
+    )
+}
+
+/// Returns the operators that construct `shape` within `rect`, without painting it.
+/// Returns `None` if the shape is empty.
+pub(crate) fn shape_path(shape: &Shape, rect: Rect) -> Option<String> {
+    match shape {
+        Shape::Rect => {
+            if rect.width <= 0.0 || rect.height <= 0.0 {
+                return None;
+            }
+
+            Some(format!(
+                "{} {} {} {} re",
+                num(rect.left),
+                num(rect.top),
+                num(rect.width),
+                num(rect.height)
+            ))
+        }
+
+        Shape::Path(_) => path_ops(&shape.build_path(rect)),
+
+        shape => {
+            if rect.width <= 0.0 || rect.height <= 0.0 {
+                return None;
+            }
+
+            path_ops(&shape.build_path(rect))
+        }
+    }
+}
+
+fn path_ops(path: &lyon::path::Path) -> Option<String> {
+    let mut ops = String::new();
+
+    for event in path.iter() {
+        match event {
+            Event::Begin { at } => {
+                let _ = write!(ops, "{} {} m ", num(at.x), num(at.y));
+            }
+
+            Event::Line { to, .. } => {
+                let _ = write!(ops, "{} {} l ", num(to.x), num(to.y));
+            }
+
+            // PDF only has cubic curves, which can represent any quadratic exactly
+            Event::Quadratic { from, ctrl, to } => {
+                let ctrl1 = from + (ctrl - from) * (2.0 / 3.0);
+                let ctrl2 = to + (ctrl - to) * (2.0 / 3.0);
+
+                let _ = write!(
+                    ops,
+                    "{} {} {} {} {} {} c ",
+                    num(ctrl1.x),
+                    num(ctrl1.y),
+                    num(ctrl2.x),
+                    num(ctrl2.y),
+                    num(to.x),
+                    num(to.y)
+                );
+            }
+
+            Event::Cubic {
+                ctrl1, ctrl2, to, ..
+            } => {
+                let _ = write!(
+                    ops,
+                    "{} {} {} {} {} {} c ",
+                    num(ctrl1.x),
+                    num(ctrl1.y),
+                    num(ctrl2.x),
+                    num(ctrl2.y),
+                    num(to.x),
+                    num(to.y)
+                );
+            }
+
+            Event::End { close, .. } => {
+                if close {
+                    ops.push_str("h ");
+                }
+            }
+        }
+    }
+
+    if ops.is_empty() {
+        return None;
+    }
+
+    ops.pop();
+
+    Some(ops)
+}
+
+/// Returns the operator that fills the current path, or clips to it if `clip` is set.
+pub(crate) fn fill_op(fill_rule: FillRule, clip: bool) -> &'static str {
+    match (fill_rule, clip) {
+        (FillRule::NonZero, false) => "f",
+        (FillRule::EvenOdd, false) => "f*",
+        (FillRule::NonZero, true) => "W n",
+        (FillRule::EvenOdd, true) => "W* n",
+    }
+}
+
+/// Returns the operators that set up the stroke, excluding its paint.
+pub(crate) fn stroke_ops(style: &StrokeStyle) -> String {
+    let mut ops = format!(
+        "{} w {} J {} j {} M",
+        num(style.width),
+        match style.cap {
+            StrokeCap::Butt => 0,
+            StrokeCap::Round => 1,
+            StrokeCap::Square => 2,
+        },
+        match style.join {
+            StrokeJoin::Miter => 0,
+            StrokeJoin::Round => 1,
+            StrokeJoin::Bevel => 2,
+        },
+        num(style.miter_limit)
+    );
+
+    if style.is_dashed() {
+        let pattern = style
+            .dash_pattern
+            .iter()
+            .map(|length| num(*length))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let _ = write!(ops, " [{}] {} d", pattern, num(style.dash_offset));
+    }
+
+    ops
+}
+
+/// Returns the name of the PDF equivalent of the blend mode. Modes that PDF has no
+/// equivalent for are drawn normally.
+pub(crate) fn blend_mode(blend_mode: BlendMode) -> &'static str {
+    match blend_mode {
+        BlendMode::SourceOver => "Normal",
+
+        BlendMode::Screen => "Screen",
+        BlendMode::Overlay => "Overlay",
+        BlendMode::Darken => "Darken",
+        BlendMode::Lighten => "Lighten",
+        BlendMode::ColorDodge => "ColorDodge",
+        BlendMode::ColorBurn => "ColorBurn",
+        BlendMode::HardLight => "HardLight",
+        BlendMode::SoftLight => "SoftLight",
+        BlendMode::Difference => "Difference",
+        BlendMode::Exclusion => "Exclusion",
+        BlendMode::Multiply => "Multiply",
+        BlendMode::Hue => "Hue",
+        BlendMode::Saturation => "Saturation",
+        BlendMode::Color => "Color",
+        BlendMode::Luminosity => "Luminosity",
+
+        blend_mode => {
+            tracing::warn!("unsupported blend mode: {:?}", blend_mode);
+
+            "Normal"
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use agui_core::unit::{Rect, Shape};
+
+    use super::{num, shape_path};
+
+    #[test]
+    fn formats_numbers() {
+        assert_eq!(num(1.0), "1");
+        assert_eq!(num(0.12345), "0.123");
+        assert_eq!(num(-0.0001), "0");
+        assert_eq!(num(1e-10), "0");
+    }
+
+    #[test]
+    fn builds_paths() {
+        assert_eq!(
+            shape_path(&Shape::Rect, Rect::new(1.0, 2.0, 3.0, 4.0)),
+            Some(String::from("1 2 3 4 re"))
+        );
+
+        assert_eq!(
+            shape_path(&Shape::Rect, Rect::new(0.0, 0.0, 0.0, 10.0)),
+            None
+        );
+
+        let circle = shape_path(&Shape::Circle, Rect::new(0.0, 0.0, 10.0, 10.0)).unwrap();
+
+        assert!(circle.contains(" m "));
+        assert!(circle.contains(" c"));
+        assert!(circle.ends_with('h'));
+    }
+}
@@ -0,0 +1,1358 @@
+use std::{collections::BTreeMap, fmt::Write, sync::Arc};
+
+use agui_core::{
+    asset::AssetResolver,
+    render::canvas::{command::CanvasCommand, paint::Paint, Canvas, CanvasLayer},
+    unit::{
+        BlendMode, Bounds, BoxShadow, Color, Constraints, Font, FontId, GlyphRun, Gradient,
+        GradientExtend, GradientKind, Offset, PaintingStyle, Rect, Shape, Size, TextStyle, Texture,
+        TextureData, TextureId,
+    },
+};
+use agui_text::TextFonts;
+use rustc_hash::FxHashMap;
+
+use crate::render::{
+    convert::{self, num},
+    pdf::{ObjectRef, PdfWriter},
+};
+
+/// Writes canvases into the pages of a PDF document.
+///
+/// Each page's content stream flips the coordinate space so that its origin is at the
+/// top left, matching the canvas, and everything is positioned in page coordinates. Layers
+/// are written as transparency groups, which stay open while anything drawn within them
+/// is written.
+pub(crate) struct DocumentWriter<'fonts> {
+    fonts: &'fonts mut TextFonts,
+
+    pdf: PdfWriter,
+
+    size: Size,
+
+    pages_ref: ObjectRef,
+    resources_ref: ObjectRef,
+
+    pages: Vec<ObjectRef>,
+
+    /// The content of the page currently being written.
+    page: String,
+
+    /// The layers that are currently open, from the outermost to the innermost.
+    layers: Vec<Layer>,
+
+    next_id: usize,
+
+    /// The name of each graphics state, keyed by its alpha and blend mode.
+    graphics_states: FxHashMap<(u32, &'static str), String>,
+
+    ext_g_state_resources: Vec<(String, String)>,
+    shading_resources: Vec<(String, String)>,
+    x_object_resources: Vec<(String, ObjectRef)>,
+
+    fonts_used: FxHashMap<FontId, usize>,
+    font_resources: Vec<FontResource>,
+
+    /// The name of the image written for each texture, or `None` if it could not be
+    /// decoded.
+    textures: FxHashMap<TextureId, Option<String>>,
+}
+
+struct Layer {
+    content: String,
+
+    /// Where the layer is drawn once it's complete, or `None` if it's hidden.
+    placement: Option<LayerPlacement>,
+}
+
+struct LayerPlacement {
+    bbox: Rect,
+
+    /// The operators that clip to the layer's shape.
+    clip: String,
+
+    graphics_state: String,
+}
+
+/// A font whose data is embedded in the document.
+struct FontResource {
+    name: String,
+
+    data: Arc<Vec<u8>>,
+    index: u32,
+
+    /// The glyphs used from the font, and the character each one was drawn for if it's
+    /// known. Glyphs that were shaped ahead of time don't know their character.
+    glyphs: BTreeMap<u16, Option<char>>,
+}
+
+impl<'fonts> DocumentWriter<'fonts> {
+    pub fn new(fonts: &'fonts mut TextFonts, size: Size) -> Self {
+        let mut pdf = PdfWriter::default();
+
+        let pages_ref = pdf.reserve();
+        let resources_ref = pdf.reserve();
+
+        Self {
+            fonts,
+
+            pdf,
+
+            size,
+
+            pages_ref,
+            resources_ref,
+
+            pages: Vec::new(),
+
+            page: String::new(),
+
+            layers: Vec::new(),
+
+            next_id: 0,
+
+            graphics_states: FxHashMap::default(),
+
+            ext_g_state_resources: Vec::new(),
+            shading_resources: Vec::new(),
+            x_object_resources: Vec::new(),
+
+            fonts_used: FxHashMap::default(),
+            font_resources: Vec::new(),
+
+            textures: FxHashMap::default(),
+        }
+    }
+
+    /// Completes the document, returning its bytes.
+    pub fn finish(mut self) -> Vec<u8> {
+        if self.pages.is_empty() {
+            self.begin_page();
+            self.end_page();
+        }
+
+        let mut fonts = String::new();
+
+        for font in std::mem::take(&mut self.font_resources) {
+            let Some(font_ref) =
+                self.write_embedded_font(&font.name, &font.data, font.index, &font.glyphs)
+            else {
+                continue;
+            };
+
+            let _ = write!(fonts, "/{} {} ", font.name, font_ref);
+        }
+
+        let mut resources = String::from("<< /ProcSet [/PDF /Text /ImageB /ImageC /ImageI]");
+
+        let mut write_dict = |key: &str, entries: String| {
+            if !entries.is_empty() {
+                let _ = write!(resources, " /{} << {}>>", key, entries);
+            }
+        };
+
+        write_dict(
+            "ExtGState",
+            self.ext_g_state_resources
+                .iter()
+                .map(|(name, dict)| format!("/{} {} ", name, dict))
+                .collect(),
+        );
+
+        write_dict(
+            "Shading",
+            self.shading_resources
+                .iter()
+                .map(|(name, dict)| format!("/{} {} ", name, dict))
+                .collect(),
+        );
+
+        write_dict(
+            "XObject",
+            self.x_object_resources
+                .iter()
+                .map(|(name, object)| format!("/{} {} ", name, object))
+                .collect(),
+        );
+
+        write_dict("Font", fonts);
+
+        resources.push_str(" >>");
+
+        self.pdf.write(self.resources_ref, resources);
+
+        self.pdf.write(
+            self.pages_ref,
+            format!(
+                "<< /Type /Pages /Kids [{}] /Count {} /MediaBox [0 0 {} {}] >>",
+                self.pages
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                self.pages.len(),
+                num(self.size.width),
+                num(self.size.height)
+            ),
+        );
+
+        let catalog_ref = self.pdf.reserve();
+
+        self.pdf.write(
+            catalog_ref,
+            format!("<< /Type /Catalog /Pages {} >>", self.pages_ref),
+        );
+
+        self.pdf.finish(catalog_ref)
+    }
+
+    pub fn begin_page(&mut self) {
+        // Flip the page so that its origin is at the top left
+        self.page = format!("1 0 0 -1 0 {} cm\n", num(self.size.height));
+    }
+
+    pub fn end_page(&mut self) {
+        debug_assert!(self.layers.is_empty(), "page ended with layers still open");
+
+        let content_ref = self.pdf.reserve();
+
+        self.pdf
+            .write_stream(content_ref, "", std::mem::take(&mut self.page).as_bytes());
+
+        let page_ref = self.pdf.reserve();
+
+        self.pdf.write(
+            page_ref,
+            format!(
+                "<< /Type /Page /Parent {} /Resources {} /Contents {} >>",
+                self.pages_ref, self.resources_ref, content_ref
+            ),
+        );
+
+        self.pages.push(page_ref);
+    }
+
+    /// Clips anything written until [`pop_clip`](Self::pop_clip) is called to `rect`.
+    pub fn push_clip(&mut self, rect: Rect) {
+        let _ = writeln!(
+            self.content(),
+            "q {} {} {} {} re W n",
+            num(rect.left),
+            num(rect.top),
+            num(rect.width),
+            num(rect.height)
+        );
+    }
+
+    pub fn pop_clip(&mut self) {
+        self.content().push_str("Q\n");
+    }
+
+    /// Writes the canvas, including any of its child layers. If the canvas has a tail,
+    /// its layer is left open so that anything written afterwards is drawn within it
+    /// until [`end_canvas`](Self::end_canvas) is called.
+    pub fn begin_canvas(&mut self, origin: Offset, canvas: &Canvas) {
+        for command in &canvas.head {
+            self.write_command(origin, &canvas.paints, command);
+        }
+
+        for layer in &canvas.children {
+            let layer_origin = self.begin_layer(origin, &canvas.paints, layer);
+
+            self.begin_canvas(layer_origin, &layer.canvas);
+            self.end_canvas(&layer.canvas);
+
+            self.end_layer();
+        }
+
+        if let Some(tail) = &canvas.tail {
+            let tail_origin = self.begin_layer(origin, &canvas.paints, tail);
+
+            self.begin_canvas(tail_origin, &tail.canvas);
+        }
+    }
+
+    /// Closes the layers that were left open by [`begin_canvas`](Self::begin_canvas).
+    pub fn end_canvas(&mut self, canvas: &Canvas) {
+        if let Some(tail) = &canvas.tail {
+            self.end_canvas(&tail.canvas);
+
+            self.end_layer();
+        }
+    }
+
+    fn next_name(&mut self, prefix: &str) -> String {
+        self.next_id += 1;
+
+        format!("{}{}", prefix, self.next_id)
+    }
+
+    /// Returns the content stream currently being written to.
+    fn content(&mut self) -> &mut String {
+        match self.layers.last_mut() {
+            Some(layer) => &mut layer.content,
+            None => &mut self.page,
+        }
+    }
+
+    /// Opens a layer, returning the origin of its contents.
+    fn begin_layer(&mut self, origin: Offset, paints: &[Paint], layer: &CanvasLayer) -> Offset {
+        let paint = paints
+            .get(layer.style.paint_idx)
+            .cloned()
+            .unwrap_or_else(|| {
+                tracing::warn!("layer refers to a paint that does not exist");
+
+                Paint::default()
+            });
+
+        let origin = origin + layer.offset;
+
+        let bbox = Rect::new(
+            origin.x,
+            origin.y,
+            layer.canvas.size.width,
+            layer.canvas.size.height,
+        );
+
+        if layer.style.filter.is_some() {
+            tracing::warn!("layer filters are not supported in pdf documents");
+        }
+
+        if layer.style.backdrop_filter.is_some() {
+            tracing::warn!("backdrop filters are not supported in pdf documents");
+        }
+
+        // An empty shape hides the layer entirely
+        let placement = convert::shape_path(&layer.style.shape, bbox).map(|clip| LayerPlacement {
+            bbox,
+
+            clip,
+
+            graphics_state: self.graphics_state(paint.color.alpha, paint.blend_mode),
+        });
+
+        self.layers.push(Layer {
+            content: String::new(),
+
+            placement,
+        });
+
+        origin
+    }
+
+    /// Closes the innermost layer, drawing it into the one that contains it.
+    fn end_layer(&mut self) {
+        let layer = self.layers.pop().expect("no layer to end");
+
+        let Some(placement) = layer.placement else {
+            return;
+        };
+
+        if layer.content.is_empty() {
+            return;
+        }
+
+        let form_ref = self.pdf.reserve();
+
+        // Isolating the group ensures that its opacity and blend mode apply to its
+        // contents as a whole
+        self.pdf.write_stream(
+            form_ref,
+            &format!(
+                "/Type /XObject /Subtype /Form /BBox [{} {} {} {}] /Group << /S /Transparency /I true >> /Resources {}",
+                num(placement.bbox.left),
+                num(placement.bbox.top),
+                num(placement.bbox.right()),
+                num(placement.bbox.bottom()),
+                self.resources_ref
+            ),
+            layer.content.as_bytes(),
+        );
+
+        let name = self.next_name("Fm");
+
+        self.x_object_resources.push((name.clone(), form_ref));
+
+        let _ = writeln!(
+            self.content(),
+            "q {} W n {}/{} Do Q",
+            placement.clip,
+            placement.graphics_state,
+            name
+        );
+    }
+
+    /// Returns the operator that sets the alpha and blend mode, if they aren't the
+    /// defaults.
+    fn graphics_state(&mut self, alpha: f32, blend_mode: BlendMode) -> String {
+        let alpha = (alpha.clamp(0.0, 1.0) * 1000.0).round() / 1000.0;
+        let blend_mode = convert::blend_mode(blend_mode);
+
+        if alpha >= 1.0 && blend_mode == "Normal" {
+            return String::new();
+        }
+
+        let key = (alpha.to_bits(), blend_mode);
+
+        if let Some(name) = self.graphics_states.get(&key) {
+            return format!("/{} gs ", name);
+        }
+
+        let name = self.next_name("GS");
+
+        self.ext_g_state_resources.push((
+            name.clone(),
+            format!(
+                "<< /Type /ExtGState /ca {} /CA {} /BM /{} >>",
+                num(alpha),
+                num(alpha),
+                blend_mode
+            ),
+        ));
+
+        self.graphics_states.insert(key, name.clone());
+
+        format!("/{} gs ", name)
+    }
+
+    fn write_command(&mut self, origin: Offset, paints: &[Paint], command: &CanvasCommand) {
+        match command {
+            CanvasCommand::Shape {
+                paint_idx,
+                rect,
+                shape,
+            } => {
+                let Some(paint) = paints.get(*paint_idx) else {
+                    tracing::warn!("shape refers to a paint that does not exist");
+                    return;
+                };
+
+                self.write_shape(paint, rect.translate(origin), shape);
+            }
+
+            CanvasCommand::Shadow {
+                rect,
+                shape,
+                shadow,
+            } => self.write_shadow(rect.translate(origin), shape, shadow),
+
+            CanvasCommand::Texture {
+                rect,
+                shape,
+                texture,
+                tex_bounds,
+            } => self.write_texture(rect.translate(origin), shape, texture, *tex_bounds),
+
+            CanvasCommand::Text {
+                paint_idx,
+                rect,
+                text_style,
+                text,
+            } => {
+                let Some(paint) = paints.get(*paint_idx) else {
+                    tracing::warn!("text refers to a paint that does not exist");
+                    return;
+                };
+
+                self.write_text(paint, rect.translate(origin), text_style, text);
+            }
+
+            CanvasCommand::Glyphs {
+                paint_idx,
+                rect,
+                runs,
+            } => {
+                let Some(paint) = paints.get(*paint_idx) else {
+                    tracing::warn!("glyphs refer to a paint that does not exist");
+                    return;
+                };
+
+                self.write_glyphs(paint, rect.translate(origin), runs, None);
+            }
+
+            cmd => {
+                tracing::error!("unknown command: {:?}", cmd);
+            }
+        }
+    }
+
+    fn write_shape(&mut self, paint: &Paint, rect: Rect, shape: &Shape) {
+        let Some(path) = convert::shape_path(shape, rect) else {
+            return;
+        };
+
+        let ops = match (&paint.style, &paint.gradient) {
+            (PaintingStyle::Fill, Some(gradient)) => {
+                let Some(shading) = self.write_shading(gradient, rect.into()) else {
+                    return;
+                };
+
+                // Shadings carry their own colors, so only the blend mode applies
+                format!(
+                    "q {}{} {} /{} sh Q",
+                    self.graphics_state(1.0, paint.blend_mode),
+                    path,
+                    convert::fill_op(paint.fill_rule, true),
+                    shading
+                )
+            }
+
+            (PaintingStyle::Fill, None) => format!(
+                "q {}{} rg {} {} Q",
+                self.graphics_state(paint.color.alpha, paint.blend_mode),
+                convert::color(paint.color),
+                path,
+                convert::fill_op(paint.fill_rule, false)
+            ),
+
+            (PaintingStyle::Stroke(stroke), gradient) => {
+                let color = match gradient {
+                    Some(gradient) => {
+                        tracing::warn!("gradient strokes are not supported in pdf documents");
+
+                        gradient
+                            .stops
+                            .first()
+                            .map_or(paint.color, |stop| stop.color)
+                    }
+
+                    None => paint.color,
+                };
+
+                format!(
+                    "q {}{} RG {} {} S Q",
+                    self.graphics_state(color.alpha, paint.blend_mode),
+                    convert::color(color),
+                    convert::stroke_ops(stroke),
+                    path
+                )
+            }
+        };
+
+        let _ = writeln!(self.content(), "{}", ops);
+    }
+
+    /// Defines the gradient as a shading, positioned relative to `origin`, and returns
+    /// its name. Returns `None` if the gradient has no stops.
+    fn write_shading(&mut self, gradient: &Gradient, origin: Offset) -> Option<String> {
+        let function = gradient_function(&gradient.stops)?;
+
+        if gradient.extend != GradientExtend::Pad {
+            tracing::warn!(
+                "{:?} gradients are not supported in pdf documents",
+                gradient.extend
+            );
+        }
+
+        if gradient.stops.iter().any(|stop| stop.color.alpha < 1.0) {
+            tracing::warn!("translucent gradients are drawn opaque in pdf documents");
+        }
+
+        let shading = match gradient.kind {
+            GradientKind::Linear { start, end } => format!(
+                "<< /ShadingType 2 /ColorSpace /DeviceRGB /Coords [{} {} {} {}] /Function {} /Extend [true true] >>",
+                num(origin.x + start.x),
+                num(origin.y + start.y),
+                num(origin.x + end.x),
+                num(origin.y + end.y),
+                function
+            ),
+
+            GradientKind::Radial {
+                center,
+                radius,
+                focal,
+            } => format!(
+                "<< /ShadingType 3 /ColorSpace /DeviceRGB /Coords [{} {} 0 {} {} {}] /Function {} /Extend [true true] >>",
+                num(origin.x + focal.x),
+                num(origin.y + focal.y),
+                num(origin.x + center.x),
+                num(origin.y + center.y),
+                num(radius),
+                function
+            ),
+        };
+
+        let name = self.next_name("Sh");
+
+        self.shading_resources.push((name.clone(), shading));
+
+        Some(name)
+    }
+
+    /// Shadows are drawn without any blur, since PDF has no way to describe it.
+    fn write_shadow(&mut self, rect: Rect, shape: &Shape, shadow: &BoxShadow) {
+        let local_rect = Rect::new(0.0, 0.0, rect.width, rect.height);
+
+        let shadow_rect = shadow.shadow_rect(local_rect);
+
+        // Custom paths can't be spread, so they're only offset
+        let path = match shape {
+            Shape::Path(_) => convert::shape_path(
+                shape,
+                Rect {
+                    left: rect.left + shadow.offset.x,
+                    top: rect.top + shadow.offset.y,
+                    ..rect
+                },
+            ),
+
+            Shape::RoundedRect {
+                top_left,
+                top_right,
+                bottom_right,
+                bottom_left,
+            } => convert::shape_path(
+                &Shape::RoundedRect {
+                    top_left: (top_left + shadow.spread_radius).max(0.0),
+                    top_right: (top_right + shadow.spread_radius).max(0.0),
+                    bottom_right: (bottom_right + shadow.spread_radius).max(0.0),
+                    bottom_left: (bottom_left + shadow.spread_radius).max(0.0),
+                },
+                shadow_rect.translate(rect.into()),
+            ),
+
+            shape => convert::shape_path(shape, shadow_rect.translate(rect.into())),
+        };
+
+        let Some(path) = path else {
+            return;
+        };
+
+        let graphics_state = self.graphics_state(shadow.color.alpha, BlendMode::SourceOver);
+
+        let _ = writeln!(
+            self.content(),
+            "q {}{} rg {} f Q",
+            graphics_state,
+            convert::color(shadow.color),
+            path
+        );
+    }
+
+    fn write_texture(&mut self, rect: Rect, shape: &Shape, texture: &Texture, tex_bounds: Bounds) {
+        let texture_id = texture.id();
+
+        if !self.textures.contains_key(&texture_id) {
+            let name = self.write_image(texture);
+
+            self.textures.insert(texture_id.clone(), name);
+        }
+
+        let Some(name) = self.textures.get(&texture_id).cloned().flatten() else {
+            return;
+        };
+
+        let source_width = tex_bounds.right - tex_bounds.left;
+        let source_height = tex_bounds.bottom - tex_bounds.top;
+
+        if source_width <= 0.0 || source_height <= 0.0 {
+            return;
+        }
+
+        let Some(clip) = convert::shape_path(shape, rect) else {
+            return;
+        };
+
+        // Scale the whole image so that the selected region of it covers the rect, and
+        // clip away the rest
+        let width = rect.width / source_width;
+        let height = rect.height / source_height;
+
+        let left = rect.left - tex_bounds.left * width;
+        let top = rect.top - tex_bounds.top * height;
+
+        // Images are drawn into a unit square whose first row is at the top, which is
+        // flipped relative to the page
+        let _ = writeln!(
+            self.content(),
+            "q {} W n {} 0 0 {} {} {} cm /{} Do Q",
+            clip,
+            num(width),
+            num(-height),
+            num(left),
+            num(top + height),
+            name
+        );
+    }
+
+    /// Writes the texture into the document as an image, and returns its name.
+    fn write_image(&mut self, texture: &Texture) -> Option<String> {
+        let image = decode_texture(texture)?;
+
+        let (width, height) = image.dimensions();
+
+        let mut color = Vec::with_capacity((width * height * 3) as usize);
+        let mut alpha = Vec::with_capacity((width * height) as usize);
+
+        for pixel in image.pixels() {
+            color.extend_from_slice(&pixel.0[..3]);
+            alpha.push(pixel.0[3]);
+        }
+
+        let mut dict = format!(
+            "/Type /XObject /Subtype /Image /Width {} /Height {} /ColorSpace /DeviceRGB /BitsPerComponent 8",
+            width, height
+        );
+
+        // Only images with transparency need a mask
+        if alpha.iter().any(|alpha| *alpha < u8::MAX) {
+            let mask_ref = self.pdf.reserve();
+
+            self.pdf.write_stream(
+                mask_ref,
+                &format!(
+                    "/Type /XObject /Subtype /Image /Width {} /Height {} /ColorSpace /DeviceGray /BitsPerComponent 8",
+                    width, height
+                ),
+                &alpha,
+            );
+
+            let _ = write!(dict, " /SMask {}", mask_ref);
+        }
+
+        let image_ref = self.pdf.reserve();
+
+        self.pdf.write_stream(image_ref, &dict, &color);
+
+        let name = self.next_name("Im");
+
+        self.x_object_resources.push((name.clone(), image_ref));
+
+        Some(name)
+    }
+
+    /// Lays out the text within its rect, then writes it the same way as glyphs that were
+    /// laid out ahead of time.
+    fn write_text(&mut self, paint: &Paint, rect: Rect, text_style: &TextStyle, text: &str) {
+        let paragraph = agui_text::layout_paragraph(
+            self.fonts,
+            text_style,
+            text,
+            Constraints::tight(Size::from(rect)),
+        );
+
+        let runs = paragraph.glyph_runs().cloned().collect::<Vec<_>>();
+
+        self.write_glyphs(paint, rect, &runs, Some(text));
+    }
+
+    /// Writes glyphs that were shaped ahead of time, using their fonts' glyph IDs as-is. If
+    /// the text they were shaped from is given, each glyph is mapped back to the character
+    /// at the start of its cluster so that the text can be copied out of the document.
+    fn write_glyphs(&mut self, paint: &Paint, rect: Rect, runs: &[GlyphRun], text: Option<&str>) {
+        let color = text_color(paint);
+
+        let mut ops = format!(
+            "q {}{} rg BT",
+            self.graphics_state(color.alpha, paint.blend_mode),
+            convert::color(color)
+        );
+
+        let mut written = false;
+
+        for run in runs {
+            let Some(data) = self.fonts.get_or_insert(&run.font).cloned() else {
+                continue;
+            };
+
+            let font_idx = self.font_resource(&run.font, data);
+
+            let font = &mut self.font_resources[font_idx];
+
+            let _ = write!(ops, " /{} {} Tf", font.name, num(run.font_size));
+
+            for glyph in &run.glyphs {
+                let ch = text
+                    .and_then(|text| text.get(glyph.cluster..))
+                    .and_then(|cluster| cluster.chars().next());
+
+                let known_ch = font.glyphs.entry(glyph.id).or_insert(None);
+
+                if known_ch.is_none() {
+                    *known_ch = ch;
+                }
+
+                // Glyphs are drawn upright, despite the page being flipped
+                let _ = write!(
+                    ops,
+                    " 1 0 0 -1 {} {} Tm <{:04X}> Tj",
+                    num(rect.left + glyph.x),
+                    num(rect.top + glyph.y),
+                    glyph.id
+                );
+
+                written = true;
+            }
+        }
+
+        if !written {
+            return;
+        }
+
+        ops.push_str(" ET Q");
+
+        let _ = writeln!(self.content(), "{}", ops);
+    }
+
+    /// Returns the index of the font's resource, adding it to the document if it hasn't
+    /// been used yet.
+    fn font_resource(&mut self, font: &Font, data: Arc<Vec<u8>>) -> usize {
+        if let Some(idx) = self.fonts_used.get(&font.id()) {
+            return *idx;
+        }
+
+        let name = self.next_name("F");
+
+        self.font_resources.push(FontResource {
+            name,
+
+            data,
+            index: font.index(),
+
+            glyphs: BTreeMap::default(),
+        });
+
+        self.fonts_used
+            .insert(font.id(), self.font_resources.len() - 1);
+
+        self.font_resources.len() - 1
+    }
+
+    /// Embeds the font as a composite font whose character codes are glyph IDs, which
+    /// avoids needing to fit its characters into a single-byte encoding.
+    fn write_embedded_font(
+        &mut self,
+        name: &str,
+        data: &[u8],
+        index: u32,
+        glyphs: &BTreeMap<u16, Option<char>>,
+    ) -> Option<ObjectRef> {
+        let face = ttf_parser::Face::parse(data, index).ok()?;
+
+        // Glyph space in PDF is always 1000 units per em
+        let scale = 1000.0 / face.units_per_em() as f32;
+        let scaled = |value: i16| num(value as f32 * scale);
+
+        let font_file_ref = self.pdf.reserve();
+
+        self.pdf
+            .write_stream(font_file_ref, &format!("/Length1 {}", data.len()), data);
+
+        let bbox = face.global_bounding_box();
+
+        let descriptor_ref = self.pdf.reserve();
+
+        self.pdf.write(
+            descriptor_ref,
+            format!(
+                "<< /Type /FontDescriptor /FontName /{} /Flags 4 /FontBBox [{} {} {} {}] /ItalicAngle 0 /Ascent {} /Descent {} /CapHeight {} /StemV 80 /FontFile2 {} >>",
+                name,
+                scaled(bbox.x_min),
+                scaled(bbox.y_min),
+                scaled(bbox.x_max),
+                scaled(bbox.y_max),
+                scaled(face.ascender()),
+                scaled(face.descender()),
+                scaled(face.capital_height().unwrap_or(face.ascender())),
+                font_file_ref
+            ),
+        );
+
+        let widths = glyphs
+            .keys()
+            .map(|glyph_id| {
+                let advance = face
+                    .glyph_hor_advance(ttf_parser::GlyphId(*glyph_id))
+                    .unwrap_or_default();
+
+                format!("{} [{}]", glyph_id, num(advance as f32 * scale))
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let cid_font_ref = self.pdf.reserve();
+
+        self.pdf.write(
+            cid_font_ref,
+            format!(
+                "<< /Type /Font /Subtype /CIDFontType2 /BaseFont /{} /CIDSystemInfo << /Registry (Adobe) /Ordering (Identity) /Supplement 0 >> /FontDescriptor {} /W [{}] /CIDToGIDMap /Identity >>",
+                name, descriptor_ref, widths
+            ),
+        );
+
+        let to_unicode_ref = self.pdf.reserve();
+
+        self.pdf.write_stream(
+            to_unicode_ref,
+            "",
+            to_unicode_cmap(&glyph_chars(&face, glyphs)).as_bytes(),
+        );
+
+        let font_ref = self.pdf.reserve();
+
+        self.pdf.write(
+            font_ref,
+            format!(
+                "<< /Type /Font /Subtype /Type0 /BaseFont /{} /Encoding /Identity-H /DescendantFonts [{}] /ToUnicode {} >>",
+                name, cid_font_ref, to_unicode_ref
+            ),
+        );
+
+        Some(font_ref)
+    }
+}
+
+/// Returns a function that maps a position along the gradient, from `0.0` to `1.0`, to
+/// its color. Returns `None` if there are no stops.
+fn gradient_function(stops: &[agui_core::unit::GradientStop]) -> Option<String> {
+    let mut stops = stops
+        .iter()
+        .map(|stop| (stop.offset.clamp(0.0, 1.0), stop.color))
+        .collect::<Vec<_>>();
+
+    let (first, last) = (*stops.first()?, *stops.last()?);
+
+    // The function must cover the whole domain, so the outermost colors are extended to
+    // its edges
+    if first.0 > 0.0 {
+        stops.insert(0, (0.0, first.1));
+    }
+
+    if last.0 < 1.0 || stops.len() == 1 {
+        stops.push((1.0, last.1));
+    }
+
+    let exponential = |from: Color, to: Color| {
+        format!(
+            "<< /FunctionType 2 /Domain [0 1] /C0 [{}] /C1 [{}] /N 1 >>",
+            convert::color(from),
+            convert::color(to)
+        )
+    };
+
+    if stops.len() == 2 {
+        return Some(exponential(stops[0].1, stops[1].1));
+    }
+
+    let functions = stops
+        .windows(2)
+        .map(|pair| exponential(pair[0].1, pair[1].1))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let bounds = stops[1..stops.len() - 1]
+        .iter()
+        .map(|(offset, _)| num(*offset))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let encode = vec!["0 1"; stops.len() - 1].join(" ");
+
+    Some(format!(
+        "<< /FunctionType 3 /Domain [0 1] /Functions [{}] /Bounds [{}] /Encode [{}] >>",
+        functions, bounds, encode
+    ))
+}
+
+/// Text is always filled with a solid color.
+fn text_color(paint: &Paint) -> Color {
+    match &paint.gradient {
+        Some(gradient) => {
+            tracing::warn!("gradient text is not supported in pdf documents");
+
+            gradient
+                .stops
+                .first()
+                .map_or(paint.color, |stop| stop.color)
+        }
+
+        None => paint.color,
+    }
+}
+
+/// Returns the character of each glyph, looking up those that aren't known in the font's
+/// character map. Glyphs that no character maps to are left out.
+fn glyph_chars(
+    face: &ttf_parser::Face,
+    glyphs: &BTreeMap<u16, Option<char>>,
+) -> BTreeMap<u16, char> {
+    let mut chars = glyphs
+        .iter()
+        .filter_map(|(glyph_id, ch)| Some((*glyph_id, (*ch)?)))
+        .collect::<BTreeMap<_, _>>();
+
+    if chars.len() == glyphs.len() {
+        return chars;
+    }
+
+    let Some(cmap) = face.tables().cmap else {
+        return chars;
+    };
+
+    for subtable in cmap
+        .subtables
+        .into_iter()
+        .filter(|subtable| subtable.is_unicode())
+    {
+        subtable.codepoints(|codepoint| {
+            let Some(glyph_id) = subtable.glyph_index(codepoint) else {
+                return;
+            };
+
+            // Control characters are often mapped to the missing glyph
+            let Some(ch) = char::from_u32(codepoint).filter(|ch| !ch.is_control()) else {
+                return;
+            };
+
+            if glyphs.contains_key(&glyph_id.0) {
+                chars.entry(glyph_id.0).or_insert(ch);
+            }
+        });
+    }
+
+    chars
+}
+
+/// Builds a CMap that maps each glyph back to the character it was drawn for, so that
+/// text can be selected and copied out of the document.
+fn to_unicode_cmap(glyphs: &BTreeMap<u16, char>) -> String {
+    let mut cmap = String::from(
+        "/CIDInit /ProcSet findresource begin\n\
+         12 dict begin\n\
+         begincmap\n\
+         /CIDSystemInfo << /Registry (Adobe) /Ordering (UCS) /Supplement 0 >> def\n\
+         /CMapName /Adobe-Identity-UCS def\n\
+         /CMapType 2 def\n\
+         1 begincodespacerange\n\
+         <0000> <FFFF>\n\
+         endcodespacerange\n",
+    );
+
+    let glyphs = glyphs.iter().collect::<Vec<_>>();
+
+    // Each block may contain at most 100 mappings
+    for chunk in glyphs.chunks(100) {
+        let _ = writeln!(cmap, "{} beginbfchar", chunk.len());
+
+        for (glyph_id, ch) in chunk {
+            let _ = write!(cmap, "<{:04X}> <", glyph_id);
+
+            for unit in ch.encode_utf16(&mut [0; 2]) {
+                let _ = write!(cmap, "{:04X}", unit);
+            }
+
+            cmap.push_str(">\n");
+        }
+
+        cmap.push_str("endbfchar\n");
+    }
+
+    cmap.push_str(
+        "endcmap\n\
+         CMapName currentdict /CMap defineresource pop\n\
+         end\n\
+         end\n",
+    );
+
+    cmap
+}
+
+/// Decodes the texture into pixels, resolving it first if it refers to a URL.
+fn decode_texture(texture: &Texture) -> Option<image::RgbaImage> {
+    match texture.data() {
+        TextureData::Rgba8 {
+            width,
+            height,
+            pixels,
+        } => {
+            let image = image::RgbaImage::from_raw(*width, *height, pixels.clone());
+
+            if image.is_none() {
+                tracing::error!("texture pixels do not match its dimensions");
+            }
+
+            image
+        }
+
+        TextureData::Bytes(bytes) => match image::load_from_memory(bytes) {
+            Ok(image) => Some(image.into_rgba8()),
+
+            Err(err) => {
+                tracing::error!("failed to decode texture: {}", err);
+
+                None
+            }
+        },
+
+        TextureData::Url(_) => {
+            match futures::executor::block_on(AssetResolver::global().resolve_texture(texture)) {
+                Ok(resolved) => decode_texture(&resolved),
+
+                Err(err) => {
+                    tracing::error!("failed to load texture {:?}: {}", texture, err);
+
+                    None
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use agui_core::{
+        engine::rendering::mock::{deja_vu_sans, DEJA_VU_SANS},
+        render::canvas::{
+            paint::Paint,
+            painter::{CanvasPainter, Head},
+            Canvas,
+        },
+        unit::{
+            Color, Font, GlyphRun, GradientStop, HorizontalAlign, Offset, PositionedGlyph, Rect,
+            Size, TextStyle, VerticalAlign,
+        },
+    };
+
+    use agui_text::TextFonts;
+
+    use super::{glyph_chars, gradient_function, to_unicode_cmap, DocumentWriter};
+
+    const RED: Color = Color::from_rgb((1.0, 0.0, 0.0));
+    const BLUE: Color = Color::from_rgb((0.0, 0.0, 1.0));
+
+    fn write(canvas: &Canvas) -> String {
+        let mut fonts = TextFonts::new();
+
+        let mut writer = DocumentWriter::new(&mut fonts, canvas.size);
+
+        writer.begin_page();
+        writer.begin_canvas(Offset::ZERO, canvas);
+        writer.end_canvas(canvas);
+        writer.end_page();
+
+        String::from_utf8_lossy(&writer.finish()).into_owned()
+    }
+
+    #[test]
+    fn writes_pages() {
+        let mut fonts = TextFonts::new();
+
+        let mut writer = DocumentWriter::new(&mut fonts, Size::new(100.0, 200.0));
+
+        for _ in 0..3 {
+            writer.begin_page();
+            writer.end_page();
+        }
+
+        let document = String::from_utf8_lossy(&writer.finish()).into_owned();
+
+        assert!(document.contains("/Count 3 /MediaBox [0 0 100 200]"));
+        assert_eq!(document.matches("/Type /Page ").count(), 3);
+    }
+
+    #[test]
+    fn translucent_paints_share_graphics_states() {
+        let mut canvas = Canvas::new(Size::new(20.0, 20.0));
+
+        {
+            let mut painter = CanvasPainter::<Head<()>>::begin(&mut canvas);
+
+            let brush = painter.add_paint(Paint {
+                color: Color { alpha: 0.5, ..RED },
+                ..Paint::default()
+            });
+
+            painter.draw_rect_at(Rect::new(0.0, 0.0, 10.0, 10.0), &brush);
+            painter.draw_rect_at(Rect::new(10.0, 10.0, 10.0, 10.0), &brush);
+        }
+
+        let document = write(&canvas);
+
+        assert_eq!(document.matches("/Type /ExtGState").count(), 1);
+        assert!(document.contains("/ca 0.5 /CA 0.5 /BM /Normal"));
+    }
+
+    #[test]
+    fn builds_gradient_functions() {
+        assert_eq!(gradient_function(&[]), None);
+
+        assert_eq!(
+            gradient_function(&[GradientStop::new(0.0, RED), GradientStop::new(1.0, BLUE)]),
+            Some(String::from(
+                "<< /FunctionType 2 /Domain [0 1] /C0 [1 0 0] /C1 [0 0 1] /N 1 >>"
+            ))
+        );
+
+        let stitched =
+            gradient_function(&[GradientStop::new(0.25, RED), GradientStop::new(0.5, BLUE)])
+                .unwrap();
+
+        assert!(stitched.starts_with("<< /FunctionType 3"));
+        assert!(stitched.contains("/Bounds [0.25 0.5] /Encode [0 1 0 1 0 1]"));
+    }
+
+    #[test]
+    fn embeds_fonts_with_data() {
+        let mut canvas = Canvas::new(Size::new(100.0, 20.0));
+
+        let font = deja_vu_sans();
+
+        {
+            let mut painter = CanvasPainter::<Head<()>>::begin(&mut canvas);
+
+            let brush = painter.add_paint(Paint::default());
+
+            painter.draw_text(
+                &brush,
+                TextStyle {
+                    font,
+                    size: 12.0,
+                    color: RED,
+                    h_align: HorizontalAlign::Start,
+                    v_align: VerticalAlign::Top,
+                    ..TextStyle::default()
+                },
+                "Hi",
+            );
+        }
+
+        let document = write(&canvas);
+
+        assert!(document.contains("/Subtype /Type0"));
+        assert!(document.contains("/Encoding /Identity-H"));
+        assert!(document.contains("/FontFile2"));
+        assert!(document.contains("/ToUnicode"));
+    }
+
+    #[test]
+    fn maps_glyphs_to_unicode() {
+        let cmap = to_unicode_cmap(&BTreeMap::from([(3, 'a'), (7, '😀')]));
+
+        assert!(cmap.contains("2 beginbfchar\n<0003> <0061>\n<0007> <D83DDE00>\nendbfchar"));
+    }
+
+    #[test]
+    fn writes_glyphs_by_id() {
+        let data = DEJA_VU_SANS.to_vec();
+
+        let id = ttf_parser::Face::parse(&data, 0)
+            .unwrap()
+            .glyph_index('H')
+            .unwrap();
+
+        let mut canvas = Canvas::new(Size::new(100.0, 20.0));
+
+        {
+            let mut painter = CanvasPainter::<Head<()>>::begin(&mut canvas);
+
+            let brush = painter.add_paint(Paint {
+                color: RED,
+                ..Paint::default()
+            });
+
+            painter.draw_glyphs_at(
+                Rect::new(10.0, 5.0, 90.0, 15.0),
+                &brush,
+                vec![GlyphRun {
+                    font: Font::from_bytes(data),
+                    font_size: 12.0,
+
+                    range: 0..1,
+                    rtl: false,
+
+                    glyphs: vec![PositionedGlyph {
+                        id: id.0,
+                        cluster: 0,
+
+                        x: 2.0,
+                        y: 11.0,
+
+                        advance: 9.0,
+                    }],
+                }],
+            );
+        }
+
+        let mut fonts = TextFonts::new();
+
+        let mut writer = DocumentWriter::new(&mut fonts, canvas.size);
+
+        writer.begin_page();
+        writer.begin_canvas(Offset::ZERO, &canvas);
+        writer.end_canvas(&canvas);
+
+        // Content streams are compressed once the page ends
+        assert!(writer.page.contains(&format!(
+            "1 0 0 rg BT /F1 12 Tf 1 0 0 -1 12 16 Tm <{:04X}> Tj ET Q",
+            id.0
+        )));
+
+        assert_eq!(writer.font_resources[0].glyphs.get(&id.0), Some(&None));
+    }
+
+    #[test]
+    fn maps_text_glyphs_to_their_characters() {
+        let data = DEJA_VU_SANS.to_vec();
+
+        let face = ttf_parser::Face::parse(&data, 0).unwrap();
+
+        let mut canvas = Canvas::new(Size::new(100.0, 20.0));
+
+        {
+            let mut painter = CanvasPainter::<Head<()>>::begin(&mut canvas);
+
+            let brush = painter.add_paint(Paint::default());
+
+            painter.draw_text(
+                &brush,
+                TextStyle {
+                    font: Font::from_bytes(data.clone()),
+                    size: 12.0,
+                    color: RED,
+                    h_align: HorizontalAlign::Start,
+                    v_align: VerticalAlign::Top,
+                    ..TextStyle::default()
+                },
+                "Hi",
+            );
+        }
+
+        let mut fonts = TextFonts::new();
+
+        let mut writer = DocumentWriter::new(&mut fonts, canvas.size);
+
+        writer.begin_page();
+        writer.begin_canvas(Offset::ZERO, &canvas);
+        writer.end_canvas(&canvas);
+
+        assert_eq!(
+            writer.font_resources[0].glyphs,
+            BTreeMap::from([
+                (face.glyph_index('H').unwrap().0, Some('H')),
+                (face.glyph_index('i').unwrap().0, Some('i')),
+            ])
+        );
+    }
+
+    #[test]
+    fn looks_up_unknown_glyph_characters() {
+        let data = DEJA_VU_SANS;
+
+        let face = ttf_parser::Face::parse(data, 0).unwrap();
+
+        let a = face.glyph_index('a').unwrap().0;
+        let b = face.glyph_index('b').unwrap().0;
+
+        assert_eq!(
+            glyph_chars(
+                &face,
+                &BTreeMap::from([(a, Some('x')), (b, None), (0, None)])
+            ),
+            BTreeMap::from([(a, 'x'), (b, 'b')])
+        );
+    }
+}
@@ -0,0 +1,6 @@
+mod convert;
+mod document;
+mod pdf;
+mod scene;
+
+pub use scene::*;
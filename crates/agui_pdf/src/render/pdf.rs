@@ -0,0 +1,152 @@
+use std::{fmt, io::Write};
+
+use flate2::{write::ZlibEncoder, Compression};
+
+/// A reference to an indirect object in the document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ObjectRef(usize);
+
+impl fmt::Display for ObjectRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} 0 R", self.0)
+    }
+}
+
+/// Collects the indirect objects of a PDF document, and writes them out along with the
+/// cross-reference table that viewers use to locate them.
+#[derive(Default)]
+pub(crate) struct PdfWriter {
+    objects: Vec<Option<Vec<u8>>>,
+}
+
+impl PdfWriter {
+    /// Allocates a new object, so that it can be referred to before it's written.
+    pub fn reserve(&mut self) -> ObjectRef {
+        self.objects.push(None);
+
+        ObjectRef(self.objects.len())
+    }
+
+    /// Sets the contents of the object.
+    pub fn write(&mut self, object: ObjectRef, contents: impl Into<Vec<u8>>) {
+        self.objects[object.0 - 1] = Some(contents.into());
+    }
+
+    /// Sets the object to a compressed stream. The `dict` contains the entries of the
+    /// stream's dictionary, excluding its length and filter.
+    pub fn write_stream(&mut self, object: ObjectRef, dict: &str, data: &[u8]) {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+
+        let data = encoder
+            .write_all(data)
+            .and_then(|_| encoder.finish())
+            .expect("writing to a vec cannot fail");
+
+        let mut contents = format!(
+            "<< {}{}/Length {} /Filter /FlateDecode >>\nstream\n",
+            dict,
+            if dict.is_empty() { "" } else { " " },
+            data.len()
+        )
+        .into_bytes();
+
+        contents.extend_from_slice(&data);
+        contents.extend_from_slice(b"\nendstream");
+
+        self.write(object, contents);
+    }
+
+    /// Writes the complete document, starting from its catalog.
+    pub fn finish(self, catalog: ObjectRef) -> Vec<u8> {
+        // The comment of high bytes marks the file as binary to anything transferring it
+        let mut document = b"%PDF-1.7\n%\xe2\xe3\xcf\xd3\n".to_vec();
+
+        let mut offsets = Vec::with_capacity(self.objects.len());
+
+        for (idx, contents) in self.objects.into_iter().enumerate() {
+            offsets.push(document.len());
+
+            document.extend_from_slice(format!("{} 0 obj\n", idx + 1).as_bytes());
+
+            match contents {
+                Some(contents) => document.extend_from_slice(&contents),
+
+                None => {
+                    tracing::warn!("object {} was reserved but never written", idx + 1);
+
+                    document.extend_from_slice(b"null");
+                }
+            }
+
+            document.extend_from_slice(b"\nendobj\n");
+        }
+
+        let xref_offset = document.len();
+
+        document.extend_from_slice(
+            format!("xref\n0 {}\n0000000000 65535 f \n", offsets.len() + 1).as_bytes(),
+        );
+
+        for offset in &offsets {
+            document.extend_from_slice(format!("{:010} 00000 n \n", offset).as_bytes());
+        }
+
+        document.extend_from_slice(
+            format!(
+                "trailer\n<< /Size {} /Root {} >>\nstartxref\n{}\n%%EOF\n",
+                offsets.len() + 1,
+                catalog,
+                xref_offset
+            )
+            .as_bytes(),
+        );
+
+        document
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PdfWriter;
+
+    #[test]
+    fn cross_references_point_at_objects() {
+        let mut writer = PdfWriter::default();
+
+        let catalog = writer.reserve();
+        let stream = writer.reserve();
+
+        writer.write(catalog, "<< /Type /Catalog >>");
+        writer.write_stream(stream, "", b"0 0 10 10 re f");
+
+        let document = writer.finish(catalog);
+
+        // Compressed streams aren't valid UTF-8, so positions are found in the raw bytes
+        let find = |needle: &[u8]| {
+            document
+                .windows(needle.len())
+                .rposition(|window| window == needle)
+                .unwrap()
+        };
+
+        assert!(document.starts_with(b"%PDF-1.7"));
+        assert!(document.ends_with(b"%%EOF\n"));
+
+        let xref = find(b"xref\n0 3\n");
+        let tail = String::from_utf8_lossy(&document[xref..]).into_owned();
+
+        assert!(tail.contains("/Root 1 0 R"));
+
+        let offsets = tail
+            .lines()
+            .skip(3)
+            .take(2)
+            .map(|line| line[..10].parse::<usize>().unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(&document[offsets[0]..offsets[0] + 7], b"1 0 obj");
+        assert_eq!(&document[offsets[1]..offsets[1] + 7], b"2 0 obj");
+
+        assert!(tail.ends_with(&format!("startxref\n{}\n%%EOF\n", xref)));
+    }
+}
@@ -0,0 +1,137 @@
+use agui_core::{
+    engine::rendering::scene::{Scene, SceneChange, SceneObject, SceneVisitor},
+    render::{canvas::Canvas, RenderObjectId},
+    unit::Offset,
+};
+use agui_renderer::view::ViewScene;
+use agui_text::TextFonts;
+
+use crate::{pages::PageLayout, render::document::DocumentWriter};
+
+#[derive(Default)]
+pub struct PdfScene {
+    scene: Scene,
+}
+
+impl PdfScene {
+    /// Writes the scene as a PDF document, with one page for each page in the layout.
+    /// Without a layout, the whole scene is written to a single page.
+    pub(crate) fn to_document(
+        &self,
+        fonts: &mut TextFonts,
+        page_layout: Option<&PageLayout>,
+    ) -> Vec<u8> {
+        let size = self.scene.size().unwrap_or_default();
+
+        // The layout may be left over from a multi-page that has since been removed
+        let page_layout =
+            page_layout.filter(|page_layout| self.scene.contains(page_layout.render_object_id));
+
+        let page_tops = match page_layout {
+            Some(page_layout) => page_layout.page_tops.clone(),
+            None => vec![0.0],
+        };
+
+        let mut writer = DocumentWriter::new(fonts, size);
+
+        for page_top in page_tops {
+            writer.begin_page();
+
+            self.scene.visit(&mut PageWriter {
+                scene: &self.scene,
+                writer: &mut writer,
+
+                page_layout,
+                page_top,
+
+                clipped: Vec::new(),
+            });
+
+            writer.end_page();
+        }
+
+        writer.finish()
+    }
+}
+
+impl ViewScene for PdfScene {
+    fn apply(&mut self, change: SceneChange) {
+        self.scene.apply(change);
+    }
+}
+
+/// Writes the part of a scene that falls on a single page.
+struct PageWriter<'a, 'fonts> {
+    scene: &'a Scene,
+    writer: &'a mut DocumentWriter<'fonts>,
+
+    page_layout: Option<&'a PageLayout>,
+    page_top: f32,
+
+    /// Whether each object that has begun is a child of a
+    /// [`MultiPage`](crate::pages::MultiPage), and was clipped to the page's content area.
+    clipped: Vec<bool>,
+}
+
+impl SceneVisitor for PageWriter<'_, '_> {
+    fn begin_object(
+        &mut self,
+        render_object_id: RenderObjectId,
+        object: &SceneObject,
+        parent_origin: Offset,
+    ) -> Option<Offset> {
+        let mut origin = parent_origin + object.offset;
+
+        let mut is_clipped = false;
+
+        if let Some(page_layout) = self.page_layout {
+            // Children of the multi-page that aren't on this page are skipped entirely
+            let extent = self
+                .scene
+                .get_parent(render_object_id)
+                .filter(|parent_id| *parent_id == page_layout.render_object_id)
+                .and_then(|_| page_layout.children.get(&render_object_id));
+
+            if let Some((top, bottom)) = extent {
+                let page_bottom = self.page_top + page_layout.content.height;
+
+                if *bottom <= self.page_top || *top >= page_bottom {
+                    return None;
+                }
+
+                origin.y -= self.page_top;
+
+                self.writer
+                    .push_clip(page_layout.content.translate(parent_origin));
+
+                is_clipped = true;
+            }
+        }
+
+        if let Some(canvas) = &object.canvas {
+            self.writer.begin_canvas(origin, canvas);
+        }
+
+        self.clipped.push(is_clipped);
+
+        Some(origin)
+    }
+
+    fn end_object(&mut self, _: RenderObjectId, object: &SceneObject) {
+        if let Some(canvas) = &object.canvas {
+            self.writer.end_canvas(canvas);
+        }
+
+        if self.clipped.pop().unwrap_or_default() {
+            self.writer.pop_clip();
+        }
+    }
+
+    fn begin_canvas(&mut self, origin: Offset, canvas: &Canvas) {
+        self.writer.begin_canvas(origin, canvas);
+    }
+
+    fn end_canvas(&mut self, canvas: &Canvas) {
+        self.writer.end_canvas(canvas);
+    }
+}
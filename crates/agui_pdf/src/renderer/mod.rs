@@ -0,0 +1,27 @@
+use std::sync::Arc;
+
+use agui_text::TextFonts;
+use parking_lot::Mutex;
+
+use crate::view::{self, PdfView, PdfViewHandle};
+
+#[derive(Default, Clone)]
+pub struct PdfRenderer {
+    fonts: Arc<Mutex<TextFonts>>,
+}
+
+impl PdfRenderer {
+    pub fn new() -> PdfRenderer {
+        Self::default()
+    }
+
+    pub(crate) fn new_view(&self) -> (PdfView, PdfViewHandle) {
+        view::new_view(Arc::clone(&self.fonts))
+    }
+}
+
+impl PartialEq for PdfRenderer {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.fonts, &other.fonts)
+    }
+}
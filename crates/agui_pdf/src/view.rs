@@ -0,0 +1,51 @@
+use std::sync::Arc;
+
+use agui_renderer::view::{SceneView, SceneViewHandle};
+use agui_text::{ShapedTextLayout, TextFonts};
+use parking_lot::Mutex;
+
+use crate::{pages::SharedPageLayout, render::PdfScene};
+
+pub type PdfView = SceneView<PdfScene>;
+
+#[derive(Clone)]
+pub struct PdfViewHandle {
+    fonts: Arc<Mutex<TextFonts>>,
+
+    handle: SceneViewHandle<PdfScene>,
+
+    page_layout: SharedPageLayout,
+}
+
+pub(crate) fn new_view(fonts: Arc<Mutex<TextFonts>>) -> (PdfView, PdfViewHandle) {
+    let view = SceneView::new(
+        PdfScene::default(),
+        ShapedTextLayout::new(Arc::clone(&fonts)),
+    );
+
+    let handle = PdfViewHandle {
+        fonts,
+
+        handle: view.handle(),
+
+        page_layout: SharedPageLayout::default(),
+    };
+
+    (view, handle)
+}
+
+impl PdfViewHandle {
+    pub(crate) fn page_layout(&self) -> SharedPageLayout {
+        Arc::clone(&self.page_layout)
+    }
+
+    /// Writes the most recently synced scene as a PDF document. If it contains a
+    /// [`MultiPage`](crate::pages::MultiPage), each of its pages is written separately;
+    /// otherwise, the document has a single page.
+    pub fn document(&self) -> Vec<u8> {
+        let scene = self.handle.scene();
+        let page_layout = self.page_layout.lock();
+
+        scene.to_document(&mut self.fonts.lock(), page_layout.as_ref())
+    }
+}
@@ -1,22 +1,26 @@
 use agui_core::{
-    element::{RenderObjectCreateContext, RenderObjectUpdateContext},
-    unit::Alignment,
-    widget::Widget,
+    unit::{AlignmentGeometry, TextDirection},
+    widget::{IntoWidget, Widget},
 };
-use agui_elements::render::RenderObjectWidget;
-use agui_macros::RenderObjectWidget;
+use agui_elements::stateless::{StatelessBuildContext, StatelessWidget};
+use agui_macros::StatelessWidget;
+
+use crate::directionality::Directionality;
 
 mod aligned_box;
 mod center;
+mod raw_align;
 
 pub use center::*;
+pub use raw_align::*;
 
-use self::aligned_box::RenderAlignedBox;
-
-#[derive(RenderObjectWidget, Debug)]
+#[derive(StatelessWidget, Debug)]
 #[props(default)]
 pub struct Align {
-    pub alignment: Alignment,
+    /// Where to place the child. Directional alignments are resolved against the ambient
+    /// [`Directionality`].
+    #[prop(into)]
+    pub alignment: AlignmentGeometry,
 
     pub width_factor: Option<f32>,
     pub height_factor: Option<f32>,
@@ -25,30 +29,21 @@ pub struct Align {
     pub child: Option<Widget>,
 }
 
-impl RenderObjectWidget for Align {
-    type RenderObject = RenderAlignedBox;
-
-    fn children(&self) -> Vec<Widget> {
-        Vec::from_iter(self.child.clone())
-    }
+impl StatelessWidget for Align {
+    fn build(&self, ctx: &mut StatelessBuildContext<Self>) -> Widget {
+        let text_direction = match self.alignment {
+            AlignmentGeometry::Absolute(_) => TextDirection::default(),
+            AlignmentGeometry::Directional(_) => Directionality::of(ctx).unwrap_or_default(),
+        };
 
-    fn create_render_object(&self, _: &mut RenderObjectCreateContext) -> Self::RenderObject {
-        RenderAlignedBox {
-            alignment: self.alignment,
+        RawAlign {
+            alignment: self.alignment.resolve(text_direction),
 
             width_factor: self.width_factor,
             height_factor: self.height_factor,
-        }
-    }
 
-    fn update_render_object(
-        &self,
-        ctx: &mut RenderObjectUpdateContext,
-        render_object: &mut Self::RenderObject,
-    ) {
-        render_object.update_alignment(ctx, self.alignment);
-
-        render_object.update_width_factor(ctx, self.width_factor);
-        render_object.update_height_factor(ctx, self.height_factor);
+            child: self.child.clone(),
+        }
+        .into_widget()
     }
 }
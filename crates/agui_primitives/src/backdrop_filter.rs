@@ -0,0 +1,94 @@
+use agui_core::{
+    element::{ContextDirtyRenderObject, RenderObjectCreateContext, RenderObjectUpdateContext},
+    render::{
+        canvas::{paint::Paint, painter::CanvasPainter},
+        object::RenderObjectImpl,
+    },
+    unit::{LayerFilter, Shape},
+    widget::Widget,
+};
+use agui_elements::render::RenderObjectWidget;
+use agui_macros::RenderObjectWidget;
+
+/// Applies a filter to everything painted behind it, within its bounds, before painting
+/// its child.
+///
+/// Backdrop filters are only applied by the software backend. Elsewhere the child is
+/// painted over an unfiltered backdrop.
+#[derive(RenderObjectWidget, Debug)]
+pub struct BackdropFilter {
+    pub filter: LayerFilter,
+
+    #[prop(default)]
+    pub shape: Shape,
+
+    #[prop(into, default)]
+    pub child: Option<Widget>,
+}
+
+impl RenderObjectWidget for BackdropFilter {
+    type RenderObject = RenderBackdropFilter;
+
+    fn children(&self) -> Vec<Widget> {
+        Vec::from_iter(self.child.clone())
+    }
+
+    fn create_render_object(&self, _: &mut RenderObjectCreateContext) -> Self::RenderObject {
+        RenderBackdropFilter {
+            filter: self.filter,
+
+            shape: self.shape.clone(),
+        }
+    }
+
+    fn update_render_object(
+        &self,
+        ctx: &mut RenderObjectUpdateContext,
+        render_object: &mut Self::RenderObject,
+    ) {
+        render_object.update_filter(ctx, self.filter);
+
+        render_object.update_shape(ctx, self.shape.clone());
+    }
+}
+
+pub struct RenderBackdropFilter {
+    pub filter: LayerFilter,
+
+    pub shape: Shape,
+}
+
+impl RenderBackdropFilter {
+    fn update_filter(&mut self, ctx: &mut RenderObjectUpdateContext, filter: LayerFilter) {
+        if self.filter == filter {
+            return;
+        }
+
+        self.filter = filter;
+        ctx.mark_needs_paint();
+    }
+
+    fn update_shape(&mut self, ctx: &mut RenderObjectUpdateContext, shape: Shape) {
+        if self.shape == shape {
+            return;
+        }
+
+        self.shape = shape;
+        ctx.mark_needs_paint();
+    }
+}
+
+impl RenderObjectImpl for RenderBackdropFilter {
+    fn does_paint(&self) -> bool {
+        true
+    }
+
+    fn paint(&self, mut canvas: CanvasPainter) {
+        let brush = canvas.add_paint(Paint {
+            anti_alias: true,
+            ..Paint::default()
+        });
+
+        canvas.start_backdrop_layer(&brush, self.shape.clone(), self.filter);
+    }
+}
@@ -0,0 +1,100 @@
+use agui_core::{
+    element::{ContextDirtyRenderObject, RenderObjectCreateContext, RenderObjectUpdateContext},
+    render::object::{RenderObjectImpl, RenderObjectLayoutContext},
+    unit::{Constraints, Offset, Size, TextBaseline},
+    widget::Widget,
+};
+use agui_elements::render::RenderObjectWidget;
+use agui_macros::RenderObjectWidget;
+
+/// Positions its child so that the child's baseline is `baseline` pixels below the top of
+/// this widget.
+///
+/// The widget is as tall as it needs to be to fit its child below that point, and as wide
+/// as its child. If the child has no baseline, its bottom is placed on the baseline
+/// instead.
+#[derive(RenderObjectWidget, Debug)]
+pub struct Baseline {
+    pub baseline: f32,
+
+    #[prop(default = TextBaseline::Alphabetic)]
+    pub baseline_type: TextBaseline,
+
+    #[prop(into)]
+    pub child: Option<Widget>,
+}
+
+impl RenderObjectWidget for Baseline {
+    type RenderObject = RenderBaseline;
+
+    fn children(&self) -> Vec<Widget> {
+        Vec::from_iter(self.child.clone())
+    }
+
+    fn create_render_object(&self, _: &mut RenderObjectCreateContext) -> Self::RenderObject {
+        RenderBaseline {
+            baseline: self.baseline,
+            baseline_type: self.baseline_type,
+        }
+    }
+
+    fn update_render_object(
+        &self,
+        ctx: &mut RenderObjectUpdateContext,
+        render_object: &mut Self::RenderObject,
+    ) {
+        render_object.update_baseline(ctx, self.baseline);
+        render_object.update_baseline_type(ctx, self.baseline_type);
+    }
+}
+
+pub struct RenderBaseline {
+    pub baseline: f32,
+    pub baseline_type: TextBaseline,
+}
+
+impl RenderBaseline {
+    pub fn update_baseline(&mut self, ctx: &mut RenderObjectUpdateContext, baseline: f32) {
+        if self.baseline == baseline {
+            return;
+        }
+
+        self.baseline = baseline;
+        ctx.mark_needs_layout();
+    }
+
+    pub fn update_baseline_type(
+        &mut self,
+        ctx: &mut RenderObjectUpdateContext,
+        baseline_type: TextBaseline,
+    ) {
+        if self.baseline_type == baseline_type {
+            return;
+        }
+
+        self.baseline_type = baseline_type;
+        ctx.mark_needs_layout();
+    }
+}
+
+impl RenderObjectImpl for RenderBaseline {
+    fn layout(&self, ctx: &mut RenderObjectLayoutContext, constraints: Constraints) -> Size {
+        let mut children = ctx.iter_children_mut();
+
+        let Some(mut child) = children.next() else {
+            return constraints.smallest();
+        };
+
+        let child_size = child.compute_layout(constraints.loosen());
+
+        let child_baseline = child
+            .compute_distance_to_baseline(self.baseline_type)
+            .unwrap_or(child_size.height);
+
+        let top = self.baseline - child_baseline;
+
+        child.set_offset(Offset::new(0.0, top));
+
+        constraints.constrain(Size::new(child_size.width, top + child_size.height))
+    }
+}
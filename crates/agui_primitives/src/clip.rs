@@ -2,9 +2,9 @@ use agui_core::{
     element::{ContextDirtyRenderObject, RenderObjectCreateContext, RenderObjectUpdateContext},
     render::{
         canvas::{paint::Paint, painter::CanvasPainter},
-        object::RenderObjectImpl,
+        object::{RenderObjectHitTestContext, RenderObjectImpl},
     },
-    unit::{Rect, Shape},
+    unit::{HitTest, Offset, Rect, Shape},
     widget::Widget,
 };
 use agui_elements::render::RenderObjectWidget;
@@ -87,6 +87,31 @@ impl RenderClip {
 }
 
 impl RenderObjectImpl for RenderClip {
+    fn hit_test<'ctx>(
+        &self,
+        ctx: &'ctx mut RenderObjectHitTestContext<'ctx>,
+        position: Offset,
+    ) -> HitTest {
+        let clip_rect = self.rect.unwrap_or_else(|| ctx.size().into());
+
+        // Anything that has been clipped away can't be hit.
+        if !ctx.size().contains(position) || !self.shape.contains(clip_rect, position) {
+            return HitTest::Pass;
+        }
+
+        let mut children = ctx.iter_children();
+
+        while let Some(mut child) = children.next_back() {
+            let offset = child.offset();
+
+            if child.hit_test_with_offset(offset, position) == HitTest::Absorb {
+                return HitTest::Absorb;
+            }
+        }
+
+        HitTest::Pass
+    }
+
     fn does_paint(&self) -> bool {
         true
     }
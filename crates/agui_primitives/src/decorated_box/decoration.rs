@@ -0,0 +1,183 @@
+use agui_core::unit::{BoxShadow, Color, EdgeInsets};
+
+/// Describes how to paint a box: its shadows, background, border, and corner radii.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct BoxDecoration {
+    /// The color to fill the box with. If `None`, no background is painted.
+    pub color: Option<Color>,
+
+    /// The border to draw around the box. If `None`, no border is painted.
+    pub border: Option<Border>,
+
+    /// The radii of each corner of the box. Applies to both the background and the border.
+    ///
+    /// A border whose sides differ in width is rounded as long as its sides share a color.
+    /// Otherwise, its corners are drawn square.
+    pub border_radius: BorderRadius,
+
+    /// The shadows cast by the box, painted in order beneath the background.
+    pub box_shadow: Vec<BoxShadow>,
+}
+
+impl BoxDecoration {
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn border(mut self, border: Border) -> Self {
+        self.border = Some(border);
+        self
+    }
+
+    pub fn border_radius(mut self, border_radius: BorderRadius) -> Self {
+        self.border_radius = border_radius;
+        self
+    }
+
+    pub fn box_shadow(mut self, box_shadow: BoxShadow) -> Self {
+        self.box_shadow.push(box_shadow);
+        self
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct BorderRadius {
+    pub top_left: f32,
+    pub top_right: f32,
+    pub bottom_right: f32,
+    pub bottom_left: f32,
+}
+
+impl BorderRadius {
+    pub const ZERO: Self = Self::all(0.0);
+
+    pub const fn all(radius: f32) -> Self {
+        Self {
+            top_left: radius,
+            top_right: radius,
+            bottom_right: radius,
+            bottom_left: radius,
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.top_left <= 0.0
+            && self.top_right <= 0.0
+            && self.bottom_right <= 0.0
+            && self.bottom_left <= 0.0
+    }
+
+    /// Shrinks each radius by `amount`, clamping them at zero.
+    pub fn deflate(&self, amount: f32) -> Self {
+        Self {
+            top_left: (self.top_left - amount).max(0.0),
+            top_right: (self.top_right - amount).max(0.0),
+            bottom_right: (self.bottom_right - amount).max(0.0),
+            bottom_left: (self.bottom_left - amount).max(0.0),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Border {
+    pub top: BorderSide,
+    pub right: BorderSide,
+    pub bottom: BorderSide,
+    pub left: BorderSide,
+}
+
+impl Border {
+    pub const fn all(side: BorderSide) -> Self {
+        Self {
+            top: side,
+            right: side,
+            bottom: side,
+            left: side,
+        }
+    }
+
+    pub const fn symmetric(vertical: BorderSide, horizontal: BorderSide) -> Self {
+        Self {
+            top: vertical,
+            right: horizontal,
+            bottom: vertical,
+            left: horizontal,
+        }
+    }
+
+    /// Returns `true` if all sides share the same color, width, and style.
+    pub fn is_uniform(&self) -> bool {
+        self.top == self.right && self.top == self.bottom && self.top == self.left
+    }
+
+    /// Returns the color shared by every side that's drawn, or `None` if they differ or
+    /// no side is drawn.
+    pub fn uniform_color(&self) -> Option<Color> {
+        let mut colors = [self.top, self.right, self.bottom, self.left]
+            .into_iter()
+            .filter(|side| side.effective_width() > 0.0)
+            .map(|side| side.color);
+
+        let color = colors.next()?;
+
+        colors.all(|other| other == color).then_some(color)
+    }
+
+    /// The widths of each side of the border.
+    pub fn dimensions(&self) -> EdgeInsets {
+        EdgeInsets::new(
+            self.top.effective_width(),
+            self.right.effective_width(),
+            self.bottom.effective_width(),
+            self.left.effective_width(),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BorderSide {
+    pub color: Color,
+    pub width: f32,
+    pub style: BorderStyle,
+}
+
+impl Default for BorderSide {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+impl BorderSide {
+    pub const NONE: Self = Self {
+        color: Color::from_rgba((0.0, 0.0, 0.0, 0.0)),
+        width: 0.0,
+        style: BorderStyle::None,
+    };
+
+    pub const fn new(color: Color, width: f32) -> Self {
+        Self {
+            color,
+            width,
+            style: BorderStyle::Solid,
+        }
+    }
+
+    /// The width of the side, or zero if it is not drawn.
+    pub fn effective_width(&self) -> f32 {
+        match self.style {
+            BorderStyle::None => 0.0,
+            BorderStyle::Solid => self.width.max(0.0),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BorderStyle {
+    /// The side is not drawn.
+    #[default]
+    None,
+
+    /// The side is drawn as a solid line.
+    Solid,
+}
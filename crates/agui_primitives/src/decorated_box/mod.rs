@@ -0,0 +1,47 @@
+use agui_core::{
+    element::{RenderObjectCreateContext, RenderObjectUpdateContext},
+    widget::Widget,
+};
+use agui_elements::render::RenderObjectWidget;
+use agui_macros::RenderObjectWidget;
+
+mod decoration;
+mod render_decorated_box;
+
+pub use decoration::*;
+pub use render_decorated_box::*;
+
+/// Paints a [`BoxDecoration`] behind its child.
+///
+/// The decoration doesn't affect layout: the box takes the size of its child, which is
+/// drawn over the border rather than inset by it. Wrap the child in a
+/// [`Padding`](crate::padding::Padding) to keep it clear of the border.
+#[derive(RenderObjectWidget, Debug)]
+pub struct DecoratedBox {
+    pub decoration: BoxDecoration,
+
+    #[prop(into, default)]
+    pub child: Option<Widget>,
+}
+
+impl RenderObjectWidget for DecoratedBox {
+    type RenderObject = RenderDecoratedBox;
+
+    fn children(&self) -> Vec<Widget> {
+        Vec::from_iter(self.child.clone())
+    }
+
+    fn create_render_object(&self, _: &mut RenderObjectCreateContext) -> Self::RenderObject {
+        RenderDecoratedBox {
+            decoration: self.decoration.clone(),
+        }
+    }
+
+    fn update_render_object(
+        &self,
+        ctx: &mut RenderObjectUpdateContext,
+        render_object: &mut Self::RenderObject,
+    ) {
+        render_object.update_decoration(ctx, self.decoration.clone());
+    }
+}
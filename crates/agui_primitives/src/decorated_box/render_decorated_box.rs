@@ -0,0 +1,284 @@
+use agui_core::{
+    element::{ContextDirtyRenderObject, RenderObjectUpdateContext},
+    render::{
+        canvas::{paint::Paint, painter::CanvasPainter},
+        object::RenderObjectImpl,
+    },
+    unit::{Color, FillRule, Offset, PaintingStyle, Rect, Shape, Size, StrokeStyle},
+};
+use lyon::{
+    geom::{point, Box2D},
+    path::{builder::BorderRadii, Path, Winding},
+};
+
+use crate::decorated_box::{Border, BorderRadius, BorderSide, BoxDecoration};
+
+pub struct RenderDecoratedBox {
+    pub decoration: BoxDecoration,
+}
+
+impl RenderDecoratedBox {
+    pub(super) fn update_decoration(
+        &mut self,
+        ctx: &mut RenderObjectUpdateContext,
+        decoration: BoxDecoration,
+    ) {
+        if self.decoration == decoration {
+            return;
+        }
+
+        self.decoration = decoration;
+        ctx.mark_needs_paint();
+    }
+
+    fn shape(&self) -> Shape {
+        let radius = self.decoration.border_radius;
+
+        if radius.is_zero() {
+            Shape::Rect
+        } else {
+            Shape::RoundedRect {
+                top_left: radius.top_left,
+                top_right: radius.top_right,
+                bottom_right: radius.bottom_right,
+                bottom_left: radius.bottom_left,
+            }
+        }
+    }
+
+    fn paint_uniform_border(&self, canvas: &mut CanvasPainter, size: Size, side: BorderSide) {
+        let width = side.effective_width();
+
+        let brush = canvas.add_paint(Paint {
+            anti_alias: true,
+            color: side.color,
+            style: PaintingStyle::Stroke(StrokeStyle::new(width)),
+
+            ..Paint::default()
+        });
+
+        // Strokes are centered on the outline, so inset the rect by half of the width to
+        // keep the border within the bounds of the box.
+        let half_width = width / 2.0;
+
+        let rect = Rect::new(
+            half_width,
+            half_width,
+            (size.width - width).max(0.0),
+            (size.height - width).max(0.0),
+        );
+
+        let radius = self.decoration.border_radius;
+
+        if radius.is_zero() {
+            canvas.draw_rect_at(rect, &brush);
+        } else {
+            let radius = radius.deflate(half_width);
+
+            canvas.draw_rounded_rect_at(
+                rect,
+                &brush,
+                radius.top_left,
+                radius.top_right,
+                radius.bottom_right,
+                radius.bottom_left,
+            );
+        }
+    }
+
+    /// Paints a border whose sides share a color but not a width, by filling the area
+    /// between the outline of the box and the outline of its inside.
+    fn paint_rounded_border(
+        &self,
+        canvas: &mut CanvasPainter,
+        size: Size,
+        border: &Border,
+        color: Color,
+    ) {
+        let radius = self.decoration.border_radius;
+        let insets = border.dimensions();
+
+        let brush = canvas.add_paint(Paint {
+            anti_alias: true,
+            color,
+            fill_rule: FillRule::EvenOdd,
+
+            ..Paint::default()
+        });
+
+        // Each inner corner is rounded by whatever is left of the outer radius once the
+        // wider of the sides that meet there is taken away.
+        let inner_radius = BorderRadius {
+            top_left: (radius.top_left - insets.top.max(insets.left)).max(0.0),
+            top_right: (radius.top_right - insets.top.max(insets.right)).max(0.0),
+            bottom_right: (radius.bottom_right - insets.bottom.max(insets.right)).max(0.0),
+            bottom_left: (radius.bottom_left - insets.bottom.max(insets.left)).max(0.0),
+        };
+
+        let inner = Box2D::new(
+            point(insets.left, insets.top),
+            point(
+                (size.width - insets.right).max(insets.left),
+                (size.height - insets.bottom).max(insets.top),
+            ),
+        );
+
+        let mut builder = Path::builder();
+
+        builder.add_rounded_rectangle(
+            &Box2D::new(point(0.0, 0.0), point(size.width, size.height)),
+            &border_radii(radius),
+            Winding::Positive,
+        );
+
+        builder.add_rounded_rectangle(&inner, &border_radii(inner_radius), Winding::Negative);
+
+        canvas.draw_path(&brush, builder.build());
+    }
+
+    fn paint_border_sides(&self, canvas: &mut CanvasPainter, size: Size, border: &Border) {
+        if !self.decoration.border_radius.is_zero() {
+            tracing::warn!(
+                "a border radius can't be applied to a border whose sides differ in color"
+            );
+        }
+
+        let insets = border.dimensions();
+
+        let (right, bottom) = (size.width, size.height);
+        let (inner_left, inner_top) = (insets.left, insets.top);
+        let (inner_right, inner_bottom) = (right - insets.right, bottom - insets.bottom);
+
+        // Each side is drawn as a trapezoid so that adjacent sides meet at a mitered corner.
+        let sides = [
+            (
+                border.top,
+                [
+                    (0.0, 0.0),
+                    (right, 0.0),
+                    (inner_right, inner_top),
+                    (inner_left, inner_top),
+                ],
+            ),
+            (
+                border.right,
+                [
+                    (right, 0.0),
+                    (right, bottom),
+                    (inner_right, inner_bottom),
+                    (inner_right, inner_top),
+                ],
+            ),
+            (
+                border.bottom,
+                [
+                    (right, bottom),
+                    (0.0, bottom),
+                    (inner_left, inner_bottom),
+                    (inner_right, inner_bottom),
+                ],
+            ),
+            (
+                border.left,
+                [
+                    (0.0, bottom),
+                    (0.0, 0.0),
+                    (inner_left, inner_top),
+                    (inner_left, inner_bottom),
+                ],
+            ),
+        ];
+
+        for (side, points) in sides {
+            if side.effective_width() <= 0.0 {
+                continue;
+            }
+
+            let brush = canvas.add_paint(Paint {
+                anti_alias: true,
+                color: side.color,
+
+                ..Paint::default()
+            });
+
+            let mut builder = Path::builder();
+
+            builder.begin(point(points[0].0, points[0].1));
+
+            for (x, y) in &points[1..] {
+                builder.line_to(point(*x, *y));
+            }
+
+            builder.close();
+
+            canvas.draw_path(&brush, builder.build());
+        }
+    }
+}
+
+impl RenderObjectImpl for RenderDecoratedBox {
+    fn does_paint(&self) -> bool {
+        true
+    }
+
+    fn paint_bounds(&self, size: Size) -> Rect {
+        let rect = Offset::ZERO & size;
+
+        self.decoration
+            .box_shadow
+            .iter()
+            .fold(rect, |bounds, shadow| bounds.union(shadow.bounds(rect)))
+    }
+
+    fn paint(&self, mut canvas: CanvasPainter) {
+        let size = canvas.size();
+
+        let radius: BorderRadius = self.decoration.border_radius;
+
+        for shadow in &self.decoration.box_shadow {
+            canvas.draw_shadow(self.shape(), *shadow);
+        }
+
+        if let Some(color) = self.decoration.color {
+            let brush = canvas.add_paint(Paint {
+                anti_alias: !radius.is_zero(),
+                color,
+
+                ..Paint::default()
+            });
+
+            if radius.is_zero() {
+                canvas.draw_rect(&brush);
+            } else {
+                canvas.draw_rounded_rect(
+                    &brush,
+                    radius.top_left,
+                    radius.top_right,
+                    radius.bottom_right,
+                    radius.bottom_left,
+                );
+            }
+        }
+
+        if let Some(border) = &self.decoration.border {
+            if border.is_uniform() {
+                if border.top.effective_width() > 0.0 {
+                    self.paint_uniform_border(&mut canvas, size, border.top);
+                }
+            } else if let Some(color) = border.uniform_color().filter(|_| !radius.is_zero()) {
+                self.paint_rounded_border(&mut canvas, size, border, color);
+            } else {
+                self.paint_border_sides(&mut canvas, size, border);
+            }
+        }
+    }
+}
+
+fn border_radii(radius: BorderRadius) -> BorderRadii {
+    BorderRadii {
+        top_left: radius.top_left,
+        top_right: radius.top_right,
+        bottom_left: radius.bottom_left,
+        bottom_right: radius.bottom_right,
+    }
+}
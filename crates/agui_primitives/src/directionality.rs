@@ -0,0 +1,151 @@
+use agui_core::{element::ElementBuildContext, unit::TextDirection, widget::Widget};
+use agui_elements::inherited::InheritedWidget;
+use agui_macros::InheritedWidget;
+
+/// Sets the direction that text and directional layouts within its subtree flow in.
+///
+/// Text takes this as its base direction, and rows, [`Align`](crate::align::Align) and
+/// [`Padding`](crate::padding::Padding) use it to decide which side is their start.
+/// Without an ancestor `Directionality`, layouts are left-to-right and text takes the
+/// direction of its content.
+#[derive(InheritedWidget)]
+pub struct Directionality {
+    pub text_direction: TextDirection,
+
+    pub child: Widget,
+}
+
+impl InheritedWidget for Directionality {
+    fn child(&self) -> Widget {
+        self.child.clone()
+    }
+
+    fn should_notify(&self, old_widget: &Self) -> bool {
+        self.text_direction != old_widget.text_direction
+    }
+}
+
+impl Directionality {
+    /// Returns the direction set by the closest ancestor `Directionality`, and rebuilds
+    /// the widget whenever it changes.
+    pub fn of(ctx: &mut ElementBuildContext) -> Option<TextDirection> {
+        ctx.depend_on_inherited_widget::<Self>()
+            .map(|directionality| directionality.text_direction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use agui_core::{
+        engine::elements::{strategies::mocks::MockInflateElements, ElementTree},
+        query::by_widget::{ExactWidgetIterator, FilterByWidgetExt},
+        unit::{EdgeInsets, EdgeInsetsDirectional, TextDirection, TextStyle},
+        widget::{IntoWidget, Widget},
+    };
+
+    use crate::{
+        flex::{RawFlex, Row},
+        padding::{Padding, RawPadding},
+        text::{RawParagraph, Text},
+    };
+
+    use super::Directionality;
+
+    fn inflate(child: Widget) -> ElementTree {
+        let mut tree = ElementTree::new();
+
+        tree.inflate(
+            &mut MockInflateElements::default(),
+            Directionality {
+                text_direction: TextDirection::RightToLeft,
+
+                child,
+            }
+            .into_widget(),
+        )
+        .expect("failed to inflate widget");
+
+        tree
+    }
+
+    #[test]
+    pub fn text_and_rows_take_the_ambient_direction() {
+        let tree = inflate(
+            Row::builder()
+                .children(vec![Text {
+                    style: TextStyle::default(),
+
+                    text: "foo".into(),
+                }
+                .into_widget()])
+                .build()
+                .into_widget(),
+        );
+
+        let flex = tree
+            .iter()
+            .filter_widget::<RawFlex>()
+            .and_downcast()
+            .next()
+            .expect("should have built a flex");
+
+        assert_eq!(flex.text_direction, Some(TextDirection::RightToLeft));
+
+        let paragraph = tree
+            .iter()
+            .filter_widget::<RawParagraph>()
+            .and_downcast()
+            .next()
+            .expect("should have built a paragraph");
+
+        assert_eq!(
+            paragraph.style.text_direction,
+            Some(TextDirection::RightToLeft)
+        );
+    }
+
+    #[test]
+    pub fn explicit_directions_are_kept() {
+        let tree = inflate(
+            Text {
+                style: TextStyle::default().text_direction(TextDirection::LeftToRight),
+
+                text: "foo".into(),
+            }
+            .into_widget(),
+        );
+
+        let paragraph = tree
+            .iter()
+            .filter_widget::<RawParagraph>()
+            .and_downcast()
+            .next()
+            .expect("should have built a paragraph");
+
+        assert_eq!(
+            paragraph.style.text_direction,
+            Some(TextDirection::LeftToRight)
+        );
+    }
+
+    #[test]
+    pub fn directional_padding_is_mirrored() {
+        let tree = inflate(
+            Padding {
+                padding: EdgeInsetsDirectional::new(1.0, 2.0, 3.0, 4.0).into(),
+
+                child: None,
+            }
+            .into_widget(),
+        );
+
+        let padding = tree
+            .iter()
+            .filter_widget::<RawPadding>()
+            .and_downcast()
+            .next()
+            .expect("should have built the padding");
+
+        assert_eq!(padding.padding, EdgeInsets::new(1.0, 4.0, 3.0, 2.0));
+    }
+}
@@ -1,16 +1,16 @@
 use agui_core::{
-    element::{RenderObjectCreateContext, RenderObjectUpdateContext},
     unit::{Axis, TextDirection},
-    widget::Widget,
+    widget::{IntoWidget, Widget},
 };
-use agui_elements::render::RenderObjectWidget;
-use agui_macros::RenderObjectWidget;
+use agui_elements::stateless::{StatelessBuildContext, StatelessWidget};
+use agui_macros::StatelessWidget;
 
-use crate::flex::{CrossAxisAlignment, MainAxisAlignment, MainAxisSize, VerticalDirection};
-
-use super::{FlexChildParams, RenderFlex};
+use crate::{
+    directionality::Directionality,
+    flex::{CrossAxisAlignment, MainAxisAlignment, MainAxisSize, RawFlex, VerticalDirection},
+};
 
-#[derive(RenderObjectWidget, Debug)]
+#[derive(StatelessWidget, Debug)]
 #[props(default)]
 pub struct Column {
     pub main_axis_size: MainAxisSize,
@@ -19,6 +19,8 @@ pub struct Column {
     pub cross_axis_alignment: CrossAxisAlignment,
     pub vertical_direction: VerticalDirection,
 
+    /// The direction that children are aligned in along the cross axis. If unset, the
+    /// ambient [`Directionality`] is used.
     pub text_direction: Option<TextDirection>,
 
     // pub clip_behavior: ClipBehavior,
@@ -26,15 +28,9 @@ pub struct Column {
     pub children: Vec<Widget>,
 }
 
-impl RenderObjectWidget for Column {
-    type RenderObject = RenderFlex;
-
-    fn children(&self) -> Vec<Widget> {
-        self.children.clone()
-    }
-
-    fn create_render_object(&self, _: &mut RenderObjectCreateContext) -> Self::RenderObject {
-        RenderFlex {
+impl StatelessWidget for Column {
+    fn build(&self, ctx: &mut StatelessBuildContext<Self>) -> Widget {
+        RawFlex {
             direction: Axis::Vertical,
 
             main_axis_size: self.main_axis_size,
@@ -42,32 +38,10 @@ impl RenderObjectWidget for Column {
             cross_axis_alignment: self.cross_axis_alignment,
             vertical_direction: self.vertical_direction,
 
-            text_direction: self.text_direction,
+            text_direction: self.text_direction.or_else(|| Directionality::of(ctx)),
 
-            // clip_behavior: self.clip_behavior,
-            children_params: self.children.iter().map(FlexChildParams::from).collect(),
+            children: self.children.clone(),
         }
-    }
-
-    fn update_render_object(
-        &self,
-        ctx: &mut RenderObjectUpdateContext,
-        render_object: &mut Self::RenderObject,
-    ) {
-        render_object.update_direction(ctx, Axis::Vertical);
-
-        render_object.update_main_axis_size(ctx, self.main_axis_size);
-        render_object.update_main_axis_alignment(ctx, self.main_axis_alignment);
-        render_object.update_cross_axis_alignment(ctx, self.cross_axis_alignment);
-        render_object.update_vertical_direction(ctx, self.vertical_direction);
-
-        render_object.update_text_direction(ctx, self.text_direction);
-
-        // render_object.update_clip_behavior(ctx, self.clip_behavior);
-
-        render_object.update_children_params(
-            ctx,
-            self.children.iter().map(FlexChildParams::from).collect(),
-        );
+        .into_widget()
     }
 }
@@ -2,9 +2,14 @@ use agui_core::{
     element::{ContextDirtyRenderObject, RenderObjectUpdateContext},
     render::{
         canvas::{paint::Paint, painter::CanvasPainter},
-        object::{RenderObjectImpl, RenderObjectIntrinsicSizeContext, RenderObjectLayoutContext},
+        object::{
+            RenderObjectBaselineContext, RenderObjectImpl, RenderObjectIntrinsicSizeContext,
+            RenderObjectLayoutContext,
+        },
+    },
+    unit::{
+        Axis, Constraints, IntrinsicDimension, Offset, Shape, Size, TextBaseline, TextDirection,
     },
-    unit::{Axis, Constraints, IntrinsicDimension, Offset, Shape, Size, TextDirection},
 };
 
 use crate::flex::FlexFit;
@@ -239,23 +244,35 @@ impl RenderObjectImpl for RenderFlex {
            };
         */
 
-        // let mut max_baseline_distance = 0.0;
+        let mut max_baseline_distance = 0.0_f32;
+
+        // The distance from the top of each child to its baseline, if the children of a row
+        // are aligned by their baselines
+        let baseline_distances = match self.cross_axis_alignment {
+            CrossAxisAlignment::Baseline(text_baseline) if self.direction == Axis::Horizontal => {
+                let mut max_size_below_baseline = 0.0_f32;
+
+                let distances = ctx
+                    .iter_children()
+                    .zip(&child_sizes)
+                    .map(|(child, child_size)| {
+                        let distance = child.compute_distance_to_baseline(text_baseline)?;
 
-        // if let CrossAxisAlignment::Baseline(text_baseline) = self.cross_axis_alignment {
-        //     let mut max_size_above_baseline = 0.0;
-        //     let mut max_size_below_baseline = 0.0;
+                        max_baseline_distance = max_baseline_distance.max(distance);
+                        max_size_below_baseline =
+                            max_size_below_baseline.max(child_size.height - distance);
 
-        //     for (idx, child_id) in ctx.get_children().iter().enumerate() {
-        //         /*
-        //         if let Some(distance) = ctx.get_distance_to_baseline(*child_id, text_baseline, only_real: true) {
-        //             max_baseline_distance = max_baseline_distance.max(distance);
-        //             max_size_above_baseline = max_size_above_baseline.max(distance);
-        //             max_size_below_baseline = max_size_below_baseline.max(child.size.height - distance);
-        //             cross_size = cross_size.max(max_size_above_baseline + max_size_below_baseline);
-        //         }
-        //         */
-        //     }
-        // }
+                        Some(distance)
+                    })
+                    .collect::<Vec<_>>();
+
+                cross_size = cross_size.max(max_baseline_distance + max_size_below_baseline);
+
+                distances
+            }
+
+            _ => Vec::new(),
+        };
 
         let size = match self.direction {
             Axis::Horizontal => {
@@ -364,19 +381,13 @@ impl RenderObjectImpl for RenderFlex {
 
                 CrossAxisAlignment::Stretch => 0.0,
 
-                CrossAxisAlignment::Baseline(_) => {
-                    // if self.direction == Axis::Horizontal {
-                    //     let distance = ctx.get_distance_to_baseline(*child_id, text_baseline, onlyReal: true);
-
-                    //     if let Some(distance) = distance {
-                    //         max_baseline_distance - distance
-                    //     } else {
-                    //         0.0
-                    //     }
-                    // }
-
-                    0.0
-                }
+                // Children without a baseline, and the children of a column, are placed at
+                // the start
+                CrossAxisAlignment::Baseline(_) => baseline_distances
+                    .get(child.index())
+                    .copied()
+                    .flatten()
+                    .map_or(0.0, |distance| max_baseline_distance - distance),
             };
 
             if flip_main_axis {
@@ -403,6 +414,25 @@ impl RenderObjectImpl for RenderFlex {
         size
     }
 
+    /// A row's baseline is the highest baseline of its children, and a column's baseline is
+    /// that of its first child that has one.
+    fn compute_distance_to_baseline(
+        &self,
+        ctx: &mut RenderObjectBaselineContext,
+        baseline: TextBaseline,
+    ) -> Option<f32> {
+        let mut distances = ctx.iter_children().filter_map(|child| {
+            child
+                .compute_distance_to_baseline(baseline)
+                .map(|distance| distance + child.offset().y)
+        });
+
+        match self.direction {
+            Axis::Horizontal => distances.reduce(f32::min),
+            Axis::Vertical => distances.next(),
+        }
+    }
+
     fn does_paint(&self) -> bool {
         true
     }
@@ -1,24 +1,27 @@
 use agui_core::{
-    element::{RenderObjectCreateContext, RenderObjectUpdateContext},
     unit::{Axis, ClipBehavior, TextDirection},
-    widget::Widget,
+    widget::{IntoWidget, Widget},
 };
-use agui_elements::render::RenderObjectWidget;
-use agui_macros::RenderObjectWidget;
+use agui_elements::stateless::{StatelessBuildContext, StatelessWidget};
+use agui_macros::StatelessWidget;
+
+use crate::directionality::Directionality;
 
 mod column;
 mod flex_render_object;
 mod flexible;
 mod params;
+mod raw_flex;
 mod row;
 
 pub use column::*;
 pub use flex_render_object::*;
 pub use flexible::*;
 pub use params::*;
+pub use raw_flex::*;
 pub use row::*;
 
-#[derive(RenderObjectWidget, Debug)]
+#[derive(StatelessWidget, Debug)]
 #[props(default)]
 pub struct Flex {
     #[prop(!default)]
@@ -29,6 +32,8 @@ pub struct Flex {
     pub cross_axis_alignment: CrossAxisAlignment,
     pub vertical_direction: VerticalDirection,
 
+    /// The direction that horizontal children are laid out in. If unset, the ambient
+    /// [`Directionality`] is used.
     pub text_direction: Option<TextDirection>,
 
     pub clip_behavior: ClipBehavior,
@@ -37,15 +42,9 @@ pub struct Flex {
     pub children: Vec<Widget>,
 }
 
-impl RenderObjectWidget for Flex {
-    type RenderObject = RenderFlex;
-
-    fn children(&self) -> Vec<Widget> {
-        self.children.clone()
-    }
-
-    fn create_render_object(&self, _: &mut RenderObjectCreateContext) -> Self::RenderObject {
-        RenderFlex {
+impl StatelessWidget for Flex {
+    fn build(&self, ctx: &mut StatelessBuildContext<Self>) -> Widget {
+        RawFlex {
             direction: self.direction,
 
             main_axis_size: self.main_axis_size,
@@ -53,27 +52,10 @@ impl RenderObjectWidget for Flex {
             cross_axis_alignment: self.cross_axis_alignment,
             vertical_direction: self.vertical_direction,
 
-            text_direction: self.text_direction,
+            text_direction: self.text_direction.or_else(|| Directionality::of(ctx)),
 
-            // clip_behavior: self.clip_behavior,
-            children_params: self.children.iter().map(FlexChildParams::from).collect(),
+            children: self.children.clone(),
         }
-    }
-
-    fn update_render_object(
-        &self,
-        ctx: &mut RenderObjectUpdateContext,
-        render_object: &mut Self::RenderObject,
-    ) {
-        render_object.update_direction(ctx, self.direction);
-
-        render_object.update_main_axis_size(ctx, self.main_axis_size);
-        render_object.update_main_axis_alignment(ctx, self.main_axis_alignment);
-        render_object.update_cross_axis_alignment(ctx, self.cross_axis_alignment);
-        render_object.update_vertical_direction(ctx, self.vertical_direction);
-
-        render_object.update_text_direction(ctx, self.text_direction);
-
-        // render_object.update_clip_behavior(ctx, self.clip_behavior);
+        .into_widget()
     }
 }
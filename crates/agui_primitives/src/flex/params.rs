@@ -1,6 +1,4 @@
-use agui_core::widget::Widget;
-
-use crate::text::TextBaseline;
+use agui_core::{unit::TextBaseline, widget::Widget};
 
 use super::Flexible;
 
@@ -0,0 +1,113 @@
+use agui_core::{
+    callback::Callback,
+    element::{RenderObjectCreateContext, RenderObjectUpdateContext},
+    input::{EventResult, InputEvent, KeyEvent, PointerEventKind},
+    render::object::{RenderObjectEventContext, RenderObjectHitTestContext, RenderObjectImpl},
+    unit::{HitTest, Offset},
+    widget::Widget,
+};
+use agui_elements::render::RenderObjectWidget;
+use agui_macros::RenderObjectWidget;
+
+use crate::listener::{hit_test_with_behavior, HitTestBehavior};
+
+/// Receives keyboard and text events once it has been focused.
+///
+/// The widget takes focus when a pointer goes down anywhere within its bounds. Events that
+/// it has no callback for are passed on to its ancestors.
+#[derive(RenderObjectWidget, Debug)]
+#[props(default)]
+pub struct Focus {
+    pub on_key: Option<Callback<KeyEvent>>,
+    pub on_text: Option<Callback<String>>,
+
+    pub on_focus_change: Option<Callback<bool>>,
+
+    #[prop(into)]
+    pub child: Option<Widget>,
+}
+
+impl RenderObjectWidget for Focus {
+    type RenderObject = RenderFocus;
+
+    fn children(&self) -> Vec<Widget> {
+        Vec::from_iter(self.child.clone())
+    }
+
+    fn create_render_object(&self, _: &mut RenderObjectCreateContext) -> Self::RenderObject {
+        RenderFocus {
+            on_key: self.on_key.clone(),
+            on_text: self.on_text.clone(),
+
+            on_focus_change: self.on_focus_change.clone(),
+        }
+    }
+
+    fn update_render_object(
+        &self,
+        _: &mut RenderObjectUpdateContext,
+        render_object: &mut Self::RenderObject,
+    ) {
+        render_object.on_key = self.on_key.clone();
+        render_object.on_text = self.on_text.clone();
+
+        render_object.on_focus_change = self.on_focus_change.clone();
+    }
+}
+
+pub struct RenderFocus {
+    pub on_key: Option<Callback<KeyEvent>>,
+    pub on_text: Option<Callback<String>>,
+
+    pub on_focus_change: Option<Callback<bool>>,
+}
+
+impl RenderObjectImpl for RenderFocus {
+    fn hit_test<'ctx>(
+        &self,
+        ctx: &'ctx mut RenderObjectHitTestContext<'ctx>,
+        position: Offset,
+    ) -> HitTest {
+        hit_test_with_behavior(ctx, HitTestBehavior::Translucent, position)
+    }
+
+    fn handle_event(&self, ctx: &mut RenderObjectEventContext, event: &InputEvent) -> EventResult {
+        match event {
+            InputEvent::Pointer(event) if event.kind == PointerEventKind::Down => {
+                ctx.request_focus();
+
+                EventResult::Ignored
+            }
+
+            InputEvent::Key(event) => match &self.on_key {
+                Some(on_key) => {
+                    on_key.call(*event);
+
+                    EventResult::Handled
+                }
+
+                None => EventResult::Ignored,
+            },
+
+            InputEvent::Text(text) => match &self.on_text {
+                Some(on_text) => {
+                    on_text.call(text.clone());
+
+                    EventResult::Handled
+                }
+
+                None => EventResult::Ignored,
+            },
+
+            InputEvent::FocusChanged(has_focus) => {
+                if let Some(on_focus_change) = &self.on_focus_change {
+                    on_focus_change.call(*has_focus);
+                }
+
+                EventResult::Handled
+            }
+
+            InputEvent::Pointer(_) | InputEvent::Ime(_) => EventResult::Ignored,
+        }
+    }
+}
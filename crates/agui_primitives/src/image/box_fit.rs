@@ -0,0 +1,230 @@
+use agui_core::unit::Size;
+
+/// How an image should be inscribed into the space allocated to it during layout.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BoxFit {
+    /// Fill the target box by distorting the image's aspect ratio.
+    Fill,
+
+    /// As large as possible while still containing the entire image within the target box.
+    Contain,
+
+    /// As small as possible while still covering the entire target box.
+    Cover,
+
+    /// Match the width of the target box, regardless of whether the image overflows it
+    /// vertically.
+    FitWidth,
+
+    /// Match the height of the target box, regardless of whether the image overflows it
+    /// horizontally.
+    FitHeight,
+
+    /// Center the image within the target box without scaling it, discarding any portion
+    /// that lies outside of it.
+    None,
+
+    /// Center the image within the target box and, if necessary, scale it down to ensure
+    /// it fits. This is the same as [`BoxFit::Contain`] if that would shrink the image,
+    /// otherwise it is the same as [`BoxFit::None`].
+    #[default]
+    ScaleDown,
+}
+
+/// The result of applying a [`BoxFit`] to an image.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FittedSizes {
+    /// The size of the region of the image that should be drawn.
+    pub source: Size,
+
+    /// The size of the region in the target box the source region should be drawn into.
+    pub destination: Size,
+}
+
+impl BoxFit {
+    /// Computes how an image of the `input` size should be fitted into a box of the
+    /// `output` size.
+    pub fn apply(self, input: Size, output: Size) -> FittedSizes {
+        if input.width <= 0.0 || input.height <= 0.0 || output.width <= 0.0 || output.height <= 0.0
+        {
+            return FittedSizes {
+                source: Size::ZERO,
+                destination: Size::ZERO,
+            };
+        }
+
+        let input_aspect = input.width / input.height;
+        let output_aspect = output.width / output.height;
+
+        match self {
+            BoxFit::Fill => FittedSizes {
+                source: input,
+                destination: output,
+            },
+
+            BoxFit::Contain => FittedSizes {
+                source: input,
+                destination: if output_aspect > input_aspect {
+                    Size::new(input.width * output.height / input.height, output.height)
+                } else {
+                    Size::new(output.width, input.height * output.width / input.width)
+                },
+            },
+
+            BoxFit::Cover => FittedSizes {
+                source: if output_aspect > input_aspect {
+                    Size::new(input.width, input.width * output.height / output.width)
+                } else {
+                    Size::new(input.height * output.width / output.height, input.height)
+                },
+                destination: output,
+            },
+
+            BoxFit::FitWidth => {
+                if output_aspect > input_aspect {
+                    // Like `cover`
+                    FittedSizes {
+                        source: Size::new(input.width, input.width * output.height / output.width),
+                        destination: output,
+                    }
+                } else {
+                    // Like `contain`
+                    FittedSizes {
+                        source: input,
+                        destination: Size::new(
+                            output.width,
+                            input.height * output.width / input.width,
+                        ),
+                    }
+                }
+            }
+
+            BoxFit::FitHeight => {
+                if output_aspect > input_aspect {
+                    // Like `contain`
+                    FittedSizes {
+                        source: input,
+                        destination: Size::new(
+                            input.width * output.height / input.height,
+                            output.height,
+                        ),
+                    }
+                } else {
+                    // Like `cover`
+                    FittedSizes {
+                        source: Size::new(
+                            input.height * output.width / output.height,
+                            input.height,
+                        ),
+                        destination: output,
+                    }
+                }
+            }
+
+            BoxFit::None => {
+                let source = Size::new(
+                    input.width.min(output.width),
+                    input.height.min(output.height),
+                );
+
+                FittedSizes {
+                    source,
+                    destination: source,
+                }
+            }
+
+            BoxFit::ScaleDown => {
+                let FittedSizes {
+                    source,
+                    destination,
+                } = BoxFit::Contain.apply(input, output);
+
+                if destination.width > input.width || destination.height > input.height {
+                    FittedSizes {
+                        source,
+                        destination: input,
+                    }
+                } else {
+                    FittedSizes {
+                        source,
+                        destination,
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use agui_core::unit::Size;
+
+    use super::{BoxFit, FittedSizes};
+
+    const WIDE: Size = Size::new(200.0, 100.0);
+    const SQUARE: Size = Size::new(100.0, 100.0);
+
+    #[test]
+    fn contain() {
+        assert_eq!(
+            BoxFit::Contain.apply(WIDE, SQUARE),
+            FittedSizes {
+                source: WIDE,
+                destination: Size::new(100.0, 50.0),
+            }
+        );
+    }
+
+    #[test]
+    fn cover() {
+        assert_eq!(
+            BoxFit::Cover.apply(WIDE, SQUARE),
+            FittedSizes {
+                source: Size::new(100.0, 100.0),
+                destination: SQUARE,
+            }
+        );
+    }
+
+    #[test]
+    fn fit_width() {
+        assert_eq!(
+            BoxFit::FitWidth.apply(WIDE, SQUARE).destination,
+            Size::new(100.0, 50.0)
+        );
+
+        assert_eq!(
+            BoxFit::FitWidth.apply(SQUARE, WIDE),
+            FittedSizes {
+                source: Size::new(100.0, 50.0),
+                destination: WIDE,
+            }
+        );
+    }
+
+    #[test]
+    fn none_crops_to_output() {
+        assert_eq!(
+            BoxFit::None.apply(WIDE, SQUARE),
+            FittedSizes {
+                source: SQUARE,
+                destination: SQUARE,
+            }
+        );
+    }
+
+    #[test]
+    fn scale_down_never_enlarges() {
+        assert_eq!(
+            BoxFit::ScaleDown
+                .apply(Size::new(10.0, 10.0), SQUARE)
+                .destination,
+            Size::new(10.0, 10.0)
+        );
+
+        assert_eq!(
+            BoxFit::ScaleDown.apply(WIDE, SQUARE).destination,
+            Size::new(100.0, 50.0)
+        );
+    }
+}
@@ -0,0 +1,231 @@
+use std::sync::{Arc, OnceLock};
+
+use agui_core::unit::{Texture, TextureData, TextureId};
+use lru::LruCache;
+use parking_lot::Mutex;
+
+/// A cache of decoded images, keyed on the [identity](Texture::id) of the texture they were
+/// decoded from, so that looking up an image never reads the data of its source.
+///
+/// The cache is bounded by the total number of bytes of pixel data it holds. When an
+/// insertion would exceed the budget, the least recently used images are evicted.
+///
+/// Cloning the cache is cheap and the clones share the same underlying storage.
+#[derive(Clone)]
+pub struct ImageCache {
+    inner: Arc<Mutex<ImageCacheInner>>,
+}
+
+struct ImageCacheInner {
+    entries: LruCache<TextureId, Texture>,
+
+    byte_budget: usize,
+    current_bytes: usize,
+}
+
+impl Default for ImageCache {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_BYTE_BUDGET)
+    }
+}
+
+impl ImageCache {
+    pub const DEFAULT_BYTE_BUDGET: usize = 100 << 20;
+
+    pub fn new(byte_budget: usize) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(ImageCacheInner {
+                entries: LruCache::unbounded(),
+
+                byte_budget,
+                current_bytes: 0,
+            })),
+        }
+    }
+
+    /// The cache shared by all images which aren't given one explicitly.
+    pub fn global() -> &'static ImageCache {
+        static GLOBAL: OnceLock<ImageCache> = OnceLock::new();
+
+        GLOBAL.get_or_init(ImageCache::default)
+    }
+
+    pub fn byte_budget(&self) -> usize {
+        self.inner.lock().byte_budget
+    }
+
+    /// Changes the maximum number of bytes the cache may hold, evicting images as
+    /// necessary to fit within it.
+    pub fn set_byte_budget(&self, byte_budget: usize) {
+        let mut inner = self.inner.lock();
+
+        inner.byte_budget = byte_budget;
+        inner.evict();
+    }
+
+    /// The number of bytes of pixel data currently held by the cache.
+    pub fn current_bytes(&self) -> usize {
+        self.inner.lock().current_bytes
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.lock().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.lock().entries.is_empty()
+    }
+
+    /// Returns the decoded image for the `source` texture, marking it as recently used.
+    pub fn get(&self, source: &Texture) -> Option<Texture> {
+        self.inner.lock().entries.get(&source.id()).cloned()
+    }
+
+    /// Caches the decoded `image` for the `source` texture. Images larger than the entire
+    /// byte budget are not cached.
+    pub fn insert(&self, source: Texture, image: Texture) {
+        let size = byte_size(&image);
+
+        let mut inner = self.inner.lock();
+
+        if size > inner.byte_budget {
+            return;
+        }
+
+        if let Some(previous) = inner.entries.put(source.id(), image) {
+            inner.current_bytes -= byte_size(&previous);
+        }
+
+        inner.current_bytes += size;
+        inner.evict();
+    }
+
+    pub fn remove(&self, source: &Texture) -> Option<Texture> {
+        let mut inner = self.inner.lock();
+
+        let image = inner.entries.pop(&source.id())?;
+
+        inner.current_bytes -= byte_size(&image);
+
+        Some(image)
+    }
+
+    pub fn clear(&self) {
+        let mut inner = self.inner.lock();
+
+        inner.entries.clear();
+        inner.current_bytes = 0;
+    }
+}
+
+impl ImageCacheInner {
+    fn evict(&mut self) {
+        while self.current_bytes > self.byte_budget {
+            let Some((_, image)) = self.entries.pop_lru() else {
+                break;
+            };
+
+            self.current_bytes -= byte_size(&image);
+        }
+    }
+}
+
+impl PartialEq for ImageCache {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl std::fmt::Debug for ImageCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let inner = self.inner.lock();
+
+        f.debug_struct("ImageCache")
+            .field("len", &inner.entries.len())
+            .field("byte_budget", &inner.byte_budget)
+            .field("current_bytes", &inner.current_bytes)
+            .finish()
+    }
+}
+
+fn byte_size(texture: &Texture) -> usize {
+    match texture.data() {
+        TextureData::Bytes(bytes) => bytes.len(),
+        TextureData::Url(_) => 0,
+        TextureData::Rgba8 { pixels, .. } => pixels.len(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use agui_core::unit::Texture;
+
+    use super::ImageCache;
+
+    fn image(width: u32, height: u32) -> Texture {
+        Texture::from_rgba8(width, height, vec![0; (width * height * 4) as usize])
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let cache = ImageCache::new(64 * 3);
+
+        let sources = (0..4u8)
+            .map(|i| Texture::from_bytes(vec![i]))
+            .collect::<Vec<_>>();
+
+        cache.insert(sources[0].clone(), image(4, 4));
+        cache.insert(sources[1].clone(), image(4, 4));
+        cache.insert(sources[2].clone(), image(4, 4));
+
+        // Mark the first image as recently used, so the second is evicted instead
+        assert!(cache.get(&sources[0]).is_some());
+
+        cache.insert(sources[3].clone(), image(4, 4));
+
+        assert_eq!(cache.len(), 3);
+        assert_eq!(cache.current_bytes(), 64 * 3);
+
+        assert!(cache.get(&sources[0]).is_some());
+        assert!(cache.get(&sources[1]).is_none(), "should have been evicted");
+        assert!(cache.get(&sources[2]).is_some());
+        assert!(cache.get(&sources[3]).is_some());
+    }
+
+    #[test]
+    fn does_not_cache_images_over_budget() {
+        let cache = ImageCache::new(32);
+
+        cache.insert(Texture::from_bytes(vec![0]), image(4, 4));
+
+        assert!(cache.is_empty());
+        assert_eq!(cache.current_bytes(), 0);
+    }
+
+    #[test]
+    fn shrinking_budget_evicts() {
+        let cache = ImageCache::new(128);
+
+        cache.insert(Texture::from_bytes(vec![0]), image(4, 4));
+        cache.insert(Texture::from_bytes(vec![1]), image(4, 4));
+
+        cache.set_byte_budget(64);
+
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.current_bytes(), 64);
+    }
+
+    #[test]
+    fn keys_images_by_the_identity_of_their_source() {
+        let cache = ImageCache::new(128);
+
+        let source = Texture::from_bytes(vec![0; 1024]);
+
+        cache.insert(source.clone(), image(4, 4));
+
+        assert!(cache.get(&source.clone()).is_some());
+
+        // Equal data, but not the same texture
+        assert!(cache.get(&Texture::from_bytes(vec![0; 1024])).is_none());
+    }
+}
@@ -0,0 +1,55 @@
+use agui_core::{
+    asset::{AssetError, AssetResolver},
+    task::spawn_blocking,
+    unit::{Texture, TextureData},
+};
+use url::Url;
+
+#[derive(thiserror::Error, Debug)]
+pub enum ImageError {
+    #[error("failed to load image: {0}")]
+    Asset(#[from] AssetError),
+
+    #[error("failed to decode image: {0}")]
+    Decode(#[from] image::ImageError),
+
+    #[error("image url must be resolved before decoding: {0}")]
+    UnresolvedUrl(Url),
+}
+
+/// Loads the texture using the given resolver, then decodes it into RGBA pixels on a
+/// background thread.
+pub async fn load_texture(
+    assets: &AssetResolver,
+    texture: &Texture,
+) -> Result<Texture, ImageError> {
+    let texture = assets.resolve_texture(texture).await?;
+
+    spawn_blocking(move || decode_texture(&texture)).await
+}
+
+/// Decodes the texture into RGBA pixels.
+///
+/// Supports PNG, JPEG, WebP, and GIF images. For animated images, only the first frame
+/// is decoded. Textures that have already been decoded are returned as-is. Textures that
+/// refer to a URL must be resolved first; see [`load_texture`].
+///
+/// This may block for a significant amount of time, so it should not be called on the
+/// build thread.
+pub fn decode_texture(texture: &Texture) -> Result<Texture, ImageError> {
+    match texture.data() {
+        TextureData::Rgba8 { .. } => Ok(texture.clone()),
+
+        TextureData::Bytes(bytes) => decode_bytes(bytes),
+
+        TextureData::Url(url) => Err(ImageError::UnresolvedUrl(url.clone())),
+    }
+}
+
+fn decode_bytes(bytes: &[u8]) -> Result<Texture, ImageError> {
+    let image = image::load_from_memory(bytes)?.into_rgba8();
+
+    let (width, height) = image.dimensions();
+
+    Ok(Texture::from_rgba8(width, height, image.into_raw()))
+}
@@ -0,0 +1,152 @@
+use agui_core::{
+    asset::AssetResolver,
+    task::{context::ContextSpawnElementTask, TaskHandle},
+    unit::{Alignment, Texture},
+    widget::Widget,
+};
+use agui_elements::stateful::{
+    ContextWidgetStateMut, StatefulBuildContext, StatefulWidget, WidgetState,
+};
+use agui_macros::StatefulWidget;
+
+mod box_fit;
+mod cache;
+mod decode;
+mod raw_image;
+
+pub use box_fit::*;
+pub use cache::*;
+pub use decode::*;
+pub use raw_image::*;
+
+/// Displays an image.
+///
+/// Textures that refer to a URL are loaded through an [`AssetResolver`], then decoded on
+/// a background thread. Nothing is drawn until the image has finished loading. Decoded images are stored in an [`ImageCache`], so the same texture is only
+/// decoded once while it remains in the cache.
+#[derive(StatefulWidget, Debug)]
+pub struct Image {
+    pub texture: Texture,
+
+    #[prop(default)]
+    pub fit: BoxFit,
+    #[prop(default)]
+    pub alignment: Alignment,
+    #[prop(default)]
+    pub repeat: ImageRepeat,
+
+    /// The cache to store the decoded image in. If `None`, the global cache is used.
+    #[prop(default)]
+    pub cache: Option<ImageCache>,
+
+    /// The resolver used to load textures that refer to a URL. If `None`, the global
+    /// resolver is used.
+    #[prop(default)]
+    pub assets: Option<AssetResolver>,
+}
+
+impl StatefulWidget for Image {
+    type State = ImageState;
+
+    fn create_state(&self) -> Self::State {
+        ImageState {
+            image: None,
+            load_task: None,
+        }
+    }
+}
+
+pub struct ImageState {
+    image: Option<Texture>,
+    load_task: Option<TaskHandle<()>>,
+}
+
+impl ImageState {
+    fn load(&mut self, ctx: &mut StatefulBuildContext<Self>) {
+        let source = ctx.widget.texture.clone();
+
+        let cache = ctx
+            .widget
+            .cache
+            .clone()
+            .unwrap_or_else(|| ImageCache::global().clone());
+
+        let assets = ctx
+            .widget
+            .assets
+            .clone()
+            .unwrap_or_else(|| AssetResolver::global().clone());
+
+        // Dropping the previous task cancels it, if it hasn't finished yet
+        self.load_task = None;
+
+        if let Some(image) = cache.get(&source) {
+            self.image = Some(image);
+            return;
+        }
+
+        self.image = None;
+
+        let on_loaded = ctx.callback(
+            |ctx, (source, result): (Texture, Result<Texture, ImageError>)| {
+                // The widget may have been given a different texture while loading
+                if ctx.widget.texture.id() != source.id() {
+                    return;
+                }
+
+                match result {
+                    Ok(image) => ctx.set_state(move |state| {
+                        state.image = Some(image);
+                        state.load_task = None;
+                    }),
+
+                    Err(err) => tracing::error!("failed to load image: {}", err),
+                }
+            },
+        );
+
+        self.load_task = match ctx.spawn_task(move |_| async move {
+            let result = load_texture(&assets, &source).await;
+
+            if let Ok(image) = &result {
+                cache.insert(source.clone(), image.clone());
+            }
+
+            on_loaded.call((source, result));
+        }) {
+            Ok(task) => Some(task),
+            Err(err) => {
+                tracing::warn!("failed to spawn image load task: {:?}", err);
+                None
+            }
+        };
+    }
+}
+
+impl WidgetState for ImageState {
+    type Widget = Image;
+
+    fn init_state(&mut self, ctx: &mut StatefulBuildContext<Self>) {
+        self.load(ctx);
+    }
+
+    fn updated(&mut self, ctx: &mut StatefulBuildContext<Self>, old_widget: &Self::Widget) {
+        if ctx.widget.texture.id() != old_widget.texture.id()
+            || ctx.widget.cache != old_widget.cache
+            || ctx.widget.assets != old_widget.assets
+        {
+            self.load(ctx);
+        }
+    }
+
+    fn build(&mut self, ctx: &mut StatefulBuildContext<Self>) -> Widget {
+        RawImage {
+            image: self.image.clone(),
+
+            fit: ctx.widget.fit,
+            alignment: ctx.widget.alignment,
+            repeat: ctx.widget.repeat,
+        }
+        .into()
+    }
+}
@@ -0,0 +1,284 @@
+use agui_core::{
+    element::{ContextDirtyRenderObject, RenderObjectCreateContext, RenderObjectUpdateContext},
+    render::{
+        canvas::{
+            paint::Paint,
+            painter::{CanvasPainter, CanvasPainterState, Head},
+        },
+        object::{RenderObjectImpl, RenderObjectIntrinsicSizeContext, RenderObjectLayoutContext},
+    },
+    unit::{
+        Alignment, Axis, Bounds, Constraints, IntrinsicDimension, Offset, Rect, Shape, Size,
+        Texture,
+    },
+    widget::Widget,
+};
+use agui_elements::render::RenderObjectWidget;
+use agui_macros::RenderObjectWidget;
+
+use crate::image::{BoxFit, FittedSizes};
+
+/// How to paint any portions of the layout bounds not covered by an image.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ImageRepeat {
+    /// Leave uncovered portions of the bounds transparent.
+    #[default]
+    NoRepeat,
+
+    /// Repeat the image in both the horizontal and vertical directions.
+    Repeat,
+
+    /// Repeat the image only in the horizontal direction.
+    RepeatX,
+
+    /// Repeat the image only in the vertical direction.
+    RepeatY,
+}
+
+/// Displays an image that has already been decoded.
+///
+/// Generally, you'll want to use [`Image`](crate::image::Image) instead, which handles
+/// decoding and caching.
+#[derive(RenderObjectWidget, Debug)]
+#[props(default)]
+pub struct RawImage {
+    /// A texture containing decoded pixels. If `None`, nothing is drawn.
+    pub image: Option<Texture>,
+
+    pub fit: BoxFit,
+    pub alignment: Alignment,
+    pub repeat: ImageRepeat,
+}
+
+impl RenderObjectWidget for RawImage {
+    type RenderObject = RenderImage;
+
+    fn children(&self) -> Vec<Widget> {
+        Vec::default()
+    }
+
+    fn create_render_object(&self, _: &mut RenderObjectCreateContext) -> Self::RenderObject {
+        RenderImage {
+            image: self.image.clone(),
+
+            fit: self.fit,
+            alignment: self.alignment,
+            repeat: self.repeat,
+        }
+    }
+
+    fn update_render_object(
+        &self,
+        ctx: &mut RenderObjectUpdateContext,
+        render_object: &mut Self::RenderObject,
+    ) {
+        render_object.update_image(ctx, self.image.clone());
+
+        render_object.update_fit(ctx, self.fit);
+        render_object.update_alignment(ctx, self.alignment);
+        render_object.update_repeat(ctx, self.repeat);
+    }
+}
+
+pub struct RenderImage {
+    pub image: Option<Texture>,
+
+    pub fit: BoxFit,
+    pub alignment: Alignment,
+    pub repeat: ImageRepeat,
+}
+
+impl RenderImage {
+    fn update_image(&mut self, ctx: &mut RenderObjectUpdateContext, image: Option<Texture>) {
+        if self.image.as_ref().map(Texture::id) == image.as_ref().map(Texture::id) {
+            return;
+        }
+
+        let size_changed = self.image_size() != image.as_ref().and_then(image_size);
+
+        self.image = image;
+
+        if size_changed {
+            ctx.mark_needs_layout();
+        } else {
+            ctx.mark_needs_paint();
+        }
+    }
+
+    fn update_fit(&mut self, ctx: &mut RenderObjectUpdateContext, fit: BoxFit) {
+        if self.fit == fit {
+            return;
+        }
+
+        self.fit = fit;
+        ctx.mark_needs_paint();
+    }
+
+    fn update_alignment(&mut self, ctx: &mut RenderObjectUpdateContext, alignment: Alignment) {
+        if self.alignment == alignment {
+            return;
+        }
+
+        self.alignment = alignment;
+        ctx.mark_needs_paint();
+    }
+
+    fn update_repeat(&mut self, ctx: &mut RenderObjectUpdateContext, repeat: ImageRepeat) {
+        if self.repeat == repeat {
+            return;
+        }
+
+        self.repeat = repeat;
+        ctx.mark_needs_paint();
+    }
+
+    fn image_size(&self) -> Option<Size> {
+        self.image.as_ref().and_then(image_size)
+    }
+
+    fn draw_tiles<S>(
+        &self,
+        canvas: &mut CanvasPainter<Head<S>>,
+        image: &Texture,
+        output: Size,
+        dest_rect: Rect,
+        tex_bounds: Bounds,
+    ) where
+        S: CanvasPainterState,
+    {
+        let (repeat_x, repeat_y) = match self.repeat {
+            ImageRepeat::NoRepeat => (false, false),
+            ImageRepeat::Repeat => (true, true),
+            ImageRepeat::RepeatX => (true, false),
+            ImageRepeat::RepeatY => (false, true),
+        };
+
+        let (start_x, end_x) = tile_range(repeat_x, dest_rect.left, dest_rect.width, output.width);
+        let (start_y, end_y) = tile_range(repeat_y, dest_rect.top, dest_rect.height, output.height);
+
+        for y in start_y..=end_y {
+            for x in start_x..=end_x {
+                canvas.draw_texture_at(
+                    dest_rect.translate(Offset::new(
+                        x as f32 * dest_rect.width,
+                        y as f32 * dest_rect.height,
+                    )),
+                    Shape::Rect,
+                    image.clone(),
+                    tex_bounds,
+                );
+            }
+        }
+    }
+}
+
+impl RenderObjectImpl for RenderImage {
+    fn intrinsic_size(
+        &self,
+        _: &mut RenderObjectIntrinsicSizeContext,
+        dimension: IntrinsicDimension,
+        cross_extent: f32,
+    ) -> f32 {
+        let Some(image_size) = self.image_size() else {
+            return 0.0;
+        };
+
+        // Images keep their aspect ratio when constrained along the cross axis.
+        let constraints = match dimension.axis() {
+            Axis::Horizontal => Constraints::new(0.0, f32::INFINITY, 0.0, cross_extent),
+            Axis::Vertical => Constraints::new(0.0, cross_extent, 0.0, f32::INFINITY),
+        };
+
+        constraints
+            .constrain_preserve_aspect_ratio(image_size)
+            .extent(dimension.axis())
+    }
+
+    fn layout(&self, _: &mut RenderObjectLayoutContext, constraints: Constraints) -> Size {
+        match self.image_size() {
+            Some(image_size) => constraints.constrain_preserve_aspect_ratio(image_size),
+            None => constraints.smallest(),
+        }
+    }
+
+    fn does_paint(&self) -> bool {
+        true
+    }
+
+    fn paint(&self, mut canvas: CanvasPainter) {
+        let (Some(image), Some(image_size)) = (&self.image, self.image_size()) else {
+            return;
+        };
+
+        let output = canvas.size();
+
+        let FittedSizes {
+            source,
+            destination,
+        } = self.fit.apply(image_size, output);
+
+        if destination.width <= 0.0 || destination.height <= 0.0 {
+            return;
+        }
+
+        let source_rect = self.alignment.inscribe(source, Offset::ZERO & image_size);
+        let dest_rect = self.alignment.inscribe(destination, Offset::ZERO & output);
+
+        let tex_bounds = Bounds {
+            top: source_rect.top / image_size.height,
+            right: source_rect.right() / image_size.width,
+            bottom: source_rect.bottom() / image_size.height,
+            left: source_rect.left / image_size.width,
+        };
+
+        let overflows = dest_rect.left < 0.0
+            || dest_rect.top < 0.0
+            || dest_rect.right() > output.width
+            || dest_rect.bottom() > output.height;
+
+        if self.repeat == ImageRepeat::NoRepeat && !overflows {
+            canvas.draw_texture_at(dest_rect, Shape::Rect, image.clone(), tex_bounds);
+        } else {
+            // Tiles (and images that overflow) must be clipped to the bounds of the box
+            let brush = canvas.add_paint(Paint::default());
+
+            canvas.layer(&brush, Shape::Rect, |mut canvas| {
+                self.draw_tiles(&mut canvas, image, output, dest_rect, tex_bounds);
+            });
+        }
+    }
+}
+
+fn image_size(texture: &Texture) -> Option<Size> {
+    texture
+        .dimensions()
+        .map(|(width, height)| Size::new(width as f32, height as f32))
+}
+
+/// Returns the range of tile indices, relative to the tile at `start`, required to cover
+/// the `output` extent.
+fn tile_range(repeat: bool, start: f32, extent: f32, output: f32) -> (i32, i32) {
+    if !repeat || extent <= 0.0 {
+        return (0, 0);
+    }
+
+    let before = (start / extent).ceil() as i32;
+    let after = ((output - (start + extent)) / extent).ceil() as i32;
+
+    (-before.max(0), after.max(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::tile_range;
+
+    #[test]
+    fn tile_range_covers_output() {
+        assert_eq!(tile_range(false, 25.0, 50.0, 100.0), (0, 0));
+
+        // A 50 wide tile at 25 needs one tile before it and one after it to cover 0..100
+        assert_eq!(tile_range(true, 25.0, 50.0, 100.0), (-1, 1));
+
+        assert_eq!(tile_range(true, 0.0, 10.0, 100.0), (0, 9));
+    }
+}
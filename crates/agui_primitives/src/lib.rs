@@ -1,11 +1,19 @@
 pub mod align;
+pub mod backdrop_filter;
+pub mod baseline;
 pub mod builder;
 pub mod clip;
 pub mod colored_box;
+pub mod decorated_box;
+pub mod directionality;
 pub mod flex;
+pub mod focus;
+pub mod image;
 pub mod intrinsic;
 pub mod layout_builder;
+pub mod listener;
 pub mod padding;
 pub mod sized_box;
 pub mod stack;
+pub mod svg;
 pub mod text;
@@ -0,0 +1,160 @@
+use agui_core::{
+    callback::Callback,
+    element::{RenderObjectCreateContext, RenderObjectUpdateContext},
+    input::{EventResult, InputEvent, PointerEvent, PointerEventKind},
+    render::object::{RenderObjectEventContext, RenderObjectHitTestContext, RenderObjectImpl},
+    unit::{HitTest, Offset},
+    widget::Widget,
+};
+use agui_elements::render::RenderObjectWidget;
+use agui_macros::RenderObjectWidget;
+
+/// How a widget that listens for pointer events behaves during hit testing.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HitTestBehavior {
+    /// Only receive events within the bounds of a child that was hit.
+    #[default]
+    DeferToChild,
+
+    /// Receive events anywhere within its bounds, and prevent anything behind it from
+    /// receiving them.
+    Opaque,
+
+    /// Receive events anywhere within its bounds, while still allowing anything behind it
+    /// to receive them.
+    Translucent,
+}
+
+/// Calls its callbacks with the pointer events sent to it.
+///
+/// Once a pointer goes down within the listener, its move, up and cancel events are sent
+/// to the listener even if the pointer leaves its bounds.
+#[derive(RenderObjectWidget, Debug)]
+#[props(default)]
+pub struct Listener {
+    pub behavior: HitTestBehavior,
+
+    pub on_pointer_down: Option<Callback<PointerEvent>>,
+    pub on_pointer_move: Option<Callback<PointerEvent>>,
+    pub on_pointer_up: Option<Callback<PointerEvent>>,
+    pub on_pointer_cancel: Option<Callback<PointerEvent>>,
+    pub on_pointer_scroll: Option<Callback<PointerEvent>>,
+
+    #[prop(into)]
+    pub child: Option<Widget>,
+}
+
+impl RenderObjectWidget for Listener {
+    type RenderObject = RenderPointerListener;
+
+    fn children(&self) -> Vec<Widget> {
+        Vec::from_iter(self.child.clone())
+    }
+
+    fn create_render_object(&self, _: &mut RenderObjectCreateContext) -> Self::RenderObject {
+        RenderPointerListener {
+            behavior: self.behavior,
+
+            on_pointer_down: self.on_pointer_down.clone(),
+            on_pointer_move: self.on_pointer_move.clone(),
+            on_pointer_up: self.on_pointer_up.clone(),
+            on_pointer_cancel: self.on_pointer_cancel.clone(),
+            on_pointer_scroll: self.on_pointer_scroll.clone(),
+        }
+    }
+
+    fn update_render_object(
+        &self,
+        _: &mut RenderObjectUpdateContext,
+        render_object: &mut Self::RenderObject,
+    ) {
+        // None of these affect layout or paint, so they can be replaced outright.
+        render_object.behavior = self.behavior;
+
+        render_object.on_pointer_down = self.on_pointer_down.clone();
+        render_object.on_pointer_move = self.on_pointer_move.clone();
+        render_object.on_pointer_up = self.on_pointer_up.clone();
+        render_object.on_pointer_cancel = self.on_pointer_cancel.clone();
+        render_object.on_pointer_scroll = self.on_pointer_scroll.clone();
+    }
+}
+
+pub struct RenderPointerListener {
+    pub behavior: HitTestBehavior,
+
+    pub on_pointer_down: Option<Callback<PointerEvent>>,
+    pub on_pointer_move: Option<Callback<PointerEvent>>,
+    pub on_pointer_up: Option<Callback<PointerEvent>>,
+    pub on_pointer_cancel: Option<Callback<PointerEvent>>,
+    pub on_pointer_scroll: Option<Callback<PointerEvent>>,
+}
+
+impl RenderObjectImpl for RenderPointerListener {
+    fn hit_test<'ctx>(
+        &self,
+        ctx: &'ctx mut RenderObjectHitTestContext<'ctx>,
+        position: Offset,
+    ) -> HitTest {
+        hit_test_with_behavior(ctx, self.behavior, position)
+    }
+
+    fn handle_event(&self, _: &mut RenderObjectEventContext, event: &InputEvent) -> EventResult {
+        let InputEvent::Pointer(event) = event else {
+            return EventResult::Ignored;
+        };
+
+        let callback = match event.kind {
+            PointerEventKind::Down => &self.on_pointer_down,
+            PointerEventKind::Move => &self.on_pointer_move,
+            PointerEventKind::Up => &self.on_pointer_up,
+            PointerEventKind::Cancel => &self.on_pointer_cancel,
+            PointerEventKind::Scroll { .. } => &self.on_pointer_scroll,
+        };
+
+        match callback {
+            Some(callback) => {
+                callback.call(*event);
+
+                EventResult::Handled
+            }
+
+            None => EventResult::Ignored,
+        }
+    }
+}
+
+/// Hit tests the children of a render object, then decides whether the render object
+/// itself was hit according to `behavior`.
+pub(crate) fn hit_test_with_behavior<'ctx>(
+    ctx: &'ctx mut RenderObjectHitTestContext<'ctx>,
+    behavior: HitTestBehavior,
+    position: Offset,
+) -> HitTest {
+    if !ctx.size().contains(position) {
+        return HitTest::Pass;
+    }
+
+    let render_object_id = *ctx.render_object_id;
+
+    let mut children = ctx.iter_children();
+
+    while let Some(mut child) = children.next_back() {
+        let offset = child.offset();
+
+        if child.hit_test_with_offset(offset, position) == HitTest::Absorb {
+            return HitTest::Absorb;
+        }
+    }
+
+    match behavior {
+        HitTestBehavior::DeferToChild => HitTest::Pass,
+
+        HitTestBehavior::Opaque => HitTest::Absorb,
+
+        HitTestBehavior::Translucent => {
+            ctx.add(render_object_id);
+
+            HitTest::Pass
+        }
+    }
+}
@@ -3,21 +3,60 @@ use agui_core::{
     render::object::{
         RenderObjectImpl, RenderObjectIntrinsicSizeContext, RenderObjectLayoutContext,
     },
-    unit::{Constraints, EdgeInsets, IntrinsicDimension, Offset, Size},
-    widget::Widget,
+    unit::{
+        Constraints, EdgeInsets, EdgeInsetsGeometry, IntrinsicDimension, Offset, Size,
+        TextDirection,
+    },
+    widget::{IntoWidget, Widget},
+};
+use agui_elements::{
+    render::RenderObjectWidget,
+    stateless::{StatelessBuildContext, StatelessWidget},
 };
-use agui_elements::render::RenderObjectWidget;
-use agui_macros::RenderObjectWidget;
+use agui_macros::{RenderObjectWidget, StatelessWidget};
 
-#[derive(RenderObjectWidget, Debug)]
+use crate::directionality::Directionality;
+
+#[derive(StatelessWidget, Debug)]
 pub struct Padding {
+    /// The space around the child. Directional insets are resolved against the ambient
+    /// [`Directionality`].
+    #[prop(into)]
+    pub padding: EdgeInsetsGeometry,
+
+    #[prop(into)]
+    pub child: Option<Widget>,
+}
+
+impl StatelessWidget for Padding {
+    fn build(&self, ctx: &mut StatelessBuildContext<Self>) -> Widget {
+        let text_direction = match self.padding {
+            EdgeInsetsGeometry::Absolute(_) => TextDirection::default(),
+            EdgeInsetsGeometry::Directional(_) => Directionality::of(ctx).unwrap_or_default(),
+        };
+
+        RawPadding {
+            padding: self.padding.resolve(text_direction),
+
+            child: self.child.clone(),
+        }
+        .into_widget()
+    }
+}
+
+/// Insets its child by padding that has already been resolved.
+///
+/// Generally, you'll want to use [`Padding`] instead, which resolves directional insets
+/// against the ambient text direction.
+#[derive(RenderObjectWidget, Debug)]
+pub struct RawPadding {
     pub padding: EdgeInsets,
 
     #[prop(into)]
     pub child: Option<Widget>,
 }
 
-impl RenderObjectWidget for Padding {
+impl RenderObjectWidget for RawPadding {
     type RenderObject = RenderPadding;
 
     fn children(&self) -> Vec<Widget> {
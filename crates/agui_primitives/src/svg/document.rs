@@ -0,0 +1,737 @@
+use std::{str::FromStr, sync::Arc};
+
+use agui_core::{
+    asset::AssetError,
+    render::canvas::paint::Paint,
+    unit::{
+        Alignment, BlendMode, Color, FillRule, Gradient, GradientExtend, GradientKind,
+        GradientStop, Offset, PaintingStyle, Rect, Size, StrokeCap, StrokeJoin, StrokeStyle,
+    },
+};
+use lyon::{
+    math::{point, Transform},
+    path::Path,
+};
+
+use crate::image::{BoxFit, FittedSizes};
+
+#[derive(thiserror::Error, Debug)]
+pub enum SvgError {
+    #[error("failed to load svg: {0}")]
+    Asset(#[from] AssetError),
+
+    #[error("failed to parse svg: {0}")]
+    Parse(#[from] usvg::Error),
+
+    #[error("svg textures must contain the encoded document")]
+    NotEncoded,
+}
+
+/// A parsed SVG document.
+///
+/// Paths, groups, transforms, fills, strokes, gradients, and clip paths are supported.
+/// Text is drawn as the outlines of its glyphs, though usvg only lays it out when its
+/// `text` feature is enabled. Masks, filters, patterns, and embedded images are ignored.
+///
+/// Cloning an `Svg` is cheap, as the parsed document is shared between clones.
+#[derive(Clone)]
+pub struct Svg {
+    inner: Arc<SvgDocument>,
+}
+
+struct SvgDocument {
+    size: Size,
+    view_box: Rect,
+
+    fit: BoxFit,
+    alignment: Alignment,
+
+    nodes: Vec<SvgNode>,
+}
+
+pub(crate) enum SvgNode {
+    /// A filled or stroked path, in view box coordinates.
+    Path { path: Path, paint: Paint },
+
+    /// Nodes that must be composited together before being drawn.
+    Layer {
+        opacity: f32,
+        blend_mode: BlendMode,
+
+        /// The clip path of the layer, in view box coordinates.
+        clip: Option<Path>,
+
+        children: Vec<SvgNode>,
+    },
+}
+
+impl Svg {
+    /// Parses an SVG document. Compressed documents (`.svgz`) are also supported.
+    pub fn from_data(data: &[u8]) -> Result<Self, SvgError> {
+        let decompressed;
+
+        let data = if data.starts_with(&[0x1f, 0x8b]) {
+            decompressed = usvg::decompress_svgz(data)?;
+
+            &decompressed
+        } else {
+            data
+        };
+
+        let text = std::str::from_utf8(data).map_err(|_| usvg::Error::NotAnUtf8Str)?;
+
+        // The tree doesn't expose the view box or how it's fitted, so the document is parsed
+        // here and shared with usvg, rather than being parsed again to read them.
+        let document = usvg::roxmltree::Document::parse_with_options(
+            text,
+            usvg::roxmltree::ParsingOptions {
+                allow_dtd: true,
+                ..Default::default()
+            },
+        )
+        .map_err(usvg::Error::ParsingFailed)?;
+
+        let tree = usvg::Tree::from_xmltree(&document, &usvg::Options::default())?;
+
+        let size = Size::new(tree.size().width(), tree.size().height());
+
+        let root = RootAttributes::from_element(document.root_element(), size);
+
+        // The tree has already been transformed from the view box into the document's size,
+        // but we want to fit the view box into the layout size instead, so undo it.
+        let to_view_box = view_box_transform(root.view_box, size, root.fit, root.alignment)
+            .inverse()
+            .unwrap_or_default();
+
+        let mut nodes = Vec::new();
+
+        convert_group(&mut nodes, tree.root(), &to_view_box);
+
+        Ok(Self {
+            inner: Arc::new(SvgDocument {
+                size,
+                view_box: root.view_box,
+
+                fit: root.fit,
+                alignment: root.alignment,
+
+                nodes,
+            }),
+        })
+    }
+
+    /// The size of the document, as defined by its `width` and `height`.
+    pub fn size(&self) -> Size {
+        self.inner.size
+    }
+
+    /// The region of the document that is visible, as defined by its `viewBox`.
+    pub fn view_box(&self) -> Rect {
+        self.inner.view_box
+    }
+
+    /// How the view box is scaled to fit its layout, as defined by the document's
+    /// `preserveAspectRatio`.
+    pub fn fit(&self) -> BoxFit {
+        self.inner.fit
+    }
+
+    /// How the view box is aligned within its layout, as defined by the document's
+    /// `preserveAspectRatio`.
+    pub fn alignment(&self) -> Alignment {
+        self.inner.alignment
+    }
+
+    pub(crate) fn nodes(&self) -> &[SvgNode] {
+        &self.inner.nodes
+    }
+}
+
+impl FromStr for Svg {
+    type Err = SvgError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Self::from_data(text.as_bytes())
+    }
+}
+
+impl PartialEq for Svg {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl std::fmt::Debug for Svg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Svg")
+            .field("size", &self.inner.size)
+            .field("view_box", &self.inner.view_box)
+            .field("fit", &self.inner.fit)
+            .field("alignment", &self.inner.alignment)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Returns the transform that fits `view_box` into a box of the `output` size.
+pub(crate) fn view_box_transform(
+    view_box: Rect,
+    output: Size,
+    fit: BoxFit,
+    alignment: Alignment,
+) -> Transform {
+    let FittedSizes {
+        source,
+        destination,
+    } = fit.apply(view_box.into(), output);
+
+    if source.width <= 0.0 || source.height <= 0.0 {
+        return Transform::scale(0.0, 0.0);
+    }
+
+    let source_rect = alignment.inscribe(source, view_box);
+    let dest_rect = alignment.inscribe(destination, Offset::ZERO & output);
+
+    Transform::translation(-source_rect.left, -source_rect.top)
+        .then_scale(
+            destination.width / source.width,
+            destination.height / source.height,
+        )
+        .then_translate((dest_rect.left, dest_rect.top).into())
+}
+
+/// Transforms the paint's gradient and stroke into a new coordinate space.
+pub(crate) fn transform_paint(paint: &Paint, transform: &Transform) -> Paint {
+    let scale = transform.determinant().abs().sqrt();
+
+    let mut paint = paint.clone();
+
+    if let PaintingStyle::Stroke(stroke) = &mut paint.style {
+        stroke.width *= scale;
+        stroke.dash_offset *= scale;
+
+        for length in &mut stroke.dash_pattern {
+            *length *= scale;
+        }
+    }
+
+    if let Some(gradient) = &mut paint.gradient {
+        let transform_point = |offset: Offset| {
+            let point = transform.transform_point(point(offset.x, offset.y));
+
+            Offset::new(point.x, point.y)
+        };
+
+        // Gradients can only be transformed by a uniform scale, so skews and non-uniform
+        // scales are approximated.
+        gradient.kind = match gradient.kind {
+            GradientKind::Linear { start, end } => GradientKind::Linear {
+                start: transform_point(start),
+                end: transform_point(end),
+            },
+
+            GradientKind::Radial {
+                center,
+                radius,
+                focal,
+            } => GradientKind::Radial {
+                center: transform_point(center),
+                radius: radius * scale,
+                focal: transform_point(focal),
+            },
+        };
+    }
+
+    paint
+}
+
+struct RootAttributes {
+    view_box: Rect,
+
+    fit: BoxFit,
+    alignment: Alignment,
+}
+
+impl RootAttributes {
+    fn new(size: Size) -> Self {
+        Self {
+            view_box: Offset::ZERO & size,
+
+            fit: BoxFit::Contain,
+            alignment: Alignment::CENTER,
+        }
+    }
+
+    fn from_element(svg: usvg::roxmltree::Node, size: Size) -> Self {
+        let mut attributes = Self::new(size);
+
+        if let Some(view_box) = svg.attribute("viewBox").and_then(parse_view_box) {
+            attributes.view_box = view_box;
+        }
+
+        if let Some((fit, alignment)) = svg
+            .attribute("preserveAspectRatio")
+            .and_then(parse_aspect_ratio)
+        {
+            attributes.fit = fit;
+            attributes.alignment = alignment;
+        }
+
+        attributes
+    }
+}
+
+fn parse_view_box(value: &str) -> Option<Rect> {
+    let mut numbers = value
+        .split(|c: char| c == ',' || c.is_ascii_whitespace())
+        .filter(|part| !part.is_empty())
+        .map(str::parse::<f32>);
+
+    let mut next = || numbers.next()?.ok();
+
+    let view_box = Rect::new(next()?, next()?, next()?, next()?);
+
+    (view_box.width > 0.0 && view_box.height > 0.0).then_some(view_box)
+}
+
+fn parse_aspect_ratio(value: &str) -> Option<(BoxFit, Alignment)> {
+    let mut parts = value
+        .split_ascii_whitespace()
+        .skip_while(|part| *part == "defer");
+
+    let align = parts.next()?;
+
+    if align == "none" {
+        return Some((BoxFit::Fill, Alignment::CENTER));
+    }
+
+    let fit = match parts.next() {
+        None | Some("meet") => BoxFit::Contain,
+        Some("slice") => BoxFit::Cover,
+        Some(_) => return None,
+    };
+
+    let axis = |part: &str| match part {
+        "Min" => Some(-1.0),
+        "Mid" => Some(0.0),
+        "Max" => Some(1.0),
+        _ => None,
+    };
+
+    // Alignments are in the form of `xMidYMid`
+    let (x, y) = align.strip_prefix('x')?.split_once('Y')?;
+
+    Some((fit, Alignment::new(axis(x)?, axis(y)?)))
+}
+
+fn convert_group(nodes: &mut Vec<SvgNode>, group: &usvg::Group, to_view_box: &Transform) {
+    if group.mask().is_some() {
+        tracing::warn!("svg masks are not supported, drawing unmasked");
+    }
+
+    if !group.filters().is_empty() {
+        tracing::warn!("svg filters are not supported, drawing unfiltered");
+    }
+
+    let opacity = group.opacity().get();
+    let blend_mode = convert_blend_mode(group.blend_mode());
+
+    let clip = group.clip_path().map(|clip_path| {
+        if clip_path.clip_path().is_some() {
+            tracing::warn!("nested svg clip paths are not supported, ignoring them");
+        }
+
+        let transform = to_transform(clip_path.transform())
+            .then(&to_transform(group.abs_transform()))
+            .then(to_view_box);
+
+        let mut clip = Path::builder();
+
+        collect_clip(&mut clip, clip_path.root(), &transform);
+
+        clip.build()
+    });
+
+    if opacity >= 1.0 && blend_mode == BlendMode::SourceOver && clip.is_none() {
+        // Groups that don't need to be composited are flattened into their parent
+        for child in group.children() {
+            convert_node(nodes, child, to_view_box);
+        }
+    } else {
+        let mut children = Vec::new();
+
+        for child in group.children() {
+            convert_node(&mut children, child, to_view_box);
+        }
+
+        nodes.push(SvgNode::Layer {
+            opacity,
+            blend_mode,
+
+            clip,
+
+            children,
+        });
+    }
+}
+
+fn convert_node(nodes: &mut Vec<SvgNode>, node: &usvg::Node, to_view_box: &Transform) {
+    match node {
+        usvg::Node::Group(group) => convert_group(nodes, group, to_view_box),
+
+        usvg::Node::Path(path) => convert_path(nodes, path, to_view_box),
+
+        usvg::Node::Text(text) => convert_group(nodes, text.flattened(), to_view_box),
+
+        usvg::Node::Image(_) => {
+            tracing::warn!("images embedded in svgs are not supported, skipping");
+        }
+    }
+}
+
+fn convert_path(nodes: &mut Vec<SvgNode>, path: &usvg::Path, to_view_box: &Transform) {
+    if !path.is_visible() {
+        return;
+    }
+
+    let transform = to_transform(path.abs_transform()).then(to_view_box);
+
+    let fill = path.fill().and_then(|fill| {
+        let paint = Paint {
+            fill_rule: match fill.rule() {
+                usvg::FillRule::NonZero => FillRule::NonZero,
+                usvg::FillRule::EvenOdd => FillRule::EvenOdd,
+            },
+
+            ..convert_paint(fill.paint(), fill.opacity().get())?
+        };
+
+        Some(transform_paint(&paint, &transform))
+    });
+
+    let stroke = path.stroke().and_then(|stroke| {
+        let paint = Paint {
+            style: PaintingStyle::Stroke(StrokeStyle {
+                width: stroke.width().get(),
+
+                join: match stroke.linejoin() {
+                    usvg::LineJoin::Miter | usvg::LineJoin::MiterClip => StrokeJoin::Miter,
+                    usvg::LineJoin::Round => StrokeJoin::Round,
+                    usvg::LineJoin::Bevel => StrokeJoin::Bevel,
+                },
+
+                cap: match stroke.linecap() {
+                    usvg::LineCap::Butt => StrokeCap::Butt,
+                    usvg::LineCap::Round => StrokeCap::Round,
+                    usvg::LineCap::Square => StrokeCap::Square,
+                },
+
+                miter_limit: stroke.miterlimit().get(),
+
+                dash_pattern: stroke.dasharray().map(<[f32]>::to_vec).unwrap_or_default(),
+                dash_offset: stroke.dashoffset(),
+            }),
+
+            ..convert_paint(stroke.paint(), stroke.opacity().get())?
+        };
+
+        Some(transform_paint(&paint, &transform))
+    });
+
+    let paints = match path.paint_order() {
+        usvg::PaintOrder::FillAndStroke => [fill, stroke],
+        usvg::PaintOrder::StrokeAndFill => [stroke, fill],
+    };
+
+    let path = to_path(path.data(), &transform);
+
+    for paint in paints.into_iter().flatten() {
+        nodes.push(SvgNode::Path {
+            path: path.clone(),
+            paint,
+        });
+    }
+}
+
+fn collect_clip(clip: &mut lyon::path::path::Builder, group: &usvg::Group, transform: &Transform) {
+    for node in group.children() {
+        match node {
+            usvg::Node::Group(group) => collect_clip(clip, group, transform),
+
+            usvg::Node::Path(path) => {
+                let transform = to_transform(path.abs_transform()).then(transform);
+
+                clip.extend_from_paths(&[to_path(path.data(), &transform).as_slice()]);
+            }
+
+            usvg::Node::Text(text) => collect_clip(clip, text.flattened(), transform),
+
+            usvg::Node::Image(_) => {}
+        }
+    }
+}
+
+/// Converts the paint, returning `None` if it cannot be drawn.
+fn convert_paint(paint: &usvg::Paint, opacity: f32) -> Option<Paint> {
+    let convert_color = |color: usvg::Color, alpha: f32| Color {
+        red: color.red as f32 / 255.0,
+        green: color.green as f32 / 255.0,
+        blue: color.blue as f32 / 255.0,
+        alpha,
+    };
+
+    let convert_stops = |stops: &[usvg::Stop]| {
+        stops
+            .iter()
+            .map(|stop| {
+                GradientStop::new(
+                    stop.offset().get(),
+                    convert_color(stop.color(), stop.opacity().get() * opacity),
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let convert_extend = |spread_method: usvg::SpreadMethod| match spread_method {
+        usvg::SpreadMethod::Pad => GradientExtend::Pad,
+        usvg::SpreadMethod::Reflect => GradientExtend::Reflect,
+        usvg::SpreadMethod::Repeat => GradientExtend::Repeat,
+    };
+
+    let gradient = match paint {
+        usvg::Paint::Color(color) => {
+            return Some(Paint {
+                anti_alias: true,
+                color: convert_color(*color, opacity),
+
+                ..Paint::default()
+            });
+        }
+
+        usvg::Paint::LinearGradient(linear) => {
+            let transform = to_transform(linear.transform());
+
+            let start = transform.transform_point(point(linear.x1(), linear.y1()));
+            let end = transform.transform_point(point(linear.x2(), linear.y2()));
+
+            Gradient::linear(
+                Offset::new(start.x, start.y),
+                Offset::new(end.x, end.y),
+                convert_stops(linear.stops()),
+            )
+            .with_extend(convert_extend(linear.spread_method()))
+        }
+
+        usvg::Paint::RadialGradient(radial) => {
+            let transform = to_transform(radial.transform());
+
+            let center = transform.transform_point(point(radial.cx(), radial.cy()));
+            let focal = transform.transform_point(point(radial.fx(), radial.fy()));
+
+            Gradient::radial(
+                Offset::new(center.x, center.y),
+                radial.r().get() * transform.determinant().abs().sqrt(),
+                convert_stops(radial.stops()),
+            )
+            .with_focal(Offset::new(focal.x, focal.y))
+            .with_extend(convert_extend(radial.spread_method()))
+        }
+
+        usvg::Paint::Pattern(_) => {
+            tracing::warn!("svg patterns are not supported, skipping");
+
+            return None;
+        }
+    };
+
+    Some(Paint {
+        anti_alias: true,
+        gradient: Some(gradient),
+
+        ..Paint::default()
+    })
+}
+
+fn convert_blend_mode(blend_mode: usvg::BlendMode) -> BlendMode {
+    match blend_mode {
+        usvg::BlendMode::Normal => BlendMode::SourceOver,
+        usvg::BlendMode::Multiply => BlendMode::Multiply,
+        usvg::BlendMode::Screen => BlendMode::Screen,
+        usvg::BlendMode::Overlay => BlendMode::Overlay,
+        usvg::BlendMode::Darken => BlendMode::Darken,
+        usvg::BlendMode::Lighten => BlendMode::Lighten,
+        usvg::BlendMode::ColorDodge => BlendMode::ColorDodge,
+        usvg::BlendMode::ColorBurn => BlendMode::ColorBurn,
+        usvg::BlendMode::HardLight => BlendMode::HardLight,
+        usvg::BlendMode::SoftLight => BlendMode::SoftLight,
+        usvg::BlendMode::Difference => BlendMode::Difference,
+        usvg::BlendMode::Exclusion => BlendMode::Exclusion,
+        usvg::BlendMode::Hue => BlendMode::Hue,
+        usvg::BlendMode::Saturation => BlendMode::Saturation,
+        usvg::BlendMode::Color => BlendMode::Color,
+        usvg::BlendMode::Luminosity => BlendMode::Luminosity,
+    }
+}
+
+fn to_transform(transform: usvg::Transform) -> Transform {
+    Transform::new(
+        transform.sx,
+        transform.ky,
+        transform.kx,
+        transform.sy,
+        transform.tx,
+        transform.ty,
+    )
+}
+
+fn to_path(data: &usvg::tiny_skia_path::Path, transform: &Transform) -> Path {
+    use usvg::tiny_skia_path::PathSegment;
+
+    let mut builder = Path::builder();
+
+    let mut start = point(0.0, 0.0);
+    let mut is_open = false;
+
+    for segment in data.segments() {
+        match segment {
+            PathSegment::MoveTo(to) => {
+                if is_open {
+                    builder.end(false);
+                }
+
+                start = point(to.x, to.y);
+
+                builder.begin(start);
+                is_open = true;
+
+                continue;
+            }
+
+            PathSegment::Close => {
+                if is_open {
+                    builder.end(true);
+                    is_open = false;
+                }
+
+                continue;
+            }
+
+            _ => {}
+        }
+
+        if !is_open {
+            builder.begin(start);
+            is_open = true;
+        }
+
+        match segment {
+            PathSegment::LineTo(to) => {
+                builder.line_to(point(to.x, to.y));
+            }
+
+            PathSegment::QuadTo(ctrl, to) => {
+                builder.quadratic_bezier_to(point(ctrl.x, ctrl.y), point(to.x, to.y));
+            }
+
+            PathSegment::CubicTo(ctrl1, ctrl2, to) => {
+                builder.cubic_bezier_to(
+                    point(ctrl1.x, ctrl1.y),
+                    point(ctrl2.x, ctrl2.y),
+                    point(to.x, to.y),
+                );
+            }
+
+            PathSegment::MoveTo(_) | PathSegment::Close => unreachable!(),
+        }
+    }
+
+    if is_open {
+        builder.end(false);
+    }
+
+    builder.build().transformed(transform)
+}
+
+#[cfg(test)]
+mod tests {
+    use agui_core::unit::{Alignment, Offset, Rect, Size};
+    use lyon::math::point;
+
+    use crate::{
+        image::BoxFit,
+        svg::{document::view_box_transform, Svg, SvgNode},
+    };
+
+    #[test]
+    fn parses_root_attributes() {
+        let svg: Svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="48" height="24"
+            viewBox="0 0 20 10" preserveAspectRatio="xMinYMax slice" />"#
+            .parse()
+            .unwrap();
+
+        assert_eq!(svg.size(), Size::new(48.0, 24.0));
+        assert_eq!(svg.view_box(), Rect::new(0.0, 0.0, 20.0, 10.0));
+        assert_eq!(svg.fit(), BoxFit::Cover);
+        assert_eq!(svg.alignment(), Alignment::BOTTOM_LEFT);
+    }
+
+    #[test]
+    fn paths_are_in_view_box_coordinates() {
+        let svg: Svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100"
+            viewBox="0 0 10 10">
+                <g transform="translate(2 3)">
+                    <rect width="4" height="4" fill="red" />
+                </g>
+            </svg>"#
+            .parse()
+            .unwrap();
+
+        let [SvgNode::Path { path, .. }] = svg.nodes() else {
+            panic!("expected a single path");
+        };
+
+        let bounds = lyon::algorithms::aabb::bounding_box(path.iter());
+
+        // The view box transform is undone, so allow for some rounding error
+        assert!((bounds.min - point(2.0, 3.0)).length() < 1e-4);
+        assert!((bounds.max - point(6.0, 7.0)).length() < 1e-4);
+    }
+
+    #[test]
+    fn group_opacity_creates_layer() {
+        let svg: Svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10">
+                <g opacity="0.5">
+                    <rect width="4" height="4" fill="red" stroke="blue" />
+                </g>
+            </svg>"#
+            .parse()
+            .unwrap();
+
+        let [SvgNode::Layer {
+            opacity, children, ..
+        }] = svg.nodes()
+        else {
+            panic!("expected a single layer");
+        };
+
+        assert_eq!(*opacity, 0.5);
+
+        // Both the fill and the stroke are drawn
+        assert_eq!(children.len(), 2);
+    }
+
+    #[test]
+    fn fits_view_box() {
+        let transform = view_box_transform(
+            Rect::new(0.0, 0.0, 10.0, 20.0),
+            Size::new(100.0, 100.0),
+            BoxFit::Contain,
+            Alignment::CENTER,
+        );
+
+        let origin = transform.transform_point(point(0.0, 0.0));
+        let corner = transform.transform_point(point(10.0, 20.0));
+
+        assert_eq!(Offset::new(origin.x, origin.y), Offset::new(25.0, 0.0));
+        assert_eq!(Offset::new(corner.x, corner.y), Offset::new(75.0, 100.0));
+    }
+}
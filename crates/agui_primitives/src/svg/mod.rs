@@ -0,0 +1,134 @@
+use agui_core::{
+    asset::AssetResolver,
+    task::{context::ContextSpawnElementTask, spawn_blocking, TaskHandle},
+    unit::{Alignment, Texture, TextureData},
+    widget::Widget,
+};
+use agui_elements::stateful::{
+    ContextWidgetStateMut, StatefulBuildContext, StatefulWidget, WidgetState,
+};
+use agui_macros::StatefulWidget;
+
+use crate::image::BoxFit;
+
+mod document;
+mod raw_svg;
+
+pub(crate) use document::SvgNode;
+pub use document::{Svg, SvgError};
+pub use raw_svg::*;
+
+/// Displays an SVG document.
+///
+/// The document is scaled to fit its layout bounds, according to its `preserveAspectRatio`
+/// unless a `fit` or `alignment` is given. Documents that refer to a URL are loaded
+/// through an [`AssetResolver`], then parsed on a background thread. Nothing is drawn
+/// until the document has finished loading.
+#[derive(StatefulWidget, Debug)]
+pub struct SvgImage {
+    /// The encoded SVG document, or the URL to load it from.
+    pub source: Texture,
+
+    #[prop(default)]
+    pub fit: Option<BoxFit>,
+    #[prop(default)]
+    pub alignment: Option<Alignment>,
+
+    /// The resolver used to load documents that refer to a URL. If `None`, the global
+    /// resolver is used.
+    #[prop(default)]
+    pub assets: Option<AssetResolver>,
+}
+
+impl StatefulWidget for SvgImage {
+    type State = SvgImageState;
+
+    fn create_state(&self) -> Self::State {
+        SvgImageState {
+            svg: None,
+            load_task: None,
+        }
+    }
+}
+
+pub struct SvgImageState {
+    svg: Option<Svg>,
+    load_task: Option<TaskHandle<()>>,
+}
+
+impl SvgImageState {
+    fn load(&mut self, ctx: &mut StatefulBuildContext<Self>) {
+        let source = ctx.widget.source.clone();
+
+        let assets = ctx
+            .widget
+            .assets
+            .clone()
+            .unwrap_or_else(|| AssetResolver::global().clone());
+
+        self.svg = None;
+
+        let on_loaded = ctx.callback(|ctx, (source, result): (Texture, Result<Svg, SvgError>)| {
+            // The widget may have been given a different source while loading
+            if ctx.widget.source != source {
+                return;
+            }
+
+            match result {
+                Ok(svg) => ctx.set_state(move |state| {
+                    state.svg = Some(svg);
+                    state.load_task = None;
+                }),
+
+                Err(err) => tracing::error!("failed to load svg: {}", err),
+            }
+        });
+
+        self.load_task = match ctx.spawn_task(move |_| async move {
+            let result = load_svg(&assets, &source).await;
+
+            on_loaded.call((source, result));
+        }) {
+            Ok(task) => Some(task),
+            Err(err) => {
+                tracing::warn!("failed to spawn svg load task: {:?}", err);
+                None
+            }
+        };
+    }
+}
+
+impl WidgetState for SvgImageState {
+    type Widget = SvgImage;
+
+    fn init_state(&mut self, ctx: &mut StatefulBuildContext<Self>) {
+        self.load(ctx);
+    }
+
+    fn updated(&mut self, ctx: &mut StatefulBuildContext<Self>, old_widget: &Self::Widget) {
+        if ctx.widget.source != old_widget.source || ctx.widget.assets != old_widget.assets {
+            self.load(ctx);
+        }
+    }
+
+    fn build(&mut self, ctx: &mut StatefulBuildContext<Self>) -> Widget {
+        RawSvg {
+            svg: self.svg.clone(),
+
+            fit: ctx.widget.fit,
+            alignment: ctx.widget.alignment,
+        }
+        .into()
+    }
+}
+
+async fn load_svg(assets: &AssetResolver, source: &Texture) -> Result<Svg, SvgError> {
+    let source = assets.resolve_texture(source).await?;
+
+    spawn_blocking(move || match source.data() {
+        TextureData::Bytes(bytes) => Svg::from_data(bytes),
+
+        _ => Err(SvgError::NotEncoded),
+    })
+    .await
+}
@@ -0,0 +1,272 @@
+use agui_core::{
+    element::{ContextDirtyRenderObject, RenderObjectCreateContext, RenderObjectUpdateContext},
+    render::{
+        canvas::{
+            paint::Paint,
+            painter::{CanvasPainter, CanvasPainterState, Head},
+        },
+        object::{RenderObjectImpl, RenderObjectIntrinsicSizeContext, RenderObjectLayoutContext},
+    },
+    unit::{Alignment, Axis, Color, Constraints, IntrinsicDimension, Shape, Size},
+    widget::Widget,
+};
+use agui_elements::render::RenderObjectWidget;
+use agui_macros::RenderObjectWidget;
+use lyon::{math::Transform, path::Path};
+
+use crate::{
+    image::BoxFit,
+    svg::{
+        document::{transform_paint, view_box_transform},
+        Svg, SvgNode,
+    },
+};
+
+/// Displays an SVG document that has already been parsed.
+///
+/// Generally, you'll want to use [`SvgImage`](crate::svg::SvgImage) instead, which handles
+/// loading and parsing.
+#[derive(RenderObjectWidget, Debug)]
+#[props(default)]
+pub struct RawSvg {
+    /// The document to draw. If `None`, nothing is drawn.
+    pub svg: Option<Svg>,
+
+    /// How to fit the document's view box into the layout bounds. If `None`, the
+    /// document's `preserveAspectRatio` is used.
+    pub fit: Option<BoxFit>,
+
+    /// How to align the document's view box within the layout bounds. If `None`, the
+    /// document's `preserveAspectRatio` is used.
+    pub alignment: Option<Alignment>,
+}
+
+impl RenderObjectWidget for RawSvg {
+    type RenderObject = RenderSvg;
+
+    fn children(&self) -> Vec<Widget> {
+        Vec::default()
+    }
+
+    fn create_render_object(&self, _: &mut RenderObjectCreateContext) -> Self::RenderObject {
+        RenderSvg {
+            svg: self.svg.clone(),
+
+            fit: self.fit,
+            alignment: self.alignment,
+        }
+    }
+
+    fn update_render_object(
+        &self,
+        ctx: &mut RenderObjectUpdateContext,
+        render_object: &mut Self::RenderObject,
+    ) {
+        render_object.update_svg(ctx, self.svg.clone());
+
+        render_object.update_fit(ctx, self.fit);
+        render_object.update_alignment(ctx, self.alignment);
+    }
+}
+
+pub struct RenderSvg {
+    pub svg: Option<Svg>,
+
+    pub fit: Option<BoxFit>,
+    pub alignment: Option<Alignment>,
+}
+
+impl RenderSvg {
+    fn update_svg(&mut self, ctx: &mut RenderObjectUpdateContext, svg: Option<Svg>) {
+        if self.svg == svg {
+            return;
+        }
+
+        let size_changed = self.svg.as_ref().map(Svg::size) != svg.as_ref().map(Svg::size);
+
+        self.svg = svg;
+
+        if size_changed {
+            ctx.mark_needs_layout();
+        } else {
+            ctx.mark_needs_paint();
+        }
+    }
+
+    fn update_fit(&mut self, ctx: &mut RenderObjectUpdateContext, fit: Option<BoxFit>) {
+        if self.fit == fit {
+            return;
+        }
+
+        self.fit = fit;
+        ctx.mark_needs_paint();
+    }
+
+    fn update_alignment(
+        &mut self,
+        ctx: &mut RenderObjectUpdateContext,
+        alignment: Option<Alignment>,
+    ) {
+        if self.alignment == alignment {
+            return;
+        }
+
+        self.alignment = alignment;
+        ctx.mark_needs_paint();
+    }
+}
+
+impl RenderObjectImpl for RenderSvg {
+    fn intrinsic_size(
+        &self,
+        _: &mut RenderObjectIntrinsicSizeContext,
+        dimension: IntrinsicDimension,
+        cross_extent: f32,
+    ) -> f32 {
+        let Some(svg) = &self.svg else {
+            return 0.0;
+        };
+
+        // Like images, documents keep their aspect ratio when constrained along the cross axis.
+        let constraints = match dimension.axis() {
+            Axis::Horizontal => Constraints::new(0.0, f32::INFINITY, 0.0, cross_extent),
+            Axis::Vertical => Constraints::new(0.0, cross_extent, 0.0, f32::INFINITY),
+        };
+
+        constraints
+            .constrain_preserve_aspect_ratio(svg.size())
+            .extent(dimension.axis())
+    }
+
+    fn layout(&self, _: &mut RenderObjectLayoutContext, constraints: Constraints) -> Size {
+        match &self.svg {
+            Some(svg) => constraints.constrain_preserve_aspect_ratio(svg.size()),
+            None => constraints.smallest(),
+        }
+    }
+
+    fn does_paint(&self) -> bool {
+        true
+    }
+
+    fn paint(&self, mut canvas: CanvasPainter) {
+        let Some(svg) = &self.svg else {
+            return;
+        };
+
+        let output = canvas.size();
+
+        let fit = self.fit.unwrap_or_else(|| svg.fit());
+        let alignment = self.alignment.unwrap_or_else(|| svg.alignment());
+
+        let transform = view_box_transform(svg.view_box(), output, fit, alignment);
+
+        // Anything outside of the view box is hidden, which only matters if the view box
+        // overflows the layout bounds.
+        let view_box = svg.view_box();
+
+        let overflows = [
+            (view_box.left, view_box.top),
+            (view_box.right(), view_box.bottom()),
+        ]
+        .into_iter()
+        .map(|point| transform.transform_point(point.into()))
+        .any(|point| {
+            point.x < 0.0 || point.y < 0.0 || point.x > output.width || point.y > output.height
+        });
+
+        if overflows {
+            let brush = canvas.add_paint(Paint::default());
+
+            canvas.layer(&brush, Shape::Rect, |mut canvas| {
+                paint_nodes(&mut canvas, svg.nodes(), &transform);
+            });
+        } else {
+            paint_nodes(&mut canvas, svg.nodes(), &transform);
+        }
+    }
+}
+
+fn paint_nodes<S>(canvas: &mut CanvasPainter<Head<S>>, nodes: &[SvgNode], transform: &Transform)
+where
+    S: CanvasPainterState,
+{
+    let is_path = |node: &SvgNode| matches!(node, SvgNode::Path { .. });
+
+    let first_layer = nodes
+        .iter()
+        .position(|node| !is_path(node))
+        .unwrap_or(nodes.len());
+
+    let (paths, rest) = nodes.split_at(first_layer);
+
+    for node in paths {
+        paint_node(canvas, node, transform);
+    }
+
+    // Once a layer has been started, nothing else may be drawn to the canvas, so each run
+    // of paths after it is drawn to a layer of its own.
+    for run in rest.chunk_by(|a, b| is_path(a) && is_path(b)) {
+        match run {
+            [node @ SvgNode::Layer { .. }] => paint_node(canvas, node, transform),
+
+            paths => {
+                let brush = canvas.add_paint(Paint::default());
+
+                canvas.nested_layer(&brush, Shape::Rect, |mut canvas| {
+                    for node in paths {
+                        paint_node(&mut canvas, node, transform);
+                    }
+                });
+            }
+        }
+    }
+}
+
+fn paint_node<S>(canvas: &mut CanvasPainter<Head<S>>, node: &SvgNode, transform: &Transform)
+where
+    S: CanvasPainterState,
+{
+    match node {
+        SvgNode::Path { path, paint } => draw_path(canvas, path, paint, transform),
+
+        SvgNode::Layer {
+            opacity,
+            blend_mode,
+            clip,
+            children,
+        } => {
+            let brush = canvas.add_paint(Paint {
+                color: Color {
+                    alpha: *opacity,
+                    ..Color::default()
+                },
+                blend_mode: *blend_mode,
+
+                ..Paint::default()
+            });
+
+            let shape = match clip {
+                Some(clip) => Shape::Path(clip.clone().transformed(transform)),
+                None => Shape::Rect,
+            };
+
+            canvas.nested_layer(&brush, shape, |mut canvas| {
+                paint_nodes(&mut canvas, children, transform);
+            });
+        }
+    }
+}
+
+fn draw_path<S>(
+    canvas: &mut CanvasPainter<Head<S>>,
+    path: &Path,
+    paint: &Paint,
+    transform: &Transform,
+) where
+    S: CanvasPainterState,
+{
+    let brush = canvas.add_paint(transform_paint(paint, transform));
+
+    canvas.draw_path(&brush, path.clone().transformed(transform));
+}
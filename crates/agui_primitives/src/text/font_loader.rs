@@ -0,0 +1,159 @@
+use agui_core::{
+    asset::AssetResolver,
+    task::{context::ContextSpawnElementTask, TaskHandle},
+    unit::{Font, FontData, TextStyle},
+    widget::Widget,
+};
+use agui_elements::stateful::{
+    ContextWidgetStateMut, StatefulBuildContext, StatefulWidget, WidgetState,
+};
+use agui_macros::StatefulWidget;
+use rustc_hash::FxHashMap;
+
+/// Loads fonts that refer to a URL through an [`AssetResolver`], so they're ready before
+/// the text that uses them is laid out.
+///
+/// Text is laid out without waiting on fonts, so the child is built with [`LoadedFonts`]
+/// to swap each font that has finished loading for one that holds its data. Until a font
+/// has loaded, text using it is laid out empty.
+#[derive(StatefulWidget)]
+pub struct FontLoader {
+    pub fonts: Vec<Font>,
+
+    /// The resolver used to load the fonts. If `None`, the global resolver is used.
+    #[prop(default)]
+    pub assets: Option<AssetResolver>,
+
+    #[allow(clippy::type_complexity)]
+    pub builder: Box<dyn Fn(&LoadedFonts) -> Widget>,
+}
+
+impl FontLoader {
+    pub fn new<F>(fonts: Vec<Font>, builder: F) -> Self
+    where
+        F: Fn(&LoadedFonts) -> Widget + 'static,
+    {
+        Self {
+            fonts,
+
+            assets: None,
+
+            builder: Box::new(builder),
+        }
+    }
+}
+
+impl StatefulWidget for FontLoader {
+    type State = FontLoaderState;
+
+    fn create_state(&self) -> Self::State {
+        FontLoaderState {
+            loaded: LoadedFonts::default(),
+            load_task: None,
+        }
+    }
+}
+
+/// The fonts a [`FontLoader`] has finished loading.
+#[derive(Debug, Default, Clone)]
+pub struct LoadedFonts {
+    fonts: FxHashMap<Font, Font>,
+}
+
+impl LoadedFonts {
+    /// Returns the loaded font that holds the data of the given font, or the font itself
+    /// if it doesn't need to be loaded or hasn't loaded yet.
+    pub fn get(&self, font: &Font) -> Font {
+        self.fonts.get(font).unwrap_or(font).clone()
+    }
+
+    /// Returns the style with its font swapped for the loaded font.
+    pub fn style(&self, style: &TextStyle) -> TextStyle {
+        style.clone().font(self.get(&style.font))
+    }
+}
+
+pub struct FontLoaderState {
+    loaded: LoadedFonts,
+    load_task: Option<TaskHandle<()>>,
+}
+
+impl FontLoaderState {
+    fn load(&mut self, ctx: &mut StatefulBuildContext<Self>) {
+        let assets = ctx
+            .widget
+            .assets
+            .clone()
+            .unwrap_or_else(|| AssetResolver::global().clone());
+
+        // Dropping the previous task cancels it, if it hasn't finished yet
+        self.load_task = None;
+
+        self.loaded
+            .fonts
+            .retain(|font, _| ctx.widget.fonts.contains(font));
+
+        let mut fonts = ctx
+            .widget
+            .fonts
+            .iter()
+            .filter(|font| matches!(font.as_ref(), FontData::Url(_)))
+            .filter(|font| !self.loaded.fonts.contains_key(font))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        fonts.sort();
+        fonts.dedup();
+
+        if fonts.is_empty() {
+            return;
+        }
+
+        let on_loaded = ctx.callback(|ctx, loaded: Vec<(Font, Font)>| {
+            ctx.set_state(move |state| {
+                state.loaded.fonts.extend(loaded);
+                state.load_task = None;
+            });
+        });
+
+        self.load_task = match ctx.spawn_task(move |_| async move {
+            let mut loaded = Vec::with_capacity(fonts.len());
+
+            for font in fonts {
+                match assets.resolve_font(&font).await {
+                    Ok(resolved) => loaded.push((font, resolved)),
+
+                    // Failures aren't remembered, so the font is tried again when the
+                    // widget is next given it
+                    Err(err) => tracing::error!("failed to load font {:?}: {}", font, err),
+                }
+            }
+
+            on_loaded.call(loaded);
+        }) {
+            Ok(task) => Some(task),
+            Err(err) => {
+                tracing::warn!("failed to spawn font load task: {:?}", err);
+                None
+            }
+        };
+    }
+}
+
+impl WidgetState for FontLoaderState {
+    type Widget = FontLoader;
+
+    fn init_state(&mut self, ctx: &mut StatefulBuildContext<Self>) {
+        self.load(ctx);
+    }
+
+    fn updated(&mut self, ctx: &mut StatefulBuildContext<Self>, old_widget: &Self::Widget) {
+        if ctx.widget.fonts != old_widget.fonts || ctx.widget.assets != old_widget.assets {
+            self.load(ctx);
+        }
+    }
+
+    fn build(&mut self, ctx: &mut StatefulBuildContext<Self>) -> Widget {
+        (ctx.widget.builder)(&self.loaded)
+    }
+}
@@ -1,58 +1,62 @@
 use std::borrow::Cow;
 
 use agui_core::{
-    element::{RenderObjectCreateContext, RenderObjectUpdateContext},
     unit::TextStyle,
-    widget::Widget,
+    widget::{IntoWidget, Widget},
 };
-use agui_elements::render::RenderObjectWidget;
-use agui_macros::RenderObjectWidget;
+use agui_elements::stateless::{StatelessBuildContext, StatelessWidget};
+use agui_macros::StatelessWidget;
+
+use crate::directionality::Directionality;
 
 pub mod edit;
+mod font_loader;
 pub mod fonts;
 pub mod query;
+mod raw_paragraph;
 mod render_paragraph;
+mod rich_text;
+mod selectable_text;
+mod span;
 
+pub use font_loader::*;
+pub use raw_paragraph::*;
 pub use render_paragraph::*;
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub enum TextBaseline {
-    /// The horizontal line used to align the bottom of glyphs for alphabetic characters.
-    Alphabetic,
-
-    /// The horizontal line used to align ideographic characters.
-    Ideographic,
-}
-
-#[derive(RenderObjectWidget, Debug)]
+pub use rich_text::*;
+pub use selectable_text::*;
+pub use span::*;
+
+/// Displays a paragraph of text in a single style. If the style doesn't set a text
+/// direction, the ambient [`Directionality`] is used. A font that refers to a URL is
+/// loaded with a [`FontLoader`], and the text is shown once it has loaded.
+#[derive(StatelessWidget, Debug)]
 pub struct Text {
     pub style: TextStyle,
 
     pub text: Cow<'static, str>,
 }
 
-impl RenderObjectWidget for Text {
-    type RenderObject = RenderParagraph;
+impl StatelessWidget for Text {
+    fn build(&self, ctx: &mut StatelessBuildContext<Self>) -> Widget {
+        let mut style = self.style.clone();
 
-    fn children(&self) -> Vec<Widget> {
-        Vec::default()
-    }
+        style.text_direction = style.text_direction.or_else(|| Directionality::of(ctx));
 
-    fn create_render_object(&self, _: &mut RenderObjectCreateContext) -> Self::RenderObject {
-        RenderParagraph {
-            style: self.style.clone(),
+        let text = Cow::clone(&self.text);
 
-            text: Cow::clone(&self.text),
-        }
-    }
+        FontLoader::new(vec![style.font.clone()], move |fonts| {
+            RawParagraph {
+                style: fonts.style(&style),
+
+                text: Cow::clone(&text),
 
-    fn update_render_object(
-        &self,
-        ctx: &mut RenderObjectUpdateContext,
-        render_object: &mut Self::RenderObject,
-    ) {
-        render_object.update_style(ctx, self.style.clone());
+                styles: Vec::new(),
+                placeholders: Vec::new(),
 
-        render_object.update_text(ctx, Cow::clone(&self.text));
+                children: Vec::new(),
+            }
+            .into_widget()
+        })
+        .into_widget()
     }
 }
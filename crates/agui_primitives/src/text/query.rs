@@ -7,7 +7,7 @@ use agui_core::{
     query::by_widget::ExactWidgetIterator,
 };
 
-use crate::text::Text;
+use crate::text::{RichText, Text};
 
 pub trait FilterTextExt {
     fn with_text(self, text: &str) -> FilterText<Self>
@@ -16,6 +16,15 @@ pub trait FilterTextExt {
     {
         FilterText::new(text, self)
     }
+
+    /// Filters for [`RichText`] widgets whose spans, concatenated together, have the given
+    /// text. Inline widgets are matched by the object replacement character (`U+FFFC`).
+    fn with_rich_text(self, text: &str) -> FilterRichText<Self>
+    where
+        Self: Sized,
+    {
+        FilterRichText::new(text, self)
+    }
 }
 
 impl<I> FilterTextExt for I where I: ElementTreeIterator {}
@@ -62,18 +71,62 @@ impl<I> ExactWidgetIterator for FilterText<'_, I> {
     type Widget = Text;
 }
 
+#[derive(Clone)]
+pub struct FilterRichText<'query, I> {
+    text: &'query str,
+
+    inner: I,
+}
+
+impl<'query, I> FilterRichText<'query, I> {
+    pub fn new(text: &'query str, inner: I) -> Self {
+        Self { text, inner }
+    }
+}
+
+impl<'query, I> Iterator for FilterRichText<'query, I>
+where
+    I: ElementTreeIterator<Item = ElementEntry<'query>>,
+{
+    type Item = ElementEntry<'query>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.find(|node| {
+            node.element()
+                .downcast::<<RichText as ElementBuilder>::Element>()
+                .filter(|element| element.widget().text.to_plain_text() == self.text)
+                .is_some()
+        })
+    }
+}
+
+impl<'query, I> ElementTreeIterator for FilterRichText<'query, I>
+where
+    I: ElementTreeIterator<Item = ElementEntry<'query>>,
+{
+    fn tree(&self) -> &ElementTree {
+        self.inner.tree()
+    }
+}
+
+impl<I> ExactWidgetIterator for FilterRichText<'_, I> {
+    type Widget = RichText;
+}
+
 #[cfg(test)]
 mod tests {
     use agui_core::{
         engine::elements::{strategies::mocks::MockInflateElements, ElementTree},
         query::by_widget::ExactWidgetIterator,
         unit::{Color, Font, HorizontalAlign, TextStyle, VerticalAlign},
+        widget::Widget,
     };
     use agui_macros::build;
 
     use crate::{
         flex::Column,
-        text::{query::FilterTextExt, Text},
+        sized_box::SizedBox,
+        text::{query::FilterTextExt, RichText, Text, TextSpan, TextSpanStyle},
     };
 
     #[test]
@@ -94,6 +147,8 @@ mod tests {
 
                                 h_align: HorizontalAlign::default(),
                                 v_align: VerticalAlign::default(),
+
+                                ..TextStyle::default()
                             },
 
                             text: "foo".into(),
@@ -107,6 +162,8 @@ mod tests {
 
                                 h_align: HorizontalAlign::default(),
                                 v_align: VerticalAlign::default(),
+
+                                ..TextStyle::default()
                             },
 
                             text: "bar".into(),
@@ -139,4 +196,55 @@ mod tests {
             "should have found the \"bar\" text widget"
         );
     }
+
+    #[test]
+    pub fn finds_rich_text_by_its_concatenated_text() {
+        let mut tree = ElementTree::new();
+
+        tree.inflate(
+            &mut MockInflateElements::default(),
+            build! {
+                <RichText> {
+                    style: TextStyle {
+                        font: Font::from_family("Arial"),
+
+                        size: 16.0,
+                        color: Color::from_rgb((0.0, 0.0, 0.0)),
+
+                        h_align: HorizontalAlign::default(),
+                        v_align: VerticalAlign::default(),
+
+                        ..TextStyle::default()
+                    },
+
+                    text: TextSpan::new("hello ")
+                        .child(
+                            TextSpan::new("bold")
+                                .style(TextSpanStyle::default().size(20.0))
+                        )
+                        .child(Widget::from(SizedBox::new(8.0, 8.0)))
+                        .child(TextSpan::new(" world")),
+                }
+            },
+        )
+        .expect("failed to inflate widget");
+
+        assert!(
+            tree.iter().with_rich_text("hello bold").next().is_none(),
+            "should not match part of the text"
+        );
+
+        assert_eq!(
+            tree.iter()
+                .with_rich_text("hello bold\u{FFFC} world")
+                .and_downcast()
+                .next()
+                .expect("should have found a widget")
+                .text
+                .children
+                .len(),
+            3,
+            "should have found the rich text widget"
+        );
+    }
 }
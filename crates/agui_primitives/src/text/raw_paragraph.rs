@@ -0,0 +1,62 @@
+use std::borrow::Cow;
+
+use agui_core::{
+    element::{RenderObjectCreateContext, RenderObjectUpdateContext},
+    unit::TextStyle,
+    widget::Widget,
+};
+use agui_elements::render::RenderObjectWidget;
+use agui_macros::RenderObjectWidget;
+
+use crate::text::{RenderParagraph, StyledRange};
+
+/// Displays a paragraph of text whose spans have already been flattened and whose
+/// direction has already been resolved.
+///
+/// Generally, you'll want to use [`Text`](crate::text::Text) or
+/// [`RichText`](crate::text::RichText) instead.
+#[derive(RenderObjectWidget, Debug)]
+pub struct RawParagraph {
+    pub style: TextStyle,
+
+    pub text: Cow<'static, str>,
+
+    #[prop(default)]
+    pub styles: Vec<StyledRange>,
+
+    /// The byte offsets of the inline widgets within the text, in the same order as
+    /// `children`.
+    #[prop(default)]
+    pub placeholders: Vec<usize>,
+
+    #[prop(default)]
+    pub children: Vec<Widget>,
+}
+
+impl RenderObjectWidget for RawParagraph {
+    type RenderObject = RenderParagraph;
+
+    fn children(&self) -> Vec<Widget> {
+        self.children.clone()
+    }
+
+    fn create_render_object(&self, _: &mut RenderObjectCreateContext) -> Self::RenderObject {
+        RenderParagraph::new(self.style.clone(), Cow::clone(&self.text))
+            .with_styles(self.styles.clone())
+            .with_placeholders(self.placeholders.clone())
+    }
+
+    fn update_render_object(
+        &self,
+        ctx: &mut RenderObjectUpdateContext,
+        render_object: &mut Self::RenderObject,
+    ) {
+        render_object.update_style(ctx, self.style.clone());
+
+        render_object.update_text(ctx, Cow::clone(&self.text));
+
+        render_object.update_styles(ctx, self.styles.clone());
+
+        render_object.update_placeholders(ctx, self.placeholders.clone());
+    }
+}
@@ -1,27 +1,128 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, ops::Range};
 
 use agui_core::{
+    callback::Callback,
     element::{ContextDirtyRenderObject, RenderObjectUpdateContext},
+    input::{EventResult, InputEvent, PointerButton, PointerEventKind, PointerId},
     render::{
-        canvas::{paint::Paint, painter::CanvasPainter},
-        object::{RenderObjectImpl, RenderObjectIntrinsicSizeContext, RenderObjectLayoutContext},
+        canvas::{
+            paint::{Brush, Paint},
+            painter::{CanvasPainter, CanvasPainterState, Head},
+        },
+        object::{
+            RenderObjectBaselineContext, RenderObjectEventContext, RenderObjectHitTestContext,
+            RenderObjectImpl, RenderObjectIntrinsicSizeContext, RenderObjectLayoutContext,
+        },
+    },
+    unit::{
+        BlendMode, Color, Constraints, GlyphRun, Gradient, GradientStop, HitTest,
+        IntrinsicDimension, LineMetrics, Offset, PaintingStyle, Paragraph, ParagraphSpan, Rect,
+        Shape, Size, StrokeStyle, TextBaseline, TextDecorationStyle, TextLine, TextOverflow,
+        TextPosition, TextSelection, TextStyle,
     },
-    unit::{Constraints, IntrinsicDimension, Size, TextStyle},
 };
+use parking_lot::Mutex;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// How far below the baseline underlines are drawn, relative to the font size.
+const UNDERLINE_OFFSET: f32 = 0.1;
+
+/// How far above the baseline overlines are drawn, relative to the font size.
+const OVERLINE_OFFSET: f32 = 0.9;
+
+/// How far above the baseline strikethroughs are drawn, relative to the font size.
+const LINE_THROUGH_OFFSET: f32 = 0.3;
+
+/// How far from the edge of the paragraph faded text starts to fade, relative to the
+/// font size.
+const FADE_EXTENT: f32 = 2.0;
+
+/// Overflow is ignored if it's smaller than this, so rounding doesn't cause clipping.
+const OVERFLOW_TOLERANCE: f32 = 1e-3;
+
+/// The color painted behind selected text, unless another is given.
+pub const DEFAULT_SELECTION_COLOR: Color = Color::from_rgba((0.2, 0.45, 0.95, 0.35));
+
+/// A range of a paragraph's text that's styled differently to the rest of it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StyledRange {
+    pub range: Range<usize>,
+
+    pub style: TextStyle,
+
+    pub background: Option<Color>,
+
+    pub on_tap: Option<Callback<()>>,
+}
 
 pub struct RenderParagraph {
     pub style: TextStyle,
 
     pub text: Cow<'static, str>,
+
+    /// The ranges of the text that are styled differently to the paragraph. They're
+    /// sorted, and don't overlap.
+    pub styles: Vec<StyledRange>,
+
+    /// The byte offsets of the characters that are replaced by the children of the
+    /// paragraph, in the order of the children.
+    pub placeholders: Vec<usize>,
+
+    /// The selected text, which is highlighted with the selection color.
+    pub selection: Option<TextSelection>,
+    pub selection_color: Color,
+
+    /// The paragraph produced by the last layout, and what it was laid out with. It's
+    /// reused until the text, styles, constraints, or size of a child change.
+    paragraph: Mutex<Option<LaidOutParagraph>>,
+
+    /// The pointer that went down on a tappable range, and the index of that range.
+    pressed: Mutex<Option<(PointerId, usize)>>,
+}
+
+struct LaidOutParagraph {
+    constraints: Constraints,
+    placeholder_sizes: Vec<Size>,
+
+    paragraph: Paragraph,
 }
 
 impl RenderParagraph {
+    pub fn new(style: TextStyle, text: Cow<'static, str>) -> Self {
+        Self {
+            style,
+
+            text,
+
+            styles: Vec::new(),
+            placeholders: Vec::new(),
+
+            selection: None,
+            selection_color: DEFAULT_SELECTION_COLOR,
+
+            paragraph: Mutex::default(),
+
+            pressed: Mutex::default(),
+        }
+    }
+
+    pub fn with_styles(mut self, styles: Vec<StyledRange>) -> Self {
+        self.styles = styles;
+        self
+    }
+
+    pub fn with_placeholders(mut self, placeholders: Vec<usize>) -> Self {
+        self.placeholders = placeholders;
+        self
+    }
+
     pub fn update_style(&mut self, ctx: &mut RenderObjectUpdateContext, style: TextStyle) {
         if self.style == style {
             return;
         }
 
         self.style = style;
+        self.paragraph.get_mut().take();
         ctx.mark_needs_layout();
         ctx.mark_needs_paint();
     }
@@ -32,9 +133,182 @@ impl RenderParagraph {
         }
 
         self.text = text;
+        self.paragraph.get_mut().take();
+        self.pressed.get_mut().take();
+        ctx.mark_needs_layout();
+        ctx.mark_needs_paint();
+    }
+
+    pub fn update_styles(&mut self, ctx: &mut RenderObjectUpdateContext, styles: Vec<StyledRange>) {
+        if self.styles == styles {
+            return;
+        }
+
+        self.styles = styles;
+        self.paragraph.get_mut().take();
+        self.pressed.get_mut().take();
+        ctx.mark_needs_layout();
+        ctx.mark_needs_paint();
+    }
+
+    pub fn update_placeholders(
+        &mut self,
+        ctx: &mut RenderObjectUpdateContext,
+        placeholders: Vec<usize>,
+    ) {
+        if self.placeholders == placeholders {
+            return;
+        }
+
+        self.placeholders = placeholders;
+        self.paragraph.get_mut().take();
         ctx.mark_needs_layout();
         ctx.mark_needs_paint();
     }
+
+    pub fn update_selection(
+        &mut self,
+        ctx: &mut RenderObjectUpdateContext,
+        selection: Option<TextSelection>,
+    ) {
+        if self.selection == selection {
+            return;
+        }
+
+        self.selection = selection;
+        ctx.mark_needs_paint();
+    }
+
+    pub fn update_selection_color(&mut self, ctx: &mut RenderObjectUpdateContext, color: Color) {
+        if self.selection_color == color {
+            return;
+        }
+
+        self.selection_color = color;
+        ctx.mark_needs_paint();
+    }
+
+    /// Returns the position in the text closest to `offset`, which is relative to the top
+    /// left of the paragraph.
+    ///
+    /// Like the other text queries, this uses the paragraph that the view's text layout
+    /// strategy produced during the last layout. If the strategy doesn't produce
+    /// paragraphs, the text is treated as if it had no lines.
+    pub fn position_for_offset(&self, offset: Offset) -> TextPosition {
+        self.with_paragraph(|paragraph| paragraph.position_for_offset(offset))
+    }
+
+    /// Returns where a caret at `position` is drawn, as a rect with no width that spans
+    /// the height of its line.
+    pub fn caret_rect(&self, position: TextPosition) -> Rect {
+        self.with_paragraph(|paragraph| paragraph.caret_rect(position))
+    }
+
+    /// Returns the rects that cover the text in `range`.
+    pub fn selection_rects(&self, range: Range<usize>) -> Vec<Rect> {
+        self.with_paragraph(|paragraph| paragraph.selection_rects(range))
+    }
+
+    pub fn line_metrics(&self) -> Vec<LineMetrics> {
+        self.with_paragraph(Paragraph::line_metrics)
+    }
+
+    /// Returns the range of the line that `position` is on, excluding any line break
+    /// that ends it.
+    pub fn line_boundary(&self, position: TextPosition) -> Range<usize> {
+        let Some(range) = self.with_paragraph(|paragraph| {
+            paragraph
+                .line_for_position(position)
+                .map(|idx| paragraph.lines[idx].range.clone())
+        }) else {
+            return position.offset..position.offset;
+        };
+
+        let content = self.text[range.clone()].trim_end_matches(is_line_terminator);
+
+        range.start..range.start + content.len()
+    }
+
+    /// Returns the range of the word at the byte `offset`, as given by the Unicode word
+    /// boundaries (UAX #29). Whitespace and punctuation between words count as words of
+    /// their own.
+    pub fn word_boundary(&self, offset: usize) -> Range<usize> {
+        let mut last = None;
+
+        for (start, word) in self.text.split_word_bound_indices() {
+            let range = start..start + word.len();
+
+            if offset < range.end {
+                return range;
+            }
+
+            last = Some(range);
+        }
+
+        last.unwrap_or(offset..offset)
+    }
+
+    fn with_paragraph<R>(&self, func: impl FnOnce(&Paragraph) -> R) -> R {
+        match self.paragraph.lock().as_ref() {
+            Some(laid_out) => func(&laid_out.paragraph),
+            None => func(&Paragraph::default()),
+        }
+    }
+
+    fn spans(&self, placeholder_sizes: &[Size]) -> Vec<ParagraphSpan> {
+        let mut spans = self
+            .styles
+            .iter()
+            .map(|styled| ParagraphSpan::Text {
+                range: styled.range.clone(),
+                style: styled.style.clone(),
+            })
+            .chain(
+                self.placeholders
+                    .iter()
+                    .zip(placeholder_sizes)
+                    .map(|(offset, size)| ParagraphSpan::Placeholder {
+                        offset: *offset,
+                        size: *size,
+                    }),
+            )
+            .collect::<Vec<_>>();
+
+        spans.sort_by_key(|span| span.range().start);
+
+        spans
+    }
+
+    /// Returns the styled range that contains the byte `offset`, if any.
+    fn styled_range_at(&self, offset: usize) -> Option<(usize, &StyledRange)> {
+        let idx = self
+            .styles
+            .partition_point(|styled| styled.range.end <= offset);
+
+        self.styles
+            .get(idx)
+            .filter(|styled| styled.range.contains(&offset))
+            .map(|styled| (idx, styled))
+    }
+
+    /// Returns the index of the tappable range at `position`, if any.
+    fn tap_target_at(&self, position: Offset) -> Option<usize> {
+        let laid_out = self.paragraph.lock();
+
+        let paragraph = &laid_out.as_ref()?.paragraph;
+
+        paragraph
+            .lines
+            .iter()
+            .filter(|line| position.y >= line.top && position.y < line.top + line.height)
+            .flat_map(|line| line.runs.iter().map(move |run| (line, run)))
+            .filter(|(line, run)| run_bounds(line, run).contains((position.x, position.y)))
+            .find_map(|(_, run)| {
+                self.styled_range_at(run.range.start)
+                    .filter(|(_, styled)| styled.on_tap.is_some())
+                    .map(|(idx, _)| idx)
+            })
+    }
 }
 
 impl RenderObjectImpl for RenderParagraph {
@@ -42,26 +316,211 @@ impl RenderObjectImpl for RenderParagraph {
         &self,
         ctx: &mut RenderObjectIntrinsicSizeContext,
         dimension: IntrinsicDimension,
-        _: f32,
+        cross_axis: f32,
     ) -> f32 {
-        ctx.text_layout().map_or(0.0, |text_layout| {
-            text_layout.compute_intrinsic_size(&self.style, &self.text, dimension, self.style.size)
-        })
+        // Inline widgets are measured at their natural size
+        let placeholder_sizes = ctx
+            .iter_children()
+            .map(|child| {
+                let width =
+                    child.compute_intrinsic_size(IntrinsicDimension::MaxWidth, f32::INFINITY);
+
+                Size::new(
+                    width,
+                    child.compute_intrinsic_size(IntrinsicDimension::MaxHeight, width),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let Some(text_layout) = ctx.text_layout() else {
+            return 0.0;
+        };
+
+        if self.styles.is_empty() && self.placeholders.is_empty() {
+            return text_layout.compute_intrinsic_size(
+                &self.style,
+                &self.text,
+                dimension,
+                cross_axis,
+            );
+        }
+
+        text_layout
+            .compute_paragraph_intrinsic_size(
+                &self.style,
+                &self.text,
+                &self.spans(&placeholder_sizes),
+                dimension,
+                cross_axis,
+            )
+            .unwrap_or_else(|| {
+                text_layout.compute_intrinsic_size(&self.style, &self.text, dimension, cross_axis)
+            })
     }
 
     fn layout(&self, ctx: &mut RenderObjectLayoutContext, constraints: Constraints) -> Size {
-        assert_eq!(
-            ctx.child_count(),
-            0,
-            "RenderParagraph should have no children"
-        );
-
-        if let Some(text_layout) = ctx.text_layout() {
-            text_layout.compute_size(&self.style, &self.text, constraints)
-        } else {
+        // Inline widgets are laid out first, so that room can be made for them in the text
+        let mut placeholder_sizes = Vec::with_capacity(ctx.child_count());
+
+        let mut children = ctx.iter_children_mut();
+
+        while let Some(mut child) = children.next() {
+            placeholder_sizes.push(child.compute_layout(Constraints::loose(Size::new(
+                constraints.max_width(),
+                f32::INFINITY,
+            ))));
+        }
+
+        let mut laid_out = self.paragraph.lock();
+
+        if let Some(laid_out) = laid_out.as_ref().filter(|laid_out| {
+            laid_out.constraints == constraints && laid_out.placeholder_sizes == placeholder_sizes
+        }) {
+            return laid_out.paragraph.size;
+        }
+
+        let Some(text_layout) = ctx.text_layout() else {
             tracing::warn!("No text layout delegate available");
 
-            constraints.smallest()
+            laid_out.take();
+
+            return constraints.smallest();
+        };
+
+        let spans = self.spans(&placeholder_sizes);
+
+        let Some(paragraph) =
+            text_layout.layout_paragraph(&self.style, &self.text, &spans, constraints)
+        else {
+            laid_out.take();
+
+            return text_layout.compute_size(&self.style, &self.text, constraints);
+        };
+
+        let mut children = ctx.iter_children_mut();
+
+        while let Some(mut child) = children.next() {
+            let rect = paragraph
+                .placeholders
+                .get(child.index())
+                .copied()
+                .unwrap_or_default();
+
+            child.set_offset(Offset::new(rect.left, rect.top));
+        }
+
+        let size = paragraph.size;
+
+        laid_out.replace(LaidOutParagraph {
+            constraints,
+            placeholder_sizes,
+
+            paragraph,
+        });
+
+        size
+    }
+
+    /// The baseline of the paragraph is that of its first line, which is as far below the
+    /// top of the line as the ascent of the line's tallest font.
+    fn compute_distance_to_baseline(
+        &self,
+        _: &mut RenderObjectBaselineContext,
+        baseline: TextBaseline,
+    ) -> Option<f32> {
+        let laid_out = self.paragraph.lock();
+
+        let line = laid_out
+            .as_ref()?
+            .paragraph
+            .line_metrics()
+            .into_iter()
+            .next()?;
+
+        Some(match baseline {
+            TextBaseline::Alphabetic => line.baseline,
+
+            // Ideographic characters sit on the bottom of the em box, which is approximated
+            // by the line's descent
+            TextBaseline::Ideographic => line.baseline + line.descent,
+        })
+    }
+
+    fn hit_test<'ctx>(
+        &self,
+        ctx: &'ctx mut RenderObjectHitTestContext<'ctx>,
+        position: Offset,
+    ) -> HitTest {
+        if !ctx.size().contains(position) {
+            return HitTest::Pass;
+        }
+
+        let mut children = ctx.iter_children();
+
+        while let Some(mut child) = children.next_back() {
+            let offset = child.offset();
+
+            if child.hit_test_with_offset(offset, position) == HitTest::Absorb {
+                return HitTest::Absorb;
+            }
+        }
+
+        if self.tap_target_at(position).is_some() {
+            HitTest::Absorb
+        } else {
+            HitTest::Pass
+        }
+    }
+
+    fn handle_event(&self, _: &mut RenderObjectEventContext, event: &InputEvent) -> EventResult {
+        let InputEvent::Pointer(event) = event else {
+            return EventResult::Ignored;
+        };
+
+        match event.kind {
+            PointerEventKind::Down if event.button == PointerButton::Primary => {
+                let Some(idx) = self.tap_target_at(event.local_position) else {
+                    return EventResult::Ignored;
+                };
+
+                self.pressed.lock().replace((event.pointer, idx));
+
+                EventResult::Handled
+            }
+
+            PointerEventKind::Up => {
+                let mut pressed = self.pressed.lock();
+
+                let Some((_, idx)) = pressed.filter(|(pointer, _)| *pointer == event.pointer)
+                else {
+                    return EventResult::Ignored;
+                };
+
+                pressed.take();
+
+                drop(pressed);
+
+                // The tap only counts if the pointer was released over the same range
+                if self.tap_target_at(event.local_position) == Some(idx) {
+                    if let Some(on_tap) = &self.styles[idx].on_tap {
+                        on_tap.call(());
+                    }
+                }
+
+                EventResult::Handled
+            }
+
+            PointerEventKind::Cancel => {
+                let mut pressed = self.pressed.lock();
+
+                if pressed.is_some_and(|(pointer, _)| pointer == event.pointer) {
+                    pressed.take();
+                }
+
+                EventResult::Ignored
+            }
+
+            _ => EventResult::Ignored,
         }
     }
 
@@ -70,12 +529,335 @@ impl RenderObjectImpl for RenderParagraph {
     }
 
     fn paint(&self, mut canvas: CanvasPainter) {
-        let brush = canvas.add_paint(Paint {
-            color: self.style.color,
+        self.paint_to(&mut canvas);
+    }
+}
+
+impl RenderParagraph {
+    /// Paints the paragraph with a painter in any state, so that it can be painted into
+    /// the layers of other render objects.
+    pub fn paint_to<S>(&self, canvas: &mut CanvasPainter<Head<S>>)
+    where
+        S: CanvasPainterState,
+    {
+        let laid_out = self.paragraph.lock();
 
-            ..Paint::default()
+        // Paint the glyphs that were shaped during layout, if the view produced any
+        let Some(laid_out) = laid_out.as_ref() else {
+            let brush = canvas.add_paint(Paint {
+                color: self.style.color,
+
+                ..Paint::default()
+            });
+
+            canvas.draw_text(&brush, self.style.clone(), Cow::clone(&self.text));
+
+            return;
+        };
+
+        let paragraph = &laid_out.paragraph;
+        let size = paragraph.size;
+
+        let overflows_horizontally = paragraph.lines.iter().any(|line| {
+            line.left < -OVERFLOW_TOLERANCE
+                || line.left + line.width > size.width + OVERFLOW_TOLERANCE
         });
 
-        canvas.draw_text(&brush, self.style.clone(), Cow::clone(&self.text));
+        let overflows_vertically = paragraph.content_height() > size.height + OVERFLOW_TOLERANCE;
+
+        let fade = (self.style.overflow == TextOverflow::Fade).then(|| {
+            // Text that's limited to a single line fades out at the end of the line,
+            // otherwise it fades out over the last line
+            if self.style.max_lines == Some(1) || !self.style.soft_wrap {
+                overflows_horizontally.then(|| {
+                    let extent = (self.style.size * FADE_EXTENT).min(size.width);
+
+                    (
+                        Offset::new(size.width - extent, 0.0),
+                        Offset::new(size.width, 0.0),
+                    )
+                })
+            } else if overflows_vertically || paragraph.did_exceed_max_lines {
+                paragraph
+                    .lines
+                    .iter()
+                    .rev()
+                    .find(|line| line.top < size.height)
+                    .map(|line| {
+                        (
+                            Offset::new(0.0, line.top),
+                            Offset::new(0.0, (line.top + line.height).min(size.height)),
+                        )
+                    })
+            } else {
+                None
+            }
+        });
+
+        let fade = fade.flatten();
+
+        if !overflows_horizontally && !overflows_vertically && fade.is_none() {
+            self.paint_paragraph(canvas, paragraph);
+
+            return;
+        }
+
+        // Text that doesn't fit is clipped to the bounds of the paragraph
+        let brush = canvas.add_paint(Paint::default());
+
+        canvas.nested_layer(&brush, Shape::Rect, |mut canvas| {
+            self.paint_paragraph(&mut canvas, paragraph);
+
+            if let Some((start, end)) = fade {
+                let brush = canvas.add_paint(Paint {
+                    gradient: Some(Gradient::linear(
+                        start,
+                        end,
+                        [
+                            GradientStop::new(0.0, Color::default()),
+                            GradientStop::new(
+                                1.0,
+                                Color {
+                                    alpha: 0.0,
+                                    ..Color::default()
+                                },
+                            ),
+                        ],
+                    )),
+                    blend_mode: BlendMode::DestinationIn,
+
+                    ..Paint::default()
+                });
+
+                canvas.draw_rect(&brush);
+            }
+        });
     }
+
+    fn paint_paragraph<S>(&self, canvas: &mut CanvasPainter<Head<S>>, paragraph: &Paragraph)
+    where
+        S: CanvasPainterState,
+    {
+        let mut brushes = Vec::<(Paint, Brush<Head<S>>)>::new();
+
+        let runs = paragraph
+            .lines
+            .iter()
+            .flat_map(|line| line.runs.iter().map(move |run| (line, run)))
+            .map(|(line, run)| {
+                let styled = self.styled_range_at(run.range.start);
+
+                (line, run, styled.map(|(_, styled)| styled))
+            })
+            .collect::<Vec<_>>();
+
+        for (line, run, styled) in &runs {
+            if let Some(background) = styled.and_then(|styled| styled.background) {
+                let brush = brush_for(canvas, &mut brushes, fill(background));
+
+                canvas.draw_rect_at(run_bounds(line, run), brush);
+            }
+        }
+
+        if let Some(selection) = self.selection.filter(|selection| !selection.is_collapsed()) {
+            let brush = brush_for(canvas, &mut brushes, fill(self.selection_color));
+
+            for rect in paragraph.selection_rects(selection.range()) {
+                canvas.draw_rect_at(rect, brush);
+            }
+        }
+
+        // Consecutive runs of the same color are drawn together
+        let mut pending = Vec::<GlyphRun>::new();
+        let mut pending_color = self.style.color;
+
+        for (_, run, styled) in &runs {
+            let color = styled.map_or(self.style.color, |styled| styled.style.color);
+
+            if color != pending_color && !pending.is_empty() {
+                let brush = brush_for(canvas, &mut brushes, fill(pending_color));
+
+                canvas.draw_glyphs(brush, std::mem::take(&mut pending));
+            }
+
+            pending_color = color;
+            pending.push((*run).clone());
+        }
+
+        if !pending.is_empty() {
+            let brush = brush_for(canvas, &mut brushes, fill(pending_color));
+
+            canvas.draw_glyphs(brush, pending);
+        }
+
+        for (line, run, styled) in &runs {
+            let style = styled.map_or(&self.style, |styled| &styled.style);
+
+            if style.decoration.is_none() {
+                continue;
+            }
+
+            let color = style.decoration_color.unwrap_or(style.color);
+
+            let bounds = run_bounds(line, run);
+            let thickness = (run.font_size / 16.0).max(1.0);
+
+            for (enabled, offset) in [
+                (style.decoration.underline, UNDERLINE_OFFSET),
+                (style.decoration.overline, -OVERLINE_OFFSET),
+                (style.decoration.line_through, -LINE_THROUGH_OFFSET),
+            ] {
+                if enabled {
+                    paint_decoration(
+                        canvas,
+                        &mut brushes,
+                        style.decoration_style,
+                        color,
+                        Rect::new(
+                            bounds.left,
+                            line.baseline + offset * run.font_size - thickness / 2.0,
+                            bounds.width,
+                            thickness,
+                        ),
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Paints a decoration line that would fill `rect` if it were solid.
+fn paint_decoration<S>(
+    canvas: &mut CanvasPainter<Head<S>>,
+    brushes: &mut Vec<(Paint, Brush<Head<S>>)>,
+    style: TextDecorationStyle,
+    color: Color,
+    rect: Rect,
+) where
+    S: CanvasPainterState,
+{
+    let thickness = rect.height;
+    let center = rect.top + thickness / 2.0;
+
+    let stroke = |dash_pattern: Vec<f32>| Paint {
+        color,
+
+        style: PaintingStyle::Stroke(StrokeStyle::new(thickness).dashes(0.0, dash_pattern)),
+
+        ..Paint::default()
+    };
+
+    match style {
+        TextDecorationStyle::Solid => {
+            let brush = brush_for(canvas, brushes, fill(color));
+
+            canvas.draw_rect_at(rect, brush);
+        }
+
+        // The two lines are a line's thickness apart, centered on where a solid line would be
+        TextDecorationStyle::Double => {
+            let brush = brush_for(canvas, brushes, fill(color));
+
+            for top in [rect.top - thickness, rect.top + thickness] {
+                canvas.draw_rect_at(Rect::new(rect.left, top, rect.width, thickness), brush);
+            }
+        }
+
+        TextDecorationStyle::Dotted | TextDecorationStyle::Dashed => {
+            let dash_pattern = if style == TextDecorationStyle::Dotted {
+                vec![thickness, thickness]
+            } else {
+                vec![thickness * 3.0, thickness * 2.0]
+            };
+
+            let brush = brush_for(canvas, brushes, stroke(dash_pattern));
+
+            canvas.draw_polyline(
+                brush,
+                &[
+                    Offset::new(rect.left, center),
+                    Offset::new(rect.left + rect.width, center),
+                ],
+            );
+        }
+
+        // A zigzag that rises and falls by the line's thickness every two thicknesses
+        TextDecorationStyle::Wavy => {
+            let step = thickness * 2.0;
+
+            let points = (0..=(rect.width / step).ceil() as usize)
+                .map(|idx| {
+                    let x = (idx as f32 * step).min(rect.width);
+                    let y = if idx % 2 == 0 { -thickness } else { thickness };
+
+                    Offset::new(rect.left + x, center + y)
+                })
+                .collect::<Vec<_>>();
+
+            let brush = brush_for(canvas, brushes, stroke(Vec::new()));
+
+            canvas.draw_polyline(brush, &points);
+        }
+    }
+}
+
+fn fill(color: Color) -> Paint {
+    Paint {
+        color,
+
+        ..Paint::default()
+    }
+}
+
+/// Returns the brush for `paint`, adding the paint to the canvas if it hasn't been yet.
+fn brush_for<'a, S>(
+    canvas: &mut CanvasPainter<Head<S>>,
+    brushes: &'a mut Vec<(Paint, Brush<Head<S>>)>,
+    paint: Paint,
+) -> &'a Brush<Head<S>>
+where
+    S: CanvasPainterState,
+{
+    match brushes
+        .iter()
+        .position(|(brush_paint, _)| *brush_paint == paint)
+    {
+        Some(idx) => &brushes[idx].1,
+
+        None => {
+            let brush = canvas.add_paint(paint.clone());
+
+            brushes.push((paint, brush));
+
+            &brushes[brushes.len() - 1].1
+        }
+    }
+}
+
+/// Returns the area covered by a glyph run, spanning the full height of its line.
+fn run_bounds(line: &TextLine, run: &GlyphRun) -> Rect {
+    let left = run
+        .glyphs
+        .iter()
+        .map(|glyph| glyph.x)
+        .fold(f32::INFINITY, f32::min);
+
+    let right = run
+        .glyphs
+        .iter()
+        .map(|glyph| glyph.x + glyph.advance)
+        .fold(f32::NEG_INFINITY, f32::max);
+
+    if left > right {
+        return Rect::new(line.left, line.top, 0.0, line.height);
+    }
+
+    Rect::new(left, line.top, right - left, line.height)
+}
+
+fn is_line_terminator(ch: char) -> bool {
+    matches!(
+        ch,
+        '\n' | '\r' | '\u{0B}' | '\u{0C}' | '\u{85}' | '\u{2028}' | '\u{2029}'
+    )
 }
@@ -0,0 +1,67 @@
+use std::borrow::Cow;
+
+use agui_core::{
+    unit::TextStyle,
+    widget::{IntoWidget, Widget},
+};
+use agui_elements::stateless::{StatelessBuildContext, StatelessWidget};
+use agui_macros::StatelessWidget;
+
+use crate::{
+    directionality::Directionality,
+    text::{FontLoader, RawParagraph, StyledRange, TextSpan},
+};
+
+/// Displays a tree of text spans as a single paragraph.
+///
+/// Each span may override the style it inherits from its parent, starting from `style`,
+/// and may be tapped. Widgets within the tree are laid out inline with the text, at their
+/// natural size. If `style` doesn't set a text direction, the ambient
+/// [`Directionality`] is used. Fonts that refer to a URL are loaded with a
+/// [`FontLoader`].
+#[derive(StatelessWidget, Debug)]
+pub struct RichText {
+    pub style: TextStyle,
+
+    pub text: TextSpan,
+}
+
+impl StatelessWidget for RichText {
+    fn build(&self, ctx: &mut StatelessBuildContext<Self>) -> Widget {
+        let mut style = self.style.clone();
+
+        style.text_direction = style.text_direction.or_else(|| Directionality::of(ctx));
+
+        let flattened = self.text.flatten(&style);
+
+        let fonts = std::iter::once(&style)
+            .chain(flattened.styles.iter().map(|range| &range.style))
+            .map(|style| style.font.clone())
+            .collect();
+
+        let text = Cow::<'static, str>::from(flattened.text);
+        let children = self.text.widgets();
+
+        FontLoader::new(fonts, move |fonts| {
+            RawParagraph {
+                style: fonts.style(&style),
+
+                text: Cow::clone(&text),
+
+                styles: flattened
+                    .styles
+                    .iter()
+                    .map(|range| StyledRange {
+                        style: fonts.style(&range.style),
+                        ..range.clone()
+                    })
+                    .collect(),
+                placeholders: flattened.placeholders.clone(),
+
+                children: children.clone(),
+            }
+            .into_widget()
+        })
+        .into_widget()
+    }
+}
@@ -0,0 +1,461 @@
+use std::{borrow::Cow, ops::Range, time::Duration};
+
+use agui_core::{
+    callback::Callback,
+    clipboard::Clipboard,
+    element::{RenderObjectCreateContext, RenderObjectUpdateContext},
+    input::{
+        EventResult, InputEvent, Key, KeyEvent, Modifiers, PointerButton, PointerEvent,
+        PointerEventKind, PointerId,
+    },
+    render::{
+        canvas::painter::{CanvasPainter, Head},
+        object::{
+            RenderObjectBaselineContext, RenderObjectEventContext, RenderObjectHitTestContext,
+            RenderObjectImpl, RenderObjectIntrinsicSizeContext, RenderObjectLayoutContext,
+        },
+    },
+    unit::{
+        Color, Constraints, HitTest, IntrinsicDimension, Offset, Rect, Size, TextBaseline,
+        TextSelection, TextStyle,
+    },
+    widget::{IntoWidget, Widget},
+};
+use agui_elements::{
+    render::RenderObjectWidget,
+    stateful::{ContextWidgetStateMut, StatefulBuildContext, StatefulWidget, WidgetState},
+};
+use agui_macros::{RenderObjectWidget, StatefulWidget};
+use parking_lot::Mutex;
+
+use crate::{
+    directionality::Directionality,
+    text::{FontLoader, RenderParagraph, DEFAULT_SELECTION_COLOR},
+};
+
+/// The longest time between two clicks for them to count as a double click.
+const DOUBLE_CLICK_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// The furthest apart two clicks can be for them to count as a double click.
+const DOUBLE_CLICK_SLOP: f32 = 8.0;
+
+/// Displays a paragraph of text in a single style that can be selected with the mouse and
+/// copied.
+///
+/// Dragging selects text, double clicking selects a word (and dragging afterwards extends
+/// the selection a word at a time), and clicking with shift held extends the selection.
+/// While focused, `Ctrl+A` selects everything and `Ctrl+C` copies the selection to the
+/// clipboard. The selection is cleared when the text changes or focus is lost.
+#[derive(StatefulWidget, Debug)]
+pub struct SelectableText {
+    pub style: TextStyle,
+
+    pub text: Cow<'static, str>,
+
+    #[prop(default = DEFAULT_SELECTION_COLOR)]
+    pub selection_color: Color,
+
+    /// The clipboard that selected text is copied to. If `None`, the global clipboard is
+    /// used.
+    #[prop(default)]
+    pub clipboard: Option<Clipboard>,
+
+    #[prop(default)]
+    pub on_selection_change: Option<Callback<Option<TextSelection>>>,
+}
+
+impl StatefulWidget for SelectableText {
+    type State = SelectableTextState;
+
+    fn create_state(&self) -> Self::State {
+        SelectableTextState { selection: None }
+    }
+}
+
+pub struct SelectableTextState {
+    selection: Option<TextSelection>,
+}
+
+impl WidgetState for SelectableTextState {
+    type Widget = SelectableText;
+
+    fn updated(&mut self, ctx: &mut StatefulBuildContext<Self>, old_widget: &Self::Widget) {
+        if ctx.widget.text != old_widget.text {
+            self.selection = None;
+        }
+    }
+
+    fn build(&mut self, ctx: &mut StatefulBuildContext<Self>) -> Widget {
+        let mut style = ctx.widget.style.clone();
+
+        if style.text_direction.is_none() {
+            style.text_direction = Directionality::of(ctx);
+        }
+
+        let on_selection_change = ctx.callback(|ctx, selection: Option<TextSelection>| {
+            ctx.set_state(move |state| {
+                state.selection = selection;
+            });
+
+            if let Some(on_selection_change) = &ctx.widget.on_selection_change {
+                on_selection_change.call(selection);
+            }
+        });
+
+        let text = Cow::clone(&ctx.widget.text);
+
+        let selection = self.selection;
+        let selection_color = ctx.widget.selection_color;
+
+        let clipboard = ctx
+            .widget
+            .clipboard
+            .clone()
+            .unwrap_or_else(|| Clipboard::global().clone());
+
+        FontLoader::new(vec![style.font.clone()], move |fonts| {
+            SelectableParagraph {
+                style: fonts.style(&style),
+
+                text: Cow::clone(&text),
+
+                selection,
+                selection_color,
+
+                clipboard: clipboard.clone(),
+
+                on_selection_change: on_selection_change.clone(),
+            }
+            .into_widget()
+        })
+        .into_widget()
+    }
+}
+
+#[derive(RenderObjectWidget, Debug)]
+struct SelectableParagraph {
+    style: TextStyle,
+
+    text: Cow<'static, str>,
+
+    selection: Option<TextSelection>,
+    selection_color: Color,
+
+    clipboard: Clipboard,
+
+    on_selection_change: Callback<Option<TextSelection>>,
+}
+
+impl RenderObjectWidget for SelectableParagraph {
+    type RenderObject = RenderSelectableText;
+
+    fn children(&self) -> Vec<Widget> {
+        Vec::new()
+    }
+
+    fn create_render_object(&self, _: &mut RenderObjectCreateContext) -> Self::RenderObject {
+        let mut paragraph = RenderParagraph::new(self.style.clone(), Cow::clone(&self.text));
+
+        paragraph.selection = self.selection;
+        paragraph.selection_color = self.selection_color;
+
+        RenderSelectableText {
+            paragraph,
+
+            clipboard: self.clipboard.clone(),
+
+            on_selection_change: self.on_selection_change.clone(),
+
+            gesture: Mutex::new(SelectionGesture {
+                selection: self.selection,
+
+                ..SelectionGesture::default()
+            }),
+        }
+    }
+
+    fn update_render_object(
+        &self,
+        ctx: &mut RenderObjectUpdateContext,
+        render_object: &mut Self::RenderObject,
+    ) {
+        render_object
+            .paragraph
+            .update_style(ctx, self.style.clone());
+
+        render_object
+            .paragraph
+            .update_text(ctx, Cow::clone(&self.text));
+
+        render_object
+            .paragraph
+            .update_selection(ctx, self.selection);
+
+        render_object
+            .paragraph
+            .update_selection_color(ctx, self.selection_color);
+
+        render_object.clipboard = self.clipboard.clone();
+
+        render_object.on_selection_change = self.on_selection_change.clone();
+
+        render_object.gesture.get_mut().selection = self.selection;
+    }
+}
+
+/// A paragraph that can be selected with a pointer and copied from with the keyboard.
+pub struct RenderSelectableText {
+    paragraph: RenderParagraph,
+
+    clipboard: Clipboard,
+
+    on_selection_change: Callback<Option<TextSelection>>,
+
+    gesture: Mutex<SelectionGesture>,
+}
+
+#[derive(Default)]
+struct SelectionGesture {
+    /// The selection as of the last event. It's kept here, rather than read back from the
+    /// paragraph, so that events arriving before the widget rebuilds build on each other.
+    selection: Option<TextSelection>,
+
+    /// The pointer dragging out the selection, if any.
+    dragging: Option<PointerId>,
+
+    /// The word that was double clicked, if the selection is being made a word at a time.
+    anchor_word: Option<Range<usize>>,
+
+    /// When and where the primary button last went down, to detect double clicks.
+    last_click: Option<(Duration, Offset)>,
+}
+
+impl RenderSelectableText {
+    fn set_selection(&self, gesture: &mut SelectionGesture, selection: Option<TextSelection>) {
+        if gesture.selection == selection {
+            return;
+        }
+
+        gesture.selection = selection;
+
+        self.on_selection_change.call(selection);
+    }
+
+    /// Returns the selection from `anchor` to the word at `offset`, so that the selection
+    /// always covers whole words in the direction it's being dragged.
+    fn select_words(&self, anchor: &Range<usize>, offset: usize) -> TextSelection {
+        let word = self.paragraph.word_boundary(offset);
+
+        if word.start < anchor.start {
+            TextSelection::new(anchor.end, word.start)
+        } else {
+            TextSelection::new(anchor.start, word.end.max(anchor.end))
+        }
+    }
+
+    fn handle_pointer(
+        &self,
+        ctx: &mut RenderObjectEventContext,
+        event: &PointerEvent,
+    ) -> EventResult {
+        let mut gesture = self.gesture.lock();
+
+        match event.kind {
+            PointerEventKind::Down if event.button == PointerButton::Primary => {
+                ctx.request_focus();
+
+                let offset = self
+                    .paragraph
+                    .position_for_offset(event.local_position)
+                    .offset;
+
+                let is_double_click = gesture.last_click.is_some_and(|(timestamp, position)| {
+                    event.timestamp.saturating_sub(timestamp) <= DOUBLE_CLICK_TIMEOUT
+                        && (event.local_position - position).distance() <= DOUBLE_CLICK_SLOP
+                });
+
+                // A third click starts over, rather than counting as another double click
+                gesture.last_click = if is_double_click {
+                    None
+                } else {
+                    Some((event.timestamp, event.local_position))
+                };
+
+                gesture.dragging = Some(event.pointer);
+
+                let selection = if is_double_click {
+                    let word = self.paragraph.word_boundary(offset);
+
+                    gesture.anchor_word = Some(word.clone());
+
+                    TextSelection::new(word.start, word.end)
+                } else {
+                    gesture.anchor_word = None;
+
+                    match gesture.selection {
+                        Some(selection) if event.modifiers.contains(Modifiers::SHIFT) => {
+                            TextSelection::new(selection.base, offset)
+                        }
+
+                        _ => TextSelection::collapsed(offset),
+                    }
+                };
+
+                self.set_selection(&mut gesture, Some(selection));
+
+                EventResult::Handled
+            }
+
+            PointerEventKind::Move if gesture.dragging == Some(event.pointer) => {
+                let offset = self
+                    .paragraph
+                    .position_for_offset(event.local_position)
+                    .offset;
+
+                let selection = match &gesture.anchor_word {
+                    Some(anchor) => self.select_words(anchor, offset),
+
+                    None => TextSelection::new(
+                        gesture.selection.map_or(offset, |selection| selection.base),
+                        offset,
+                    ),
+                };
+
+                self.set_selection(&mut gesture, Some(selection));
+
+                EventResult::Handled
+            }
+
+            PointerEventKind::Up | PointerEventKind::Cancel
+                if gesture.dragging == Some(event.pointer) =>
+            {
+                gesture.dragging = None;
+
+                EventResult::Handled
+            }
+
+            _ => EventResult::Ignored,
+        }
+    }
+
+    fn handle_key(&self, event: &KeyEvent) -> EventResult {
+        if !event.is_pressed()
+            || !event
+                .modifiers
+                .intersects(Modifiers::CONTROL | Modifiers::SUPER)
+        {
+            return EventResult::Ignored;
+        }
+
+        let mut gesture = self.gesture.lock();
+
+        match event.key {
+            Key::Character('a' | 'A') => {
+                let selection = TextSelection::new(0, self.paragraph.text.len());
+
+                self.set_selection(&mut gesture, Some(selection));
+
+                EventResult::Handled
+            }
+
+            Key::Character('c' | 'C') => {
+                if let Some(selection) = gesture.selection.filter(|sel| !sel.is_collapsed()) {
+                    if let Some(text) = self.paragraph.text.get(selection.range()) {
+                        self.clipboard.write_text(text);
+                    }
+                }
+
+                EventResult::Handled
+            }
+
+            _ => EventResult::Ignored,
+        }
+    }
+}
+
+impl RenderObjectImpl for RenderSelectableText {
+    fn intrinsic_size(
+        &self,
+        ctx: &mut RenderObjectIntrinsicSizeContext,
+        dimension: IntrinsicDimension,
+        cross_axis: f32,
+    ) -> f32 {
+        self.paragraph.intrinsic_size(ctx, dimension, cross_axis)
+    }
+
+    fn layout(&self, ctx: &mut RenderObjectLayoutContext, constraints: Constraints) -> Size {
+        self.paragraph.layout(ctx, constraints)
+    }
+
+    fn compute_distance_to_baseline(
+        &self,
+        ctx: &mut RenderObjectBaselineContext,
+        baseline: TextBaseline,
+    ) -> Option<f32> {
+        self.paragraph.compute_distance_to_baseline(ctx, baseline)
+    }
+
+    fn hit_test<'ctx>(
+        &self,
+        ctx: &'ctx mut RenderObjectHitTestContext<'ctx>,
+        position: Offset,
+    ) -> HitTest {
+        if ctx.size().contains(position) {
+            HitTest::Absorb
+        } else {
+            HitTest::Pass
+        }
+    }
+
+    fn handle_event(&self, ctx: &mut RenderObjectEventContext, event: &InputEvent) -> EventResult {
+        match event {
+            InputEvent::Pointer(event) => self.handle_pointer(ctx, event),
+
+            InputEvent::Key(event) => self.handle_key(event),
+
+            InputEvent::FocusChanged(false) => {
+                let mut gesture = self.gesture.lock();
+
+                gesture.dragging = None;
+
+                self.set_selection(&mut gesture, None);
+
+                EventResult::Handled
+            }
+
+            _ => EventResult::Ignored,
+        }
+    }
+
+    fn does_paint(&self) -> bool {
+        true
+    }
+
+    fn paint_bounds(&self, size: Size) -> Rect {
+        self.paragraph.paint_bounds(size)
+    }
+
+    fn paint(&self, canvas: CanvasPainter<Head<()>>) {
+        self.paragraph.paint(canvas)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use agui_core::unit::TextStyle;
+
+    use crate::text::RenderParagraph;
+
+    #[test]
+    pub fn word_boundaries_follow_unicode_words() {
+        let paragraph = RenderParagraph::new(TextStyle::default(), Cow::Borrowed("hello, world"));
+
+        assert_eq!(paragraph.word_boundary(2), 0..5);
+        assert_eq!(paragraph.word_boundary(5), 5..6);
+        assert_eq!(paragraph.word_boundary(6), 6..7);
+        assert_eq!(paragraph.word_boundary(9), 7..12);
+        assert_eq!(paragraph.word_boundary(12), 7..12);
+    }
+}
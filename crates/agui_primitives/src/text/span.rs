@@ -0,0 +1,403 @@
+use std::borrow::Cow;
+
+use agui_core::{
+    callback::Callback,
+    unit::{
+        Color, Font, FontStretch, FontStyle, FontWeight, TextDecoration, TextDecorationStyle,
+        TextStyle, OBJECT_REPLACEMENT_CHAR,
+    },
+    widget::Widget,
+};
+
+use crate::text::StyledRange;
+
+/// Overrides for the style of a span of text. Anything left unset is inherited from the
+/// parent span, or from the style of the paragraph.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TextSpanStyle {
+    pub font: Option<Font>,
+
+    pub size: Option<f32>,
+    pub color: Option<Color>,
+
+    pub letter_spacing: Option<f32>,
+    pub word_spacing: Option<f32>,
+
+    pub font_weight: Option<FontWeight>,
+    pub font_style: Option<FontStyle>,
+    pub font_stretch: Option<FontStretch>,
+
+    pub decoration: Option<TextDecoration>,
+    pub decoration_style: Option<TextDecorationStyle>,
+    pub decoration_color: Option<Color>,
+
+    /// The color painted behind the text.
+    pub background: Option<Color>,
+}
+
+impl TextSpanStyle {
+    pub fn font(mut self, font: Font) -> Self {
+        self.font = Some(font);
+        self
+    }
+
+    pub fn size(mut self, size: f32) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn letter_spacing(mut self, letter_spacing: f32) -> Self {
+        self.letter_spacing = Some(letter_spacing);
+        self
+    }
+
+    pub fn word_spacing(mut self, word_spacing: f32) -> Self {
+        self.word_spacing = Some(word_spacing);
+        self
+    }
+
+    pub fn font_weight(mut self, font_weight: FontWeight) -> Self {
+        self.font_weight = Some(font_weight);
+        self
+    }
+
+    pub fn font_style(mut self, font_style: FontStyle) -> Self {
+        self.font_style = Some(font_style);
+        self
+    }
+
+    pub fn font_stretch(mut self, font_stretch: FontStretch) -> Self {
+        self.font_stretch = Some(font_stretch);
+        self
+    }
+
+    pub fn decoration(mut self, decoration: TextDecoration) -> Self {
+        self.decoration = Some(decoration);
+        self
+    }
+
+    pub fn decoration_style(mut self, decoration_style: TextDecorationStyle) -> Self {
+        self.decoration_style = Some(decoration_style);
+        self
+    }
+
+    pub fn decoration_color(mut self, decoration_color: Color) -> Self {
+        self.decoration_color = Some(decoration_color);
+        self
+    }
+
+    pub fn background(mut self, background: Color) -> Self {
+        self.background = Some(background);
+        self
+    }
+}
+
+/// A piece of rich text, which is either styled text or a widget laid out inline with it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InlineSpan {
+    Text(Box<TextSpan>),
+
+    /// A widget that's laid out at its natural size, with its bottom on the baseline.
+    Widget(Widget),
+}
+
+impl From<TextSpan> for InlineSpan {
+    fn from(span: TextSpan) -> Self {
+        Self::Text(Box::new(span))
+    }
+}
+
+impl From<Widget> for InlineSpan {
+    fn from(widget: Widget) -> Self {
+        Self::Widget(widget)
+    }
+}
+
+/// A tree of styled text. The text of a span comes before the text of its children, and
+/// its children inherit its style and tap callback unless they override them.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TextSpan {
+    pub text: Cow<'static, str>,
+
+    pub style: TextSpanStyle,
+
+    /// Called when the span is tapped with the primary pointer button.
+    pub on_tap: Option<Callback<()>>,
+
+    pub children: Vec<InlineSpan>,
+}
+
+impl TextSpan {
+    pub fn new(text: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            text: text.into(),
+
+            ..Self::default()
+        }
+    }
+
+    pub fn style(mut self, style: TextSpanStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn on_tap(mut self, on_tap: Callback<()>) -> Self {
+        self.on_tap = Some(on_tap);
+        self
+    }
+
+    pub fn child(mut self, child: impl Into<InlineSpan>) -> Self {
+        self.children.push(child.into());
+        self
+    }
+
+    pub fn children<I>(mut self, children: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<InlineSpan>,
+    {
+        self.children.extend(children.into_iter().map(Into::into));
+        self
+    }
+
+    /// Returns the text of the span and all of its children, with each widget replaced by
+    /// the object replacement character (`U+FFFC`).
+    pub fn to_plain_text(&self) -> String {
+        let mut text = String::new();
+
+        self.push_plain_text(&mut text);
+
+        text
+    }
+
+    /// Returns the widgets within the span, in the order they appear in the text.
+    pub fn widgets(&self) -> Vec<Widget> {
+        let mut widgets = Vec::new();
+
+        self.push_widgets(&mut widgets);
+
+        widgets
+    }
+
+    /// Flattens the span tree into its plain text and the style of each range of it,
+    /// resolving each span's style against `style`.
+    pub(crate) fn flatten(&self, style: &TextStyle) -> FlattenedText {
+        let mut flattened = FlattenedText::default();
+
+        self.flatten_into(
+            &mut flattened,
+            &Inherited {
+                style: style.clone(),
+
+                background: None,
+
+                on_tap: None,
+            },
+        );
+
+        flattened
+    }
+
+    fn flatten_into(&self, flattened: &mut FlattenedText, parent: &Inherited) {
+        let inherited = Inherited {
+            style: TextStyle {
+                font: self
+                    .style
+                    .font
+                    .clone()
+                    .unwrap_or_else(|| parent.style.font.clone()),
+
+                size: self.style.size.unwrap_or(parent.style.size),
+                color: self.style.color.unwrap_or(parent.style.color),
+
+                letter_spacing: self
+                    .style
+                    .letter_spacing
+                    .unwrap_or(parent.style.letter_spacing),
+                word_spacing: self.style.word_spacing.unwrap_or(parent.style.word_spacing),
+
+                font_weight: self.style.font_weight.unwrap_or(parent.style.font_weight),
+                font_style: self.style.font_style.unwrap_or(parent.style.font_style),
+                font_stretch: self.style.font_stretch.unwrap_or(parent.style.font_stretch),
+
+                decoration: self.style.decoration.unwrap_or(parent.style.decoration),
+                decoration_style: self
+                    .style
+                    .decoration_style
+                    .unwrap_or(parent.style.decoration_style),
+                decoration_color: self
+                    .style
+                    .decoration_color
+                    .or(parent.style.decoration_color),
+
+                ..parent.style.clone()
+            },
+
+            background: self.style.background.or(parent.background),
+
+            on_tap: self.on_tap.clone().or_else(|| parent.on_tap.clone()),
+        };
+
+        if !self.text.is_empty() {
+            let start = flattened.text.len();
+
+            flattened.text.push_str(&self.text);
+
+            flattened.styles.push(StyledRange {
+                range: start..flattened.text.len(),
+
+                style: inherited.style.clone(),
+
+                background: inherited.background,
+
+                on_tap: inherited.on_tap.clone(),
+            });
+        }
+
+        for child in &self.children {
+            match child {
+                InlineSpan::Text(span) => span.flatten_into(flattened, &inherited),
+
+                InlineSpan::Widget(_) => {
+                    flattened.placeholders.push(flattened.text.len());
+                    flattened.text.push(OBJECT_REPLACEMENT_CHAR);
+                }
+            }
+        }
+    }
+
+    fn push_plain_text(&self, text: &mut String) {
+        text.push_str(&self.text);
+
+        for child in &self.children {
+            match child {
+                InlineSpan::Text(span) => span.push_plain_text(text),
+                InlineSpan::Widget(_) => text.push(OBJECT_REPLACEMENT_CHAR),
+            }
+        }
+    }
+
+    fn push_widgets(&self, widgets: &mut Vec<Widget>) {
+        for child in &self.children {
+            match child {
+                InlineSpan::Text(span) => span.push_widgets(widgets),
+                InlineSpan::Widget(widget) => widgets.push(widget.clone()),
+            }
+        }
+    }
+}
+
+/// The style that a span passes down to its children.
+struct Inherited {
+    style: TextStyle,
+
+    background: Option<Color>,
+
+    on_tap: Option<Callback<()>>,
+}
+
+/// A span tree flattened into the text and styles of a paragraph.
+#[derive(Debug, Default)]
+pub(crate) struct FlattenedText {
+    pub text: String,
+
+    pub styles: Vec<StyledRange>,
+
+    /// The byte offsets of the inline widgets, in order.
+    pub placeholders: Vec<usize>,
+}
+
+#[cfg(test)]
+mod tests {
+    use agui_core::{
+        unit::{Color, Font, HorizontalAlign, TextDecoration, TextStyle, VerticalAlign},
+        widget::Widget,
+    };
+
+    use crate::sized_box::SizedBox;
+
+    use super::{TextSpan, TextSpanStyle};
+
+    fn style() -> TextStyle {
+        TextStyle {
+            font: Font::from_family("Arial"),
+
+            size: 16.0,
+            color: Color::from_rgb((0.0, 0.0, 0.0)),
+
+            h_align: HorizontalAlign::default(),
+            v_align: VerticalAlign::default(),
+
+            ..TextStyle::default()
+        }
+    }
+
+    #[test]
+    pub fn children_inherit_overrides() {
+        let red = Color::from_rgb((1.0, 0.0, 0.0));
+
+        let span = TextSpan::new("a")
+            .style(
+                TextSpanStyle::default()
+                    .color(red)
+                    .decoration(TextDecoration::UNDERLINE),
+            )
+            .child(TextSpan::new("b").style(TextSpanStyle::default().size(24.0)))
+            .child(
+                TextSpan::new("c").style(TextSpanStyle::default().decoration(TextDecoration::NONE)),
+            );
+
+        let flattened = span.flatten(&style());
+
+        assert_eq!(flattened.text, "abc");
+
+        let styles = &flattened.styles;
+
+        assert_eq!(
+            styles
+                .iter()
+                .map(|styled| styled.range.clone())
+                .collect::<Vec<_>>(),
+            [0..1, 1..2, 2..3]
+        );
+
+        assert_eq!(styles[0].style.color, red);
+        assert_eq!(styles[0].style.size, 16.0);
+        assert_eq!(styles[0].style.decoration, TextDecoration::UNDERLINE);
+
+        assert_eq!(styles[1].style.color, red, "should inherit the color");
+        assert_eq!(styles[1].style.size, 24.0);
+        assert_eq!(styles[1].style.decoration, TextDecoration::UNDERLINE);
+
+        assert_eq!(styles[2].style.decoration, TextDecoration::NONE);
+    }
+
+    #[test]
+    pub fn widgets_are_replaced_in_the_text() {
+        let span = TextSpan::new("a")
+            .child(Widget::from(SizedBox::new(8.0, 8.0)))
+            .child(TextSpan::new("b").child(Widget::from(SizedBox::new(4.0, 4.0))));
+
+        let flattened = span.flatten(&style());
+
+        assert_eq!(flattened.text, "a\u{FFFC}b\u{FFFC}");
+        assert_eq!(flattened.text, span.to_plain_text());
+        assert_eq!(flattened.placeholders, [1, 5]);
+        assert_eq!(span.widgets().len(), 2);
+
+        assert_eq!(
+            flattened
+                .styles
+                .iter()
+                .map(|styled| styled.range.clone())
+                .collect::<Vec<_>>(),
+            [0..1, 4..5],
+            "widgets should not be styled"
+        );
+    }
+}
@@ -1,5 +1,7 @@
 use std::error::Error;
 
+pub mod view;
+
 pub trait Renderer {
     fn render(&mut self);
 }
@@ -0,0 +1,140 @@
+use std::rc::Rc;
+
+use agui_core::{
+    element::{
+        lifecycle::ElementLifecycle, render::ElementRender, view::ElementView,
+        widget::ElementWidget, ElementComparison, ElementMountContext, RenderObjectCreateContext,
+        RenderObjectUpdateContext,
+    },
+    engine::rendering::view::View,
+    render::object::{
+        RenderObject, RenderObjectImpl, RenderObjectIntrinsicSizeContext, RenderObjectLayoutContext,
+    },
+    unit::{Constraints, IntrinsicDimension, Size},
+    widget::{AnyWidget, Widget},
+};
+
+use super::ViewWidget;
+
+pub struct ViewWidgetElement<W>
+where
+    W: ViewWidget,
+{
+    widget: Rc<W>,
+
+    view: Option<W::View>,
+    view_handle: Option<W::ViewHandle>,
+}
+
+impl<W> ViewWidgetElement<W>
+where
+    W: ViewWidget,
+{
+    pub fn new(widget: Rc<W>) -> Self {
+        Self {
+            widget,
+
+            view: None,
+            view_handle: None,
+        }
+    }
+}
+
+impl<W> ElementLifecycle for ViewWidgetElement<W>
+where
+    W: AnyWidget + ViewWidget,
+{
+    fn mount(&mut self, _: &mut ElementMountContext) {
+        let (view, view_handle) = self.widget.create_view();
+
+        self.view = Some(view);
+        self.view_handle = Some(view_handle);
+    }
+
+    fn update(&mut self, new_widget: &Widget) -> ElementComparison {
+        if new_widget == &self.widget {
+            return ElementComparison::Identical;
+        }
+
+        if let Some(new_widget) = new_widget.downcast::<W>() {
+            if self.widget.is_same_view(&new_widget) {
+                self.widget = new_widget;
+
+                ElementComparison::Changed
+            } else {
+                // TODO: currently, create_view would not get called again if we return Changed here
+                // so we have to recreate the element entirely. `is_valid_render_object` should actually
+                // be called to re-use render objects in order to do that.
+                ElementComparison::Invalid
+            }
+        } else {
+            ElementComparison::Invalid
+        }
+    }
+}
+
+impl<W> ElementWidget for ViewWidgetElement<W>
+where
+    W: AnyWidget + ViewWidget,
+{
+    type Widget = W;
+
+    fn widget(&self) -> &Rc<Self::Widget> {
+        &self.widget
+    }
+}
+
+impl<W> ElementRender for ViewWidgetElement<W>
+where
+    W: AnyWidget + ViewWidget,
+{
+    fn children(&self) -> Vec<Widget> {
+        let view_handle = self.view_handle.as_ref().expect("view handle missing");
+
+        vec![self.widget.build(view_handle)]
+    }
+
+    fn create_render_object(&self, _: &mut RenderObjectCreateContext) -> RenderObject {
+        RenderObject::new(RenderView)
+    }
+
+    fn is_valid_render_object(&self, render_object: &RenderObject) -> bool {
+        render_object.is::<RenderView>()
+    }
+
+    fn update_render_object(&self, _: &mut RenderObjectUpdateContext, _: &mut RenderObject) {}
+}
+
+impl<W> ElementView for ViewWidgetElement<W>
+where
+    W: AnyWidget + ViewWidget,
+{
+    fn create_view(&mut self) -> Box<dyn View + Send> {
+        Box::new(self.view.take().expect("view has already been created"))
+    }
+}
+
+struct RenderView;
+
+impl RenderObjectImpl for RenderView {
+    fn intrinsic_size(
+        &self,
+        ctx: &mut RenderObjectIntrinsicSizeContext,
+        dimension: IntrinsicDimension,
+        cross_extent: f32,
+    ) -> f32 {
+        // Views always take the size of the child
+        ctx.iter_children()
+            .next()
+            .unwrap()
+            .compute_intrinsic_size(dimension, cross_extent)
+    }
+
+    fn layout(&self, ctx: &mut RenderObjectLayoutContext, constraints: Constraints) -> Size {
+        // Views always take the size of the child
+        ctx.iter_children_mut()
+            .next()
+            .unwrap()
+            .compute_layout(constraints)
+    }
+}
@@ -0,0 +1,22 @@
+use agui_core::{engine::rendering::view::View, widget::Widget};
+
+mod element;
+mod scene;
+
+pub use element::*;
+pub use scene::*;
+
+/// A widget that creates a view for its child to be rendered into.
+pub trait ViewWidget: Sized {
+    type View: View + Send + 'static;
+    type ViewHandle: 'static;
+
+    /// Creates the view, along with a handle to it that's given to [`build`](Self::build).
+    fn create_view(&self) -> (Self::View, Self::ViewHandle);
+
+    /// Returns true if a view created by this widget can continue to be used once the
+    /// widget is replaced by `other`.
+    fn is_same_view(&self, other: &Self) -> bool;
+
+    fn build(&self, view_handle: &Self::ViewHandle) -> Widget;
+}
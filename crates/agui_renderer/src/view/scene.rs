@@ -0,0 +1,219 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use agui_core::{
+    engine::rendering::{
+        scene::SceneChange, strategies::RenderingTreeTextLayoutStrategy, view::View,
+    },
+    render::{canvas::Canvas, RenderObjectId},
+    unit::{Offset, Size},
+};
+use parking_lot::{Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use crate::FrameNotifier;
+
+/// The state a backend draws a view from, which is kept up to date with the view's
+/// render objects by a [`SceneView`].
+pub trait ViewScene: Send + Sync + 'static {
+    fn apply(&mut self, change: SceneChange);
+
+    /// Called once all of the changes since the view was last synced have been applied.
+    fn sync(&mut self) {}
+}
+
+/// A view that records the changes made to its render objects, and applies them to its
+/// scene each time it's synced.
+pub struct SceneView<S> {
+    text_layout: Box<dyn RenderingTreeTextLayoutStrategy + Send>,
+
+    scene: Arc<RwLock<S>>,
+
+    changes: Vec<SceneChange>,
+
+    frame_notifier: Arc<Mutex<Option<FrameNotifier>>>,
+}
+
+pub struct SceneViewHandle<S> {
+    scene: Arc<RwLock<S>>,
+
+    frame_notifier: Arc<Mutex<Option<FrameNotifier>>>,
+}
+
+impl<S> Clone for SceneViewHandle<S> {
+    fn clone(&self) -> Self {
+        Self {
+            scene: Arc::clone(&self.scene),
+
+            frame_notifier: Arc::clone(&self.frame_notifier),
+        }
+    }
+}
+
+impl<S> SceneView<S>
+where
+    S: ViewScene,
+{
+    pub fn new<T>(scene: S, text_layout: T) -> Self
+    where
+        T: RenderingTreeTextLayoutStrategy + Send + 'static,
+    {
+        Self {
+            text_layout: Box::new(text_layout),
+
+            scene: Arc::new(RwLock::new(scene)),
+
+            changes: Vec::default(),
+
+            frame_notifier: Arc::default(),
+        }
+    }
+
+    pub fn handle(&self) -> SceneViewHandle<S> {
+        SceneViewHandle {
+            scene: Arc::clone(&self.scene),
+
+            frame_notifier: Arc::clone(&self.frame_notifier),
+        }
+    }
+}
+
+impl<S> SceneViewHandle<S> {
+    /// Sets the notifier that's called each time the view is synced.
+    pub fn set_frame_notifier(&self, frame_notifier: FrameNotifier) {
+        self.frame_notifier.lock().replace(frame_notifier);
+    }
+
+    pub fn scene(&self) -> RwLockReadGuard<'_, S> {
+        self.scene.read()
+    }
+
+    pub fn scene_mut(&self) -> RwLockWriteGuard<'_, S> {
+        self.scene.write()
+    }
+}
+
+impl<S> View for SceneView<S>
+where
+    S: ViewScene,
+{
+    fn text_layout(&self) -> &dyn RenderingTreeTextLayoutStrategy {
+        self.text_layout.as_ref()
+    }
+
+    fn text_layout_mut(&mut self) -> &mut dyn RenderingTreeTextLayoutStrategy {
+        self.text_layout.as_mut()
+    }
+
+    fn on_attach(
+        &mut self,
+        parent_render_object_id: Option<RenderObjectId>,
+        render_object_id: RenderObjectId,
+    ) {
+        tracing::trace!(
+            "SceneView::on_attach {:?} {:?}",
+            parent_render_object_id,
+            render_object_id
+        );
+
+        self.changes.push(SceneChange::Attach {
+            parent_render_object_id,
+            render_object_id,
+        });
+    }
+
+    fn on_detach(&mut self, render_object_id: RenderObjectId) {
+        tracing::trace!("SceneView::on_detach {:?}", render_object_id);
+
+        self.changes.push(SceneChange::Detach { render_object_id });
+    }
+
+    fn on_size_changed(&mut self, render_object_id: RenderObjectId, size: Size) {
+        tracing::trace!(
+            "SceneView::on_size_changed {:?} {:?}",
+            render_object_id,
+            size
+        );
+
+        self.changes.push(SceneChange::SizeChanged {
+            render_object_id,
+            size,
+        });
+    }
+
+    fn on_offset_changed(&mut self, render_object_id: RenderObjectId, offset: Offset) {
+        tracing::trace!(
+            "SceneView::on_offset_changed {:?} {:?}",
+            render_object_id,
+            offset
+        );
+
+        self.changes.push(SceneChange::OffsetChanged {
+            render_object_id,
+            offset,
+        });
+    }
+
+    fn on_paint(&mut self, render_object_id: RenderObjectId, canvas: Canvas) {
+        tracing::trace!("SceneView::on_paint {:?} {:?}", render_object_id, canvas);
+
+        self.changes.push(SceneChange::Paint {
+            render_object_id,
+            canvas,
+        });
+    }
+
+    fn on_sync(&mut self) {
+        tracing::trace!("SceneView::on_sync");
+
+        let start = Instant::now();
+
+        // TODO: if this is locked, we should somehow check if another frame is ready and
+        // skip this one
+        let mut scene = self.scene.write();
+
+        let lock_scene_end = Instant::now();
+
+        for change in self.changes.drain(..) {
+            scene.apply(change);
+        }
+
+        let apply_changes_end = Instant::now();
+
+        scene.sync();
+
+        drop(scene);
+
+        let sync_end = Instant::now();
+
+        // Views that are only read on demand have no use for a notifier
+        if let Some(frame_notifier) = self.frame_notifier.lock().as_ref() {
+            frame_notifier.notify();
+        }
+
+        let frame_notify_end = Instant::now();
+
+        let timings = SyncTimings {
+            duration: start.elapsed(),
+
+            lock_scene: lock_scene_end - start,
+            apply_changes: apply_changes_end - lock_scene_end,
+            sync: sync_end - apply_changes_end,
+            frame_notify: frame_notify_end - sync_end,
+        };
+
+        tracing::debug!(?timings, "sync complete");
+    }
+}
+
+#[derive(Debug)]
+#[allow(dead_code)]
+struct SyncTimings {
+    duration: Duration,
+
+    lock_scene: Duration,
+    apply_changes: Duration,
+    sync: Duration,
+    frame_notify: Duration,
+}
@@ -0,0 +1,77 @@
+use agui_core::unit::{Color, Texture};
+
+/// A rendered image of a view.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    width: u32,
+    height: u32,
+
+    pixels: Vec<u8>,
+}
+
+impl Frame {
+    pub(crate) fn from_pixmap(pixmap: &tiny_skia::Pixmap) -> Self {
+        let pixels = pixmap
+            .pixels()
+            .iter()
+            .flat_map(|pixel| {
+                let pixel = pixel.demultiply();
+
+                [pixel.red(), pixel.green(), pixel.blue(), pixel.alpha()]
+            })
+            .collect();
+
+        Self {
+            width: pixmap.width(),
+            height: pixmap.height(),
+
+            pixels,
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// The non-premultiplied RGBA pixels of the frame, row by row.
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// Returns the RGBA components of the pixel at the given position, or `None` if it
+    /// lies outside of the frame.
+    pub fn pixel(&self, x: u32, y: u32) -> Option<[u8; 4]> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        let idx = (y as usize * self.width as usize + x as usize) * 4;
+
+        Some([
+            self.pixels[idx],
+            self.pixels[idx + 1],
+            self.pixels[idx + 2],
+            self.pixels[idx + 3],
+        ])
+    }
+
+    /// Returns the color of the pixel at the given position, or `None` if it lies outside
+    /// of the frame.
+    pub fn color(&self, x: u32, y: u32) -> Option<Color> {
+        self.pixel(x, y).map(|[red, green, blue, alpha]| Color {
+            red: red as f32 / 255.0,
+            green: green as f32 / 255.0,
+            blue: blue as f32 / 255.0,
+            alpha: alpha as f32 / 255.0,
+        })
+    }
+
+    /// Converts the frame into a texture, so that it may be drawn by another view.
+    pub fn into_texture(self) -> Texture {
+        Texture::from_rgba8(self.width, self.height, self.pixels)
+    }
+}
@@ -0,0 +1,5 @@
+pub mod create_view;
+pub mod frame;
+mod render;
+pub mod renderer;
+pub mod view;
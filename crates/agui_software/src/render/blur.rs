@@ -0,0 +1,177 @@
+use tiny_skia::Pixmap;
+
+/// The number of box blurs used to approximate a gaussian blur.
+const PASSES: usize = 3;
+
+/// Approximates a gaussian blur with the given standard deviations by applying successive
+/// box blurs along each axis. Pixels outside of the pixmap are treated as transparent.
+pub(crate) fn blur(pixmap: &mut Pixmap, sigma_x: f32, sigma_y: f32) {
+    let width = pixmap.width() as usize;
+    let height = pixmap.height() as usize;
+
+    let mut scratch = Vec::with_capacity(pixmap.data().len());
+
+    for radius in box_radii(sigma_x) {
+        box_blur(pixmap.data_mut(), &mut scratch, width, height, radius, true);
+    }
+
+    for radius in box_radii(sigma_y) {
+        box_blur(
+            pixmap.data_mut(),
+            &mut scratch,
+            width,
+            height,
+            radius,
+            false,
+        );
+    }
+}
+
+/// Returns the radius of each box blur pass that together approximate a gaussian blur.
+///
+/// See: <https://www.peterkovesi.com/papers/FastGaussianSmoothing.pdf>
+fn box_radii(sigma: f32) -> [usize; PASSES] {
+    if sigma <= 0.0 {
+        return [0; PASSES];
+    }
+
+    let passes = PASSES as f32;
+
+    let ideal_width = (12.0 * sigma * sigma / passes + 1.0).sqrt();
+
+    let mut lower_width = ideal_width.floor() as i32;
+
+    if lower_width % 2 == 0 {
+        lower_width -= 1;
+    }
+
+    let lower_width = lower_width.max(1) as f32;
+
+    let lower_passes = ((12.0 * sigma * sigma
+        - passes * lower_width * lower_width
+        - 4.0 * passes * lower_width
+        - 3.0 * passes)
+        / (-4.0 * lower_width - 4.0))
+        .round()
+        .clamp(0.0, passes) as usize;
+
+    let lower_radius = (lower_width as usize - 1) / 2;
+
+    let mut radii = [lower_radius + 1; PASSES];
+
+    for radius in radii.iter_mut().take(lower_passes) {
+        *radius = lower_radius;
+    }
+
+    radii
+}
+
+fn box_blur(
+    data: &mut [u8],
+    scratch: &mut Vec<u8>,
+    width: usize,
+    height: usize,
+    radius: usize,
+    horizontal: bool,
+) {
+    if radius == 0 || width == 0 || height == 0 {
+        return;
+    }
+
+    let (lines, len, line_stride, pixel_stride) = if horizontal {
+        (height, width, width * 4, 4)
+    } else {
+        (width, height, 4, width * 4)
+    };
+
+    scratch.clear();
+    scratch.extend_from_slice(data);
+
+    let diameter = (radius * 2 + 1) as u32;
+
+    for line in 0..lines {
+        let start = line * line_stride;
+
+        for channel in 0..4 {
+            let at = |idx: usize| scratch[start + idx * pixel_stride + channel] as u32;
+
+            let mut sum = (0..=radius.min(len - 1)).map(at).sum::<u32>();
+
+            for idx in 0..len {
+                data[start + idx * pixel_stride + channel] =
+                    ((sum + diameter / 2) / diameter) as u8;
+
+                if idx + radius + 1 < len {
+                    sum += at(idx + radius + 1);
+                }
+
+                if idx >= radius {
+                    sum -= at(idx - radius);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tiny_skia::{Color, Pixmap};
+
+    use super::{blur, box_radii};
+
+    fn alpha_at(pixmap: &Pixmap, x: u32, y: u32) -> u8 {
+        pixmap.pixel(x, y).unwrap().alpha()
+    }
+
+    #[test]
+    fn zero_sigma_does_nothing() {
+        assert_eq!(box_radii(0.0), [0, 0, 0]);
+
+        let mut pixmap = Pixmap::new(4, 4).unwrap();
+        pixmap.fill(Color::WHITE);
+
+        let before = pixmap.clone();
+
+        blur(&mut pixmap, 0.0, 0.0);
+
+        assert_eq!(pixmap, before);
+    }
+
+    #[test]
+    fn radii_grow_with_sigma() {
+        let small = box_radii(1.0).iter().sum::<usize>();
+        let large = box_radii(8.0).iter().sum::<usize>();
+
+        assert!(small > 0);
+        assert!(large > small);
+    }
+
+    #[test]
+    fn spreads_pixels() {
+        let mut pixmap = Pixmap::new(21, 21).unwrap();
+
+        let idx = (10 * 21 + 10) * 4;
+        pixmap.data_mut()[idx..idx + 4].copy_from_slice(&[255, 255, 255, 255]);
+
+        blur(&mut pixmap, 2.0, 2.0);
+
+        assert!(alpha_at(&pixmap, 10, 10) < 255);
+        assert!(alpha_at(&pixmap, 10, 10) > alpha_at(&pixmap, 12, 10));
+        assert!(alpha_at(&pixmap, 12, 10) > 0);
+        assert!(alpha_at(&pixmap, 10, 12) > 0);
+        assert_eq!(alpha_at(&pixmap, 0, 0), 0);
+    }
+
+    #[test]
+    fn blurs_each_axis_independently() {
+        let mut pixmap = Pixmap::new(21, 21).unwrap();
+
+        let idx = (10 * 21 + 10) * 4;
+        pixmap.data_mut()[idx..idx + 4].copy_from_slice(&[255, 255, 255, 255]);
+
+        blur(&mut pixmap, 2.0, 0.0);
+
+        assert!(alpha_at(&pixmap, 12, 10) > 0);
+        assert_eq!(alpha_at(&pixmap, 10, 12), 0);
+    }
+}
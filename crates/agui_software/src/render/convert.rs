@@ -0,0 +1,255 @@
+use agui_core::{
+    render::canvas::paint::Paint,
+    unit::{
+        BlendMode, Color, FillRule, Gradient, GradientExtend, GradientKind, Rect, Shape, StrokeCap,
+        StrokeJoin, StrokeStyle, Texture, TextureData,
+    },
+};
+use lyon::path::Event;
+use tiny_skia::{GradientStop, LinearGradient, Point, RadialGradient, Shader, SpreadMode};
+
+/// Builds the path for `shape` within a box of the given size, with its origin at zero.
+/// Returns `None` if the shape is empty.
+pub(crate) fn to_path(shape: &Shape, width: f32, height: f32) -> Option<tiny_skia::Path> {
+    match shape {
+        Shape::Rect => tiny_skia::Rect::from_xywh(0.0, 0.0, width, height)
+            .map(tiny_skia::PathBuilder::from_rect),
+
+        shape => to_skia_path(&shape.build_path(Rect::new(0.0, 0.0, width, height))),
+    }
+}
+
+pub(crate) fn to_skia_path(path: &lyon::path::Path) -> Option<tiny_skia::Path> {
+    let mut builder = tiny_skia::PathBuilder::new();
+
+    for event in path.iter() {
+        match event {
+            Event::Begin { at } => builder.move_to(at.x, at.y),
+
+            Event::Line { to, .. } => builder.line_to(to.x, to.y),
+
+            Event::Quadratic { ctrl, to, .. } => builder.quad_to(ctrl.x, ctrl.y, to.x, to.y),
+
+            Event::Cubic {
+                ctrl1, ctrl2, to, ..
+            } => builder.cubic_to(ctrl1.x, ctrl1.y, ctrl2.x, ctrl2.y, to.x, to.y),
+
+            Event::End { close, .. } => {
+                if close {
+                    builder.close();
+                }
+            }
+        }
+    }
+
+    builder.finish()
+}
+
+pub(crate) fn to_color(color: Color) -> tiny_skia::Color {
+    tiny_skia::Color::from_rgba(
+        color.red.clamp(0.0, 1.0),
+        color.green.clamp(0.0, 1.0),
+        color.blue.clamp(0.0, 1.0),
+        color.alpha.clamp(0.0, 1.0),
+    )
+    .unwrap_or(tiny_skia::Color::TRANSPARENT)
+}
+
+pub(crate) fn to_fill_rule(fill_rule: FillRule) -> tiny_skia::FillRule {
+    match fill_rule {
+        FillRule::NonZero => tiny_skia::FillRule::Winding,
+        FillRule::EvenOdd => tiny_skia::FillRule::EvenOdd,
+    }
+}
+
+/// Returns the paint used to draw shapes, using its gradient if it has one.
+pub(crate) fn to_paint(paint: &Paint) -> tiny_skia::Paint<'static> {
+    let shader = paint
+        .gradient
+        .as_ref()
+        .and_then(to_gradient)
+        .unwrap_or_else(|| Shader::SolidColor(to_color(paint.color)));
+
+    tiny_skia::Paint {
+        shader,
+        blend_mode: to_blend_mode(paint.blend_mode),
+        anti_alias: paint.anti_alias,
+
+        ..tiny_skia::Paint::default()
+    }
+}
+
+/// Returns the gradient's shader, or `None` if the gradient is degenerate.
+fn to_gradient(gradient: &Gradient) -> Option<Shader<'static>> {
+    let stops = gradient
+        .stops
+        .iter()
+        .map(|stop| GradientStop::new(stop.offset, to_color(stop.color)))
+        .collect::<Vec<_>>();
+
+    let mode = match gradient.extend {
+        GradientExtend::Pad => SpreadMode::Pad,
+        GradientExtend::Repeat => SpreadMode::Repeat,
+        GradientExtend::Reflect => SpreadMode::Reflect,
+    };
+
+    match gradient.kind {
+        GradientKind::Linear { start, end } => LinearGradient::new(
+            Point::from_xy(start.x, start.y),
+            Point::from_xy(end.x, end.y),
+            stops,
+            mode,
+            tiny_skia::Transform::identity(),
+        ),
+
+        GradientKind::Radial {
+            center,
+            radius,
+            focal,
+        } => RadialGradient::new(
+            Point::from_xy(focal.x, focal.y),
+            Point::from_xy(center.x, center.y),
+            radius,
+            stops,
+            mode,
+            tiny_skia::Transform::identity(),
+        ),
+    }
+}
+
+pub(crate) fn to_stroke(style: &StrokeStyle) -> tiny_skia::Stroke {
+    let dash = if style.is_dashed() {
+        let mut pattern = style.dash_pattern.clone();
+
+        // Like SVG, an odd number of lengths is repeated to produce an even number
+        if pattern.len() % 2 == 1 {
+            pattern.extend_from_within(..);
+        }
+
+        tiny_skia::StrokeDash::new(pattern, style.dash_offset)
+    } else {
+        None
+    };
+
+    tiny_skia::Stroke {
+        width: style.width,
+        miter_limit: style.miter_limit,
+
+        line_cap: match style.cap {
+            StrokeCap::Butt => tiny_skia::LineCap::Butt,
+            StrokeCap::Round => tiny_skia::LineCap::Round,
+            StrokeCap::Square => tiny_skia::LineCap::Square,
+        },
+
+        line_join: match style.join {
+            StrokeJoin::Miter => tiny_skia::LineJoin::Miter,
+            StrokeJoin::Round => tiny_skia::LineJoin::Round,
+            StrokeJoin::Bevel => tiny_skia::LineJoin::Bevel,
+        },
+
+        dash,
+    }
+}
+
+pub(crate) fn to_blend_mode(blend_mode: BlendMode) -> tiny_skia::BlendMode {
+    match blend_mode {
+        BlendMode::Clear => tiny_skia::BlendMode::Clear,
+        BlendMode::Source => tiny_skia::BlendMode::Source,
+        BlendMode::Destination => tiny_skia::BlendMode::Destination,
+        BlendMode::SourceOver => tiny_skia::BlendMode::SourceOver,
+        BlendMode::DestinationOver => tiny_skia::BlendMode::DestinationOver,
+        BlendMode::SourceIn => tiny_skia::BlendMode::SourceIn,
+        BlendMode::DestinationIn => tiny_skia::BlendMode::DestinationIn,
+        BlendMode::SourceOut => tiny_skia::BlendMode::SourceOut,
+        BlendMode::DestinationOut => tiny_skia::BlendMode::DestinationOut,
+        BlendMode::SourceAlphaTop => tiny_skia::BlendMode::SourceAtop,
+        BlendMode::DestinationAlphaTop => tiny_skia::BlendMode::DestinationAtop,
+        BlendMode::XOr => tiny_skia::BlendMode::Xor,
+        BlendMode::Plus => tiny_skia::BlendMode::Plus,
+        BlendMode::Modulate => tiny_skia::BlendMode::Modulate,
+        BlendMode::Screen => tiny_skia::BlendMode::Screen,
+        BlendMode::Overlay => tiny_skia::BlendMode::Overlay,
+        BlendMode::Darken => tiny_skia::BlendMode::Darken,
+        BlendMode::Lighten => tiny_skia::BlendMode::Lighten,
+        BlendMode::ColorDodge => tiny_skia::BlendMode::ColorDodge,
+        BlendMode::ColorBurn => tiny_skia::BlendMode::ColorBurn,
+        BlendMode::HardLight => tiny_skia::BlendMode::HardLight,
+        BlendMode::SoftLight => tiny_skia::BlendMode::SoftLight,
+        BlendMode::Difference => tiny_skia::BlendMode::Difference,
+        BlendMode::Exclusion => tiny_skia::BlendMode::Exclusion,
+        BlendMode::Multiply => tiny_skia::BlendMode::Multiply,
+        BlendMode::Hue => tiny_skia::BlendMode::Hue,
+        BlendMode::Saturation => tiny_skia::BlendMode::Saturation,
+        BlendMode::Color => tiny_skia::BlendMode::Color,
+        BlendMode::Luminosity => tiny_skia::BlendMode::Luminosity,
+    }
+}
+
+/// Returns the texture's pixels, or `None` if the texture has not been decoded.
+pub(crate) fn to_pixmap(texture: &Texture) -> Option<tiny_skia::Pixmap> {
+    let TextureData::Rgba8 {
+        width,
+        height,
+        pixels,
+    } = texture.data()
+    else {
+        return None;
+    };
+
+    let mut pixmap = tiny_skia::Pixmap::new(*width, *height)?;
+
+    for (pixel, rgba) in pixmap.pixels_mut().iter_mut().zip(pixels.chunks_exact(4)) {
+        *pixel = tiny_skia::ColorU8::from_rgba(rgba[0], rgba[1], rgba[2], rgba[3]).premultiply();
+    }
+
+    Some(pixmap)
+}
+
+/// Converts glyph outlines into a path, flipping them so that they point downwards.
+pub(crate) struct GlyphOutliner<'a> {
+    pub builder: &'a mut tiny_skia::PathBuilder,
+
+    pub scale: f32,
+
+    pub x: f32,
+    pub y: f32,
+}
+
+impl GlyphOutliner<'_> {
+    fn point(&self, x: f32, y: f32) -> (f32, f32) {
+        (self.x + x * self.scale, self.y - y * self.scale)
+    }
+}
+
+impl ttf_parser::OutlineBuilder for GlyphOutliner<'_> {
+    fn move_to(&mut self, x: f32, y: f32) {
+        let (x, y) = self.point(x, y);
+
+        self.builder.move_to(x, y);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let (x, y) = self.point(x, y);
+
+        self.builder.line_to(x, y);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let (x1, y1) = self.point(x1, y1);
+        let (x, y) = self.point(x, y);
+
+        self.builder.quad_to(x1, y1, x, y);
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let (x1, y1) = self.point(x1, y1);
+        let (x2, y2) = self.point(x2, y2);
+        let (x, y) = self.point(x, y);
+
+        self.builder.cubic_to(x1, y1, x2, y2, x, y);
+    }
+
+    fn close(&mut self) {
+        self.builder.close();
+    }
+}
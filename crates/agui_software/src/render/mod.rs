@@ -0,0 +1,6 @@
+mod blur;
+mod convert;
+mod rasterizer;
+mod scene;
+
+pub use scene::*;
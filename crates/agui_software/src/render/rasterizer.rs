@@ -0,0 +1,993 @@
+use agui_core::{
+    render::canvas::{command::CanvasCommand, paint::Paint, Canvas, CanvasLayer},
+    unit::{
+        Bounds, BoxShadow, Constraints, GlyphRun, LayerFilter, Offset, PaintingStyle, Rect, Shape,
+        Size, TextStyle, Texture,
+    },
+};
+use agui_text::TextFonts;
+use tiny_skia::{
+    FilterQuality, IntRect, Mask, Pattern, Pixmap, PixmapPaint, SpreadMode, Transform,
+};
+use ttf_parser::GlyphId;
+
+use crate::render::{
+    blur::blur,
+    convert::{self, GlyphOutliner},
+};
+
+/// Draws canvases into a pixmap.
+///
+/// Layers are drawn into a pixmap of their own, which is composited into the layer
+/// beneath it once the layer has ended.
+pub(crate) struct Rasterizer<'fonts> {
+    fonts: &'fonts mut TextFonts,
+
+    base: Pixmap,
+    layers: Vec<LayerTarget>,
+}
+
+struct LayerTarget {
+    pixmap: Pixmap,
+
+    /// The shape of the layer, in device coordinates.
+    mask: Mask,
+
+    /// The area that the filtered contents of the layer may be drawn into, in device
+    /// coordinates. This is only set for filtered layers, since their contents may
+    /// spread beyond the layer's shape.
+    bounds: Option<Mask>,
+
+    opacity: f32,
+    blend_mode: tiny_skia::BlendMode,
+
+    filter: Option<LayerFilter>,
+}
+
+impl<'fonts> Rasterizer<'fonts> {
+    /// Returns `None` if either dimension is zero.
+    pub fn new(fonts: &'fonts mut TextFonts, width: u32, height: u32) -> Option<Self> {
+        Some(Self {
+            fonts,
+
+            base: Pixmap::new(width, height)?,
+            layers: Vec::new(),
+        })
+    }
+
+    /// Ends any layers that are still open and returns the drawn pixmap.
+    pub fn finish(mut self) -> Pixmap {
+        while !self.layers.is_empty() {
+            self.pop_layer();
+        }
+
+        self.base
+    }
+
+    /// Draws the canvas, including any of its child layers. If the canvas has a tail,
+    /// its layer is left open so that anything drawn afterwards is drawn within it until
+    /// [`end_canvas`](Self::end_canvas) is called.
+    pub fn begin_canvas(&mut self, transform: Transform, canvas: &Canvas) {
+        for command in &canvas.head {
+            self.draw_command(transform, &canvas.paints, command);
+        }
+
+        for layer in &canvas.children {
+            let layer_transform = self.push_layer(transform, &canvas.paints, layer);
+
+            self.begin_canvas(layer_transform, &layer.canvas);
+            self.end_canvas(&layer.canvas);
+
+            self.pop_layer();
+        }
+
+        if let Some(tail) = &canvas.tail {
+            let tail_transform = self.push_layer(transform, &canvas.paints, tail);
+
+            self.begin_canvas(tail_transform, &tail.canvas);
+        }
+    }
+
+    /// Ends the layers that were left open by [`begin_canvas`](Self::begin_canvas).
+    pub fn end_canvas(&mut self, canvas: &Canvas) {
+        if let Some(tail) = &canvas.tail {
+            self.end_canvas(&tail.canvas);
+
+            self.pop_layer();
+        }
+    }
+
+    fn target(&mut self) -> &mut Pixmap {
+        match self.layers.last_mut() {
+            Some(layer) => &mut layer.pixmap,
+            None => &mut self.base,
+        }
+    }
+
+    /// Starts drawing into a new layer, returning the transform of its contents.
+    fn push_layer(
+        &mut self,
+        transform: Transform,
+        paints: &[Paint],
+        layer: &CanvasLayer,
+    ) -> Transform {
+        let paint = paints
+            .get(layer.style.paint_idx)
+            .cloned()
+            .unwrap_or_else(|| {
+                tracing::warn!("layer refers to a paint that does not exist");
+
+                Paint::default()
+            });
+
+        let (width, height) = (self.base.width(), self.base.height());
+
+        let bounds = layer.style.filter.map(|_| {
+            let mut bounds = Mask::new(width, height).expect("the base pixmap is never empty");
+
+            let rect = layer.bounds();
+
+            if let Some(rect) =
+                tiny_skia::Rect::from_xywh(rect.left, rect.top, rect.width, rect.height)
+            {
+                bounds.fill_path(
+                    &tiny_skia::PathBuilder::from_rect(rect),
+                    tiny_skia::FillRule::Winding,
+                    false,
+                    transform,
+                );
+            }
+
+            bounds
+        });
+
+        let transform = transform.pre_translate(layer.offset.x, layer.offset.y);
+
+        let mut mask = Mask::new(width, height).expect("the base pixmap is never empty");
+
+        if let Some(path) = convert::to_path(
+            &layer.style.shape,
+            layer.canvas.size.width,
+            layer.canvas.size.height,
+        ) {
+            mask.fill_path(
+                &path,
+                tiny_skia::FillRule::Winding,
+                paint.anti_alias,
+                transform,
+            );
+        }
+
+        if let Some(filter) = layer.style.backdrop_filter {
+            let target = self.target();
+
+            let mut backdrop = target.clone();
+
+            apply_filter(&mut backdrop, filter);
+
+            // Replace everything within the layer's shape with its filtered counterpart
+            target.draw_pixmap(
+                0,
+                0,
+                backdrop.as_ref(),
+                &PixmapPaint {
+                    blend_mode: tiny_skia::BlendMode::Source,
+                    ..PixmapPaint::default()
+                },
+                Transform::identity(),
+                Some(&mask),
+            );
+        }
+
+        self.layers.push(LayerTarget {
+            pixmap: Pixmap::new(width, height).expect("the base pixmap is never empty"),
+
+            mask,
+            bounds,
+
+            opacity: paint.color.alpha.clamp(0.0, 1.0),
+            blend_mode: convert::to_blend_mode(paint.blend_mode),
+
+            filter: layer.style.filter,
+        });
+
+        transform
+    }
+
+    fn pop_layer(&mut self) {
+        let Some(mut layer) = self.layers.pop() else {
+            return;
+        };
+
+        let mask = match layer.filter {
+            Some(filter) => {
+                // The contents are clipped to the layer's shape before they're filtered, so
+                // that the filter can spread them beyond it
+                layer.pixmap.apply_mask(&layer.mask);
+
+                apply_filter(&mut layer.pixmap, filter);
+
+                layer.bounds.as_ref()
+            }
+
+            None => Some(&layer.mask),
+        };
+
+        self.target().draw_pixmap(
+            0,
+            0,
+            layer.pixmap.as_ref(),
+            &PixmapPaint {
+                opacity: layer.opacity,
+                blend_mode: layer.blend_mode,
+                quality: FilterQuality::Nearest,
+            },
+            Transform::identity(),
+            mask,
+        );
+    }
+
+    fn draw_command(&mut self, transform: Transform, paints: &[Paint], command: &CanvasCommand) {
+        match command {
+            CanvasCommand::Shape {
+                paint_idx,
+                rect,
+                shape,
+            } => {
+                let Some(paint) = paints.get(*paint_idx) else {
+                    tracing::warn!("shape refers to a paint that does not exist");
+                    return;
+                };
+
+                self.draw_shape(transform, paint, *rect, shape);
+            }
+
+            CanvasCommand::Shadow {
+                rect,
+                shape,
+                shadow,
+            } => self.draw_shadow(transform, *rect, shape, shadow),
+
+            CanvasCommand::Texture {
+                rect,
+                shape,
+                texture,
+                tex_bounds,
+            } => self.draw_texture(transform, *rect, shape, texture, *tex_bounds),
+
+            CanvasCommand::Text {
+                paint_idx,
+                rect,
+                text_style,
+                text,
+            } => {
+                let Some(paint) = paints.get(*paint_idx) else {
+                    tracing::warn!("text refers to a paint that does not exist");
+                    return;
+                };
+
+                self.draw_text(transform, paint, *rect, text_style, text);
+            }
+
+            CanvasCommand::Glyphs {
+                paint_idx,
+                rect,
+                runs,
+            } => {
+                let Some(paint) = paints.get(*paint_idx) else {
+                    tracing::warn!("glyphs refer to a paint that does not exist");
+                    return;
+                };
+
+                self.draw_glyphs(transform, paint, *rect, runs);
+            }
+
+            cmd => {
+                tracing::error!("unknown command: {:?}", cmd);
+            }
+        }
+    }
+
+    fn draw_shape(&mut self, transform: Transform, paint: &Paint, rect: Rect, shape: &Shape) {
+        let Some(path) = convert::to_path(shape, rect.width, rect.height) else {
+            return;
+        };
+
+        let transform = transform.pre_translate(rect.left, rect.top);
+
+        let skia_paint = convert::to_paint(paint);
+
+        match &paint.style {
+            PaintingStyle::Fill => self.target().fill_path(
+                &path,
+                &skia_paint,
+                convert::to_fill_rule(paint.fill_rule),
+                transform,
+                None,
+            ),
+
+            PaintingStyle::Stroke(stroke) => self.target().stroke_path(
+                &path,
+                &skia_paint,
+                &convert::to_stroke(stroke),
+                transform,
+                None,
+            ),
+        }
+    }
+
+    fn draw_shadow(&mut self, transform: Transform, rect: Rect, shape: &Shape, shadow: &BoxShadow) {
+        let local_rect = Rect::new(0.0, 0.0, rect.width, rect.height);
+
+        let shadow_rect = shadow.shadow_rect(local_rect);
+
+        // Custom paths can't be spread, so they're only offset
+        let (origin, path): (Offset, _) = match shape {
+            Shape::Path(path) => (shadow.offset, convert::to_skia_path(path)),
+
+            Shape::RoundedRect {
+                top_left,
+                top_right,
+                bottom_right,
+                bottom_left,
+            } => (
+                shadow_rect.into(),
+                convert::to_path(
+                    &Shape::RoundedRect {
+                        top_left: (top_left + shadow.spread_radius).max(0.0),
+                        top_right: (top_right + shadow.spread_radius).max(0.0),
+                        bottom_right: (bottom_right + shadow.spread_radius).max(0.0),
+                        bottom_left: (bottom_left + shadow.spread_radius).max(0.0),
+                    },
+                    shadow_rect.width,
+                    shadow_rect.height,
+                ),
+            ),
+
+            shape => (
+                shadow_rect.into(),
+                convert::to_path(shape, shadow_rect.width, shadow_rect.height),
+            ),
+        };
+
+        let Some(path) = path else {
+            return;
+        };
+
+        let target = self.target();
+
+        // Only the area that the shadow covers needs to be blurred
+        let Some(area) = bounds_in_target(
+            target,
+            transform,
+            shadow.bounds(local_rect).translate(rect.into()),
+        ) else {
+            return;
+        };
+
+        let Some(mut pixmap) = Pixmap::new(area.width(), area.height()) else {
+            return;
+        };
+
+        pixmap.fill_path(
+            &path,
+            &tiny_skia::Paint {
+                shader: tiny_skia::Shader::SolidColor(convert::to_color(shadow.color)),
+                anti_alias: true,
+
+                ..tiny_skia::Paint::default()
+            },
+            tiny_skia::FillRule::Winding,
+            Transform::from_translate(-area.x() as f32, -area.y() as f32)
+                .pre_concat(transform)
+                .pre_translate(rect.left + origin.x, rect.top + origin.y),
+            None,
+        );
+
+        let sigma = shadow.blur_sigma();
+
+        blur(&mut pixmap, sigma, sigma);
+
+        target.draw_pixmap(
+            area.x(),
+            area.y(),
+            pixmap.as_ref(),
+            &PixmapPaint::default(),
+            Transform::identity(),
+            None,
+        );
+    }
+
+    fn draw_texture(
+        &mut self,
+        transform: Transform,
+        rect: Rect,
+        shape: &Shape,
+        texture: &Texture,
+        tex_bounds: Bounds,
+    ) {
+        let Some(image) = convert::to_pixmap(texture) else {
+            tracing::warn!("textures must be decoded before they can be drawn");
+            return;
+        };
+
+        let (image_width, image_height) = (image.width() as f32, image.height() as f32);
+
+        let source_width = image_width * (tex_bounds.right - tex_bounds.left);
+        let source_height = image_height * (tex_bounds.bottom - tex_bounds.top);
+
+        if source_width <= 0.0 || source_height <= 0.0 {
+            return;
+        }
+
+        let Some(path) = convert::to_path(shape, rect.width, rect.height) else {
+            return;
+        };
+
+        // Maps the selected region of the texture onto the rect
+        let pattern_transform =
+            Transform::from_scale(rect.width / source_width, rect.height / source_height)
+                .pre_translate(
+                    -image_width * tex_bounds.left,
+                    -image_height * tex_bounds.top,
+                );
+
+        let paint = tiny_skia::Paint {
+            shader: Pattern::new(
+                image.as_ref(),
+                SpreadMode::Pad,
+                FilterQuality::Bilinear,
+                1.0,
+                pattern_transform,
+            ),
+            anti_alias: true,
+
+            ..tiny_skia::Paint::default()
+        };
+
+        self.target().fill_path(
+            &path,
+            &paint,
+            tiny_skia::FillRule::Winding,
+            transform.pre_translate(rect.left, rect.top),
+            None,
+        );
+    }
+
+    /// Lays out the text within its rect, then draws it the same way as glyphs that were
+    /// laid out ahead of time.
+    fn draw_text(
+        &mut self,
+        transform: Transform,
+        paint: &Paint,
+        rect: Rect,
+        text_style: &TextStyle,
+        text: &str,
+    ) {
+        let paragraph = agui_text::layout_paragraph(
+            self.fonts,
+            text_style,
+            text,
+            Constraints::tight(Size::from(rect)),
+        );
+
+        let runs = paragraph.glyph_runs().cloned().collect::<Vec<_>>();
+
+        self.draw_glyphs(transform, paint, rect, &runs);
+    }
+
+    fn draw_glyphs(&mut self, transform: Transform, paint: &Paint, rect: Rect, runs: &[GlyphRun]) {
+        let mut builder = tiny_skia::PathBuilder::new();
+
+        for run in runs {
+            let Some(data) = self.fonts.get_or_insert(&run.font) else {
+                continue;
+            };
+
+            let Ok(face) = ttf_parser::Face::parse(data, run.font.index()) else {
+                continue;
+            };
+
+            let scale = run.font_size / face.units_per_em() as f32;
+
+            for glyph in &run.glyphs {
+                face.outline_glyph(
+                    GlyphId(glyph.id),
+                    &mut GlyphOutliner {
+                        builder: &mut builder,
+
+                        scale,
+
+                        x: glyph.x,
+                        y: glyph.y,
+                    },
+                );
+            }
+        }
+
+        let Some(path) = builder.finish() else {
+            return;
+        };
+
+        // Text is unreadable without anti-aliasing, so it's always enabled
+        let paint = tiny_skia::Paint {
+            anti_alias: true,
+
+            ..convert::to_paint(paint)
+        };
+
+        self.target().fill_path(
+            &path,
+            &paint,
+            tiny_skia::FillRule::Winding,
+            transform.pre_translate(rect.left, rect.top),
+            None,
+        );
+    }
+}
+
+fn apply_filter(pixmap: &mut Pixmap, filter: LayerFilter) {
+    match filter {
+        LayerFilter::Blur { sigma_x, sigma_y } => blur(pixmap, sigma_x, sigma_y),
+
+        filter => tracing::warn!("unsupported layer filter: {:?}", filter),
+    }
+}
+
+/// Returns the area of the target covered by `rect` once transformed, or `None` if it
+/// doesn't overlap the target.
+fn bounds_in_target(target: &Pixmap, transform: Transform, rect: Rect) -> Option<IntRect> {
+    tiny_skia::Rect::from_xywh(rect.left, rect.top, rect.width, rect.height)?
+        .transform(transform)?
+        .round_out()?
+        .intersect(&IntRect::from_xywh(0, 0, target.width(), target.height())?)
+}
+
+#[cfg(test)]
+mod tests {
+    use agui_core::{
+        engine::rendering::mock::{text_style, DEJA_VU_SANS},
+        render::canvas::{
+            paint::Paint,
+            painter::{CanvasPainter, Head},
+            Canvas,
+        },
+        unit::{
+            BlendMode, Bounds, BoxShadow, Color, Font, GlyphRun, LayerFilter, Offset,
+            PositionedGlyph, Rect, Shape, Size, Texture,
+        },
+    };
+    use tiny_skia::{Pixmap, Transform};
+
+    use agui_text::TextFonts;
+
+    use super::Rasterizer;
+
+    const RED: Color = Color::from_rgb((1.0, 0.0, 0.0));
+    const BLUE: Color = Color::from_rgb((0.0, 0.0, 1.0));
+
+    fn draw(canvas: &Canvas) -> Pixmap {
+        let mut fonts = TextFonts::new();
+
+        let mut rasterizer = Rasterizer::new(
+            &mut fonts,
+            canvas.size.width as u32,
+            canvas.size.height as u32,
+        )
+        .unwrap();
+
+        rasterizer.begin_canvas(Transform::identity(), canvas);
+        rasterizer.end_canvas(canvas);
+
+        rasterizer.finish()
+    }
+
+    fn rgba(pixmap: &Pixmap, x: u32, y: u32) -> [u8; 4] {
+        let pixel = pixmap.pixel(x, y).unwrap().demultiply();
+
+        [pixel.red(), pixel.green(), pixel.blue(), pixel.alpha()]
+    }
+
+    #[test]
+    fn fills_shapes() {
+        let mut canvas = Canvas::new(Size::new(20.0, 20.0));
+
+        {
+            let mut painter = CanvasPainter::<Head<()>>::begin(&mut canvas);
+
+            let brush = painter.add_paint(Paint {
+                color: RED,
+                ..Paint::default()
+            });
+
+            painter.draw_rect_at(Rect::new(5.0, 5.0, 10.0, 10.0), &brush);
+        }
+
+        let pixmap = draw(&canvas);
+
+        assert_eq!(rgba(&pixmap, 10, 10), [255, 0, 0, 255]);
+        assert_eq!(rgba(&pixmap, 2, 2), [0, 0, 0, 0]);
+        assert_eq!(rgba(&pixmap, 16, 16), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn clips_layers_to_their_shape() {
+        let mut canvas = Canvas::new(Size::new(20.0, 20.0));
+
+        {
+            let mut painter = CanvasPainter::<Head<()>>::begin(&mut canvas);
+
+            let clip = painter.add_paint(Paint::default());
+
+            painter.layer(&clip, Shape::Circle, |mut canvas| {
+                let brush = canvas.add_paint(Paint {
+                    color: RED,
+                    ..Paint::default()
+                });
+
+                canvas.draw_rect(&brush);
+            });
+        }
+
+        let pixmap = draw(&canvas);
+
+        assert_eq!(rgba(&pixmap, 10, 10), [255, 0, 0, 255]);
+        assert_eq!(rgba(&pixmap, 1, 1), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn layers_are_offset() {
+        let mut canvas = Canvas::new(Size::new(20.0, 20.0));
+
+        {
+            let painter = CanvasPainter::<Head<()>>::begin(&mut canvas);
+
+            let mut painter = painter;
+            let clip = painter.add_paint(Paint::default());
+
+            painter.layer_at(
+                Rect::new(10.0, 10.0, 10.0, 10.0),
+                &clip,
+                Shape::Rect,
+                |mut canvas| {
+                    let brush = canvas.add_paint(Paint {
+                        color: RED,
+                        ..Paint::default()
+                    });
+
+                    canvas.draw_rect_at(Rect::new(0.0, 0.0, 5.0, 5.0), &brush);
+                },
+            );
+        }
+
+        let pixmap = draw(&canvas);
+
+        assert_eq!(rgba(&pixmap, 12, 12), [255, 0, 0, 255]);
+        assert_eq!(rgba(&pixmap, 2, 2), [0, 0, 0, 0]);
+        assert_eq!(rgba(&pixmap, 17, 17), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn applies_layer_opacity() {
+        let mut canvas = Canvas::new(Size::new(10.0, 10.0));
+
+        {
+            let mut painter = CanvasPainter::<Head<()>>::begin(&mut canvas);
+
+            let layer = painter.add_paint(Paint {
+                color: Color {
+                    alpha: 0.5,
+                    ..Color::default()
+                },
+                ..Paint::default()
+            });
+
+            painter.layer(&layer, Shape::Rect, |mut canvas| {
+                let brush = canvas.add_paint(Paint {
+                    color: RED,
+                    ..Paint::default()
+                });
+
+                canvas.draw_rect(&brush);
+            });
+        }
+
+        let [red, _, _, alpha] = rgba(&draw(&canvas), 5, 5);
+
+        assert_eq!(red, 255);
+        assert!((alpha as i32 - 128).abs() <= 1, "alpha was {}", alpha);
+    }
+
+    #[test]
+    fn applies_layer_blend_modes() {
+        let mut canvas = Canvas::new(Size::new(10.0, 10.0));
+
+        {
+            let mut painter = CanvasPainter::<Head<()>>::begin(&mut canvas);
+
+            let white = painter.add_paint(Paint::default());
+
+            let multiply = painter.add_paint(Paint {
+                blend_mode: BlendMode::Multiply,
+                ..Paint::default()
+            });
+
+            painter.draw_rect(&white);
+
+            painter.layer(&multiply, Shape::Rect, |mut canvas| {
+                let brush = canvas.add_paint(Paint {
+                    color: BLUE,
+                    ..Paint::default()
+                });
+
+                canvas.draw_rect(&brush);
+            });
+        }
+
+        assert_eq!(rgba(&draw(&canvas), 5, 5), [0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn children_draw_into_open_tail_layers() {
+        let mut parent = Canvas::new(Size::new(20.0, 20.0));
+
+        {
+            let mut painter = CanvasPainter::<Head<()>>::begin(&mut parent);
+
+            let clip = painter.add_paint(Paint::default());
+
+            painter.start_layer_at(Rect::new(0.0, 0.0, 10.0, 20.0), &clip, Shape::Rect);
+        }
+
+        let mut child = Canvas::new(Size::new(20.0, 20.0));
+
+        {
+            let mut painter = CanvasPainter::<Head<()>>::begin(&mut child);
+
+            let brush = painter.add_paint(Paint {
+                color: RED,
+                ..Paint::default()
+            });
+
+            painter.draw_rect(&brush);
+        }
+
+        let mut fonts = TextFonts::new();
+
+        let mut rasterizer = Rasterizer::new(&mut fonts, 20, 20).unwrap();
+
+        rasterizer.begin_canvas(Transform::identity(), &parent);
+
+        rasterizer.begin_canvas(Transform::identity(), &child);
+        rasterizer.end_canvas(&child);
+
+        rasterizer.end_canvas(&parent);
+
+        let pixmap = rasterizer.finish();
+
+        assert_eq!(rgba(&pixmap, 5, 5), [255, 0, 0, 255]);
+        assert_eq!(rgba(&pixmap, 15, 5), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn blurs_filtered_layers() {
+        let mut canvas = Canvas::new(Size::new(40.0, 20.0));
+
+        {
+            let mut painter = CanvasPainter::<Head<()>>::begin(&mut canvas);
+
+            let layer = painter.add_paint(Paint::default());
+
+            let mut painter =
+                painter.start_filtered_layer(&layer, Shape::Rect, LayerFilter::blur(2.0));
+
+            let brush = painter.add_paint(Paint {
+                color: RED,
+                ..Paint::default()
+            });
+
+            painter.draw_rect_at(Rect::new(10.0, 0.0, 20.0, 20.0), &brush);
+        }
+
+        let pixmap = draw(&canvas);
+
+        assert_eq!(rgba(&pixmap, 20, 10)[3], 255);
+        assert!(rgba(&pixmap, 31, 10)[3] > 0);
+        assert!(rgba(&pixmap, 31, 10)[3] < 255);
+    }
+
+    #[test]
+    fn filtered_layers_spread_beyond_their_shape() {
+        let mut canvas = Canvas::new(Size::new(40.0, 20.0));
+
+        {
+            let mut painter = CanvasPainter::<Head<()>>::begin(&mut canvas);
+
+            let layer = painter.add_paint(Paint::default());
+
+            let mut painter = painter.start_filtered_layer_at(
+                Rect::new(10.0, 0.0, 10.0, 20.0),
+                &layer,
+                Shape::Rect,
+                LayerFilter::blur(1.0),
+            );
+
+            let brush = painter.add_paint(Paint {
+                color: RED,
+                ..Paint::default()
+            });
+
+            painter.draw_rect_at(Rect::new(-10.0, 0.0, 50.0, 20.0), &brush);
+        }
+
+        let pixmap = draw(&canvas);
+
+        assert_eq!(rgba(&pixmap, 15, 10)[3], 255);
+
+        // The blur spreads past the edge of the shape...
+        assert!(rgba(&pixmap, 20, 10)[3] > 0);
+
+        // ...but only what was drawn within the shape is blurred
+        assert_eq!(rgba(&pixmap, 25, 10)[3], 0);
+    }
+
+    #[test]
+    fn draws_blurred_shadows() {
+        let mut canvas = Canvas::new(Size::new(30.0, 30.0));
+
+        {
+            let mut painter = CanvasPainter::<Head<()>>::begin(&mut canvas);
+
+            painter.draw_shadow_at(
+                Rect::new(10.0, 10.0, 10.0, 10.0),
+                Shape::Rect,
+                BoxShadow::new(RED, Offset::new(2.0, 2.0), 4.0, 0.0),
+            );
+        }
+
+        let pixmap = draw(&canvas);
+
+        assert!(rgba(&pixmap, 17, 17)[3] > 200);
+        assert!(rgba(&pixmap, 23, 23)[3] > 0);
+        assert_eq!(rgba(&pixmap, 0, 0)[3], 0);
+    }
+
+    #[test]
+    fn draws_textures() {
+        let mut canvas = Canvas::new(Size::new(4.0, 2.0));
+
+        let texture = Texture::from_rgba8(2, 1, vec![255, 0, 0, 255, 0, 0, 255, 255]);
+
+        {
+            let mut painter = CanvasPainter::<Head<()>>::begin(&mut canvas);
+
+            painter.draw_texture(
+                Shape::Rect,
+                texture,
+                Bounds {
+                    top: 0.0,
+                    right: 1.0,
+                    bottom: 1.0,
+                    left: 0.0,
+                },
+            );
+        }
+
+        let pixmap = draw(&canvas);
+
+        assert_eq!(rgba(&pixmap, 0, 0), [255, 0, 0, 255]);
+        assert_eq!(rgba(&pixmap, 3, 1), [0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn draws_text() {
+        let mut canvas = Canvas::new(Size::new(100.0, 30.0));
+
+        {
+            let mut painter = CanvasPainter::<Head<()>>::begin(&mut canvas);
+
+            let brush = painter.add_paint(Paint {
+                color: RED,
+                ..Paint::default()
+            });
+
+            painter.draw_text(&brush, text_style(24.0, RED), "agui");
+        }
+
+        let pixmap = draw(&canvas);
+
+        let covered = pixmap
+            .pixels()
+            .iter()
+            .filter(|pixel| pixel.alpha() > 0)
+            .count();
+
+        assert!(covered > 50, "only {} pixels were covered", covered);
+
+        assert!(pixmap
+            .pixels()
+            .iter()
+            .all(|pixel| pixel.alpha() == 0 || pixel.demultiply().red() == 255));
+    }
+
+    #[test]
+    fn draws_glyphs() {
+        let data = DEJA_VU_SANS.to_vec();
+
+        let face = ttf_parser::Face::parse(&data, 0).unwrap();
+
+        let scale = 24.0 / face.units_per_em() as f32;
+
+        let mut x = 0.0;
+
+        let glyphs = "agui"
+            .char_indices()
+            .map(|(cluster, ch)| {
+                let id = face.glyph_index(ch).unwrap();
+                let advance = face.glyph_hor_advance(id).unwrap() as f32 * scale;
+
+                let glyph = PositionedGlyph {
+                    id: id.0,
+                    cluster,
+
+                    x,
+                    y: 20.0,
+
+                    advance,
+                };
+
+                x += advance;
+
+                glyph
+            })
+            .collect::<Vec<_>>();
+
+        let mut canvas = Canvas::new(Size::new(100.0, 30.0));
+
+        {
+            let mut painter = CanvasPainter::<Head<()>>::begin(&mut canvas);
+
+            let brush = painter.add_paint(Paint {
+                color: RED,
+                ..Paint::default()
+            });
+
+            painter.draw_glyphs_at(
+                Rect::new(10.0, 0.0, 90.0, 30.0),
+                &brush,
+                vec![GlyphRun {
+                    font: Font::from_bytes(data.clone()),
+                    font_size: 24.0,
+
+                    range: 0..4,
+                    rtl: false,
+
+                    glyphs,
+                }],
+            );
+        }
+
+        let pixmap = draw(&canvas);
+
+        let covered = |columns: std::ops::Range<u32>| {
+            columns
+                .flat_map(|x| (0..30).map(move |y| (x, y)))
+                .filter(|(x, y)| rgba(&pixmap, *x, *y)[3] > 0)
+                .count()
+        };
+
+        // The glyphs are drawn from the left of the rect, on their baseline
+        assert_eq!(covered(0..10), 0, "glyphs were drawn outside of their rect");
+        assert!(covered(10..60) > 50, "the glyphs were not drawn");
+
+        assert!(
+            (26..30).all(|y| (0..100).all(|x| rgba(&pixmap, x, y)[3] == 0)),
+            "glyphs were drawn below their descent"
+        );
+
+        assert!(pixmap
+            .pixels()
+            .iter()
+            .all(|pixel| pixel.alpha() == 0 || pixel.demultiply().red() == 255));
+    }
+}
@@ -0,0 +1,125 @@
+use std::sync::Arc;
+
+use agui_core::{
+    engine::rendering::scene::{Scene, SceneChange, SceneVisitor},
+    render::canvas::Canvas,
+    unit::{Offset, Rect},
+};
+use agui_renderer::view::ViewScene;
+use agui_text::TextFonts;
+use parking_lot::Mutex;
+use tiny_skia::{Pixmap, Transform};
+
+use crate::render::rasterizer::Rasterizer;
+
+pub struct SoftwareScene {
+    fonts: Arc<Mutex<TextFonts>>,
+
+    scene: Scene,
+
+    needs_redraw: bool,
+
+    /// The number of pixels drawn for each logical pixel.
+    device_pixel_ratio: f32,
+
+    /// The most recently drawn frame, or `None` if nothing has been drawn yet.
+    pixmap: Option<Pixmap>,
+}
+
+impl SoftwareScene {
+    pub(crate) fn new(fonts: Arc<Mutex<TextFonts>>) -> Self {
+        Self {
+            fonts,
+
+            scene: Scene::default(),
+
+            needs_redraw: true,
+
+            device_pixel_ratio: 1.0,
+
+            pixmap: None,
+        }
+    }
+
+    pub(crate) fn set_device_pixel_ratio(&mut self, device_pixel_ratio: f32) {
+        if self.device_pixel_ratio == device_pixel_ratio {
+            return;
+        }
+
+        self.device_pixel_ratio = device_pixel_ratio;
+
+        self.needs_redraw = true;
+    }
+
+    /// Rasterizes the scene, if anything visible has changed since it was last drawn.
+    pub(crate) fn redraw(&mut self) {
+        let size = self.scene.size().unwrap_or_default();
+
+        let damage = self.scene.take_damage();
+
+        // Changes that only affect what's outside of the view can't be seen
+        let damaged = damage.is_some_and(|damage| damage.intersects(Rect::from(size)));
+
+        if !self.needs_redraw && !damaged {
+            tracing::debug!("SoftwareScene::redraw: no visible changes, skipping");
+            return;
+        }
+
+        self.needs_redraw = false;
+
+        let scale = self.device_pixel_ratio;
+
+        let mut fonts = self.fonts.lock();
+
+        let Some(mut rasterizer) = Rasterizer::new(
+            &mut fonts,
+            (size.width * scale).ceil() as u32,
+            (size.height * scale).ceil() as u32,
+        ) else {
+            self.pixmap = None;
+            return;
+        };
+
+        self.scene.visit(&mut ScaledRasterizer {
+            rasterizer: &mut rasterizer,
+            scale,
+        });
+
+        self.pixmap = Some(rasterizer.finish());
+    }
+
+    /// Returns the most recently drawn frame.
+    pub(crate) fn pixmap(&self) -> Option<&Pixmap> {
+        self.pixmap.as_ref()
+    }
+}
+
+impl ViewScene for SoftwareScene {
+    fn apply(&mut self, change: SceneChange) {
+        self.scene.apply(change);
+    }
+
+    fn sync(&mut self) {
+        self.redraw();
+    }
+}
+
+/// Draws each object of a scene at its origin, scaled by the device pixel ratio.
+struct ScaledRasterizer<'a, 'fonts> {
+    rasterizer: &'a mut Rasterizer<'fonts>,
+
+    scale: f32,
+}
+
+impl SceneVisitor for ScaledRasterizer<'_, '_> {
+    fn begin_canvas(&mut self, origin: Offset, canvas: &Canvas) {
+        self.rasterizer.begin_canvas(
+            Transform::from_scale(self.scale, self.scale).pre_translate(origin.x, origin.y),
+            canvas,
+        );
+    }
+
+    fn end_canvas(&mut self, canvas: &Canvas) {
+        self.rasterizer.end_canvas(canvas);
+    }
+}
@@ -0,0 +1,70 @@
+use std::{error::Error, sync::Arc};
+
+use agui_renderer::{BindRenderer, FrameNotifier, Renderer};
+use parking_lot::Mutex;
+
+use crate::{frame::Frame, view::SoftwareViewHandle};
+
+/// Holds the most recent frame rendered by a [`SoftwareHeadlessRenderer`].
+///
+/// Clones share the same frame, so one can be bound to a renderer while another is used
+/// to read the result.
+#[derive(Debug, Default, Clone)]
+pub struct FrameBuffer {
+    frame: Arc<Mutex<Option<Frame>>>,
+}
+
+impl FrameBuffer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the most recently rendered frame, or `None` if nothing has been rendered.
+    pub fn frame(&self) -> Option<Frame> {
+        self.frame.lock().clone()
+    }
+}
+
+/// Renders a view into a [`FrameBuffer`] rather than a window.
+#[derive(Clone)]
+pub struct SoftwareHeadlessRenderer {
+    view_handle: SoftwareViewHandle,
+}
+
+impl SoftwareHeadlessRenderer {
+    pub fn new(view_handle: &SoftwareViewHandle) -> Self {
+        Self {
+            view_handle: view_handle.clone(),
+        }
+    }
+}
+
+impl BindRenderer<FrameBuffer> for SoftwareHeadlessRenderer {
+    async fn bind(
+        self,
+        target: &FrameBuffer,
+        frame_notifier: FrameNotifier,
+    ) -> Result<Box<dyn Renderer>, Box<dyn Error + Send + Sync>> {
+        self.view_handle.set_frame_notifier(frame_notifier);
+
+        Ok(Box::new(BoundSoftwareHeadlessRenderer {
+            view_handle: self.view_handle,
+
+            target: target.clone(),
+        }))
+    }
+}
+
+struct BoundSoftwareHeadlessRenderer {
+    view_handle: SoftwareViewHandle,
+
+    target: FrameBuffer,
+}
+
+impl Renderer for BoundSoftwareHeadlessRenderer {
+    fn render(&mut self) {
+        tracing::trace!("SoftwareHeadlessRenderer::render");
+
+        *self.target.frame.lock() = self.view_handle.snapshot();
+    }
+}
@@ -0,0 +1,31 @@
+use std::sync::Arc;
+
+use agui_text::TextFonts;
+use parking_lot::Mutex;
+
+use crate::view::{self, SoftwareView, SoftwareViewHandle};
+
+pub mod headless;
+#[cfg(feature = "window")]
+pub mod window;
+
+#[derive(Default, Clone)]
+pub struct SoftwareRenderer {
+    fonts: Arc<Mutex<TextFonts>>,
+}
+
+impl SoftwareRenderer {
+    pub fn new() -> SoftwareRenderer {
+        Self::default()
+    }
+
+    pub(crate) fn new_view(&self) -> (SoftwareView, SoftwareViewHandle) {
+        view::new_view(Arc::clone(&self.fonts))
+    }
+}
+
+impl PartialEq for SoftwareRenderer {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.fonts, &other.fonts)
+    }
+}
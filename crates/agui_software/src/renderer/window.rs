@@ -0,0 +1,233 @@
+use std::{
+    error::Error,
+    num::{NonZeroIsize, NonZeroU32},
+    ptr::NonNull,
+};
+
+use agui_renderer::{BindRenderer, FrameNotifier, Renderer};
+use raw_window_handle::{
+    HasRawDisplayHandle, HasRawWindowHandle, RawDisplayHandle, RawWindowHandle,
+};
+use raw_window_handle_06 as rwh06;
+use softbuffer::{Context, Surface};
+
+use crate::view::SoftwareViewHandle;
+
+#[derive(Clone)]
+pub struct SoftwareWindowRenderer {
+    view_handle: SoftwareViewHandle,
+}
+
+impl SoftwareWindowRenderer {
+    pub fn new(view_handle: &SoftwareViewHandle) -> Self {
+        Self {
+            view_handle: view_handle.clone(),
+        }
+    }
+}
+
+impl<T> BindRenderer<T> for SoftwareWindowRenderer
+where
+    T: HasRawWindowHandle + HasRawDisplayHandle,
+{
+    async fn bind(
+        self,
+        target: &T,
+        frame_notifier: FrameNotifier,
+    ) -> Result<Box<dyn Renderer>, Box<dyn Error + Send + Sync>> {
+        let handles = WindowHandles::new(target)?;
+
+        let context =
+            Context::new(handles).map_err(|err| SoftwareBindError::Context(err.to_string()))?;
+
+        let surface = Surface::new(&context, handles)
+            .map_err(|err| SoftwareBindError::Surface(err.to_string()))?;
+
+        self.view_handle.set_frame_notifier(frame_notifier);
+
+        Ok(Box::new(BoundSoftwareWindowRenderer {
+            view_handle: self.view_handle,
+
+            _context: context,
+            surface,
+
+            surface_size: None,
+        }))
+    }
+}
+
+struct BoundSoftwareWindowRenderer {
+    view_handle: SoftwareViewHandle,
+
+    _context: Context<WindowHandles>,
+    surface: Surface<WindowHandles, WindowHandles>,
+
+    surface_size: Option<(NonZeroU32, NonZeroU32)>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SoftwareBindError {
+    #[error("the window system is not supported")]
+    UnsupportedWindow,
+
+    #[error("failed to create the context: {0}")]
+    Context(String),
+
+    #[error("failed to create surface: {0}")]
+    Surface(String),
+}
+
+impl Renderer for BoundSoftwareWindowRenderer {
+    fn render(&mut self) {
+        tracing::trace!("SoftwareWindowRenderer::render");
+
+        let surface = &mut self.surface;
+        let surface_size = &mut self.surface_size;
+
+        self.view_handle.with_scene(|scene| {
+            let Some(pixmap) = scene.pixmap() else {
+                tracing::warn!("scene has not been drawn, skipping render");
+                return;
+            };
+
+            let (Some(width), Some(height)) = (
+                NonZeroU32::new(pixmap.width()),
+                NonZeroU32::new(pixmap.height()),
+            ) else {
+                return;
+            };
+
+            if *surface_size != Some((width, height)) {
+                if let Err(err) = surface.resize(width, height) {
+                    tracing::error!("failed to resize surface: {}", err);
+                    return;
+                }
+
+                *surface_size = Some((width, height));
+            }
+
+            let mut buffer = match surface.buffer_mut() {
+                Ok(buffer) => buffer,
+                Err(err) => {
+                    tracing::error!("failed to get surface buffer: {}", err);
+                    return;
+                }
+            };
+
+            // The pixmap is premultiplied, so dropping the alpha composites it over black
+            for (out, pixel) in buffer.iter_mut().zip(pixmap.pixels()) {
+                *out =
+                    (pixel.red() as u32) << 16 | (pixel.green() as u32) << 8 | pixel.blue() as u32;
+            }
+
+            if let Err(err) = buffer.present() {
+                tracing::error!("failed to present surface buffer: {}", err);
+            }
+        });
+    }
+}
+
+/// The raw handles of a window, converted to the version of `raw-window-handle` used by
+/// `softbuffer`.
+///
+/// These are borrowed from the window without a lifetime, so the window must outlive the
+/// renderer bound to it.
+#[derive(Clone, Copy)]
+struct WindowHandles {
+    window: rwh06::RawWindowHandle,
+    display: rwh06::RawDisplayHandle,
+}
+
+impl WindowHandles {
+    fn new<T>(target: &T) -> Result<Self, SoftwareBindError>
+    where
+        T: HasRawWindowHandle + HasRawDisplayHandle,
+    {
+        let window = match target.raw_window_handle() {
+            RawWindowHandle::Win32(handle) => {
+                let mut converted = rwh06::Win32WindowHandle::new(
+                    NonZeroIsize::new(handle.hwnd as isize)
+                        .ok_or(SoftwareBindError::UnsupportedWindow)?,
+                );
+
+                converted.hinstance = NonZeroIsize::new(handle.hinstance as isize);
+
+                rwh06::RawWindowHandle::Win32(converted)
+            }
+
+            RawWindowHandle::Xlib(handle) => {
+                let mut converted = rwh06::XlibWindowHandle::new(handle.window);
+
+                converted.visual_id = handle.visual_id;
+
+                rwh06::RawWindowHandle::Xlib(converted)
+            }
+
+            RawWindowHandle::Xcb(handle) => {
+                let mut converted = rwh06::XcbWindowHandle::new(
+                    NonZeroU32::new(handle.window).ok_or(SoftwareBindError::UnsupportedWindow)?,
+                );
+
+                converted.visual_id = NonZeroU32::new(handle.visual_id);
+
+                rwh06::RawWindowHandle::Xcb(converted)
+            }
+
+            RawWindowHandle::Wayland(handle) => {
+                rwh06::RawWindowHandle::Wayland(rwh06::WaylandWindowHandle::new(
+                    NonNull::new(handle.surface).ok_or(SoftwareBindError::UnsupportedWindow)?,
+                ))
+            }
+
+            RawWindowHandle::AppKit(handle) => {
+                rwh06::RawWindowHandle::AppKit(rwh06::AppKitWindowHandle::new(
+                    NonNull::new(handle.ns_view).ok_or(SoftwareBindError::UnsupportedWindow)?,
+                ))
+            }
+
+            _ => return Err(SoftwareBindError::UnsupportedWindow),
+        };
+
+        let display = match target.raw_display_handle() {
+            RawDisplayHandle::Windows(_) => {
+                rwh06::RawDisplayHandle::Windows(rwh06::WindowsDisplayHandle::new())
+            }
+
+            RawDisplayHandle::Xlib(handle) => rwh06::RawDisplayHandle::Xlib(
+                rwh06::XlibDisplayHandle::new(NonNull::new(handle.display), handle.screen),
+            ),
+
+            RawDisplayHandle::Xcb(handle) => rwh06::RawDisplayHandle::Xcb(
+                rwh06::XcbDisplayHandle::new(NonNull::new(handle.connection), handle.screen),
+            ),
+
+            RawDisplayHandle::Wayland(handle) => {
+                rwh06::RawDisplayHandle::Wayland(rwh06::WaylandDisplayHandle::new(
+                    NonNull::new(handle.display).ok_or(SoftwareBindError::UnsupportedWindow)?,
+                ))
+            }
+
+            RawDisplayHandle::AppKit(_) => {
+                rwh06::RawDisplayHandle::AppKit(rwh06::AppKitDisplayHandle::new())
+            }
+
+            _ => return Err(SoftwareBindError::UnsupportedWindow),
+        };
+
+        Ok(Self { window, display })
+    }
+}
+
+impl rwh06::HasWindowHandle for WindowHandles {
+    fn window_handle(&self) -> Result<rwh06::WindowHandle<'_>, rwh06::HandleError> {
+        // SAFETY: the window outlives the renderer, which owns these handles
+        Ok(unsafe { rwh06::WindowHandle::borrow_raw(self.window) })
+    }
+}
+
+impl rwh06::HasDisplayHandle for WindowHandles {
+    fn display_handle(&self) -> Result<rwh06::DisplayHandle<'_>, rwh06::HandleError> {
+        // SAFETY: the window outlives the renderer, which owns these handles
+        Ok(unsafe { rwh06::DisplayHandle::borrow_raw(self.display) })
+    }
+}
@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use agui_renderer::{
+    view::{SceneView, SceneViewHandle},
+    FrameNotifier,
+};
+use agui_text::{ShapedTextLayout, TextFonts};
+use parking_lot::Mutex;
+
+use crate::{frame::Frame, render::SoftwareScene};
+
+pub type SoftwareView = SceneView<SoftwareScene>;
+
+#[derive(Clone)]
+pub struct SoftwareViewHandle {
+    handle: SceneViewHandle<SoftwareScene>,
+}
+
+pub(crate) fn new_view(fonts: Arc<Mutex<TextFonts>>) -> (SoftwareView, SoftwareViewHandle) {
+    let view = SceneView::new(
+        SoftwareScene::new(Arc::clone(&fonts)),
+        ShapedTextLayout::new(fonts),
+    );
+
+    let handle = SoftwareViewHandle {
+        handle: view.handle(),
+    };
+
+    (view, handle)
+}
+
+impl SoftwareViewHandle {
+    pub(crate) fn set_frame_notifier(&self, frame_notifier: FrameNotifier) {
+        self.handle.set_frame_notifier(frame_notifier);
+    }
+
+    pub(crate) fn with_scene<F, Ret>(&self, func: F) -> Ret
+    where
+        F: FnOnce(&SoftwareScene) -> Ret,
+    {
+        let scene = self.handle.scene();
+
+        func(&scene)
+    }
+
+    /// Sets the number of pixels drawn for each logical pixel. This takes effect the next
+    /// time the view is synced.
+    pub fn set_device_pixel_ratio(&self, device_pixel_ratio: f32) {
+        self.handle
+            .scene_mut()
+            .set_device_pixel_ratio(device_pixel_ratio);
+    }
+
+    /// Returns a copy of the most recently drawn frame, or `None` if nothing has been
+    /// drawn yet.
+    pub fn snapshot(&self) -> Option<Frame> {
+        self.with_scene(|scene| scene.pixmap().map(Frame::from_pixmap))
+    }
+}
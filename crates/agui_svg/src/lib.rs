@@ -0,0 +1,89 @@
+use std::{cell::RefCell, rc::Rc};
+
+use agui_core::{
+    unit::ViewConfiguration,
+    widget::{IntoWidget, Widget},
+};
+use agui_executor::{EngineExecutor, LocalEngineExecutor};
+use agui_primitives::sized_box::SizedBox;
+
+use crate::{create_view::CreateSvgView, renderer::SvgRenderer, view::SvgViewHandle};
+
+pub mod create_view;
+mod render;
+pub mod renderer;
+pub mod view;
+
+/// Builds the widget in a view of the configured size, and returns it as a standalone SVG
+/// document.
+///
+/// Text is written as the outlines of its glyphs, so the document looks the same without
+/// its fonts installed, and textures are inlined as data URIs. The document is scaled by the configuration's
+/// device pixel ratio.
+pub fn render_to_svg(widget: impl IntoWidget, config: ViewConfiguration) -> String {
+    let child = widget.into_widget();
+
+    let view_handle = Rc::new(RefCell::new(None::<SvgViewHandle>));
+
+    let root = CreateSvgView {
+        renderer: SvgRenderer::new(),
+
+        builder: {
+            let view_handle = Rc::clone(&view_handle);
+
+            move |handle: &SvgViewHandle| -> Widget {
+                handle.set_device_pixel_ratio(config.device_pixel_ratio);
+
+                view_handle.borrow_mut().replace(handle.clone());
+
+                SizedBox {
+                    width: Some(config.size.width),
+                    height: Some(config.size.height),
+
+                    child: Some(child.clone()),
+                }
+                .into_widget()
+            }
+        },
+    };
+
+    let mut executor = LocalEngineExecutor::with_root(root).expect("failed to build tree");
+
+    executor.run_until_stalled();
+
+    let view_handle = view_handle
+        .borrow_mut()
+        .take()
+        .expect("the view was never created");
+
+    view_handle.document()
+}
+
+#[cfg(test)]
+mod tests {
+    use agui_core::unit::{Color, Size, ViewConfiguration};
+    use agui_primitives::colored_box::ColoredBox;
+
+    use super::render_to_svg;
+
+    #[test]
+    fn renders_widgets() {
+        let document = render_to_svg(
+            ColoredBox {
+                color: Color::from_rgb((1.0, 0.0, 0.0)),
+
+                child: None,
+            },
+            ViewConfiguration {
+                size: Size::new(100.0, 50.0),
+                device_pixel_ratio: 2.0,
+            },
+        );
+
+        assert!(document.starts_with(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="100" viewBox="0 0 100 50">"#
+        ));
+
+        assert!(document.contains(r##"width="100" height="50" fill="#ff0000""##));
+    }
+}
@@ -0,0 +1,304 @@
+use std::fmt::Write;
+
+use agui_core::unit::{
+    BlendMode, Color, FillRule, GradientExtend, Rect, Shape, StrokeCap, StrokeJoin, StrokeStyle,
+};
+use lyon::{
+    geom::point,
+    path::{path::Builder, Event},
+};
+
+/// Formats a number for use in an attribute, dropping any unnecessary precision.
+pub(crate) fn num(value: f32) -> String {
+    let value = (value * 1000.0).round() / 1000.0;
+
+    // Avoid writing `-0`
+    if value == 0.0 {
+        return String::from("0");
+    }
+
+    value.to_string()
+}
+
+/// Returns the color as a `#rrggbb` hex code, and its opacity.
+pub(crate) fn color(color: Color) -> (String, f32) {
+    let channel = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+
+    (
+        format!(
+            "#{:02x}{:02x}{:02x}",
+            channel(color.red),
+            channel(color.green),
+            channel(color.blue)
+        ),
+        color.alpha.clamp(0.0, 1.0),
+    )
+}
+
+/// Returns the opening of the element that draws `shape` within `rect`, to which any
+/// further attributes may be appended. Returns `None` if the shape is empty.
+pub(crate) fn shape_element(shape: &Shape, rect: Rect) -> Option<String> {
+    match shape {
+        Shape::Rect => {
+            if rect.width <= 0.0 || rect.height <= 0.0 {
+                return None;
+            }
+
+            Some(format!(
+                r#"<rect x="{}" y="{}" width="{}" height="{}""#,
+                num(rect.left),
+                num(rect.top),
+                num(rect.width),
+                num(rect.height)
+            ))
+        }
+
+        Shape::Path(_) => path_element(&shape.build_path(rect)),
+
+        shape => {
+            if rect.width <= 0.0 || rect.height <= 0.0 {
+                return None;
+            }
+
+            path_element(&shape.build_path(rect))
+        }
+    }
+}
+
+fn path_element(path: &lyon::path::Path) -> Option<String> {
+    let data = path_data(path);
+
+    if data.is_empty() {
+        return None;
+    }
+
+    Some(format!(r#"<path d="{}""#, data))
+}
+
+pub(crate) fn path_data(path: &lyon::path::Path) -> String {
+    let mut data = String::new();
+
+    for event in path.iter() {
+        if !data.is_empty() {
+            data.push(' ');
+        }
+
+        match event {
+            Event::Begin { at } => {
+                let _ = write!(data, "M{} {}", num(at.x), num(at.y));
+            }
+
+            Event::Line { to, .. } => {
+                let _ = write!(data, "L{} {}", num(to.x), num(to.y));
+            }
+
+            Event::Quadratic { ctrl, to, .. } => {
+                let _ = write!(
+                    data,
+                    "Q{} {} {} {}",
+                    num(ctrl.x),
+                    num(ctrl.y),
+                    num(to.x),
+                    num(to.y)
+                );
+            }
+
+            Event::Cubic {
+                ctrl1, ctrl2, to, ..
+            } => {
+                let _ = write!(
+                    data,
+                    "C{} {} {} {} {} {}",
+                    num(ctrl1.x),
+                    num(ctrl1.y),
+                    num(ctrl2.x),
+                    num(ctrl2.y),
+                    num(to.x),
+                    num(to.y)
+                );
+            }
+
+            Event::End { close, .. } => {
+                if close {
+                    data.push('Z');
+                } else {
+                    data.pop();
+                }
+            }
+        }
+    }
+
+    data
+}
+
+pub(crate) fn fill_rule(fill_rule: FillRule) -> &'static str {
+    match fill_rule {
+        FillRule::NonZero => "nonzero",
+        FillRule::EvenOdd => "evenodd",
+    }
+}
+
+pub(crate) fn spread_method(extend: GradientExtend) -> &'static str {
+    match extend {
+        GradientExtend::Pad => "pad",
+        GradientExtend::Repeat => "repeat",
+        GradientExtend::Reflect => "reflect",
+    }
+}
+
+/// Returns the attributes describing the stroke, excluding its paint.
+pub(crate) fn stroke_attributes(style: &StrokeStyle) -> String {
+    let mut attributes = format!(
+        r#" stroke-width="{}" stroke-linejoin="{}" stroke-linecap="{}" stroke-miterlimit="{}""#,
+        num(style.width),
+        match style.join {
+            StrokeJoin::Miter => "miter",
+            StrokeJoin::Round => "round",
+            StrokeJoin::Bevel => "bevel",
+        },
+        match style.cap {
+            StrokeCap::Butt => "butt",
+            StrokeCap::Round => "round",
+            StrokeCap::Square => "square",
+        },
+        num(style.miter_limit)
+    );
+
+    if style.is_dashed() {
+        let pattern = style
+            .dash_pattern
+            .iter()
+            .map(|length| num(*length))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let _ = write!(
+            attributes,
+            r#" stroke-dasharray="{}" stroke-dashoffset="{}""#,
+            pattern,
+            num(style.dash_offset)
+        );
+    }
+
+    attributes
+}
+
+/// Returns the CSS `mix-blend-mode` equivalent of the blend mode, or `None` if it's the
+/// default. Modes that CSS has no equivalent for are drawn normally.
+pub(crate) fn blend_mode(blend_mode: BlendMode) -> Option<&'static str> {
+    let mode = match blend_mode {
+        BlendMode::SourceOver => return None,
+
+        BlendMode::Plus => "plus-lighter",
+        BlendMode::Screen => "screen",
+        BlendMode::Overlay => "overlay",
+        BlendMode::Darken => "darken",
+        BlendMode::Lighten => "lighten",
+        BlendMode::ColorDodge => "color-dodge",
+        BlendMode::ColorBurn => "color-burn",
+        BlendMode::HardLight => "hard-light",
+        BlendMode::SoftLight => "soft-light",
+        BlendMode::Difference => "difference",
+        BlendMode::Exclusion => "exclusion",
+        BlendMode::Multiply => "multiply",
+        BlendMode::Hue => "hue",
+        BlendMode::Saturation => "saturation",
+        BlendMode::Color => "color",
+        BlendMode::Luminosity => "luminosity",
+
+        blend_mode => {
+            tracing::warn!("unsupported blend mode: {:?}", blend_mode);
+
+            return None;
+        }
+    };
+
+    Some(mode)
+}
+
+/// Converts glyph outlines into a path, flipping them so that they point downwards.
+pub(crate) struct GlyphOutliner<'a> {
+    pub builder: &'a mut Builder,
+
+    /// Converts from font units to pixels.
+    pub scale: f32,
+
+    /// The position of the glyph's origin, on its baseline.
+    pub x: f32,
+    pub y: f32,
+}
+
+impl GlyphOutliner<'_> {
+    fn point(&self, x: f32, y: f32) -> lyon::math::Point {
+        point(self.x + x * self.scale, self.y - y * self.scale)
+    }
+}
+
+impl ttf_parser::OutlineBuilder for GlyphOutliner<'_> {
+    fn move_to(&mut self, x: f32, y: f32) {
+        let at = self.point(x, y);
+
+        self.builder.begin(at);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let to = self.point(x, y);
+
+        self.builder.line_to(to);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let (ctrl, to) = (self.point(x1, y1), self.point(x, y));
+
+        self.builder.quadratic_bezier_to(ctrl, to);
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let (ctrl1, ctrl2, to) = (self.point(x1, y1), self.point(x2, y2), self.point(x, y));
+
+        self.builder.cubic_bezier_to(ctrl1, ctrl2, to);
+    }
+
+    fn close(&mut self) {
+        self.builder.end(true);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use agui_core::unit::{Color, Rect, Shape};
+
+    use super::{color, num, shape_element};
+
+    #[test]
+    fn formats_numbers() {
+        assert_eq!(num(1.0), "1");
+        assert_eq!(num(0.12345), "0.123");
+        assert_eq!(num(-0.0001), "0");
+    }
+
+    #[test]
+    fn formats_colors() {
+        assert_eq!(
+            color(Color::from_rgba((1.0, 0.5, 0.0, 0.25))),
+            (String::from("#ff8000"), 0.25)
+        );
+    }
+
+    #[test]
+    fn skips_empty_shapes() {
+        assert_eq!(
+            shape_element(&Shape::Rect, Rect::new(0.0, 0.0, 0.0, 10.0)),
+            None
+        );
+        assert_eq!(
+            shape_element(&Shape::Circle, Rect::new(0.0, 0.0, 10.0, 0.0)),
+            None
+        );
+
+        assert!(
+            shape_element(&Shape::Circle, Rect::new(0.0, 0.0, 10.0, 10.0))
+                .is_some_and(|element| element.starts_with("<path d=\"M"))
+        );
+    }
+}
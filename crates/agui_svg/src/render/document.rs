@@ -0,0 +1,955 @@
+use std::{fmt::Write, io::Cursor};
+
+use agui_core::{
+    asset::AssetResolver,
+    render::canvas::{command::CanvasCommand, paint::Paint, Canvas, CanvasLayer},
+    unit::{
+        Bounds, BoxShadow, Constraints, GlyphRun, Gradient, GradientKind, LayerFilter, Offset,
+        PaintingStyle, Rect, Shape, Size, TextStyle, Texture, TextureData, TextureId,
+    },
+};
+use agui_text::TextFonts;
+use base64::Engine;
+use image::ImageEncoder;
+use rustc_hash::FxHashMap;
+
+use crate::render::convert::{self, num, GlyphOutliner};
+
+/// Writes canvases into an SVG document.
+///
+/// Every element is positioned in document coordinates, so nothing relies on transforms
+/// being inherited. Layers are written as groups, which stay open while anything drawn
+/// within them is written.
+pub(crate) struct DocumentWriter<'fonts> {
+    fonts: &'fonts mut TextFonts,
+
+    defs: String,
+    body: String,
+
+    next_id: usize,
+
+    /// The encoded data URI of each texture, or `None` if it could not be encoded.
+    textures: FxHashMap<TextureId, Option<EncodedTexture>>,
+}
+
+struct EncodedTexture {
+    width: u32,
+    height: u32,
+
+    uri: String,
+}
+
+impl<'fonts> DocumentWriter<'fonts> {
+    pub fn new(fonts: &'fonts mut TextFonts) -> Self {
+        Self {
+            fonts,
+
+            defs: String::new(),
+            body: String::new(),
+
+            next_id: 0,
+
+            textures: FxHashMap::default(),
+        }
+    }
+
+    /// Completes the document. Its content is scaled to fit `width` by `height`.
+    pub fn finish(self, size: Size, width: f32, height: f32) -> String {
+        let mut document = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {} {}">"#,
+            num(width),
+            num(height),
+            num(size.width),
+            num(size.height)
+        );
+
+        if !self.defs.is_empty() {
+            document.push_str("<defs>");
+            document.push_str(&self.defs);
+            document.push_str("</defs>");
+        }
+
+        document.push_str(&self.body);
+        document.push_str("</svg>");
+
+        document
+    }
+
+    /// Writes the canvas, including any of its child layers. If the canvas has a tail,
+    /// its group is left open so that anything written afterwards is drawn within it
+    /// until [`end_canvas`](Self::end_canvas) is called.
+    pub fn begin_canvas(&mut self, origin: Offset, canvas: &Canvas) {
+        for command in &canvas.head {
+            self.write_command(origin, &canvas.paints, command);
+        }
+
+        for layer in &canvas.children {
+            let layer_origin = self.begin_layer(origin, &canvas.paints, layer);
+
+            self.begin_canvas(layer_origin, &layer.canvas);
+            self.end_canvas(&layer.canvas);
+
+            self.end_layer(layer);
+        }
+
+        if let Some(tail) = &canvas.tail {
+            let tail_origin = self.begin_layer(origin, &canvas.paints, tail);
+
+            self.begin_canvas(tail_origin, &tail.canvas);
+        }
+    }
+
+    /// Closes the groups that were left open by [`begin_canvas`](Self::begin_canvas).
+    pub fn end_canvas(&mut self, canvas: &Canvas) {
+        if let Some(tail) = &canvas.tail {
+            self.end_canvas(&tail.canvas);
+
+            self.end_layer(tail);
+        }
+    }
+
+    fn next_id(&mut self, prefix: &str) -> String {
+        self.next_id += 1;
+
+        format!("{}{}", prefix, self.next_id)
+    }
+
+    /// Opens the groups for a layer, returning the origin of its contents.
+    fn begin_layer(&mut self, origin: Offset, paints: &[Paint], layer: &CanvasLayer) -> Offset {
+        let paint = paints
+            .get(layer.style.paint_idx)
+            .cloned()
+            .unwrap_or_else(|| {
+                tracing::warn!("layer refers to a paint that does not exist");
+
+                Paint::default()
+            });
+
+        let bounds = layer.bounds().translate(origin);
+
+        let origin = origin + layer.offset;
+
+        let rect = Rect::new(
+            origin.x,
+            origin.y,
+            layer.canvas.size.width,
+            layer.canvas.size.height,
+        );
+
+        if layer.style.backdrop_filter.is_some() {
+            tracing::warn!("backdrop filters are not supported in svg documents");
+        }
+
+        self.body.push_str("<g");
+
+        let opacity = paint.color.alpha.clamp(0.0, 1.0);
+
+        if opacity < 1.0 {
+            let _ = write!(self.body, r#" opacity="{}""#, num(opacity));
+        }
+
+        if let Some(mode) = convert::blend_mode(paint.blend_mode) {
+            let _ = write!(self.body, r#" style="mix-blend-mode:{}""#, mode);
+        }
+
+        // A filter is applied before the clip of the same group, so the contents of a
+        // filtered layer are clipped within a group of their own, letting the filter spread
+        // them beyond the layer's shape
+        if let Some(filter) = layer.style.filter {
+            let filter_id = self.write_filter(bounds, filter);
+
+            let _ = write!(self.body, r#" filter="url(#{})"><g"#, filter_id);
+        }
+
+        match convert::shape_element(&layer.style.shape, rect) {
+            Some(element) => {
+                let clip_id = self.next_id("clip");
+
+                let _ = write!(
+                    self.defs,
+                    r#"<clipPath id="{}">{}/></clipPath>"#,
+                    clip_id, element
+                );
+
+                let _ = write!(self.body, r#" clip-path="url(#{})""#, clip_id);
+            }
+
+            // An empty shape hides the layer entirely
+            None => self.body.push_str(r#" display="none""#),
+        }
+
+        self.body.push('>');
+
+        origin
+    }
+
+    /// Closes the groups opened by [`begin_layer`](Self::begin_layer).
+    fn end_layer(&mut self, layer: &CanvasLayer) {
+        if layer.style.filter.is_some() {
+            self.body.push_str("</g>");
+        }
+
+        self.body.push_str("</g>");
+    }
+
+    /// Defines the filter in the document, covering `region`, and returns its ID.
+    fn write_filter(&mut self, region: Rect, filter: LayerFilter) -> String {
+        let filter_id = self.next_id("filter");
+
+        let _ = write!(
+            self.defs,
+            r#"<filter id="{}" filterUnits="userSpaceOnUse" x="{}" y="{}" width="{}" height="{}">"#,
+            filter_id,
+            num(region.left),
+            num(region.top),
+            num(region.width),
+            num(region.height)
+        );
+
+        match filter {
+            LayerFilter::Blur { sigma_x, sigma_y } => {
+                let _ = write!(
+                    self.defs,
+                    r#"<feGaussianBlur stdDeviation="{} {}"/>"#,
+                    num(sigma_x),
+                    num(sigma_y)
+                );
+            }
+
+            filter => tracing::warn!("unsupported layer filter: {:?}", filter),
+        }
+
+        self.defs.push_str("</filter>");
+
+        filter_id
+    }
+
+    fn write_command(&mut self, origin: Offset, paints: &[Paint], command: &CanvasCommand) {
+        match command {
+            CanvasCommand::Shape {
+                paint_idx,
+                rect,
+                shape,
+            } => {
+                let Some(paint) = paints.get(*paint_idx) else {
+                    tracing::warn!("shape refers to a paint that does not exist");
+                    return;
+                };
+
+                self.write_shape(paint, rect.translate(origin), shape);
+            }
+
+            CanvasCommand::Shadow {
+                rect,
+                shape,
+                shadow,
+            } => self.write_shadow(rect.translate(origin), shape, shadow),
+
+            CanvasCommand::Texture {
+                rect,
+                shape,
+                texture,
+                tex_bounds,
+            } => self.write_texture(rect.translate(origin), shape, texture, *tex_bounds),
+
+            CanvasCommand::Text {
+                paint_idx,
+                rect,
+                text_style,
+                text,
+            } => {
+                let Some(paint) = paints.get(*paint_idx) else {
+                    tracing::warn!("text refers to a paint that does not exist");
+                    return;
+                };
+
+                self.write_text(paint, rect.translate(origin), text_style, text);
+            }
+
+            CanvasCommand::Glyphs {
+                paint_idx,
+                rect,
+                runs,
+            } => {
+                let Some(paint) = paints.get(*paint_idx) else {
+                    tracing::warn!("glyphs refer to a paint that does not exist");
+                    return;
+                };
+
+                self.write_glyphs(paint, rect.translate(origin), runs);
+            }
+
+            cmd => {
+                tracing::error!("unknown command: {:?}", cmd);
+            }
+        }
+    }
+
+    /// Returns the attributes that apply the paint to an element whose shape-local
+    /// coordinates start at `origin`.
+    fn paint_attributes(&mut self, paint: &Paint, origin: Offset) -> String {
+        let fill = match &paint.gradient {
+            Some(gradient) => format!("url(#{})", self.write_gradient(gradient, origin)),
+
+            None => convert::color(paint.color).0,
+        };
+
+        // Gradients carry their own opacity in their stops
+        let opacity = match &paint.gradient {
+            Some(_) => 1.0,
+            None => paint.color.alpha.clamp(0.0, 1.0),
+        };
+
+        let mut attributes = match &paint.style {
+            PaintingStyle::Fill => {
+                let mut attributes = format!(
+                    r#" fill="{}" fill-rule="{}""#,
+                    fill,
+                    convert::fill_rule(paint.fill_rule)
+                );
+
+                if opacity < 1.0 {
+                    let _ = write!(attributes, r#" fill-opacity="{}""#, num(opacity));
+                }
+
+                attributes
+            }
+
+            PaintingStyle::Stroke(stroke) => {
+                let mut attributes = format!(r#" fill="none" stroke="{}""#, fill);
+
+                if opacity < 1.0 {
+                    let _ = write!(attributes, r#" stroke-opacity="{}""#, num(opacity));
+                }
+
+                attributes.push_str(&convert::stroke_attributes(stroke));
+
+                attributes
+            }
+        };
+
+        if !paint.anti_alias {
+            attributes.push_str(r#" shape-rendering="crispEdges""#);
+        }
+
+        if let Some(mode) = convert::blend_mode(paint.blend_mode) {
+            let _ = write!(attributes, r#" style="mix-blend-mode:{}""#, mode);
+        }
+
+        attributes
+    }
+
+    /// Defines the gradient in the document, positioned relative to `origin`, and returns
+    /// its ID.
+    fn write_gradient(&mut self, gradient: &Gradient, origin: Offset) -> String {
+        let gradient_id = self.next_id("gradient");
+
+        let spread = convert::spread_method(gradient.extend);
+
+        match gradient.kind {
+            GradientKind::Linear { start, end } => {
+                let _ = write!(
+                    self.defs,
+                    r#"<linearGradient id="{}" gradientUnits="userSpaceOnUse" spreadMethod="{}" x1="{}" y1="{}" x2="{}" y2="{}">"#,
+                    gradient_id,
+                    spread,
+                    num(origin.x + start.x),
+                    num(origin.y + start.y),
+                    num(origin.x + end.x),
+                    num(origin.y + end.y)
+                );
+            }
+
+            GradientKind::Radial {
+                center,
+                radius,
+                focal,
+            } => {
+                let _ = write!(
+                    self.defs,
+                    r#"<radialGradient id="{}" gradientUnits="userSpaceOnUse" spreadMethod="{}" cx="{}" cy="{}" r="{}" fx="{}" fy="{}">"#,
+                    gradient_id,
+                    spread,
+                    num(origin.x + center.x),
+                    num(origin.y + center.y),
+                    num(radius),
+                    num(origin.x + focal.x),
+                    num(origin.y + focal.y)
+                );
+            }
+        }
+
+        for stop in &gradient.stops {
+            let (color, opacity) = convert::color(stop.color);
+
+            let _ = write!(
+                self.defs,
+                r#"<stop offset="{}" stop-color="{}""#,
+                num(stop.offset),
+                color
+            );
+
+            if opacity < 1.0 {
+                let _ = write!(self.defs, r#" stop-opacity="{}""#, num(opacity));
+            }
+
+            self.defs.push_str("/>");
+        }
+
+        self.defs.push_str(match gradient.kind {
+            GradientKind::Linear { .. } => "</linearGradient>",
+            GradientKind::Radial { .. } => "</radialGradient>",
+        });
+
+        gradient_id
+    }
+
+    fn write_shape(&mut self, paint: &Paint, rect: Rect, shape: &Shape) {
+        let Some(element) = convert::shape_element(shape, rect) else {
+            return;
+        };
+
+        let attributes = self.paint_attributes(paint, rect.into());
+
+        let _ = write!(self.body, "{}{}/>", element, attributes);
+    }
+
+    fn write_shadow(&mut self, rect: Rect, shape: &Shape, shadow: &BoxShadow) {
+        let local_rect = Rect::new(0.0, 0.0, rect.width, rect.height);
+
+        let shadow_rect = shadow.shadow_rect(local_rect);
+
+        // Custom paths can't be spread, so they're only offset
+        let element = match shape {
+            Shape::Path(_) => convert::shape_element(
+                shape,
+                Rect {
+                    left: rect.left + shadow.offset.x,
+                    top: rect.top + shadow.offset.y,
+                    ..rect
+                },
+            ),
+
+            Shape::RoundedRect {
+                top_left,
+                top_right,
+                bottom_right,
+                bottom_left,
+            } => convert::shape_element(
+                &Shape::RoundedRect {
+                    top_left: (top_left + shadow.spread_radius).max(0.0),
+                    top_right: (top_right + shadow.spread_radius).max(0.0),
+                    bottom_right: (bottom_right + shadow.spread_radius).max(0.0),
+                    bottom_left: (bottom_left + shadow.spread_radius).max(0.0),
+                },
+                shadow_rect.translate(rect.into()),
+            ),
+
+            shape => convert::shape_element(shape, shadow_rect.translate(rect.into())),
+        };
+
+        let Some(element) = element else {
+            return;
+        };
+
+        let (color, opacity) = convert::color(shadow.color);
+
+        let _ = write!(self.body, r#"{} fill="{}""#, element, color);
+
+        if opacity < 1.0 {
+            let _ = write!(self.body, r#" fill-opacity="{}""#, num(opacity));
+        }
+
+        let sigma = shadow.blur_sigma();
+
+        if sigma > 0.0 {
+            let filter_id = self.write_filter(
+                shadow.bounds(local_rect).translate(rect.into()),
+                LayerFilter::blur(sigma),
+            );
+
+            let _ = write!(self.body, r#" filter="url(#{})""#, filter_id);
+        }
+
+        self.body.push_str("/>");
+    }
+
+    fn write_texture(&mut self, rect: Rect, shape: &Shape, texture: &Texture, tex_bounds: Bounds) {
+        let texture_id = texture.id();
+
+        if !self.textures.contains_key(&texture_id) {
+            let encoded = encode_texture(texture);
+
+            self.textures.insert(texture_id.clone(), encoded);
+        }
+
+        let Some(encoded) = self.textures.get(&texture_id).and_then(Option::as_ref) else {
+            return;
+        };
+
+        let (image_width, image_height) = (encoded.width as f32, encoded.height as f32);
+
+        let source_width = image_width * (tex_bounds.right - tex_bounds.left);
+        let source_height = image_height * (tex_bounds.bottom - tex_bounds.top);
+
+        if source_width <= 0.0 || source_height <= 0.0 || rect.width <= 0.0 || rect.height <= 0.0 {
+            return;
+        }
+
+        // The nested viewport maps the selected region of the texture onto the rect, and
+        // hides anything outside of it
+        let image = format!(
+            r#"<svg x="{}" y="{}" width="{}" height="{}" viewBox="{} {} {} {}" preserveAspectRatio="none"><image width="{}" height="{}" href="{}"/></svg>"#,
+            num(rect.left),
+            num(rect.top),
+            num(rect.width),
+            num(rect.height),
+            num(image_width * tex_bounds.left),
+            num(image_height * tex_bounds.top),
+            num(source_width),
+            num(source_height),
+            encoded.width,
+            encoded.height,
+            encoded.uri
+        );
+
+        if matches!(shape, Shape::Rect) {
+            self.body.push_str(&image);
+
+            return;
+        }
+
+        let Some(element) = convert::shape_element(shape, rect) else {
+            return;
+        };
+
+        let clip_id = self.next_id("clip");
+
+        let _ = write!(
+            self.defs,
+            r#"<clipPath id="{}">{}/></clipPath>"#,
+            clip_id, element
+        );
+
+        let _ = write!(
+            self.body,
+            r#"<g clip-path="url(#{})">{}</g>"#,
+            clip_id, image
+        );
+    }
+
+    /// Lays out the text within its rect, then writes it the same way as glyphs that were
+    /// laid out ahead of time.
+    fn write_text(&mut self, paint: &Paint, rect: Rect, text_style: &TextStyle, text: &str) {
+        let paragraph = agui_text::layout_paragraph(
+            self.fonts,
+            text_style,
+            text,
+            Constraints::tight(Size::from(rect)),
+        );
+
+        let runs = paragraph.glyph_runs().cloned().collect::<Vec<_>>();
+
+        self.write_glyphs(paint, rect, &runs);
+    }
+
+    /// Writes the glyphs as a single path of their outlines, since they refer to glyphs
+    /// of a specific font rather than to characters that a viewer could draw itself.
+    fn write_glyphs(&mut self, paint: &Paint, rect: Rect, runs: &[GlyphRun]) {
+        let mut builder = lyon::path::Path::builder();
+
+        for run in runs {
+            let Some(data) = self.fonts.get_or_insert(&run.font) else {
+                continue;
+            };
+
+            let Ok(face) = ttf_parser::Face::parse(data, run.font.index()) else {
+                continue;
+            };
+
+            let scale = run.font_size / face.units_per_em() as f32;
+
+            for glyph in &run.glyphs {
+                face.outline_glyph(
+                    ttf_parser::GlyphId(glyph.id),
+                    &mut GlyphOutliner {
+                        builder: &mut builder,
+
+                        scale,
+
+                        x: rect.left + glyph.x,
+                        y: rect.top + glyph.y,
+                    },
+                );
+            }
+        }
+
+        let data = convert::path_data(&builder.build());
+
+        if data.is_empty() {
+            return;
+        }
+
+        // Glyphs are filled like text, regardless of the paint's style
+        let paint = Paint {
+            anti_alias: true,
+            style: PaintingStyle::Fill,
+
+            ..paint.clone()
+        };
+
+        let attributes = self.paint_attributes(&paint, rect.into());
+
+        let _ = write!(self.body, r#"<path d="{}"{}/>"#, data, attributes);
+    }
+}
+
+/// Encodes the texture as a data URI, resolving it first if it refers to a URL.
+fn encode_texture(texture: &Texture) -> Option<EncodedTexture> {
+    let engine = base64::engine::general_purpose::STANDARD;
+
+    match texture.data() {
+        TextureData::Rgba8 {
+            width,
+            height,
+            pixels,
+        } => {
+            let mut png = Vec::new();
+
+            if let Err(err) = image::codecs::png::PngEncoder::new(&mut png).write_image(
+                pixels,
+                *width,
+                *height,
+                image::ColorType::Rgba8,
+            ) {
+                tracing::error!("failed to encode texture: {}", err);
+
+                return None;
+            }
+
+            Some(EncodedTexture {
+                width: *width,
+                height: *height,
+
+                uri: format!("data:image/png;base64,{}", engine.encode(png)),
+            })
+        }
+
+        TextureData::Bytes(bytes) => {
+            let format = match image::guess_format(bytes) {
+                Ok(format) => format,
+
+                Err(err) => {
+                    tracing::error!("failed to detect texture format: {}", err);
+
+                    return None;
+                }
+            };
+
+            let (width, height) = match image::io::Reader::with_format(Cursor::new(bytes), format)
+                .into_dimensions()
+            {
+                Ok(dimensions) => dimensions,
+
+                Err(err) => {
+                    tracing::error!("failed to read texture dimensions: {}", err);
+
+                    return None;
+                }
+            };
+
+            Some(EncodedTexture {
+                width,
+                height,
+
+                uri: format!(
+                    "data:{};base64,{}",
+                    format.to_mime_type(),
+                    engine.encode(bytes)
+                ),
+            })
+        }
+
+        TextureData::Url(_) => {
+            match futures::executor::block_on(AssetResolver::global().resolve_texture(texture)) {
+                Ok(resolved) => encode_texture(&resolved),
+
+                Err(err) => {
+                    tracing::error!("failed to load texture {:?}: {}", texture, err);
+
+                    None
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use agui_core::{
+        engine::rendering::mock::{text_style, DEJA_VU_SANS},
+        render::canvas::{
+            paint::Paint,
+            painter::{CanvasPainter, Head},
+            Canvas,
+        },
+        unit::{
+            Bounds, BoxShadow, Color, Font, GlyphRun, LayerFilter, Offset, PositionedGlyph, Rect,
+            Shape, Size, Texture,
+        },
+    };
+
+    use agui_text::TextFonts;
+
+    use super::DocumentWriter;
+
+    const RED: Color = Color::from_rgb((1.0, 0.0, 0.0));
+
+    fn write(canvas: &Canvas) -> String {
+        let mut fonts = TextFonts::new();
+
+        let mut writer = DocumentWriter::new(&mut fonts);
+
+        writer.begin_canvas(Offset::ZERO, canvas);
+        writer.end_canvas(canvas);
+
+        writer.finish(canvas.size, canvas.size.width, canvas.size.height)
+    }
+
+    #[test]
+    fn writes_shapes() {
+        let mut canvas = Canvas::new(Size::new(20.0, 20.0));
+
+        {
+            let mut painter = CanvasPainter::<Head<()>>::begin(&mut canvas);
+
+            let brush = painter.add_paint(Paint {
+                color: RED,
+                ..Paint::default()
+            });
+
+            painter.draw_rect_at(Rect::new(5.0, 5.0, 10.0, 10.0), &brush);
+        }
+
+        assert_eq!(
+            write(&canvas),
+            concat!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="20" viewBox="0 0 20 20">"#,
+                r##"<rect x="5" y="5" width="10" height="10" fill="#ff0000" fill-rule="nonzero" shape-rendering="crispEdges"/>"##,
+                "</svg>"
+            )
+        );
+    }
+
+    #[test]
+    fn clips_layers_to_their_shape() {
+        let mut canvas = Canvas::new(Size::new(20.0, 20.0));
+
+        {
+            let mut painter = CanvasPainter::<Head<()>>::begin(&mut canvas);
+
+            let clip = painter.add_paint(Paint {
+                color: Color {
+                    alpha: 0.5,
+                    ..Color::default()
+                },
+                ..Paint::default()
+            });
+
+            painter.layer_at(
+                Rect::new(10.0, 10.0, 10.0, 10.0),
+                &clip,
+                Shape::Circle,
+                |mut canvas| {
+                    let brush = canvas.add_paint(Paint {
+                        color: RED,
+                        ..Paint::default()
+                    });
+
+                    canvas.draw_rect_at(Rect::new(0.0, 0.0, 5.0, 5.0), &brush);
+                },
+            );
+        }
+
+        let document = write(&canvas);
+
+        assert!(document.contains(r#"<clipPath id="clip1"><path d="M20 15"#));
+        assert!(document.contains(r#"<g opacity="0.5" clip-path="url(#clip1)">"#));
+
+        // Layer contents are written in document coordinates
+        assert!(document.contains(r#"<rect x="10" y="10" width="5" height="5""#));
+    }
+
+    #[test]
+    fn children_write_into_open_tail_layers() {
+        let mut parent = Canvas::new(Size::new(20.0, 20.0));
+
+        {
+            let mut painter = CanvasPainter::<Head<()>>::begin(&mut parent);
+
+            let clip = painter.add_paint(Paint::default());
+
+            painter.start_filtered_layer(&clip, Shape::Rect, LayerFilter::blur(2.0));
+        }
+
+        let mut child = Canvas::new(Size::new(20.0, 20.0));
+
+        {
+            let mut painter = CanvasPainter::<Head<()>>::begin(&mut child);
+
+            let brush = painter.add_paint(Paint {
+                color: RED,
+                ..Paint::default()
+            });
+
+            painter.draw_rect(&brush);
+        }
+
+        let mut fonts = TextFonts::new();
+
+        let mut writer = DocumentWriter::new(&mut fonts);
+
+        writer.begin_canvas(Offset::ZERO, &parent);
+
+        writer.begin_canvas(Offset::new(5.0, 0.0), &child);
+        writer.end_canvas(&child);
+
+        writer.end_canvas(&parent);
+
+        let document = writer.finish(parent.size, 20.0, 20.0);
+
+        assert!(document.contains(
+            r#"<filter id="filter1" filterUnits="userSpaceOnUse" x="-6" y="-6" width="32" height="32"><feGaussianBlur stdDeviation="2 2"/></filter>"#
+        ));
+
+        // The contents are clipped to the shape within the filtered group
+        assert!(document.ends_with(concat!(
+            r#"<g filter="url(#filter1)"><g clip-path="url(#clip2)">"#,
+            r##"<rect x="5" y="0" width="20" height="20" fill="#ff0000" fill-rule="nonzero" shape-rendering="crispEdges"/>"##,
+            "</g></g></svg>"
+        )));
+    }
+
+    #[test]
+    fn writes_blurred_shadows() {
+        let mut canvas = Canvas::new(Size::new(30.0, 30.0));
+
+        {
+            let mut painter = CanvasPainter::<Head<()>>::begin(&mut canvas);
+
+            painter.draw_shadow_at(
+                Rect::new(10.0, 10.0, 10.0, 10.0),
+                Shape::Rect,
+                BoxShadow::new(RED, Offset::new(2.0, 2.0), 0.0, 1.0),
+            );
+        }
+
+        assert!(write(&canvas)
+            .contains(r##"<rect x="11" y="11" width="12" height="12" fill="#ff0000"/>"##));
+    }
+
+    #[test]
+    fn inlines_textures() {
+        let mut canvas = Canvas::new(Size::new(4.0, 2.0));
+
+        let texture = Texture::from_rgba8(2, 1, vec![255, 0, 0, 255, 0, 0, 255, 255]);
+
+        {
+            let mut painter = CanvasPainter::<Head<()>>::begin(&mut canvas);
+
+            painter.draw_texture(
+                Shape::Rect,
+                texture,
+                Bounds {
+                    top: 0.0,
+                    right: 0.5,
+                    bottom: 1.0,
+                    left: 0.0,
+                },
+            );
+        }
+
+        let document = write(&canvas);
+
+        assert!(document.contains(concat!(
+            r#"<svg x="0" y="0" width="4" height="2" viewBox="0 0 1 1" preserveAspectRatio="none">"#,
+            r#"<image width="2" height="1" href="data:image/png;base64,"#
+        )));
+    }
+
+    #[test]
+    fn writes_text_as_paths() {
+        let mut canvas = Canvas::new(Size::new(100.0, 30.0));
+
+        {
+            let mut painter = CanvasPainter::<Head<()>>::begin(&mut canvas);
+
+            let brush = painter.add_paint(Paint {
+                color: RED,
+                ..Paint::default()
+            });
+
+            // Scales the font's units to a hundredth of a pixel
+            painter.draw_text(&brush, text_style(20.48, RED), "l");
+        }
+
+        // The glyph sits on the first line's baseline, beneath the font's ascent
+        assert!(write(&canvas).contains(concat!(
+            r#"<path d="M1.93 3.45 L3.77 3.45 L3.77 19.01 L1.93 19.01 L1.93 3.45 Z""#,
+            r##" fill="#ff0000" fill-rule="nonzero"/>"##
+        )));
+    }
+
+    #[test]
+    fn writes_glyphs_as_paths() {
+        let data = DEJA_VU_SANS.to_vec();
+
+        let id = ttf_parser::Face::parse(&data, 0)
+            .unwrap()
+            .glyph_index('l')
+            .unwrap();
+
+        let mut canvas = Canvas::new(Size::new(100.0, 30.0));
+
+        {
+            let mut painter = CanvasPainter::<Head<()>>::begin(&mut canvas);
+
+            let brush = painter.add_paint(Paint {
+                color: RED,
+                ..Paint::default()
+            });
+
+            // Scales the font's units to a hundredth of a pixel
+            painter.draw_glyphs_at(
+                Rect::new(10.0, 5.0, 90.0, 25.0),
+                &brush,
+                vec![GlyphRun {
+                    font: Font::from_bytes(data),
+                    font_size: 20.48,
+
+                    range: 0..1,
+                    rtl: false,
+
+                    glyphs: vec![PositionedGlyph {
+                        id: id.0,
+                        cluster: 0,
+
+                        x: 0.0,
+                        y: 20.0,
+
+                        advance: 5.0,
+                    }],
+                }],
+            );
+        }
+
+        let document = write(&canvas);
+
+        assert!(!document.contains("<text"));
+
+        assert!(document.contains(concat!(
+            r#"<path d="M11.93 9.44 L13.77 9.44 L13.77 25 L11.93 25 L11.93 9.44 Z""#,
+            r##" fill="#ff0000" fill-rule="nonzero"/>"##
+        )));
+    }
+}
@@ -0,0 +1,5 @@
+mod convert;
+mod document;
+mod scene;
+
+pub use scene::*;
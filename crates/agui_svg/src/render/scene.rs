@@ -0,0 +1,59 @@
+use agui_core::{
+    engine::rendering::scene::{Scene, SceneChange, SceneVisitor},
+    render::canvas::Canvas,
+    unit::Offset,
+};
+use agui_renderer::view::ViewScene;
+use agui_text::TextFonts;
+
+use crate::render::document::DocumentWriter;
+
+pub struct SvgScene {
+    scene: Scene,
+
+    /// The number of pixels in the document for each logical pixel.
+    pub(crate) device_pixel_ratio: f32,
+}
+
+impl Default for SvgScene {
+    fn default() -> Self {
+        Self {
+            scene: Scene::default(),
+
+            device_pixel_ratio: 1.0,
+        }
+    }
+}
+
+impl SvgScene {
+    /// Writes the scene as a standalone SVG document.
+    pub(crate) fn to_document(&self, fonts: &mut TextFonts) -> String {
+        let size = self.scene.size().unwrap_or_default();
+
+        let mut writer = DocumentWriter::new(fonts);
+
+        self.scene.visit(&mut writer);
+
+        writer.finish(
+            size,
+            size.width * self.device_pixel_ratio,
+            size.height * self.device_pixel_ratio,
+        )
+    }
+}
+
+impl ViewScene for SvgScene {
+    fn apply(&mut self, change: SceneChange) {
+        self.scene.apply(change);
+    }
+}
+
+impl SceneVisitor for DocumentWriter<'_> {
+    fn begin_canvas(&mut self, origin: Offset, canvas: &Canvas) {
+        DocumentWriter::begin_canvas(self, origin, canvas);
+    }
+
+    fn end_canvas(&mut self, canvas: &Canvas) {
+        DocumentWriter::end_canvas(self, canvas);
+    }
+}
@@ -0,0 +1,29 @@
+use std::sync::Arc;
+
+use agui_text::TextFonts;
+use parking_lot::Mutex;
+
+use crate::view::{self, SvgView, SvgViewHandle};
+
+#[derive(Default, Clone)]
+pub struct SvgRenderer {
+    fonts: Arc<Mutex<TextFonts>>,
+}
+
+impl SvgRenderer {
+    pub fn new() -> SvgRenderer {
+        Self::default()
+    }
+
+    /// Creates a view that isn't attached to a widget tree, such as to replay a captured
+    /// scene into.
+    pub fn new_view(&self) -> (SvgView, SvgViewHandle) {
+        view::new_view(Arc::clone(&self.fonts))
+    }
+}
+
+impl PartialEq for SvgRenderer {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.fonts, &other.fonts)
+    }
+}
@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use agui_renderer::view::{SceneView, SceneViewHandle};
+use agui_text::{ShapedTextLayout, TextFonts};
+use parking_lot::Mutex;
+
+use crate::render::SvgScene;
+
+pub type SvgView = SceneView<SvgScene>;
+
+#[derive(Clone)]
+pub struct SvgViewHandle {
+    fonts: Arc<Mutex<TextFonts>>,
+
+    handle: SceneViewHandle<SvgScene>,
+}
+
+pub(crate) fn new_view(fonts: Arc<Mutex<TextFonts>>) -> (SvgView, SvgViewHandle) {
+    let view = SceneView::new(
+        SvgScene::default(),
+        ShapedTextLayout::new(Arc::clone(&fonts)),
+    );
+
+    let handle = SvgViewHandle {
+        fonts,
+
+        handle: view.handle(),
+    };
+
+    (view, handle)
+}
+
+impl SvgViewHandle {
+    /// Sets the number of pixels in the document for each logical pixel. The content is
+    /// scaled to fit, so this only affects the document's width and height.
+    pub fn set_device_pixel_ratio(&self, device_pixel_ratio: f32) {
+        self.handle.scene_mut().device_pixel_ratio = device_pixel_ratio;
+    }
+
+    /// Writes the most recently synced scene as a standalone SVG document.
+    pub fn document(&self) -> String {
+        let scene = self.handle.scene();
+
+        scene.to_document(&mut self.fonts.lock())
+    }
+}
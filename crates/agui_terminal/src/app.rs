@@ -0,0 +1,168 @@
+use std::{
+    cell::RefCell,
+    io::{self, Write},
+    rc::Rc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use agui_core::{
+    unit::Size,
+    widget::{IntoWidget, Widget},
+};
+use agui_executor::{EngineExecutor, LocalEngineExecutor};
+use agui_renderer::FrameNotifier;
+use agui_sync::watch;
+use crossterm::{
+    cursor,
+    event::{
+        self, DisableBracketedPaste, DisableFocusChange, DisableMouseCapture, EnableBracketedPaste,
+        EnableFocusChange, EnableMouseCapture, Event, KeyCode, KeyEventKind, KeyModifiers,
+    },
+    execute, queue,
+    terminal::{self, BeginSynchronizedUpdate, EndSynchronizedUpdate},
+};
+
+use crate::{
+    create_view::CreateTerminalView, input::EventTranslator, layout::TerminalLayout,
+    renderer::TerminalRenderer, view::TerminalViewHandle,
+};
+
+/// How long to wait for terminal events before checking for updates to the tree.
+const POLL_INTERVAL: Duration = Duration::from_millis(16);
+
+/// Runs the widget in the terminal until `Ctrl+C` is pressed.
+///
+/// The terminal is switched to its alternate screen for the duration, with mouse capture
+/// enabled, and is restored before returning.
+pub fn run(widget: impl IntoWidget) -> io::Result<()> {
+    run_with(TerminalRenderer::new(), widget)
+}
+
+/// Runs the widget in the terminal using the given renderer until `Ctrl+C` is pressed.
+pub fn run_with(renderer: TerminalRenderer, widget: impl IntoWidget) -> io::Result<()> {
+    let cell_size = renderer.cell_size();
+
+    let (size_tx, size_rx) = watch::channel(terminal_size(cell_size)?);
+
+    let frame_ready = Arc::new(AtomicBool::new(false));
+
+    let child = widget.into_widget();
+
+    let view_handle = Rc::new(RefCell::new(None::<TerminalViewHandle>));
+
+    let root = CreateTerminalView {
+        renderer,
+
+        builder: {
+            let frame_ready = Arc::clone(&frame_ready);
+            let view_handle = Rc::clone(&view_handle);
+
+            move |handle: &TerminalViewHandle| -> Widget {
+                handle.set_frame_notifier(FrameNotifier::new({
+                    let frame_ready = Arc::clone(&frame_ready);
+
+                    move || frame_ready.store(true, Ordering::Release)
+                }));
+
+                view_handle.borrow_mut().replace(handle.clone());
+
+                TerminalLayout::new(Some(size_rx.clone()), child.clone()).into_widget()
+            }
+        },
+    };
+
+    let mut executor = LocalEngineExecutor::with_root(root)
+        .map_err(|err| io::Error::other(format!("{:?}", err)))?;
+
+    let mut stdout = io::stdout();
+
+    terminal::enable_raw_mode()?;
+
+    execute!(
+        stdout,
+        terminal::EnterAlternateScreen,
+        EnableMouseCapture,
+        EnableFocusChange,
+        EnableBracketedPaste,
+        cursor::Hide
+    )?;
+
+    let result = (|| -> io::Result<()> {
+        let translator = EventTranslator::new(cell_size);
+
+        loop {
+            executor.run_until_stalled();
+
+            if frame_ready.swap(false, Ordering::Acquire) {
+                if let Some(grid) = view_handle.borrow().as_ref().and_then(|h| h.grid()) {
+                    queue!(stdout, BeginSynchronizedUpdate)?;
+
+                    grid.write_ansi(&mut stdout)?;
+
+                    queue!(stdout, EndSynchronizedUpdate)?;
+
+                    stdout.flush()?;
+                }
+            }
+
+            if !event::poll(POLL_INTERVAL)? {
+                continue;
+            }
+
+            // Handle every event that's already waiting before updating the tree again
+            loop {
+                let event = event::read()?;
+
+                match &event {
+                    Event::Key(key)
+                        if key.kind == KeyEventKind::Press
+                            && key.code == KeyCode::Char('c')
+                            && key.modifiers.contains(KeyModifiers::CONTROL) =>
+                    {
+                        return Ok(());
+                    }
+
+                    Event::Resize(..) => {
+                        size_tx.send(terminal_size(cell_size)?).ok();
+                    }
+
+                    _ => {}
+                }
+
+                for input_event in translator.translate(&event) {
+                    executor.dispatch_event(input_event);
+                }
+
+                if !event::poll(Duration::ZERO)? {
+                    break;
+                }
+            }
+        }
+    })();
+
+    execute!(
+        stdout,
+        cursor::Show,
+        DisableBracketedPaste,
+        DisableFocusChange,
+        DisableMouseCapture,
+        terminal::LeaveAlternateScreen
+    )?;
+
+    terminal::disable_raw_mode()?;
+
+    result
+}
+
+fn terminal_size(cell_size: Size) -> io::Result<Size> {
+    let (columns, rows) = terminal::size()?;
+
+    Ok(Size::new(
+        columns as f32 * cell_size.width,
+        rows as f32 * cell_size.height,
+    ))
+}
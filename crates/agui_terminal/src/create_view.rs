@@ -0,0 +1,66 @@
+use std::rc::Rc;
+
+use agui_core::{
+    element::{Element, ElementBuilder},
+    widget::{IntoWidget, Widget},
+};
+use agui_macros::WidgetProps;
+use agui_renderer::view::{ViewWidget, ViewWidgetElement};
+
+use crate::{
+    renderer::TerminalRenderer,
+    view::{TerminalView, TerminalViewHandle},
+};
+
+#[derive(WidgetProps)]
+pub struct CreateTerminalView<BuilderFn>
+where
+    BuilderFn: Fn(&TerminalViewHandle) -> Widget + Clone + 'static,
+{
+    pub renderer: TerminalRenderer,
+
+    pub builder: BuilderFn,
+}
+
+impl<BuilderFn> IntoWidget for CreateTerminalView<BuilderFn>
+where
+    BuilderFn: Fn(&TerminalViewHandle) -> Widget + Clone + 'static,
+{
+    fn into_widget(self) -> Widget {
+        Widget::new(self)
+    }
+}
+
+impl<BuilderFn> ElementBuilder for CreateTerminalView<BuilderFn>
+where
+    BuilderFn: Fn(&TerminalViewHandle) -> Widget + Clone + 'static,
+{
+    type Element = ViewWidgetElement<Self>;
+
+    fn create_element(self: Rc<Self>) -> Element
+    where
+        Self: Sized,
+    {
+        Element::new_view(ViewWidgetElement::new(self))
+    }
+}
+
+impl<BuilderFn> ViewWidget for CreateTerminalView<BuilderFn>
+where
+    BuilderFn: Fn(&TerminalViewHandle) -> Widget + Clone + 'static,
+{
+    type View = TerminalView;
+    type ViewHandle = TerminalViewHandle;
+
+    fn create_view(&self) -> (Self::View, Self::ViewHandle) {
+        self.renderer.new_view()
+    }
+
+    fn is_same_view(&self, other: &Self) -> bool {
+        self.renderer == other.renderer
+    }
+
+    fn build(&self, view_handle: &Self::ViewHandle) -> Widget {
+        (self.builder)(view_handle)
+    }
+}
@@ -0,0 +1,191 @@
+use std::{fmt::Write as _, io};
+
+/// A 24-bit color, as sent to the terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rgb {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+}
+
+impl Rgb {
+    pub const fn new(red: u8, green: u8, blue: u8) -> Self {
+        Self { red, green, blue }
+    }
+}
+
+/// A single character cell in the grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cell {
+    pub ch: char,
+
+    /// The color of the character, or `None` to use the terminal's default.
+    pub fg: Option<Rgb>,
+
+    /// The color behind the character, or `None` to use the terminal's default.
+    pub bg: Option<Rgb>,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self {
+            ch: ' ',
+
+            fg: None,
+            bg: None,
+        }
+    }
+}
+
+/// A grid of character cells that views are drawn onto.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid {
+    columns: usize,
+    rows: usize,
+
+    cells: Vec<Cell>,
+}
+
+impl Grid {
+    /// Creates an empty grid of the given size.
+    pub fn new(columns: usize, rows: usize) -> Self {
+        Self {
+            columns,
+            rows,
+
+            cells: vec![Cell::default(); columns * rows],
+        }
+    }
+
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Returns the cell at the given position, or `None` if it's outside of the grid.
+    pub fn get(&self, column: usize, row: usize) -> Option<&Cell> {
+        if column >= self.columns || row >= self.rows {
+            return None;
+        }
+
+        self.cells.get(row * self.columns + column)
+    }
+
+    /// Returns the cell at the given position, or `None` if it's outside of the grid.
+    pub fn get_mut(&mut self, column: usize, row: usize) -> Option<&mut Cell> {
+        if column >= self.columns || row >= self.rows {
+            return None;
+        }
+
+        self.cells.get_mut(row * self.columns + column)
+    }
+
+    /// Returns the characters in the given row, or `None` if it's outside of the grid.
+    pub fn row_text(&self, row: usize) -> Option<String> {
+        if row >= self.rows {
+            return None;
+        }
+
+        Some(
+            self.cells[row * self.columns..(row + 1) * self.columns]
+                .iter()
+                .map(|cell| cell.ch)
+                .collect(),
+        )
+    }
+
+    /// Returns the characters in the grid, without any colors, with each row on its own
+    /// line.
+    pub fn text(&self) -> String {
+        (0..self.rows)
+            .filter_map(|row| self.row_text(row))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Returns the escape sequences that draw the whole grid, starting from the top left
+    /// of the terminal.
+    pub fn to_ansi(&self) -> String {
+        let mut ansi = String::new();
+
+        let mut fg = None;
+        let mut bg = None;
+
+        for row in 0..self.rows {
+            let _ = write!(ansi, "\x1b[{};1H", row + 1);
+
+            for cell in &self.cells[row * self.columns..(row + 1) * self.columns] {
+                if cell.fg != fg {
+                    match cell.fg {
+                        Some(Rgb { red, green, blue }) => {
+                            let _ = write!(ansi, "\x1b[38;2;{};{};{}m", red, green, blue);
+                        }
+
+                        None => ansi.push_str("\x1b[39m"),
+                    }
+
+                    fg = cell.fg;
+                }
+
+                if cell.bg != bg {
+                    match cell.bg {
+                        Some(Rgb { red, green, blue }) => {
+                            let _ = write!(ansi, "\x1b[48;2;{};{};{}m", red, green, blue);
+                        }
+
+                        None => ansi.push_str("\x1b[49m"),
+                    }
+
+                    bg = cell.bg;
+                }
+
+                ansi.push(cell.ch);
+            }
+        }
+
+        ansi.push_str("\x1b[0m");
+
+        ansi
+    }
+
+    /// Draws the whole grid to the terminal.
+    pub fn write_ansi(&self, out: &mut impl io::Write) -> io::Result<()> {
+        out.write_all(self.to_ansi().as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Grid, Rgb};
+
+    #[test]
+    fn writes_text() {
+        let mut grid = Grid::new(3, 2);
+
+        grid.get_mut(1, 0).unwrap().ch = 'a';
+        grid.get_mut(2, 1).unwrap().ch = 'b';
+
+        assert!(grid.get_mut(3, 0).is_none());
+
+        assert_eq!(grid.text(), " a \n  b");
+    }
+
+    #[test]
+    fn only_writes_changed_colors() {
+        let mut grid = Grid::new(3, 1);
+
+        for column in 0..2 {
+            grid.get_mut(column, 0).unwrap().fg = Some(Rgb::new(255, 0, 0));
+        }
+
+        grid.get_mut(2, 0).unwrap().bg = Some(Rgb::new(0, 0, 255));
+
+        assert_eq!(
+            grid.to_ansi(),
+            "\x1b[1;1H\x1b[38;2;255;0;0m  \x1b[39m\x1b[48;2;0;0;255m \x1b[0m"
+        );
+    }
+}
@@ -0,0 +1,245 @@
+use std::time::Instant;
+
+use agui_core::{
+    input::{
+        InputEvent, Key, KeyEvent, Modifiers, NamedKey, PointerButton, PointerEvent,
+        PointerEventKind,
+    },
+    unit::{Offset, Size},
+};
+use crossterm::event::{
+    self as ct, Event, KeyCode, KeyEventKind, KeyModifiers, ModifierKeyCode, MouseButton,
+    MouseEventKind,
+};
+
+/// Translates terminal events into the input events understood by the engine.
+///
+/// Pointer positions are reported at the center of the cell the mouse is over.
+pub struct EventTranslator {
+    cell_size: Size,
+
+    start: Instant,
+}
+
+impl EventTranslator {
+    pub fn new(cell_size: Size) -> Self {
+        Self {
+            cell_size,
+
+            start: Instant::now(),
+        }
+    }
+
+    /// Returns the input events that correspond to the terminal event. Events that have no
+    /// equivalent, such as resizes, produce nothing.
+    pub fn translate(&self, event: &Event) -> Vec<InputEvent> {
+        match event {
+            Event::Key(event) => self.translate_key(event),
+            Event::Mouse(event) => self.translate_mouse(event).into_iter().collect(),
+
+            Event::Paste(text) => vec![InputEvent::Text(text.clone())],
+
+            Event::FocusGained => vec![InputEvent::FocusChanged(true)],
+            Event::FocusLost => vec![InputEvent::FocusChanged(false)],
+
+            Event::Resize(..) => Vec::new(),
+        }
+    }
+
+    fn translate_key(&self, event: &ct::KeyEvent) -> Vec<InputEvent> {
+        let Some(key) = translate_key_code(event.code) else {
+            return Vec::new();
+        };
+
+        let modifiers = translate_modifiers(event.modifiers);
+
+        let key_event = match event.kind {
+            KeyEventKind::Press => KeyEvent::pressed(key),
+
+            KeyEventKind::Repeat => KeyEvent {
+                repeat: true,
+
+                ..KeyEvent::pressed(key)
+            },
+
+            KeyEventKind::Release => KeyEvent::released(key),
+        }
+        .with_modifiers(modifiers);
+
+        let mut events = vec![InputEvent::Key(key_event)];
+
+        // Terminals don't report text separately from key presses, so it's inferred from
+        // any characters typed without a shortcut modifier
+        if key_event.is_pressed() && !modifiers.intersects(Modifiers::CONTROL | Modifiers::ALT) {
+            if let KeyCode::Char(ch) = event.code {
+                events.push(InputEvent::Text(ch.to_string()));
+            }
+        }
+
+        events
+    }
+
+    fn translate_mouse(&self, event: &ct::MouseEvent) -> Option<InputEvent> {
+        let position = Offset::new(
+            (event.column as f32 + 0.5) * self.cell_size.width,
+            (event.row as f32 + 0.5) * self.cell_size.height,
+        );
+
+        let (kind, button) = match event.kind {
+            MouseEventKind::Down(button) => (PointerEventKind::Down, Some(button)),
+            MouseEventKind::Up(button) => (PointerEventKind::Up, Some(button)),
+            MouseEventKind::Drag(button) => (PointerEventKind::Move, Some(button)),
+            MouseEventKind::Moved => (PointerEventKind::Move, None),
+
+            // Each step of the wheel scrolls by a single cell
+            MouseEventKind::ScrollDown => (self.scroll(0.0, 1.0), None),
+            MouseEventKind::ScrollUp => (self.scroll(0.0, -1.0), None),
+            MouseEventKind::ScrollRight => (self.scroll(1.0, 0.0), None),
+            MouseEventKind::ScrollLeft => (self.scroll(-1.0, 0.0), None),
+        };
+
+        let mut pointer_event = PointerEvent::new(kind, position)
+            .with_timestamp(self.start.elapsed())
+            .with_modifiers(translate_modifiers(event.modifiers));
+
+        if let Some(button) = button {
+            pointer_event = pointer_event.with_button(match button {
+                MouseButton::Left => PointerButton::Primary,
+                MouseButton::Right => PointerButton::Secondary,
+                MouseButton::Middle => PointerButton::Middle,
+            });
+        }
+
+        Some(InputEvent::Pointer(pointer_event))
+    }
+
+    fn scroll(&self, columns: f32, rows: f32) -> PointerEventKind {
+        PointerEventKind::Scroll {
+            delta: Offset::new(columns * self.cell_size.width, rows * self.cell_size.height),
+        }
+    }
+}
+
+fn translate_key_code(code: KeyCode) -> Option<Key> {
+    let named = match code {
+        KeyCode::Char(' ') => NamedKey::Space,
+        KeyCode::Char(ch) => return Some(Key::Character(ch)),
+
+        KeyCode::Enter => NamedKey::Enter,
+        KeyCode::Tab | KeyCode::BackTab => NamedKey::Tab,
+        KeyCode::Backspace => NamedKey::Backspace,
+        KeyCode::Delete => NamedKey::Delete,
+        KeyCode::Esc => NamedKey::Escape,
+        KeyCode::Insert => NamedKey::Insert,
+
+        KeyCode::Left => NamedKey::ArrowLeft,
+        KeyCode::Right => NamedKey::ArrowRight,
+        KeyCode::Up => NamedKey::ArrowUp,
+        KeyCode::Down => NamedKey::ArrowDown,
+
+        KeyCode::Home => NamedKey::Home,
+        KeyCode::End => NamedKey::End,
+        KeyCode::PageUp => NamedKey::PageUp,
+        KeyCode::PageDown => NamedKey::PageDown,
+
+        KeyCode::CapsLock => NamedKey::CapsLock,
+
+        KeyCode::Modifier(modifier) => match modifier {
+            ModifierKeyCode::LeftShift | ModifierKeyCode::RightShift => NamedKey::Shift,
+            ModifierKeyCode::LeftControl | ModifierKeyCode::RightControl => NamedKey::Control,
+            ModifierKeyCode::LeftAlt | ModifierKeyCode::RightAlt => NamedKey::Alt,
+            ModifierKeyCode::LeftSuper | ModifierKeyCode::RightSuper => NamedKey::Super,
+            _ => return None,
+        },
+
+        KeyCode::F(1) => NamedKey::F1,
+        KeyCode::F(2) => NamedKey::F2,
+        KeyCode::F(3) => NamedKey::F3,
+        KeyCode::F(4) => NamedKey::F4,
+        KeyCode::F(5) => NamedKey::F5,
+        KeyCode::F(6) => NamedKey::F6,
+        KeyCode::F(7) => NamedKey::F7,
+        KeyCode::F(8) => NamedKey::F8,
+        KeyCode::F(9) => NamedKey::F9,
+        KeyCode::F(10) => NamedKey::F10,
+        KeyCode::F(11) => NamedKey::F11,
+        KeyCode::F(12) => NamedKey::F12,
+
+        _ => return None,
+    };
+
+    Some(Key::Named(named))
+}
+
+fn translate_modifiers(modifiers: KeyModifiers) -> Modifiers {
+    let mut translated = Modifiers::empty();
+
+    translated.set(Modifiers::SHIFT, modifiers.contains(KeyModifiers::SHIFT));
+    translated.set(
+        Modifiers::CONTROL,
+        modifiers.contains(KeyModifiers::CONTROL),
+    );
+    translated.set(Modifiers::ALT, modifiers.contains(KeyModifiers::ALT));
+    translated.set(Modifiers::SUPER, modifiers.contains(KeyModifiers::SUPER));
+
+    translated
+}
+
+#[cfg(test)]
+mod tests {
+    use agui_core::{
+        input::{InputEvent, Key, KeyEvent, PointerButton, PointerEventKind},
+        unit::{Offset, Size},
+    };
+    use crossterm::event::{
+        Event, KeyCode, KeyEvent as CtKeyEvent, KeyModifiers, MouseButton, MouseEvent,
+        MouseEventKind,
+    };
+
+    use super::EventTranslator;
+
+    #[test]
+    fn typing_produces_text() {
+        let translator = EventTranslator::new(Size::new(1.0, 1.0));
+
+        let events = translator.translate(&Event::Key(CtKeyEvent::new(
+            KeyCode::Char('a'),
+            KeyModifiers::NONE,
+        )));
+
+        assert_eq!(
+            events,
+            vec![
+                InputEvent::Key(KeyEvent::pressed(Key::Character('a'))),
+                InputEvent::Text(String::from("a")),
+            ]
+        );
+
+        let events = translator.translate(&Event::Key(CtKeyEvent::new(
+            KeyCode::Char('c'),
+            KeyModifiers::CONTROL,
+        )));
+
+        assert_eq!(events.len(), 1);
+    }
+
+    #[test]
+    fn clicks_at_cell_centers() {
+        let translator = EventTranslator::new(Size::new(8.0, 16.0));
+
+        let events = translator.translate(&Event::Mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Right),
+            column: 2,
+            row: 1,
+            modifiers: KeyModifiers::NONE,
+        }));
+
+        let [InputEvent::Pointer(event)] = events.as_slice() else {
+            panic!("expected a single pointer event");
+        };
+
+        assert_eq!(event.kind, PointerEventKind::Down);
+        assert_eq!(event.button, PointerButton::Secondary);
+        assert_eq!(event.position, Offset::new(20.0, 24.0));
+    }
+}
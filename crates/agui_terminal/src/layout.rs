@@ -0,0 +1,135 @@
+use std::sync::Arc;
+
+use agui_core::{
+    element::{ContextDirtyRenderObject, RenderObjectCreateContext, RenderObjectUpdateContext},
+    render::object::{
+        RenderObjectImpl, RenderObjectIntrinsicSizeContext, RenderObjectLayoutContext,
+    },
+    task::{context::ContextSpawnRenderingTask, TaskHandle},
+    unit::{Constraints, IntrinsicDimension, Size},
+    widget::{IntoWidget, Widget},
+};
+use agui_elements::render::RenderObjectWidget;
+use agui_macros::RenderObjectWidget;
+use agui_sync::watch;
+use parking_lot::Mutex;
+
+/// Sizes its child to match the terminal, relaying it out whenever a new size is
+/// received.
+#[derive(RenderObjectWidget)]
+pub struct TerminalLayout {
+    size_rx: Option<watch::Receiver<Size>>,
+
+    child: Widget,
+}
+
+impl TerminalLayout {
+    /// Lays out `child` at the most recent size received from `size_rx`, or at zero size
+    /// until one is given.
+    pub fn new(size_rx: Option<watch::Receiver<Size>>, child: impl IntoWidget) -> Self {
+        Self {
+            size_rx,
+
+            child: child.into_widget(),
+        }
+    }
+}
+
+impl RenderObjectWidget for TerminalLayout {
+    type RenderObject = RenderTerminalLayout;
+
+    fn children(&self) -> Vec<Widget> {
+        vec![self.child.clone()]
+    }
+
+    fn create_render_object(&self, ctx: &mut RenderObjectCreateContext) -> Self::RenderObject {
+        RenderTerminalLayout::new(ctx, self.size_rx.clone())
+    }
+
+    fn update_render_object(
+        &self,
+        ctx: &mut RenderObjectUpdateContext,
+        render_object: &mut Self::RenderObject,
+    ) {
+        render_object.update_size_rx(ctx, self.size_rx.clone());
+    }
+}
+
+pub struct RenderTerminalLayout {
+    size: Arc<Mutex<Size>>,
+
+    size_task: Option<TaskHandle<()>>,
+}
+
+impl RenderTerminalLayout {
+    fn new(ctx: &mut RenderObjectCreateContext, size_rx: Option<watch::Receiver<Size>>) -> Self {
+        let mut ro = Self {
+            size: Arc::new(Mutex::new(Size::default())),
+
+            size_task: None,
+        };
+
+        ro.update_size_rx(ctx, size_rx);
+
+        ro
+    }
+
+    fn update_size_rx<C>(&mut self, ctx: &mut C, size_rx: Option<watch::Receiver<Size>>)
+    where
+        C: ContextDirtyRenderObject + ContextSpawnRenderingTask,
+    {
+        if let Some(size_rx) = &size_rx {
+            let mut size = self.size.lock();
+
+            let new_size = *size_rx.borrow();
+
+            if *size != new_size {
+                *size = new_size;
+                ctx.mark_needs_layout();
+            }
+        }
+
+        self.size_task = size_rx.and_then(|mut size_rx| {
+            let size = Arc::clone(&self.size);
+
+            ctx.spawn_task(move |mut ctx| async move {
+                while let Ok(new_size) = size_rx.recv().await {
+                    let mut size = size.lock();
+
+                    if *size == new_size {
+                        continue;
+                    }
+
+                    *size = new_size;
+                    ctx.mark_needs_layout();
+                }
+            })
+            .ok()
+        });
+    }
+}
+
+impl RenderObjectImpl for RenderTerminalLayout {
+    fn intrinsic_size(
+        &self,
+        ctx: &mut RenderObjectIntrinsicSizeContext,
+        dimension: IntrinsicDimension,
+        cross_extent: f32,
+    ) -> f32 {
+        ctx.iter_children().next().map_or(0.0, |child| {
+            child.compute_intrinsic_size(dimension, cross_extent)
+        })
+    }
+
+    fn layout(&self, ctx: &mut RenderObjectLayoutContext, _: Constraints) -> Size {
+        let size = *self.size.lock();
+
+        let mut children = ctx.iter_children_mut();
+
+        while let Some(mut child) = children.next() {
+            child.layout(Constraints::tight(size));
+        }
+
+        size
+    }
+}
@@ -0,0 +1,121 @@
+use std::{cell::RefCell, rc::Rc};
+
+use agui_core::{
+    unit::Size,
+    widget::{IntoWidget, Widget},
+};
+use agui_executor::{EngineExecutor, LocalEngineExecutor};
+use agui_primitives::sized_box::SizedBox;
+
+use crate::{
+    create_view::CreateTerminalView, renderer::TerminalRenderer, view::TerminalViewHandle,
+};
+
+#[cfg(feature = "crossterm")]
+mod app;
+pub mod create_view;
+mod grid;
+#[cfg(feature = "crossterm")]
+mod input;
+mod layout;
+mod render;
+pub mod renderer;
+pub mod view;
+
+#[cfg(feature = "crossterm")]
+pub use app::{run, run_with};
+pub use grid::{Cell, Grid, Rgb};
+#[cfg(feature = "crossterm")]
+pub use input::EventTranslator;
+pub use layout::TerminalLayout;
+
+/// Builds the widget in a view of the given number of columns and rows, and returns the
+/// grid it draws, without touching the terminal.
+///
+/// Each cell is the default size of a [`TerminalRenderer`] cell.
+pub fn render_to_grid(widget: impl IntoWidget, columns: usize, rows: usize) -> Grid {
+    let child = widget.into_widget();
+
+    let view_handle = Rc::new(RefCell::new(None::<TerminalViewHandle>));
+
+    let renderer = TerminalRenderer::new();
+
+    let size = Size::new(
+        columns as f32 * renderer.cell_size().width,
+        rows as f32 * renderer.cell_size().height,
+    );
+
+    let root = CreateTerminalView {
+        renderer,
+
+        builder: {
+            let view_handle = Rc::clone(&view_handle);
+
+            move |handle: &TerminalViewHandle| -> Widget {
+                view_handle.borrow_mut().replace(handle.clone());
+
+                SizedBox {
+                    width: Some(size.width),
+                    height: Some(size.height),
+
+                    child: Some(child.clone()),
+                }
+                .into_widget()
+            }
+        },
+    };
+
+    let mut executor = LocalEngineExecutor::with_root(root).expect("failed to build tree");
+
+    executor.run_until_stalled();
+
+    let view_handle = view_handle
+        .borrow_mut()
+        .take()
+        .expect("the view was never created");
+
+    view_handle
+        .grid()
+        .unwrap_or_else(|| Grid::new(columns, rows))
+}
+
+#[cfg(test)]
+mod tests {
+    use agui_core::{engine::rendering::mock::text_style, unit::Color};
+    use agui_primitives::{colored_box::ColoredBox, text::Text};
+
+    use crate::{render_to_grid, Rgb};
+
+    #[test]
+    fn renders_widgets() {
+        let grid = render_to_grid(
+            ColoredBox {
+                color: Color::from_rgb((0.0, 0.0, 1.0)),
+
+                child: None,
+            },
+            4,
+            2,
+        );
+
+        assert_eq!(grid.columns(), 4);
+        assert_eq!(grid.rows(), 2);
+
+        assert_eq!(grid.get(3, 1).unwrap().bg, Some(Rgb::new(0, 0, 255)));
+    }
+
+    #[test]
+    fn renders_text() {
+        let grid = render_to_grid(
+            Text {
+                style: text_style(14.0, Color::from_rgb((1.0, 1.0, 1.0))),
+
+                text: "hello world".into(),
+            },
+            5,
+            3,
+        );
+
+        assert_eq!(grid.text(), "hello\nworld\n     ");
+    }
+}
@@ -0,0 +1,63 @@
+use agui_core::unit::{Font, FontData, FontId};
+use rustc_hash::FxHashMap;
+
+/// Maps the glyphs of each font back to the characters they were shaped from, since a
+/// terminal can only draw characters.
+#[derive(Default)]
+pub(crate) struct GlyphChars {
+    fonts: FxHashMap<FontId, Option<FxHashMap<u16, char>>>,
+}
+
+impl GlyphChars {
+    /// Returns the character that the glyph is drawn for, or `None` if no character in the
+    /// font's character map refers to it.
+    pub fn get(&mut self, font: &Font, glyph_id: u16) -> Option<char> {
+        self.fonts
+            .entry(font.id())
+            .or_insert_with(|| Self::load(font))
+            .as_ref()?
+            .get(&glyph_id)
+            .copied()
+    }
+
+    fn load(font: &Font) -> Option<FxHashMap<u16, char>> {
+        let FontData::Bytes(data) = font.as_ref() else {
+            tracing::error!("glyph runs must be drawn with the data of their font");
+
+            return None;
+        };
+
+        let face = match ttf_parser::Face::parse(data, font.index()) {
+            Ok(face) => face,
+
+            Err(err) => {
+                tracing::error!("failed to parse font {:?}: {}", font, err);
+
+                return None;
+            }
+        };
+
+        let mut chars = FxHashMap::default();
+
+        for subtable in face
+            .tables()
+            .cmap?
+            .subtables
+            .into_iter()
+            .filter(|subtable| subtable.is_unicode())
+        {
+            subtable.codepoints(|codepoint| {
+                // Control characters are often mapped to the missing glyph
+                let Some(ch) = char::from_u32(codepoint).filter(|ch| !ch.is_control()) else {
+                    return;
+                };
+
+                if let Some(glyph_id) = subtable.glyph_index(codepoint) {
+                    chars.entry(glyph_id.0).or_insert(ch);
+                }
+            });
+        }
+
+        Some(chars)
+    }
+}
@@ -0,0 +1,5 @@
+mod glyphs;
+mod painter;
+mod scene;
+
+pub use scene::*;
@@ -0,0 +1,689 @@
+use agui_core::{
+    render::canvas::{command::CanvasCommand, paint::Paint, Canvas, CanvasLayer},
+    unit::{
+        Color, Constraints, FillRule, GlyphRun, Offset, PaintingStyle, Rect, Shape, Size,
+        StrokeStyle, TextStyle,
+    },
+};
+use agui_text::TextFonts;
+use lyon::{algorithms::hit_test::hit_test_path, math::point};
+use rustc_hash::FxHashMap;
+
+use crate::{
+    grid::{Cell, Grid, Rgb},
+    render::glyphs::GlyphChars,
+};
+
+/// Colors less opaque than this are too faint to replace what's beneath them, since a cell
+/// can't be partially covered.
+const OPAQUE_THRESHOLD: f32 = 0.5;
+
+/// A rectangle of cells, exclusive of its right and bottom edges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct CellRect {
+    left: i64,
+    top: i64,
+    right: i64,
+    bottom: i64,
+}
+
+impl CellRect {
+    fn intersect(self, other: Self) -> Self {
+        Self {
+            left: self.left.max(other.left),
+            top: self.top.max(other.top),
+            right: self.right.min(other.right),
+            bottom: self.bottom.min(other.bottom),
+        }
+    }
+
+    fn is_empty(self) -> bool {
+        self.left >= self.right || self.top >= self.bottom
+    }
+
+    fn contains(self, column: i64, row: i64) -> bool {
+        column >= self.left && column < self.right && row >= self.top && row < self.bottom
+    }
+
+    fn iter(self) -> impl Iterator<Item = (i64, i64)> {
+        (self.top..self.bottom)
+            .flat_map(move |row| (self.left..self.right).map(move |column| (column, row)))
+    }
+}
+
+/// Draws canvases onto a grid of cells.
+///
+/// A cell is covered by a shape if its center is, and anything drawn is clipped to the
+/// bounds of the layers it's within.
+pub(crate) struct GridPainter<'grid> {
+    grid: &'grid mut Grid,
+
+    fonts: &'grid mut TextFonts,
+    glyph_chars: &'grid mut GlyphChars,
+
+    cell_size: Size,
+
+    /// The cells that can currently be drawn to, narrowed by each open layer.
+    clips: Vec<CellRect>,
+}
+
+impl<'grid> GridPainter<'grid> {
+    pub fn new(
+        grid: &'grid mut Grid,
+        fonts: &'grid mut TextFonts,
+        glyph_chars: &'grid mut GlyphChars,
+        cell_size: Size,
+    ) -> Self {
+        let clip = CellRect {
+            left: 0,
+            top: 0,
+            right: grid.columns() as i64,
+            bottom: grid.rows() as i64,
+        };
+
+        Self {
+            grid,
+
+            fonts,
+            glyph_chars,
+
+            cell_size,
+
+            clips: vec![clip],
+        }
+    }
+
+    /// Draws the canvas, including any of its child layers. If the canvas has a tail, its
+    /// layer is left open so that anything drawn afterwards is clipped to it until
+    /// [`end_canvas`](Self::end_canvas) is called.
+    pub fn begin_canvas(&mut self, origin: Offset, canvas: &Canvas) {
+        for command in &canvas.head {
+            self.draw_command(origin, &canvas.paints, command);
+        }
+
+        for layer in &canvas.children {
+            let layer_origin = self.begin_layer(origin, layer);
+
+            self.begin_canvas(layer_origin, &layer.canvas);
+            self.end_canvas(&layer.canvas);
+
+            self.clips.pop();
+        }
+
+        if let Some(tail) = &canvas.tail {
+            let tail_origin = self.begin_layer(origin, tail);
+
+            self.begin_canvas(tail_origin, &tail.canvas);
+        }
+    }
+
+    /// Closes the layers that were left open by [`begin_canvas`](Self::begin_canvas).
+    pub fn end_canvas(&mut self, canvas: &Canvas) {
+        if let Some(tail) = &canvas.tail {
+            self.end_canvas(&tail.canvas);
+
+            self.clips.pop();
+        }
+    }
+
+    fn clip(&self) -> CellRect {
+        *self.clips.last().expect("the grid is always clipped")
+    }
+
+    /// Returns the cells whose centers are within `rect`.
+    fn cells(&self, rect: Rect) -> CellRect {
+        CellRect {
+            left: (rect.left / self.cell_size.width).round() as i64,
+            top: (rect.top / self.cell_size.height).round() as i64,
+            right: (rect.right() / self.cell_size.width).round() as i64,
+            bottom: (rect.bottom() / self.cell_size.height).round() as i64,
+        }
+    }
+
+    fn cell_mut(&mut self, column: i64, row: i64) -> Option<&mut Cell> {
+        if !self.clip().contains(column, row) {
+            return None;
+        }
+
+        self.grid.get_mut(column as usize, row as usize)
+    }
+
+    /// Opens a layer, returning the origin of its contents.
+    fn begin_layer(&mut self, origin: Offset, layer: &CanvasLayer) -> Offset {
+        let origin = origin + layer.offset;
+
+        let rect = Rect::new(
+            origin.x,
+            origin.y,
+            layer.canvas.size.width,
+            layer.canvas.size.height,
+        );
+
+        // Layers are clipped to their bounds, regardless of their shape
+        let clip = if is_empty(&layer.style.shape, rect) {
+            CellRect {
+                left: 0,
+                top: 0,
+                right: 0,
+                bottom: 0,
+            }
+        } else {
+            self.cells(rect).intersect(self.clip())
+        };
+
+        self.clips.push(clip);
+
+        origin
+    }
+
+    fn draw_command(&mut self, origin: Offset, paints: &[Paint], command: &CanvasCommand) {
+        match command {
+            CanvasCommand::Shape {
+                paint_idx,
+                rect,
+                shape,
+            } => {
+                let Some(paint) = paints.get(*paint_idx) else {
+                    tracing::warn!("shape refers to a paint that does not exist");
+                    return;
+                };
+
+                let rect = rect.translate(origin);
+
+                match &paint.style {
+                    PaintingStyle::Fill => self.fill_shape(paint, rect, shape),
+                    PaintingStyle::Stroke(stroke) => self.stroke_shape(paint, stroke, rect, shape),
+                }
+            }
+
+            // Shadows and textures have no sensible representation in a grid of characters
+            CanvasCommand::Shadow { .. } => {}
+
+            CanvasCommand::Texture { .. } => {
+                tracing::trace!("textures cannot be drawn in a terminal");
+            }
+
+            CanvasCommand::Text {
+                paint_idx,
+                rect,
+                text_style,
+                text,
+            } => {
+                let Some(paint) = paints.get(*paint_idx) else {
+                    tracing::warn!("text refers to a paint that does not exist");
+                    return;
+                };
+
+                self.draw_text(paint, rect.translate(origin), text_style, text);
+            }
+
+            CanvasCommand::Glyphs {
+                paint_idx,
+                rect,
+                runs,
+            } => {
+                let Some(paint) = paints.get(*paint_idx) else {
+                    tracing::warn!("glyphs refer to a paint that does not exist");
+                    return;
+                };
+
+                self.draw_glyphs(paint, rect.translate(origin), runs);
+            }
+
+            cmd => {
+                tracing::error!("unknown command: {:?}", cmd);
+            }
+        }
+    }
+
+    fn fill_shape(&mut self, paint: &Paint, rect: Rect, shape: &Shape) {
+        let color = paint_color(paint);
+
+        if color.alpha <= 0.0 || is_empty(shape, rect) {
+            return;
+        }
+
+        let path = match shape {
+            Shape::Rect => None,
+            shape => Some(shape.build_path(rect)),
+        };
+
+        let fill_rule = match paint.fill_rule {
+            FillRule::NonZero => lyon::path::FillRule::NonZero,
+            FillRule::EvenOdd => lyon::path::FillRule::EvenOdd,
+        };
+
+        let cells = match &path {
+            // Paths may extend beyond the rect they're drawn in
+            Some(path) => {
+                let bounds = lyon::algorithms::aabb::bounding_box(path.iter());
+
+                self.cells(Rect::new(
+                    bounds.min.x,
+                    bounds.min.y,
+                    bounds.width(),
+                    bounds.height(),
+                ))
+            }
+
+            None => self.cells(rect),
+        };
+
+        let cell_size = self.cell_size;
+
+        for (column, row) in cells.intersect(self.clip()).iter() {
+            if let Some(path) = &path {
+                let center = point(
+                    (column as f32 + 0.5) * cell_size.width,
+                    (row as f32 + 0.5) * cell_size.height,
+                );
+
+                if !hit_test_path(&center, path.iter(), fill_rule, 0.1) {
+                    continue;
+                }
+            }
+
+            if let Some(cell) = self.cell_mut(column, row) {
+                fill_cell(cell, color);
+            }
+        }
+    }
+
+    fn stroke_shape(&mut self, paint: &Paint, stroke: &StrokeStyle, rect: Rect, shape: &Shape) {
+        let color = paint_color(paint);
+
+        if color.alpha < OPAQUE_THRESHOLD || is_empty(shape, rect) {
+            return;
+        }
+
+        let cells = self.cells(rect);
+
+        if cells.is_empty() {
+            return;
+        }
+
+        let (horizontal, vertical) = if stroke.is_dashed() {
+            ('┄', '┆')
+        } else {
+            ('─', '│')
+        };
+
+        let [top_left, top_right, bottom_left, bottom_right] = match shape {
+            Shape::RoundedRect { .. } | Shape::Circle => ['╭', '╮', '╰', '╯'],
+            _ => ['┌', '┐', '└', '┘'],
+        };
+
+        let (last_column, last_row) = (cells.right - 1, cells.bottom - 1);
+
+        for (column, row) in cells.iter() {
+            let on_left = column == cells.left;
+            let on_right = column == last_column;
+            let on_top = row == cells.top;
+            let on_bottom = row == last_row;
+
+            let ch = match (on_left, on_right, on_top, on_bottom) {
+                // Outlines only a single cell wide collapse into a line
+                (true, true, true, true) => '□',
+                (true, true, _, _) => vertical,
+                (_, _, true, true) => horizontal,
+
+                (true, _, true, _) => top_left,
+                (_, true, true, _) => top_right,
+                (true, _, _, true) => bottom_left,
+                (_, true, _, true) => bottom_right,
+
+                (true, ..) | (_, true, ..) => vertical,
+                (_, _, true, _) | (_, _, _, true) => horizontal,
+
+                _ => continue,
+            };
+
+            if let Some(cell) = self.cell_mut(column, row) {
+                cell.ch = ch;
+                cell.fg = Some(to_rgb(color));
+            }
+        }
+    }
+
+    /// Lays out the text within its rect, then draws it the same way as glyphs that were
+    /// laid out ahead of time.
+    fn draw_text(&mut self, paint: &Paint, rect: Rect, text_style: &TextStyle, text: &str) {
+        let paragraph = agui_text::layout_paragraph(
+            self.fonts,
+            text_style,
+            text,
+            Constraints::tight(Size::from(rect)),
+        );
+
+        let runs = paragraph.glyph_runs().cloned().collect::<Vec<_>>();
+
+        self.draw_glyphs(paint, rect, &runs);
+    }
+
+    /// Draws each glyph as the character it was shaped from, in the cell beneath the
+    /// middle of its advance. Glyphs are kept in order even if the font is narrower than a
+    /// cell, so text lines up best when drawn in a monospace font as wide as a cell.
+    fn draw_glyphs(&mut self, paint: &Paint, rect: Rect, runs: &[GlyphRun]) {
+        let color = paint_color(paint);
+
+        if color.alpha < OPAQUE_THRESHOLD {
+            return;
+        }
+
+        // The last cell drawn to on each row
+        let mut last_columns = FxHashMap::<i64, i64>::default();
+
+        for run in runs {
+            for glyph in &run.glyphs {
+                let Some(ch) = self.glyph_chars.get(&run.font, glyph.id) else {
+                    continue;
+                };
+
+                let column = ((rect.left + glyph.x + glyph.advance / 2.0) / self.cell_size.width)
+                    .floor() as i64;
+
+                // Baselines sit within the line, above its descent
+                let row = ((rect.top + glyph.y) / self.cell_size.height).floor() as i64;
+
+                let column = match last_columns.get(&row) {
+                    Some(last_column) if column <= *last_column => last_column + 1,
+                    _ => column,
+                };
+
+                last_columns.insert(row, column);
+
+                if let Some(cell) = self.cell_mut(column, row) {
+                    cell.ch = ch;
+                    cell.fg = Some(to_rgb(color));
+                }
+            }
+        }
+    }
+}
+
+fn is_empty(shape: &Shape, rect: Rect) -> bool {
+    !matches!(shape, Shape::Path(_)) && (rect.width <= 0.0 || rect.height <= 0.0)
+}
+
+/// Returns the single color that best represents the paint. Gradients are drawn as the
+/// average of their stops.
+fn paint_color(paint: &Paint) -> Color {
+    match &paint.gradient {
+        Some(gradient) if !gradient.stops.is_empty() => {
+            let count = gradient.stops.len() as f32;
+
+            let sum = gradient.stops.iter().fold([0.0; 4], |sum, stop| {
+                [
+                    sum[0] + stop.color.red,
+                    sum[1] + stop.color.green,
+                    sum[2] + stop.color.blue,
+                    sum[3] + stop.color.alpha,
+                ]
+            });
+
+            Color::from_rgba((
+                sum[0] / count,
+                sum[1] / count,
+                sum[2] / count,
+                sum[3] / count,
+            ))
+        }
+
+        _ => paint.color,
+    }
+}
+
+fn to_rgb(color: Color) -> Rgb {
+    let channel = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+
+    Rgb::new(
+        channel(color.red),
+        channel(color.green),
+        channel(color.blue),
+    )
+}
+
+/// Fills the background of the cell. Opaque colors hide any character in the cell, while
+/// translucent ones are blended with the existing background.
+fn fill_cell(cell: &mut Cell, color: Color) {
+    let alpha = color.alpha.clamp(0.0, 1.0);
+    let rgb = to_rgb(color);
+
+    match cell.bg {
+        Some(bg) if alpha < 1.0 => {
+            let blend =
+                |from: u8, to: u8| (from as f32 + (to as f32 - from as f32) * alpha).round() as u8;
+
+            cell.bg = Some(Rgb::new(
+                blend(bg.red, rgb.red),
+                blend(bg.green, rgb.green),
+                blend(bg.blue, rgb.blue),
+            ));
+        }
+
+        // The terminal's default background is unknown, so there's nothing to blend with
+        _ if alpha >= OPAQUE_THRESHOLD => cell.bg = Some(rgb),
+
+        _ => return,
+    }
+
+    if alpha >= OPAQUE_THRESHOLD {
+        cell.ch = ' ';
+        cell.fg = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use agui_core::{
+        engine::rendering::mock::{text_style, DEJA_VU_SANS},
+        render::canvas::{
+            paint::Paint,
+            painter::{CanvasPainter, Head},
+            Canvas,
+        },
+        unit::{
+            Color, Font, GlyphRun, Offset, PaintingStyle, PositionedGlyph, Rect, Size, StrokeStyle,
+        },
+    };
+
+    use agui_text::TextFonts;
+
+    use crate::{
+        grid::{Grid, Rgb},
+        render::glyphs::GlyphChars,
+    };
+
+    use super::GridPainter;
+
+    const RED: Color = Color::from_rgb((1.0, 0.0, 0.0));
+
+    fn draw(canvas: &Canvas) -> Grid {
+        draw_with_cell_size(canvas, Size::new(1.0, 1.0))
+    }
+
+    fn draw_with_cell_size(canvas: &Canvas, cell_size: Size) -> Grid {
+        let mut grid = Grid::new(
+            (canvas.size.width / cell_size.width) as usize,
+            (canvas.size.height / cell_size.height) as usize,
+        );
+
+        let mut fonts = TextFonts::new();
+        let mut glyph_chars = GlyphChars::default();
+
+        let mut painter = GridPainter::new(&mut grid, &mut fonts, &mut glyph_chars, cell_size);
+
+        painter.begin_canvas(Offset::ZERO, canvas);
+        painter.end_canvas(canvas);
+
+        grid
+    }
+
+    #[test]
+    fn fills_backgrounds() {
+        let mut canvas = Canvas::new(Size::new(4.0, 2.0));
+
+        {
+            let mut painter = CanvasPainter::<Head<()>>::begin(&mut canvas);
+
+            let brush = painter.add_paint(Paint {
+                color: RED,
+                ..Paint::default()
+            });
+
+            painter.draw_rect_at(Rect::new(1.0, 0.0, 2.0, 1.0), &brush);
+        }
+
+        let grid = draw(&canvas);
+
+        assert_eq!(grid.get(0, 0).unwrap().bg, None);
+        assert_eq!(grid.get(1, 0).unwrap().bg, Some(Rgb::new(255, 0, 0)));
+        assert_eq!(grid.get(2, 0).unwrap().bg, Some(Rgb::new(255, 0, 0)));
+        assert_eq!(grid.get(3, 0).unwrap().bg, None);
+        assert_eq!(grid.get(1, 1).unwrap().bg, None);
+    }
+
+    #[test]
+    fn outlines_with_box_drawing_characters() {
+        let mut canvas = Canvas::new(Size::new(4.0, 3.0));
+
+        {
+            let mut painter = CanvasPainter::<Head<()>>::begin(&mut canvas);
+
+            let brush = painter.add_paint(Paint {
+                color: RED,
+                style: PaintingStyle::Stroke(StrokeStyle::default()),
+                ..Paint::default()
+            });
+
+            painter.draw_rect_at(Rect::new(0.0, 0.0, 4.0, 3.0), &brush);
+        }
+
+        assert_eq!(draw(&canvas).text(), "┌──┐\n│  │\n└──┘");
+    }
+
+    #[test]
+    fn rounds_corners() {
+        let mut canvas = Canvas::new(Size::new(3.0, 2.0));
+
+        {
+            let mut painter = CanvasPainter::<Head<()>>::begin(&mut canvas);
+
+            let brush = painter.add_paint(Paint {
+                color: RED,
+                style: PaintingStyle::Stroke(StrokeStyle::default()),
+                ..Paint::default()
+            });
+
+            painter.draw_rounded_rect_at(Rect::new(0.0, 0.0, 3.0, 2.0), &brush, 1.0, 1.0, 1.0, 1.0);
+        }
+
+        assert_eq!(draw(&canvas).text(), "╭─╮\n╰─╯");
+    }
+
+    #[test]
+    fn wraps_text_within_its_rect() {
+        let mut canvas = Canvas::new(Size::new(40.0, 48.0));
+
+        {
+            let mut painter = CanvasPainter::<Head<()>>::begin(&mut canvas);
+
+            let brush = painter.add_paint(Paint {
+                color: RED,
+                ..Paint::default()
+            });
+
+            painter.draw_text_at(
+                Rect::new(8.0, 0.0, 24.0, 48.0),
+                &brush,
+                text_style(14.0, RED),
+                "abcdefg",
+            );
+        }
+
+        let grid = draw_with_cell_size(&canvas, Size::new(8.0, 16.0));
+
+        assert_eq!(grid.text(), " ab  \n cd  \n efg ");
+        assert_eq!(grid.get(1, 0).unwrap().fg, Some(Rgb::new(255, 0, 0)));
+    }
+
+    #[test]
+    fn opaque_fills_hide_text() {
+        let mut canvas = Canvas::new(Size::new(24.0, 16.0));
+
+        {
+            let mut painter = CanvasPainter::<Head<()>>::begin(&mut canvas);
+
+            let brush = painter.add_paint(Paint {
+                color: RED,
+                ..Paint::default()
+            });
+
+            painter.draw_text_at(
+                Rect::new(0.0, 0.0, 24.0, 16.0),
+                &brush,
+                text_style(14.0, RED),
+                "ab",
+            );
+
+            painter.draw_rect_at(Rect::new(8.0, 0.0, 8.0, 16.0), &brush);
+        }
+
+        assert_eq!(
+            draw_with_cell_size(&canvas, Size::new(8.0, 16.0)).text(),
+            "a  "
+        );
+    }
+
+    #[test]
+    fn draws_glyphs_as_characters() {
+        let data = DEJA_VU_SANS.to_vec();
+
+        let face = ttf_parser::Face::parse(&data, 0).unwrap();
+
+        // Glyphs narrower than a cell still take up a cell each
+        let glyphs = [('h', 0.0, 0.8), ('i', 0.3, 0.8), ('!', 0.0, 1.8)]
+            .into_iter()
+            .enumerate()
+            .map(|(cluster, (ch, x, y))| PositionedGlyph {
+                id: face.glyph_index(ch).unwrap().0,
+                cluster,
+
+                x,
+                y,
+
+                advance: 0.3,
+            })
+            .collect();
+
+        let mut canvas = Canvas::new(Size::new(4.0, 2.0));
+
+        {
+            let mut painter = CanvasPainter::<Head<()>>::begin(&mut canvas);
+
+            let brush = painter.add_paint(Paint {
+                color: RED,
+                ..Paint::default()
+            });
+
+            painter.draw_glyphs_at(
+                Rect::new(1.0, 0.0, 3.0, 2.0),
+                &brush,
+                vec![GlyphRun {
+                    font: Font::from_bytes(data.clone()),
+                    font_size: 1.0,
+
+                    range: 0..3,
+                    rtl: false,
+
+                    glyphs,
+                }],
+            );
+        }
+
+        let grid = draw(&canvas);
+
+        assert_eq!(grid.text(), " hi \n !  ");
+        assert_eq!(grid.get(1, 0).unwrap().fg, Some(Rgb::new(255, 0, 0)));
+    }
+}
@@ -0,0 +1,84 @@
+use std::sync::Arc;
+
+use agui_core::{
+    engine::rendering::scene::{Scene, SceneChange, SceneVisitor},
+    render::canvas::Canvas,
+    unit::{Offset, Size},
+};
+use agui_renderer::view::ViewScene;
+use agui_text::TextFonts;
+use parking_lot::Mutex;
+
+use crate::{
+    grid::Grid,
+    render::{glyphs::GlyphChars, painter::GridPainter},
+};
+
+pub struct TerminalScene {
+    fonts: Arc<Mutex<TextFonts>>,
+
+    scene: Scene,
+
+    /// The logical size of a single character cell.
+    cell_size: Size,
+
+    glyph_chars: GlyphChars,
+
+    /// The grid drawn during the most recent redraw, if any.
+    pub(crate) grid: Option<Grid>,
+}
+
+impl TerminalScene {
+    pub(crate) fn new(fonts: Arc<Mutex<TextFonts>>, cell_size: Size) -> Self {
+        Self {
+            fonts,
+
+            scene: Scene::default(),
+
+            cell_size,
+
+            glyph_chars: GlyphChars::default(),
+
+            grid: None,
+        }
+    }
+
+    /// Draws the scene onto a grid large enough to cover the root object.
+    pub(crate) fn redraw(&mut self) {
+        let size = self.scene.size().unwrap_or_default();
+
+        let mut grid = Grid::new(
+            (size.width / self.cell_size.width).ceil().max(0.0) as usize,
+            (size.height / self.cell_size.height).ceil().max(0.0) as usize,
+        );
+
+        self.scene.visit(&mut GridPainter::new(
+            &mut grid,
+            &mut self.fonts.lock(),
+            &mut self.glyph_chars,
+            self.cell_size,
+        ));
+
+        self.grid = Some(grid);
+    }
+}
+
+impl ViewScene for TerminalScene {
+    fn apply(&mut self, change: SceneChange) {
+        self.scene.apply(change);
+    }
+
+    fn sync(&mut self) {
+        self.redraw();
+    }
+}
+
+impl SceneVisitor for GridPainter<'_> {
+    fn begin_canvas(&mut self, origin: Offset, canvas: &Canvas) {
+        GridPainter::begin_canvas(self, origin, canvas);
+    }
+
+    fn end_canvas(&mut self, canvas: &Canvas) {
+        GridPainter::end_canvas(self, canvas);
+    }
+}
@@ -0,0 +1,70 @@
+use std::{error::Error, sync::Arc};
+
+use agui_renderer::{BindRenderer, FrameNotifier, Renderer};
+use parking_lot::Mutex;
+
+use crate::{grid::Grid, view::TerminalViewHandle};
+
+/// Holds the most recent grid rendered by a [`TerminalHeadlessRenderer`].
+///
+/// Clones share the same grid, so one can be bound to a renderer while another is used
+/// to read the result.
+#[derive(Debug, Default, Clone)]
+pub struct GridBuffer {
+    grid: Arc<Mutex<Option<Grid>>>,
+}
+
+impl GridBuffer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the most recently rendered grid, or `None` if nothing has been rendered.
+    pub fn grid(&self) -> Option<Grid> {
+        self.grid.lock().clone()
+    }
+}
+
+/// Renders a view into a [`GridBuffer`] rather than a terminal.
+#[derive(Clone)]
+pub struct TerminalHeadlessRenderer {
+    view_handle: TerminalViewHandle,
+}
+
+impl TerminalHeadlessRenderer {
+    pub fn new(view_handle: &TerminalViewHandle) -> Self {
+        Self {
+            view_handle: view_handle.clone(),
+        }
+    }
+}
+
+impl BindRenderer<GridBuffer> for TerminalHeadlessRenderer {
+    async fn bind(
+        self,
+        target: &GridBuffer,
+        frame_notifier: FrameNotifier,
+    ) -> Result<Box<dyn Renderer>, Box<dyn Error + Send + Sync>> {
+        self.view_handle.set_frame_notifier(frame_notifier);
+
+        Ok(Box::new(BoundTerminalHeadlessRenderer {
+            view_handle: self.view_handle,
+
+            target: target.clone(),
+        }))
+    }
+}
+
+struct BoundTerminalHeadlessRenderer {
+    view_handle: TerminalViewHandle,
+
+    target: GridBuffer,
+}
+
+impl Renderer for BoundTerminalHeadlessRenderer {
+    fn render(&mut self) {
+        tracing::trace!("TerminalHeadlessRenderer::render");
+
+        *self.target.grid.lock() = self.view_handle.grid();
+    }
+}
@@ -0,0 +1,60 @@
+use std::sync::Arc;
+
+use agui_core::unit::Size;
+use agui_text::TextFonts;
+use parking_lot::Mutex;
+
+use crate::view::{self, TerminalView, TerminalViewHandle};
+
+pub mod headless;
+
+/// Draws views onto a grid of character cells.
+///
+/// Text is laid out by its font and size like any other backend, and each of its glyphs
+/// is drawn as a character in the cell beneath it. It lines up with the grid best when
+/// it's drawn in a monospace font whose characters are as wide as a cell.
+#[derive(Clone)]
+pub struct TerminalRenderer {
+    fonts: Arc<Mutex<TextFonts>>,
+
+    cell_size: Size,
+}
+
+impl Default for TerminalRenderer {
+    fn default() -> Self {
+        Self {
+            fonts: Arc::default(),
+
+            cell_size: Size::new(8.0, 16.0),
+        }
+    }
+}
+
+impl TerminalRenderer {
+    /// Creates a renderer where each character cell is 8 by 16 logical pixels, which is
+    /// about the size of a cell in most terminals.
+    pub fn new() -> TerminalRenderer {
+        Self::default()
+    }
+
+    /// Sets the logical size of each character cell, so that widgets sized for other
+    /// backends remain legible.
+    pub fn with_cell_size(mut self, cell_size: Size) -> Self {
+        self.cell_size = cell_size;
+        self
+    }
+
+    pub fn cell_size(&self) -> Size {
+        self.cell_size
+    }
+
+    pub(crate) fn new_view(&self) -> (TerminalView, TerminalViewHandle) {
+        view::new_view(Arc::clone(&self.fonts), self.cell_size)
+    }
+}
+
+impl PartialEq for TerminalRenderer {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.fonts, &other.fonts) && self.cell_size == other.cell_size
+    }
+}
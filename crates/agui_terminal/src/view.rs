@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use agui_core::unit::Size;
+use agui_renderer::{
+    view::{SceneView, SceneViewHandle},
+    FrameNotifier,
+};
+use agui_text::{ShapedTextLayout, TextFonts};
+use parking_lot::Mutex;
+
+use crate::{grid::Grid, render::TerminalScene};
+
+pub type TerminalView = SceneView<TerminalScene>;
+
+#[derive(Clone)]
+pub struct TerminalViewHandle {
+    handle: SceneViewHandle<TerminalScene>,
+}
+
+pub(crate) fn new_view(
+    fonts: Arc<Mutex<TextFonts>>,
+    cell_size: Size,
+) -> (TerminalView, TerminalViewHandle) {
+    let view = SceneView::new(
+        TerminalScene::new(Arc::clone(&fonts), cell_size),
+        ShapedTextLayout::new(fonts),
+    );
+
+    let handle = TerminalViewHandle {
+        handle: view.handle(),
+    };
+
+    (view, handle)
+}
+
+impl TerminalViewHandle {
+    pub(crate) fn set_frame_notifier(&self, frame_notifier: FrameNotifier) {
+        self.handle.set_frame_notifier(frame_notifier);
+    }
+
+    /// Returns the grid drawn during the most recent sync, or `None` if the view has not
+    /// been synced.
+    pub fn grid(&self) -> Option<Grid> {
+        self.handle.scene().grid.clone()
+    }
+}
@@ -0,0 +1,709 @@
+use std::{
+    borrow::Cow,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use agui_core::unit::{Font, FontData, FontStretch, FontStyle, FontWeight};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+/// The families that each generic family name stands for, in order of preference.
+const GENERIC_FAMILIES: &[(&str, &[&str])] = &[
+    (
+        "sans-serif",
+        &[
+            "DejaVu Sans",
+            "Noto Sans",
+            "Liberation Sans",
+            "Cantarell",
+            "Ubuntu",
+            "Roboto",
+            "Arial",
+            "Helvetica",
+        ],
+    ),
+    (
+        "serif",
+        &[
+            "DejaVu Serif",
+            "Noto Serif",
+            "Liberation Serif",
+            "Times New Roman",
+            "Times",
+        ],
+    ),
+    (
+        "monospace",
+        &[
+            "DejaVu Sans Mono",
+            "Noto Sans Mono",
+            "Liberation Mono",
+            "Ubuntu Mono",
+            "Courier New",
+            "Courier",
+        ],
+    ),
+    ("cursive", &["Comic Sans MS", "Z003", "URW Chancery L"]),
+    ("fantasy", &["Impact", "Papyrus"]),
+    ("emoji", &["Noto Color Emoji", "Noto Emoji", "Twemoji"]),
+    (
+        "system-ui",
+        &["Cantarell", "Ubuntu", "Noto Sans", "DejaVu Sans"],
+    ),
+];
+
+/// The families that are searched, in order, for characters that a font doesn't have.
+const FALLBACK_FAMILIES: &[&str] = &[
+    "sans-serif",
+    "Noto Sans",
+    "Noto Sans CJK SC",
+    "Noto Sans CJK JP",
+    "Noto Sans CJK KR",
+    "WenQuanYi Micro Hei",
+    "Droid Sans Fallback",
+    "emoji",
+    "Noto Sans Symbols",
+    "Noto Sans Symbols2",
+    "Noto Sans Math",
+    "Symbola",
+];
+
+/// The extensions of the font files that are loaded from directories.
+const FONT_EXTENSIONS: &[&str] = &["ttf", "otf", "ttc", "otc"];
+
+/// The properties a face is picked by, after its family.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct FaceProperties {
+    weight: FontWeight,
+    style: FontStyle,
+    stretch: FontStretch,
+}
+
+/// A font file, or font data that was registered directly.
+struct Source {
+    path: Option<PathBuf>,
+
+    /// The data of the source, once it's been read. Files are only read once one of their
+    /// faces is used, and `Some(None)` means the file couldn't be read.
+    data: Option<Option<Arc<Vec<u8>>>>,
+}
+
+struct FaceInfo {
+    source: usize,
+    index: u32,
+
+    families: Vec<String>,
+    properties: FaceProperties,
+}
+
+/// A collection of font faces that can be looked up by family, weight, style and stretch.
+///
+/// Faces are registered from font data, or discovered in the system's font directories
+/// in the same places fontconfig looks. Font files are only read in full once one of their
+/// faces is picked.
+///
+/// Generic families such as `sans-serif` and `monospace` stand for a list of common
+/// families, and characters that a font doesn't have are looked for in a chain of
+/// fallback families that covers most scripts, emoji and symbols.
+pub struct FontDatabase {
+    sources: Vec<Source>,
+    faces: Vec<FaceInfo>,
+
+    /// The families of each generic family, keyed by its lowercase name.
+    generic_families: FxHashMap<String, Vec<Cow<'static, str>>>,
+    fallback_families: Vec<Cow<'static, str>>,
+
+    /// The face picked for each character that needed a fallback.
+    fallbacks: FxHashMap<(char, FaceProperties), Option<usize>>,
+}
+
+impl Default for FontDatabase {
+    fn default() -> Self {
+        Self {
+            sources: Vec::new(),
+            faces: Vec::new(),
+
+            generic_families: GENERIC_FAMILIES
+                .iter()
+                .map(|(generic, families)| {
+                    (
+                        (*generic).to_owned(),
+                        families.iter().copied().map(Cow::Borrowed).collect(),
+                    )
+                })
+                .collect(),
+            fallback_families: FALLBACK_FAMILIES
+                .iter()
+                .copied()
+                .map(Cow::Borrowed)
+                .collect(),
+
+            fallbacks: FxHashMap::default(),
+        }
+    }
+}
+
+impl FontDatabase {
+    /// Creates a database without any faces.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of faces in the database.
+    pub fn len(&self) -> usize {
+        self.faces.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.faces.is_empty()
+    }
+
+    /// Sets the families that a generic family, such as `sans-serif`, stands for. They're
+    /// tried in order until one of them is in the database.
+    pub fn set_generic_family<I>(&mut self, generic: &str, families: I)
+    where
+        I: IntoIterator,
+        I::Item: Into<Cow<'static, str>>,
+    {
+        self.generic_families.insert(
+            generic.to_lowercase(),
+            families.into_iter().map(Into::into).collect(),
+        );
+
+        self.fallbacks.clear();
+    }
+
+    /// Sets the families that are searched, in order, for characters that a font doesn't
+    /// have. These may include generic families.
+    pub fn set_fallback_families<I>(&mut self, families: I)
+    where
+        I: IntoIterator,
+        I::Item: Into<Cow<'static, str>>,
+    {
+        self.fallback_families = families.into_iter().map(Into::into).collect();
+
+        self.fallbacks.clear();
+    }
+
+    /// Registers every face in the font data.
+    pub fn load_font_data(&mut self, data: Vec<u8>) {
+        let data = Arc::new(data);
+
+        self.sources.push(Source {
+            path: None,
+
+            data: Some(Some(Arc::clone(&data))),
+        });
+
+        self.register_faces(self.sources.len() - 1, &data);
+    }
+
+    /// Registers every face in the font file.
+    pub fn load_font_file(&mut self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+
+        let data = fs::read(path)?;
+
+        self.sources.push(Source {
+            path: Some(path.to_owned()),
+
+            data: None,
+        });
+
+        self.register_faces(self.sources.len() - 1, &data);
+
+        Ok(())
+    }
+
+    /// Registers the faces of every font file in the directory and its subdirectories.
+    /// Files that can't be read are skipped.
+    pub fn load_fonts_dir(&mut self, path: impl AsRef<Path>) {
+        let mut visited = FxHashSet::default();
+
+        self.load_fonts_dir_inner(path.as_ref(), &mut visited);
+    }
+
+    fn load_fonts_dir_inner(&mut self, path: &Path, visited: &mut FxHashSet<PathBuf>) {
+        // Symlinks may lead back to a directory that's already been loaded
+        let Ok(canonical) = path.canonicalize() else {
+            return;
+        };
+
+        if !visited.insert(canonical) {
+            return;
+        }
+
+        let Ok(entries) = fs::read_dir(path) else {
+            return;
+        };
+
+        let mut paths = entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .collect::<Vec<_>>();
+
+        // Faces that match equally well are picked in the order they were loaded, so the
+        // order shouldn't depend on the file system
+        paths.sort();
+
+        for path in paths {
+            if path.is_dir() {
+                self.load_fonts_dir_inner(&path, visited);
+            } else if is_font_file(&path) {
+                if let Err(err) = self.load_font_file(&path) {
+                    tracing::warn!("failed to load font {}: {}", path.display(), err);
+                }
+            }
+        }
+    }
+
+    /// Registers the faces of the fonts installed on the system.
+    pub fn load_system_fonts(&mut self) {
+        for dir in system_font_dirs() {
+            self.load_fonts_dir(dir);
+        }
+
+        tracing::debug!("found {} system font faces", self.faces.len());
+    }
+
+    /// Returns the face of the family that best matches the weight, style and stretch,
+    /// in the same way as CSS. If the family is generic, the first of the families it
+    /// stands for that's in the database is used.
+    ///
+    /// The returned font contains the face's data, and its index if it's in a collection.
+    pub fn query(
+        &mut self,
+        family: &str,
+        weight: FontWeight,
+        style: FontStyle,
+        stretch: FontStretch,
+    ) -> Option<Font> {
+        let face = self.find(
+            family,
+            FaceProperties {
+                weight,
+                style,
+                stretch,
+            },
+        )?;
+
+        self.font(face)
+    }
+
+    /// Returns the face that best matches the weight, style and stretch among the fallback
+    /// families that have a glyph for the character.
+    pub fn fallback(
+        &mut self,
+        ch: char,
+        weight: FontWeight,
+        style: FontStyle,
+        stretch: FontStretch,
+    ) -> Option<Font> {
+        let properties = FaceProperties {
+            weight,
+            style,
+            stretch,
+        };
+
+        let face = match self.fallbacks.get(&(ch, properties)) {
+            Some(face) => *face,
+
+            None => {
+                let face = (0..self.fallback_families.len()).find_map(|idx| {
+                    let family = Cow::clone(&self.fallback_families[idx]);
+
+                    self.find(&family, properties)
+                        .filter(|face| self.has_glyph(*face, ch))
+                });
+
+                self.fallbacks.insert((ch, properties), face);
+
+                face
+            }
+        };
+
+        self.font(face?)
+    }
+
+    fn register_faces(&mut self, source: usize, data: &[u8]) {
+        let count = ttf_parser::fonts_in_collection(data).unwrap_or(1);
+
+        for index in 0..count {
+            let face = match ttf_parser::Face::parse(data, index) {
+                Ok(face) => face,
+
+                Err(err) => {
+                    tracing::warn!("failed to parse font face {}: {}", index, err);
+
+                    continue;
+                }
+            };
+
+            let families = family_names(&face);
+
+            if families.is_empty() {
+                continue;
+            }
+
+            self.faces.push(FaceInfo {
+                source,
+                index,
+
+                families,
+                properties: FaceProperties {
+                    weight: FontWeight(face.weight().to_number()),
+                    style: match face.style() {
+                        ttf_parser::Style::Normal => FontStyle::Normal,
+                        ttf_parser::Style::Italic => FontStyle::Italic,
+                        ttf_parser::Style::Oblique => FontStyle::Oblique,
+                    },
+                    stretch: match face.width() {
+                        ttf_parser::Width::UltraCondensed => FontStretch::UltraCondensed,
+                        ttf_parser::Width::ExtraCondensed => FontStretch::ExtraCondensed,
+                        ttf_parser::Width::Condensed => FontStretch::Condensed,
+                        ttf_parser::Width::SemiCondensed => FontStretch::SemiCondensed,
+                        ttf_parser::Width::Normal => FontStretch::Normal,
+                        ttf_parser::Width::SemiExpanded => FontStretch::SemiExpanded,
+                        ttf_parser::Width::Expanded => FontStretch::Expanded,
+                        ttf_parser::Width::ExtraExpanded => FontStretch::ExtraExpanded,
+                        ttf_parser::Width::UltraExpanded => FontStretch::UltraExpanded,
+                    },
+                },
+            });
+        }
+
+        self.fallbacks.clear();
+    }
+
+    fn find(&self, family: &str, properties: FaceProperties) -> Option<usize> {
+        if let Some(families) = self.generic_families.get(&family.to_lowercase()) {
+            return families.iter().find_map(|family| {
+                let candidates = self.faces_of(family);
+
+                best_match(&self.faces, &candidates, properties)
+            });
+        }
+
+        best_match(&self.faces, &self.faces_of(family), properties)
+    }
+
+    fn faces_of(&self, family: &str) -> Vec<usize> {
+        self.faces
+            .iter()
+            .enumerate()
+            .filter(|(_, face)| {
+                face.families
+                    .iter()
+                    .any(|name| name.eq_ignore_ascii_case(family))
+            })
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    fn data(&mut self, face: usize) -> Option<Arc<Vec<u8>>> {
+        let source = &mut self.sources[self.faces[face].source];
+
+        source
+            .data
+            .get_or_insert_with(|| {
+                let path = source.path.as_ref()?;
+
+                match fs::read(path) {
+                    Ok(data) => Some(Arc::new(data)),
+
+                    Err(err) => {
+                        tracing::error!("failed to read font {}: {}", path.display(), err);
+
+                        None
+                    }
+                }
+            })
+            .clone()
+    }
+
+    fn font(&mut self, face: usize) -> Option<Font> {
+        let data = self.data(face)?;
+
+        Some(Font::new(FontData::Bytes(data)).with_index(self.faces[face].index))
+    }
+
+    fn has_glyph(&mut self, face: usize, ch: char) -> bool {
+        let Some(data) = self.data(face) else {
+            return false;
+        };
+
+        ttf_parser::Face::parse(&data, self.faces[face].index)
+            .is_ok_and(|face| face.glyph_index(ch).is_some())
+    }
+}
+
+/// Returns the names of the face's family, preferring its typographic family, which groups
+/// more weights and widths together than its legacy family.
+fn family_names(face: &ttf_parser::Face) -> Vec<String> {
+    let names = |name_id| {
+        let mut names = Vec::<String>::new();
+
+        for name in face.names() {
+            if name.name_id != name_id {
+                continue;
+            }
+
+            if let Some(name) = name.to_string().filter(|name| !names.contains(name)) {
+                names.push(name);
+            }
+        }
+
+        names
+    };
+
+    let typographic = names(ttf_parser::name_id::TYPOGRAPHIC_FAMILY);
+
+    if typographic.is_empty() {
+        return names(ttf_parser::name_id::FAMILY);
+    }
+
+    let mut families = typographic;
+
+    // The legacy family is still matched, such as "DejaVu Sans Condensed"
+    for name in names(ttf_parser::name_id::FAMILY) {
+        if !families.contains(&name) {
+            families.push(name);
+        }
+    }
+
+    families
+}
+
+/// Picks the candidate that best matches the properties, following the CSS font matching
+/// algorithm: the closest stretch is narrowed down to first, then the style, then the
+/// closest weight.
+fn best_match(
+    faces: &[FaceInfo],
+    candidates: &[usize],
+    properties: FaceProperties,
+) -> Option<usize> {
+    let stretch = closest(
+        properties.stretch,
+        candidates.iter().map(|&idx| faces[idx].properties.stretch),
+        properties.stretch <= FontStretch::Normal,
+    )?;
+
+    let candidates = candidates
+        .iter()
+        .copied()
+        .filter(|&idx| faces[idx].properties.stretch == stretch)
+        .collect::<Vec<_>>();
+
+    let styles = match properties.style {
+        FontStyle::Normal => [FontStyle::Normal, FontStyle::Oblique, FontStyle::Italic],
+        FontStyle::Italic => [FontStyle::Italic, FontStyle::Oblique, FontStyle::Normal],
+        FontStyle::Oblique => [FontStyle::Oblique, FontStyle::Italic, FontStyle::Normal],
+    };
+
+    let style = styles.into_iter().find(|style| {
+        candidates
+            .iter()
+            .any(|&idx| faces[idx].properties.style == *style)
+    })?;
+
+    let candidates = candidates
+        .into_iter()
+        .filter(|&idx| faces[idx].properties.style == style)
+        .collect::<Vec<_>>();
+
+    let weights = candidates.iter().map(|&idx| faces[idx].properties.weight);
+
+    let desired = properties.weight;
+
+    // Weights between normal and medium first look for heavier weights up to medium
+    let weight = if (FontWeight::NORMAL..=FontWeight::MEDIUM).contains(&desired) {
+        weights
+            .clone()
+            .filter(|weight| (desired..=FontWeight::MEDIUM).contains(weight))
+            .min()
+            .or_else(|| weights.clone().filter(|weight| *weight < desired).max())
+            .or_else(|| weights.filter(|weight| *weight > FontWeight::MEDIUM).min())
+    } else {
+        closest(desired, weights, desired < FontWeight::NORMAL)
+    }?;
+
+    candidates
+        .into_iter()
+        .find(|&idx| faces[idx].properties.weight == weight)
+}
+
+/// Returns the available value closest to the desired one, looking first in the preferred
+/// direction.
+fn closest<T>(
+    desired: T,
+    available: impl Iterator<Item = T> + Clone,
+    prefer_lower: bool,
+) -> Option<T>
+where
+    T: Ord + Copy,
+{
+    let lower = available.clone().filter(|value| *value <= desired).max();
+    let higher = available.filter(|value| *value >= desired).min();
+
+    if prefer_lower {
+        lower.or(higher)
+    } else {
+        higher.or(lower)
+    }
+}
+
+fn is_font_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            FONT_EXTENSIONS
+                .iter()
+                .any(|font_extension| extension.eq_ignore_ascii_case(font_extension))
+        })
+}
+
+/// Returns the directories that fonts are installed in, in the same places fontconfig
+/// looks on Linux.
+fn system_font_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+
+    let home = std::env::var_os("HOME").map(PathBuf::from);
+
+    let data_dirs = std::env::var("XDG_DATA_DIRS")
+        .ok()
+        .filter(|dirs| !dirs.is_empty())
+        .unwrap_or_else(|| String::from("/usr/local/share:/usr/share"));
+
+    for dir in data_dirs.split(':').filter(|dir| !dir.is_empty()) {
+        dirs.push(Path::new(dir).join("fonts"));
+    }
+
+    let data_home = std::env::var_os("XDG_DATA_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| home.as_ref().map(|home| home.join(".local/share")));
+
+    dirs.extend(data_home.map(|dir| dir.join("fonts")));
+    dirs.extend(home.as_ref().map(|home| home.join(".fonts")));
+
+    if cfg!(target_os = "macos") {
+        dirs.push(PathBuf::from("/System/Library/Fonts"));
+        dirs.push(PathBuf::from("/Library/Fonts"));
+        dirs.extend(home.as_ref().map(|home| home.join("Library/Fonts")));
+    }
+
+    if cfg!(target_os = "windows") {
+        if let Some(windir) = std::env::var_os("WINDIR") {
+            dirs.push(PathBuf::from(windir).join("Fonts"));
+        }
+    }
+
+    dirs
+}
+
+#[cfg(test)]
+mod tests {
+    use agui_core::{
+        engine::rendering::mock::DEJA_VU_SANS,
+        unit::{FontStretch, FontStyle, FontWeight},
+    };
+
+    use super::{best_match, FaceInfo, FaceProperties, FontDatabase};
+
+    #[test]
+    fn matches_registered_families() {
+        let mut database = FontDatabase::new();
+
+        database.load_font_data(DEJA_VU_SANS.to_vec());
+
+        assert_eq!(database.len(), 1);
+
+        let query = |database: &mut FontDatabase, family: &str| {
+            database.query(
+                family,
+                FontWeight::BOLD,
+                FontStyle::Italic,
+                FontStretch::Condensed,
+            )
+        };
+
+        // The closest face is used when there's no exact match
+        assert!(query(&mut database, "DejaVu Sans").is_some());
+        assert!(query(&mut database, "dejavu sans").is_some());
+        assert!(query(&mut database, "sans-serif").is_some());
+
+        assert!(query(&mut database, "serif").is_none());
+        assert!(query(&mut database, "missing").is_none());
+    }
+
+    #[test]
+    fn falls_back_to_faces_with_the_glyph() {
+        let mut database = FontDatabase::new();
+
+        database.load_font_data(DEJA_VU_SANS.to_vec());
+        database.set_fallback_families(["missing", "DejaVu Sans"]);
+
+        let fallback = |database: &mut FontDatabase, ch| {
+            database.fallback(
+                ch,
+                FontWeight::NORMAL,
+                FontStyle::Normal,
+                FontStretch::Normal,
+            )
+        };
+
+        assert!(fallback(&mut database, 'a').is_some());
+
+        // DejaVu Sans doesn't have any CJK characters
+        assert!(fallback(&mut database, '你').is_none());
+    }
+
+    #[test]
+    fn weights_are_matched_like_css() {
+        let face = |weight, style| FaceInfo {
+            source: 0,
+            index: 0,
+
+            families: vec![String::from("Family")],
+            properties: FaceProperties {
+                weight: FontWeight(weight),
+                style,
+                stretch: FontStretch::Normal,
+            },
+        };
+
+        let faces = [
+            face(300, FontStyle::Normal),
+            face(400, FontStyle::Normal),
+            face(600, FontStyle::Normal),
+            face(700, FontStyle::Italic),
+        ];
+
+        let candidates = [0, 1, 2, 3];
+
+        let matched = |weight, style| {
+            best_match(
+                &faces,
+                &candidates,
+                FaceProperties {
+                    weight: FontWeight(weight),
+                    style,
+                    stretch: FontStretch::Condensed,
+                },
+            )
+        };
+
+        assert_eq!(matched(400, FontStyle::Normal), Some(1));
+
+        // Between normal and medium, lighter weights are preferred over heavier ones
+        assert_eq!(matched(500, FontStyle::Normal), Some(1));
+
+        assert_eq!(matched(200, FontStyle::Normal), Some(0));
+        assert_eq!(matched(800, FontStyle::Normal), Some(2));
+        assert_eq!(matched(650, FontStyle::Normal), Some(2));
+
+        // The style is matched before the weight
+        assert_eq!(matched(100, FontStyle::Oblique), Some(3));
+    }
+}
@@ -0,0 +1,213 @@
+use std::sync::Arc;
+
+use agui_core::unit::{Font, FontData, FontId, FontStretch, FontStyle, FontWeight, TextStyle};
+use rustc_hash::FxHashMap;
+
+use crate::database::FontDatabase;
+
+/// Loads and caches the data of the fonts used to lay out text.
+///
+/// Font families are looked up in a [`FontDatabase`]. Unless the fonts were created with
+/// their own database, the system's fonts are added to it the first time a family or a
+/// fallback is needed.
+///
+/// Layout never waits on a font to load, so fonts that refer to a URL must be loaded
+/// beforehand, such as with [`AssetResolver::resolve_font`]. Text in a font that hasn't
+/// been loaded is laid out empty.
+///
+/// [`AssetResolver::resolve_font`]: agui_core::asset::AssetResolver::resolve_font
+pub struct TextFonts {
+    fonts: FxHashMap<FontId, Option<Arc<Vec<u8>>>>,
+
+    database: FontDatabase,
+
+    /// The face each family was resolved to, for a weight, style and stretch.
+    families: FxHashMap<(Font, FontWeight, FontStyle, FontStretch), Option<Font>>,
+
+    /// Whether the system's fonts still need to be added to the database.
+    load_system_fonts: bool,
+}
+
+impl Default for TextFonts {
+    fn default() -> Self {
+        Self {
+            fonts: FxHashMap::default(),
+
+            database: FontDatabase::new(),
+            families: FxHashMap::default(),
+
+            load_system_fonts: true,
+        }
+    }
+}
+
+impl TextFonts {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates fonts that look up families in the given database, without adding the
+    /// system's fonts to it.
+    pub fn with_database(database: FontDatabase) -> Self {
+        Self {
+            fonts: FxHashMap::default(),
+
+            database,
+            families: FxHashMap::default(),
+
+            load_system_fonts: false,
+        }
+    }
+
+    /// Returns the database that font families are looked up in, so that fonts can be
+    /// registered with it.
+    pub fn database(&mut self) -> &mut FontDatabase {
+        // Fonts may be registered that match families better
+        self.families.clear();
+
+        self.loaded_database()
+    }
+
+    /// Returns the database with the system's fonts added to it, if they haven't been yet.
+    fn loaded_database(&mut self) -> &mut FontDatabase {
+        if std::mem::take(&mut self.load_system_fonts) {
+            self.database.load_system_fonts();
+        }
+
+        &mut self.database
+    }
+
+    /// Returns the data of the font. If the font doesn't hold its data or could not be
+    /// parsed, `None` is returned and text using it will be laid out empty.
+    ///
+    /// Font families need to be resolved to a face with [`TextFonts::resolve`] first, and
+    /// fonts that refer to a URL need to have been loaded.
+    pub fn get_or_insert(&mut self, font: &Font) -> Option<&Arc<Vec<u8>>> {
+        let data = match font.as_ref() {
+            FontData::Bytes(data) => data,
+
+            // Neither is remembered, so the font may be used once it has been loaded
+            FontData::Url(url) => {
+                tracing::debug!("font {} has not been loaded", url);
+
+                return None;
+            }
+
+            // A family may resolve to a face within a collection, which needs its index
+            FontData::Family(family) => {
+                tracing::error!("font family {} must be resolved with a style", family);
+
+                return None;
+            }
+        };
+
+        let id = font.id();
+
+        if !self.fonts.contains_key(&id) {
+            let data = match ttf_parser::Face::parse(data, font.index()) {
+                Ok(_) => Some(Arc::clone(data)),
+                Err(err) => {
+                    tracing::error!("failed to parse font {:?}: {}", font, err);
+                    None
+                }
+            };
+
+            self.fonts.insert(id.clone(), data);
+        }
+
+        self.fonts.get(&id).and_then(Option::as_ref)
+    }
+
+    /// Returns the font the style's text is drawn with, which always contains the font's
+    /// data. A font family is resolved to the face that best matches the style's weight,
+    /// style and stretch.
+    pub fn resolve(&mut self, style: &TextStyle) -> Option<Font> {
+        match style.font.as_ref() {
+            FontData::Family(family) => {
+                let key = (
+                    style.font.clone(),
+                    style.font_weight,
+                    style.font_style,
+                    style.font_stretch,
+                );
+
+                if let Some(font) = self.families.get(&key) {
+                    return font.clone();
+                }
+
+                let font = self.loaded_database().query(
+                    family,
+                    style.font_weight,
+                    style.font_style,
+                    style.font_stretch,
+                );
+
+                if font.is_none() {
+                    tracing::error!("font family not found: {}", family);
+                }
+
+                self.families.insert(key, font.clone());
+
+                font
+            }
+
+            FontData::Bytes(_) | FontData::Url(_) => {
+                self.get_or_insert(&style.font).map(|_| style.font.clone())
+            }
+        }
+    }
+
+    /// Returns a font that has a glyph for the character, for text in the style whose own
+    /// font doesn't have one.
+    pub fn fallback(&mut self, style: &TextStyle, ch: char) -> Option<Font> {
+        self.loaded_database()
+            .fallback(ch, style.font_weight, style.font_style, style.font_stretch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use agui_core::{
+        engine::rendering::mock::{deja_vu_sans, DEJA_VU_SANS},
+        unit::{Font, FontData, TextStyle},
+    };
+
+    use super::TextFonts;
+    use crate::database::FontDatabase;
+
+    #[test]
+    fn families_stay_resolved_until_fonts_are_registered() {
+        let mut fonts = TextFonts::with_database(FontDatabase::new());
+
+        let style = TextStyle::default().font(Font::from_family("DejaVu Sans"));
+
+        assert!(fonts.resolve(&style).is_none());
+
+        // Laying out text doesn't forget the families it has resolved
+        assert!(fonts.fallback(&style, 'a').is_none());
+        assert_eq!(fonts.families.len(), 1);
+
+        fonts.database().load_font_data(DEJA_VU_SANS.to_vec());
+
+        let font = fonts.resolve(&style).expect("family was registered");
+
+        assert!(matches!(font.as_ref(), FontData::Bytes(_)));
+        assert_eq!(fonts.resolve(&style), Some(font));
+    }
+
+    #[test]
+    fn fonts_are_cached_by_the_identity_of_their_data() {
+        let mut fonts = TextFonts::new();
+
+        let font = deja_vu_sans();
+
+        assert!(fonts.get_or_insert(&font).is_some());
+        assert!(fonts.get_or_insert(&font.clone()).is_some());
+        assert_eq!(fonts.fonts.len(), 1);
+
+        assert!(fonts
+            .get_or_insert(&Font::from_bytes(vec![0; 16]))
+            .is_none());
+        assert_eq!(fonts.fonts.len(), 2);
+    }
+}
@@ -0,0 +1,1851 @@
+use std::ops::Range;
+
+use agui_core::unit::{
+    Constraints, EllipsisPosition, Font, FontData, GlyphRun, HorizontalAlign, Paragraph,
+    ParagraphSpan, PositionedGlyph, Rect, Size, TextDirection, TextLine, TextOverflow, TextStyle,
+    VerticalAlign,
+};
+use rustc_hash::FxHashSet;
+use rustybuzz::Face;
+use unicode_bidi::{BidiInfo, Level};
+use unicode_linebreak::BreakOpportunity;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{
+    fonts::TextFonts,
+    shaping::{self, FontMetrics, ShapedGlyph},
+};
+
+/// Widths are compared with a little tolerance, so that text laid out at exactly its
+/// measured width doesn't wrap due to rounding.
+const WIDTH_TOLERANCE: f32 = 1e-3;
+
+/// Replaces text that's elided when a line overflows.
+const ELLIPSIS: &str = "\u{2026}";
+
+/// Shapes `text` and wraps it into a paragraph that fits within the constraints.
+///
+/// Lines are broken at Unicode line break opportunities (UAX #14). If a single word is
+/// too wide to fit on a line by itself, it's broken between grapheme clusters instead.
+/// Lines are aligned within the resulting size according to the style.
+///
+/// If the style doesn't allow soft wrapping, lines only break at mandatory breaks. Any
+/// lines past the style's `max_lines` are dropped, and if its overflow is an ellipsis,
+/// the last line that's kept and any line that's too wide are elided.
+pub fn layout_paragraph(
+    fonts: &mut TextFonts,
+    style: &TextStyle,
+    text: &str,
+    constraints: Constraints,
+) -> Paragraph {
+    layout_rich_paragraph(fonts, style, text, &[], constraints)
+}
+
+/// Shapes `text`, where parts of it are styled by `spans`, and wraps it into a paragraph
+/// that fits within the constraints.
+///
+/// The spans flow together as if they were a single piece of text, so lines may break
+/// within a span or between two of them, and each line is as tall as the tallest span
+/// on it. Text that isn't covered by a span is laid out with `style`. If a span's font
+/// can't be loaded, the paragraph's font is used instead.
+///
+/// Font families are resolved to the face that best matches each style. Characters that
+/// a style's font doesn't have are drawn with a fallback font that does, if there is one.
+///
+/// Bidirectional text is resolved with the Unicode Bidirectional Algorithm (UAX #9), and
+/// each line's runs are reordered visually. A paragraph takes its base direction from the
+/// style, or from its first strongly directional character if the style doesn't set one.
+pub fn layout_rich_paragraph(
+    fonts: &mut TextFonts,
+    style: &TextStyle,
+    text: &str,
+    spans: &[ParagraphSpan],
+    constraints: Constraints,
+) -> Paragraph {
+    let Some(default_font) = fonts.resolve(style) else {
+        return Paragraph {
+            size: constraints.smallest(),
+
+            ..Paragraph::default()
+        };
+    };
+
+    let styles = Some(style)
+        .into_iter()
+        .chain(spans.iter().filter_map(|span| match span {
+            ParagraphSpan::Text { style, .. } => Some(style),
+            ParagraphSpan::Placeholder { .. } => None,
+        }))
+        .collect::<Vec<_>>();
+
+    let style_fonts = styles
+        .iter()
+        .map(|style| fonts.resolve(style).unwrap_or_else(|| default_font.clone()))
+        .collect::<Vec<_>>();
+
+    let Some(mut faces) = style_fonts
+        .iter()
+        .map(|font| Face::from_slice(font_data(font), font.index()))
+        .collect::<Option<Vec<_>>>()
+    else {
+        return Paragraph {
+            size: constraints.smallest(),
+
+            ..Paragraph::default()
+        };
+    };
+
+    let metrics = styles
+        .iter()
+        .zip(&faces)
+        .map(|(style, face)| FontMetrics::new(face, style.size))
+        .collect::<Vec<_>>();
+
+    let line_metrics = styles
+        .iter()
+        .zip(&metrics)
+        .map(|(style, metrics)| LineMetrics::new(metrics, style))
+        .collect::<Vec<_>>();
+
+    let items = Item::resolve(text, spans, &line_metrics);
+
+    let (fallbacks, fallback_fonts) = resolve_fallbacks(fonts, text, &items, &styles, &faces);
+
+    faces.extend(fallback_fonts.iter().map(|font| {
+        Face::from_slice(font_data(font), font.index())
+            .expect("fonts are only fallen back to if they can be parsed")
+    }));
+
+    // The font of each style, followed by the fallback fonts
+    let resolved = style_fonts
+        .iter()
+        .chain(&fallback_fonts)
+        .cloned()
+        .collect::<Vec<_>>();
+
+    let mut breaker = LineBreaker {
+        max_width: if style.soft_wrap {
+            constraints.max_width()
+        } else {
+            f32::INFINITY
+        },
+
+        lines: Vec::new(),
+
+        min_intrinsic_width: 0.0,
+        max_intrinsic_width: 0.0,
+    };
+
+    let default_level = style.text_direction.map(|direction| match direction {
+        TextDirection::LeftToRight => Level::ltr(),
+        TextDirection::RightToLeft => Level::rtl(),
+    });
+
+    let bidi = BidiInfo::new(text, default_level);
+
+    // Returns the base level of the paragraph that contains `offset`
+    let paragraph_level = |offset: usize| {
+        bidi.paragraphs
+            .iter()
+            .find(|paragraph| paragraph.range.contains(&offset))
+            .or(bidi.paragraphs.last())
+            .map_or(default_level.unwrap_or_else(Level::ltr), |paragraph| {
+                paragraph.level
+            })
+    };
+
+    let mut allowed_breaks = FxHashSet::default();
+
+    let mut line_start = 0;
+
+    for (idx, opportunity) in unicode_linebreak::linebreaks(text) {
+        match opportunity {
+            BreakOpportunity::Allowed => {
+                allowed_breaks.insert(idx);
+            }
+
+            BreakOpportunity::Mandatory => {
+                let content = text[line_start..idx].trim_end_matches(is_line_terminator);
+                let content = line_start..line_start + content.len();
+
+                let mut glyphs = Vec::new();
+                let mut segments = Vec::new();
+
+                for item in &items {
+                    let range =
+                        item.range.start.max(content.start)..item.range.end.min(content.end);
+
+                    if range.is_empty() {
+                        continue;
+                    }
+
+                    // Each run of a single embedding level is shaped in its own direction, and
+                    // each run of a single font is shaped with that font
+                    for (range, level) in level_runs(&bidi.levels, range) {
+                        for (range, kind) in font_runs(item.kind, range, &fallbacks) {
+                            let glyphs_start = glyphs.len();
+
+                            match kind {
+                                ItemKind::Text { style, font } => {
+                                    let fallback_metrics;
+
+                                    let font_metrics = if font == style {
+                                        &metrics[style]
+                                    } else {
+                                        fallback_metrics =
+                                            FontMetrics::new(&faces[font], styles[style].size);
+
+                                        &fallback_metrics
+                                    };
+
+                                    let mut shaped = shaping::shape(
+                                        &faces[font],
+                                        font_metrics,
+                                        &text[range.clone()],
+                                        range.start,
+                                        level.is_rtl(),
+                                    );
+
+                                    apply_spacing(text, &mut shaped, styles[style]);
+
+                                    glyphs.extend(shaped);
+                                }
+
+                                ItemKind::Placeholder { size, .. } => {
+                                    glyphs.push(ShapedGlyph {
+                                        id: 0,
+
+                                        cluster: range.start,
+
+                                        advance: size.width,
+
+                                        x_offset: 0.0,
+                                        y_offset: 0.0,
+                                    });
+                                }
+                            }
+
+                            segments.push(Segment {
+                                kind,
+
+                                range,
+                                glyphs: glyphs_start..glyphs.len(),
+                                level,
+
+                                metrics: item.metrics,
+                            });
+                        }
+                    }
+                }
+
+                breaker.break_lines(
+                    text,
+                    &allowed_breaks,
+                    &glyphs,
+                    &segments,
+                    paragraph_level(content.start),
+                    content.clone(),
+                    idx,
+                    Item::metrics_at(&items, content.start),
+                );
+
+                line_start = idx;
+            }
+        }
+    }
+
+    // A trailing line break starts a new, empty line, and empty text still has a line
+    if breaker.lines.is_empty() || text.ends_with(is_line_terminator) {
+        breaker.lines.push(PendingLine {
+            range: text.len()..text.len(),
+
+            runs: Vec::new(),
+            level: paragraph_level(text.len()),
+
+            width: 0.0,
+            trailing_whitespace: 0.0,
+
+            metrics: Item::metrics_at(&items, text.len()),
+        });
+    }
+
+    if !style.soft_wrap {
+        breaker.min_intrinsic_width = breaker.max_intrinsic_width;
+    }
+
+    let did_exceed_max_lines = style
+        .max_lines
+        .is_some_and(|max_lines| breaker.lines.len() > max_lines);
+
+    if let Some(max_lines) = style.max_lines {
+        breaker.lines.truncate(max_lines);
+    }
+
+    if let TextOverflow::Ellipsis(position) = style.overflow {
+        // The ellipsis is shaped in the style of the text it's placed next to
+        let ellipses = styles
+            .iter()
+            .zip(&faces)
+            .zip(&metrics)
+            .map(|((style, face), metrics)| {
+                let mut glyphs = shaping::shape(face, metrics, ELLIPSIS, 0, false);
+
+                apply_spacing(ELLIPSIS, &mut glyphs, style);
+
+                glyphs
+            })
+            .collect::<Vec<_>>();
+
+        let line_count = breaker.lines.len();
+
+        for (idx, line) in breaker.lines.iter_mut().enumerate() {
+            if did_exceed_max_lines && idx == line_count - 1 {
+                line.elide(
+                    text,
+                    EllipsisPosition::End,
+                    constraints.max_width(),
+                    &ellipses,
+                );
+            } else if line.width > constraints.max_width() + WIDTH_TOLERANCE {
+                line.elide(text, position, constraints.max_width(), &ellipses);
+            }
+        }
+    }
+
+    let content_size = Size::new(
+        breaker
+            .lines
+            .iter()
+            .map(|line| line.width)
+            .fold(0.0, f32::max),
+        breaker.lines.iter().map(|line| line.metrics.height()).sum(),
+    );
+
+    let size = constraints.constrain(content_size);
+
+    let mut top = match style.v_align {
+        VerticalAlign::Top => 0.0,
+        VerticalAlign::Center => (size.height - content_size.height) / 2.0,
+        VerticalAlign::Bottom => size.height - content_size.height,
+    };
+
+    let mut placeholders = vec![
+        Rect::default();
+        spans
+            .iter()
+            .filter(|span| matches!(span, ParagraphSpan::Placeholder { .. }))
+            .count()
+    ];
+
+    let lines = breaker
+        .lines
+        .into_iter()
+        .map(|line| {
+            let height = line.metrics.height();
+            let baseline = top + line.metrics.ascent;
+
+            // The start of a line is on the right if its paragraph is right-to-left
+            let left = match (style.h_align, line.level.is_rtl()) {
+                (HorizontalAlign::Start, false) | (HorizontalAlign::End, true) => 0.0,
+                (HorizontalAlign::Center, _) => (size.width - line.width) / 2.0,
+                (HorizontalAlign::Start, true) | (HorizontalAlign::End, false) => {
+                    size.width - line.width
+                }
+            };
+
+            let runs = line.place(&styles, &resolved, left, baseline, &mut placeholders);
+
+            let line = TextLine {
+                range: line.range,
+
+                top,
+                baseline,
+                height,
+
+                left,
+                width: line.width,
+
+                runs,
+            };
+
+            top += height;
+
+            line
+        })
+        .collect();
+
+    Paragraph {
+        size,
+
+        lines,
+
+        placeholders,
+
+        min_intrinsic_width: breaker.min_intrinsic_width,
+        max_intrinsic_width: breaker.max_intrinsic_width,
+
+        did_exceed_max_lines,
+    }
+}
+
+/// Returns the data of a font that's been resolved.
+fn font_data(font: &Font) -> &[u8] {
+    match font.as_ref() {
+        FontData::Bytes(bytes) => bytes,
+
+        _ => unreachable!("resolved fonts always contain bytes"),
+    }
+}
+
+/// Finds the grapheme clusters of the text that the font of their style doesn't have,
+/// and the fonts they fall back to, which are numbered after the fonts of the styles.
+///
+/// Returns the ranges of the text that fall back, in order, along with the index of their
+/// font. A cluster falls back as a whole, to a font that has its first character.
+fn resolve_fallbacks(
+    fonts: &mut TextFonts,
+    text: &str,
+    items: &[Item],
+    styles: &[&TextStyle],
+    faces: &[Face],
+) -> (Vec<(Range<usize>, usize)>, Vec<Font>) {
+    let mut fallbacks = Vec::<(Range<usize>, usize)>::new();
+    let mut fallback_fonts = Vec::<Font>::new();
+
+    for item in items {
+        let ItemKind::Text { style, .. } = item.kind else {
+            continue;
+        };
+
+        for (offset, grapheme) in text[item.range.clone()].grapheme_indices(true) {
+            let start = item.range.start + offset;
+
+            let is_missing = grapheme
+                .chars()
+                .any(|ch| !ch.is_control() && faces[style].glyph_index(ch).is_none());
+
+            if !is_missing {
+                continue;
+            }
+
+            let Some(fallback) = grapheme
+                .chars()
+                .next()
+                .and_then(|ch| fonts.fallback(styles[style], ch))
+            else {
+                continue;
+            };
+
+            let font = match fallback_fonts.iter().position(|font| *font == fallback) {
+                Some(idx) => styles.len() + idx,
+
+                None => {
+                    fallback_fonts.push(fallback);
+
+                    styles.len() + fallback_fonts.len() - 1
+                }
+            };
+
+            // Neighbouring clusters that fall back to the same font are shaped together
+            match fallbacks.last_mut() {
+                Some((last, last_font)) if last.end == start && *last_font == font => {
+                    last.end = start + grapheme.len();
+                }
+
+                _ => fallbacks.push((start..start + grapheme.len(), font)),
+            }
+        }
+    }
+
+    (fallbacks, fallback_fonts)
+}
+
+/// Splits a range of an item into the ranges that are shaped with each font.
+fn font_runs(
+    kind: ItemKind,
+    range: Range<usize>,
+    fallbacks: &[(Range<usize>, usize)],
+) -> Vec<(Range<usize>, ItemKind)> {
+    let ItemKind::Text { style, .. } = kind else {
+        return vec![(range, kind)];
+    };
+
+    let mut runs = Vec::new();
+    let mut start = range.start;
+
+    for (fallback, font) in fallbacks
+        .iter()
+        .filter(|(fallback, _)| fallback.start < range.end && fallback.end > range.start)
+    {
+        let fallback = fallback.start.max(range.start)..fallback.end.min(range.end);
+
+        if fallback.start > start {
+            runs.push((start..fallback.start, kind));
+        }
+
+        runs.push((fallback.clone(), ItemKind::Text { style, font: *font }));
+
+        start = fallback.end;
+    }
+
+    if start < range.end {
+        runs.push((start..range.end, kind));
+    }
+
+    runs
+}
+
+/// Adds the style's letter spacing after the last glyph of each cluster, and its word
+/// spacing after whitespace. The glyphs must be in logical order.
+fn apply_spacing(text: &str, glyphs: &mut [ShapedGlyph], style: &TextStyle) {
+    if style.letter_spacing == 0.0 && style.word_spacing == 0.0 {
+        return;
+    }
+
+    for idx in 0..glyphs.len() {
+        let cluster = glyphs[idx].cluster;
+
+        if glyphs
+            .get(idx + 1)
+            .is_some_and(|next| next.cluster == cluster)
+        {
+            continue;
+        }
+
+        glyphs[idx].advance += style.letter_spacing;
+
+        if text[cluster..]
+            .chars()
+            .next()
+            .is_some_and(char::is_whitespace)
+        {
+            glyphs[idx].advance += style.word_spacing;
+        }
+    }
+}
+
+/// The vertical extent of the content of a line, relative to its baseline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct LineMetrics {
+    ascent: f32,
+    descent: f32,
+    line_gap: f32,
+}
+
+impl LineMetrics {
+    /// Returns the metrics of a line of text in the font with the given style. If the
+    /// style sets a line height, the difference from the font's own line height is split
+    /// evenly above and below the text.
+    fn new(metrics: &FontMetrics, style: &TextStyle) -> Self {
+        let Some(line_height) = style.line_height else {
+            return Self {
+                ascent: metrics.ascent,
+                descent: metrics.descent,
+                line_gap: metrics.line_gap,
+            };
+        };
+
+        let leading = line_height * style.size - (metrics.ascent + metrics.descent);
+
+        Self {
+            ascent: metrics.ascent + leading / 2.0,
+            descent: metrics.descent + leading / 2.0,
+            line_gap: 0.0,
+        }
+    }
+
+    fn height(&self) -> f32 {
+        self.ascent + self.descent + self.line_gap
+    }
+
+    fn max(self, other: Self) -> Self {
+        Self {
+            ascent: self.ascent.max(other.ascent),
+            descent: self.descent.max(other.descent),
+            line_gap: self.line_gap.max(other.line_gap),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ItemKind {
+    /// Text laid out with the style at the given index, in the font at the given index.
+    /// The font of each style has the same index as the style, and fallback fonts follow.
+    Text { style: usize, font: usize },
+
+    /// The placeholder at the given index.
+    Placeholder { index: usize, size: Size },
+}
+
+/// A range of the source text that's laid out in a single way.
+struct Item {
+    range: Range<usize>,
+
+    kind: ItemKind,
+    metrics: LineMetrics,
+}
+
+impl Item {
+    /// Splits the text into items that cover all of it, in order. There's always at least
+    /// one item.
+    fn resolve(text: &str, spans: &[ParagraphSpan], metrics: &[LineMetrics]) -> Vec<Self> {
+        let mut items = Vec::new();
+
+        let mut end = 0;
+
+        let mut next_style = 1;
+        let mut next_placeholder = 0;
+
+        let default_item = |range: Range<usize>| Self {
+            range,
+
+            kind: ItemKind::Text { style: 0, font: 0 },
+            metrics: metrics[0],
+        };
+
+        for span in spans {
+            let kind = match *span {
+                ParagraphSpan::Text { .. } => {
+                    next_style += 1;
+
+                    ItemKind::Text {
+                        style: next_style - 1,
+                        font: next_style - 1,
+                    }
+                }
+
+                ParagraphSpan::Placeholder { size, .. } => {
+                    next_placeholder += 1;
+
+                    ItemKind::Placeholder {
+                        index: next_placeholder - 1,
+                        size,
+                    }
+                }
+            };
+
+            let range = span.range();
+            let range = range.start.max(end)..range.end.min(text.len());
+
+            if range.is_empty() {
+                continue;
+            }
+
+            if range.start > end {
+                items.push(default_item(end..range.start));
+            }
+
+            end = range.end;
+
+            items.push(Self {
+                range,
+
+                metrics: match kind {
+                    ItemKind::Text { style, .. } => metrics[style],
+
+                    ItemKind::Placeholder { size, .. } => LineMetrics {
+                        ascent: size.height,
+                        descent: 0.0,
+                        line_gap: 0.0,
+                    },
+                },
+
+                kind,
+            });
+        }
+
+        // Empty text still has an item, so that its line has metrics
+        if end < text.len() || items.is_empty() {
+            items.push(default_item(end..text.len()));
+        }
+
+        items
+    }
+
+    /// Returns the metrics of an empty line at `offset`.
+    fn metrics_at(items: &[Self], offset: usize) -> LineMetrics {
+        items
+            .iter()
+            .rev()
+            .find(|item| item.range.start <= offset && matches!(item.kind, ItemKind::Text { .. }))
+            .or_else(|| items.first())
+            .expect("items cover the whole text")
+            .metrics
+    }
+}
+
+/// The glyphs shaped from an item, within a single paragraph of the source text.
+struct Segment {
+    kind: ItemKind,
+
+    range: Range<usize>,
+    glyphs: Range<usize>,
+    level: Level,
+
+    metrics: LineMetrics,
+}
+
+struct PendingLine {
+    range: Range<usize>,
+
+    /// The runs of the line, in logical order.
+    runs: Vec<PendingRun>,
+
+    /// The base embedding level of the line's paragraph.
+    level: Level,
+
+    width: f32,
+    trailing_whitespace: f32,
+
+    metrics: LineMetrics,
+}
+
+struct PendingRun {
+    kind: ItemKind,
+
+    range: Range<usize>,
+
+    /// The glyphs of the run, in logical order.
+    glyphs: Vec<ShapedGlyph>,
+    level: Level,
+}
+
+impl PendingLine {
+    /// Positions the glyphs of the line, returning its glyph runs and recording where its
+    /// placeholders were placed.
+    fn place(
+        &self,
+        styles: &[&TextStyle],
+        fonts: &[Font],
+        left: f32,
+        baseline: f32,
+        placeholders: &mut [Rect],
+    ) -> Vec<GlyphRun> {
+        let mut pen_x = left;
+
+        // Trailing whitespace takes the paragraph's direction, so in right-to-left text it's
+        // placed on the left, where it's pushed outside of the line's bounds
+        if self.level.is_rtl() {
+            pen_x -= self.trailing_whitespace;
+        }
+
+        let mut runs = Vec::with_capacity(self.runs.len());
+
+        let mut place_run = |run: &PendingRun| {
+            let mut place = |glyph: &ShapedGlyph| {
+                let positioned = PositionedGlyph {
+                    id: glyph.id,
+
+                    cluster: glyph.cluster,
+
+                    x: pen_x + glyph.x_offset,
+                    y: baseline + glyph.y_offset,
+
+                    advance: glyph.advance,
+                };
+
+                pen_x += glyph.advance;
+
+                positioned
+            };
+
+            let glyphs: Vec<_> = if run.level.is_rtl() {
+                run.glyphs.iter().rev().map(&mut place).collect()
+            } else {
+                run.glyphs.iter().map(&mut place).collect()
+            };
+
+            match run.kind {
+                ItemKind::Text { style, font } => runs.push(GlyphRun {
+                    font: fonts[font].clone(),
+                    font_size: styles[style].size,
+
+                    range: run.range.clone(),
+                    rtl: run.level.is_rtl(),
+
+                    glyphs,
+                }),
+
+                ItemKind::Placeholder { index, size } => {
+                    placeholders[index] =
+                        Rect::new(glyphs[0].x, baseline - size.height, size.width, size.height);
+                }
+            }
+        };
+
+        let levels = self.runs.iter().map(|run| run.level).collect::<Vec<_>>();
+
+        for idx in BidiInfo::reorder_visual(&levels) {
+            place_run(&self.runs[idx]);
+        }
+
+        runs
+    }
+}
+
+impl PendingLine {
+    /// Removes clusters from the line at `position` until it fits within `max_width`
+    /// along with an ellipsis, which takes their place. The ellipsis is added even if
+    /// nothing needs to be removed, and is shaped in the style of the text it replaces.
+    fn elide(
+        &mut self,
+        text: &str,
+        position: EllipsisPosition,
+        max_width: f32,
+        ellipses: &[Vec<ShapedGlyph>],
+    ) {
+        // The clusters of the line, as the index of their run and their glyphs within it
+        let mut clusters = Vec::<(usize, Range<usize>, f32)>::new();
+
+        for (run_idx, run) in self.runs.iter().enumerate() {
+            for (idx, glyph) in run.glyphs.iter().enumerate() {
+                match clusters.last_mut() {
+                    Some((last_run, glyphs, advance))
+                        if *last_run == run_idx
+                            && run.glyphs[glyphs.start].cluster == glyph.cluster =>
+                    {
+                        glyphs.end = idx + 1;
+                        *advance += glyph.advance;
+                    }
+
+                    _ => clusters.push((run_idx, idx..idx + 1, glyph.advance)),
+                }
+            }
+        }
+
+        let cluster_start = |(run_idx, glyphs, _): &(usize, Range<usize>, f32)| {
+            self.runs[*run_idx].glyphs[glyphs.start].cluster
+        };
+
+        let is_whitespace = |cluster: &(usize, Range<usize>, f32)| {
+            text[cluster_start(cluster)..]
+                .chars()
+                .next()
+                .is_some_and(char::is_whitespace)
+        };
+
+        // Trailing whitespace isn't visible, so there's no need to keep it
+        while clusters.last().is_some_and(is_whitespace) {
+            clusters.pop();
+        }
+
+        let content_end = self.range.start
+            + text[self.range.clone()]
+                .trim_end_matches(is_line_terminator)
+                .len();
+
+        let replaced = match position {
+            EllipsisPosition::Start => clusters.first(),
+            EllipsisPosition::Middle => clusters.get(clusters.len() / 2),
+            EllipsisPosition::End => clusters.last(),
+        };
+
+        let ellipsis_style = match replaced.map(|(run_idx, ..)| self.runs[*run_idx].kind) {
+            Some(ItemKind::Text { style, .. }) => style,
+            _ => 0,
+        };
+
+        let ellipsis_width = ellipses[ellipsis_style]
+            .iter()
+            .map(|glyph| glyph.advance)
+            .sum::<f32>();
+
+        let available = max_width - ellipsis_width + WIDTH_TOLERANCE;
+
+        // The number of clusters kept from the start and end of the line
+        let mut front = 0;
+        let mut back = 0;
+        let mut width = 0.0;
+
+        let keep = |idx: usize, width: &mut f32| {
+            if *width + clusters[idx].2 > available {
+                return false;
+            }
+
+            *width += clusters[idx].2;
+
+            true
+        };
+
+        match position {
+            EllipsisPosition::Start => {
+                while back < clusters.len() && keep(clusters.len() - back - 1, &mut width) {
+                    back += 1;
+                }
+            }
+
+            EllipsisPosition::Middle => loop {
+                let mut kept = false;
+
+                if front + back < clusters.len() && keep(front, &mut width) {
+                    front += 1;
+                    kept = true;
+                }
+
+                if front + back < clusters.len() && keep(clusters.len() - back - 1, &mut width) {
+                    back += 1;
+                    kept = true;
+                }
+
+                if !kept {
+                    break;
+                }
+            },
+
+            EllipsisPosition::End => {
+                while front < clusters.len() && keep(front, &mut width) {
+                    front += 1;
+                }
+            }
+        }
+
+        // Whitespace next to the ellipsis would only widen the gap around it
+        while front > 0 && is_whitespace(&clusters[front - 1]) {
+            front -= 1;
+            width -= clusters[front].2;
+        }
+
+        while back > 0 && is_whitespace(&clusters[clusters.len() - back]) {
+            width -= clusters[clusters.len() - back].2;
+            back -= 1;
+        }
+
+        let elided_at = clusters
+            .get(front)
+            .filter(|_| front + back < clusters.len())
+            .map_or(content_end, &cluster_start);
+
+        let ellipsis = PendingRun {
+            kind: ItemKind::Text {
+                style: ellipsis_style,
+                font: ellipsis_style,
+            },
+
+            range: elided_at..elided_at,
+
+            glyphs: ellipses[ellipsis_style]
+                .iter()
+                .map(|glyph| ShapedGlyph {
+                    cluster: elided_at,
+                    ..*glyph
+                })
+                .collect(),
+            level: self.level,
+        };
+
+        let mut runs = Vec::<PendingRun>::new();
+        let mut last_run = None::<usize>;
+        let mut ellipsis = Some(ellipsis);
+
+        for idx in (0..front).chain(clusters.len() - back..clusters.len()) {
+            if idx >= front {
+                if let Some(ellipsis) = ellipsis.take() {
+                    runs.push(ellipsis);
+                    last_run = None;
+                }
+            }
+
+            let cluster = &clusters[idx];
+            let (run_idx, glyphs, _) = cluster;
+            let source = &self.runs[*run_idx];
+
+            // A run ends where the next cluster of its source run starts
+            let end = clusters
+                .get(idx + 1)
+                .filter(|next| next.0 == *run_idx)
+                .map_or(source.range.end, &cluster_start);
+
+            match runs.last_mut().filter(|_| last_run == Some(*run_idx)) {
+                Some(run) => {
+                    run.range.end = end;
+                    run.glyphs.extend_from_slice(&source.glyphs[glyphs.clone()]);
+                }
+
+                None => runs.push(PendingRun {
+                    kind: source.kind,
+
+                    range: cluster_start(cluster)..end,
+
+                    glyphs: source.glyphs[glyphs.clone()].to_vec(),
+                    level: source.level,
+                }),
+            }
+
+            last_run = Some(*run_idx);
+        }
+
+        runs.extend(ellipsis);
+
+        self.runs = runs;
+
+        self.width = width + ellipsis_width;
+        self.trailing_whitespace = 0.0;
+    }
+}
+
+/// A grapheme cluster, which is the smallest unit that text can be broken at.
+struct Cluster {
+    glyphs: Range<usize>,
+    bytes: Range<usize>,
+
+    advance: f32,
+
+    is_whitespace: bool,
+    can_break_after: bool,
+}
+
+struct LineBreaker {
+    max_width: f32,
+
+    lines: Vec<PendingLine>,
+
+    min_intrinsic_width: f32,
+    max_intrinsic_width: f32,
+}
+
+impl LineBreaker {
+    /// Breaks a single paragraph of shaped text, which covers `content` of the source text
+    /// and is followed by a mandatory line break at `end`. If the paragraph is empty, its
+    /// line has the `empty` metrics.
+    #[allow(clippy::too_many_arguments)]
+    fn break_lines(
+        &mut self,
+        text: &str,
+        allowed_breaks: &FxHashSet<usize>,
+        glyphs: &[ShapedGlyph],
+        segments: &[Segment],
+        level: Level,
+        content: Range<usize>,
+        end: usize,
+        empty: LineMetrics,
+    ) {
+        if glyphs.is_empty() {
+            self.lines.push(PendingLine {
+                range: content.start..end,
+
+                runs: Vec::new(),
+                level,
+
+                width: 0.0,
+                trailing_whitespace: 0.0,
+
+                metrics: empty,
+            });
+
+            return;
+        }
+
+        let clusters = Self::clusters(text, allowed_breaks, glyphs, content.end);
+
+        self.measure_intrinsics(&clusters);
+
+        let mut line_start = 0;
+        let mut last_break = None::<usize>;
+        let mut width = 0.0;
+
+        for (idx, cluster) in clusters.iter().enumerate() {
+            // Whitespace is allowed to hang off of the end of a line
+            while !cluster.is_whitespace
+                && idx > line_start
+                && width + cluster.advance > self.max_width + WIDTH_TOLERANCE
+            {
+                let break_at = match last_break {
+                    Some(last_break) if last_break >= line_start => last_break + 1,
+
+                    // There's nowhere to break the line, so break it between clusters
+                    _ => idx,
+                };
+
+                self.push_line(
+                    glyphs,
+                    segments,
+                    level,
+                    &clusters[line_start..break_at],
+                    None,
+                );
+
+                width = clusters[break_at..idx]
+                    .iter()
+                    .map(|cluster| cluster.advance)
+                    .sum();
+
+                line_start = break_at;
+                last_break = None;
+            }
+
+            width += cluster.advance;
+
+            if cluster.can_break_after {
+                last_break = Some(idx);
+            }
+        }
+
+        self.push_line(glyphs, segments, level, &clusters[line_start..], Some(end));
+    }
+
+    fn clusters(
+        text: &str,
+        allowed_breaks: &FxHashSet<usize>,
+        glyphs: &[ShapedGlyph],
+        content_end: usize,
+    ) -> Vec<Cluster> {
+        let mut clusters = Vec::<Cluster>::new();
+
+        for (idx, glyph) in glyphs.iter().enumerate() {
+            match clusters.last_mut() {
+                Some(cluster) if cluster.bytes.start == glyph.cluster => {
+                    cluster.glyphs.end = idx + 1;
+                    cluster.advance += glyph.advance;
+                }
+
+                _ => clusters.push(Cluster {
+                    glyphs: idx..idx + 1,
+                    bytes: glyph.cluster..content_end,
+
+                    advance: glyph.advance,
+
+                    is_whitespace: false,
+                    can_break_after: false,
+                }),
+            }
+        }
+
+        for idx in 1..clusters.len() {
+            clusters[idx - 1].bytes.end = clusters[idx].bytes.start;
+        }
+
+        for cluster in &mut clusters {
+            cluster.is_whitespace = text[cluster.bytes.clone()].chars().all(char::is_whitespace);
+            cluster.can_break_after = allowed_breaks.contains(&cluster.bytes.end);
+        }
+
+        clusters
+    }
+
+    fn measure_intrinsics(&mut self, clusters: &[Cluster]) {
+        let mut word_width = 0.0;
+        let mut word_whitespace = 0.0;
+
+        let mut line_width = 0.0;
+        let mut line_whitespace = 0.0;
+
+        for cluster in clusters {
+            if cluster.is_whitespace {
+                word_whitespace += cluster.advance;
+                line_whitespace += cluster.advance;
+            } else {
+                word_width += word_whitespace + cluster.advance;
+                word_whitespace = 0.0;
+
+                line_width += line_whitespace + cluster.advance;
+                line_whitespace = 0.0;
+            }
+
+            if cluster.can_break_after {
+                self.min_intrinsic_width = self.min_intrinsic_width.max(word_width);
+
+                word_width = 0.0;
+                word_whitespace = 0.0;
+            }
+        }
+
+        self.min_intrinsic_width = self.min_intrinsic_width.max(word_width);
+        self.max_intrinsic_width = self.max_intrinsic_width.max(line_width);
+    }
+
+    fn push_line(
+        &mut self,
+        glyphs: &[ShapedGlyph],
+        segments: &[Segment],
+        level: Level,
+        clusters: &[Cluster],
+        end: Option<usize>,
+    ) {
+        let (Some(first), Some(last)) = (clusters.first(), clusters.last()) else {
+            return;
+        };
+
+        let whitespace = &clusters[clusters
+            .iter()
+            .rposition(|cluster| !cluster.is_whitespace)
+            .map_or(0, |idx| idx + 1)..];
+
+        let trailing_whitespace = whitespace
+            .iter()
+            .map(|cluster| cluster.advance)
+            .sum::<f32>();
+
+        let width = clusters.iter().map(|cluster| cluster.advance).sum::<f32>();
+
+        let range = first.bytes.start..end.unwrap_or(last.bytes.end);
+        let line_glyphs = first.glyphs.start..last.glyphs.end;
+
+        // Trailing whitespace is reset to the paragraph's level (UAX #9, rule L1)
+        let whitespace_glyphs = whitespace
+            .first()
+            .map_or(line_glyphs.end, |cluster| cluster.glyphs.start);
+        let whitespace_bytes = whitespace
+            .first()
+            .map_or(range.end, |cluster| cluster.bytes.start);
+
+        let mut metrics = None::<LineMetrics>;
+        let mut runs = Vec::with_capacity(segments.len());
+
+        for segment in segments {
+            let (split_glyphs, split_bytes) = if segment.level == level {
+                (line_glyphs.end, range.end)
+            } else {
+                (whitespace_glyphs, whitespace_bytes)
+            };
+
+            let pieces = [
+                (
+                    line_glyphs.start..split_glyphs,
+                    range.start..split_bytes,
+                    segment.level,
+                ),
+                (split_glyphs..line_glyphs.end, split_bytes..range.end, level),
+            ];
+
+            for (piece_glyphs, piece_bytes, level) in pieces {
+                let glyphs_range = segment.glyphs.start.max(piece_glyphs.start)
+                    ..segment.glyphs.end.min(piece_glyphs.end);
+
+                if glyphs_range.is_empty() {
+                    continue;
+                }
+
+                metrics =
+                    Some(metrics.map_or(segment.metrics, |metrics| metrics.max(segment.metrics)));
+
+                runs.push(PendingRun {
+                    kind: segment.kind,
+
+                    range: segment.range.start.max(piece_bytes.start)
+                        ..segment.range.end.min(piece_bytes.end),
+
+                    glyphs: glyphs[glyphs_range].to_vec(),
+                    level,
+                });
+            }
+        }
+
+        self.lines.push(PendingLine {
+            range,
+
+            runs,
+            level,
+
+            width: width - trailing_whitespace,
+            trailing_whitespace,
+
+            metrics: metrics.expect("a line with glyphs has at least one segment"),
+        });
+    }
+}
+
+/// Splits `range` of the text into runs that share a single embedding level.
+fn level_runs(
+    levels: &[Level],
+    range: Range<usize>,
+) -> impl Iterator<Item = (Range<usize>, Level)> + '_ {
+    let mut start = range.start;
+
+    std::iter::from_fn(move || {
+        if start >= range.end {
+            return None;
+        }
+
+        let level = levels[start];
+
+        let end = (start..range.end)
+            .find(|&idx| levels[idx] != level)
+            .unwrap_or(range.end);
+
+        let run = start..end;
+
+        start = end;
+
+        Some((run, level))
+    })
+}
+
+fn is_line_terminator(ch: char) -> bool {
+    matches!(
+        ch,
+        '\n' | '\r' | '\u{0B}' | '\u{0C}' | '\u{85}' | '\u{2028}' | '\u{2029}'
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use agui_core::{
+        engine::rendering::mock::{text_style, DEJA_VU_SANS},
+        unit::{
+            Color, Constraints, EllipsisPosition, Font, HorizontalAlign, Paragraph, ParagraphSpan,
+            Rect, Size, TextDirection, TextOverflow, TextStyle, VerticalAlign,
+        },
+    };
+
+    use crate::{database::FontDatabase, fonts::TextFonts};
+
+    use super::{font_runs, layout_paragraph, layout_rich_paragraph, ItemKind};
+
+    fn style() -> TextStyle {
+        text_style(16.0, Color::from_rgb((0.0, 0.0, 0.0)))
+    }
+
+    /// Fonts that don't look for any of the system's fonts.
+    fn fonts() -> TextFonts {
+        TextFonts::with_database(FontDatabase::new())
+    }
+
+    fn layout(text: &str, max_width: f32) -> Paragraph {
+        layout_styled(&style(), text, max_width)
+    }
+
+    fn layout_styled(style: &TextStyle, text: &str, max_width: f32) -> Paragraph {
+        layout_paragraph(
+            &mut fonts(),
+            style,
+            text,
+            Constraints::new(0.0, max_width, 0.0, f32::INFINITY),
+        )
+    }
+
+    /// Returns the text of each run of a line, with ellipses shown as `…`.
+    fn run_texts<'a>(text: &'a str, shaped: &Paragraph, line: usize) -> Vec<&'a str> {
+        shaped.lines[line]
+            .runs
+            .iter()
+            .map(|run| {
+                if run.range.is_empty() {
+                    "\u{2026}"
+                } else {
+                    &text[run.range.clone()]
+                }
+            })
+            .collect()
+    }
+
+    fn line_texts<'a>(text: &'a str, shaped: &Paragraph) -> Vec<&'a str> {
+        shaped
+            .lines
+            .iter()
+            .map(|line| &text[line.range.clone()])
+            .collect()
+    }
+
+    #[test]
+    fn wraps_at_word_boundaries() {
+        let text = "hello wide world";
+
+        let single_line = layout(text, f32::INFINITY);
+
+        assert_eq!(single_line.lines.len(), 1);
+        assert_eq!(single_line.size.width, single_line.max_intrinsic_width);
+
+        let shaped = layout(text, single_line.size.width * 0.75);
+
+        assert_eq!(line_texts(text, &shaped), ["hello wide ", "world"]);
+        assert_eq!(shaped.size.height, shaped.lines[0].height * 2.0);
+
+        // Trailing whitespace doesn't count towards the width of a line
+        assert!(shaped.lines[0].width < single_line.size.width * 0.75);
+        assert_eq!(shaped.lines[0].runs[0].glyphs.len(), "hello wide ".len());
+    }
+
+    #[test]
+    fn laying_out_at_the_measured_width_does_not_wrap() {
+        let text = "a few words of text";
+
+        let measured = layout(text, f32::INFINITY);
+
+        assert_eq!(layout(text, measured.max_intrinsic_width).lines.len(), 1);
+    }
+
+    #[test]
+    fn min_intrinsic_width_is_the_widest_word() {
+        let text = "a wider word";
+
+        let shaped = layout(text, f32::INFINITY);
+        let wider = layout("wider", f32::INFINITY);
+
+        assert_eq!(shaped.min_intrinsic_width, wider.size.width);
+
+        let wrapped = layout(text, shaped.min_intrinsic_width);
+
+        assert_eq!(line_texts(text, &wrapped), ["a ", "wider ", "word"]);
+    }
+
+    #[test]
+    fn breaks_long_words_between_clusters() {
+        let shaped = layout("mmmm", 1.0);
+
+        assert_eq!(shaped.lines.len(), 4);
+        assert!(shaped
+            .lines
+            .iter()
+            .all(|line| line.runs[0].glyphs.len() == 1));
+    }
+
+    #[test]
+    fn honours_mandatory_breaks() {
+        let text = "a\n\nb\n";
+
+        let shaped = layout(text, f32::INFINITY);
+
+        assert_eq!(line_texts(text, &shaped), ["a\n", "\n", "b\n", ""]);
+        assert!(shaped.lines[1].runs.is_empty());
+    }
+
+    #[test]
+    fn empty_text_has_a_single_line() {
+        let shaped = layout("", f32::INFINITY);
+
+        assert_eq!(shaped.lines.len(), 1);
+        assert_eq!(shaped.size.width, 0.0);
+        assert_eq!(shaped.size.height, shaped.lines[0].height);
+    }
+
+    #[test]
+    fn glyphs_are_positioned_on_the_baseline() {
+        let shaped = layout("ab\nc", f32::INFINITY);
+
+        let first = &shaped.lines[0].runs[0].glyphs;
+
+        assert_eq!(first[0].x, 0.0);
+        assert_eq!(first[1].x, first[0].advance);
+        assert_eq!(first[0].y, shaped.lines[0].baseline);
+
+        let second = &shaped.lines[1];
+
+        assert_eq!(second.runs[0].glyphs[0].cluster, 3);
+        assert_eq!(second.runs[0].glyphs[0].y, second.baseline);
+        assert!(second.baseline > shaped.lines[0].baseline);
+    }
+
+    #[test]
+    fn applies_kerning() {
+        let kerned = layout("AV", f32::INFINITY);
+
+        let separate =
+            layout("A", f32::INFINITY).size.width + layout("V", f32::INFINITY).size.width;
+
+        assert!(kerned.size.width < separate);
+    }
+
+    #[test]
+    fn aligns_lines_within_the_constraints() {
+        let mut style = style();
+
+        style.h_align = HorizontalAlign::End;
+        style.v_align = VerticalAlign::Center;
+
+        let shaped = layout_paragraph(
+            &mut fonts(),
+            &style,
+            "a",
+            Constraints::tight(Size::new(100.0, 100.0)),
+        );
+
+        let line = &shaped.lines[0];
+
+        assert_eq!(line.left + line.width, 100.0);
+        assert_eq!(line.top, (100.0 - line.height) / 2.0);
+        assert_eq!(line.runs[0].glyphs[0].x, line.left);
+    }
+
+    #[test]
+    fn missing_fonts_lay_out_empty() {
+        let mut style = style();
+
+        style.font = Font::from_family("missing");
+
+        let shaped = layout_paragraph(
+            &mut fonts(),
+            &style,
+            "text",
+            Constraints::new(10.0, 100.0, 5.0, 100.0),
+        );
+
+        assert!(shaped.lines.is_empty());
+        assert_eq!(shaped.size, Size::new(10.0, 5.0));
+    }
+
+    #[test]
+    fn spans_flow_together_in_one_paragraph() {
+        let text = "small LARGE small";
+
+        let spans = [ParagraphSpan::Text {
+            range: 6..11,
+            style: style().size(32.0),
+        }];
+
+        let shaped = layout_rich_paragraph(
+            &mut fonts(),
+            &style(),
+            text,
+            &spans,
+            Constraints::new(0.0, f32::INFINITY, 0.0, f32::INFINITY),
+        );
+
+        assert_eq!(shaped.lines.len(), 1);
+
+        let line = &shaped.lines[0];
+
+        assert_eq!(
+            line.runs
+                .iter()
+                .map(|run| (run.range.clone(), run.font_size))
+                .collect::<Vec<_>>(),
+            [(0..6, 16.0), (6..11, 32.0), (11..17, 16.0)]
+        );
+
+        // The line is as tall as its largest span
+        assert_eq!(line.height, layout("", f32::INFINITY).lines[0].height * 2.0);
+
+        // Runs continue where the previous one left off
+        let first = &line.runs[0];
+        let second = &line.runs[1];
+
+        assert_eq!(second.glyphs[0].x, first.advance());
+        assert_eq!(second.glyphs[0].y, line.baseline);
+    }
+
+    #[test]
+    fn lines_break_across_spans() {
+        let text = "one two";
+
+        let spans = [ParagraphSpan::Text {
+            range: 4..7,
+            style: style().color(Color::from_rgb((1.0, 0.0, 0.0))),
+        }];
+
+        let wide = layout_rich_paragraph(
+            &mut fonts(),
+            &style(),
+            text,
+            &spans,
+            Constraints::new(0.0, f32::INFINITY, 0.0, f32::INFINITY),
+        );
+
+        let wrapped = layout_rich_paragraph(
+            &mut fonts(),
+            &style(),
+            text,
+            &spans,
+            Constraints::new(0.0, wide.size.width * 0.75, 0.0, f32::INFINITY),
+        );
+
+        assert_eq!(line_texts(text, &wrapped), ["one ", "two"]);
+        assert_eq!(wrapped.lines[1].runs[0].range, 4..7);
+        assert_eq!(wrapped.lines[1].runs[0].glyphs[0].x, 0.0);
+    }
+
+    #[test]
+    fn places_placeholders_on_the_baseline() {
+        let text = "a\u{FFFC}b";
+
+        let size = Size::new(20.0, 40.0);
+
+        let shaped = layout_rich_paragraph(
+            &mut fonts(),
+            &style(),
+            text,
+            &[ParagraphSpan::Placeholder { offset: 1, size }],
+            Constraints::new(0.0, f32::INFINITY, 0.0, f32::INFINITY),
+        );
+
+        let line = &shaped.lines[0];
+
+        let a = &line.runs[0];
+        let b = &line.runs[1];
+
+        assert_eq!(
+            shaped.placeholders,
+            [Rect::new(a.advance(), line.baseline - 40.0, 20.0, 40.0)]
+        );
+
+        // The placeholder is taller than the text, so it pushes the baseline down
+        assert_eq!(line.baseline, 40.0);
+        assert_eq!(b.glyphs[0].x, a.advance() + 20.0);
+        assert_eq!(b.range, 4..5);
+    }
+
+    #[test]
+    fn letter_spacing_is_added_after_each_character() {
+        let plain = layout("ab c", f32::INFINITY);
+        let spaced = layout_styled(&style().letter_spacing(2.0), "ab c", f32::INFINITY);
+
+        assert_eq!(spaced.size.width, plain.size.width + 2.0 * 4.0);
+
+        let glyphs = &spaced.lines[0].runs[0].glyphs;
+
+        assert_eq!(glyphs[1].x, glyphs[0].advance);
+        assert_eq!(
+            glyphs[0].advance,
+            plain.lines[0].runs[0].glyphs[0].advance + 2.0
+        );
+    }
+
+    #[test]
+    fn word_spacing_is_added_to_whitespace() {
+        let plain = layout("a b c", f32::INFINITY);
+        let spaced = layout_styled(&style().word_spacing(3.0), "a b c", f32::INFINITY);
+
+        assert_eq!(spaced.size.width, plain.size.width + 3.0 * 2.0);
+        assert_eq!(spaced.min_intrinsic_width, plain.min_intrinsic_width);
+    }
+
+    #[test]
+    fn line_height_is_a_multiple_of_the_font_size() {
+        let plain = layout("a", f32::INFINITY);
+        let tall = layout_styled(&style().line_height(3.0), "a\nb", f32::INFINITY);
+
+        assert_eq!(tall.lines[0].height, 48.0);
+        assert_eq!(tall.size.height, 96.0);
+
+        // The extra space is split evenly above and below the text
+        let plain_line = &plain.lines[0];
+        let extra = 48.0 - plain_line.height;
+
+        assert!((tall.lines[0].baseline - (plain_line.baseline + extra / 2.0)).abs() < 1e-3);
+        assert_eq!(tall.lines[1].top, 48.0);
+    }
+
+    #[test]
+    fn lines_only_break_at_mandatory_breaks_without_soft_wrap() {
+        let text = "hello wide world\nagain";
+
+        let shaped = layout_styled(&style().soft_wrap(false), text, 10.0);
+
+        assert_eq!(line_texts(text, &shaped), ["hello wide world\n", "again"]);
+        assert_eq!(shaped.min_intrinsic_width, shaped.max_intrinsic_width);
+
+        // The text overflows its constraints rather than wrapping
+        assert_eq!(shaped.size.width, 10.0);
+        assert!(shaped.lines[0].width > 10.0);
+    }
+
+    #[test]
+    fn lines_past_max_lines_are_dropped() {
+        let text = "one two three";
+
+        let width = layout("three", f32::INFINITY).size.width;
+
+        let wrapped = layout(text, width);
+
+        assert_eq!(wrapped.lines.len(), 3);
+        assert!(!wrapped.did_exceed_max_lines);
+
+        let shaped = layout_styled(&style().max_lines(2), text, width);
+
+        assert_eq!(line_texts(text, &shaped), ["one ", "two "]);
+        assert_eq!(shaped.size.height, wrapped.lines[0].height * 2.0);
+        assert!(shaped.did_exceed_max_lines);
+
+        assert!(!layout_styled(&style().max_lines(3), text, width).did_exceed_max_lines);
+    }
+
+    #[test]
+    fn ellipsizes_lines_that_are_too_wide() {
+        let text = "abcdefgh";
+
+        let full = layout(text, f32::INFINITY);
+        let max_width = full.size.width * 0.6;
+
+        for (position, expected) in [
+            (EllipsisPosition::End, vec!["abcd", "\u{2026}"]),
+            (EllipsisPosition::Start, vec!["\u{2026}", "efgh"]),
+            (EllipsisPosition::Middle, vec!["abc", "\u{2026}", "fgh"]),
+        ] {
+            let style = style()
+                .soft_wrap(false)
+                .overflow(TextOverflow::Ellipsis(position));
+
+            let shaped = layout_styled(&style, text, max_width);
+
+            let line = &shaped.lines[0];
+
+            assert!(line.width <= max_width, "{position:?} should fit");
+            assert_eq!(
+                shaped.glyph_runs().map(|run| run.advance()).sum::<f32>(),
+                line.width
+            );
+
+            let runs = run_texts(text, &shaped, 0);
+
+            // The exact number of characters that fit depends on the font, so only check
+            // that the ellipsis is where it's expected
+            assert_eq!(runs.len(), expected.len(), "{position:?}: {runs:?}");
+
+            for (run, expected) in runs.iter().zip(&expected) {
+                assert_eq!(*run == "\u{2026}", *expected == "\u{2026}", "{position:?}");
+            }
+
+            // Glyphs are laid out one after another, with the ellipsis in between
+            let xs = line
+                .runs
+                .iter()
+                .flat_map(|run| &run.glyphs)
+                .map(|glyph| glyph.x)
+                .collect::<Vec<_>>();
+
+            assert!(xs.windows(2).all(|pair| pair[0] < pair[1]), "{position:?}");
+        }
+    }
+
+    #[test]
+    fn ellipsizes_the_last_line_when_lines_are_dropped() {
+        let text = "one two three";
+
+        let width = layout("one two", f32::INFINITY).size.width;
+
+        let style = style()
+            .max_lines(1)
+            .overflow(TextOverflow::Ellipsis(EllipsisPosition::End));
+
+        let shaped = layout_styled(&style, text, width);
+
+        assert_eq!(shaped.lines.len(), 1);
+
+        let runs = run_texts(text, &shaped, 0);
+
+        assert_eq!(runs.last(), Some(&"\u{2026}"));
+        assert!(runs[0].starts_with("one"));
+
+        // The ellipsis sits right after the text it follows
+        let line = &shaped.lines[0];
+        let ellipsis = line.runs.last().unwrap();
+
+        assert!(line.width <= width);
+        assert_eq!(ellipsis.glyphs[0].x, line.runs[0].advance());
+    }
+
+    #[test]
+    fn reorders_runs_of_mixed_direction_text() {
+        let text = "abc \u{5D0}\u{5D1}\u{5D2} def";
+
+        let shaped = layout(text, f32::INFINITY);
+
+        assert_eq!(
+            run_texts(text, &shaped, 0),
+            ["abc ", "\u{5D0}\u{5D1}\u{5D2}", " def"]
+        );
+
+        // The right-to-left run is placed in reverse, so its last character is on the left
+        let clusters = shaped.lines[0].runs[1]
+            .glyphs
+            .iter()
+            .map(|glyph| glyph.cluster)
+            .collect::<Vec<_>>();
+
+        assert_eq!(clusters, [8, 6, 4]);
+
+        let xs = shaped.lines[0]
+            .runs
+            .iter()
+            .flat_map(|run| run.glyphs.iter().map(|glyph| glyph.x))
+            .collect::<Vec<_>>();
+
+        assert!(xs.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn right_to_left_paragraphs_start_on_the_right() {
+        let text = "\u{5D0}\u{5D1} abc";
+
+        let shaped = layout_paragraph(
+            &mut fonts(),
+            &style(),
+            text,
+            Constraints::tight(Size::new(100.0, 100.0)),
+        );
+
+        let line = &shaped.lines[0];
+
+        assert_eq!(line.left + line.width, 100.0);
+        assert_eq!(run_texts(text, &shaped, 0), ["abc", "\u{5D0}\u{5D1} "]);
+    }
+
+    #[test]
+    fn text_direction_sets_the_base_direction() {
+        let text = "abc ";
+
+        let style = style().text_direction(TextDirection::RightToLeft);
+
+        let shaped = layout_paragraph(
+            &mut fonts(),
+            &style,
+            text,
+            Constraints::tight(Size::new(100.0, 100.0)),
+        );
+
+        let line = &shaped.lines[0];
+
+        assert_eq!(line.left + line.width, 100.0);
+
+        // Trailing whitespace takes the paragraph's direction, so it hangs off the left
+        assert_eq!(run_texts(text, &shaped, 0), [" ", "abc"]);
+        assert!(line.runs[0].glyphs[0].x < line.left);
+        assert_eq!(line.runs[1].glyphs[0].x, line.left);
+    }
+
+    #[test]
+    fn resolves_families_from_the_database() {
+        let mut database = FontDatabase::new();
+
+        database.load_font_data(DEJA_VU_SANS.to_vec());
+
+        let shaped = layout_paragraph(
+            &mut TextFonts::with_database(database),
+            &TextStyle {
+                font: Font::from_family("sans-serif"),
+                ..style()
+            },
+            "hello",
+            Constraints::new(0.0, f32::INFINITY, 0.0, f32::INFINITY),
+        );
+
+        assert_eq!(shaped.lines[0].runs.len(), 1);
+        assert_eq!(shaped.lines[0].runs[0].font, style().font);
+        assert_eq!(shaped.size, layout("hello", f32::INFINITY).size);
+    }
+
+    #[test]
+    fn missing_glyphs_without_a_fallback_stay_in_the_same_run() {
+        let text = "hi 你好";
+
+        let shaped = layout(text, f32::INFINITY);
+
+        assert_eq!(run_texts(text, &shaped, 0), [text]);
+    }
+
+    #[test]
+    fn fallbacks_split_runs_by_font() {
+        let kind = ItemKind::Text { style: 1, font: 1 };
+
+        let fallbacks = [(2..5, 3), (7..9, 4), (12..14, 3)];
+
+        assert_eq!(
+            font_runs(kind, 0..10, &fallbacks),
+            [
+                (0..2, kind),
+                (2..5, ItemKind::Text { style: 1, font: 3 }),
+                (5..7, kind),
+                (7..9, ItemKind::Text { style: 1, font: 4 }),
+                (9..10, kind),
+            ]
+        );
+
+        assert_eq!(
+            font_runs(kind, 3..8, &fallbacks),
+            [
+                (3..5, ItemKind::Text { style: 1, font: 3 }),
+                (5..7, kind),
+                (7..8, ItemKind::Text { style: 1, font: 4 }),
+            ]
+        );
+    }
+}
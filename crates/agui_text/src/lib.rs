@@ -0,0 +1,16 @@
+//! A text layout engine for agui that isn't tied to a renderer.
+//!
+//! Text is shaped with rustybuzz and wrapped at Unicode line break opportunities. The
+//! resulting [`GlyphRun`](agui_core::unit::GlyphRun)s are positioned relative to the top
+//! left of the text, so backends can draw them without shaping the text themselves.
+
+mod database;
+mod fonts;
+mod layout;
+mod shaping;
+mod strategy;
+
+pub use database::*;
+pub use fonts::*;
+pub use layout::*;
+pub use strategy::*;
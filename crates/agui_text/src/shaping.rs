@@ -0,0 +1,96 @@
+use rustybuzz::{Direction, Face, UnicodeBuffer};
+
+/// A glyph produced by shaping, with its metrics already scaled to the font size.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ShapedGlyph {
+    pub id: u16,
+
+    /// The byte offset in the source text of the cluster the glyph belongs to.
+    pub cluster: usize,
+
+    pub advance: f32,
+
+    /// The offset of the glyph from the pen position, pointing down and to the right.
+    pub x_offset: f32,
+    pub y_offset: f32,
+}
+
+/// The vertical metrics of a font at a given size.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct FontMetrics {
+    /// Converts from font units to pixels.
+    pub scale: f32,
+
+    /// The distance from the top of a line to its baseline.
+    pub ascent: f32,
+
+    /// The distance from the baseline to the bottom of a line.
+    pub descent: f32,
+
+    pub line_gap: f32,
+}
+
+impl FontMetrics {
+    pub fn new(face: &Face, font_size: f32) -> Self {
+        let scale = font_size / face.units_per_em() as f32;
+
+        Self {
+            scale,
+
+            ascent: face.ascender() as f32 * scale,
+            descent: -face.descender() as f32 * scale,
+            line_gap: face.line_gap() as f32 * scale,
+        }
+    }
+}
+
+/// Shapes `text`, which starts at byte `offset` of the source text, in a single direction.
+///
+/// The script is guessed from the text, which must not contain any mandatory line breaks.
+/// The glyphs are returned in logical order, even if the text is right-to-left.
+pub(crate) fn shape(
+    face: &Face,
+    metrics: &FontMetrics,
+    text: &str,
+    offset: usize,
+    rtl: bool,
+) -> Vec<ShapedGlyph> {
+    if text.is_empty() {
+        return Vec::new();
+    }
+
+    let mut buffer = UnicodeBuffer::new();
+
+    buffer.push_str(text);
+    buffer.set_direction(if rtl {
+        Direction::RightToLeft
+    } else {
+        Direction::LeftToRight
+    });
+    buffer.guess_segment_properties();
+
+    let output = rustybuzz::shape(face, &[], buffer);
+
+    let mut glyphs = output
+        .glyph_infos()
+        .iter()
+        .zip(output.glyph_positions())
+        .map(|(info, position)| ShapedGlyph {
+            id: info.glyph_id as u16,
+
+            cluster: offset + info.cluster as usize,
+
+            advance: position.x_advance as f32 * metrics.scale,
+
+            x_offset: position.x_offset as f32 * metrics.scale,
+            y_offset: -position.y_offset as f32 * metrics.scale,
+        })
+        .collect::<Vec<_>>();
+
+    // Right-to-left text is shaped in visual order, but lines are broken in logical order
+    if rtl {
+        glyphs.reverse();
+    }
+
+    glyphs
+}
@@ -0,0 +1,177 @@
+use std::sync::Arc;
+
+use agui_core::{
+    engine::rendering::strategies::RenderingTreeTextLayoutStrategy,
+    unit::{Constraints, IntrinsicDimension, Paragraph, ParagraphSpan, Size, TextStyle},
+};
+use parking_lot::Mutex;
+
+use crate::{fonts::TextFonts, layout::layout_rich_paragraph};
+
+fn unbounded() -> Constraints {
+    Constraints::loose(Size::new(f32::INFINITY, f32::INFINITY))
+}
+
+/// A text layout strategy that shapes text, so that it can be shared by any backend that
+/// draws the resulting glyph runs.
+#[derive(Clone, Default)]
+pub struct ShapedTextLayout {
+    fonts: Arc<Mutex<TextFonts>>,
+}
+
+impl ShapedTextLayout {
+    pub fn new(fonts: Arc<Mutex<TextFonts>>) -> Self {
+        Self { fonts }
+    }
+
+    pub fn fonts(&self) -> &Arc<Mutex<TextFonts>> {
+        &self.fonts
+    }
+
+    /// Shapes `text` and lays it out within the constraints, with parts of it styled by
+    /// `spans`.
+    pub fn layout(
+        &self,
+        text_style: &TextStyle,
+        text: &str,
+        spans: &[ParagraphSpan],
+        constraints: Constraints,
+    ) -> Paragraph {
+        layout_rich_paragraph(&mut self.fonts.lock(), text_style, text, spans, constraints)
+    }
+
+    fn intrinsic_size(
+        &self,
+        text_style: &TextStyle,
+        text: &str,
+        spans: &[ParagraphSpan],
+        dimension: IntrinsicDimension,
+        cross_axis: f32,
+    ) -> f32 {
+        match dimension {
+            IntrinsicDimension::MinWidth => {
+                self.layout(text_style, text, spans, unbounded())
+                    .min_intrinsic_width
+            }
+
+            IntrinsicDimension::MaxWidth => {
+                self.layout(text_style, text, spans, unbounded())
+                    .max_intrinsic_width
+            }
+
+            // The height of the text necessary to fit within the given width (`cross_axis`)
+            IntrinsicDimension::MinHeight | IntrinsicDimension::MaxHeight => {
+                self.layout(
+                    text_style,
+                    text,
+                    spans,
+                    Constraints::new(0.0, cross_axis, 0.0, f32::INFINITY),
+                )
+                .size
+                .height
+            }
+        }
+    }
+}
+
+impl RenderingTreeTextLayoutStrategy for ShapedTextLayout {
+    fn compute_intrinsic_size(
+        &self,
+        text_style: &TextStyle,
+        text: &str,
+        dimension: IntrinsicDimension,
+        cross_axis: f32,
+    ) -> f32 {
+        self.intrinsic_size(text_style, text, &[], dimension, cross_axis)
+    }
+
+    fn compute_paragraph_intrinsic_size(
+        &self,
+        text_style: &TextStyle,
+        text: &str,
+        spans: &[ParagraphSpan],
+        dimension: IntrinsicDimension,
+        cross_axis: f32,
+    ) -> Option<f32> {
+        Some(self.intrinsic_size(text_style, text, spans, dimension, cross_axis))
+    }
+
+    fn compute_size(
+        &mut self,
+        text_style: &TextStyle,
+        text: &str,
+        constraints: Constraints,
+    ) -> Size {
+        self.layout(text_style, text, &[], constraints).size
+    }
+
+    fn layout_paragraph(
+        &mut self,
+        text_style: &TextStyle,
+        text: &str,
+        spans: &[ParagraphSpan],
+        constraints: Constraints,
+    ) -> Option<Paragraph> {
+        Some(self.layout(text_style, text, spans, constraints))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use agui_core::{
+        engine::rendering::{mock::text_style, strategies::RenderingTreeTextLayoutStrategy},
+        unit::{Color, Constraints, IntrinsicDimension, TextStyle},
+    };
+
+    use super::ShapedTextLayout;
+
+    fn style() -> TextStyle {
+        text_style(16.0, Color::from_rgb((0.0, 0.0, 0.0)))
+    }
+
+    #[test]
+    fn intrinsic_sizes_agree_with_layout() {
+        let mut text_layout = ShapedTextLayout::default();
+
+        let style = style();
+        let text = "some words to wrap";
+
+        let min_width =
+            text_layout.compute_intrinsic_size(&style, text, IntrinsicDimension::MinWidth, 0.0);
+        let max_width = text_layout.compute_intrinsic_size(
+            &style,
+            text,
+            IntrinsicDimension::MaxWidth,
+            f32::INFINITY,
+        );
+
+        assert!(min_width > 0.0);
+        assert!(min_width < max_width);
+
+        let narrow = text_layout.compute_size(
+            &style,
+            text,
+            Constraints::new(0.0, min_width, 0.0, f32::INFINITY),
+        );
+
+        assert_eq!(narrow.width, min_width);
+        assert_eq!(
+            narrow.height,
+            text_layout.compute_intrinsic_size(
+                &style,
+                text,
+                IntrinsicDimension::MinHeight,
+                min_width
+            )
+        );
+
+        let wide = text_layout.compute_size(
+            &style,
+            text,
+            Constraints::new(0.0, f32::INFINITY, 0.0, f32::INFINITY),
+        );
+
+        assert_eq!(wide.width, max_width);
+        assert!(wide.height < narrow.height);
+    }
+}
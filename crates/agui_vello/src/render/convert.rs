@@ -0,0 +1,277 @@
+use std::sync::Arc;
+
+use agui_core::{
+    render::canvas::paint::Paint,
+    unit::{
+        BlendMode, Color, FillRule, Gradient, GradientExtend, GradientKind, Rect, Shape, StrokeCap,
+        StrokeJoin, StrokeStyle, Texture, TextureData,
+    },
+};
+use lyon::path::Event;
+use vello::{
+    kurbo::{self, BezPath, Cap, Join, RoundedRectRadii},
+    peniko::{self, Blob, ColorStop, Compose, Extend, Fill, Format, Mix},
+};
+
+/// Builds the path for `shape` within a box of the given size, with its origin at zero.
+pub(crate) fn to_bez_path(shape: &Shape, width: f32, height: f32) -> BezPath {
+    use kurbo::Shape as _;
+
+    let rect = kurbo::Rect::new(0.0, 0.0, width as f64, height as f64);
+
+    match shape {
+        Shape::Rect => rect.to_path(0.1),
+
+        Shape::RoundedRect {
+            top_left,
+            top_right,
+            bottom_right,
+            bottom_left,
+        } => kurbo::RoundedRect::from_rect(
+            rect,
+            RoundedRectRadii::new(
+                *top_left as f64,
+                *top_right as f64,
+                *bottom_right as f64,
+                *bottom_left as f64,
+            ),
+        )
+        .to_path(0.1),
+
+        Shape::Circle => kurbo::Ellipse::from_rect(rect).to_path(0.1),
+
+        Shape::Path(path) => {
+            let mut bez_path = BezPath::new();
+
+            for event in path.iter() {
+                match event {
+                    Event::Begin { at } => bez_path.move_to((at.x as f64, at.y as f64)),
+
+                    Event::Line { to, .. } => bez_path.line_to((to.x as f64, to.y as f64)),
+
+                    Event::Quadratic { ctrl, to, .. } => {
+                        bez_path.quad_to((ctrl.x as f64, ctrl.y as f64), (to.x as f64, to.y as f64))
+                    }
+
+                    Event::Cubic {
+                        ctrl1, ctrl2, to, ..
+                    } => bez_path.curve_to(
+                        (ctrl1.x as f64, ctrl1.y as f64),
+                        (ctrl2.x as f64, ctrl2.y as f64),
+                        (to.x as f64, to.y as f64),
+                    ),
+
+                    Event::End { close, .. } => {
+                        if close {
+                            bez_path.close_path();
+                        }
+                    }
+                }
+            }
+
+            bez_path
+        }
+    }
+}
+
+pub(crate) fn to_stroke(style: &StrokeStyle) -> kurbo::Stroke {
+    let stroke = kurbo::Stroke::new(style.width as f64)
+        .with_join(match style.join {
+            StrokeJoin::Miter => Join::Miter,
+            StrokeJoin::Round => Join::Round,
+            StrokeJoin::Bevel => Join::Bevel,
+        })
+        .with_caps(match style.cap {
+            StrokeCap::Butt => Cap::Butt,
+            StrokeCap::Round => Cap::Round,
+            StrokeCap::Square => Cap::Square,
+        })
+        .with_miter_limit(style.miter_limit as f64);
+
+    if style.is_dashed() {
+        stroke.with_dashes(
+            style.dash_offset as f64,
+            style.dash_pattern.iter().map(|length| *length as f64),
+        )
+    } else {
+        stroke
+    }
+}
+
+pub(crate) fn to_color(color: Color) -> peniko::Color {
+    peniko::Color::rgba(
+        color.red as f64,
+        color.green as f64,
+        color.blue as f64,
+        color.alpha as f64,
+    )
+}
+
+pub(crate) fn to_fill(fill_rule: FillRule) -> Fill {
+    match fill_rule {
+        FillRule::NonZero => Fill::NonZero,
+        FillRule::EvenOdd => Fill::EvenOdd,
+    }
+}
+
+pub(crate) fn to_blend_mode(blend_mode: BlendMode) -> peniko::BlendMode {
+    match blend_mode {
+        BlendMode::Clear => Compose::Clear.into(),
+        BlendMode::Source => Compose::Copy.into(),
+        BlendMode::Destination => Compose::Dest.into(),
+        BlendMode::SourceOver => Compose::SrcOver.into(),
+        BlendMode::DestinationOver => Compose::DestOver.into(),
+        BlendMode::SourceIn => Compose::SrcIn.into(),
+        BlendMode::DestinationIn => Compose::DestIn.into(),
+        BlendMode::SourceOut => Compose::SrcOut.into(),
+        BlendMode::DestinationOut => Compose::DestOut.into(),
+        BlendMode::SourceAlphaTop => Compose::SrcAtop.into(),
+        BlendMode::DestinationAlphaTop => Compose::DestAtop.into(),
+        BlendMode::XOr => Compose::Xor.into(),
+        BlendMode::Plus => Compose::Plus.into(),
+
+        // Vello has no modulate mode, but multiplying the colors is identical for opaque
+        // pixels
+        BlendMode::Modulate | BlendMode::Multiply => Mix::Multiply.into(),
+
+        BlendMode::Screen => Mix::Screen.into(),
+        BlendMode::Overlay => Mix::Overlay.into(),
+        BlendMode::Darken => Mix::Darken.into(),
+        BlendMode::Lighten => Mix::Lighten.into(),
+        BlendMode::ColorDodge => Mix::ColorDodge.into(),
+        BlendMode::ColorBurn => Mix::ColorBurn.into(),
+        BlendMode::HardLight => Mix::HardLight.into(),
+        BlendMode::SoftLight => Mix::SoftLight.into(),
+        BlendMode::Difference => Mix::Difference.into(),
+        BlendMode::Exclusion => Mix::Exclusion.into(),
+        BlendMode::Hue => Mix::Hue.into(),
+        BlendMode::Saturation => Mix::Saturation.into(),
+        BlendMode::Color => Mix::Color.into(),
+        BlendMode::Luminosity => Mix::Luminosity.into(),
+    }
+}
+
+/// Rounds the edges of the rect to whole pixels.
+///
+/// Vello always anti-aliases, so this is as close as it can get to drawing without it:
+/// shapes aligned to the pixel grid are drawn with crisp edges.
+pub(crate) fn snap_to_pixels(rect: Rect) -> Rect {
+    let left = rect.left.round();
+    let top = rect.top.round();
+
+    Rect::new(
+        left,
+        top,
+        (rect.left + rect.width).round() - left,
+        (rect.top + rect.height).round() - top,
+    )
+}
+
+/// Returns the brush for the paint, using its gradient if it has one.
+pub(crate) fn to_brush(paint: &Paint) -> peniko::Brush {
+    match &paint.gradient {
+        Some(gradient) => peniko::Brush::Gradient(to_gradient(gradient)),
+        None => peniko::Brush::Solid(to_color(paint.color)),
+    }
+}
+
+fn to_gradient(gradient: &Gradient) -> peniko::Gradient {
+    let stops = gradient
+        .stops
+        .iter()
+        .map(|stop| ColorStop {
+            offset: stop.offset,
+            color: to_color(stop.color),
+        })
+        .collect::<Vec<_>>();
+
+    let extend = match gradient.extend {
+        GradientExtend::Pad => Extend::Pad,
+        GradientExtend::Repeat => Extend::Repeat,
+        GradientExtend::Reflect => Extend::Reflect,
+    };
+
+    match gradient.kind {
+        GradientKind::Linear { start, end } => peniko::Gradient::new_linear(
+            (start.x as f64, start.y as f64),
+            (end.x as f64, end.y as f64),
+        ),
+
+        GradientKind::Radial {
+            center,
+            radius,
+            focal,
+        } => {
+            if focal == center {
+                peniko::Gradient::new_radial((center.x as f64, center.y as f64), radius)
+            } else {
+                peniko::Gradient::new_two_point_radial(
+                    (focal.x as f64, focal.y as f64),
+                    0.0,
+                    (center.x as f64, center.y as f64),
+                    radius,
+                )
+            }
+        }
+    }
+    .with_stops(stops.as_slice())
+    .with_extend(extend)
+}
+
+/// Wraps a decoded texture so its pixels can be shared with vello without copying them.
+struct TexturePixels(Texture);
+
+impl AsRef<[u8]> for TexturePixels {
+    fn as_ref(&self) -> &[u8] {
+        match self.0.data() {
+            TextureData::Rgba8 { pixels, .. } => pixels,
+            _ => &[],
+        }
+    }
+}
+
+/// Returns the image for the texture, or `None` if the texture has not been decoded.
+pub(crate) fn to_image(texture: &Texture) -> Option<peniko::Image> {
+    let (width, height) = texture.dimensions()?;
+
+    Some(peniko::Image::new(
+        Blob::new(Arc::new(TexturePixels(texture.clone()))),
+        Format::Rgba8,
+        width,
+        height,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use agui_core::unit::{BlendMode, Rect};
+    use vello::peniko::{self, Compose, Mix};
+
+    use super::{snap_to_pixels, to_blend_mode};
+
+    #[test]
+    fn maps_blend_modes() {
+        assert_eq!(
+            to_blend_mode(BlendMode::SourceOver),
+            peniko::BlendMode::new(Mix::Normal, Compose::SrcOver)
+        );
+
+        assert_eq!(
+            to_blend_mode(BlendMode::DestinationOut),
+            peniko::BlendMode::new(Mix::Normal, Compose::DestOut)
+        );
+
+        assert_eq!(
+            to_blend_mode(BlendMode::Luminosity),
+            peniko::BlendMode::new(Mix::Luminosity, Compose::SrcOver)
+        );
+    }
+
+    #[test]
+    fn snaps_edges_to_pixels() {
+        assert_eq!(
+            snap_to_pixels(Rect::new(0.4, 1.6, 10.2, 2.0)),
+            Rect::new(0.0, 2.0, 11.0, 2.0)
+        );
+    }
+}
@@ -1,3 +1,4 @@
+mod convert;
 mod object;
 mod scene;
 
@@ -1,19 +1,54 @@
 use agui_core::{
-    render::canvas::{command::CanvasCommand, paint::Paint, Canvas},
-    unit::{Offset, Rect, Size},
+    render::canvas::{command::CanvasCommand, paint::Paint, Canvas, CanvasLayer},
+    unit::{BlendMode, Constraints, GlyphRun, Offset, PaintingStyle, Rect, Shape, Size},
 };
 use rustc_hash::FxHashMap;
 use vello::{
-    glyph::{
-        skrifa::{GlyphId, MetadataProvider},
-        Glyph,
-    },
-    kurbo::{Affine, PathEl, Vec2},
-    peniko::{Color, Fill, Mix},
+    glyph::{skrifa::GlyphId, Glyph},
+    kurbo::{self, Affine, BezPath, Shape as _},
+    peniko::{self, Compose, Fill, Mix},
     Scene,
 };
 
-use crate::renderer::fonts::VelloFonts;
+use crate::{render::convert, renderer::fonts::VelloFonts};
+
+/// A target that canvas fragments and their layers are encoded into.
+///
+/// This is implemented for [`Scene`], but allows the layer structure of a canvas to be
+/// inspected without a GPU.
+pub trait SceneSink {
+    fn push_layer(
+        &mut self,
+        blend: peniko::BlendMode,
+        alpha: f32,
+        transform: Affine,
+        clip: &BezPath,
+    );
+
+    fn pop_layer(&mut self);
+
+    fn append(&mut self, fragment: &Scene, transform: Affine);
+}
+
+impl SceneSink for Scene {
+    fn push_layer(
+        &mut self,
+        blend: peniko::BlendMode,
+        alpha: f32,
+        transform: Affine,
+        clip: &BezPath,
+    ) {
+        Scene::push_layer(self, blend, alpha, transform, clip);
+    }
+
+    fn pop_layer(&mut self) {
+        Scene::pop_layer(self);
+    }
+
+    fn append(&mut self, fragment: &Scene, transform: Affine) {
+        Scene::append(self, fragment, Some(transform));
+    }
+}
 
 #[derive(Debug, Default)]
 pub struct VelloRenderObject {
@@ -28,10 +63,10 @@ pub struct VelloRenderObject {
 
 #[derive(Default)]
 pub struct VelloCanvasObject {
-    pub offset: Offset,
-
     pub fragment: Scene,
 
+    pub head: Vec<CanvasCommand>,
+
     pub children: Vec<LayerObject>,
     pub tail: Option<Box<LayerObject>>,
 
@@ -42,58 +77,144 @@ pub struct VelloCanvasObject {
 impl std::fmt::Debug for VelloCanvasObject {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("VelloCanvasObject")
-            .field("offset", &self.offset)
+            .field("head", &self.head.len())
+            .field("children", &self.children.len())
+            .field("tail", &self.tail.is_some())
             .finish_non_exhaustive()
     }
 }
 
 impl VelloCanvasObject {
-    pub fn update(&mut self, fonts: &mut VelloFonts, canvas: Canvas) {
+    /// Updates the object to match `canvas`, returning `true` if anything changed.
+    ///
+    /// Fragments are only re-encoded when their commands or paints differ from the
+    /// previous canvas, so unchanged layers are reused as-is.
+    pub fn update(&mut self, fonts: &mut VelloFonts, canvas: Canvas) -> bool {
+        let mut changed = false;
+
         // TODO: only invalidate paints that are different
-        if self.paints.len() != canvas.paints.len() || self.paints != canvas.paints {
+        if self.paints != canvas.paints {
             self.paints = canvas.paints;
 
             // If our paints have changed, we need to invalidate the glyph cache
             // TODO: only invalidate glpyhs whose paint has changed
             self.glyph_cache.clear();
+
+            changed = true;
         }
 
-        if canvas.head.is_empty() {
-            self.fragment.reset();
-        } else {
-            self.update_head(fonts, canvas.head);
+        if changed || self.head != canvas.head {
+            if canvas.head.is_empty() {
+                self.fragment.reset();
+            } else {
+                self.update_head(fonts, &canvas.head);
+            }
+
+            self.head = canvas.head;
+
+            changed = true;
         }
 
-        for _ in canvas.children {
-            println!("child");
+        if self.children.len() > canvas.children.len() {
+            self.children.truncate(canvas.children.len());
+
+            changed = true;
         }
 
-        if let Some(tail) = canvas.tail {
-            let mut layer = LayerObject {
-                rect: tail.offset & tail.canvas.size,
+        for (idx, layer) in canvas.children.into_iter().enumerate() {
+            if let Some(child) = self.children.get_mut(idx) {
+                changed |= child.update(fonts, &self.paints, layer);
+            } else {
+                self.children
+                    .push(LayerObject::new(fonts, &self.paints, layer));
 
-                canvas: VelloCanvasObject {
-                    offset: tail.offset,
+                changed = true;
+            }
+        }
 
-                    fragment: Scene::new(),
+        match canvas.tail {
+            Some(layer) => {
+                if let Some(tail) = &mut self.tail {
+                    changed |= tail.update(fonts, &self.paints, *layer);
+                } else {
+                    self.tail = Some(Box::new(LayerObject::new(fonts, &self.paints, *layer)));
 
-                    children: Vec::new(),
-                    tail: None,
+                    changed = true;
+                }
+            }
 
-                    paints: Vec::new(),
-                    glyph_cache: FxHashMap::default(),
-                },
+            None => {
+                changed |= self.tail.take().is_some();
+            }
+        }
+
+        changed
+    }
+
+    /// Pushes a layer onto `fragment` that the next draw will be blended into, if its paint
+    /// uses anything other than the default blend mode. Returns `true` if a layer was pushed.
+    fn push_blend(
+        fragment: &mut Scene,
+        paint: &Paint,
+        transform: Affine,
+        clip: impl FnOnce() -> BezPath,
+    ) -> bool {
+        if paint.blend_mode == BlendMode::SourceOver {
+            return false;
+        }
+
+        fragment.push_layer(
+            convert::to_blend_mode(paint.blend_mode),
+            1.0,
+            transform,
+            &clip(),
+        );
+
+        true
+    }
+
+    /// Draws glyph runs that were positioned relative to the top left of `rect`.
+    fn draw_glyph_runs<'a>(
+        fragment: &mut Scene,
+        fonts: &mut VelloFonts,
+        paint: &Paint,
+        rect: Rect,
+        runs: impl IntoIterator<Item = &'a GlyphRun>,
+    ) {
+        let transform = Affine::translate((rect.left as f64, rect.top as f64));
+        let brush = convert::to_brush(paint);
+
+        let blend = Self::push_blend(fragment, paint, transform, || {
+            kurbo::Rect::new(0.0, 0.0, rect.width as f64, rect.height as f64).to_path(0.1)
+        });
+
+        for run in runs {
+            let Some(font) = fonts.get_or_insert(run.font.clone()) else {
+                continue;
             };
 
-            layer.update(fonts, tail.canvas);
+            fragment
+                .draw_glyphs(font)
+                .font_size(run.font_size)
+                .transform(transform)
+                .brush(&brush)
+                .draw(
+                    Fill::NonZero,
+                    run.glyphs.iter().map(|glyph| Glyph {
+                        id: glyph.id as u32,
+
+                        x: glyph.x,
+                        y: glyph.y,
+                    }),
+                );
+        }
 
-            self.tail = Some(Box::new(layer));
-        } else {
-            self.tail = None;
+        if blend {
+            fragment.pop_layer();
         }
     }
 
-    fn update_head(&mut self, fonts: &mut VelloFonts, commands: Vec<CanvasCommand>) {
+    fn update_head(&mut self, fonts: &mut VelloFonts, commands: &[CanvasCommand]) {
         self.fragment.reset();
 
         for command in commands {
@@ -103,26 +224,129 @@ impl VelloCanvasObject {
                     rect,
                     shape,
                 } => {
-                    let paint = &self.paints[paint_idx];
+                    let paint = &self.paints[*paint_idx];
+
+                    let rect = if paint.anti_alias {
+                        *rect
+                    } else {
+                        convert::snap_to_pixels(*rect)
+                    };
+
+                    let transform = Affine::translate((rect.left as f64, rect.top as f64));
+                    let brush = convert::to_brush(paint);
+                    let path = convert::to_bez_path(shape, rect.width, rect.height);
+
+                    let blend = Self::push_blend(&mut self.fragment, paint, transform, || {
+                        match &paint.style {
+                            PaintingStyle::Fill => path.clone(),
+
+                            // Strokes are centered on the outline, so half of their width
+                            // falls outside of the shape
+                            PaintingStyle::Stroke(stroke) => {
+                                kurbo::Rect::new(0.0, 0.0, rect.width as f64, rect.height as f64)
+                                    .inflate(stroke.width as f64 / 2.0, stroke.width as f64 / 2.0)
+                                    .to_path(0.1)
+                            }
+                        }
+                    });
+
+                    match &paint.style {
+                        PaintingStyle::Fill => {
+                            self.fragment.fill(
+                                convert::to_fill(paint.fill_rule),
+                                transform,
+                                &brush,
+                                None,
+                                &path,
+                            );
+                        }
+
+                        PaintingStyle::Stroke(stroke) => {
+                            self.fragment.stroke(
+                                &convert::to_stroke(stroke),
+                                transform,
+                                &brush,
+                                None,
+                                &path,
+                            );
+                        }
+                    }
+
+                    if blend {
+                        self.fragment.pop_layer();
+                    }
+                }
 
-                    self.fragment.fill(
-                        Fill::NonZero,
-                        Affine::translate((rect.left as f64, rect.top as f64)),
-                        Color::rgba(
-                            paint.color.red as f64,
-                            paint.color.green as f64,
-                            paint.color.blue as f64,
-                            paint.color.alpha as f64,
+                CanvasCommand::Shadow {
+                    rect,
+                    shape,
+                    shadow,
+                } => {
+                    let shadow_rect =
+                        shadow.shadow_rect(Rect::new(0.0, 0.0, rect.width, rect.height));
+
+                    let transform = Affine::translate((
+                        (rect.left + shadow_rect.left) as f64,
+                        (rect.top + shadow_rect.top) as f64,
+                    ));
+
+                    let color = convert::to_color(shadow.color);
+
+                    let radius = match shape {
+                        Shape::Rect => Some(0.0),
+
+                        Shape::RoundedRect {
+                            top_left,
+                            top_right,
+                            bottom_right,
+                            bottom_left,
+                        } => Some(
+                            (top_left
+                                .max(*top_right)
+                                .max(*bottom_right)
+                                .max(*bottom_left)
+                                + shadow.spread_radius)
+                                .max(0.0),
                         ),
-                        None,
-                        &[
-                            PathEl::LineTo((0.0, 0.0).into()),
-                            PathEl::LineTo((rect.width as f64, 0.0).into()),
-                            PathEl::LineTo((rect.width as f64, rect.height as f64).into()),
-                            PathEl::LineTo((0.0, rect.height as f64).into()),
-                            PathEl::ClosePath,
-                        ],
-                    );
+
+                        Shape::Circle => Some(shadow_rect.width.min(shadow_rect.height) / 2.0),
+
+                        Shape::Path(_) => None,
+                    };
+
+                    match radius {
+                        Some(radius) => {
+                            self.fragment.draw_blurred_rounded_rect(
+                                transform,
+                                kurbo::Rect::new(
+                                    0.0,
+                                    0.0,
+                                    shadow_rect.width as f64,
+                                    shadow_rect.height as f64,
+                                ),
+                                color,
+                                radius as f64,
+                                shadow.blur_sigma() as f64,
+                            );
+                        }
+
+                        None => {
+                            tracing::warn!("blurred shadows are not supported for paths");
+
+                            let transform = Affine::translate((
+                                (rect.left + shadow.offset.x) as f64,
+                                (rect.top + shadow.offset.y) as f64,
+                            ));
+
+                            self.fragment.fill(
+                                Fill::NonZero,
+                                transform,
+                                color,
+                                None,
+                                &convert::to_bez_path(shape, rect.width, rect.height),
+                            );
+                        }
+                    }
                 }
 
                 CanvasCommand::Texture {
@@ -131,7 +355,37 @@ impl VelloCanvasObject {
                     texture,
                     tex_bounds,
                 } => {
-                    tracing::info!("texture: {:?}", texture);
+                    let Some(image) = convert::to_image(texture) else {
+                        tracing::warn!("textures must be decoded before they can be drawn");
+
+                        continue;
+                    };
+
+                    let (image_width, image_height) = (image.width as f64, image.height as f64);
+
+                    let source_width = image_width * (tex_bounds.right - tex_bounds.left) as f64;
+                    let source_height = image_height * (tex_bounds.bottom - tex_bounds.top) as f64;
+
+                    if source_width <= 0.0 || source_height <= 0.0 {
+                        continue;
+                    }
+
+                    // Maps the selected region of the texture onto the rect
+                    let brush_transform = Affine::scale_non_uniform(
+                        rect.width as f64 / source_width,
+                        rect.height as f64 / source_height,
+                    ) * Affine::translate((
+                        -image_width * tex_bounds.left as f64,
+                        -image_height * tex_bounds.top as f64,
+                    ));
+
+                    self.fragment.fill(
+                        Fill::NonZero,
+                        Affine::translate((rect.left as f64, rect.top as f64)),
+                        &image,
+                        Some(brush_transform),
+                        &convert::to_bez_path(shape, rect.width, rect.height),
+                    );
                 }
 
                 CanvasCommand::Text {
@@ -139,109 +393,37 @@ impl VelloCanvasObject {
                     rect,
                     text_style,
                     text,
-                    ..
                 } => {
-                    // TODO: should we handle handle text wrapping here or in the render object?
-
-                    let paint = &self.paints[paint_idx];
+                    let paint = &self.paints[*paint_idx];
 
-                    let font = fonts.get_or_insert(text_style.font);
-                    let font_ref = VelloFonts::to_font_ref(font).expect("failed to get font ref");
-
-                    let transform = Affine::translate((
-                        rect.left as f64,
-                        rect.top as f64 + text_style.size as f64,
-                    ));
+                    let paragraph = agui_text::layout_paragraph(
+                        &mut fonts.text_fonts().lock(),
+                        text_style,
+                        text,
+                        Constraints::tight(Size::from(*rect)),
+                    );
 
-                    let glyph_brush = vello::peniko::Brush::Solid(Color::rgba(
-                        paint.color.red as f64,
-                        paint.color.green as f64,
-                        paint.color.blue as f64,
-                        paint.color.alpha as f64,
-                    ));
+                    Self::draw_glyph_runs(
+                        &mut self.fragment,
+                        fonts,
+                        paint,
+                        *rect,
+                        paragraph.glyph_runs(),
+                    );
+                }
 
-                    let axes = font_ref.axes();
-                    let font_size = vello::skrifa::instance::Size::new(text_style.size);
-                    let var_loc = axes.location(&[] as &[(&str, f32)]);
-                    let charmap = font_ref.charmap();
-                    let metrics = font_ref.metrics(font_size, &var_loc);
-                    let line_height = metrics.ascent - metrics.descent + metrics.leading;
-                    let glyph_metrics = font_ref.glyph_metrics(font_size, &var_loc);
-                    let mut pen_x = 0f32;
-                    let mut pen_y = 0f32;
-
-                    self.fragment
-                        .draw_glyphs(font)
-                        .font_size(text_style.size)
-                        .transform(transform)
-                        .glyph_transform(None)
-                        .normalized_coords(var_loc.coords())
-                        .brush(&glyph_brush)
-                        .draw(
-                            Fill::NonZero,
-                            text.chars().filter_map(|ch| {
-                                if ch == '\n' {
-                                    pen_y += line_height;
-                                    pen_x = 0.0;
-                                    return None;
-                                }
-
-                                let gid = charmap.map(ch).unwrap_or_default();
-                                let advance = glyph_metrics.advance_width(gid).unwrap_or_default();
-
-                                // Naive wrapping (doesn't account for word boundaries)
-                                if pen_x + advance > rect.width {
-                                    pen_y += line_height;
-                                    pen_x = 0.0;
-                                }
-
-                                let x = pen_x;
-                                pen_x += advance;
-
-                                Some(Glyph {
-                                    id: gid.to_u16() as u32,
-
-                                    x,
-                                    y: pen_y,
-                                })
-                            }),
-                        );
-
-                    // let fello_size = vello::fello::Size::new(text_style.size);
-                    // let charmap = font.charmap();
-                    // let metrics = font.metrics(fello_size, Default::default());
-                    // let line_height = metrics.ascent - metrics.descent + metrics.leading;
-                    // let glyph_metrics = font.glyph_metrics(fello_size, Default::default());
-                    // let mut pen_x = 0f64;
-                    // let mut pen_y = 0f64;
-                    // let vars: [(&str, f32); 0] = [];
-                    // let mut provider = fonts.new_provider(&font, text_style.size, false, vars);
-
-                    // for ch in text.chars() {
-                    //     if ch == '\n' {
-                    //         pen_y += line_height as f64;
-                    //         pen_x = 0.0;
-                    //         continue;
-                    //     }
-
-                    //     let gid = charmap.map(ch).unwrap_or_default();
-                    //     let advance = glyph_metrics.advance_width(gid).unwrap_or_default() as f64;
-
-                    //     // Getting the glyph from the provider is expensive
-                    //     if let Some(glyph) = self
-                    //         .glyph_cache
-                    //         .entry((gid, *paint_idx))
-                    //         .or_insert_with(|| provider.get(gid.to_u16(), Some(glyph_brush)))
-                    //     {
-                    //         let xform = transform
-                    //             * Affine::translate((pen_x, pen_y + text_style.size as f64))
-                    //             * Affine::scale_non_uniform(1.0, -1.0);
-
-                    //         sb.append(glyph, Some(xform));
-                    //     }
-
-                    //     pen_x += advance;
-                    // }
+                CanvasCommand::Glyphs {
+                    paint_idx,
+                    rect,
+                    runs,
+                } => {
+                    Self::draw_glyph_runs(
+                        &mut self.fragment,
+                        fonts,
+                        &self.paints[*paint_idx],
+                        *rect,
+                        runs,
+                    );
                 }
 
                 cmd => {
@@ -253,63 +435,353 @@ impl VelloCanvasObject {
         }
     }
 
-    pub fn begin(&self, transform: Affine, scene: &mut Scene) {
-        let transform =
-            transform * Affine::translate(Vec2::new(self.offset.x as f64, self.offset.y as f64));
-
-        scene.append(&self.fragment, Some(transform));
+    pub fn begin(&self, transform: Affine, sink: &mut impl SceneSink) {
+        sink.append(&self.fragment, transform);
 
         for child in &self.children {
-            child.begin(transform, scene);
-            child.end(transform, scene);
+            child.begin(transform, sink);
+            child.end(transform, sink);
         }
 
         if let Some(tail) = &self.tail {
-            tail.begin(transform, scene);
+            tail.begin(transform, sink);
         }
     }
 
-    pub fn end(&self, transform: Affine, scene: &mut Scene) {
+    pub fn end(&self, transform: Affine, sink: &mut impl SceneSink) {
         if let Some(tail) = &self.tail {
-            tail.end(transform, scene);
+            tail.end(transform, sink);
         }
     }
 }
 
 pub struct LayerObject {
     pub rect: Rect,
+    pub shape: Shape,
+
+    /// The path of `shape` within the layer, used to clip its contents.
+    pub clip: BezPath,
+
+    pub blend: peniko::BlendMode,
+    pub alpha: f32,
 
     pub canvas: VelloCanvasObject,
 }
 
 impl LayerObject {
-    pub fn update(&mut self, fonts: &mut VelloFonts, canvas: Canvas) {
-        self.canvas.update(fonts, canvas);
+    pub fn new(fonts: &mut VelloFonts, parent_paints: &[Paint], layer: CanvasLayer) -> Self {
+        let rect = layer.offset & layer.canvas.size;
+
+        let mut object = Self {
+            rect,
+            shape: layer.style.shape.clone(),
+
+            clip: convert::to_bez_path(&layer.style.shape, rect.width, rect.height),
+
+            blend: Mix::Clip.into(),
+            alpha: 1.0,
+
+            canvas: VelloCanvasObject::default(),
+        };
+
+        object.update(fonts, parent_paints, layer);
+
+        object
     }
 
-    pub fn begin(&self, transform: Affine, scene: &mut Scene) {
+    /// Updates the layer to match `layer`, returning `true` if anything changed.
+    ///
+    /// The layer's paint lives in the parent canvas, so its paints must be given.
+    pub fn update(
+        &mut self,
+        fonts: &mut VelloFonts,
+        parent_paints: &[Paint],
+        layer: CanvasLayer,
+    ) -> bool {
+        let mut changed = false;
+
+        if layer.style.filter.is_some() || layer.style.backdrop_filter.is_some() {
+            tracing::warn!("layer filters are not supported by vello, drawing unfiltered");
+        }
+
+        let rect = layer.offset & layer.canvas.size;
+
+        if self.rect != rect || self.shape != layer.style.shape {
+            self.clip = convert::to_bez_path(&layer.style.shape, rect.width, rect.height);
+
+            self.rect = rect;
+            self.shape = layer.style.shape;
+
+            changed = true;
+        }
+
+        let (blend, alpha) = match parent_paints.get(layer.style.paint_idx) {
+            Some(paint) => {
+                let blend = convert::to_blend_mode(paint.blend_mode);
+
+                // A fully opaque layer drawn normally only needs to clip its contents
+                if blend.compose == Compose::SrcOver
+                    && blend.mix == Mix::Normal
+                    && paint.color.alpha >= 1.0
+                {
+                    (Mix::Clip.into(), 1.0)
+                } else {
+                    (blend, paint.color.alpha.clamp(0.0, 1.0))
+                }
+            }
+
+            None => {
+                tracing::warn!(
+                    paint_idx = layer.style.paint_idx,
+                    "layer references a paint that does not exist"
+                );
+
+                (Mix::Clip.into(), 1.0)
+            }
+        };
+
+        if self.blend != blend || self.alpha != alpha {
+            self.blend = blend;
+            self.alpha = alpha;
+
+            changed = true;
+        }
+
+        changed |= self.canvas.update(fonts, layer.canvas);
+
+        changed
+    }
+
+    pub fn begin(&self, transform: Affine, sink: &mut impl SceneSink) {
         let transform =
             transform * Affine::translate((self.rect.left as f64, self.rect.top as f64));
 
-        scene.push_layer(
-            Mix::Clip,
-            1.0,
-            transform,
-            &[
-                PathEl::LineTo((0.0, 0.0).into()),
-                PathEl::LineTo((self.rect.width as f64, 0.0).into()),
-                PathEl::LineTo((self.rect.width as f64, self.rect.height as f64).into()),
-                PathEl::LineTo((0.0, self.rect.height as f64).into()),
-                PathEl::ClosePath,
-            ],
+        sink.push_layer(self.blend, self.alpha, transform, &self.clip);
+
+        self.canvas.begin(transform, sink);
+    }
+
+    pub fn end(&self, transform: Affine, sink: &mut impl SceneSink) {
+        let transform =
+            transform * Affine::translate((self.rect.left as f64, self.rect.top as f64));
+
+        self.canvas.end(transform, sink);
+
+        sink.pop_layer();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use agui_core::{
+        render::canvas::{
+            paint::Paint,
+            painter::{CanvasPainter, Head},
+            Canvas,
+        },
+        unit::{BlendMode, Color, Rect, Shape, Size},
+    };
+    use vello::{
+        kurbo::{self, Affine, BezPath, Shape as _},
+        peniko::{self, Compose, Mix},
+        Scene,
+    };
+
+    use crate::renderer::fonts::VelloFonts;
+
+    use super::{SceneSink, VelloCanvasObject};
+
+    #[derive(Debug, PartialEq)]
+    enum Op {
+        Push {
+            blend: peniko::BlendMode,
+            alpha: f32,
+            origin: (f64, f64),
+            clip: kurbo::Rect,
+        },
+        Pop,
+        Append {
+            origin: (f64, f64),
+        },
+    }
+
+    #[derive(Default)]
+    struct RecordingSink {
+        ops: Vec<Op>,
+    }
+
+    impl SceneSink for RecordingSink {
+        fn push_layer(
+            &mut self,
+            blend: peniko::BlendMode,
+            alpha: f32,
+            transform: Affine,
+            clip: &BezPath,
+        ) {
+            let origin = transform.translation();
+
+            self.ops.push(Op::Push {
+                blend,
+                alpha,
+                origin: (origin.x, origin.y),
+                clip: clip.bounding_box(),
+            });
+        }
+
+        fn pop_layer(&mut self) {
+            self.ops.push(Op::Pop);
+        }
+
+        fn append(&mut self, _: &Scene, transform: Affine) {
+            let origin = transform.translation();
+
+            self.ops.push(Op::Append {
+                origin: (origin.x, origin.y),
+            });
+        }
+    }
+
+    fn encode(object: &VelloCanvasObject) -> Vec<Op> {
+        let mut sink = RecordingSink::default();
+
+        object.begin(Affine::IDENTITY, &mut sink);
+        object.end(Affine::IDENTITY, &mut sink);
+
+        sink.ops
+    }
+
+    fn clip() -> peniko::BlendMode {
+        Mix::Clip.into()
+    }
+
+    #[test]
+    fn encodes_child_layers() {
+        let mut canvas = Canvas::new(Size::new(20.0, 20.0));
+
+        {
+            let mut painter = CanvasPainter::<Head<()>>::begin(&mut canvas);
+
+            let brush = painter.add_paint(Paint {
+                color: Color::from_rgba((1.0, 1.0, 1.0, 0.5)),
+                ..Paint::default()
+            });
+
+            let _ = painter.layer_at(
+                Rect::new(5.0, 5.0, 10.0, 10.0),
+                &brush,
+                Shape::Circle,
+                |mut painter| {
+                    let brush = painter.add_paint(Paint::default());
+
+                    painter.layer(&brush, Shape::Rect, |_| {});
+                },
+            );
+        }
+
+        let mut object = VelloCanvasObject::default();
+
+        assert!(object.update(&mut VelloFonts::default(), canvas));
+
+        assert_eq!(
+            encode(&object),
+            [
+                Op::Append { origin: (0.0, 0.0) },
+                Op::Push {
+                    blend: peniko::BlendMode::new(Mix::Normal, Compose::SrcOver),
+                    alpha: 0.5,
+                    origin: (5.0, 5.0),
+                    clip: kurbo::Rect::new(0.0, 0.0, 10.0, 10.0),
+                },
+                Op::Append { origin: (5.0, 5.0) },
+                Op::Push {
+                    blend: clip(),
+                    alpha: 1.0,
+                    origin: (5.0, 5.0),
+                    clip: kurbo::Rect::new(0.0, 0.0, 10.0, 10.0),
+                },
+                Op::Append { origin: (5.0, 5.0) },
+                Op::Pop,
+                Op::Pop,
+            ]
+        );
+    }
+
+    #[test]
+    fn tail_layers_stay_open_until_the_canvas_ends() {
+        let mut canvas = Canvas::new(Size::new(20.0, 20.0));
+
+        {
+            let mut painter = CanvasPainter::<Head<()>>::begin(&mut canvas);
+
+            let brush = painter.add_paint(Paint {
+                blend_mode: BlendMode::Multiply,
+                ..Paint::default()
+            });
+
+            painter.start_layer_at(Rect::new(2.0, 4.0, 6.0, 8.0), &brush, Shape::Rect);
+        }
+
+        let mut object = VelloCanvasObject::default();
+
+        object.update(&mut VelloFonts::default(), canvas);
+
+        let mut sink = RecordingSink::default();
+
+        object.begin(Affine::IDENTITY, &mut sink);
+
+        assert_eq!(
+            sink.ops,
+            [
+                Op::Append { origin: (0.0, 0.0) },
+                Op::Push {
+                    blend: peniko::BlendMode::new(Mix::Multiply, Compose::SrcOver),
+                    alpha: 1.0,
+                    origin: (2.0, 4.0),
+                    clip: kurbo::Rect::new(0.0, 0.0, 6.0, 8.0),
+                },
+                Op::Append { origin: (2.0, 4.0) },
+            ]
         );
 
-        self.canvas.begin(transform, scene);
+        object.end(Affine::IDENTITY, &mut sink);
+
+        assert_eq!(sink.ops.last(), Some(&Op::Pop));
     }
 
-    pub fn end(&self, transform: Affine, scene: &mut Scene) {
-        self.canvas.end(transform, scene);
+    #[test]
+    fn reuses_unchanged_layers() {
+        let build = |shape: Shape| {
+            let mut canvas = Canvas::new(Size::new(20.0, 20.0));
+
+            {
+                let mut painter = CanvasPainter::<Head<()>>::begin(&mut canvas);
+
+                let brush = painter.add_paint(Paint::default());
+
+                painter.draw_rect(&brush);
+
+                let _ = painter.layer(&brush, shape, |mut painter| {
+                    let brush = painter.add_paint(Paint::default());
+
+                    painter.draw_rect(&brush);
+                });
+            }
+
+            canvas
+        };
+
+        let mut fonts = VelloFonts::default();
+
+        let mut object = VelloCanvasObject::default();
+
+        assert!(object.update(&mut fonts, build(Shape::Rect)));
+        assert!(!object.update(&mut fonts, build(Shape::Rect)));
+
+        assert!(object.update(&mut fonts, build(Shape::Circle)));
+        assert_eq!(object.children.len(), 1);
+        assert_eq!(object.children[0].shape, Shape::Circle);
 
-        scene.pop_layer();
+        assert!(object.update(&mut fonts, Canvas::new(Size::new(20.0, 20.0))));
+        assert!(object.children.is_empty());
     }
 }
@@ -94,10 +94,9 @@ impl VelloScene {
             .get_mut(render_object_id)
             .expect("received canvas for a removed object");
 
-        object.canvas.update(fonts, canvas);
-
-        // TODO: check if the canvas actually changed
-        self.needs_redraw = true;
+        if object.canvas.update(fonts, canvas) {
+            self.needs_redraw = true;
+        }
     }
 
     pub fn redraw(&mut self) {
@@ -1,6 +1,8 @@
 use std::sync::Arc;
 
-use agui_core::unit::{Font, FontData};
+use agui_core::unit::{Font, FontData, FontId, TextStyle};
+use agui_text::TextFonts;
+use parking_lot::Mutex;
 use rustc_hash::FxHashMap;
 use vello::{
     glyph::{
@@ -12,7 +14,10 @@ use vello::{
 
 pub struct VelloFonts {
     glyph_context: GlyphContext,
-    fonts: FxHashMap<Font, peniko::Font>,
+    fonts: FxHashMap<FontId, peniko::Font>,
+
+    /// The fonts used to shape text, shared with the view's text layout.
+    text_fonts: Arc<Mutex<TextFonts>>,
 }
 
 impl Default for VelloFonts {
@@ -20,6 +25,8 @@ impl Default for VelloFonts {
         Self {
             glyph_context: GlyphContext::new(),
             fonts: FxHashMap::default(),
+
+            text_fonts: Arc::default(),
         }
     }
 }
@@ -44,20 +51,42 @@ impl VelloFonts {
             .new_provider(font, ppem, hint, variations)
     }
 
+    pub fn text_fonts(&self) -> &Arc<Mutex<TextFonts>> {
+        &self.text_fonts
+    }
+
     pub fn get(&self, font: &Font) -> Option<&peniko::Font> {
-        self.fonts.get(font)
+        self.fonts.get(&font.id())
     }
 
-    pub fn get_or_insert(&mut self, font: Font) -> &peniko::Font {
-        self.fonts
-            .entry(font)
-            .or_insert_with_key(|font| match font.as_ref() {
-                FontData::Bytes(bytes) => {
-                    peniko::Font::new(Blob::new(Arc::clone(bytes) as Arc<_>), 0)
-                }
+    /// Returns the font to draw glyphs with, or `None` if it isn't available. Fonts that
+    /// refer to a URL are only available once they've been loaded, and a font that isn't
+    /// available isn't remembered, so it's drawn once it is.
+    pub fn get_or_insert(&mut self, font: Font) -> Option<&peniko::Font> {
+        let id = font.id();
+
+        if !self.fonts.contains_key(&id) {
+            let resolved = match font.as_ref() {
+                FontData::Bytes(_) => font.clone(),
+
+                // Glyph runs are drawn with the face their text was shaped with, so a family
+                // only gets here if it's drawn some other way, and uses its regular face
+                FontData::Url(_) | FontData::Family(_) => self
+                    .text_fonts
+                    .lock()
+                    .resolve(&TextStyle::default().font(font.clone()))?,
+            };
+
+            let FontData::Bytes(bytes) = resolved.as_ref() else {
+                unreachable!("resolved fonts always contain bytes");
+            };
+
+            let data = peniko::Font::new(Blob::new(Arc::clone(bytes) as Arc<_>), resolved.index());
+
+            self.fonts.insert(id.clone(), data);
+        }
 
-                _ => todo!("font data not supported"),
-            })
+        self.fonts.get(&id)
     }
 
     pub fn to_font_ref(font: &peniko::Font) -> Option<FontRef<'_>> {
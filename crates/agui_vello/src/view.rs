@@ -6,11 +6,11 @@ use std::{
 use agui_core::{
     engine::rendering::{strategies::RenderingTreeTextLayoutStrategy, view::View},
     render::{canvas::Canvas, RenderObjectId},
-    unit::{Constraints, IntrinsicDimension, Offset, Size, TextStyle},
+    unit::{Offset, Size},
 };
 use agui_renderer::FrameNotifier;
+use agui_text::ShapedTextLayout;
 use parking_lot::{Mutex, RwLock};
-use vello::glyph::skrifa::MetadataProvider;
 
 use crate::{render::VelloScene, renderer::fonts::VelloFonts};
 
@@ -40,10 +40,12 @@ impl VelloViewHandle {
 
 impl VelloView {
     pub(crate) fn new(fonts: Arc<Mutex<VelloFonts>>) -> Self {
+        let text_fonts = Arc::clone(fonts.lock().text_fonts());
+
         Self {
-            fonts: Arc::clone(&fonts),
+            fonts,
 
-            text_layout: Box::new(VelloTextLayout { fonts }),
+            text_layout: Box::new(ShapedTextLayout::new(text_fonts)),
 
             scene: Arc::default(),
 
@@ -234,136 +236,6 @@ enum Change {
     },
 }
 
-#[derive(Clone)]
-struct VelloTextLayout {
-    fonts: Arc<Mutex<VelloFonts>>,
-}
-
-impl RenderingTreeTextLayoutStrategy for VelloTextLayout {
-    fn compute_intrinsic_size(
-        &self,
-        text_style: &TextStyle,
-        text: &str,
-        dimension: IntrinsicDimension,
-        cross_axis: f32,
-    ) -> f32 {
-        let mut fonts = self.fonts.lock();
-
-        let font = fonts.get_or_insert(text_style.font.clone());
-        let font_ref = VelloFonts::to_font_ref(font).expect("failed to get font ref");
-
-        let axes = font_ref.axes();
-        let font_size = vello::skrifa::instance::Size::new(text_style.size);
-        let var_loc = axes.location(&[] as &[(&str, f32)]);
-        let charmap = font_ref.charmap();
-        let metrics = font_ref.metrics(font_size, &var_loc);
-        let line_height = metrics.ascent - metrics.descent + metrics.leading;
-        let glyph_metrics = font_ref.glyph_metrics(font_size, &var_loc);
-
-        let mut pen_x = 0f32;
-        let mut pen_y = 0f32;
-
-        match dimension {
-            IntrinsicDimension::MinWidth => {
-                todo!("minimum text intrinsic width is not yet not implemented");
-            }
-
-            // The maximum intrinsic width is the width of the widest line without wrapping
-            IntrinsicDimension::MaxWidth => {
-                for ch in text.chars() {
-                    if ch == '\n' {
-                        pen_y += line_height;
-                        pen_x = 0.0;
-                        continue;
-                    }
-
-                    let gid = charmap.map(ch).unwrap_or_default();
-                    let advance = glyph_metrics.advance_width(gid).unwrap_or_default();
-
-                    pen_x += advance;
-                }
-
-                pen_x
-            }
-
-            // The height of the text necessary to fit within the given width (`cross_axis`)
-            IntrinsicDimension::MinHeight | IntrinsicDimension::MaxHeight => {
-                for ch in text.chars() {
-                    if ch == '\n' {
-                        pen_y += line_height;
-                        pen_x = 0.0;
-                        continue;
-                    }
-
-                    let gid = charmap.map(ch).unwrap_or_default();
-                    let advance = glyph_metrics.advance_width(gid).unwrap_or_default();
-
-                    // Naive wrapping (doesn't account for word boundaries)
-                    if pen_x + advance > cross_axis {
-                        pen_y += line_height;
-                        pen_x = 0.0;
-                    }
-
-                    pen_x += advance;
-                }
-
-                pen_y + line_height
-            }
-        }
-    }
-
-    fn compute_size(
-        &mut self,
-        text_style: &TextStyle,
-        text: &str,
-        constraints: Constraints,
-    ) -> Size {
-        if text.is_empty() {
-            return Size::ZERO;
-        }
-
-        let mut fonts = self.fonts.lock();
-
-        let font = fonts.get_or_insert(text_style.font.clone());
-        let font_ref = VelloFonts::to_font_ref(font).expect("failed to get font ref");
-
-        let axes = font_ref.axes();
-        let font_size = vello::skrifa::instance::Size::new(text_style.size);
-        let var_loc = axes.location(&[] as &[(&str, f32)]);
-        let charmap = font_ref.charmap();
-        let metrics = font_ref.metrics(font_size, &var_loc);
-        let line_height = metrics.ascent - metrics.descent + metrics.leading;
-        let glyph_metrics = font_ref.glyph_metrics(font_size, &var_loc);
-
-        let mut pen_x = 0f32;
-
-        let mut size = Size::new(0.0, line_height);
-
-        for ch in text.chars() {
-            if ch == '\n' {
-                size.height += line_height;
-                pen_x = 0.0;
-                continue;
-            }
-
-            let gid = charmap.map(ch).unwrap_or_default();
-            let advance = glyph_metrics.advance_width(gid).unwrap_or_default();
-
-            // Naive wrapping (doesn't account for word boundaries)
-            if pen_x + advance > constraints.max_width() {
-                size.height += line_height;
-                pen_x = 0.0;
-            }
-
-            pen_x += advance;
-
-            size.width = size.width.max(pen_x);
-        }
-
-        size
-    }
-}
-
 #[derive(Debug)]
 #[allow(dead_code)]
 struct SyncTimings {
@@ -1,7 +1,9 @@
 // mod button;
 // mod gesture_detector;
-// mod text_input;
+mod markdown;
+mod text_field;
 
 // pub use self::button::*;
 // pub use self::gesture_detector::*;
-// pub use self::text_input::*;
+pub use self::markdown::*;
+pub use self::text_field::*;
@@ -0,0 +1,1143 @@
+use std::borrow::Cow;
+
+use agui_core::{
+    callback::Callback,
+    unit::{HorizontalAlign, TextBaseline, Texture},
+    widget::{IntoWidget, Widget},
+};
+use agui_primitives::{
+    colored_box::ColoredBox,
+    decorated_box::{Border, BoxDecoration, DecoratedBox},
+    flex::{
+        Column, CrossAxisAlignment, FlexFit, Flexible, MainAxisAlignment, MainAxisSize, Row,
+        VerticalDirection,
+    },
+    image::Image,
+    padding::Padding,
+    sized_box::SizedBox,
+    text::{InlineSpan, RichText, TextSpan, TextSpanStyle},
+};
+use pulldown_cmark::{Alignment, Event, Tag, TagEnd};
+use url::Url;
+
+use super::MarkdownStyleSheet;
+
+/// Builds the widgets of a markdown document from the events of its parser.
+///
+/// Blocks are collected into the innermost open container, such as a list item or a
+/// block quote, which becomes a widget of its own once it's closed. Inline content is
+/// collected into spans until the block that contains it is closed.
+pub(crate) struct MarkdownBuilder<'a> {
+    style_sheet: &'a MarkdownStyleSheet,
+
+    base_url: Option<&'a Url>,
+    on_tap_link: Option<&'a Callback<String>>,
+
+    /// The containers that are open, innermost last.
+    blocks: Vec<Block>,
+
+    /// The spans of the paragraph being built, if any.
+    inline: Option<Inline>,
+
+    /// The style of inline content at each level of nesting, with each level already
+    /// combined with the levels above it.
+    styles: Vec<TextSpanStyle>,
+
+    /// The tap callback of each link being built, innermost last.
+    links: Vec<Option<Callback<()>>>,
+
+    /// The text of the code block being built.
+    code: Option<String>,
+
+    /// The source and alternative text of the image being built.
+    image: Option<(String, String)>,
+}
+
+enum Container {
+    Root,
+
+    BlockQuote,
+
+    List { next_number: Option<u64> },
+
+    Item { marker: Cow<'static, str> },
+
+    Table { alignments: Vec<Alignment> },
+
+    TableRow,
+}
+
+struct Block {
+    container: Container,
+    children: Vec<Widget>,
+}
+
+struct Inline {
+    h_align: HorizontalAlign,
+
+    spans: Vec<InlineSpan>,
+}
+
+impl<'a> MarkdownBuilder<'a> {
+    pub fn new(
+        style_sheet: &'a MarkdownStyleSheet,
+        base_url: Option<&'a Url>,
+        on_tap_link: Option<&'a Callback<String>>,
+    ) -> Self {
+        Self {
+            style_sheet,
+
+            base_url,
+            on_tap_link,
+
+            blocks: vec![Block {
+                container: Container::Root,
+                children: Vec::new(),
+            }],
+
+            inline: None,
+
+            styles: vec![TextSpanStyle::default()],
+            links: Vec::new(),
+
+            code: None,
+            image: None,
+        }
+    }
+
+    /// Builds the document into a column of its blocks.
+    pub fn build<'e>(mut self, events: impl IntoIterator<Item = Event<'e>>) -> Widget {
+        for event in events {
+            self.event(event);
+        }
+
+        self.flush_inline();
+
+        // Close any containers the events left open
+        while self.blocks.len() > 1 {
+            self.close();
+        }
+
+        let blocks = self
+            .blocks
+            .pop()
+            .map(|block| block.children)
+            .unwrap_or_default();
+
+        self.column(blocks)
+    }
+
+    fn event(&mut self, event: Event) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+
+            Event::Text(text) => {
+                if let Some(code) = &mut self.code {
+                    code.push_str(&text);
+                } else if let Some((_, alt)) = &mut self.image {
+                    alt.push_str(&text);
+                } else {
+                    self.push_text(text.into_string(), None);
+                }
+            }
+
+            Event::Code(code) => {
+                if let Some((_, alt)) = &mut self.image {
+                    alt.push_str(&code);
+                } else {
+                    self.push_text(code.into_string(), Some(&self.style_sheet.code));
+                }
+            }
+
+            Event::SoftBreak => self.push_text(String::from(" "), None),
+            Event::HardBreak => self.push_text(String::from("\n"), None),
+
+            Event::Rule => {
+                self.flush_inline();
+
+                self.push_child(
+                    SizedBox {
+                        width: None,
+                        height: Some(self.style_sheet.horizontal_rule_thickness),
+
+                        child: Some(
+                            ColoredBox {
+                                color: self.style_sheet.horizontal_rule_color,
+
+                                child: None,
+                            }
+                            .into_widget(),
+                        ),
+                    }
+                    .into_widget(),
+                );
+            }
+
+            Event::TaskListMarker(checked) => {
+                // The marker replaces the bullet of the item it starts
+                if let Some(Block {
+                    container: Container::Item { marker },
+                    ..
+                }) = self.blocks.last_mut()
+                {
+                    *marker = if checked {
+                        Cow::clone(&self.style_sheet.task_checked)
+                    } else {
+                        Cow::clone(&self.style_sheet.task_unchecked)
+                    };
+                }
+            }
+
+            // HTML, footnotes and metadata aren't rendered
+            _ => {}
+        }
+    }
+
+    fn start(&mut self, tag: Tag) {
+        match tag {
+            Tag::Paragraph => {
+                self.flush_inline();
+                self.open_inline(HorizontalAlign::Start);
+            }
+
+            Tag::Heading { level, .. } => {
+                self.flush_inline();
+                self.push_style(self.style_sheet.heading_style(level));
+                self.open_inline(HorizontalAlign::Start);
+            }
+
+            Tag::BlockQuote => {
+                self.flush_inline();
+                self.push_style(&self.style_sheet.block_quote);
+                self.open(Container::BlockQuote);
+            }
+
+            Tag::CodeBlock(_) => {
+                self.flush_inline();
+                self.code = Some(String::new());
+            }
+
+            Tag::List(first_number) => {
+                self.flush_inline();
+                self.open(Container::List {
+                    next_number: first_number,
+                });
+            }
+
+            Tag::Item => {
+                self.flush_inline();
+
+                let marker = match self.blocks.last_mut() {
+                    Some(Block {
+                        container:
+                            Container::List {
+                                next_number: Some(number),
+                            },
+                        ..
+                    }) => {
+                        *number += 1;
+
+                        Cow::Owned(format!("{}.", *number - 1))
+                    }
+
+                    _ => Cow::clone(&self.style_sheet.list_bullet),
+                };
+
+                self.open(Container::Item { marker });
+            }
+
+            Tag::Table(alignments) => {
+                self.flush_inline();
+                self.open(Container::Table { alignments });
+            }
+
+            Tag::TableHead => {
+                self.push_style(&self.style_sheet.table_head);
+                self.open(Container::TableRow);
+            }
+
+            Tag::TableRow => self.open(Container::TableRow),
+
+            Tag::TableCell => {
+                let column = self.blocks.last().map_or(0, |block| block.children.len());
+
+                let alignment = self
+                    .blocks
+                    .iter()
+                    .rev()
+                    .find_map(|block| match &block.container {
+                        Container::Table { alignments } => alignments.get(column).copied(),
+                        _ => None,
+                    })
+                    .unwrap_or(Alignment::None);
+
+                self.open_inline(match alignment {
+                    Alignment::None | Alignment::Left => HorizontalAlign::Start,
+                    Alignment::Center => HorizontalAlign::Center,
+                    Alignment::Right => HorizontalAlign::End,
+                });
+            }
+
+            Tag::Emphasis => self.push_style(&self.style_sheet.emphasis),
+            Tag::Strong => self.push_style(&self.style_sheet.strong),
+            Tag::Strikethrough => self.push_style(&self.style_sheet.strikethrough),
+
+            Tag::Link { dest_url, .. } => {
+                self.push_style(&self.style_sheet.link);
+
+                self.links.push(
+                    self.on_tap_link
+                        .map(|on_tap_link| on_tap_link.bind(dest_url.into_string())),
+                );
+            }
+
+            Tag::Image { dest_url, .. } => {
+                self.image = Some((dest_url.into_string(), String::new()));
+            }
+
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Paragraph => self.flush_inline(),
+
+            TagEnd::Heading(_) => {
+                self.flush_inline();
+                self.styles.pop();
+            }
+
+            TagEnd::BlockQuote => {
+                self.flush_inline();
+                self.close();
+                self.styles.pop();
+            }
+
+            TagEnd::CodeBlock => {
+                let code = self.code.take().unwrap_or_default();
+
+                let style = merge(self.current_style(), &self.style_sheet.code_block);
+
+                self.push_child(
+                    DecoratedBox {
+                        decoration: self.style_sheet.code_block_decoration.clone(),
+
+                        child: Some(
+                            Padding {
+                                padding: self.style_sheet.code_block_padding.into(),
+
+                                child: Some(
+                                    RichText {
+                                        style: self.style_sheet.text.clone(),
+
+                                        // The last line of a code block always ends with a
+                                        // line break, which shouldn't add an empty line
+                                        text: TextSpan::new(
+                                            code.strip_suffix('\n').unwrap_or(&code).to_owned(),
+                                        )
+                                        .style(style),
+                                    }
+                                    .into_widget(),
+                                ),
+                            }
+                            .into_widget(),
+                        ),
+                    }
+                    .into_widget(),
+                );
+            }
+
+            TagEnd::List(_) | TagEnd::Item | TagEnd::Table => {
+                self.flush_inline();
+                self.close();
+            }
+
+            TagEnd::TableHead => {
+                self.close();
+                self.styles.pop();
+            }
+
+            TagEnd::TableRow => self.close(),
+
+            TagEnd::TableCell => {
+                let cell = self.take_inline();
+
+                self.push_child(
+                    Padding {
+                        padding: self.style_sheet.table_cell_padding.into(),
+
+                        child: cell,
+                    }
+                    .into_widget(),
+                );
+            }
+
+            TagEnd::Emphasis | TagEnd::Strong | TagEnd::Strikethrough => {
+                self.styles.pop();
+            }
+
+            TagEnd::Link => {
+                self.styles.pop();
+                self.links.pop();
+            }
+
+            TagEnd::Image => {
+                if let Some((source, alt)) = self.image.take() {
+                    self.push_image(&source, alt);
+                }
+            }
+
+            _ => {}
+        }
+    }
+
+    fn open(&mut self, container: Container) {
+        self.blocks.push(Block {
+            container,
+            children: Vec::new(),
+        });
+    }
+
+    /// Closes the innermost container, adding its widget to the container around it.
+    fn close(&mut self) {
+        if self.blocks.len() <= 1 {
+            return;
+        }
+
+        let Some(block) = self.blocks.pop() else {
+            return;
+        };
+
+        let style_sheet = self.style_sheet;
+
+        let widget = match block.container {
+            Container::Root => return,
+
+            Container::BlockQuote => DecoratedBox {
+                decoration: style_sheet.block_quote_decoration.clone(),
+
+                child: Some(
+                    Padding {
+                        padding: style_sheet.block_quote_padding.into(),
+
+                        child: Some(self.column(block.children)),
+                    }
+                    .into_widget(),
+                ),
+            }
+            .into_widget(),
+
+            Container::List { .. } => self.column(block.children),
+
+            Container::Item { marker } => Row {
+                main_axis_size: MainAxisSize::default(),
+
+                main_axis_alignment: MainAxisAlignment::default(),
+                cross_axis_alignment: CrossAxisAlignment::Baseline(TextBaseline::Alphabetic),
+                vertical_direction: VerticalDirection::default(),
+
+                text_direction: None,
+
+                children: vec![
+                    SizedBox {
+                        width: Some(style_sheet.list_indent),
+                        height: None,
+
+                        child: Some(
+                            RichText {
+                                style: style_sheet.text.clone(),
+
+                                text: TextSpan::new(marker).style(self.current_style().clone()),
+                            }
+                            .into_widget(),
+                        ),
+                    }
+                    .into_widget(),
+                    Flexible {
+                        flex: Some(1.0),
+                        fit: Some(FlexFit::Tight),
+
+                        child: self.column(block.children),
+                    }
+                    .into_widget(),
+                ],
+            }
+            .into_widget(),
+
+            Container::Table { .. } => DecoratedBox {
+                decoration: BoxDecoration::default().border(Border::all(style_sheet.table_border)),
+
+                child: Some(Self::stretched(block.children)),
+            }
+            .into_widget(),
+
+            Container::TableRow => DecoratedBox {
+                decoration: BoxDecoration::default().border(Border {
+                    bottom: style_sheet.table_border,
+
+                    ..Border::default()
+                }),
+
+                child: Some(
+                    Row {
+                        main_axis_size: MainAxisSize::default(),
+
+                        main_axis_alignment: MainAxisAlignment::default(),
+                        cross_axis_alignment: CrossAxisAlignment::Start,
+                        vertical_direction: VerticalDirection::default(),
+
+                        text_direction: None,
+
+                        // Each column of the table is given an equal share of its width
+                        children: block
+                            .children
+                            .into_iter()
+                            .map(|cell| {
+                                Flexible {
+                                    flex: Some(1.0),
+                                    fit: Some(FlexFit::Tight),
+
+                                    child: cell,
+                                }
+                                .into_widget()
+                            })
+                            .collect(),
+                    }
+                    .into_widget(),
+                ),
+            }
+            .into_widget(),
+        };
+
+        self.push_child(widget);
+    }
+
+    /// Adds a widget to the innermost container.
+    fn push_child(&mut self, widget: Widget) {
+        if let Some(block) = self.blocks.last_mut() {
+            block.children.push(widget);
+        }
+    }
+
+    fn open_inline(&mut self, h_align: HorizontalAlign) {
+        self.inline = Some(Inline {
+            h_align,
+
+            spans: Vec::new(),
+        });
+    }
+
+    /// Ends the paragraph being built, if any, returning its widget.
+    fn take_inline(&mut self) -> Option<Widget> {
+        let inline = self.inline.take()?;
+
+        Some(
+            RichText {
+                style: self.style_sheet.text.clone().h_align(inline.h_align),
+
+                text: TextSpan::default().children(inline.spans),
+            }
+            .into_widget(),
+        )
+    }
+
+    /// Ends the paragraph being built, if any, adding it to the innermost container.
+    fn flush_inline(&mut self) {
+        if let Some(paragraph) = self.take_inline() {
+            self.push_child(paragraph);
+        }
+    }
+
+    fn push_style(&mut self, style: &TextSpanStyle) {
+        self.styles.push(merge(self.current_style(), style));
+    }
+
+    fn current_style(&self) -> &TextSpanStyle {
+        self.styles
+            .last()
+            .expect("the style stack always contains the base style")
+    }
+
+    fn push_span(&mut self, span: impl Into<InlineSpan>) {
+        // Text that isn't within a paragraph, such as in a tight list item, starts one
+        self.inline
+            .get_or_insert_with(|| Inline {
+                h_align: HorizontalAlign::Start,
+
+                spans: Vec::new(),
+            })
+            .spans
+            .push(span.into());
+    }
+
+    fn push_text(&mut self, text: String, style: Option<&TextSpanStyle>) {
+        let style = match style {
+            Some(style) => merge(self.current_style(), style),
+            None => self.current_style().clone(),
+        };
+
+        let mut span = TextSpan::new(text).style(style);
+
+        span.on_tap = self.links.last().cloned().flatten();
+
+        self.push_span(span);
+    }
+
+    fn push_image(&mut self, source: &str, alt: String) {
+        let url = match self.base_url {
+            Some(base_url) => base_url.join(source),
+            None => Url::parse(source),
+        };
+
+        match url {
+            Ok(url) => self.push_span(
+                Image {
+                    texture: Texture::from_url(url),
+
+                    fit: Default::default(),
+                    alignment: Default::default(),
+                    repeat: Default::default(),
+
+                    cache: None,
+                    assets: None,
+                }
+                .into_widget(),
+            ),
+
+            Err(err) => {
+                tracing::warn!("failed to resolve markdown image {:?}: {}", source, err);
+
+                // Show the description of the image in its place
+                if !alt.is_empty() {
+                    self.push_text(alt, None);
+                }
+            }
+        }
+    }
+
+    fn column(&self, children: Vec<Widget>) -> Widget {
+        let spacing = self.style_sheet.block_spacing;
+
+        let mut spaced = Vec::with_capacity(children.len() * 2);
+
+        for (idx, child) in children.into_iter().enumerate() {
+            if idx > 0 && spacing > 0.0 {
+                spaced.push(
+                    SizedBox {
+                        width: None,
+                        height: Some(spacing),
+
+                        child: None,
+                    }
+                    .into_widget(),
+                );
+            }
+
+            spaced.push(child);
+        }
+
+        Self::stretched(spaced)
+    }
+
+    /// Returns a column that stretches each of its children to its width.
+    fn stretched(children: Vec<Widget>) -> Widget {
+        Column {
+            main_axis_size: MainAxisSize::Min,
+
+            main_axis_alignment: MainAxisAlignment::default(),
+            cross_axis_alignment: CrossAxisAlignment::Stretch,
+            vertical_direction: VerticalDirection::default(),
+
+            text_direction: None,
+
+            children,
+        }
+        .into_widget()
+    }
+}
+
+/// Combines a span style with the style it's nested within. Anything the inner style sets
+/// takes precedence, except that decorations of both are drawn.
+fn merge(outer: &TextSpanStyle, inner: &TextSpanStyle) -> TextSpanStyle {
+    TextSpanStyle {
+        font: inner.font.clone().or_else(|| outer.font.clone()),
+
+        size: inner.size.or(outer.size),
+        color: inner.color.or(outer.color),
+
+        letter_spacing: inner.letter_spacing.or(outer.letter_spacing),
+        word_spacing: inner.word_spacing.or(outer.word_spacing),
+
+        font_weight: inner.font_weight.or(outer.font_weight),
+        font_style: inner.font_style.or(outer.font_style),
+        font_stretch: inner.font_stretch.or(outer.font_stretch),
+
+        decoration: match (outer.decoration, inner.decoration) {
+            (Some(outer), Some(inner)) => Some(outer.union(inner)),
+            (outer, inner) => inner.or(outer),
+        },
+        decoration_style: inner.decoration_style.or(outer.decoration_style),
+        decoration_color: inner.decoration_color.or(outer.decoration_color),
+
+        background: inner.background.or(outer.background),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{rc::Rc, sync::Arc};
+
+    use agui_core::{
+        callback::{strategies::mocks::MockCallbackStratgy, Callback},
+        element::ElementId,
+        unit::{FontWeight, HorizontalAlign, TextureData},
+        widget::Widget,
+    };
+    use agui_primitives::{
+        decorated_box::DecoratedBox,
+        flex::{Column, Flexible, Row},
+        image::Image,
+        padding::Padding,
+        sized_box::SizedBox,
+        text::{InlineSpan, RichText, TextSpan},
+    };
+    use pulldown_cmark::{
+        Alignment, CodeBlockKind, CowStr, Event, HeadingLevel, LinkType, Tag, TagEnd,
+    };
+    use url::Url;
+
+    use crate::widgets::markdown::MarkdownStyleSheet;
+
+    use super::MarkdownBuilder;
+
+    fn style_sheet() -> MarkdownStyleSheet {
+        // Spacing between blocks would otherwise be mixed in with their widgets
+        MarkdownStyleSheet {
+            block_spacing: 0.0,
+
+            ..MarkdownStyleSheet::default()
+        }
+    }
+
+    /// Builds the events, returning the blocks of the document.
+    fn build(events: Vec<Event<'static>>) -> Vec<Widget> {
+        children(&MarkdownBuilder::new(&style_sheet(), None, None).build(events))
+    }
+
+    fn children(widget: &Widget) -> Vec<Widget> {
+        if let Some(column) = widget.downcast::<Column>() {
+            column.children.clone()
+        } else if let Some(row) = widget.downcast::<Row>() {
+            row.children.clone()
+        } else {
+            panic!("expected a column or row, got {}", widget.widget_name());
+        }
+    }
+
+    fn child(widget: &Widget) -> Widget {
+        if let Some(decorated_box) = widget.downcast::<DecoratedBox>() {
+            decorated_box.child.clone()
+        } else if let Some(padding) = widget.downcast::<Padding>() {
+            padding.child.clone()
+        } else if let Some(sized_box) = widget.downcast::<SizedBox>() {
+            sized_box.child.clone()
+        } else if let Some(flexible) = widget.downcast::<Flexible>() {
+            Some(flexible.child.clone())
+        } else {
+            panic!(
+                "expected a single child widget, got {}",
+                widget.widget_name()
+            );
+        }
+        .expect("widget has no child")
+    }
+
+    fn rich_text(widget: &Widget) -> Rc<RichText> {
+        widget
+            .downcast::<RichText>()
+            .unwrap_or_else(|| panic!("expected rich text, got {}", widget.widget_name()))
+    }
+
+    /// Returns the spans of a paragraph that hold text.
+    fn spans(widget: &Widget) -> Vec<TextSpan> {
+        fn collect(span: &TextSpan, spans: &mut Vec<TextSpan>) {
+            if !span.text.is_empty() {
+                spans.push(span.clone());
+            }
+
+            for child in &span.children {
+                if let InlineSpan::Text(child) = child {
+                    collect(child, spans);
+                }
+            }
+        }
+
+        let mut spans = Vec::new();
+
+        collect(&rich_text(widget).text, &mut spans);
+
+        spans
+    }
+
+    fn text(widget: &Widget) -> String {
+        spans(widget)
+            .iter()
+            .map(|span| span.text.as_ref())
+            .collect()
+    }
+
+    /// Returns the marker and the blocks of a list item.
+    fn item(widget: &Widget) -> (String, Vec<Widget>) {
+        let parts = children(widget);
+
+        (text(&child(&parts[0])), children(&child(&parts[1])))
+    }
+
+    fn paragraph(text: &'static str) -> Vec<Event<'static>> {
+        vec![
+            Event::Start(Tag::Paragraph),
+            Event::Text(text.into()),
+            Event::End(TagEnd::Paragraph),
+        ]
+    }
+
+    fn list(first_number: Option<u64>, items: Vec<Vec<Event<'static>>>) -> Vec<Event<'static>> {
+        let mut events = vec![Event::Start(Tag::List(first_number))];
+
+        for item in items {
+            events.push(Event::Start(Tag::Item));
+            events.extend(item);
+            events.push(Event::End(TagEnd::Item));
+        }
+
+        events.push(Event::End(TagEnd::List(first_number.is_some())));
+
+        events
+    }
+
+    fn link(dest_url: &'static str, text: &'static str) -> Vec<Event<'static>> {
+        vec![
+            Event::Start(Tag::Link {
+                link_type: LinkType::Inline,
+                dest_url: dest_url.into(),
+                title: CowStr::Borrowed(""),
+                id: CowStr::Borrowed(""),
+            }),
+            Event::Text(text.into()),
+            Event::End(TagEnd::Link),
+        ]
+    }
+
+    fn image(dest_url: &'static str, alt: &'static str) -> Vec<Event<'static>> {
+        vec![
+            Event::Start(Tag::Image {
+                link_type: LinkType::Inline,
+                dest_url: dest_url.into(),
+                title: CowStr::Borrowed(""),
+                id: CowStr::Borrowed(""),
+            }),
+            Event::Text(alt.into()),
+            Event::End(TagEnd::Image),
+        ]
+    }
+
+    #[test]
+    fn headings_use_their_level_style() {
+        let blocks = build(
+            [
+                vec![
+                    Event::Start(Tag::Heading {
+                        level: HeadingLevel::H2,
+                        id: None,
+                        classes: Vec::new(),
+                        attrs: Vec::new(),
+                    }),
+                    Event::Text("Title".into()),
+                    Event::End(TagEnd::Heading(HeadingLevel::H2)),
+                ],
+                paragraph("body"),
+            ]
+            .concat(),
+        );
+
+        assert_eq!(blocks.len(), 2);
+
+        let heading = spans(&blocks[0]);
+
+        assert_eq!(heading.len(), 1);
+        assert_eq!(heading[0].text, "Title");
+        assert_eq!(heading[0].style, style_sheet().h2);
+
+        // The heading's style doesn't leak into the blocks after it
+        let body = spans(&blocks[1]);
+
+        assert_eq!(body[0].text, "body");
+        assert_eq!(body[0].style.size, None);
+        assert_eq!(body[0].style.font_weight, None);
+    }
+
+    #[test]
+    fn nested_lists_are_indented_within_their_item() {
+        let blocks = build(list(
+            Some(3),
+            vec![
+                [
+                    vec![Event::Text("outer".into())],
+                    list(None, vec![vec![Event::Text("inner".into())]]),
+                ]
+                .concat(),
+                vec![Event::Text("second".into())],
+            ],
+        ));
+
+        assert_eq!(blocks.len(), 1);
+
+        let items = children(&blocks[0]);
+
+        assert_eq!(items.len(), 2);
+
+        let (marker, contents) = item(&items[0]);
+
+        assert_eq!(marker, "3.");
+        assert_eq!(contents.len(), 2);
+        assert_eq!(text(&contents[0]), "outer");
+
+        let nested = children(&contents[1]);
+
+        assert_eq!(nested.len(), 1);
+
+        let (marker, contents) = item(&nested[0]);
+
+        assert_eq!(marker, style_sheet().list_bullet);
+        assert_eq!(text(&contents[0]), "inner");
+
+        let (marker, contents) = item(&items[1]);
+
+        assert_eq!(marker, "4.");
+        assert_eq!(text(&contents[0]), "second");
+    }
+
+    #[test]
+    fn task_list_markers_replace_bullets() {
+        let blocks = build(list(
+            None,
+            vec![
+                vec![Event::TaskListMarker(true), Event::Text("done".into())],
+                vec![Event::TaskListMarker(false), Event::Text("todo".into())],
+            ],
+        ));
+
+        let items = children(&blocks[0]);
+
+        assert_eq!(item(&items[0]).0, style_sheet().task_checked);
+        assert_eq!(item(&items[1]).0, style_sheet().task_unchecked);
+    }
+
+    #[test]
+    fn table_cells_are_aligned_by_column() {
+        let cell = |text: &'static str| {
+            vec![
+                Event::Start(Tag::TableCell),
+                Event::Text(text.into()),
+                Event::End(TagEnd::TableCell),
+            ]
+        };
+
+        let blocks = build(
+            [
+                vec![
+                    Event::Start(Tag::Table(vec![
+                        Alignment::None,
+                        Alignment::Center,
+                        Alignment::Right,
+                    ])),
+                    Event::Start(Tag::TableHead),
+                ],
+                cell("a"),
+                cell("b"),
+                cell("c"),
+                vec![Event::End(TagEnd::TableHead), Event::Start(Tag::TableRow)],
+                cell("1"),
+                cell("2"),
+                cell("3"),
+                vec![Event::End(TagEnd::TableRow), Event::End(TagEnd::Table)],
+            ]
+            .concat(),
+        );
+
+        assert_eq!(blocks.len(), 1);
+
+        let rows = children(&child(&blocks[0]));
+
+        assert_eq!(rows.len(), 2);
+
+        let cells = |row: &Widget| {
+            children(&child(row))
+                .iter()
+                .map(|cell| child(&child(cell)))
+                .collect::<Vec<_>>()
+        };
+
+        let head = cells(&rows[0]);
+        let body = cells(&rows[1]);
+
+        assert_eq!(
+            head.iter().map(text).collect::<Vec<_>>(),
+            vec!["a", "b", "c"]
+        );
+
+        assert_eq!(
+            body.iter().map(text).collect::<Vec<_>>(),
+            vec!["1", "2", "3"]
+        );
+
+        for row in [&head, &body] {
+            assert_eq!(
+                row.iter()
+                    .map(|cell| rich_text(cell).style.h_align)
+                    .collect::<Vec<_>>(),
+                vec![
+                    HorizontalAlign::Start,
+                    HorizontalAlign::Center,
+                    HorizontalAlign::End
+                ]
+            );
+        }
+
+        assert_eq!(spans(&head[0])[0].style.font_weight, Some(FontWeight::BOLD));
+        assert_eq!(spans(&body[0])[0].style.font_weight, None);
+    }
+
+    #[test]
+    fn block_quotes_style_their_contents() {
+        let blocks = build(
+            [
+                vec![Event::Start(Tag::BlockQuote)],
+                paragraph("quoted"),
+                vec![Event::End(TagEnd::BlockQuote)],
+                paragraph("after"),
+            ]
+            .concat(),
+        );
+
+        assert_eq!(blocks.len(), 2);
+
+        let decorated_box = blocks[0]
+            .downcast::<DecoratedBox>()
+            .expect("block quotes are decorated");
+
+        assert_eq!(
+            decorated_box.decoration,
+            style_sheet().block_quote_decoration
+        );
+
+        let contents = children(&child(&child(&blocks[0])));
+
+        assert_eq!(text(&contents[0]), "quoted");
+        assert_eq!(
+            spans(&contents[0])[0].style.color,
+            style_sheet().block_quote.color
+        );
+
+        assert_eq!(spans(&blocks[1])[0].style.color, None);
+    }
+
+    #[test]
+    fn code_blocks_keep_their_text_verbatim() {
+        let blocks = build(vec![
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced("rust".into()))),
+            Event::Text("fn main() {\n".into()),
+            Event::Text("    *emphasis*\n}\n".into()),
+            Event::End(TagEnd::CodeBlock),
+        ]);
+
+        assert_eq!(blocks.len(), 1);
+
+        assert_eq!(
+            blocks[0].downcast::<DecoratedBox>().unwrap().decoration,
+            style_sheet().code_block_decoration
+        );
+
+        let code = child(&child(&blocks[0]));
+
+        // The trailing line break doesn't add an empty line
+        assert_eq!(text(&code), "fn main() {\n    *emphasis*\n}");
+        assert_eq!(spans(&code)[0].style.font, style_sheet().code_block.font);
+    }
+
+    #[test]
+    fn tapping_links_calls_on_tap_link() {
+        let strategy = MockCallbackStratgy::default();
+
+        let on_tap_link =
+            Callback::<String>::new::<()>(Arc::new(strategy.clone()), ElementId::default());
+
+        let style_sheet = style_sheet();
+
+        let blocks = MarkdownBuilder::new(&style_sheet, None, Some(&on_tap_link)).build(
+            [
+                vec![Event::Start(Tag::Paragraph), Event::Text("see ".into())],
+                link("https://example.com/", "the docs"),
+                vec![Event::End(TagEnd::Paragraph)],
+            ]
+            .concat(),
+        );
+
+        let spans = spans(&children(&blocks)[0]);
+
+        assert_eq!(spans.len(), 2);
+        assert!(spans[0].on_tap.is_none());
+        assert_eq!(spans[1].text, "the docs");
+        assert_eq!(spans[1].style.decoration, style_sheet.link.decoration);
+
+        spans[1]
+            .on_tap
+            .as_ref()
+            .expect("links can be tapped")
+            .call(());
+
+        let calls = strategy.calls.lock().unwrap();
+
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].0, on_tap_link.id());
+        assert_eq!(
+            calls[0].1.downcast_ref::<String>().unwrap(),
+            "https://example.com/"
+        );
+    }
+
+    #[test]
+    fn unresolvable_images_show_their_description() {
+        let style_sheet = style_sheet();
+        let base_url = Url::parse("https://example.com/docs/").unwrap();
+
+        let events = [
+            vec![Event::Start(Tag::Paragraph)],
+            image("images/logo.png", "logo"),
+            vec![Event::End(TagEnd::Paragraph)],
+        ]
+        .concat();
+
+        // Without a base URL, a relative source can't be loaded
+        let blocks =
+            children(&MarkdownBuilder::new(&style_sheet, None, None).build(events.clone()));
+
+        assert_eq!(text(&blocks[0]), "logo");
+
+        let blocks =
+            children(&MarkdownBuilder::new(&style_sheet, Some(&base_url), None).build(events));
+
+        let paragraph = rich_text(&blocks[0]);
+
+        let [InlineSpan::Widget(image)] = paragraph.text.children.as_slice() else {
+            panic!("expected the image to be the only span");
+        };
+
+        assert_eq!(
+            image.downcast::<Image>().unwrap().texture.data(),
+            &TextureData::Url(Url::parse("https://example.com/docs/images/logo.png").unwrap())
+        );
+    }
+}
@@ -0,0 +1,55 @@
+use std::borrow::Cow;
+
+use agui_core::{callback::Callback, widget::Widget};
+use agui_elements::stateless::{StatelessBuildContext, StatelessWidget};
+use agui_macros::StatelessWidget;
+use pulldown_cmark::{Options, Parser};
+use url::Url;
+
+mod builder;
+mod style_sheet;
+
+pub use pulldown_cmark::HeadingLevel;
+pub use style_sheet::*;
+
+use self::builder::MarkdownBuilder;
+
+/// Renders a document written in Markdown.
+///
+/// The document is parsed as CommonMark, along with tables, task lists and strikethrough,
+/// and each block is built into widgets styled by the `style_sheet`. Images are loaded
+/// through the [`Image`](agui_primitives::image::Image) widget, with relative sources
+/// resolved against `base_url`. Raw HTML isn't rendered.
+#[derive(StatelessWidget, Debug)]
+pub struct Markdown {
+    #[prop(into)]
+    pub data: Cow<'static, str>,
+
+    #[prop(default)]
+    pub style_sheet: MarkdownStyleSheet,
+
+    /// The URL that relative image sources are resolved against. If `None`, only absolute
+    /// sources are loaded, and other images are shown as their description.
+    #[prop(default)]
+    pub base_url: Option<Url>,
+
+    /// Called with the destination of a link when it's tapped.
+    #[prop(default)]
+    pub on_tap_link: Option<Callback<String>>,
+}
+
+impl StatelessWidget for Markdown {
+    fn build(&self, _: &mut StatelessBuildContext<Self>) -> Widget {
+        let parser = Parser::new_ext(
+            &self.data,
+            Options::ENABLE_TABLES | Options::ENABLE_TASKLISTS | Options::ENABLE_STRIKETHROUGH,
+        );
+
+        MarkdownBuilder::new(
+            &self.style_sheet,
+            self.base_url.as_ref(),
+            self.on_tap_link.as_ref(),
+        )
+        .build(parser)
+    }
+}
@@ -0,0 +1,269 @@
+use std::borrow::Cow;
+
+use agui_core::unit::{Color, EdgeInsets, Font, FontStyle, FontWeight, TextDecoration, TextStyle};
+use agui_primitives::{
+    decorated_box::{Border, BorderRadius, BorderSide, BoxDecoration},
+    text::TextSpanStyle,
+};
+use pulldown_cmark::HeadingLevel;
+
+/// The styles a [`Markdown`](super::Markdown) widget renders each kind of element with.
+///
+/// Text is laid out in `text`, and each span style overrides it for the text within that
+/// kind of element. Nested elements combine their styles, with the innermost taking
+/// precedence, except for decorations, which are drawn together.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarkdownStyleSheet {
+    /// The style of body text, which every other style builds upon.
+    pub text: TextStyle,
+
+    pub h1: TextSpanStyle,
+    pub h2: TextSpanStyle,
+    pub h3: TextSpanStyle,
+    pub h4: TextSpanStyle,
+    pub h5: TextSpanStyle,
+    pub h6: TextSpanStyle,
+
+    pub emphasis: TextSpanStyle,
+    pub strong: TextSpanStyle,
+    pub strikethrough: TextSpanStyle,
+
+    pub link: TextSpanStyle,
+
+    /// The style of code spans within text.
+    pub code: TextSpanStyle,
+
+    pub code_block: TextSpanStyle,
+    pub code_block_decoration: BoxDecoration,
+    pub code_block_padding: EdgeInsets,
+
+    pub block_quote: TextSpanStyle,
+    pub block_quote_decoration: BoxDecoration,
+    pub block_quote_padding: EdgeInsets,
+
+    /// The width of the space each list item's marker is placed in.
+    pub list_indent: f32,
+
+    /// The marker of items in unordered lists.
+    pub list_bullet: Cow<'static, str>,
+
+    /// The marker of unchecked items in task lists.
+    pub task_unchecked: Cow<'static, str>,
+
+    /// The marker of checked items in task lists.
+    pub task_checked: Cow<'static, str>,
+
+    /// The style of the text in a table's header row.
+    pub table_head: TextSpanStyle,
+
+    /// The line drawn around a table, and between its rows.
+    pub table_border: BorderSide,
+    pub table_cell_padding: EdgeInsets,
+
+    pub horizontal_rule_color: Color,
+    pub horizontal_rule_thickness: f32,
+
+    /// The space between consecutive blocks, such as paragraphs and list items.
+    pub block_spacing: f32,
+}
+
+impl Default for MarkdownStyleSheet {
+    fn default() -> Self {
+        let heading = TextSpanStyle::default().font_weight(FontWeight::BOLD);
+
+        let gray = Color::from_rgb((0.95, 0.95, 0.95));
+        let border = Color::from_rgb((0.8, 0.8, 0.8));
+
+        Self {
+            text: TextStyle::default(),
+
+            h1: heading.clone().size(28.0),
+            h2: heading.clone().size(22.0),
+            h3: heading.clone().size(18.0),
+            h4: heading.clone().size(16.0),
+            h5: heading.clone().size(14.0),
+            h6: heading.size(13.0),
+
+            emphasis: TextSpanStyle::default().font_style(FontStyle::Italic),
+            strong: TextSpanStyle::default().font_weight(FontWeight::BOLD),
+            strikethrough: TextSpanStyle::default().decoration(TextDecoration::LINE_THROUGH),
+
+            link: TextSpanStyle::default()
+                .color(Color::from_rgb((0.1, 0.4, 0.8)))
+                .decoration(TextDecoration::UNDERLINE),
+
+            code: TextSpanStyle::default()
+                .font(Font::from_family("monospace"))
+                .background(gray),
+
+            code_block: TextSpanStyle::default().font(Font::from_family("monospace")),
+            code_block_decoration: BoxDecoration::default()
+                .color(gray)
+                .border_radius(BorderRadius::all(4.0)),
+            code_block_padding: EdgeInsets::all(8.0),
+
+            block_quote: TextSpanStyle::default().color(Color::from_rgb((0.4, 0.4, 0.4))),
+            block_quote_decoration: BoxDecoration::default().border(Border {
+                left: BorderSide::new(border, 4.0),
+
+                ..Border::default()
+            }),
+            block_quote_padding: EdgeInsets::new(0.0, 0.0, 0.0, 12.0),
+
+            list_indent: 24.0,
+            list_bullet: Cow::Borrowed("\u{2022}"),
+            task_unchecked: Cow::Borrowed("\u{2610}"),
+            task_checked: Cow::Borrowed("\u{2611}"),
+
+            table_head: TextSpanStyle::default().font_weight(FontWeight::BOLD),
+            table_border: BorderSide::new(border, 1.0),
+            table_cell_padding: EdgeInsets::symmetric(4.0, 8.0),
+
+            horizontal_rule_color: border,
+            horizontal_rule_thickness: 1.0,
+
+            block_spacing: 8.0,
+        }
+    }
+}
+
+impl MarkdownStyleSheet {
+    pub fn text(mut self, text: TextStyle) -> Self {
+        self.text = text;
+        self
+    }
+
+    /// Sets the style of headings of the given level.
+    pub fn heading(mut self, level: HeadingLevel, style: TextSpanStyle) -> Self {
+        *self.heading_mut(level) = style;
+        self
+    }
+
+    pub fn emphasis(mut self, emphasis: TextSpanStyle) -> Self {
+        self.emphasis = emphasis;
+        self
+    }
+
+    pub fn strong(mut self, strong: TextSpanStyle) -> Self {
+        self.strong = strong;
+        self
+    }
+
+    pub fn strikethrough(mut self, strikethrough: TextSpanStyle) -> Self {
+        self.strikethrough = strikethrough;
+        self
+    }
+
+    pub fn link(mut self, link: TextSpanStyle) -> Self {
+        self.link = link;
+        self
+    }
+
+    pub fn code(mut self, code: TextSpanStyle) -> Self {
+        self.code = code;
+        self
+    }
+
+    pub fn code_block(mut self, code_block: TextSpanStyle) -> Self {
+        self.code_block = code_block;
+        self
+    }
+
+    pub fn code_block_decoration(mut self, code_block_decoration: BoxDecoration) -> Self {
+        self.code_block_decoration = code_block_decoration;
+        self
+    }
+
+    pub fn code_block_padding(mut self, code_block_padding: EdgeInsets) -> Self {
+        self.code_block_padding = code_block_padding;
+        self
+    }
+
+    pub fn block_quote(mut self, block_quote: TextSpanStyle) -> Self {
+        self.block_quote = block_quote;
+        self
+    }
+
+    pub fn block_quote_decoration(mut self, block_quote_decoration: BoxDecoration) -> Self {
+        self.block_quote_decoration = block_quote_decoration;
+        self
+    }
+
+    pub fn block_quote_padding(mut self, block_quote_padding: EdgeInsets) -> Self {
+        self.block_quote_padding = block_quote_padding;
+        self
+    }
+
+    pub fn list_indent(mut self, list_indent: f32) -> Self {
+        self.list_indent = list_indent;
+        self
+    }
+
+    pub fn list_bullet(mut self, list_bullet: impl Into<Cow<'static, str>>) -> Self {
+        self.list_bullet = list_bullet.into();
+        self
+    }
+
+    pub fn task_unchecked(mut self, task_unchecked: impl Into<Cow<'static, str>>) -> Self {
+        self.task_unchecked = task_unchecked.into();
+        self
+    }
+
+    pub fn task_checked(mut self, task_checked: impl Into<Cow<'static, str>>) -> Self {
+        self.task_checked = task_checked.into();
+        self
+    }
+
+    pub fn table_head(mut self, table_head: TextSpanStyle) -> Self {
+        self.table_head = table_head;
+        self
+    }
+
+    pub fn table_border(mut self, table_border: BorderSide) -> Self {
+        self.table_border = table_border;
+        self
+    }
+
+    pub fn table_cell_padding(mut self, table_cell_padding: EdgeInsets) -> Self {
+        self.table_cell_padding = table_cell_padding;
+        self
+    }
+
+    pub fn horizontal_rule_color(mut self, horizontal_rule_color: Color) -> Self {
+        self.horizontal_rule_color = horizontal_rule_color;
+        self
+    }
+
+    pub fn horizontal_rule_thickness(mut self, horizontal_rule_thickness: f32) -> Self {
+        self.horizontal_rule_thickness = horizontal_rule_thickness;
+        self
+    }
+
+    pub fn block_spacing(mut self, block_spacing: f32) -> Self {
+        self.block_spacing = block_spacing;
+        self
+    }
+
+    /// Returns the style of headings of the given level.
+    pub fn heading_style(&self, level: HeadingLevel) -> &TextSpanStyle {
+        match level {
+            HeadingLevel::H1 => &self.h1,
+            HeadingLevel::H2 => &self.h2,
+            HeadingLevel::H3 => &self.h3,
+            HeadingLevel::H4 => &self.h4,
+            HeadingLevel::H5 => &self.h5,
+            HeadingLevel::H6 => &self.h6,
+        }
+    }
+
+    fn heading_mut(&mut self, level: HeadingLevel) -> &mut TextSpanStyle {
+        match level {
+            HeadingLevel::H1 => &mut self.h1,
+            HeadingLevel::H2 => &mut self.h2,
+            HeadingLevel::H3 => &mut self.h3,
+            HeadingLevel::H4 => &mut self.h4,
+            HeadingLevel::H5 => &mut self.h5,
+            HeadingLevel::H6 => &mut self.h6,
+        }
+    }
+}
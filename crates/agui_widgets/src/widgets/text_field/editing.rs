@@ -0,0 +1,228 @@
+use std::ops::Range;
+
+use agui_core::unit::TextSelection;
+use agui_primitives::text::edit::EditableText;
+
+/// The text of a text field, along with what's selected in it and what's being composed
+/// by an input method.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEditingValue<S> {
+    pub text: S,
+
+    /// The selected range of the text, or the caret if it's collapsed.
+    pub selection: TextSelection,
+
+    /// The range of the text that an input method is still composing, if any.
+    pub composing: Option<Range<usize>>,
+}
+
+impl<S> TextEditingValue<S>
+where
+    S: EditableText,
+{
+    /// Creates a value with the caret at the end of `text`.
+    pub fn new(text: S) -> Self {
+        Self {
+            selection: TextSelection::collapsed(text.len()),
+            composing: None,
+
+            text,
+        }
+    }
+
+    /// Replaces the text, keeping the selection where it was if it still fits within it.
+    pub fn with_text(self, text: S) -> Self {
+        let len = text.len();
+
+        let is_within = |offset: usize| offset <= len && text.as_str().is_char_boundary(offset);
+
+        Self {
+            selection: if is_within(self.selection.base) && is_within(self.selection.extent) {
+                self.selection
+            } else {
+                TextSelection::collapsed(len)
+            },
+            composing: None,
+
+            text,
+        }
+    }
+
+    pub fn selected_text(&self) -> &str {
+        &self.text.as_str()[self.selection.range()]
+    }
+
+    /// Replaces the selection with `text`, leaving the caret after it.
+    pub fn replace_selection(&mut self, text: &str) {
+        let range = self.selection.range();
+
+        self.replace(range, text);
+    }
+
+    /// Deletes the selection, or the grapheme (or word) before the caret if nothing is
+    /// selected.
+    pub fn delete_backward(&mut self, by_word: bool) {
+        if !self.selection.is_collapsed() {
+            return self.replace_selection("");
+        }
+
+        let end = self.selection.extent;
+
+        let start = if by_word {
+            self.text.prev_word_offset(end).unwrap_or(0)
+        } else {
+            self.text.prev_grapheme_offset(end).unwrap_or(end)
+        };
+
+        self.replace(start..end, "");
+    }
+
+    /// Deletes the selection, or the grapheme (or word) after the caret if nothing is
+    /// selected.
+    pub fn delete_forward(&mut self, by_word: bool) {
+        if !self.selection.is_collapsed() {
+            return self.replace_selection("");
+        }
+
+        let start = self.selection.extent;
+
+        let end = if by_word {
+            self.text.next_word_offset(start).unwrap_or(self.text.len())
+        } else {
+            self.text.next_grapheme_offset(start).unwrap_or(start)
+        };
+
+        self.replace(start..end, "");
+    }
+
+    /// Replaces the text being composed with `text`, or inserts it in place of the
+    /// selection if nothing is being composed. An empty `text` removes the composition.
+    ///
+    /// The `cursor` is a byte range within `text` to select, as given by the input method.
+    pub fn set_preedit(&mut self, text: &str, cursor: Option<Range<usize>>) {
+        let range = self
+            .composing
+            .clone()
+            .unwrap_or_else(|| self.selection.range());
+
+        let start = range.start;
+
+        self.replace(range, text);
+
+        if text.is_empty() {
+            return;
+        }
+
+        self.composing = Some(start..start + text.len());
+
+        if let Some(cursor) = cursor.filter(|cursor| cursor.end <= text.len()) {
+            self.selection = TextSelection::new(start + cursor.start, start + cursor.end);
+        }
+    }
+
+    /// Replaces the text being composed with the finished `text`, or inserts it in place of
+    /// the selection if nothing is being composed.
+    pub fn commit(&mut self, text: &str) {
+        let range = self
+            .composing
+            .clone()
+            .unwrap_or_else(|| self.selection.range());
+
+        self.replace(range, text);
+    }
+
+    fn replace(&mut self, range: Range<usize>, text: &str) {
+        let caret = range.start + text.len();
+
+        if range.is_empty() {
+            if !text.is_empty() {
+                self.text.insert(range.start, text.to_owned());
+            }
+        } else if text.is_empty() {
+            self.text.remove(range);
+        } else {
+            self.text.replace(range, text.to_owned());
+        }
+
+        self.selection = TextSelection::collapsed(caret);
+        self.composing = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use agui_core::unit::TextSelection;
+
+    use super::TextEditingValue;
+
+    fn value(text: &str, selection: TextSelection) -> TextEditingValue<String> {
+        TextEditingValue {
+            text: text.to_owned(),
+            selection,
+            composing: None,
+        }
+    }
+
+    #[test]
+    fn replaces_the_selection() {
+        let mut value = value("hello world", TextSelection::new(6, 11));
+
+        value.replace_selection("there");
+
+        assert_eq!(value.text, "hello there");
+        assert_eq!(value.selection, TextSelection::collapsed(11));
+    }
+
+    #[test]
+    fn deletes_graphemes_and_words() {
+        let mut value = value("hello wörld", TextSelection::collapsed(12));
+
+        value.delete_backward(false);
+
+        assert_eq!(value.text, "hello wörl");
+
+        value.delete_backward(true);
+
+        assert_eq!(value.text, "hello ");
+        assert_eq!(value.selection, TextSelection::collapsed(6));
+
+        value.selection = TextSelection::collapsed(0);
+        value.delete_forward(true);
+
+        assert_eq!(value.text, " ");
+    }
+
+    #[test]
+    fn preedit_is_replaced_by_its_commit() {
+        let mut value = value("ab", TextSelection::collapsed(1));
+
+        value.set_preedit("ni", Some(2..2));
+
+        assert_eq!(value.text, "anib");
+        assert_eq!(value.composing, Some(1..3));
+        assert_eq!(value.selection, TextSelection::collapsed(3));
+
+        value.set_preedit("nih", Some(3..3));
+
+        assert_eq!(value.text, "anihb");
+        assert_eq!(value.composing, Some(1..4));
+
+        value.commit("你");
+
+        assert_eq!(value.text, "a你b");
+        assert_eq!(value.composing, None);
+        assert_eq!(value.selection, TextSelection::collapsed(4));
+    }
+
+    #[test]
+    fn cancelled_preedit_is_removed() {
+        let mut value = value("ab", TextSelection::collapsed(1));
+
+        value.set_preedit("ni", None);
+        value.set_preedit("", None);
+
+        assert_eq!(value.text, "ab");
+        assert_eq!(value.composing, None);
+        assert_eq!(value.selection, TextSelection::collapsed(1));
+    }
+}
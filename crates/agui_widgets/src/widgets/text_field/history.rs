@@ -0,0 +1,168 @@
+use super::TextEditingValue;
+
+/// The most edits that can be undone.
+const MAX_UNDO_STEPS: usize = 100;
+
+/// What kind of change an edit made, so that runs of similar edits can be undone together.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum EditKind {
+    /// Text was typed. Typing is undone a word at a time.
+    Typing,
+
+    /// Text was deleted with backspace or delete.
+    Deleting,
+
+    /// Anything else, such as a paste or a cut, which is always undone on its own.
+    Other,
+}
+
+/// The values a text field had before each of its recent edits.
+pub(crate) struct UndoHistory<S> {
+    undo: Vec<TextEditingValue<S>>,
+    redo: Vec<TextEditingValue<S>>,
+
+    /// The kind of the last edit, if the next edit of the same kind should be merged into
+    /// it.
+    coalescing: Option<EditKind>,
+}
+
+impl<S> Default for UndoHistory<S> {
+    fn default() -> Self {
+        Self {
+            undo: Vec::new(),
+            redo: Vec::new(),
+
+            coalescing: None,
+        }
+    }
+}
+
+impl<S> UndoHistory<S>
+where
+    S: Clone,
+{
+    /// Records that an edit of `kind` was made to `before`.
+    ///
+    /// If the previous edit was of the same kind, and nothing has happened in between to
+    /// break it up, the two are undone together. Typing stops being merged once a
+    /// word has been finished with `ends_word`.
+    pub fn record(&mut self, before: TextEditingValue<S>, kind: EditKind, ends_word: bool) {
+        self.redo.clear();
+
+        if kind == EditKind::Other || self.coalescing != Some(kind) {
+            self.undo.push(before);
+
+            if self.undo.len() > MAX_UNDO_STEPS {
+                self.undo.remove(0);
+            }
+        }
+
+        self.coalescing = (kind != EditKind::Other && !ends_word).then_some(kind);
+    }
+
+    /// Stops the next edit from being merged into the last one, such as when the caret is
+    /// moved.
+    pub fn break_coalescing(&mut self) {
+        self.coalescing = None;
+    }
+
+    /// Forgets every recorded edit.
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+
+        self.coalescing = None;
+    }
+
+    /// Returns the value before the last edit to `current`, if there is one.
+    pub fn undo(&mut self, current: TextEditingValue<S>) -> Option<TextEditingValue<S>> {
+        let value = self.undo.pop()?;
+
+        self.redo.push(current);
+        self.coalescing = None;
+
+        Some(value)
+    }
+
+    /// Returns the value the last undo reverted, if nothing has been edited since.
+    pub fn redo(&mut self, current: TextEditingValue<S>) -> Option<TextEditingValue<S>> {
+        let value = self.redo.pop()?;
+
+        self.undo.push(current);
+        self.coalescing = None;
+
+        Some(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::TextEditingValue;
+
+    use super::{EditKind, UndoHistory};
+
+    fn value(text: &str) -> TextEditingValue<String> {
+        TextEditingValue::new(text.to_owned())
+    }
+
+    #[test]
+    fn typing_is_undone_a_word_at_a_time() {
+        let mut history = UndoHistory::default();
+
+        history.record(value(""), EditKind::Typing, false);
+        history.record(value("h"), EditKind::Typing, false);
+        history.record(value("hi"), EditKind::Typing, true);
+        history.record(value("hi "), EditKind::Typing, false);
+
+        assert_eq!(history.undo(value("hi y")), Some(value("hi ")));
+        assert_eq!(history.undo(value("hi ")), Some(value("")));
+        assert_eq!(history.undo(value("")), None);
+
+        assert_eq!(history.redo(value("")), Some(value("hi ")));
+        assert_eq!(history.redo(value("hi ")), Some(value("hi y")));
+        assert_eq!(history.redo(value("hi y")), None);
+    }
+
+    #[test]
+    fn different_edits_are_undone_separately() {
+        let mut history = UndoHistory::default();
+
+        history.record(value(""), EditKind::Typing, false);
+        history.record(value("ab"), EditKind::Deleting, false);
+        history.record(value("a"), EditKind::Other, false);
+        history.record(value("a pasted"), EditKind::Other, false);
+
+        assert_eq!(
+            history.undo(value("a pasted twice")),
+            Some(value("a pasted"))
+        );
+        assert_eq!(history.undo(value("a pasted")), Some(value("a")));
+        assert_eq!(history.undo(value("a")), Some(value("ab")));
+        assert_eq!(history.undo(value("ab")), Some(value("")));
+    }
+
+    #[test]
+    fn moving_the_caret_breaks_up_typing() {
+        let mut history = UndoHistory::default();
+
+        history.record(value(""), EditKind::Typing, false);
+        history.break_coalescing();
+        history.record(value("a"), EditKind::Typing, false);
+
+        assert_eq!(history.undo(value("ab")), Some(value("a")));
+        assert_eq!(history.undo(value("a")), Some(value("")));
+    }
+
+    #[test]
+    fn editing_clears_redo() {
+        let mut history = UndoHistory::default();
+
+        history.record(value(""), EditKind::Other, false);
+
+        assert_eq!(history.undo(value("a")), Some(value("")));
+
+        history.record(value(""), EditKind::Other, false);
+
+        assert_eq!(history.redo(value("b")), None);
+    }
+}
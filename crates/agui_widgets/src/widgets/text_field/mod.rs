@@ -0,0 +1,248 @@
+use std::{borrow::Cow, fmt::Debug};
+
+use agui_core::{
+    callback::Callback,
+    clipboard::Clipboard,
+    unit::{Color, Offset, TextStyle},
+    widget::{IntoWidget, Widget},
+};
+use agui_elements::stateful::{
+    ContextWidgetStateMut, StatefulBuildContext, StatefulWidget, WidgetState,
+};
+use agui_macros::StatefulWidget;
+use agui_primitives::{
+    directionality::Directionality,
+    text::{edit::EditableText, FontLoader, DEFAULT_SELECTION_COLOR},
+};
+
+mod editing;
+mod history;
+mod render_editable;
+
+pub use editing::*;
+
+use self::render_editable::RawEditable;
+
+/// How opaque the placeholder is compared to the text, unless it's given its own style.
+const PLACEHOLDER_OPACITY: f32 = 0.5;
+
+/// An editable field of text.
+///
+/// The field takes focus when it's clicked, and shows a caret where text will be entered.
+/// While focused it supports:
+///
+/// - moving the caret by character with the arrow keys, by word with `Ctrl`, and to the
+///   ends of the line with `Home` and `End` (or of the text with `Ctrl`), extending the
+///   selection while `Shift` is held;
+/// - selecting by dragging, selecting a word by double clicking, and selecting everything
+///   with `Ctrl+A`;
+/// - cutting, copying and pasting with `Ctrl+X`, `Ctrl+C` and `Ctrl+V`;
+/// - undoing and redoing with `Ctrl+Z` and `Ctrl+Shift+Z` (or `Ctrl+Y`), where typing is
+///   undone a word at a time;
+/// - composing text with an input method, which is underlined until it's committed.
+///
+/// A single line field scrolls horizontally to keep the caret in view, and calls
+/// `on_submit` when `Enter` is pressed. A `multiline` field inserts line breaks instead,
+/// grows to fit up to `max_lines` lines, and scrolls vertically beyond that.
+///
+/// The field keeps its own copy of the text, which is replaced whenever it's given a
+/// different `value`. Every edit is reported through `on_change`.
+#[derive(StatefulWidget, Debug)]
+pub struct TextField<S>
+where
+    S: EditableText + Debug + 'static,
+{
+    pub value: S,
+
+    #[prop(default)]
+    pub style: TextStyle,
+
+    /// Shown in place of the text while it's empty.
+    #[prop(into, default)]
+    pub placeholder: Cow<'static, str>,
+
+    /// The style of the placeholder. If `None`, it's the field's style at half opacity.
+    #[prop(default)]
+    pub placeholder_style: Option<TextStyle>,
+
+    /// Whether each character is shown as a bullet, such as for passwords. Obscured text
+    /// can't be cut or copied, and is navigated as if it were a single word.
+    #[prop(default)]
+    pub obscured: bool,
+
+    #[prop(default)]
+    pub multiline: bool,
+
+    /// The most lines a multiline field grows to fit before it scrolls. If `None`, it grows
+    /// to fit all of its text.
+    #[prop(default)]
+    pub max_lines: Option<usize>,
+
+    /// The color of the caret. If `None`, it's the color of the text.
+    #[prop(default)]
+    pub cursor_color: Option<Color>,
+
+    #[prop(default = DEFAULT_SELECTION_COLOR)]
+    pub selection_color: Color,
+
+    /// The clipboard that text is cut, copied and pasted through. If `None`, the global
+    /// clipboard is used.
+    #[prop(default)]
+    pub clipboard: Option<Clipboard>,
+
+    #[prop(default)]
+    pub on_change: Option<Callback<S>>,
+
+    #[prop(default)]
+    pub on_submit: Option<Callback<S>>,
+
+    #[prop(default)]
+    pub on_focus_change: Option<Callback<bool>>,
+}
+
+impl<S> StatefulWidget for TextField<S>
+where
+    S: EditableText + Debug + 'static,
+{
+    type State = TextFieldState<S>;
+
+    fn create_state(&self) -> Self::State {
+        TextFieldState {
+            value: TextEditingValue::new(self.value.clone()),
+
+            focused: false,
+
+            scroll_offset: Offset::ZERO,
+        }
+    }
+}
+
+pub struct TextFieldState<S> {
+    value: TextEditingValue<S>,
+
+    focused: bool,
+
+    scroll_offset: Offset,
+}
+
+impl<S> WidgetState for TextFieldState<S>
+where
+    S: EditableText + Debug + 'static,
+{
+    type Widget = TextField<S>;
+
+    fn updated(&mut self, ctx: &mut StatefulBuildContext<Self>, old_widget: &Self::Widget) {
+        // The field reports its edits, so a new value that matches them isn't a change
+        if ctx.widget.value != old_widget.value && ctx.widget.value != self.value.text {
+            self.value = self.value.clone().with_text(ctx.widget.value.clone());
+        }
+    }
+
+    fn build(&mut self, ctx: &mut StatefulBuildContext<Self>) -> Widget {
+        let mut style = ctx.widget.style.clone();
+
+        if style.text_direction.is_none() {
+            style.text_direction = Directionality::of(ctx);
+        }
+
+        let placeholder_style = ctx.widget.placeholder_style.clone().unwrap_or_else(|| {
+            let mut placeholder_style = style.clone();
+
+            placeholder_style.color.alpha *= PLACEHOLDER_OPACITY;
+
+            placeholder_style
+        });
+
+        let on_edit = ctx.callback(|ctx, value: TextEditingValue<S>| {
+            let text = (ctx.state.value.text != value.text).then(|| value.text.clone());
+
+            ctx.set_state(move |state| {
+                state.value = value;
+            });
+
+            if let Some((on_change, text)) = ctx.widget.on_change.as_ref().zip(text) {
+                on_change.call(text);
+            }
+        });
+
+        let on_submit = ctx.callback(|ctx, ()| {
+            if let Some(on_submit) = &ctx.widget.on_submit {
+                on_submit.call(ctx.state.value.text.clone());
+            }
+        });
+
+        let on_focus_change = ctx.callback(|ctx, focused: bool| {
+            ctx.set_state(move |state| {
+                state.focused = focused;
+            });
+
+            if let Some(on_focus_change) = &ctx.widget.on_focus_change {
+                on_focus_change.call(focused);
+            }
+        });
+
+        let on_scroll = ctx.callback(|ctx, offset: Offset| {
+            ctx.set_state(move |state| {
+                state.scroll_offset = offset;
+            });
+        });
+
+        let cursor_color = ctx.widget.cursor_color.unwrap_or(style.color);
+
+        let placeholder = Cow::clone(&ctx.widget.placeholder);
+
+        let value = self.value.clone();
+
+        let obscured = ctx.widget.obscured;
+
+        let multiline = ctx.widget.multiline;
+        let max_lines = ctx.widget.max_lines;
+
+        let focused = self.focused;
+
+        let selection_color = ctx.widget.selection_color;
+
+        let scroll_offset = self.scroll_offset;
+
+        let clipboard = ctx
+            .widget
+            .clipboard
+            .clone()
+            .unwrap_or_else(Clipboard::global);
+
+        let fonts = vec![style.font.clone(), placeholder_style.font.clone()];
+
+        FontLoader::new(fonts, move |fonts| {
+            RawEditable {
+                cursor_color,
+
+                style: fonts.style(&style),
+
+                placeholder: Cow::clone(&placeholder),
+                placeholder_style: fonts.style(&placeholder_style),
+
+                value: value.clone(),
+
+                obscured,
+
+                multiline,
+                max_lines,
+
+                focused,
+
+                selection_color,
+
+                scroll_offset,
+
+                clipboard: clipboard.clone(),
+
+                on_edit: on_edit.clone(),
+                on_submit: on_submit.clone(),
+                on_focus_change: on_focus_change.clone(),
+                on_scroll: on_scroll.clone(),
+            }
+            .into_widget()
+        })
+        .into_widget()
+    }
+}
@@ -0,0 +1,1178 @@
+use std::{borrow::Cow, ops::Range, time::Duration};
+
+use agui_core::{
+    callback::Callback,
+    clipboard::Clipboard,
+    element::{ContextDirtyRenderObject, RenderObjectCreateContext, RenderObjectUpdateContext},
+    input::{
+        EventResult, ImeEvent, InputEvent, Key, KeyEvent, Modifiers, NamedKey, PointerButton,
+        PointerEvent, PointerEventKind, PointerId,
+    },
+    render::{
+        canvas::{
+            paint::Paint,
+            painter::{CanvasPainter, Head},
+        },
+        object::{
+            RenderObjectBaselineContext, RenderObjectEventContext, RenderObjectHitTestContext,
+            RenderObjectImpl, RenderObjectIntrinsicSizeContext, RenderObjectLayoutContext,
+        },
+    },
+    unit::{
+        Color, Constraints, HitTest, IntrinsicDimension, Offset, Rect, Shape, Size, TextBaseline,
+        TextDecoration, TextPosition, TextSelection, TextStyle,
+    },
+    widget::Widget,
+};
+use agui_elements::render::RenderObjectWidget;
+use agui_macros::RenderObjectWidget;
+use agui_primitives::text::{edit::EditableText, RenderParagraph, StyledRange};
+use parking_lot::Mutex;
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::{
+    history::{EditKind, UndoHistory},
+    TextEditingValue,
+};
+
+/// The character shown in place of each grapheme of obscured text.
+const OBSCURING_CHARACTER: char = '•';
+
+const CARET_WIDTH: f32 = 2.0;
+
+/// The longest time between two clicks for them to count as a double click.
+const DOUBLE_CLICK_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// The furthest apart two clicks can be for them to count as a double click.
+const DOUBLE_CLICK_SLOP: f32 = 8.0;
+
+/// Displays a [`TextEditingValue`] and edits it in response to input.
+///
+/// The value is owned by the [`TextField`](super::TextField) that builds this, and every
+/// edit is reported back to it through `on_edit`.
+#[derive(RenderObjectWidget, Debug)]
+pub(crate) struct RawEditable<S>
+where
+    S: EditableText + std::fmt::Debug + 'static,
+{
+    pub style: TextStyle,
+
+    pub placeholder: Cow<'static, str>,
+    pub placeholder_style: TextStyle,
+
+    pub value: TextEditingValue<S>,
+
+    pub obscured: bool,
+
+    pub multiline: bool,
+    pub max_lines: Option<usize>,
+
+    pub focused: bool,
+
+    pub cursor_color: Color,
+    pub selection_color: Color,
+
+    pub scroll_offset: Offset,
+
+    pub clipboard: Clipboard,
+
+    pub on_edit: Callback<TextEditingValue<S>>,
+    pub on_submit: Callback<()>,
+    pub on_focus_change: Callback<bool>,
+    pub on_scroll: Callback<Offset>,
+}
+
+impl<S> RenderObjectWidget for RawEditable<S>
+where
+    S: EditableText + std::fmt::Debug + 'static,
+{
+    type RenderObject = RenderEditable<S>;
+
+    fn children(&self) -> Vec<Widget> {
+        Vec::new()
+    }
+
+    fn create_render_object(&self, _: &mut RenderObjectCreateContext) -> Self::RenderObject {
+        let mut render_object = RenderEditable {
+            paragraph: RenderParagraph::new(self.style.clone(), Cow::Borrowed("")),
+
+            style: self.style.clone(),
+
+            placeholder: Cow::clone(&self.placeholder),
+            placeholder_style: self.placeholder_style.clone(),
+
+            obscured: self.obscured,
+
+            multiline: self.multiline,
+            max_lines: self.max_lines,
+
+            focused: self.focused,
+
+            cursor_color: self.cursor_color,
+
+            clipboard: self.clipboard.clone(),
+
+            on_edit: self.on_edit.clone(),
+            on_submit: self.on_submit.clone(),
+            on_focus_change: self.on_focus_change.clone(),
+            on_scroll: self.on_scroll.clone(),
+
+            editor: Mutex::new(Editor::new(self.value.clone())),
+
+            scroll: Mutex::new(Scroll {
+                offset: self.scroll_offset,
+                requested: self.scroll_offset,
+
+                reveal_caret: true,
+
+                ..Scroll::default()
+            }),
+        };
+
+        render_object.paragraph.selection_color = self.selection_color;
+
+        let (text, styles, selection) = render_object.display(&self.value);
+
+        render_object.paragraph.style = if render_object.is_showing_placeholder(&self.value) {
+            self.placeholder_style.clone()
+        } else {
+            self.style.clone()
+        };
+
+        render_object.paragraph.text = text;
+        render_object.paragraph.styles = styles;
+        render_object.paragraph.selection = selection;
+
+        render_object
+    }
+
+    fn update_render_object(
+        &self,
+        ctx: &mut RenderObjectUpdateContext,
+        render_object: &mut Self::RenderObject,
+    ) {
+        render_object.update_style(ctx, self.style.clone());
+
+        render_object.update_placeholder(
+            ctx,
+            Cow::clone(&self.placeholder),
+            self.placeholder_style.clone(),
+        );
+
+        render_object.update_obscured(ctx, self.obscured);
+
+        render_object.update_lines(ctx, self.multiline, self.max_lines);
+
+        render_object.update_focused(ctx, self.focused);
+
+        render_object.update_cursor_color(ctx, self.cursor_color);
+
+        render_object
+            .paragraph
+            .update_selection_color(ctx, self.selection_color);
+
+        render_object.update_scroll_offset(ctx, self.scroll_offset);
+
+        render_object.clipboard = self.clipboard.clone();
+
+        render_object.on_edit = self.on_edit.clone();
+        render_object.on_submit = self.on_submit.clone();
+        render_object.on_focus_change = self.on_focus_change.clone();
+        render_object.on_scroll = self.on_scroll.clone();
+
+        render_object.update_value(ctx, self.value.clone());
+    }
+}
+
+pub(crate) struct RenderEditable<S>
+where
+    S: EditableText,
+{
+    /// The paragraph that displays the text. Its text, styles and selection are derived
+    /// from the editor's value whenever it changes.
+    paragraph: RenderParagraph,
+
+    style: TextStyle,
+
+    placeholder: Cow<'static, str>,
+    placeholder_style: TextStyle,
+
+    obscured: bool,
+
+    multiline: bool,
+    max_lines: Option<usize>,
+
+    focused: bool,
+
+    cursor_color: Color,
+
+    clipboard: Clipboard,
+
+    on_edit: Callback<TextEditingValue<S>>,
+    on_submit: Callback<()>,
+    on_focus_change: Callback<bool>,
+    on_scroll: Callback<Offset>,
+
+    editor: Mutex<Editor<S>>,
+
+    scroll: Mutex<Scroll>,
+}
+
+/// The state of the text being edited.
+///
+/// The value here is ahead of the widget's whenever edits have been made that it hasn't
+/// been rebuilt with yet, so that input arriving in the meantime builds on those edits.
+struct Editor<S> {
+    value: TextEditingValue<S>,
+
+    /// The value the widget was last built with.
+    synced: TextEditingValue<S>,
+
+    /// The values reported through `on_edit` that the widget hasn't been built with yet.
+    pending: Vec<TextEditingValue<S>>,
+
+    history: UndoHistory<S>,
+
+    /// Where the caret was horizontally before it started moving between lines, so that it
+    /// returns there after passing through shorter lines.
+    preferred_x: Option<f32>,
+
+    /// The pointer dragging out the selection, if any.
+    dragging: Option<PointerId>,
+
+    /// The word that was double clicked, if the selection is being made a word at a time.
+    anchor_word: Option<Range<usize>>,
+
+    /// When and where the primary button last went down, to detect double clicks.
+    last_click: Option<(Duration, Offset)>,
+}
+
+impl<S> Editor<S>
+where
+    S: Clone,
+{
+    fn new(value: TextEditingValue<S>) -> Self {
+        Self {
+            synced: value.clone(),
+            value,
+
+            pending: Vec::new(),
+
+            history: UndoHistory::default(),
+
+            preferred_x: None,
+
+            dragging: None,
+            anchor_word: None,
+            last_click: None,
+        }
+    }
+}
+
+#[derive(Default)]
+struct Scroll {
+    /// How far the text is scrolled within the field.
+    offset: Offset,
+
+    /// The offset the widget last asked to scroll to.
+    requested: Offset,
+
+    /// Whether the next layout should scroll the caret into view.
+    reveal_caret: bool,
+
+    /// The offset the text was last painted at.
+    painted: Offset,
+
+    /// The size of the laid out text, and of the field it's scrolled within.
+    content_size: Size,
+    viewport_size: Size,
+}
+
+impl Scroll {
+    fn max_offset(&self) -> Offset {
+        Offset::new(
+            (self.content_size.width - self.viewport_size.width).max(0.0),
+            (self.content_size.height - self.viewport_size.height).max(0.0),
+        )
+    }
+
+    fn clamp(&self, offset: Offset) -> Offset {
+        let max = self.max_offset();
+
+        Offset::new(offset.x.clamp(0.0, max.x), offset.y.clamp(0.0, max.y))
+    }
+}
+
+impl<S> RenderEditable<S>
+where
+    S: EditableText + 'static,
+{
+    fn update_style(&mut self, ctx: &mut RenderObjectUpdateContext, style: TextStyle) {
+        if self.style == style {
+            return;
+        }
+
+        self.style = style;
+        self.sync_paragraph(ctx);
+    }
+
+    fn update_placeholder(
+        &mut self,
+        ctx: &mut RenderObjectUpdateContext,
+        placeholder: Cow<'static, str>,
+        placeholder_style: TextStyle,
+    ) {
+        if self.placeholder == placeholder && self.placeholder_style == placeholder_style {
+            return;
+        }
+
+        self.placeholder = placeholder;
+        self.placeholder_style = placeholder_style;
+        self.sync_paragraph(ctx);
+    }
+
+    fn update_obscured(&mut self, ctx: &mut RenderObjectUpdateContext, obscured: bool) {
+        if self.obscured == obscured {
+            return;
+        }
+
+        self.obscured = obscured;
+        self.sync_paragraph(ctx);
+    }
+
+    fn update_lines(
+        &mut self,
+        ctx: &mut RenderObjectUpdateContext,
+        multiline: bool,
+        max_lines: Option<usize>,
+    ) {
+        if self.multiline == multiline && self.max_lines == max_lines {
+            return;
+        }
+
+        self.multiline = multiline;
+        self.max_lines = max_lines;
+        ctx.mark_needs_layout();
+        ctx.mark_needs_paint();
+    }
+
+    fn update_focused(&mut self, ctx: &mut RenderObjectUpdateContext, focused: bool) {
+        if self.focused == focused {
+            return;
+        }
+
+        self.focused = focused;
+        ctx.mark_needs_paint();
+    }
+
+    fn update_cursor_color(&mut self, ctx: &mut RenderObjectUpdateContext, color: Color) {
+        if self.cursor_color == color {
+            return;
+        }
+
+        self.cursor_color = color;
+        ctx.mark_needs_paint();
+    }
+
+    fn update_scroll_offset(&mut self, ctx: &mut RenderObjectUpdateContext, offset: Offset) {
+        let scroll = self.scroll.get_mut();
+
+        if scroll.requested != offset {
+            scroll.requested = offset;
+            scroll.offset = offset;
+            ctx.mark_needs_layout();
+            ctx.mark_needs_paint();
+        } else if scroll.offset != scroll.painted {
+            // The field was scrolled by a pointer, and has been rebuilt with the new offset
+            ctx.mark_needs_paint();
+        }
+    }
+
+    fn update_value(&mut self, ctx: &mut RenderObjectUpdateContext, value: TextEditingValue<S>) {
+        let editor = self.editor.get_mut();
+
+        if editor.synced == value {
+            return;
+        }
+
+        editor.synced = value.clone();
+
+        // Values that this render object reported itself are already reflected in the
+        // editor, which may have moved on since
+        if let Some(idx) = editor.pending.iter().position(|pending| *pending == value) {
+            editor.pending.drain(..=idx);
+        } else {
+            editor.pending.clear();
+            editor.history.clear();
+
+            editor.value = value;
+        }
+
+        self.sync_paragraph(ctx);
+    }
+
+    /// Updates the paragraph to display the editor's current value.
+    fn sync_paragraph(&mut self, ctx: &mut RenderObjectUpdateContext) {
+        let value = self.editor.get_mut().value.clone();
+
+        let (text, styles, selection) = self.display(&value);
+
+        let style = if self.is_showing_placeholder(&value) {
+            self.placeholder_style.clone()
+        } else {
+            self.style.clone()
+        };
+
+        if self.paragraph.text != text || self.paragraph.selection != selection {
+            self.scroll.get_mut().reveal_caret = true;
+
+            ctx.mark_needs_layout();
+        }
+
+        self.paragraph.update_style(ctx, style);
+        self.paragraph.update_text(ctx, text);
+        self.paragraph.update_styles(ctx, styles);
+        self.paragraph.update_selection(ctx, selection);
+
+        ctx.mark_needs_paint();
+    }
+
+    fn is_showing_placeholder(&self, value: &TextEditingValue<S>) -> bool {
+        value.text.is_empty() && !self.placeholder.is_empty()
+    }
+
+    /// Returns the text that's displayed for `value`, along with the styles and selection
+    /// that go with it.
+    fn display(
+        &self,
+        value: &TextEditingValue<S>,
+    ) -> (Cow<'static, str>, Vec<StyledRange>, Option<TextSelection>) {
+        if self.is_showing_placeholder(value) {
+            return (
+                Cow::clone(&self.placeholder),
+                Vec::new(),
+                Some(TextSelection::collapsed(0)),
+            );
+        }
+
+        let text = value.text.as_str();
+
+        let selection = Some(TextSelection::new(
+            self.to_display(text, value.selection.base),
+            self.to_display(text, value.selection.extent),
+        ));
+
+        if self.obscured {
+            let obscured = OBSCURING_CHARACTER
+                .to_string()
+                .repeat(text.graphemes(true).count());
+
+            return (Cow::Owned(obscured), Vec::new(), selection);
+        }
+
+        // Text that's being composed is underlined
+        let styles = Vec::from_iter(value.composing.clone().map(|range| StyledRange {
+            range,
+
+            style: self.style.clone().decoration(TextDecoration {
+                underline: true,
+
+                ..self.style.decoration
+            }),
+
+            background: None,
+
+            on_tap: None,
+        }));
+
+        (Cow::Owned(text.to_owned()), styles, selection)
+    }
+
+    /// Converts a byte offset in the value's text to one in the displayed text.
+    fn to_display(&self, text: &str, offset: usize) -> usize {
+        if text.is_empty() && !self.placeholder.is_empty() {
+            0
+        } else if self.obscured {
+            text[..offset].graphemes(true).count() * OBSCURING_CHARACTER.len_utf8()
+        } else {
+            offset
+        }
+    }
+
+    /// Converts a byte offset in the displayed text to one in the value's text.
+    fn to_value(&self, text: &str, offset: usize) -> usize {
+        if self.obscured {
+            text.grapheme_indices(true)
+                .nth(offset / OBSCURING_CHARACTER.len_utf8())
+                .map_or(text.len(), |(idx, _)| idx)
+        } else {
+            let mut offset = offset.min(text.len());
+
+            while !text.is_char_boundary(offset) {
+                offset -= 1;
+            }
+
+            offset
+        }
+    }
+
+    /// Returns the offset in the value's text closest to a position within the field.
+    fn offset_at(&self, text: &str, position: Offset) -> usize {
+        let scroll = self.scroll.lock().offset;
+
+        let position = self.paragraph.position_for_offset(position + scroll).offset;
+
+        self.to_value(text, position)
+    }
+
+    /// Returns the range of the word at `offset`. Obscured text is treated as a single
+    /// word, so that its structure isn't revealed.
+    fn word_at(&self, text: &str, offset: usize) -> Range<usize> {
+        if self.obscured {
+            return 0..text.len();
+        }
+
+        self.paragraph.word_boundary(offset)
+    }
+
+    /// Reports the editor's value to the widget, if it was changed.
+    fn commit_edit(&self, editor: &mut Editor<S>, before: &TextEditingValue<S>) {
+        if editor.value == *before {
+            return;
+        }
+
+        editor.pending.push(editor.value.clone());
+
+        self.on_edit.call(editor.value.clone());
+    }
+
+    /// Applies an edit to the text, recording it so that it can be undone.
+    fn edit(
+        &self,
+        editor: &mut Editor<S>,
+        kind: EditKind,
+        func: impl FnOnce(&mut TextEditingValue<S>),
+    ) {
+        let before = editor.value.clone();
+
+        func(&mut editor.value);
+
+        if editor.value.text != before.text {
+            let ends_word = editor
+                .value
+                .text
+                .as_str()
+                .get(..editor.value.selection.extent)
+                .and_then(|text| text.chars().next_back())
+                .is_some_and(char::is_whitespace);
+
+            editor.history.record(before.clone(), kind, ends_word);
+        }
+
+        editor.preferred_x = None;
+
+        self.commit_edit(editor, &before);
+    }
+
+    /// Changes the selection without editing the text.
+    fn select(&self, editor: &mut Editor<S>, selection: TextSelection) {
+        let before = editor.value.clone();
+
+        editor.value.selection = selection;
+
+        // Moving the caret finishes any composition in progress
+        editor.value.composing = None;
+
+        editor.history.break_coalescing();
+
+        self.commit_edit(editor, &before);
+    }
+
+    /// Removes line breaks from text entered into a single line field, and any other
+    /// control characters from text entered into any field.
+    fn filter_input(&self, text: &str) -> String {
+        text.chars()
+            .map(|ch| match ch {
+                '\r' | '\n' if !self.multiline => ' ',
+                '\r' => '\n',
+                ch => ch,
+            })
+            .filter(|ch| *ch == '\n' || !ch.is_control())
+            .collect()
+    }
+
+    fn move_horizontally(
+        &self,
+        editor: &mut Editor<S>,
+        forward: bool,
+        by_word: bool,
+        extend: bool,
+    ) {
+        let value = &editor.value;
+        let selection = value.selection;
+
+        // Without extending, a selection collapses to whichever end the caret moved towards
+        if !extend && !selection.is_collapsed() {
+            let offset = if forward {
+                selection.end()
+            } else {
+                selection.start()
+            };
+
+            return self.select(editor, TextSelection::collapsed(offset));
+        }
+
+        let extent = selection.extent;
+        let text = &value.text;
+
+        let offset = match (forward, by_word && !self.obscured) {
+            (true, true) => text.next_word_offset(extent).unwrap_or(text.len()),
+            (false, true) => text.prev_word_offset(extent).unwrap_or(0),
+            (true, false) if by_word => text.len(),
+            (false, false) if by_word => 0,
+            (true, false) => text.next_grapheme_offset(extent).unwrap_or(extent),
+            (false, false) => text.prev_grapheme_offset(extent).unwrap_or(extent),
+        };
+
+        self.move_extent(editor, offset, extend);
+    }
+
+    fn move_vertically(&self, editor: &mut Editor<S>, down: bool, extend: bool) {
+        let text = editor.value.text.as_str().to_owned();
+
+        let caret = self.paragraph.caret_rect(TextPosition::new(
+            self.to_display(&text, editor.value.selection.extent),
+        ));
+
+        let x = *editor.preferred_x.get_or_insert(caret.left);
+
+        let y = if down {
+            caret.bottom() + caret.height / 2.0
+        } else {
+            caret.top - caret.height / 2.0
+        };
+
+        let content_height = self
+            .paragraph
+            .line_metrics()
+            .last()
+            .map_or(0.0, |line| line.top + line.height);
+
+        let offset = if y < 0.0 {
+            0
+        } else if y >= content_height {
+            text.len()
+        } else {
+            let position = self.paragraph.position_for_offset(Offset::new(x, y));
+
+            self.to_value(&text, position.offset)
+        };
+
+        let preferred_x = editor.preferred_x;
+
+        self.move_extent(editor, offset, extend);
+
+        editor.preferred_x = preferred_x;
+    }
+
+    fn move_to_line_edge(&self, editor: &mut Editor<S>, end: bool, extend: bool) {
+        let text = editor.value.text.as_str().to_owned();
+
+        let line = self.paragraph.line_boundary(TextPosition::new(
+            self.to_display(&text, editor.value.selection.extent),
+        ));
+
+        let offset = if end { line.end } else { line.start };
+
+        self.move_extent(editor, self.to_value(&text, offset), extend);
+    }
+
+    fn move_extent(&self, editor: &mut Editor<S>, offset: usize, extend: bool) {
+        let selection = if extend {
+            TextSelection::new(editor.value.selection.base, offset)
+        } else {
+            TextSelection::collapsed(offset)
+        };
+
+        editor.preferred_x = None;
+
+        self.select(editor, selection);
+    }
+
+    fn undo(&self, editor: &mut Editor<S>) {
+        let before = editor.value.clone();
+
+        if let Some(value) = editor.history.undo(before.clone()) {
+            editor.value = value;
+
+            self.commit_edit(editor, &before);
+        }
+    }
+
+    fn redo(&self, editor: &mut Editor<S>) {
+        let before = editor.value.clone();
+
+        if let Some(value) = editor.history.redo(before.clone()) {
+            editor.value = value;
+
+            self.commit_edit(editor, &before);
+        }
+    }
+
+    fn handle_key(&self, event: &KeyEvent) -> EventResult {
+        if !event.is_pressed() {
+            return EventResult::Ignored;
+        }
+
+        let shortcut = event
+            .modifiers
+            .intersects(Modifiers::CONTROL | Modifiers::SUPER);
+        let shift = event.modifiers.contains(Modifiers::SHIFT);
+
+        let mut editor = self.editor.lock();
+        let editor = &mut *editor;
+
+        match event.key {
+            Key::Named(NamedKey::ArrowLeft) => {
+                self.move_horizontally(editor, false, shortcut, shift)
+            }
+            Key::Named(NamedKey::ArrowRight) => {
+                self.move_horizontally(editor, true, shortcut, shift)
+            }
+
+            Key::Named(NamedKey::ArrowUp) if self.multiline => {
+                self.move_vertically(editor, false, shift)
+            }
+            Key::Named(NamedKey::ArrowDown) if self.multiline => {
+                self.move_vertically(editor, true, shift)
+            }
+
+            Key::Named(NamedKey::Home) if shortcut => self.move_extent(editor, 0, shift),
+            Key::Named(NamedKey::End) if shortcut => {
+                let len = editor.value.text.len();
+
+                self.move_extent(editor, len, shift)
+            }
+
+            Key::Named(NamedKey::Home) => self.move_to_line_edge(editor, false, shift),
+            Key::Named(NamedKey::End) => self.move_to_line_edge(editor, true, shift),
+
+            Key::Named(NamedKey::Backspace) => self.edit(editor, EditKind::Deleting, |value| {
+                value.delete_backward(shortcut && !self.obscured)
+            }),
+
+            Key::Named(NamedKey::Delete) => self.edit(editor, EditKind::Deleting, |value| {
+                value.delete_forward(shortcut && !self.obscured)
+            }),
+
+            Key::Named(NamedKey::Enter) if self.multiline => {
+                self.edit(editor, EditKind::Typing, |value| value.commit("\n"))
+            }
+
+            Key::Named(NamedKey::Enter) => self.on_submit.call(()),
+
+            Key::Character(ch) if shortcut => match ch.to_ascii_lowercase() {
+                'a' => {
+                    let len = editor.value.text.len();
+
+                    self.select(editor, TextSelection::new(0, len))
+                }
+
+                'c' | 'x' if self.obscured => {}
+
+                'c' => self.clipboard.write_text(editor.value.selected_text()),
+
+                'x' => {
+                    if !editor.value.selection.is_collapsed() {
+                        self.clipboard.write_text(editor.value.selected_text());
+
+                        self.edit(editor, EditKind::Other, |value| value.replace_selection(""));
+                    }
+                }
+
+                'v' => {
+                    if let Some(text) = self.clipboard.read_text() {
+                        let text = self.filter_input(&text);
+
+                        self.edit(editor, EditKind::Other, |value| value.commit(&text));
+                    }
+                }
+
+                'z' if shift => self.redo(editor),
+                'z' => self.undo(editor),
+                'y' => self.redo(editor),
+
+                _ => return EventResult::Ignored,
+            },
+
+            // Characters are entered through text events, but the key presses that produce
+            // them shouldn't be handled by anything else
+            Key::Character(_) if !event.modifiers.contains(Modifiers::ALT) => {}
+
+            _ => return EventResult::Ignored,
+        }
+
+        EventResult::Handled
+    }
+
+    fn handle_text(&self, text: &str) -> EventResult {
+        let text = self.filter_input(text);
+
+        if text.is_empty() {
+            return EventResult::Ignored;
+        }
+
+        let mut editor = self.editor.lock();
+
+        self.edit(&mut editor, EditKind::Typing, |value| value.commit(&text));
+
+        EventResult::Handled
+    }
+
+    fn handle_ime(&self, event: &ImeEvent) -> EventResult {
+        let mut editor = self.editor.lock();
+        let editor = &mut *editor;
+
+        match event {
+            ImeEvent::Enabled => return EventResult::Handled,
+
+            // The text being composed isn't recorded, since it's replaced by what's
+            // eventually committed
+            ImeEvent::Preedit { text, cursor } => {
+                let before = editor.value.clone();
+
+                editor
+                    .value
+                    .set_preedit(&self.filter_input(text), cursor.clone());
+
+                self.commit_edit(editor, &before);
+            }
+
+            ImeEvent::Commit(text) => {
+                let text = self.filter_input(text);
+
+                self.edit(editor, EditKind::Other, |value| value.commit(&text));
+            }
+
+            ImeEvent::Disabled => {
+                if editor.value.composing.is_some() {
+                    let before = editor.value.clone();
+
+                    editor.value.set_preedit("", None);
+
+                    self.commit_edit(editor, &before);
+                }
+            }
+        }
+
+        EventResult::Handled
+    }
+
+    fn handle_pointer(
+        &self,
+        ctx: &mut RenderObjectEventContext,
+        event: &PointerEvent,
+    ) -> EventResult {
+        let mut editor = self.editor.lock();
+        let editor = &mut *editor;
+
+        match event.kind {
+            PointerEventKind::Down if event.button == PointerButton::Primary => {
+                ctx.request_focus();
+
+                let text = editor.value.text.as_str().to_owned();
+
+                let offset = self.offset_at(&text, event.local_position);
+
+                let is_double_click = editor.last_click.is_some_and(|(timestamp, position)| {
+                    event.timestamp.saturating_sub(timestamp) <= DOUBLE_CLICK_TIMEOUT
+                        && (event.local_position - position).distance() <= DOUBLE_CLICK_SLOP
+                });
+
+                // A third click starts over, rather than counting as another double click
+                editor.last_click = if is_double_click {
+                    None
+                } else {
+                    Some((event.timestamp, event.local_position))
+                };
+
+                editor.dragging = Some(event.pointer);
+
+                let selection = if is_double_click {
+                    let word = self.word_at(&text, offset);
+
+                    editor.anchor_word = Some(word.clone());
+
+                    TextSelection::new(word.start, word.end)
+                } else {
+                    editor.anchor_word = None;
+
+                    if event.modifiers.contains(Modifiers::SHIFT) {
+                        TextSelection::new(editor.value.selection.base, offset)
+                    } else {
+                        TextSelection::collapsed(offset)
+                    }
+                };
+
+                editor.preferred_x = None;
+
+                self.select(editor, selection);
+
+                EventResult::Handled
+            }
+
+            PointerEventKind::Move if editor.dragging == Some(event.pointer) => {
+                let text = editor.value.text.as_str().to_owned();
+
+                let offset = self.offset_at(&text, event.local_position);
+
+                let selection = match &editor.anchor_word {
+                    Some(anchor) => {
+                        let word = self.word_at(&text, offset);
+
+                        if word.start < anchor.start {
+                            TextSelection::new(anchor.end, word.start)
+                        } else {
+                            TextSelection::new(anchor.start, word.end.max(anchor.end))
+                        }
+                    }
+
+                    None => TextSelection::new(editor.value.selection.base, offset),
+                };
+
+                self.select(editor, selection);
+
+                EventResult::Handled
+            }
+
+            PointerEventKind::Up | PointerEventKind::Cancel
+                if editor.dragging == Some(event.pointer) =>
+            {
+                editor.dragging = None;
+
+                EventResult::Handled
+            }
+
+            PointerEventKind::Scroll { delta } => {
+                let mut scroll = self.scroll.lock();
+
+                let offset = scroll.clamp(scroll.offset + delta);
+
+                if offset == scroll.offset {
+                    return EventResult::Ignored;
+                }
+
+                scroll.offset = offset;
+
+                self.on_scroll.call(offset);
+
+                EventResult::Handled
+            }
+
+            _ => EventResult::Ignored,
+        }
+    }
+}
+
+impl<S> RenderObjectImpl for RenderEditable<S>
+where
+    S: EditableText + 'static,
+{
+    fn intrinsic_size(
+        &self,
+        ctx: &mut RenderObjectIntrinsicSizeContext,
+        dimension: IntrinsicDimension,
+        cross_axis: f32,
+    ) -> f32 {
+        self.paragraph.intrinsic_size(ctx, dimension, cross_axis)
+            + match dimension {
+                IntrinsicDimension::MinWidth | IntrinsicDimension::MaxWidth => CARET_WIDTH,
+                IntrinsicDimension::MinHeight | IntrinsicDimension::MaxHeight => 0.0,
+            }
+    }
+
+    fn layout(&self, ctx: &mut RenderObjectLayoutContext, constraints: Constraints) -> Size {
+        // Single line fields scroll horizontally, so their text is never wrapped
+        let max_width = if self.multiline {
+            (constraints.max_width() - CARET_WIDTH).max(0.0)
+        } else {
+            f32::INFINITY
+        };
+
+        let content_size = self
+            .paragraph
+            .layout(ctx, Constraints::new(0.0, max_width, 0.0, f32::INFINITY));
+
+        let lines = self.paragraph.line_metrics();
+
+        let line_height = lines.first().map_or(self.style.size, |line| line.height);
+
+        let height = match self.max_lines {
+            Some(max_lines) if self.multiline => lines
+                .get(max_lines.saturating_sub(1))
+                .map_or(content_size.height, |line| line.top + line.height),
+
+            _ => content_size.height,
+        };
+
+        let width = if constraints.has_bounded_width() {
+            constraints.max_width()
+        } else {
+            content_size.width + CARET_WIDTH
+        };
+
+        let size = constraints.constrain(Size::new(width, height.max(line_height)));
+
+        let mut scroll = self.scroll.lock();
+
+        scroll.content_size = Size::new(content_size.width + CARET_WIDTH, content_size.height);
+        scroll.viewport_size = size;
+
+        if std::mem::take(&mut scroll.reveal_caret) {
+            if let Some(selection) = self.paragraph.selection {
+                let caret = self
+                    .paragraph
+                    .caret_rect(TextPosition::new(selection.extent));
+
+                let mut offset = scroll.offset;
+
+                offset.x = offset
+                    .x
+                    .min(caret.left)
+                    .max(caret.left + CARET_WIDTH - size.width);
+
+                offset.y = offset.y.min(caret.top).max(caret.bottom() - size.height);
+
+                scroll.offset = offset;
+            }
+        }
+
+        scroll.offset = scroll.clamp(scroll.offset);
+
+        size
+    }
+
+    /// The baseline of the field is that of its first line, where it's been scrolled to.
+    fn compute_distance_to_baseline(
+        &self,
+        ctx: &mut RenderObjectBaselineContext,
+        baseline: TextBaseline,
+    ) -> Option<f32> {
+        self.paragraph
+            .compute_distance_to_baseline(ctx, baseline)
+            .map(|distance| distance - self.scroll.lock().offset.y)
+    }
+
+    fn hit_test<'ctx>(
+        &self,
+        ctx: &'ctx mut RenderObjectHitTestContext<'ctx>,
+        position: Offset,
+    ) -> HitTest {
+        if ctx.size().contains(position) {
+            HitTest::Absorb
+        } else {
+            HitTest::Pass
+        }
+    }
+
+    fn handle_event(&self, ctx: &mut RenderObjectEventContext, event: &InputEvent) -> EventResult {
+        match event {
+            InputEvent::Pointer(event) => self.handle_pointer(ctx, event),
+
+            InputEvent::Key(event) => self.handle_key(event),
+
+            InputEvent::Text(text) => self.handle_text(text),
+
+            InputEvent::Ime(event) => self.handle_ime(event),
+
+            InputEvent::FocusChanged(has_focus) => {
+                let mut editor = self.editor.lock();
+
+                // Focus is gained by pressing the field, which may be the start of a drag
+                if !has_focus {
+                    editor.dragging = None;
+                }
+
+                editor.history.break_coalescing();
+
+                self.on_focus_change.call(*has_focus);
+
+                EventResult::Handled
+            }
+        }
+    }
+
+    fn does_paint(&self) -> bool {
+        true
+    }
+
+    fn paint(&self, mut canvas: CanvasPainter<Head<()>>) {
+        let (offset, content_size) = {
+            let mut scroll = self.scroll.lock();
+
+            scroll.painted = scroll.offset;
+
+            (scroll.offset, scroll.content_size)
+        };
+
+        let caret = self
+            .paragraph
+            .selection
+            .filter(|selection| self.focused && selection.is_collapsed())
+            .map(|selection| {
+                let caret = self
+                    .paragraph
+                    .caret_rect(TextPosition::new(selection.extent));
+
+                Rect::new(
+                    caret.left,
+                    caret.top,
+                    CARET_WIDTH,
+                    if caret.height > 0.0 {
+                        caret.height
+                    } else {
+                        self.style.size
+                    },
+                )
+            });
+
+        // The text is clipped to the field, and scrolled within it
+        let brush = canvas.add_paint(Paint::default());
+
+        canvas.layer(&brush, Shape::Rect, |mut canvas| {
+            let brush = canvas.add_paint(Paint::default());
+
+            canvas.layer_at(
+                Rect::new(
+                    -offset.x,
+                    -offset.y,
+                    content_size.width,
+                    content_size.height.max(self.style.size),
+                ),
+                &brush,
+                Shape::Rect,
+                |mut canvas| {
+                    // The paragraph may add a layer of its own, so the caret is drawn in
+                    // another layer above it
+                    let brush = canvas.add_paint(Paint::default());
+
+                    canvas.nested_layer(&brush, Shape::Rect, |mut canvas| {
+                        self.paragraph.paint_to(&mut canvas);
+                    });
+
+                    if let Some(caret) = caret {
+                        canvas.nested_layer(&brush, Shape::Rect, |mut canvas| {
+                            let brush = canvas.add_paint(Paint {
+                                color: self.cursor_color,
+
+                                ..Paint::default()
+                            });
+
+                            canvas.draw_rect_at(caret, &brush);
+                        });
+                    }
+                },
+            );
+        });
+    }
+}
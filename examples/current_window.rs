@@ -79,9 +79,9 @@ impl WidgetState for ExampleMainState {
         });
 
         self.handle = ctx
-            .spawn_task(|_| async move {
+            .spawn_task(|ctx| async move {
                 loop {
-                    futures_timer::Delay::new(Duration::from_millis(1000)).await;
+                    ctx.clock().sleep(Duration::from_millis(1000)).await;
 
                     callback.call(());
                 }
@@ -106,6 +106,8 @@ impl WidgetState for ExampleMainState {
 
                     h_align: HorizontalAlign::default(),
                     v_align: VerticalAlign::default(),
+
+                    ..TextStyle::default()
                 },
 
                 text: format!("updated {} times", self.update_count).into(),
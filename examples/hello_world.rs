@@ -47,6 +47,8 @@ fn main() {
 
                             h_align: HorizontalAlign::default(),
                             v_align: VerticalAlign::default(),
+
+                            ..TextStyle::default()
                         },
 
                         text: "Hello, world!".into(),
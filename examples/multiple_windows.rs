@@ -50,6 +50,8 @@ fn main() {
 
                                     h_align: HorizontalAlign::default(),
                                     v_align: VerticalAlign::default(),
+
+                                    ..TextStyle::default()
                                 },
 
                                 text: "Hello, world!".into(),
@@ -79,6 +81,8 @@ fn main() {
 
                                     h_align: HorizontalAlign::default(),
                                     v_align: VerticalAlign::default(),
+
+                                    ..TextStyle::default()
                                 },
 
                                 text: "Goodbye, world!".into(),
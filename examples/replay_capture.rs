@@ -0,0 +1,77 @@
+//! Replays a scene captured with a `CapturingView` into an SVG document.
+//!
+//! Usage: `cargo run --example replay_capture --features svg,serde -- <capture> [output.svg]`
+//!
+//! The capture may be in either the binary or the JSON encoding. The document is written
+//! to stdout unless an output path is given.
+
+use std::{fs, process::ExitCode};
+
+use agui::{
+    engine::rendering::capture::{CaptureError, SceneCapture},
+    svg::renderer::SvgRenderer,
+};
+
+fn main() -> ExitCode {
+    let mut args = std::env::args().skip(1);
+
+    let Some(input) = args.next() else {
+        eprintln!("usage: replay_capture <capture> [output.svg]");
+
+        return ExitCode::FAILURE;
+    };
+
+    let output = args.next();
+
+    let bytes = match fs::read(&input) {
+        Ok(bytes) => bytes,
+        Err(err) => {
+            eprintln!("failed to read {}: {}", input, err);
+
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let scene = match SceneCapture::from_bytes(&bytes) {
+        // Anything without the binary header is assumed to be JSON
+        Err(CaptureError::InvalidHeader) => match std::str::from_utf8(&bytes) {
+            Ok(json) => SceneCapture::from_json(json),
+            Err(_) => Err(CaptureError::InvalidHeader),
+        },
+
+        result => result,
+    };
+
+    let scene = match scene {
+        Ok(scene) => scene,
+        Err(err) => {
+            eprintln!("failed to decode {}: {}", input, err);
+
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let (mut view, handle) = SvgRenderer::new().new_view();
+
+    if let Err(err) = scene.replay(&mut view) {
+        eprintln!("failed to replay {}: {}", input, err);
+
+        return ExitCode::FAILURE;
+    }
+
+    let document = handle.document();
+
+    match output {
+        Some(output) => {
+            if let Err(err) = fs::write(&output, document) {
+                eprintln!("failed to write {}: {}", output, err);
+
+                return ExitCode::FAILURE;
+            }
+        }
+
+        None => println!("{}", document),
+    }
+
+    ExitCode::SUCCESS
+}
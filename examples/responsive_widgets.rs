@@ -54,6 +54,8 @@ fn main() {
 
                                             h_align: HorizontalAlign::default(),
                                             v_align: VerticalAlign::default(),
+
+                                            ..TextStyle::default()
                                         },
 
                                         text: "Chonker box moment".into(),
@@ -72,6 +74,8 @@ fn main() {
 
                                             h_align: HorizontalAlign::default(),
                                             v_align: VerticalAlign::default(),
+
+                                            ..TextStyle::default()
                                         },
 
                                         text: "itty bitty boxxy".into(),
@@ -96,9 +96,9 @@ impl WidgetState for ExampleMainState {
         });
 
         self.handle = ctx
-            .spawn_task(move |_| async move {
+            .spawn_task(move |ctx| async move {
                 loop {
-                    futures_timer::Delay::new(Duration::from_millis(1000)).await;
+                    ctx.clock().sleep(Duration::from_millis(1000)).await;
 
                     let mut system = System::new_all();
 
@@ -162,6 +162,8 @@ impl WidgetState for ExampleMainState {
 
                                     h_align: HorizontalAlign::default(),
                                     v_align: VerticalAlign::default(),
+
+                                    ..TextStyle::default()
                                 },
 
                                 text: entry.into(),
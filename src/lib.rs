@@ -11,35 +11,64 @@ pub mod widgets {
     pub use agui_widgets::*;
 }
 
+#[cfg(feature = "gestures")]
+pub use agui_gestures as gestures;
+
 #[cfg(feature = "winit")]
 pub use agui_winit as winit;
 
 #[cfg(feature = "vello")]
 pub use agui_vello as vello;
 
+#[cfg(feature = "software")]
+pub use agui_software as software;
+
+#[cfg(feature = "svg")]
+pub use agui_svg as svg;
+
+#[cfg(feature = "pdf")]
+pub use agui_pdf as pdf;
+
+#[cfg(feature = "terminal")]
+pub use agui_terminal as terminal;
+
 pub mod prelude {
     pub use agui_core::{callback::*, query::*, render::*, unit::*, widget::*};
     pub use agui_elements::{render::*, stateful::*, stateless::*};
     pub use agui_primitives::{
         align::{Align, Center},
+        backdrop_filter::BackdropFilter,
+        baseline::Baseline,
         builder::Builder,
         clip::Clip,
         colored_box::ColoredBox,
+        decorated_box::{Border, BorderRadius, BorderSide, BoxDecoration, DecoratedBox},
+        directionality::Directionality,
         flex::{
             Column, CrossAxisAlignment, Flex, FlexFit, Flexible, MainAxisAlignment, MainAxisSize,
             Row, VerticalDirection,
         },
+        focus::Focus,
+        image::{BoxFit, Image, ImageRepeat},
         intrinsic::{IntrinsicAxis, IntrinsicHeight, IntrinsicWidth},
         layout_builder::LayoutBuilder,
+        listener::{HitTestBehavior, Listener},
         padding::Padding,
         sized_box::SizedBox,
         stack::Stack,
-        text::{Text, TextBaseline},
+        svg::SvgImage,
+        text::Text,
     };
 
+    #[cfg(feature = "gestures")]
+    pub use agui_gestures::gesture_detector::GestureDetector;
+
     #[cfg(feature = "macros")]
     pub use agui_macros::*;
 }
 
 #[cfg(feature = "app")]
 pub mod app;
+
+#[cfg(feature = "testing")]
+pub mod testing;
@@ -0,0 +1,108 @@
+//! Finders locate elements in the tree of a [`WidgetTester`](super::tester::WidgetTester).
+//!
+//! ```ignore
+//! tester.tap(&find::text("Save"));
+//! ```
+
+use std::{fmt, rc::Rc};
+
+use agui_core::{
+    element::{widget::ElementWidget, ElementBuilder, ElementId},
+    engine::elements::ElementTree,
+    query::{by_key::FilterKeyExt, by_widget::FilterByWidgetExt},
+    unit::Key,
+    widget::AnyWidget,
+};
+use agui_primitives::text::query::FilterTextExt;
+
+/// Finds the `Text` widgets displaying exactly `text`.
+pub fn text(text: impl Into<String>) -> Finder {
+    let text = text.into();
+
+    Finder::new(format!("text {:?}", text), move |tree| {
+        tree.iter()
+            .with_text(&text)
+            .map(|entry| entry.id())
+            .collect()
+    })
+}
+
+/// Finds the widgets with the given key.
+pub fn by_key(key: Key) -> Finder {
+    Finder::new(format!("key {:?}", key), move |tree| {
+        tree.iter()
+            .filter_key(key)
+            .map(|entry| entry.id())
+            .collect()
+    })
+}
+
+/// Finds the widgets of type `W`.
+pub fn by_widget<W>() -> Finder
+where
+    W: AnyWidget + ElementBuilder,
+    <W as ElementBuilder>::Element: ElementWidget,
+{
+    Finder::new(format!("widget {}", std::any::type_name::<W>()), |tree| {
+        tree.iter()
+            .filter_widget::<W>()
+            .map(|entry| entry.id())
+            .collect()
+    })
+}
+
+type FindFn = dyn Fn(&ElementTree) -> Vec<ElementId>;
+
+/// A description of which elements to find in the tree.
+#[derive(Clone)]
+pub struct Finder {
+    description: String,
+
+    find: Rc<FindFn>,
+}
+
+impl Finder {
+    pub fn new<F>(description: impl Into<String>, find: F) -> Self
+    where
+        F: Fn(&ElementTree) -> Vec<ElementId> + 'static,
+    {
+        Self {
+            description: description.into(),
+
+            find: Rc::new(find),
+        }
+    }
+
+    /// Returns the elements in the tree that match this finder.
+    pub fn evaluate(&self, tree: &ElementTree) -> Vec<ElementId> {
+        (self.find)(tree)
+    }
+
+    /// Narrows the finder to only the first element it matches.
+    pub fn first(self) -> Self {
+        self.at(0)
+    }
+
+    /// Narrows the finder to only the element at `index` of those it matches.
+    pub fn at(self, index: usize) -> Self {
+        let find = self.find;
+
+        Self {
+            description: format!("{} at index {}", self.description, index),
+
+            find: Rc::new(move |tree| find(tree).into_iter().skip(index).take(1).collect()),
+        }
+    }
+}
+
+impl fmt::Display for Finder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.description)
+    }
+}
+
+impl fmt::Debug for Finder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Finder").field(&self.description).finish()
+    }
+}
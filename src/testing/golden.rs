@@ -0,0 +1,247 @@
+//! Golden image tests, which compare what a widget tree draws against a PNG stored
+//! alongside the tests.
+//!
+//! Set the `AGUI_UPDATE_GOLDENS` environment variable to write the rendered frames to
+//! the golden files instead of comparing against them.
+
+use std::path::{Path, PathBuf};
+
+use agui_core::{unit::ViewConfiguration, widget::IntoWidget};
+use agui_software::frame::Frame;
+use image::{Rgba, RgbaImage};
+
+use crate::testing::tester::WidgetTester;
+
+/// The environment variable that, when set, causes goldens to be regenerated.
+pub const UPDATE_GOLDENS_ENV: &str = "AGUI_UPDATE_GOLDENS";
+
+/// Pumps `widget` until it settles and returns the frame it draws.
+///
+/// The widget is given tight constraints matching the size of the view, and is drawn
+/// at the view's device pixel ratio.
+pub fn render(widget: impl IntoWidget, config: ViewConfiguration) -> Frame {
+    let mut tester = WidgetTester::new(widget, config);
+
+    tester.pump_and_settle();
+
+    tester.frame()
+}
+
+/// Renders `widget` and compares it against the golden at `golden`, panicking if they
+/// don't match.
+#[track_caller]
+pub fn assert_golden(
+    widget: impl IntoWidget,
+    config: ViewConfiguration,
+    golden: impl Into<Golden>,
+) {
+    let golden = golden.into();
+
+    if let Err(err) = golden.compare(&render(widget, config)) {
+        panic!("golden {} did not match: {}", golden.path.display(), err);
+    }
+}
+
+/// A PNG that a rendered frame is expected to match.
+#[derive(Debug, Clone)]
+pub struct Golden {
+    path: PathBuf,
+
+    tolerance: u8,
+}
+
+impl Golden {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+
+            tolerance: 0,
+        }
+    }
+
+    /// Sets how far each color channel of a pixel may differ from the golden before it's
+    /// considered a mismatch. Defaults to `0`.
+    pub fn tolerance(mut self, tolerance: u8) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Compares `frame` against the golden.
+    ///
+    /// If goldens are being updated, the frame is written to the golden instead. When the
+    /// frame doesn't match, the frame and an image highlighting the mismatched pixels are
+    /// written to a `failures` directory next to the golden.
+    pub fn compare(&self, frame: &Frame) -> Result<(), GoldenError> {
+        let actual = to_image(frame);
+
+        if should_update_goldens() {
+            if let Some(parent) = self.path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+
+            actual.save(&self.path)?;
+
+            return Ok(());
+        }
+
+        if !self.path.exists() {
+            return Err(GoldenError::Missing(self.path.clone()));
+        }
+
+        let expected = image::open(&self.path)?.into_rgba8();
+
+        if expected.dimensions() != actual.dimensions() {
+            return Err(GoldenError::SizeMismatch {
+                expected: expected.dimensions(),
+                actual: actual.dimensions(),
+            });
+        }
+
+        let (diff, mismatched) = diff(&expected, &actual, self.tolerance);
+
+        if mismatched == 0 {
+            return Ok(());
+        }
+
+        let failures_dir = self
+            .path
+            .parent()
+            .unwrap_or_else(|| Path::new(""))
+            .join("failures");
+
+        std::fs::create_dir_all(&failures_dir)?;
+
+        let name = self
+            .path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_else(|| String::from("golden"));
+
+        actual.save(failures_dir.join(format!("{}.actual.png", name)))?;
+
+        let diff_path = failures_dir.join(format!("{}.diff.png", name));
+
+        diff.save(&diff_path)?;
+
+        Err(GoldenError::Mismatch {
+            pixels: mismatched,
+            diff_path,
+        })
+    }
+}
+
+impl From<&str> for Golden {
+    fn from(path: &str) -> Self {
+        Self::new(path)
+    }
+}
+
+impl From<PathBuf> for Golden {
+    fn from(path: PathBuf) -> Self {
+        Self::new(path)
+    }
+}
+
+impl From<&Path> for Golden {
+    fn from(path: &Path) -> Self {
+        Self::new(path)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum GoldenError {
+    #[error("golden {0} does not exist, set {UPDATE_GOLDENS_ENV} to create it")]
+    Missing(PathBuf),
+
+    #[error("expected a {expected:?} image, but rendered {actual:?}")]
+    SizeMismatch {
+        expected: (u32, u32),
+        actual: (u32, u32),
+    },
+
+    #[error("{pixels} pixels differ, see {diff_path:?}")]
+    Mismatch { pixels: usize, diff_path: PathBuf },
+
+    #[error(transparent)]
+    Image(#[from] image::ImageError),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+fn should_update_goldens() -> bool {
+    std::env::var_os(UPDATE_GOLDENS_ENV).is_some_and(|value| !value.is_empty() && value != "0")
+}
+
+fn to_image(frame: &Frame) -> RgbaImage {
+    RgbaImage::from_raw(frame.width(), frame.height(), frame.pixels().to_vec())
+        .expect("frame pixels do not match its size")
+}
+
+/// Returns an image marking mismatched pixels in red over a faded copy of the expected
+/// image, along with the number of pixels that differ by more than `tolerance`.
+fn diff(expected: &RgbaImage, actual: &RgbaImage, tolerance: u8) -> (RgbaImage, usize) {
+    let mut mismatched = 0;
+
+    let diff = RgbaImage::from_fn(expected.width(), expected.height(), |x, y| {
+        let expected = expected.get_pixel(x, y);
+        let actual = actual.get_pixel(x, y);
+
+        let differs = expected
+            .0
+            .iter()
+            .zip(actual.0.iter())
+            .any(|(a, b)| a.abs_diff(*b) > tolerance);
+
+        if differs {
+            mismatched += 1;
+
+            Rgba([255, 0, 0, 255])
+        } else {
+            let [r, g, b, a] = expected.0;
+
+            let luma = ((r as u32 * 299 + g as u32 * 587 + b as u32 * 114) / 1000) as u8;
+
+            Rgba([luma, luma, luma, a / 4])
+        }
+    });
+
+    (diff, mismatched)
+}
+
+#[cfg(test)]
+mod tests {
+    use image::{Rgba, RgbaImage};
+
+    use super::diff;
+
+    #[test]
+    fn counts_pixels_outside_tolerance() {
+        let expected = RgbaImage::from_pixel(2, 1, Rgba([100, 100, 100, 255]));
+
+        let mut actual = expected.clone();
+        actual.put_pixel(0, 0, Rgba([104, 100, 100, 255]));
+        actual.put_pixel(1, 0, Rgba([110, 100, 100, 255]));
+
+        assert_eq!(diff(&expected, &actual, 0).1, 2);
+        assert_eq!(diff(&expected, &actual, 4).1, 1);
+        assert_eq!(diff(&expected, &actual, 10).1, 0);
+    }
+
+    #[test]
+    fn marks_mismatched_pixels() {
+        let expected = RgbaImage::from_pixel(2, 1, Rgba([0, 0, 0, 255]));
+
+        let mut actual = expected.clone();
+        actual.put_pixel(1, 0, Rgba([255, 255, 255, 255]));
+
+        let (diff, _) = diff(&expected, &actual, 0);
+
+        assert_ne!(diff.get_pixel(0, 0), &Rgba([255, 0, 0, 255]));
+        assert_eq!(diff.get_pixel(1, 0), &Rgba([255, 0, 0, 255]));
+    }
+}
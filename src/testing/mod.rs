@@ -0,0 +1,5 @@
+//! Utilities for testing widgets and what they draw.
+
+pub mod find;
+pub mod golden;
+pub mod tester;
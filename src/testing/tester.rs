@@ -0,0 +1,309 @@
+//! A headless harness for mounting widgets and interacting with them in tests.
+
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+use agui_core::{
+    element::{widget::ElementWidget, ElementBuilder, ElementId},
+    input::{InputEvent, Key, KeyEvent, PointerEvent, PointerId},
+    task::clock::ManualClock,
+    unit::{Offset, Rect, Size, ViewConfiguration},
+    widget::{AnyWidget, IntoWidget, Widget},
+};
+use agui_executor::{EngineExecutor, LocalEngineExecutor};
+use agui_primitives::sized_box::SizedBox;
+use agui_software::{
+    create_view::CreateSoftwareView, frame::Frame, renderer::SoftwareRenderer,
+    view::SoftwareViewHandle,
+};
+
+use crate::testing::find::Finder;
+
+/// Mounts a widget in a view of a fixed size, and drives it with synthetic input.
+///
+/// The tester keeps its own clock, which starts at zero and only moves forward when
+/// [`WidgetTester::advance`] is called. Every event sent by the tester is timestamped with
+/// it, and async tasks that sleep on their context's clock are woken by it, so timers and
+/// time-sensitive gestures such as double taps can be tested without waiting.
+///
+/// A callback that calls another callback only runs the inner one on the following update,
+/// so after sending gestures prefer [`WidgetTester::pump_and_settle`] to a single pump.
+pub struct WidgetTester {
+    executor: LocalEngineExecutor,
+
+    view_handle: SoftwareViewHandle,
+
+    clock: ManualClock,
+
+    next_pointer: u64,
+}
+
+impl WidgetTester {
+    /// Mounts `widget` in a view with the given configuration and builds the first frame.
+    ///
+    /// The widget is given tight constraints matching the size of the view.
+    pub fn new(widget: impl IntoWidget, config: ViewConfiguration) -> Self {
+        let child = widget.into_widget();
+
+        let view_handle = Rc::new(RefCell::new(None::<SoftwareViewHandle>));
+
+        let root = CreateSoftwareView {
+            renderer: SoftwareRenderer::new(),
+
+            builder: {
+                let view_handle = Rc::clone(&view_handle);
+
+                move |handle: &SoftwareViewHandle| -> Widget {
+                    handle.set_device_pixel_ratio(config.device_pixel_ratio);
+
+                    view_handle.borrow_mut().replace(handle.clone());
+
+                    SizedBox {
+                        width: Some(config.size.width),
+                        height: Some(config.size.height),
+
+                        child: Some(child.clone()),
+                    }
+                    .into_widget()
+                }
+            },
+        };
+
+        let clock = ManualClock::new();
+
+        let executor = LocalEngineExecutor::with_root_and_clock(root, clock.clock())
+            .expect("failed to build tree");
+
+        let view_handle = view_handle
+            .borrow_mut()
+            .take()
+            .expect("the view was never created");
+
+        Self {
+            executor,
+
+            view_handle,
+
+            clock,
+
+            next_pointer: 0,
+        }
+    }
+
+    pub fn executor(&self) -> &LocalEngineExecutor {
+        &self.executor
+    }
+
+    /// How far the tester's clock has been advanced.
+    pub fn elapsed(&self) -> Duration {
+        self.clock.now()
+    }
+
+    /// Executes pending callbacks and rebuilds, then lays out and paints a single frame.
+    ///
+    /// This does not run async tasks.
+    pub fn pump(&mut self) {
+        self.executor.update();
+    }
+
+    /// Pumps frames and runs async tasks until no more progress can be made.
+    pub fn pump_and_settle(&mut self) {
+        self.executor.run_until_stalled();
+    }
+
+    /// Advances the tester's clock by `duration`.
+    ///
+    /// Timers fire in the order they're due, and the tree is settled after each one, so a
+    /// task that sleeps again after waking is woken again if its new deadline falls within
+    /// `duration`.
+    pub fn advance(&mut self, duration: Duration) {
+        let target = self.clock.now() + duration;
+
+        // Settle first, so tasks that haven't run yet get to register their timers
+        self.pump_and_settle();
+
+        while let Some(deadline) = self
+            .clock
+            .next_deadline()
+            .filter(|deadline| *deadline <= target)
+        {
+            self.clock.advance_to(deadline);
+
+            self.pump_and_settle();
+        }
+
+        self.clock.advance_to(target);
+
+        self.pump_and_settle();
+    }
+
+    /// Returns the last frame drawn by the view.
+    pub fn frame(&self) -> Frame {
+        self.view_handle
+            .snapshot()
+            .expect("nothing has been drawn to the view")
+    }
+
+    /// Returns the elements matched by `finder`.
+    pub fn find(&self, finder: &Finder) -> Vec<ElementId> {
+        finder.evaluate(self.executor.element_tree())
+    }
+
+    /// Whether `finder` matches any elements.
+    pub fn exists(&self, finder: &Finder) -> bool {
+        !self.find(finder).is_empty()
+    }
+
+    /// Returns the element matched by `finder`, panicking unless it matches exactly one.
+    #[track_caller]
+    pub fn find_one(&self, finder: &Finder) -> ElementId {
+        match self.find(finder).as_slice() {
+            [element_id] => *element_id,
+            [] => panic!("found no elements matching {}", finder),
+            found => panic!("found {} elements matching {}", found.len(), finder),
+        }
+    }
+
+    /// Returns the widget of the element matched by `finder`.
+    #[track_caller]
+    pub fn widget<W>(&self, finder: &Finder) -> Rc<W>
+    where
+        W: AnyWidget + ElementBuilder,
+        <W as ElementBuilder>::Element: ElementWidget,
+    {
+        let element_id = self.find_one(finder);
+
+        let element = self
+            .executor
+            .element_tree()
+            .as_ref()
+            .get(element_id)
+            .expect("found element is missing from the tree");
+
+        element
+            .downcast::<<W as ElementBuilder>::Element>()
+            .and_then(|element| Rc::clone(element.widget()).as_any().downcast::<W>().ok())
+            .unwrap_or_else(|| {
+                panic!(
+                    "the widget matching {} is not a {}",
+                    finder,
+                    std::any::type_name::<W>()
+                )
+            })
+    }
+
+    /// Returns the bounds of the element matched by `finder`, relative to the root of
+    /// the view.
+    #[track_caller]
+    pub fn rect(&self, finder: &Finder) -> Rect {
+        let element_id = self.find_one(finder);
+
+        let rendering_tree = self.executor.rendering_tree();
+
+        let render_object_id = rendering_tree
+            .get_render_object_id(element_id)
+            .unwrap_or_else(|| panic!("the element matching {} has no render object", finder));
+
+        let tree = rendering_tree.as_ref();
+
+        let mut offset = Offset::ZERO;
+        let mut current_id = Some(render_object_id);
+
+        while let Some(render_object_id) = current_id {
+            offset += tree
+                .get(render_object_id)
+                .expect("render object missing from the tree")
+                .offset();
+
+            current_id = tree.get_parent(render_object_id).copied();
+        }
+
+        let size = tree
+            .get(render_object_id)
+            .expect("render object missing from the tree")
+            .size();
+
+        offset & size
+    }
+
+    #[track_caller]
+    pub fn size(&self, finder: &Finder) -> Size {
+        let rect = self.rect(finder);
+
+        Size::new(rect.width, rect.height)
+    }
+
+    #[track_caller]
+    pub fn center(&self, finder: &Finder) -> Offset {
+        let rect = self.rect(finder);
+
+        Offset::new(rect.left + rect.width / 2.0, rect.top + rect.height / 2.0)
+    }
+
+    /// Sends an input event to the tree. Any callbacks it causes are executed by the next
+    /// pump.
+    pub fn dispatch_event(&mut self, event: impl Into<InputEvent>) {
+        self.executor.dispatch_event(event);
+    }
+
+    /// Taps the center of the element matched by `finder`.
+    #[track_caller]
+    pub fn tap(&mut self, finder: &Finder) {
+        let position = self.center(finder);
+
+        self.tap_at(position);
+    }
+
+    /// Presses and releases a pointer at `position`.
+    pub fn tap_at(&mut self, position: Offset) {
+        let pointer = self.next_pointer();
+
+        self.dispatch_pointer(pointer, PointerEvent::down(position));
+        self.dispatch_pointer(pointer, PointerEvent::up(position));
+    }
+
+    /// Drags from the center of the element matched by `finder` by `delta`.
+    #[track_caller]
+    pub fn drag(&mut self, finder: &Finder, delta: Offset) {
+        let start = self.center(finder);
+
+        self.drag_from(start, delta);
+    }
+
+    /// Presses a pointer at `start`, moves it by `delta`, then releases it.
+    pub fn drag_from(&mut self, start: Offset, delta: Offset) {
+        let pointer = self.next_pointer();
+
+        let end = start + delta;
+
+        self.dispatch_pointer(pointer, PointerEvent::down(start));
+        self.dispatch_pointer(pointer, PointerEvent::moved(end));
+        self.dispatch_pointer(pointer, PointerEvent::up(end));
+    }
+
+    /// Taps the element matched by `finder` to focus it, then enters `text` as if it
+    /// was typed.
+    #[track_caller]
+    pub fn enter_text(&mut self, finder: &Finder, text: &str) {
+        self.tap(finder);
+
+        self.dispatch_event(InputEvent::Text(text.to_owned()));
+    }
+
+    /// Presses and releases `key`, sending both to the focused render object.
+    pub fn send_key(&mut self, key: impl Into<Key>) {
+        let key = key.into();
+
+        self.dispatch_event(KeyEvent::pressed(key));
+        self.dispatch_event(KeyEvent::released(key));
+    }
+
+    fn next_pointer(&mut self) -> PointerId {
+        self.next_pointer += 1;
+
+        PointerId(self.next_pointer)
+    }
+
+    fn dispatch_pointer(&mut self, pointer: PointerId, event: PointerEvent) {
+        self.dispatch_event(event.with_pointer(pointer).with_timestamp(self.clock.now()));
+    }
+}
@@ -0,0 +1,316 @@
+use agui::{
+    prelude::*,
+    testing::golden::{assert_golden, Golden},
+    widgets::primitives::text::{RichText, TextSpan, TextSpanStyle},
+};
+
+const RED: Color = Color::from_rgb((1.0, 0.0, 0.0));
+const GREEN: Color = Color::from_rgb((0.0, 1.0, 0.0));
+const BLUE: Color = Color::from_rgb((0.0, 0.0, 1.0));
+const WHITE: Color = Color::from_rgb((1.0, 1.0, 1.0));
+
+fn config(width: f32, height: f32) -> ViewConfiguration {
+    ViewConfiguration {
+        size: Size::new(width, height),
+
+        device_pixel_ratio: 1.0,
+    }
+}
+
+#[test]
+fn colored_box() {
+    assert_golden(
+        build! {
+            <ColoredBox> {
+                color: RED,
+            }
+        },
+        config(32.0, 32.0),
+        "tests/goldens/colored_box.png",
+    );
+}
+
+#[test]
+fn colored_box_device_pixel_ratio() {
+    assert_golden(
+        build! {
+            <ColoredBox> {
+                color: WHITE,
+
+                child: <Padding> {
+                    padding: EdgeInsets::all(4.0),
+
+                    child: <ColoredBox> {
+                        color: BLUE,
+                    }
+                }
+            }
+        },
+        ViewConfiguration {
+            size: Size::new(16.0, 16.0),
+
+            device_pixel_ratio: 2.0,
+        },
+        "tests/goldens/colored_box_device_pixel_ratio.png",
+    );
+}
+
+#[test]
+fn padding() {
+    assert_golden(
+        build! {
+            <ColoredBox> {
+                color: WHITE,
+
+                child: <Padding> {
+                    padding: EdgeInsets::new(4.0, 8.0, 12.0, 16.0),
+
+                    child: <ColoredBox> {
+                        color: RED,
+                    }
+                }
+            }
+        },
+        config(48.0, 48.0),
+        "tests/goldens/padding.png",
+    );
+}
+
+#[test]
+fn clip_rounded_rect() {
+    assert_golden(
+        build! {
+            <ColoredBox> {
+                color: WHITE,
+
+                child: <Padding> {
+                    padding: EdgeInsets::all(8.0),
+
+                    child: <Clip> {
+                        shape: Shape::RoundedRect {
+                            top_left: 12.0,
+                            top_right: 4.0,
+                            bottom_right: 12.0,
+                            bottom_left: 4.0,
+                        },
+                        anti_alias: true,
+
+                        child: <ColoredBox> {
+                            color: BLUE,
+                        }
+                    }
+                }
+            }
+        },
+        config(64.0, 48.0),
+        Golden::new("tests/goldens/clip_rounded_rect.png").tolerance(2),
+    );
+}
+
+#[test]
+fn clip_circle() {
+    assert_golden(
+        build! {
+            <Clip> {
+                shape: Shape::Circle,
+                anti_alias: true,
+
+                child: <ColoredBox> {
+                    color: GREEN,
+                }
+            }
+        },
+        config(48.0, 48.0),
+        Golden::new("tests/goldens/clip_circle.png").tolerance(2),
+    );
+}
+
+#[test]
+fn decorated_box() {
+    assert_golden(
+        build! {
+            <ColoredBox> {
+                color: WHITE,
+
+                child: <Padding> {
+                    padding: EdgeInsets::all(8.0),
+
+                    child: <DecoratedBox> {
+                        decoration: BoxDecoration::default()
+                            .color(BLUE)
+                            .border(Border::all(BorderSide::new(RED, 4.0)))
+                            .border_radius(BorderRadius::all(12.0)),
+                    }
+                }
+            }
+        },
+        config(64.0, 48.0),
+        Golden::new("tests/goldens/decorated_box.png").tolerance(2),
+    );
+}
+
+#[test]
+fn decorated_box_border_sides() {
+    assert_golden(
+        build! {
+            <ColoredBox> {
+                color: WHITE,
+
+                child: <Padding> {
+                    padding: EdgeInsets::all(8.0),
+
+                    child: <DecoratedBox> {
+                        decoration: BoxDecoration::default().color(WHITE).border(Border {
+                            top: BorderSide::new(RED, 2.0),
+                            right: BorderSide::new(GREEN, 4.0),
+                            bottom: BorderSide::new(BLUE, 6.0),
+                            left: BorderSide::new(RED, 8.0),
+                        }),
+                    }
+                }
+            }
+        },
+        config(64.0, 48.0),
+        Golden::new("tests/goldens/decorated_box_border_sides.png").tolerance(2),
+    );
+}
+
+#[test]
+fn decorated_box_rounded_border_sides() {
+    assert_golden(
+        build! {
+            <ColoredBox> {
+                color: WHITE,
+
+                child: <Padding> {
+                    padding: EdgeInsets::all(8.0),
+
+                    child: <DecoratedBox> {
+                        decoration: BoxDecoration::default()
+                            .color(GREEN)
+                            .border(Border {
+                                top: BorderSide::new(BLUE, 2.0),
+                                right: BorderSide::new(BLUE, 4.0),
+                                bottom: BorderSide::new(BLUE, 6.0),
+                                left: BorderSide::new(BLUE, 8.0),
+                            })
+                            .border_radius(BorderRadius::all(12.0)),
+                    }
+                }
+            }
+        },
+        config(64.0, 48.0),
+        Golden::new("tests/goldens/decorated_box_rounded_border_sides.png").tolerance(2),
+    );
+}
+
+#[test]
+fn row() {
+    assert_golden(
+        build! {
+            <ColoredBox> {
+                color: WHITE,
+
+                child: <Row> {
+                    main_axis_alignment: MainAxisAlignment::SpaceBetween,
+                    cross_axis_alignment: CrossAxisAlignment::Center,
+
+                    children: vec![
+                        <ColoredBox> {
+                            color: RED,
+
+                            child: <SizedBox>::new(16.0, 16.0),
+                        },
+                        <ColoredBox> {
+                            color: GREEN,
+
+                            child: <SizedBox>::new(8.0, 32.0),
+                        },
+                        <ColoredBox> {
+                            color: BLUE,
+
+                            child: <SizedBox>::new(16.0, 8.0),
+                        },
+                    ]
+                }
+            }
+        },
+        config(64.0, 32.0),
+        "tests/goldens/row.png",
+    );
+}
+
+#[test]
+fn column_flexible() {
+    assert_golden(
+        build! {
+            <ColoredBox> {
+                color: WHITE,
+
+                child: <Column> {
+                    cross_axis_alignment: CrossAxisAlignment::Stretch,
+
+                    children: [
+                        <ColoredBox> {
+                            color: RED,
+
+                            child: <SizedBox>::vertical(8.0),
+                        },
+                        <Flexible> {
+                            flex: Some(2.0),
+                            fit: Some(FlexFit::Tight),
+
+                            child: <ColoredBox> {
+                                color: GREEN,
+                            },
+                        },
+                        <Flexible> {
+                            flex: Some(1.0),
+                            fit: Some(FlexFit::Tight),
+
+                            child: <ColoredBox> {
+                                color: BLUE,
+                            },
+                        },
+                    ]
+                }
+            }
+        },
+        config(16.0, 38.0),
+        "tests/goldens/column_flexible.png",
+    );
+}
+
+#[test]
+fn rich_text() {
+    assert_golden(
+        build! {
+            <ColoredBox> {
+                color: WHITE,
+
+                child: <RichText> {
+                    style: TextStyle {
+                        font: Font::from_bytes(
+                            include_bytes!("../examples/fonts/DejaVuSans.ttf").to_vec(),
+                        ),
+
+                        size: 16.0,
+                        color: RED,
+
+                        ..TextStyle::default()
+                    },
+
+                    text: TextSpan::new("agui ").child(
+                        TextSpan::new("text").style(TextSpanStyle {
+                            color: Some(BLUE),
+
+                            ..TextSpanStyle::default()
+                        }),
+                    ),
+                }
+            }
+        },
+        config(96.0, 24.0),
+        Golden::new("tests/goldens/rich_text.png").tolerance(2),
+    );
+}
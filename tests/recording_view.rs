@@ -0,0 +1,183 @@
+use std::{cell::RefCell, rc::Rc};
+
+use agui::{
+    element::mock::view::MockViewWidget,
+    engine::rendering::mock::{RecordingView, ViewCall},
+    prelude::*,
+};
+use agui_executor::{EngineExecutor, LocalEngineExecutor};
+
+const RED: Color = Color::from_rgb((1.0, 0.0, 0.0));
+const GREEN: Color = Color::from_rgb((0.0, 1.0, 0.0));
+const BLUE: Color = Color::from_rgb((0.0, 0.0, 1.0));
+
+type ToggleHandle = Rc<RefCell<Option<Callback<()>>>>;
+
+/// A colored box that toggles between red and green when the callback handed out through
+/// `handle` is called.
+#[derive(StatefulWidget, Debug)]
+struct Toggle {
+    handle: ToggleHandle,
+}
+
+impl StatefulWidget for Toggle {
+    type State = ToggleState;
+
+    fn create_state(&self) -> Self::State {
+        ToggleState { toggled: false }
+    }
+}
+
+struct ToggleState {
+    toggled: bool,
+}
+
+impl WidgetState for ToggleState {
+    type Widget = Toggle;
+
+    fn build(&mut self, ctx: &mut StatefulBuildContext<Self>) -> Widget {
+        let on_toggle = ctx.callback(|ctx, _: ()| {
+            ctx.set_state(|state| state.toggled = !state.toggled);
+        });
+
+        ctx.widget.handle.borrow_mut().replace(on_toggle);
+
+        build! {
+            <ColoredBox> {
+                color: if self.toggled { GREEN } else { RED },
+            }
+        }
+    }
+}
+
+fn mount(view: &RecordingView, handle: &ToggleHandle) -> LocalEngineExecutor {
+    LocalEngineExecutor::with_root(MockViewWidget::new(
+        view.clone(),
+        build! {
+            <SizedBox> {
+                width: Some(100.0),
+                height: Some(50.0),
+
+                child: <Row> {
+                    main_axis_alignment: MainAxisAlignment::Start,
+                    cross_axis_alignment: CrossAxisAlignment::Start,
+
+                    children: vec![
+                        <SizedBox> {
+                            width: Some(20.0),
+                            height: Some(10.0),
+
+                            child: <Toggle> {
+                                handle: Rc::clone(handle),
+                            }
+                        },
+                        <SizedBox> {
+                            width: Some(30.0),
+                            height: Some(10.0),
+
+                            child: <ColoredBox> {
+                                color: BLUE,
+                            }
+                        }
+                    ]
+                }
+            }
+        },
+    ))
+    .expect("failed to build tree")
+}
+
+/// Returns the global offset, size and paint count of every leaf that has painted
+/// something.
+fn colored_boxes(view: &RecordingView) -> Vec<(Offset, Size, usize)> {
+    let scene = view.scene();
+
+    scene
+        .iter()
+        .filter(|(render_object_id, render_object)| {
+            scene.get_children(*render_object_id).is_empty()
+                && render_object
+                    .canvas
+                    .as_ref()
+                    .is_some_and(|canvas| !canvas.paints.is_empty())
+        })
+        .map(|(render_object_id, render_object)| {
+            (
+                scene.global_offset(render_object_id).unwrap(),
+                render_object.size,
+                scene.paint_count(render_object_id),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn records_the_first_frame() {
+    let view = RecordingView::new();
+    let handle = ToggleHandle::default();
+
+    let _executor = mount(&view, &handle);
+
+    assert_eq!(view.scene().sync_count(), 1);
+    assert_eq!(view.calls().last(), Some(&ViewCall::Sync));
+
+    assert!(matches!(
+        view.calls().first(),
+        Some(ViewCall::Attach {
+            parent_render_object_id: None,
+            ..
+        })
+    ));
+
+    assert_eq!(
+        colored_boxes(&view),
+        vec![
+            (Offset::new(0.0, 0.0), Size::new(20.0, 10.0), 1),
+            (Offset::new(20.0, 0.0), Size::new(30.0, 10.0), 1),
+        ]
+    );
+}
+
+#[test]
+fn state_changes_only_repaint_what_changed() {
+    let view = RecordingView::new();
+    let handle = ToggleHandle::default();
+
+    let mut executor = mount(&view, &handle);
+
+    view.take_calls();
+
+    handle.borrow().as_ref().unwrap().call(());
+
+    executor.update();
+
+    let calls = view.take_calls();
+
+    let painted = calls
+        .iter()
+        .filter(|call| matches!(call, ViewCall::Paint { .. }))
+        .count();
+
+    assert_eq!(painted, 1);
+    assert_eq!(calls.last(), Some(&ViewCall::Sync));
+
+    assert_eq!(
+        colored_boxes(&view),
+        vec![
+            (Offset::new(0.0, 0.0), Size::new(20.0, 10.0), 2),
+            (Offset::new(20.0, 0.0), Size::new(30.0, 10.0), 1),
+        ]
+    );
+}
+
+#[test]
+fn updates_without_changes_do_not_sync() {
+    let view = RecordingView::new();
+    let handle = ToggleHandle::default();
+
+    let mut executor = mount(&view, &handle);
+
+    executor.update();
+
+    assert_eq!(view.scene().sync_count(), 1);
+}
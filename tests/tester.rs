@@ -0,0 +1,750 @@
+use std::time::Duration;
+
+use agui::{
+    asset::{AssetBundle, AssetResolver},
+    gestures::details::DragUpdateDetails,
+    input::{KeyEvent, NamedKey, PointerEvent},
+    prelude::*,
+    task::{context::ContextSpawnElementTask, TaskHandle},
+    testing::{find, tester::WidgetTester},
+};
+use url::Url;
+
+fn first() -> Key {
+    Key::local("first")
+}
+
+fn second() -> Key {
+    Key::local("second")
+}
+
+fn third() -> Key {
+    Key::local("third")
+}
+
+fn config(width: f32, height: f32) -> ViewConfiguration {
+    ViewConfiguration {
+        size: Size::new(width, height),
+
+        device_pixel_ratio: 1.0,
+    }
+}
+
+fn text(text: impl Into<String>) -> Widget {
+    Text {
+        style: TextStyle {
+            font: Font::from_family("Arial"),
+
+            size: 16.0,
+            color: Color::from_rgb((0.0, 0.0, 0.0)),
+
+            h_align: HorizontalAlign::default(),
+            v_align: VerticalAlign::default(),
+
+            ..TextStyle::default()
+        },
+
+        text: text.into().into(),
+    }
+    .into_widget()
+}
+
+/// Text in a font that's always available, so that it has a baseline.
+fn sized_text(text: &'static str, size: f32) -> Text {
+    Text {
+        style: TextStyle {
+            font: Font::from_bytes(include_bytes!("../examples/fonts/DejaVuSans.ttf").to_vec()),
+
+            size,
+
+            ..TextStyle::default()
+        },
+
+        text: text.into(),
+    }
+}
+
+#[derive(StatefulWidget, Debug)]
+struct GestureCounter {}
+
+impl StatefulWidget for GestureCounter {
+    type State = GestureCounterState;
+
+    fn create_state(&self) -> Self::State {
+        GestureCounterState::default()
+    }
+}
+
+#[derive(Default)]
+struct GestureCounterState {
+    taps: usize,
+    double_taps: usize,
+
+    dragged: Offset,
+}
+
+impl WidgetState for GestureCounterState {
+    type Widget = GestureCounter;
+
+    fn build(&mut self, ctx: &mut StatefulBuildContext<Self>) -> Widget {
+        let on_tap = ctx.callback(|ctx, _: ()| {
+            ctx.set_state(|state| state.taps += 1);
+        });
+
+        let on_double_tap = ctx.callback(|ctx, _: ()| {
+            ctx.set_state(|state| state.double_taps += 1);
+        });
+
+        let on_pan_update = ctx.callback(|ctx, details: DragUpdateDetails| {
+            ctx.set_state(|state| state.dragged += details.delta);
+        });
+
+        build! {
+            <GestureDetector> {
+                on_tap: Some(on_tap),
+                on_double_tap: Some(on_double_tap),
+                on_pan_update: Some(on_pan_update),
+
+                child: <Column> {
+                    children: [
+                        text(format!("taps: {}", self.taps)),
+                        text(format!("double taps: {}", self.double_taps)),
+                        text(format!("dragged: {}, {}", self.dragged.x, self.dragged.y)),
+                    ]
+                }
+            }
+        }
+    }
+}
+
+#[derive(StatefulWidget, Debug)]
+struct PointerTracker {}
+
+impl StatefulWidget for PointerTracker {
+    type State = PointerTrackerState;
+
+    fn create_state(&self) -> Self::State {
+        PointerTrackerState::default()
+    }
+}
+
+#[derive(Default)]
+struct PointerTrackerState {
+    down_at: Option<Offset>,
+}
+
+impl WidgetState for PointerTrackerState {
+    type Widget = PointerTracker;
+
+    fn build(&mut self, ctx: &mut StatefulBuildContext<Self>) -> Widget {
+        let on_pointer_down = ctx.callback(|ctx, event: PointerEvent| {
+            ctx.set_state(move |state| state.down_at = Some(event.local_position));
+        });
+
+        build! {
+            <Listener> {
+                behavior: HitTestBehavior::Opaque,
+
+                on_pointer_down: Some(on_pointer_down),
+
+                child: text(match self.down_at {
+                    Some(position) => format!("down: {}, {}", position.x, position.y),
+                    None => String::from("up"),
+                })
+            }
+        }
+    }
+}
+
+#[derive(StatefulWidget, Debug)]
+struct Ticker {}
+
+impl StatefulWidget for Ticker {
+    type State = TickerState;
+
+    fn create_state(&self) -> Self::State {
+        TickerState::default()
+    }
+}
+
+#[derive(Default)]
+struct TickerState {
+    ticks: usize,
+
+    handle: Option<TaskHandle<()>>,
+}
+
+impl WidgetState for TickerState {
+    type Widget = Ticker;
+
+    fn init_state(&mut self, ctx: &mut StatefulBuildContext<Self>) {
+        let on_tick = ctx.callback(|ctx, _: ()| {
+            ctx.set_state(|state| state.ticks += 1);
+        });
+
+        self.handle = ctx
+            .spawn_task(move |ctx| async move {
+                loop {
+                    ctx.clock().sleep(Duration::from_millis(100)).await;
+
+                    on_tick.call(());
+                }
+            })
+            .ok();
+    }
+
+    fn build(&mut self, _: &mut StatefulBuildContext<Self>) -> Widget {
+        text(format!("ticks: {}", self.ticks))
+    }
+}
+
+#[derive(StatefulWidget, Debug)]
+struct TextEntry {}
+
+impl StatefulWidget for TextEntry {
+    type State = TextEntryState;
+
+    fn create_state(&self) -> Self::State {
+        TextEntryState::default()
+    }
+}
+
+#[derive(Default)]
+struct TextEntryState {
+    text: String,
+
+    has_focus: bool,
+}
+
+impl WidgetState for TextEntryState {
+    type Widget = TextEntry;
+
+    fn build(&mut self, ctx: &mut StatefulBuildContext<Self>) -> Widget {
+        let on_text = ctx.callback(|ctx, text: String| {
+            ctx.set_state(|state| state.text.push_str(&text));
+        });
+
+        let on_key = ctx.callback(|ctx, event: KeyEvent| {
+            if event.is_pressed() && event.key == NamedKey::Backspace.into() {
+                ctx.set_state(|state| {
+                    state.text.pop();
+                });
+            }
+        });
+
+        let on_focus_change = ctx.callback(|ctx, has_focus: bool| {
+            ctx.set_state(|state| state.has_focus = has_focus);
+        });
+
+        build! {
+            <Focus> {
+                on_text: Some(on_text),
+                on_key: Some(on_key),
+                on_focus_change: Some(on_focus_change),
+
+                child: <Column> {
+                    children: [
+                        text(format!("text: {}", self.text)),
+                        text(if self.has_focus { "focused" } else { "unfocused" }),
+                    ]
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn finds_widgets_and_their_geometry() {
+    let tester = WidgetTester::new(
+        build! {
+            <Column> {
+                main_axis_alignment: MainAxisAlignment::Start,
+                cross_axis_alignment: CrossAxisAlignment::Start,
+
+                children: [
+                    Widget::new_with_key(first(), SizedBox::new(40.0, 20.0)),
+                    Widget::new_with_key(second(), SizedBox::new(60.0, 30.0)),
+                    text("label"),
+                ]
+            }
+        },
+        config(200.0, 100.0),
+    );
+
+    assert_eq!(
+        tester.rect(&find::by_key(first())),
+        Rect::new(0.0, 0.0, 40.0, 20.0)
+    );
+
+    assert_eq!(
+        tester.rect(&find::by_key(second())),
+        Rect::new(0.0, 20.0, 60.0, 30.0)
+    );
+
+    assert_eq!(
+        tester.center(&find::by_key(second())),
+        Offset::new(30.0, 35.0)
+    );
+
+    assert_eq!(tester.find(&find::by_widget::<SizedBox>()).len(), 3);
+
+    assert!(tester.exists(&find::text("label")));
+    assert!(!tester.exists(&find::text("missing")));
+
+    assert_eq!(tester.widget::<Text>(&find::text("label")).text, "label");
+}
+
+#[test]
+fn finders_can_be_narrowed() {
+    let tester = WidgetTester::new(
+        build! {
+            <Column> {
+                children: [
+                    Widget::new_with_key(first(), SizedBox::new(40.0, 20.0)),
+                    Widget::new_with_key(second(), SizedBox::new(60.0, 30.0)),
+                ]
+            }
+        },
+        config(200.0, 100.0),
+    );
+
+    let boxes = tester.find(&find::by_widget::<SizedBox>());
+
+    assert_eq!(
+        tester.find(&find::by_widget::<SizedBox>().at(1)),
+        vec![boxes[1]]
+    );
+
+    assert!(tester
+        .find(&find::by_widget::<SizedBox>().at(boxes.len()))
+        .is_empty());
+}
+
+#[test]
+fn decorated_boxes_take_the_size_of_their_child() {
+    let tester = WidgetTester::new(
+        build! {
+            <Column> {
+                main_axis_alignment: MainAxisAlignment::Start,
+                cross_axis_alignment: CrossAxisAlignment::Start,
+
+                children: [
+                    Widget::new_with_key(
+                        first(),
+                        DecoratedBox {
+                            decoration: BoxDecoration::default()
+                                .color(Color::from_rgb((0.0, 0.0, 1.0)))
+                                .border(Border::all(BorderSide::new(
+                                    Color::from_rgb((1.0, 0.0, 0.0)),
+                                    4.0,
+                                ))),
+
+                            child: Some(
+                                Widget::new_with_key(second(), SizedBox::new(40.0, 20.0)),
+                            ),
+                        },
+                    ),
+                    Widget::new_with_key(third(), SizedBox::new(30.0, 10.0)),
+                ]
+            }
+        },
+        config(200.0, 100.0),
+    );
+
+    assert_eq!(
+        tester.rect(&find::by_key(first())),
+        Rect::new(0.0, 0.0, 40.0, 20.0)
+    );
+
+    // The border is drawn over the child, rather than insetting it
+    assert_eq!(
+        tester.rect(&find::by_key(second())),
+        Rect::new(0.0, 0.0, 40.0, 20.0)
+    );
+
+    assert_eq!(
+        tester.rect(&find::by_key(third())),
+        Rect::new(0.0, 20.0, 30.0, 10.0)
+    );
+}
+
+#[test]
+fn decorated_boxes_without_a_child_fill_their_constraints() {
+    let tester = WidgetTester::new(
+        build! {
+            <Column> {
+                main_axis_alignment: MainAxisAlignment::Start,
+                cross_axis_alignment: CrossAxisAlignment::Start,
+
+                children: [
+                    <SizedBox> {
+                        width: Some(30.0),
+                        height: Some(10.0),
+
+                        child: <DecoratedBox> {
+                            decoration: BoxDecoration::default()
+                                .color(Color::from_rgb((0.0, 0.0, 1.0))),
+                        }
+                    },
+                ]
+            }
+        },
+        config(200.0, 100.0),
+    );
+
+    assert_eq!(
+        tester.rect(&find::by_widget::<DecoratedBox>()),
+        Rect::new(0.0, 0.0, 30.0, 10.0)
+    );
+}
+
+#[test]
+fn taps_update_state() {
+    let mut tester = WidgetTester::new(GestureCounter {}, config(200.0, 100.0));
+
+    assert!(tester.exists(&find::text("taps: 0")));
+
+    tester.tap(&find::by_widget::<GestureDetector>());
+    tester.pump_and_settle();
+
+    assert!(tester.exists(&find::text("taps: 1")));
+    assert!(tester.exists(&find::text("double taps: 0")));
+}
+
+#[test]
+fn double_taps_use_the_tester_clock() {
+    let mut tester = WidgetTester::new(GestureCounter {}, config(200.0, 100.0));
+
+    tester.tap(&find::by_widget::<GestureDetector>());
+    tester.advance(Duration::from_millis(50));
+    tester.tap(&find::by_widget::<GestureDetector>());
+    tester.pump_and_settle();
+
+    assert!(tester.exists(&find::text("taps: 2")));
+    assert!(tester.exists(&find::text("double taps: 1")));
+
+    tester.advance(Duration::from_secs(1));
+    tester.tap(&find::by_widget::<GestureDetector>());
+    tester.advance(Duration::from_secs(1));
+    tester.tap(&find::by_widget::<GestureDetector>());
+    tester.pump_and_settle();
+
+    assert!(tester.exists(&find::text("taps: 4")));
+    assert!(tester.exists(&find::text("double taps: 1")));
+}
+
+#[test]
+fn advancing_fires_timers() {
+    let mut tester = WidgetTester::new(Ticker {}, config(200.0, 100.0));
+
+    assert!(tester.exists(&find::text("ticks: 0")));
+
+    tester.advance(Duration::from_millis(99));
+
+    assert!(tester.exists(&find::text("ticks: 0")));
+
+    tester.advance(Duration::from_millis(1));
+
+    assert!(tester.exists(&find::text("ticks: 1")));
+
+    // A timer that's rescheduled after firing fires again within the same advance
+    tester.advance(Duration::from_millis(350));
+
+    assert!(tester.exists(&find::text("ticks: 4")));
+    assert_eq!(tester.elapsed(), Duration::from_millis(450));
+}
+
+#[test]
+fn drags_report_their_movement() {
+    let mut tester = WidgetTester::new(GestureCounter {}, config(200.0, 100.0));
+
+    tester.drag(
+        &find::by_widget::<GestureDetector>(),
+        Offset::new(40.0, -10.0),
+    );
+    tester.pump_and_settle();
+
+    assert!(tester.exists(&find::text("dragged: 40, -10")));
+    assert!(tester.exists(&find::text("taps: 0")));
+}
+
+#[test]
+fn taps_outside_of_widgets_are_ignored() {
+    let mut tester = WidgetTester::new(
+        build! {
+            <Column> {
+                cross_axis_alignment: CrossAxisAlignment::Start,
+
+                children: [
+                    <SizedBox> {
+                        width: Some(50.0),
+                        height: Some(50.0),
+
+                        child: <GestureCounter> {}
+                    }
+                ]
+            }
+        },
+        config(200.0, 100.0),
+    );
+
+    tester.tap_at(Offset::new(150.0, 75.0));
+    tester.pump_and_settle();
+
+    assert!(tester.exists(&find::text("taps: 0")));
+
+    tester.tap_at(Offset::new(25.0, 25.0));
+    tester.pump_and_settle();
+
+    assert!(tester.exists(&find::text("taps: 1")));
+}
+
+#[test]
+fn enters_text_into_the_focused_widget() {
+    let mut tester = WidgetTester::new(TextEntry {}, config(200.0, 100.0));
+
+    // Nothing is focused yet, so this goes nowhere
+    tester.send_key(NamedKey::Backspace);
+    tester.pump_and_settle();
+
+    assert!(tester.exists(&find::text("unfocused")));
+
+    tester.enter_text(&find::by_widget::<Focus>(), "hello");
+    tester.pump_and_settle();
+
+    assert!(tester.exists(&find::text("focused")));
+    assert!(tester.exists(&find::text("text: hello")));
+
+    tester.send_key(NamedKey::Backspace);
+    tester.pump_and_settle();
+
+    assert!(tester.exists(&find::text("text: hell")));
+}
+
+#[test]
+fn loads_url_fonts_before_laying_out_text() {
+    AssetResolver::global().register(
+        "tester-fonts",
+        AssetBundle::new().with(
+            "DejaVuSans.ttf",
+            include_bytes!("../examples/fonts/DejaVuSans.ttf").as_slice(),
+        ),
+    );
+
+    let mut tester = WidgetTester::new(
+        build! {
+            <Column> {
+                cross_axis_alignment: CrossAxisAlignment::Start,
+
+                children: [
+                    <Text> {
+                        style: TextStyle {
+                            font: Font::from_url(
+                                Url::parse("tester-fonts://DejaVuSans.ttf").unwrap(),
+                            ),
+
+                            size: 16.0,
+
+                            ..TextStyle::default()
+                        },
+
+                        text: "label".into(),
+                    },
+                ]
+            }
+        },
+        config(200.0, 100.0),
+    );
+
+    // Layout doesn't wait for the font, so the text is empty until it has loaded
+    assert_eq!(tester.size(&find::text("label")).width, 0.0);
+
+    tester.pump_and_settle();
+
+    assert!(tester.size(&find::text("label")).width > 0.0);
+}
+
+#[test]
+fn pointer_events_reach_clipped_children_at_their_offset() {
+    let mut tester = WidgetTester::new(
+        build! {
+            <Column> {
+                cross_axis_alignment: CrossAxisAlignment::Start,
+
+                children: [
+                    <SizedBox> {
+                        width: Some(100.0),
+                        height: Some(100.0),
+
+                        child: <Clip> {
+                            shape: Shape::Circle,
+
+                            child: <Padding> {
+                                padding: EdgeInsets::new(50.0, 0.0, 0.0, 50.0),
+
+                                child: <PointerTracker> {}
+                            }
+                        }
+                    }
+                ]
+            }
+        },
+        config(200.0, 100.0),
+    );
+
+    // Inside the clip, but not over the child
+    tester.tap_at(Offset::new(25.0, 25.0));
+    tester.pump_and_settle();
+
+    assert!(tester.exists(&find::text("up")));
+
+    // Over the child, but clipped away by the circle
+    tester.tap_at(Offset::new(95.0, 95.0));
+    tester.pump_and_settle();
+
+    assert!(tester.exists(&find::text("up")));
+
+    tester.tap_at(Offset::new(70.0, 70.0));
+    tester.pump_and_settle();
+
+    assert!(tester.exists(&find::text("down: 20, 20")));
+}
+
+#[test]
+fn rows_align_text_of_mixed_sizes_by_their_baselines() {
+    let row = |sizes: [f32; 3]| {
+        WidgetTester::new(
+            build! {
+                <Column> {
+                    main_axis_alignment: MainAxisAlignment::Start,
+                    cross_axis_alignment: CrossAxisAlignment::Start,
+
+                    children: [
+                        <Row> {
+                            main_axis_alignment: MainAxisAlignment::Start,
+                            cross_axis_alignment: CrossAxisAlignment::Baseline(TextBaseline::Alphabetic),
+
+                            children: vec![
+                                Widget::new_with_key(first(), sized_text("small", sizes[0])),
+                                Widget::new_with_key(second(), sized_text("large", sizes[1])),
+                                Widget::new_with_key(third(), sized_text("other", sizes[2])),
+                            ]
+                        }
+                    ]
+                }
+            },
+            config(400.0, 200.0),
+        )
+    };
+
+    let tester = row([16.0, 32.0, 32.0]);
+
+    let small = tester.rect(&find::by_key(first()));
+    let large = tester.rect(&find::by_key(second()));
+
+    // The tallest text sits at the top, and the rest are moved down by the difference in
+    // their ascent, which grows with their size
+    assert_eq!(large.top, 0.0);
+    assert!(small.top > 0.0);
+
+    let ascent_16 = small.top;
+
+    assert_eq!(
+        tester.size(&find::by_widget::<Row>()).height,
+        large.height.max(small.top + small.height)
+    );
+
+    let tester = row([16.0, 32.0, 48.0]);
+
+    assert!((tester.rect(&find::by_key(first())).top - ascent_16 * 2.0).abs() < 0.01);
+    assert!((tester.rect(&find::by_key(second())).top - ascent_16).abs() < 0.01);
+    assert_eq!(tester.rect(&find::by_key(third())).top, 0.0);
+}
+
+#[test]
+fn rows_place_children_without_a_baseline_at_the_top() {
+    let tester = WidgetTester::new(
+        build! {
+            <Column> {
+                main_axis_alignment: MainAxisAlignment::Start,
+                cross_axis_alignment: CrossAxisAlignment::Start,
+
+                children: [
+                    <Row> {
+                        main_axis_alignment: MainAxisAlignment::Start,
+                        cross_axis_alignment: CrossAxisAlignment::Baseline(TextBaseline::Alphabetic),
+
+                        children: vec![
+                            Widget::new_with_key(first(), SizedBox::new(20.0, 80.0)),
+                            Widget::new_with_key(second(), sized_text("text", 16.0)),
+                        ]
+                    }
+                ]
+            }
+        },
+        config(400.0, 200.0),
+    );
+
+    assert_eq!(
+        tester.rect(&find::by_key(first())),
+        Rect::new(0.0, 0.0, 20.0, 80.0)
+    );
+
+    // The only baseline is the text's, so it isn't moved
+    assert_eq!(tester.rect(&find::by_key(second())).top, 0.0);
+    assert_eq!(tester.size(&find::by_widget::<Row>()).height, 80.0);
+}
+
+#[test]
+fn baselines_position_their_child_by_its_baseline() {
+    let tester = WidgetTester::new(
+        build! {
+            <Row> {
+                main_axis_alignment: MainAxisAlignment::Start,
+                cross_axis_alignment: CrossAxisAlignment::Start,
+
+                children: vec![
+                    <Baseline> {
+                        baseline: 40.0,
+
+                        child: Widget::new_with_key(first(), sized_text("small", 16.0)),
+                    },
+                    <Baseline> {
+                        baseline: 40.0,
+
+                        child: Widget::new_with_key(second(), sized_text("large", 32.0)),
+                    },
+                    <Baseline> {
+                        baseline: 40.0,
+
+                        child: Widget::new_with_key(third(), SizedBox::new(20.0, 30.0)),
+                    }
+                ]
+            }
+        },
+        config(400.0, 200.0),
+    );
+
+    let small = tester.rect(&find::by_key(first()));
+    let large = tester.rect(&find::by_key(second()));
+
+    // Each is 40 pixels below the top, less an ascent that doubles with the font size
+    assert!(large.top > 0.0 && small.top > large.top);
+    assert!((small.top * 2.0 - large.top - 40.0).abs() < 0.01);
+
+    assert_eq!(
+        tester.size(&find::by_widget::<Baseline>().at(0)).height,
+        small.top + small.height
+    );
+
+    // Without a baseline, the child's bottom is placed on it
+    assert_eq!(
+        tester.rect(&find::by_key(third())),
+        Rect::new(small.width + large.width, 10.0, 20.0, 30.0)
+    );
+
+    assert_eq!(
+        tester.size(&find::by_widget::<Baseline>().at(2)).height,
+        40.0
+    );
+}
@@ -0,0 +1,452 @@
+use std::time::Duration;
+
+use agui::{
+    clipboard::{Clipboard, LocalClipboard},
+    input::{ImeEvent, InputEvent, KeyEvent, Modifiers, NamedKey, PointerEvent},
+    prelude::*,
+    testing::{find, tester::WidgetTester},
+    widgets::{primitives::text::DEFAULT_SELECTION_COLOR, TextField},
+};
+
+fn field_key() -> Key {
+    Key::local("field")
+}
+
+fn config(width: f32, height: f32) -> ViewConfiguration {
+    ViewConfiguration {
+        size: Size::new(width, height),
+
+        device_pixel_ratio: 1.0,
+    }
+}
+
+fn style() -> TextStyle {
+    TextStyle {
+        font: Font::from_bytes(include_bytes!("../examples/fonts/DejaVuSans.ttf").to_vec()),
+
+        size: 16.0,
+
+        ..TextStyle::default()
+    }
+}
+
+/// A text field that shows the value it reports beneath it.
+#[derive(StatefulWidget, Debug)]
+struct Field {
+    value: &'static str,
+
+    style: TextStyle,
+
+    obscured: bool,
+
+    multiline: bool,
+    max_lines: Option<usize>,
+
+    clipboard: Clipboard,
+}
+
+impl Field {
+    fn new(value: &'static str) -> Self {
+        Self {
+            value,
+
+            style: style(),
+
+            obscured: false,
+
+            multiline: false,
+            max_lines: None,
+
+            clipboard: Clipboard::new(LocalClipboard::default()),
+        }
+    }
+}
+
+impl StatefulWidget for Field {
+    type State = FieldState;
+
+    fn create_state(&self) -> Self::State {
+        FieldState {
+            value: self.value.to_owned(),
+        }
+    }
+}
+
+struct FieldState {
+    value: String,
+}
+
+impl WidgetState for FieldState {
+    type Widget = Field;
+
+    fn build(&mut self, ctx: &mut StatefulBuildContext<Self>) -> Widget {
+        let on_change = ctx.callback(|ctx, value: String| {
+            ctx.set_state(move |state| state.value = value);
+        });
+
+        let field = TextField {
+            value: self.value.clone(),
+
+            style: ctx.widget.style.clone(),
+
+            placeholder: "".into(),
+            placeholder_style: None,
+
+            obscured: ctx.widget.obscured,
+
+            multiline: ctx.widget.multiline,
+            max_lines: ctx.widget.max_lines,
+
+            cursor_color: None,
+            selection_color: DEFAULT_SELECTION_COLOR,
+
+            clipboard: Some(ctx.widget.clipboard.clone()),
+
+            on_change: Some(on_change),
+            on_submit: None,
+            on_focus_change: None,
+        };
+
+        build! {
+            <Column> {
+                main_axis_alignment: MainAxisAlignment::Start,
+                cross_axis_alignment: CrossAxisAlignment::Stretch,
+
+                children: [
+                    Widget::new_with_key(field_key(), field),
+                    <Text> {
+                        style: ctx.widget.style.clone(),
+
+                        text: format!("value: {:?}", self.value).into(),
+                    },
+                ]
+            }
+        }
+    }
+}
+
+fn field_rect(tester: &WidgetTester) -> Rect {
+    tester.rect(&find::by_key(field_key()))
+}
+
+/// Taps the field at `position`, relative to its top left corner. The tester's clock is
+/// advanced first, so that taps are never taken for double clicks.
+fn tap_field_at(tester: &mut WidgetTester, position: Offset) {
+    let rect = field_rect(tester);
+
+    tester.advance(Duration::from_secs(1));
+    tester.tap_at(Offset::new(rect.left, rect.top) + position);
+    tester.pump_and_settle();
+}
+
+/// Focuses the field by tapping it past the end of its first line.
+fn focus_at_end(tester: &mut WidgetTester) {
+    let rect = field_rect(tester);
+
+    tap_field_at(tester, Offset::new(rect.width - 1.0, 4.0));
+}
+
+fn type_text(tester: &mut WidgetTester, text: &str) {
+    tester.dispatch_event(InputEvent::Text(text.to_owned()));
+    tester.pump_and_settle();
+}
+
+fn press(tester: &mut WidgetTester, key: impl Into<agui::input::Key>) {
+    tester.send_key(key);
+    tester.pump_and_settle();
+}
+
+fn press_with(tester: &mut WidgetTester, modifiers: Modifiers, key: impl Into<agui::input::Key>) {
+    let key = key.into();
+
+    tester.dispatch_event(KeyEvent::pressed(key).with_modifiers(modifiers));
+    tester.dispatch_event(KeyEvent::released(key).with_modifiers(modifiers));
+    tester.pump_and_settle();
+}
+
+fn ime(tester: &mut WidgetTester, event: ImeEvent) {
+    tester.dispatch_event(InputEvent::Ime(event));
+    tester.pump_and_settle();
+}
+
+#[track_caller]
+fn assert_value(tester: &WidgetTester, value: &str) {
+    let expected = format!("value: {:?}", value);
+
+    assert!(
+        tester.exists(&find::text(expected.as_str())),
+        "expected the field's value to be {:?}",
+        value
+    );
+}
+
+#[test]
+fn moves_the_caret_with_the_keyboard() {
+    let mut tester = WidgetTester::new(Field::new("hello world"), config(200.0, 100.0));
+
+    focus_at_end(&mut tester);
+
+    press(&mut tester, NamedKey::ArrowLeft);
+    type_text(&mut tester, "X");
+
+    assert_value(&tester, "hello worlXd");
+
+    // Words are skipped with `Ctrl`
+    press_with(&mut tester, Modifiers::CONTROL, NamedKey::ArrowLeft);
+    type_text(&mut tester, "Y");
+
+    assert_value(&tester, "hello YworlXd");
+
+    press(&mut tester, NamedKey::Home);
+    type_text(&mut tester, "Z");
+
+    assert_value(&tester, "Zhello YworlXd");
+
+    press(&mut tester, NamedKey::End);
+    type_text(&mut tester, "!");
+
+    assert_value(&tester, "Zhello YworlXd!");
+
+    // Holding `Shift` extends the selection, which typing replaces
+    press_with(&mut tester, Modifiers::SHIFT, NamedKey::Home);
+    type_text(&mut tester, "new");
+
+    assert_value(&tester, "new");
+}
+
+#[test]
+fn selects_text_by_dragging() {
+    let field = Field::new("hello world");
+    let clipboard = field.clipboard.clone();
+
+    let mut tester = WidgetTester::new(field, config(200.0, 100.0));
+
+    let rect = field_rect(&tester);
+
+    // Dragging backwards from past the end of the text to its start selects all of it
+    tester.drag_from(
+        Offset::new(rect.right() - 1.0, rect.top + 4.0),
+        Offset::new(-(rect.width - 2.0), 0.0),
+    );
+    tester.pump_and_settle();
+
+    press_with(&mut tester, Modifiers::CONTROL, 'c');
+
+    assert_eq!(clipboard.read_text().as_deref(), Some("hello world"));
+
+    // The selection collapses to its start, where the drag ended
+    press(&mut tester, NamedKey::ArrowLeft);
+    type_text(&mut tester, "X");
+
+    assert_value(&tester, "Xhello world");
+
+    // Double clicking selects the word under the pointer, so the text is selected a word
+    // at a time while dragging from it
+    let start = Offset::new(rect.left + 4.0, rect.top + 4.0);
+
+    tester.advance(Duration::from_secs(1));
+    tester.tap_at(start);
+    tester.drag_from(start, Offset::new(rect.width - 8.0, 0.0));
+    tester.pump_and_settle();
+
+    type_text(&mut tester, "bye");
+
+    assert_value(&tester, "bye");
+}
+
+#[test]
+fn composes_text_with_an_input_method() {
+    let mut tester = WidgetTester::new(Field::new("a"), config(200.0, 100.0));
+
+    focus_at_end(&mut tester);
+
+    ime(&mut tester, ImeEvent::Enabled);
+    ime(
+        &mut tester,
+        ImeEvent::Preedit {
+            text: String::from("ni"),
+            cursor: Some(2..2),
+        },
+    );
+
+    // Text being composed is part of the value until it's replaced
+    assert_value(&tester, "ani");
+
+    ime(
+        &mut tester,
+        ImeEvent::Preedit {
+            text: String::from("nih"),
+            cursor: Some(3..3),
+        },
+    );
+
+    assert_value(&tester, "anih");
+
+    ime(&mut tester, ImeEvent::Commit(String::from("你")));
+
+    assert_value(&tester, "a你");
+
+    // Typing continues after the committed text
+    type_text(&mut tester, "b");
+
+    assert_value(&tester, "a你b");
+
+    // Disabling the input method discards anything that wasn't committed
+    ime(
+        &mut tester,
+        ImeEvent::Preedit {
+            text: String::from("hao"),
+            cursor: None,
+        },
+    );
+
+    assert_value(&tester, "a你bhao");
+
+    ime(&mut tester, ImeEvent::Disabled);
+
+    assert_value(&tester, "a你b");
+}
+
+#[test]
+fn cuts_copies_and_pastes_through_the_clipboard() {
+    let field = Field::new("hello world");
+    let clipboard = field.clipboard.clone();
+
+    let mut tester = WidgetTester::new(field, config(200.0, 100.0));
+
+    focus_at_end(&mut tester);
+
+    press_with(
+        &mut tester,
+        Modifiers::CONTROL | Modifiers::SHIFT,
+        NamedKey::ArrowLeft,
+    );
+    press_with(&mut tester, Modifiers::CONTROL, 'c');
+
+    assert_eq!(clipboard.read_text().as_deref(), Some("world"));
+    assert_value(&tester, "hello world");
+
+    press_with(&mut tester, Modifiers::CONTROL, 'a');
+    press_with(&mut tester, Modifiers::CONTROL, 'x');
+
+    assert_eq!(clipboard.read_text().as_deref(), Some("hello world"));
+    assert_value(&tester, "");
+
+    // Line breaks can't be pasted into a single line field
+    clipboard.write_text("one\ntwo");
+
+    press_with(&mut tester, Modifiers::CONTROL, 'v');
+
+    assert_value(&tester, "one two");
+
+    press_with(&mut tester, Modifiers::CONTROL, 'z');
+
+    assert_value(&tester, "");
+}
+
+#[test]
+fn obscured_text_is_hidden_from_the_clipboard_and_navigated_as_one_word() {
+    let field = Field {
+        obscured: true,
+
+        ..Field::new("secret words")
+    };
+
+    let clipboard = field.clipboard.clone();
+
+    let mut tester = WidgetTester::new(field, config(200.0, 100.0));
+
+    focus_at_end(&mut tester);
+
+    press_with(&mut tester, Modifiers::CONTROL, 'a');
+    press_with(&mut tester, Modifiers::CONTROL, 'c');
+    press_with(&mut tester, Modifiers::CONTROL, 'x');
+
+    assert_eq!(clipboard.read_text(), None);
+    assert_value(&tester, "secret words");
+
+    // Deleting a word only deletes a character
+    press(&mut tester, NamedKey::End);
+    press_with(&mut tester, Modifiers::CONTROL, NamedKey::Backspace);
+
+    assert_value(&tester, "secret word");
+
+    // Moving by a word moves to the start of the text
+    press_with(&mut tester, Modifiers::CONTROL, NamedKey::ArrowLeft);
+    type_text(&mut tester, "X");
+
+    assert_value(&tester, "Xsecret word");
+
+    // Double clicking selects everything
+    let rect = field_rect(&tester);
+    let position = Offset::new(rect.left + 4.0, rect.top + 4.0);
+
+    tester.advance(Duration::from_secs(1));
+    tester.tap_at(position);
+    tester.tap_at(position);
+    tester.pump_and_settle();
+
+    type_text(&mut tester, "new");
+
+    assert_value(&tester, "new");
+}
+
+#[test]
+fn multiline_fields_scroll_beyond_their_max_lines() {
+    let field = Field {
+        multiline: true,
+        max_lines: Some(2),
+
+        ..Field::new("one\ntwo\nthree\nfour")
+    };
+
+    let mut tester = WidgetTester::new(field, config(200.0, 200.0));
+
+    let line_height = field_rect(&tester).height / 2.0;
+
+    let single_line = {
+        let tester = WidgetTester::new(Field::new("one"), config(200.0, 200.0));
+
+        field_rect(&tester).height
+    };
+
+    assert_eq!(
+        line_height, single_line,
+        "should have grown to fit two lines"
+    );
+
+    // Scrolling reveals the lines beneath, so tapping the first visible line places the
+    // caret on the third line of text
+    let rect = field_rect(&tester);
+
+    tester.dispatch_event(PointerEvent::scroll(
+        Offset::new(rect.left + 4.0, rect.top + 4.0),
+        Offset::new(0.0, 1000.0),
+    ));
+    tester.pump_and_settle();
+
+    tap_field_at(&mut tester, Offset::new(1.0, line_height / 2.0));
+    type_text(&mut tester, "X");
+
+    assert_value(&tester, "one\ntwo\nXthree\nfour");
+
+    // Moving the caret to the start scrolls it back into view
+    press_with(&mut tester, Modifiers::CONTROL, NamedKey::Home);
+
+    tap_field_at(&mut tester, Offset::new(1.0, line_height * 1.5));
+    type_text(&mut tester, "Y");
+
+    assert_value(&tester, "one\nYtwo\nXthree\nfour");
+
+    // Enter inserts a line break, and the field scrolls to keep the caret in view
+    press_with(&mut tester, Modifiers::CONTROL, NamedKey::End);
+    press(&mut tester, NamedKey::Enter);
+    type_text(&mut tester, "five");
+
+    assert_value(&tester, "one\nYtwo\nXthree\nfour\nfive");
+
+    tap_field_at(&mut tester, Offset::new(1.0, line_height / 2.0));
+    type_text(&mut tester, "Z");
+
+    assert_value(&tester, "one\nYtwo\nXthree\nZfour\nfive");
+}